@@ -15,6 +15,7 @@ pub use self::address::{
     NetworkDestination, ReceivedDestination, RequestDestination, Source,
 };
 pub use self::alias::Alias;
+pub use self::block::Block;
 pub use self::confirm::{ConfirmStatus, DataConfirm};
 pub use self::indication::{DataIndication, IndicationMetadata, IndicationStatus};
 pub use self::request::DataRequest;
@@ -24,6 +25,7 @@ pub use self::tx_options::TxOptions;
 
 mod address;
 mod alias;
+mod block;
 mod confirm;
 mod indication;
 mod request;
@@ -0,0 +1,85 @@
+use crate::Fragmentation;
+
+const FIRST_BLOCK_INDEX: u8 = 0;
+
+/// Position of an ASDU block within a fragmented transmission.
+///
+/// Fragmented transmissions split an oversized ASDU into consecutive blocks that share one APS
+/// counter. The first block announces the total block count in its extended header, while
+/// follow-up blocks carry their own index.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Block {
+    index: u8,
+    count: u8,
+}
+
+impl Block {
+    /// Create the block at `index` of a transmission with `count` blocks.
+    ///
+    /// Returns `None` unless `index` lies within the transmission.
+    #[must_use]
+    pub const fn new(index: u8, count: u8) -> Option<Self> {
+        if index < count {
+            Some(Self { index, count })
+        } else {
+            None
+        }
+    }
+
+    /// Return the zero-based block index.
+    #[must_use]
+    pub const fn index(self) -> u8 {
+        self.index
+    }
+
+    /// Return the total number of blocks in the transmission.
+    #[must_use]
+    pub const fn count(self) -> u8 {
+        self.count
+    }
+
+    /// Return whether this is the first block of the transmission.
+    #[must_use]
+    pub const fn is_first(self) -> bool {
+        self.index == FIRST_BLOCK_INDEX
+    }
+
+    /// Return the fragmentation field carried in the block's APS extended header.
+    #[must_use]
+    pub const fn fragmentation(self) -> Fragmentation {
+        if self.is_first() {
+            Fragmentation::First { blocks: self.count }
+        } else {
+            Fragmentation::Followup { index: self.index }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Block;
+    use crate::Fragmentation;
+
+    const BLOCK_COUNT: u8 = 3;
+
+    #[test]
+    fn first_block_announces_the_block_count() {
+        let block = Block::new(0, BLOCK_COUNT).expect("first block lies within the transmission");
+
+        assert!(block.is_first());
+        assert_eq!(
+            block.fragmentation(),
+            Fragmentation::First {
+                blocks: BLOCK_COUNT
+            }
+        );
+    }
+
+    #[test]
+    fn followup_block_carries_its_index() {
+        let block = Block::new(2, BLOCK_COUNT).expect("last block lies within the transmission");
+
+        assert_eq!(block.fragmentation(), Fragmentation::Followup { index: 2 });
+        assert!(Block::new(BLOCK_COUNT, BLOCK_COUNT).is_none());
+    }
+}
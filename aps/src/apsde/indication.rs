@@ -28,6 +28,7 @@ pub struct IndicationMetadata<T, K = ()> {
     status: IndicationStatus,
     security: Security<K>,
     link_quality: u8,
+    rssi: Option<i8>,
     rx_time: T,
 }
 
@@ -83,10 +84,21 @@ impl<T, K> IndicationMetadata<T, K> {
             status,
             security,
             link_quality,
+            rssi: None,
             rx_time,
         }
     }
 
+    /// Attach the received signal strength of the last hop in dBm.
+    ///
+    /// The RSSI is not part of the APSDE primitive. Implementations that measure it report it in
+    /// addition to the link-quality indication.
+    #[must_use]
+    pub const fn with_rssi(mut self, rssi: i8) -> Self {
+        self.rssi = Some(rssi);
+        self
+    }
+
     /// Return the received destination.
     #[must_use]
     pub const fn destination(&self) -> ReceivedDestination {
@@ -149,6 +161,13 @@ impl<T, K> IndicationMetadata<T, K> {
         self.link_quality
     }
 
+    /// Return the received signal strength of the last hop in dBm, if the implementation measured
+    /// it.
+    #[must_use]
+    pub const fn rssi(&self) -> Option<i8> {
+        self.rssi
+    }
+
     /// Return the implementation-specific reception timestamp.
     #[must_use]
     pub const fn rx_time(&self) -> &T {
@@ -170,6 +189,7 @@ impl<T, K> IndicationMetadata<T, K> {
             status: self.status,
             security: self.security.map_key_pair(map_key_pair),
             link_quality: self.link_quality,
+            rssi: self.rssi,
             rx_time: map_time(self.rx_time),
         }
     }
@@ -252,6 +272,7 @@ mod tests {
     const GROUP_ID: u16 = 0x1234;
     const KEY_INDEX: u8 = 2;
     const LINK_QUALITY: u8 = u8::MAX;
+    const RSSI: i8 = -60;
     const RX_TIME: u64 = 42;
     const SOURCE_ADDRESS: u16 = 0x4321;
 
@@ -276,7 +297,8 @@ mod tests {
             },
             LINK_QUALITY,
             RX_TIME,
-        );
+        )
+        .with_rssi(RSSI);
         let indication = DataIndication::new(metadata, ASDU);
 
         assert_eq!(indication.asdu_length(), ASDU.len());
@@ -294,6 +316,7 @@ mod tests {
 
         let normalized = indication.map_context(drop, drop);
         assert_eq!(normalized.metadata().rx_time(), &());
+        assert_eq!(normalized.metadata().rssi(), Some(RSSI));
         assert!(matches!(
             normalized.metadata().security(),
             Security::LinkKey {
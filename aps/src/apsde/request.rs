@@ -1,6 +1,6 @@
 use zb_core::{Cluster, Profile};
 
-use super::{Alias, IndividualEndpoint, RequestDestination, TxOptions};
+use super::{Alias, Block, IndividualEndpoint, RequestDestination, TxOptions};
 
 const DEFAULT_RADIUS_COUNTER: u8 = 0;
 
@@ -19,6 +19,7 @@ pub struct DataRequest<T> {
     tx_options: TxOptions,
     alias: Alias,
     radius_counter: u8,
+    block: Option<Block>,
 }
 
 impl<T> DataRequest<T> {
@@ -40,6 +41,7 @@ impl<T> DataRequest<T> {
             tx_options: TxOptions::empty(),
             alias: Alias::None,
             radius_counter: DEFAULT_RADIUS_COUNTER,
+            block: None,
         }
     }
 
@@ -66,6 +68,15 @@ impl<T> DataRequest<T> {
         self
     }
 
+    /// Mark the ASDU as one block of a fragmented transmission.
+    ///
+    /// Backends that fragment on the host set the APS extended header from the block.
+    #[must_use]
+    pub const fn with_block(mut self, block: Block) -> Self {
+        self.block = Some(block);
+        self
+    }
+
     /// Return the requested destination.
     #[must_use]
     pub const fn destination(&self) -> RequestDestination {
@@ -137,6 +148,7 @@ impl<T> DataRequest<T> {
             tx_options: self.tx_options,
             alias: self.alias,
             radius_counter: self.radius_counter,
+            block: self.block,
         }
     }
 
@@ -157,6 +169,13 @@ impl<T> DataRequest<T> {
     pub const fn radius_counter(&self) -> u8 {
         self.radius_counter
     }
+
+    /// Return the fragment block carried by the request, if it is part of a fragmented
+    /// transmission.
+    #[must_use]
+    pub const fn block(&self) -> Option<Block> {
+        self.block
+    }
 }
 
 impl<T> DataRequest<T>
@@ -22,3 +22,4 @@ pub use self::frame::{
 pub mod apsde;
 mod broadcast;
 mod frame;
+pub mod security;
@@ -0,0 +1,175 @@
+use le_stream::{FromLeStream, ToLeStream};
+use zb_core::IeeeAddress;
+
+use super::{Control, KeyIdentifier, Level};
+
+/// Auxiliary security header of a secured APS frame.
+///
+/// The header follows the APS header of every frame whose security flag is set. It carries the
+/// security control field, the sender's outgoing frame counter, and, depending on the control
+/// field, the sender's IEEE address and the active network key sequence number.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ToLeStream)]
+pub struct AuxiliaryHeader {
+    control: Control,
+    frame_counter: u32,
+    source_address: Option<IeeeAddress>,
+    key_sequence_number: Option<u8>,
+}
+
+impl AuxiliaryHeader {
+    /// Create an auxiliary header without source address or key sequence number.
+    #[must_use]
+    pub const fn new(key_identifier: KeyIdentifier, frame_counter: u32) -> Self {
+        let mut control = Control::empty();
+        control.set_key_identifier(key_identifier);
+
+        Self {
+            control,
+            frame_counter,
+            source_address: None,
+            key_sequence_number: None,
+        }
+    }
+
+    /// Carry the sender's IEEE address and mark the nonce as extended.
+    #[must_use]
+    pub fn with_source_address(mut self, source_address: IeeeAddress) -> Self {
+        self.control.insert(Control::EXTENDED_NONCE);
+        self.source_address.replace(source_address);
+        self
+    }
+
+    /// Carry the sequence number of the network key that secures the frame.
+    ///
+    /// Only headers with [`KeyIdentifier::Network`] carry a sequence number; for other key
+    /// identifiers, it is ignored.
+    #[must_use]
+    pub fn with_key_sequence_number(mut self, key_sequence_number: u8) -> Self {
+        if self.key_identifier() == KeyIdentifier::Network {
+            self.key_sequence_number = Some(key_sequence_number);
+        }
+
+        self
+    }
+
+    /// Return the security control field.
+    #[must_use]
+    pub const fn control(&self) -> Control {
+        self.control
+    }
+
+    /// Return the key identifier.
+    #[must_use]
+    pub fn key_identifier(&self) -> KeyIdentifier {
+        self.control.key_identifier()
+    }
+
+    /// Return the sender's outgoing frame counter.
+    #[must_use]
+    pub const fn frame_counter(&self) -> u32 {
+        self.frame_counter
+    }
+
+    /// Return the sender's IEEE address, if the nonce is extended.
+    #[must_use]
+    pub const fn source_address(&self) -> Option<IeeeAddress> {
+        self.source_address
+    }
+
+    /// Return the network key sequence number, if present.
+    #[must_use]
+    pub const fn key_sequence_number(&self) -> Option<u8> {
+        self.key_sequence_number
+    }
+
+    /// Return a copy of this header with the given security level.
+    pub(crate) const fn with_level(mut self, level: Level) -> Self {
+        self.control.set_level(level);
+        self
+    }
+}
+
+impl FromLeStream for AuxiliaryHeader {
+    fn from_le_stream<T>(mut bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        let control = Control::from_le_stream(&mut bytes)?;
+        let frame_counter = u32::from_le_stream(&mut bytes)?;
+        let source_address = if control.contains(Control::EXTENDED_NONCE) {
+            Some(IeeeAddress::from_le_stream(&mut bytes)?)
+        } else {
+            None
+        };
+        let key_sequence_number = if control.key_identifier() == KeyIdentifier::Network {
+            Some(u8::from_le_stream(&mut bytes)?)
+        } else {
+            None
+        };
+
+        Some(Self {
+            control,
+            frame_counter,
+            source_address,
+            key_sequence_number,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use le_stream::{FromLeStream, ToLeStream};
+    use zb_core::IeeeAddress;
+
+    use super::{AuxiliaryHeader, KeyIdentifier};
+
+    const FRAME_COUNTER: u32 = 0x0403_0201;
+    const SOURCE: IeeeAddress = IeeeAddress::new(1, 2, 3, 4, 5, 6, 7, 8);
+
+    #[test]
+    fn serializes_optional_fields_announced_by_the_control_field() {
+        let header = AuxiliaryHeader::new(KeyIdentifier::Network, FRAME_COUNTER)
+            .with_source_address(SOURCE)
+            .with_key_sequence_number(7);
+        let bytes: Vec<_> = header.to_le_stream().collect();
+
+        assert_eq!(
+            bytes,
+            [0x28, 0x01, 0x02, 0x03, 0x04, 8, 7, 6, 5, 4, 3, 2, 1, 7]
+        );
+        assert_eq!(
+            AuxiliaryHeader::from_le_stream(bytes.into_iter()),
+            Some(header)
+        );
+    }
+
+    #[test]
+    fn ignores_key_sequence_numbers_of_other_keys() {
+        let header = AuxiliaryHeader::new(KeyIdentifier::KeyTransport, FRAME_COUNTER)
+            .with_source_address(SOURCE)
+            .with_key_sequence_number(7);
+        let bytes: Vec<_> = header.to_le_stream().collect();
+
+        assert_eq!(header.key_sequence_number(), None);
+        assert_eq!(
+            bytes,
+            [0x30, 0x01, 0x02, 0x03, 0x04, 8, 7, 6, 5, 4, 3, 2, 1]
+        );
+        assert_eq!(
+            AuxiliaryHeader::from_le_stream(bytes.into_iter()),
+            Some(header)
+        );
+    }
+
+    #[test]
+    fn omits_the_source_address_without_an_extended_nonce() {
+        let header = AuxiliaryHeader::new(KeyIdentifier::Data, FRAME_COUNTER);
+        let bytes: Vec<_> = header.to_le_stream().collect();
+
+        assert_eq!(bytes, [0x00, 0x01, 0x02, 0x03, 0x04]);
+        assert_eq!(
+            AuxiliaryHeader::from_le_stream(bytes.into_iter()),
+            Some(header)
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! AES-CCM* mode as used by Zigbee frame security.
+
+use aes::Aes128;
+use aes::cipher::consts::U16;
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockEncrypt, KeyInit};
+use zb_core::security::Key;
+
+use super::{Error, Level};
+
+const BLOCK_SIZE: usize = 16;
+const NONCE_SIZE: usize = 13;
+const LENGTH_FIELD_SIZE: usize = 2;
+const ADATA_FLAG: u8 = 0b0100_0000;
+/// Flags field encoding of the length field size, `L - 1`.
+const LENGTH_FIELD_FLAG: u8 = 0b001;
+
+/// The CCM* nonce.
+pub type Nonce = [u8; NONCE_SIZE];
+
+/// Encrypt `message` in place and return its message integrity code.
+///
+/// Unencrypted security levels authenticate `message` as part of the additional data.
+///
+/// # Errors
+///
+/// Returns [`Error::TooLong`] if `message` or the authenticated data is 2^16 octets or longer.
+pub fn seal(
+    key: &Key,
+    nonce: &Nonce,
+    level: Level,
+    additional: &[u8],
+    message: &mut [u8],
+) -> Result<Vec<u8>, Error> {
+    let cipher = Aes128::new(GenericArray::from_slice(key.as_bytes()));
+    let mic = if level.is_encrypted() {
+        authenticate(&cipher, nonce, level, additional, message)?
+    } else {
+        authenticate(&cipher, nonce, level, &[additional, message].concat(), &[])?
+    };
+
+    Ok(transform(&cipher, nonce, level, message, mic))
+}
+
+/// Verify the message integrity code of `message` and decrypt it in place.
+///
+/// # Errors
+///
+/// Returns [`Error::TooLong`] if `message` or the authenticated data is 2^16 octets or longer,
+/// and [`Error::AuthenticationFailed`] if the MIC does not match. The message contents are
+/// unspecified in the latter case.
+pub fn open(
+    key: &Key,
+    nonce: &Nonce,
+    level: Level,
+    additional: &[u8],
+    message: &mut [u8],
+    mic: &[u8],
+) -> Result<(), Error> {
+    length(message.len())?;
+    let cipher = Aes128::new(GenericArray::from_slice(key.as_bytes()));
+    let received = transform(&cipher, nonce, level, message, mic.to_vec());
+    let expected = if level.is_encrypted() {
+        authenticate(&cipher, nonce, level, additional, message)?
+    } else {
+        authenticate(&cipher, nonce, level, &[additional, message].concat(), &[])?
+    };
+
+    // Compare without short-circuiting so the comparison time does not depend on the MIC.
+    let difference = received.iter().zip(&expected).fold(
+        u8::from(received.len() != expected.len()),
+        |difference, (a, b)| difference | (a ^ b),
+    );
+
+    if difference == 0 {
+        Ok(())
+    } else {
+        Err(Error::AuthenticationFailed)
+    }
+}
+
+/// Compute the unencrypted authentication tag with CBC-MAC.
+fn authenticate(
+    cipher: &Aes128,
+    nonce: &Nonce,
+    level: Level,
+    additional: &[u8],
+    message: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let message_length = length(message.len())?;
+    let additional_length = length(additional.len())?;
+    let mic_len = level.mic_len();
+
+    if mic_len == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut block = [0; BLOCK_SIZE];
+    block[0] = flags(mic_len, !additional.is_empty());
+    block[1..=NONCE_SIZE].copy_from_slice(nonce);
+    block[BLOCK_SIZE - LENGTH_FIELD_SIZE..].copy_from_slice(&message_length);
+    let mut state = GenericArray::from(block);
+    cipher.encrypt_block(&mut state);
+
+    if !additional.is_empty() {
+        let data = [&additional_length[..], additional].concat();
+        chain(cipher, &mut state, &data);
+    }
+
+    chain(cipher, &mut state, message);
+    Ok(state[..mic_len].to_vec())
+}
+
+/// Return the flags octet of the first CBC-MAC block.
+fn flags(mic_len: usize, has_additional: bool) -> u8 {
+    let mut flags = LENGTH_FIELD_FLAG;
+    flags |= u8::try_from((mic_len - 2) / 2).expect("MIC length fits into three bits") << 3;
+
+    if has_additional {
+        flags |= ADATA_FLAG;
+    }
+
+    flags
+}
+
+/// Feed `data`, zero-padded to whole blocks, into the CBC-MAC state.
+fn chain(cipher: &Aes128, state: &mut GenericArray<u8, U16>, data: &[u8]) {
+    for chunk in data.chunks(BLOCK_SIZE) {
+        for (state, byte) in state.iter_mut().zip(chunk) {
+            *state ^= byte;
+        }
+
+        cipher.encrypt_block(state);
+    }
+}
+
+/// Apply the CTR key stream to `message` and `mic` and return the transformed MIC.
+fn transform(
+    cipher: &Aes128,
+    nonce: &Nonce,
+    level: Level,
+    message: &mut [u8],
+    mut mic: Vec<u8>,
+) -> Vec<u8> {
+    let key_stream = |counter: u16| {
+        let mut block = [0; BLOCK_SIZE];
+        block[0] = LENGTH_FIELD_FLAG;
+        block[1..=NONCE_SIZE].copy_from_slice(nonce);
+        block[BLOCK_SIZE - LENGTH_FIELD_SIZE..].copy_from_slice(&counter.to_be_bytes());
+        let mut block = GenericArray::from(block);
+        cipher.encrypt_block(&mut block);
+        block
+    };
+
+    for (byte, key) in mic.iter_mut().zip(key_stream(0)) {
+        *byte ^= key;
+    }
+
+    if level.is_encrypted() {
+        for (counter, chunk) in (1..).zip(message.chunks_mut(BLOCK_SIZE)) {
+            for (byte, key) in chunk.iter_mut().zip(key_stream(counter)) {
+                *byte ^= key;
+            }
+        }
+    }
+
+    mic
+}
+
+/// Encode a length as the two-octet big-endian CCM* length field.
+fn length(len: usize) -> Result<[u8; LENGTH_FIELD_SIZE], Error> {
+    u16::try_from(len)
+        .map(u16::to_be_bytes)
+        .map_err(|_| Error::TooLong)
+}
+
+#[cfg(test)]
+mod tests {
+    use zb_core::security::Key;
+
+    use super::{Level, Nonce, open, seal};
+    use crate::security::Error;
+
+    const KEY: Key = Key::new([
+        0xC0, 0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7, 0xC8, 0xC9, 0xCA, 0xCB, 0xCC, 0xCD, 0xCE,
+        0xCF,
+    ]);
+    const NONCE: Nonce = [
+        0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5, 0xA6, 0xA7, 0x03, 0x02, 0x01, 0x00, 0x06,
+    ];
+    const ADDITIONAL: [u8; 8] = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07];
+    const PLAINTEXT: [u8; 23] = [
+        0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16,
+        0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D, 0x1E,
+    ];
+    const CIPHERTEXT: [u8; 23] = [
+        0x1A, 0x55, 0xA3, 0x6A, 0xBB, 0x6C, 0x61, 0x0D, 0x06, 0x6B, 0x33, 0x75, 0x64, 0x9C, 0xEF,
+        0x10, 0xD4, 0x66, 0x4E, 0xCA, 0xD8, 0x54, 0xA8,
+    ];
+    const MIC: [u8; 8] = [0x0A, 0x89, 0x5C, 0xC1, 0xD8, 0xFF, 0x94, 0x69];
+
+    #[test]
+    fn encrypts_the_specification_test_vector() {
+        // Zigbee specification, annex C.4.1: CCM* mode encryption and authentication.
+        let mut message = PLAINTEXT;
+        let mic = seal(&KEY, &NONCE, Level::EncMic64, &ADDITIONAL, &mut message)
+            .expect("specification test vector fits the length field");
+
+        assert_eq!(message, CIPHERTEXT);
+        assert_eq!(mic, MIC);
+    }
+
+    #[test]
+    fn decrypts_the_specification_test_vector() {
+        // Zigbee specification, annex C.4.2: CCM* mode decryption and authentication checking.
+        let mut message = CIPHERTEXT;
+        open(
+            &KEY,
+            &NONCE,
+            Level::EncMic64,
+            &ADDITIONAL,
+            &mut message,
+            &MIC,
+        )
+        .expect("specification test vector must authenticate");
+
+        assert_eq!(message, PLAINTEXT);
+    }
+
+    #[test]
+    fn rejects_a_modified_mic() {
+        let mut message = CIPHERTEXT;
+        let mut mic = MIC;
+        mic[0] ^= 1;
+
+        assert_eq!(
+            open(
+                &KEY,
+                &NONCE,
+                Level::EncMic64,
+                &ADDITIONAL,
+                &mut message,
+                &mic
+            ),
+            Err(Error::AuthenticationFailed)
+        );
+    }
+
+    #[test]
+    fn authenticates_without_encryption() {
+        let mut message = PLAINTEXT;
+        let mic = seal(&KEY, &NONCE, Level::Mic32, &ADDITIONAL, &mut message)
+            .expect("test message fits the length field");
+
+        assert_eq!(message, PLAINTEXT);
+        assert_eq!(mic.len(), 4);
+        assert!(open(&KEY, &NONCE, Level::Mic32, &ADDITIONAL, &mut message, &mic).is_ok());
+    }
+
+    #[test]
+    fn rejects_payloads_beyond_the_length_field() {
+        let mut message = vec![0; usize::from(u16::MAX) + 1];
+
+        assert_eq!(
+            seal(&KEY, &NONCE, Level::EncMic64, &ADDITIONAL, &mut message),
+            Err(Error::TooLong)
+        );
+        assert_eq!(
+            open(
+                &KEY,
+                &NONCE,
+                Level::EncMic64,
+                &ADDITIONAL,
+                &mut message,
+                &MIC
+            ),
+            Err(Error::TooLong)
+        );
+        assert_eq!(
+            seal(&KEY, &NONCE, Level::Mic32, &ADDITIONAL, &mut message),
+            Err(Error::TooLong)
+        );
+    }
+}
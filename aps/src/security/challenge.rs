@@ -0,0 +1,118 @@
+//! Authentication of APS frame counter challenge responses.
+
+use le_stream::ToLeStream;
+use zb_core::security::Key;
+use zb_core::types::tlv::ApsFrameCounterResponse;
+
+use super::ccm::{self, Nonce};
+use super::{Control, KeyIdentifier, Level};
+
+/// The security level of the challenge response MIC.
+const LEVEL: Level = Level::Mic64;
+
+/// Return whether the MIC of `response` proves knowledge of `key`.
+///
+/// The MIC authenticates the responder's EUI-64, the echoed challenge, and both frame counters.
+/// Its CCM* nonce consists of the responder's EUI-64, the challenge security frame counter, and a
+/// data-key security control field for the `MIC-64` level.
+#[must_use]
+pub fn verify_challenge_response(key: &Key, response: &ApsFrameCounterResponse) -> bool {
+    ccm::open(
+        key,
+        &nonce(response),
+        LEVEL,
+        &authenticated_data(response),
+        &mut [],
+        response.mic(),
+    )
+    .is_ok()
+}
+
+/// Compute the MIC of a challenge response with `key`, ignoring the response's current MIC.
+#[expect(
+    clippy::missing_panics_doc,
+    reason = "the authenticated data of a challenge response is 32 octets long"
+)]
+#[must_use]
+pub fn challenge_response_mic(
+    key: &Key,
+    response: &ApsFrameCounterResponse,
+) -> [u8; ApsFrameCounterResponse::MIC_SIZE] {
+    let mut mic = [0; ApsFrameCounterResponse::MIC_SIZE];
+    mic.copy_from_slice(
+        &ccm::seal(
+            key,
+            &nonce(response),
+            LEVEL,
+            &authenticated_data(response),
+            &mut [],
+        )
+        .expect("challenge response data is shorter than 2^16 octets"),
+    );
+    mic
+}
+
+fn nonce(response: &ApsFrameCounterResponse) -> Nonce {
+    let mut control = Control::empty();
+    control.set_level(LEVEL);
+    control.set_key_identifier(KeyIdentifier::Data);
+    let mut nonce = Nonce::default();
+
+    for (target, byte) in nonce.iter_mut().zip(
+        response
+            .responder_eui64()
+            .to_le_stream()
+            .chain(response.challenge_security_frame_counter().to_le_stream())
+            .chain(control.to_le_stream()),
+    ) {
+        *target = byte;
+    }
+
+    nonce
+}
+
+fn authenticated_data(response: &ApsFrameCounterResponse) -> Vec<u8> {
+    response
+        .responder_eui64()
+        .to_le_stream()
+        .chain(response.challenge().iter().copied())
+        .chain(response.aps_frame_counter().to_le_stream())
+        .chain(response.challenge_security_frame_counter().to_le_stream())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use zb_core::IeeeAddress;
+    use zb_core::security::Key;
+    use zb_core::types::tlv::ApsFrameCounterResponse;
+
+    use super::{challenge_response_mic, verify_challenge_response};
+
+    const KEY: Key = Key::new(*b"0123456789ABCDEF");
+    const RESPONDER: IeeeAddress = IeeeAddress::new(1, 2, 3, 4, 5, 6, 7, 8);
+    const CHALLENGE: [u8; 8] = [0xC0, 0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7];
+
+    fn response(key: &Key) -> ApsFrameCounterResponse {
+        let unsigned = ApsFrameCounterResponse::new(RESPONDER, CHALLENGE, 0x10, 0x20, [0; 8]);
+        ApsFrameCounterResponse::new(
+            RESPONDER,
+            CHALLENGE,
+            0x10,
+            0x20,
+            challenge_response_mic(key, &unsigned),
+        )
+    }
+
+    #[test]
+    fn accepts_a_response_authenticated_with_the_key() {
+        assert!(verify_challenge_response(&KEY, &response(&KEY)));
+    }
+
+    #[test]
+    fn rejects_a_response_authenticated_with_another_key() {
+        let other = Key::new([0; Key::SIZE]);
+
+        assert!(!verify_challenge_response(&KEY, &response(&other)));
+    }
+}
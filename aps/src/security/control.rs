@@ -0,0 +1,84 @@
+use bitflags::bitflags;
+use le_stream::{FromLeStream, ToLeStream};
+
+use super::{KeyIdentifier, Level};
+
+/// Security control field of the auxiliary security header.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, FromLeStream, ToLeStream)]
+#[repr(transparent)]
+pub struct Control(u8);
+
+bitflags! {
+    impl Control: u8 {
+        /// Security level mask.
+        const LEVEL = 0b0000_0111;
+
+        /// Key identifier mask.
+        const KEY_IDENTIFIER = 0b0001_1000;
+
+        /// The source address is carried in the auxiliary header.
+        const EXTENDED_NONCE = 0b0010_0000;
+
+        /// Reserved.
+        const RESERVED = 0b1100_0000;
+    }
+}
+
+impl core::fmt::Display for Control {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        bitflags::parser::to_writer(self, formatter)
+    }
+}
+
+impl core::str::FromStr for Control {
+    type Err = bitflags::parser::ParseError;
+
+    fn from_str(flags: &str) -> Result<Self, Self::Err> {
+        bitflags::parser::from_str(flags)
+    }
+}
+
+impl Control {
+    /// Return the security level.
+    #[must_use]
+    pub fn level(self) -> Level {
+        Level::try_from((self & Self::LEVEL).bits())
+            .unwrap_or_else(|_| unreachable!("Security level covers all possible values."))
+    }
+
+    /// Set the security level.
+    pub const fn set_level(&mut self, level: Level) {
+        self.0 = (self.bits() & !Self::LEVEL.bits()) | level as u8;
+    }
+
+    /// Return the key identifier.
+    #[must_use]
+    pub fn key_identifier(self) -> KeyIdentifier {
+        KeyIdentifier::try_from(
+            (self & Self::KEY_IDENTIFIER).bits() >> Self::KEY_IDENTIFIER.bits().trailing_zeros(),
+        )
+        .unwrap_or_else(|_| unreachable!("Key identifier covers all possible values."))
+    }
+
+    /// Set the key identifier.
+    pub const fn set_key_identifier(&mut self, key_identifier: KeyIdentifier) {
+        self.0 = (self.bits() & !Self::KEY_IDENTIFIER.bits())
+            | ((key_identifier as u8) << Self::KEY_IDENTIFIER.bits().trailing_zeros());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Control, KeyIdentifier, Level};
+
+    #[test]
+    fn packs_level_key_identifier_and_nonce_flag() {
+        let mut control = Control::EXTENDED_NONCE;
+        control.set_level(Level::EncMic32);
+        control.set_key_identifier(KeyIdentifier::KeyTransport);
+
+        assert_eq!(control.bits(), 0b0011_0101);
+        assert_eq!(control.level(), Level::EncMic32);
+        assert_eq!(control.key_identifier(), KeyIdentifier::KeyTransport);
+    }
+}
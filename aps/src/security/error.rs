@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+/// Errors that can occur while securing or unsecuring an APS frame.
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+pub enum Error {
+    /// The frame could not be parsed.
+    #[error("Malformed APS frame")]
+    Malformed,
+
+    /// The frame does not have the APS security flag set.
+    #[error("APS frame is not secured")]
+    Unsecured,
+
+    /// Neither the auxiliary header nor the caller supplied the nonce source address.
+    #[error("Missing source address for the security nonce")]
+    MissingSourceAddress,
+
+    /// A network-key-secured frame lacks its key sequence number.
+    #[error("Missing key sequence number for network-key security")]
+    MissingKeySequenceNumber,
+
+    /// The frame or its authenticated data is too long for the CCM* length field.
+    #[error("APS frame is too long to secure")]
+    TooLong,
+
+    /// The message integrity code did not match.
+    #[error("APS frame authentication failed")]
+    AuthenticationFailed,
+}
@@ -0,0 +1,266 @@
+//! Securing and unsecuring of APS data frames.
+
+use bytes::Bytes;
+use le_stream::{FromLeStream, ToLeStream};
+use zb_core::IeeeAddress;
+use zb_core::security::Key;
+
+use super::ccm::{self, Nonce};
+use super::{AuxiliaryHeader, Error, KeyIdentifier, Level};
+use crate::Control;
+use crate::frame::data::{Frame, Header};
+
+/// The security level of all secured APS frames.
+const LEVEL: Level = Level::EncMic32;
+
+/// Secure an APS data frame.
+///
+/// The returned bytes contain the APS header with the security flag set, the auxiliary header
+/// with its security level zeroed, the encrypted payload, and the message integrity code.
+///
+/// The CCM* nonce uses the source address of the auxiliary header if it carries one, and `source`
+/// otherwise. `key` is the link or network key. Key-transport and key-load keys are derived from
+/// it according to the key identifier of the auxiliary header.
+///
+/// # Errors
+///
+/// Returns [`Error::MissingSourceAddress`] if neither the auxiliary header nor the caller supply
+/// a source address, [`Error::MissingKeySequenceNumber`] if a network-key-secured auxiliary
+/// header lacks its key sequence number, and [`Error::TooLong`] if the frame is 2^16 octets or
+/// longer.
+pub fn encrypt(
+    frame: &Frame<Bytes>,
+    auxiliary: &AuxiliaryHeader,
+    key: &Key,
+    source: Option<IeeeAddress>,
+) -> Result<Bytes, Error> {
+    let auxiliary = auxiliary.with_level(LEVEL);
+
+    if auxiliary.key_identifier() == KeyIdentifier::Network
+        && auxiliary.key_sequence_number().is_none()
+    {
+        return Err(Error::MissingKeySequenceNumber);
+    }
+
+    let nonce = nonce(&auxiliary, source)?;
+    let mut header = frame.header();
+    header.set_security(true);
+    let header: Vec<u8> = header.to_le_stream().collect();
+    let additional = additional_data(&header, &auxiliary);
+    let mut payload = frame.payload().to_vec();
+    let mic = ccm::seal(
+        &auxiliary.key_identifier().derive(key),
+        &nonce,
+        LEVEL,
+        &additional,
+        &mut payload,
+    )?;
+
+    let mut bytes = header;
+    bytes.extend(auxiliary.with_level(Level::None).to_le_stream());
+    bytes.extend(payload);
+    bytes.extend(mic);
+    Ok(bytes.into())
+}
+
+/// Unsecure an APS data frame.
+///
+/// Returns the auxiliary header with the security level restored and the decrypted frame. The
+/// frame's header retains its security flag. `key` and `source` have the same meaning as for
+/// [`encrypt`].
+///
+/// # Errors
+///
+/// Returns an [`Error`] if the frame is malformed, not secured, too long, lacks a nonce source
+/// address, or fails authentication.
+pub fn decrypt(
+    bytes: &[u8],
+    key: &Key,
+    source: Option<IeeeAddress>,
+) -> Result<(AuxiliaryHeader, Frame<Bytes>), Error> {
+    let mut stream = bytes.iter().copied();
+    let header = Header::from_le_stream(&mut stream).ok_or(Error::Malformed)?;
+
+    if !header.control().contains(Control::SECURITY) {
+        return Err(Error::Unsecured);
+    }
+
+    let auxiliary = AuxiliaryHeader::from_le_stream(&mut stream)
+        .ok_or(Error::Malformed)?
+        .with_level(LEVEL);
+    let header_len = header.to_le_stream().count();
+    let payload_start = header_len + auxiliary.to_le_stream().count();
+    let mic_start = bytes
+        .len()
+        .checked_sub(LEVEL.mic_len())
+        .filter(|&mic_start| mic_start >= payload_start)
+        .ok_or(Error::Malformed)?;
+    let nonce = nonce(&auxiliary, source)?;
+    let additional = additional_data(&bytes[..header_len], &auxiliary);
+    let mut payload = bytes[payload_start..mic_start].to_vec();
+    ccm::open(
+        &auxiliary.key_identifier().derive(key),
+        &nonce,
+        LEVEL,
+        &additional,
+        &mut payload,
+        &bytes[mic_start..],
+    )?;
+
+    Ok((auxiliary, Frame::new(header, payload.into())))
+}
+
+/// Build the CCM* nonce from the source address, frame counter, and security control field.
+fn nonce(auxiliary: &AuxiliaryHeader, source: Option<IeeeAddress>) -> Result<Nonce, Error> {
+    let source = auxiliary
+        .source_address()
+        .or(source)
+        .ok_or(Error::MissingSourceAddress)?;
+    let mut nonce = Nonce::default();
+
+    for (target, byte) in nonce.iter_mut().zip(
+        source
+            .to_le_stream()
+            .chain(auxiliary.frame_counter().to_le_stream())
+            .chain(auxiliary.control().to_le_stream()),
+    ) {
+        *target = byte;
+    }
+
+    Ok(nonce)
+}
+
+/// Build the CCM* authentication data from the APS header and the auxiliary header.
+fn additional_data(header: &[u8], auxiliary: &AuxiliaryHeader) -> Vec<u8> {
+    header
+        .iter()
+        .copied()
+        .chain(auxiliary.to_le_stream())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use le_stream::ToLeStream;
+    use zb_core::security::Key;
+    use zb_core::{Application, Endpoint, IeeeAddress};
+
+    use super::{decrypt, encrypt};
+    use crate::frame::data::{Frame, Header};
+    use crate::security::{AuxiliaryHeader, Error, KeyIdentifier};
+    use crate::{Control, Destination};
+
+    const KEY: Key = Key::new(*b"ZigBeeAlliance09");
+    const SOURCE: IeeeAddress = IeeeAddress::new(0x00, 0x12, 0x4B, 0x00, 0x01, 0x02, 0x03, 0x04);
+    const FRAME_COUNTER: u32 = 0x0000_1234;
+    const PAYLOAD: &[u8] = &[0x01, 0x2A, 0x00, 0x00, 0x00];
+
+    #[test]
+    fn produces_the_expected_wire_format() {
+        let auxiliary = AuxiliaryHeader::new(KeyIdentifier::Data, FRAME_COUNTER);
+        let bytes =
+            encrypt(&frame(), &auxiliary, &KEY, Some(SOURCE)).expect("frame must be secured");
+
+        assert_eq!(
+            bytes.as_ref(),
+            [
+                0x04, 0x01, 0x06, 0x00, 0x04, 0x01, 0x01, 0x2A, 0x00, 0x34, 0x12, 0x00, 0x00, 0x2D,
+                0xBB, 0x72, 0x98, 0x7F, 0x33, 0xB8, 0xF1, 0x87,
+            ]
+        );
+    }
+
+    #[test]
+    fn roundtrips_link_key_secured_frames() {
+        let auxiliary = AuxiliaryHeader::new(KeyIdentifier::Data, FRAME_COUNTER);
+        let bytes =
+            encrypt(&frame(), &auxiliary, &KEY, Some(SOURCE)).expect("frame must be secured");
+        let (received, decrypted) =
+            decrypt(&bytes, &KEY, Some(SOURCE)).expect("frame must be unsecured");
+
+        assert_eq!(received.frame_counter(), FRAME_COUNTER);
+        assert_eq!(received.key_identifier(), KeyIdentifier::Data);
+        assert!(decrypted.header().control().contains(Control::SECURITY));
+        assert_eq!(decrypted.payload().as_ref(), PAYLOAD);
+    }
+
+    #[test]
+    fn uses_the_source_address_of_an_extended_nonce() {
+        let auxiliary = AuxiliaryHeader::new(KeyIdentifier::KeyTransport, FRAME_COUNTER)
+            .with_source_address(SOURCE);
+        let bytes = encrypt(&frame(), &auxiliary, &KEY, None).expect("frame must be secured");
+        let (received, decrypted) = decrypt(&bytes, &KEY, None).expect("frame must be unsecured");
+
+        assert_eq!(received.source_address(), Some(SOURCE));
+        assert_eq!(decrypted.payload().as_ref(), PAYLOAD);
+    }
+
+    #[test]
+    fn rejects_tampered_frames() {
+        let auxiliary = AuxiliaryHeader::new(KeyIdentifier::Data, FRAME_COUNTER);
+        let mut bytes = encrypt(&frame(), &auxiliary, &KEY, Some(SOURCE))
+            .expect("frame must be secured")
+            .to_vec();
+        bytes[2] ^= 0x01;
+
+        assert_eq!(
+            decrypt(&bytes, &KEY, Some(SOURCE)),
+            Err(Error::AuthenticationFailed)
+        );
+    }
+
+    #[test]
+    fn rejects_unsecured_frames() {
+        let (header, payload) = frame().into_parts();
+        let bytes: Vec<u8> = header.to_le_stream().chain(payload).collect();
+
+        assert_eq!(decrypt(&bytes, &KEY, Some(SOURCE)), Err(Error::Unsecured));
+    }
+
+    #[test]
+    fn requires_a_source_address() {
+        let auxiliary = AuxiliaryHeader::new(KeyIdentifier::Data, FRAME_COUNTER);
+
+        assert_eq!(
+            encrypt(&frame(), &auxiliary, &KEY, None),
+            Err(Error::MissingSourceAddress)
+        );
+    }
+
+    #[test]
+    fn requires_a_key_sequence_number_for_network_keys() {
+        let auxiliary = AuxiliaryHeader::new(KeyIdentifier::Network, FRAME_COUNTER);
+
+        assert_eq!(
+            encrypt(&frame(), &auxiliary, &KEY, Some(SOURCE)),
+            Err(Error::MissingKeySequenceNumber)
+        );
+    }
+
+    #[test]
+    fn rejects_payloads_beyond_the_ccm_length_field() {
+        let auxiliary = AuxiliaryHeader::new(KeyIdentifier::Data, FRAME_COUNTER);
+        let (header, _) = frame().into_parts();
+        let frame = Frame::new(header, vec![0; usize::from(u16::MAX) + 1].into());
+
+        assert_eq!(
+            encrypt(&frame, &auxiliary, &KEY, Some(SOURCE)),
+            Err(Error::TooLong)
+        );
+    }
+
+    fn frame() -> Frame<Bytes> {
+        Frame::new(
+            Header::new(
+                Destination::Unicast(Application::MIN.into()),
+                0x0006,
+                0x0104,
+                Endpoint::Application(Application::MIN),
+                0x2A,
+                None,
+            ),
+            Bytes::from_static(PAYLOAD),
+        )
+    }
+}
@@ -0,0 +1,39 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use zb_core::security::{Key, hmac_mmo};
+
+const KEY_TRANSPORT_KEY_INPUT: u8 = 0x00;
+const KEY_LOAD_KEY_INPUT: u8 = 0x02;
+
+/// Key identifier of the auxiliary security header.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, IntoPrimitive, Ord, PartialEq, PartialOrd, TryFromPrimitive,
+)]
+#[repr(u8)]
+pub enum KeyIdentifier {
+    /// The frame is secured with a data (link) key.
+    Data = 0b00,
+
+    /// The frame is secured with the network key.
+    Network = 0b01,
+
+    /// The frame is secured with the key-transport key derived from a link key.
+    KeyTransport = 0b10,
+
+    /// The frame is secured with the key-load key derived from a link key.
+    KeyLoad = 0b11,
+}
+
+impl KeyIdentifier {
+    /// Return the CCM* key used for this identifier.
+    ///
+    /// Data and network keys are used directly. Key-transport and key-load keys are derived from
+    /// the supplied link key with the AES-MMO keyed hash of a one-octet input.
+    #[must_use]
+    pub fn derive(self, key: &Key) -> Key {
+        match self {
+            Self::Data | Self::Network => *key,
+            Self::KeyTransport => hmac_mmo(key, &[KEY_TRANSPORT_KEY_INPUT]),
+            Self::KeyLoad => hmac_mmo(key, &[KEY_LOAD_KEY_INPUT]),
+        }
+    }
+}
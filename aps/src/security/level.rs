@@ -0,0 +1,57 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+/// Security level of a secured frame.
+///
+/// The level selects whether the payload is encrypted and how long the message integrity code
+/// (MIC) is.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, IntoPrimitive, Ord, PartialEq, PartialOrd, TryFromPrimitive,
+)]
+#[repr(u8)]
+pub enum Level {
+    /// No encryption and no MIC.
+    None = 0b000,
+
+    /// No encryption and a 32-bit MIC.
+    Mic32 = 0b001,
+
+    /// No encryption and a 64-bit MIC.
+    Mic64 = 0b010,
+
+    /// No encryption and a 128-bit MIC.
+    Mic128 = 0b011,
+
+    /// Encryption without a MIC.
+    Enc = 0b100,
+
+    /// Encryption and a 32-bit MIC.
+    EncMic32 = 0b101,
+
+    /// Encryption and a 64-bit MIC.
+    EncMic64 = 0b110,
+
+    /// Encryption and a 128-bit MIC.
+    EncMic128 = 0b111,
+}
+
+impl Level {
+    /// Return the length of the message integrity code in octets.
+    #[must_use]
+    pub const fn mic_len(self) -> usize {
+        match self {
+            Self::None | Self::Enc => 0,
+            Self::Mic32 | Self::EncMic32 => 4,
+            Self::Mic64 | Self::EncMic64 => 8,
+            Self::Mic128 | Self::EncMic128 => 16,
+        }
+    }
+
+    /// Return whether the payload is encrypted.
+    #[must_use]
+    pub const fn is_encrypted(self) -> bool {
+        matches!(
+            self,
+            Self::Enc | Self::EncMic32 | Self::EncMic64 | Self::EncMic128
+        )
+    }
+}
@@ -0,0 +1,20 @@
+//! Per-device activity tracking.
+//!
+//! The coordinator records every successful APSDE indication, including Keep-Alive packets, every
+//! APS data confirmation addressed to a NWK address, and every join or rejoin in an activity
+//! table keyed by the device's NWK short address. Each [`DeviceActivity`] holds the time the
+//! device was last seen, rolling link-quality and RSSI statistics, and transmission success and
+//! failure counts. Devices that leave the network are removed, and a device that joins or
+//! announces itself under a new short address loses the entry of its previous one.
+//!
+//! Query the table through [`Activity`](crate::api::Activity). A device that stays silent for
+//! longer than the silence threshold is reported once through
+//! [`Device::Unresponsive`](crate::Device::Unresponsive) until it is seen again.
+
+pub use self::device_activity::DeviceActivity;
+pub use self::rolling_statistics::RollingStatistics;
+pub(crate) use self::table::Table;
+
+mod device_activity;
+mod rolling_statistics;
+mod table;
@@ -0,0 +1,114 @@
+use std::time::{Duration, Instant};
+
+use super::RollingStatistics;
+
+/// Observed activity of a single device.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DeviceActivity {
+    last_seen: Option<Instant>,
+    link_quality: RollingStatistics<u8>,
+    rssi: RollingStatistics<i8>,
+    transmissions: u32,
+    transmission_failures: u32,
+    consecutive_failures: u32,
+    unresponsive: bool,
+}
+
+impl DeviceActivity {
+    /// Return when the device was last heard from.
+    ///
+    /// Returns `None` if the coordinator has only observed failed transmissions to the device.
+    #[must_use]
+    pub const fn last_seen(&self) -> Option<Instant> {
+        self.last_seen
+    }
+
+    /// Return how long the device has been silent.
+    #[must_use]
+    pub fn silent_for(&self) -> Option<Duration> {
+        self.last_seen.map(|last_seen| last_seen.elapsed())
+    }
+
+    /// Return the link-quality indications of the most recent frames received from the device.
+    #[must_use]
+    pub const fn link_quality(&self) -> &RollingStatistics<u8> {
+        &self.link_quality
+    }
+
+    /// Return the RSSI in dBm of the most recent frames received from the device.
+    ///
+    /// Only hardware backends that measure the RSSI contribute values.
+    #[must_use]
+    pub const fn rssi(&self) -> &RollingStatistics<i8> {
+        &self.rssi
+    }
+
+    /// Return the number of acknowledged transmissions to the device.
+    #[must_use]
+    pub const fn transmissions(&self) -> u32 {
+        self.transmissions
+    }
+
+    /// Return the number of acknowledged transmissions to the device that failed.
+    #[must_use]
+    pub const fn transmission_failures(&self) -> u32 {
+        self.transmission_failures
+    }
+
+    /// Return the number of failed transmissions since the device was last heard from.
+    #[must_use]
+    pub const fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    /// Return whether the device has exceeded the silence threshold since it was last seen.
+    #[must_use]
+    pub const fn is_unresponsive(&self) -> bool {
+        self.unresponsive
+    }
+
+    /// Record that the device was heard from.
+    pub(super) const fn seen(&mut self, now: Instant) {
+        self.last_seen = Some(now);
+        self.consecutive_failures = 0;
+        self.unresponsive = false;
+    }
+
+    /// Record a frame received from the device.
+    pub(super) fn received(&mut self, now: Instant, link_quality: u8, rssi: Option<i8>) {
+        self.seen(now);
+        self.link_quality.record(link_quality);
+
+        if let Some(rssi) = rssi {
+            self.rssi.record(rssi);
+        }
+    }
+
+    /// Record the completion of an acknowledged transmission to the device.
+    pub(super) const fn transmitted(&mut self, now: Instant, success: bool) {
+        self.transmissions = self.transmissions.saturating_add(1);
+
+        if success {
+            self.seen(now);
+        } else {
+            self.transmission_failures = self.transmission_failures.saturating_add(1);
+            self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        }
+    }
+
+    /// Mark the device as unresponsive if it has been silent for at least `threshold`.
+    ///
+    /// Returns whether the device became unresponsive.
+    pub(super) fn exceeds(&mut self, now: Instant, threshold: Duration) -> bool {
+        let silent = self
+            .last_seen
+            .is_some_and(|last_seen| now.saturating_duration_since(last_seen) >= threshold);
+
+        if silent && !self.unresponsive {
+            self.unresponsive = true;
+            return true;
+        }
+
+        false
+    }
+}
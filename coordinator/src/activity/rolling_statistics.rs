@@ -0,0 +1,92 @@
+use std::collections::VecDeque;
+
+/// Number of most recent values that the statistics cover.
+const WINDOW: usize = 16;
+
+/// Statistics over the most recently observed values of a link metric.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct RollingStatistics<T> {
+    values: VecDeque<T>,
+}
+
+impl<T> RollingStatistics<T>
+where
+    T: Copy + Ord + Into<f64>,
+{
+    /// Record a value, discarding the oldest one once the window is full.
+    pub(super) fn record(&mut self, value: T) {
+        if self.values.len() == WINDOW {
+            self.values.pop_front();
+        }
+
+        self.values.push_back(value);
+    }
+
+    /// Return the most recent value.
+    #[must_use]
+    pub fn last(&self) -> Option<T> {
+        self.values.back().copied()
+    }
+
+    /// Return the smallest value in the window.
+    #[must_use]
+    pub fn min(&self) -> Option<T> {
+        self.values.iter().min().copied()
+    }
+
+    /// Return the largest value in the window.
+    #[must_use]
+    pub fn max(&self) -> Option<T> {
+        self.values.iter().max().copied()
+    }
+
+    /// Return the arithmetic mean of the values in the window.
+    #[must_use]
+    pub fn mean(&self) -> Option<f64> {
+        let (sum, count) = self.values.iter().fold((0.0, 0.0), |(sum, count), &value| {
+            (sum + value.into(), count + 1.0)
+        });
+
+        (count > 0.0).then(|| sum / count)
+    }
+
+    /// Return the number of values in the window.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Return whether no value has been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RollingStatistics, WINDOW};
+
+    #[test]
+    fn covers_the_most_recent_values() {
+        let mut statistics = RollingStatistics::default();
+
+        for value in 0..=u8::try_from(WINDOW).expect("window fits into u8") {
+            statistics.record(value);
+        }
+
+        assert_eq!(statistics.len(), WINDOW);
+        assert_eq!(statistics.min(), Some(1));
+        assert_eq!(statistics.max(), Some(16));
+        assert_eq!(statistics.last(), Some(16));
+        assert_eq!(statistics.mean(), Some(8.5));
+    }
+
+    #[test]
+    fn is_empty_without_values() {
+        let statistics = RollingStatistics::<i8>::default();
+
+        assert!(statistics.is_empty());
+        assert_eq!(statistics.mean(), None);
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use tokio::spawn;
+use tokio::sync::Notify;
+use tokio::time::timeout;
+use zb_aps::apsde::{ConfirmStatus, Destination, IndicationMetadata, Source};
+use zb_core::{FullAddress, IeeeAddress, short_id};
+
+use super::DeviceActivity;
+use crate::event::EventSink;
+use crate::{Device, Event};
+
+/// Longest time between two silence checks.
+const MAX_CHECK_INTERVAL: Duration = Duration::from_mins(1);
+
+/// Shortest time between two silence checks.
+const MIN_CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Activity table shared by the mux, which updates it, and the coordinator, which queries it.
+#[derive(Clone, Debug)]
+pub struct Table(Arc<Shared>);
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<State>,
+    threshold_changed: Notify,
+}
+
+#[derive(Debug)]
+struct State {
+    devices: BTreeMap<short_id::Device, DeviceActivity>,
+    addresses: BTreeMap<IeeeAddress, short_id::Device>,
+    silence_threshold: Option<Duration>,
+}
+
+impl Table {
+    /// Create an empty table reporting devices that stay silent for `silence_threshold`.
+    pub fn new(silence_threshold: Option<Duration>) -> Self {
+        Self(Arc::new(Shared {
+            state: Mutex::new(State {
+                devices: BTreeMap::new(),
+                addresses: BTreeMap::new(),
+                silence_threshold,
+            }),
+            threshold_changed: Notify::new(),
+        }))
+    }
+
+    /// Start the task that reports devices exceeding the silence threshold.
+    ///
+    /// The task ends once every handle on the table has been dropped. Changing the silence
+    /// threshold wakes the task, so that a shorter threshold takes effect immediately.
+    pub fn spawn_watchdog(&self, events: EventSink) {
+        let table = Arc::downgrade(&self.0);
+
+        spawn(async move {
+            loop {
+                let Some(shared) = table.upgrade() else {
+                    return;
+                };
+                let interval = Self(shared.clone()).check_interval();
+                let changed = timeout(interval, shared.threshold_changed.notified())
+                    .await
+                    .is_ok();
+                drop(shared);
+
+                if changed {
+                    continue;
+                }
+
+                let Some(shared) = table.upgrade() else {
+                    return;
+                };
+
+                for device in Self(shared).silent_devices(Instant::now()) {
+                    events.emit(Event::Device(Device::Unresponsive(device)));
+                }
+            }
+        });
+    }
+
+    /// Return the activity of a device.
+    pub fn get(&self, device: short_id::Device) -> Option<DeviceActivity> {
+        self.lock().devices.get(&device).cloned()
+    }
+
+    /// Return the activity of every known device.
+    pub fn snapshot(&self) -> BTreeMap<short_id::Device, DeviceActivity> {
+        self.lock().devices.clone()
+    }
+
+    /// Set the silence threshold, or disable silence reports with `None`.
+    pub fn set_silence_threshold(&self, threshold: Option<Duration>) {
+        self.lock().silence_threshold = threshold;
+        self.0.threshold_changed.notify_one();
+    }
+
+    /// Record a successfully received indication.
+    pub fn received<T, K>(&self, metadata: &IndicationMetadata<T, K>) {
+        let Source::Network { address, .. } = metadata.source() else {
+            return;
+        };
+        let Some(device) = short_id::Device::new(address.as_u16()) else {
+            return;
+        };
+
+        self.lock().devices.entry(device).or_default().received(
+            Instant::now(),
+            metadata.link_quality(),
+            metadata.rssi(),
+        );
+    }
+
+    /// Record the completion of an acknowledged transmission.
+    ///
+    /// Transmissions to IEEE addresses, groups, and bindings are not attributed to a device.
+    pub fn confirmed(&self, destination: Destination, status: ConfirmStatus) {
+        let Destination::Network { address, .. } = destination else {
+            return;
+        };
+        let Some(device) = short_id::Device::new(address.as_u16()) else {
+            return;
+        };
+
+        self.lock()
+            .devices
+            .entry(device)
+            .or_default()
+            .transmitted(Instant::now(), status.is_success());
+    }
+
+    /// Record that a device joined, rejoined, or announced itself.
+    ///
+    /// If the device was known by another short address, the activity recorded under that address
+    /// is dropped.
+    pub fn joined(&self, address: FullAddress) {
+        let device = address.short_id();
+        let mut state = self.lock();
+
+        if let Some(previous) = state.addresses.insert(address.ieee_address(), device)
+            && previous != device
+        {
+            state.devices.remove(&previous);
+        }
+
+        state
+            .devices
+            .entry(device)
+            .or_default()
+            .seen(Instant::now());
+    }
+
+    /// Forget a device that left the network.
+    pub fn left(&self, address: FullAddress) {
+        let mut state = self.lock();
+
+        if let Some(device) = state.addresses.remove(&address.ieee_address()) {
+            state.devices.remove(&device);
+        }
+
+        state.devices.remove(&address.short_id());
+    }
+
+    /// Mark and return the devices that newly exceeded the silence threshold.
+    fn silent_devices(&self, now: Instant) -> Vec<short_id::Device> {
+        let mut state = self.lock();
+        let Some(threshold) = state.silence_threshold else {
+            return Vec::new();
+        };
+
+        state
+            .devices
+            .iter_mut()
+            .filter_map(|(&device, activity)| activity.exceeds(now, threshold).then_some(device))
+            .collect()
+    }
+
+    /// Return the time until the next silence check.
+    fn check_interval(&self) -> Duration {
+        self.lock()
+            .silence_threshold
+            .map_or(MAX_CHECK_INTERVAL, |threshold| {
+                (threshold / 4).clamp(MIN_CHECK_INTERVAL, MAX_CHECK_INTERVAL)
+            })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.0.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use zb_aps::apsde::{
+        ConfirmStatus, Destination, IndicationMetadata, IndicationStatus, IndividualEndpoint,
+        NetworkAddress, ReceivedDestination, Security, Source, Status,
+    };
+    use zb_core::{Application, Endpoint, FullAddress, IeeeAddress, short_id};
+
+    use super::Table;
+
+    const DEVICE: u16 = 0x1234;
+    const IEEE_ADDRESS: IeeeAddress = IeeeAddress::new(0, 0x17, 0x88, 0x01, 0, 0, 0, 1);
+    const THRESHOLD: Duration = Duration::from_mins(1);
+
+    fn device() -> short_id::Device {
+        short_id::Device::new(DEVICE).expect("test short ID is valid")
+    }
+
+    fn address() -> FullAddress {
+        FullAddress::new(IEEE_ADDRESS, device())
+    }
+
+    fn metadata(link_quality: u8, rssi: i8) -> IndicationMetadata<(), ()> {
+        let endpoint = IndividualEndpoint::new(Endpoint::Application(Application::MIN))
+            .expect("application endpoint is individual");
+
+        IndicationMetadata::new(
+            ReceivedDestination::Network {
+                address: NetworkAddress::new(0x0000).expect("coordinator address is valid"),
+                endpoint,
+            },
+            Source::Network {
+                address: NetworkAddress::new(DEVICE).expect("test address is valid"),
+                endpoint,
+            },
+            0x0104,
+            0x0006,
+            IndicationStatus::success(),
+            Security::NetworkKey,
+            link_quality,
+            (),
+        )
+        .with_rssi(rssi)
+    }
+
+    fn destination() -> Destination {
+        Destination::Network {
+            address: NetworkAddress::new(DEVICE).expect("test address is valid"),
+            endpoint: Endpoint::Application(Application::MIN),
+        }
+    }
+
+    #[test]
+    fn tracks_link_statistics_and_transmission_failures() {
+        let table = Table::new(None);
+
+        table.received(&metadata(200, -50));
+        table.received(&metadata(100, -70));
+        table.confirmed(destination(), ConfirmStatus::Aps(Status::NoAcknowledgement));
+        table.confirmed(destination(), ConfirmStatus::Aps(Status::NoAcknowledgement));
+
+        let activity = table.get(device()).expect("device must be tracked");
+        assert_eq!(activity.link_quality().mean(), Some(150.0));
+        assert_eq!(activity.rssi().min(), Some(-70));
+        assert_eq!(activity.transmissions(), 2);
+        assert_eq!(activity.transmission_failures(), 2);
+        assert_eq!(activity.consecutive_failures(), 2);
+
+        table.confirmed(destination(), ConfirmStatus::success());
+        let activity = table.get(device()).expect("device must be tracked");
+        assert_eq!(activity.transmission_failures(), 2);
+        assert_eq!(activity.consecutive_failures(), 0);
+
+        table.left(address());
+        assert!(table.get(device()).is_none());
+    }
+
+    #[test]
+    fn reports_silent_devices_once_until_seen_again() {
+        let table = Table::new(Some(THRESHOLD));
+        table.joined(address());
+        let later = Instant::now() + THRESHOLD;
+
+        assert_eq!(table.silent_devices(later), [device()]);
+        assert!(table.silent_devices(later).is_empty());
+        assert!(
+            table
+                .get(device())
+                .expect("device must be tracked")
+                .is_unresponsive()
+        );
+
+        table.received(&metadata(200, -50));
+        assert!(
+            !table
+                .get(device())
+                .expect("device must be tracked")
+                .is_unresponsive()
+        );
+    }
+
+    #[test]
+    fn drops_the_activity_of_a_previous_short_address() {
+        let table = Table::new(None);
+        let rejoined = short_id::Device::new(0x5678).expect("test short ID is valid");
+
+        table.joined(address());
+        table.received(&metadata(200, -50));
+        table.joined(FullAddress::new(IEEE_ADDRESS, rejoined));
+
+        assert!(table.get(device()).is_none());
+        assert!(table.get(rejoined).is_some());
+
+        table.left(FullAddress::new(IEEE_ADDRESS, rejoined));
+        assert!(table.snapshot().is_empty());
+    }
+}
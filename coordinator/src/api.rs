@@ -1,32 +1,49 @@
 //! Trait-based Zigbee API and protocol-specific deferred response aliases.
+//!
+//! The crate root re-exports every trait except [`Network`], whose name is taken by the
+//! [`Network`](crate::Network) event.
 
+pub use self::activity::Activity;
 pub use self::address_translation::AddressTranslation;
 pub use self::binding::Binding;
 pub use self::clusters::{
-    Attributes, CancellableOtaUpdate, ColorControl, Groups, Level, OnOff, Ota, ReadAttributeResult,
-    WriteAttributeResult,
+    AlarmLogEntry, Alarms, Attributes, CancellableOtaUpdate, ColorControl, Groups, Level,
+    ObservableOtaUpdate, OnOff, Ota, OtaClient, ReadAttributeResult, WriteAttributeResult,
 };
+pub use self::diagnostics::Diagnostics;
 pub use self::endpoints::{Endpoints, SimpleDescriptor};
 pub use self::joining::Joining;
+pub use self::key_negotiation::KeyNegotiation;
 pub use self::leaving::Leaving;
 pub use self::local_node::LocalNode;
+pub use self::network::{Formation, JoinPolicy, Network, NetworkParameters, TrustCenterPolicy};
 pub use self::node::Node;
+pub use self::retries::Retries;
 pub use self::routing::Routing;
 pub use self::scanning::{
     Channel, ChannelMask, FoundNetwork, NetworkDescriptor, ScanDuration, ScannedChannel, Scanning,
 };
+pub use self::sleepy::SleepyDevices;
+pub use self::touchlink::Touchlink;
 pub use self::zcl::{Zcl, ZclResponse};
 pub use self::zdp::{Zdp, ZdpResponse};
 
+mod activity;
 mod address_translation;
 mod binding;
 mod clusters;
+mod diagnostics;
 mod endpoints;
 mod joining;
+mod key_negotiation;
 mod leaving;
 mod local_node;
+mod network;
 mod node;
+mod retries;
 mod routing;
 mod scanning;
+mod sleepy;
+mod touchlink;
 mod zcl;
 mod zdp;
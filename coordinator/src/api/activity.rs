@@ -0,0 +1,35 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use zb_core::short_id::Device;
+
+use crate::Coordinator;
+use crate::activity::DeviceActivity;
+
+/// Trait for querying the activity the coordinator observed for each device.
+pub trait Activity {
+    /// Return the activity of a device, if the coordinator has observed any.
+    fn device_activity(&self, device: Device) -> Option<DeviceActivity>;
+
+    /// Return the activity of every device the coordinator has observed.
+    fn activity(&self) -> BTreeMap<Device, DeviceActivity>;
+
+    /// Set how long a device may stay silent before it is reported as unresponsive.
+    ///
+    /// `None` disables the reports.
+    fn set_silence_threshold(&self, threshold: Option<Duration>);
+}
+
+impl Activity for Coordinator {
+    fn device_activity(&self, device: Device) -> Option<DeviceActivity> {
+        self.activity.get(device)
+    }
+
+    fn activity(&self) -> BTreeMap<Device, DeviceActivity> {
+        self.activity.snapshot()
+    }
+
+    fn set_silence_threshold(&self, threshold: Option<Duration>) {
+        self.activity.set_silence_threshold(threshold);
+    }
+}
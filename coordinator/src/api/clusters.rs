@@ -1,12 +1,14 @@
 //! Zigbee cluster traits.
 
+pub use self::alarms::{AlarmLogEntry, Alarms};
 pub use self::attributes::{Attributes, ReadAttributeResult, WriteAttributeResult};
 pub use self::color_control::ColorControl;
 pub use self::groups::Groups;
 pub use self::level::Level;
 pub use self::on_off::OnOff;
-pub use self::ota::{CancellableOtaUpdate, Ota};
+pub use self::ota::{CancellableOtaUpdate, ObservableOtaUpdate, Ota, OtaClient};
 
+mod alarms;
 mod attributes;
 mod color_control;
 mod groups;
@@ -0,0 +1,184 @@
+use zb_aps::apsde::{IndividualEndpoint, NetworkDestination};
+use zb_zcl::Status;
+use zb_zcl::alarms::{
+    Entry, GetAlarm, GetAlarmResponse, ResetAlarm, ResetAlarmLog, ResetAllAlarms,
+};
+
+use crate::api::zcl::request;
+use crate::event::AlarmKey;
+use crate::{Error, StatusExt, Zcl};
+
+/// Largest number of entries [`Alarms::alarm_log`] drains from one device.
+const MAX_ALARM_LOG_ENTRIES: usize = 256;
+
+/// Entry removed from a device's alarm log.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct AlarmLogEntry {
+    key: AlarmKey,
+    timestamp: Option<u32>,
+}
+
+impl AlarmLogEntry {
+    /// Return the source cluster and alarm code of the logged alarm.
+    #[must_use]
+    pub const fn key(self) -> AlarmKey {
+        self.key
+    }
+
+    /// Return the device's time at which the alarm was raised, if the device reported one.
+    #[must_use]
+    pub const fn timestamp(self) -> Option<u32> {
+        self.timestamp
+    }
+}
+
+impl From<Entry> for AlarmLogEntry {
+    fn from(entry: Entry) -> Self {
+        Self {
+            key: AlarmKey::new(entry.cluster_id(), entry.code().into_inner()),
+            timestamp: entry.timestamp().as_option(),
+        }
+    }
+}
+
+/// Trait for Alarms cluster operations.
+///
+/// Every operation requires the local APS source endpoint. Alarm notifications sent by devices
+/// are reported as [`Event::Alarm`](crate::Event::Alarm).
+pub trait Alarms {
+    /// Resets one alarm of the device.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the command cannot be transmitted or if the device rejects it.
+    fn reset_alarm(
+        &self,
+        destination: NetworkDestination,
+        source_endpoint: IndividualEndpoint,
+        key: AlarmKey,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Resets every alarm of the device.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the command cannot be transmitted or if the device rejects it.
+    fn reset_all_alarms(
+        &self,
+        destination: NetworkDestination,
+        source_endpoint: IndividualEndpoint,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Clears the device's alarm log.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the command cannot be transmitted or if the device rejects it.
+    fn reset_alarm_log(
+        &self,
+        destination: NetworkDestination,
+        source_endpoint: IndividualEndpoint,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Removes and returns the earliest entry of the device's alarm log.
+    ///
+    /// Returns [`None`] if the alarm log is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if execution of the command failed or if the device reports a status
+    /// other than `SUCCESS` or `NOT_FOUND`.
+    fn get_alarm(
+        &self,
+        destination: NetworkDestination,
+        source_endpoint: IndividualEndpoint,
+    ) -> impl Future<Output = Result<Option<AlarmLogEntry>, Error>> + Send;
+
+    /// Drains the device's alarm log, returning its entries from the earliest to the latest.
+    ///
+    /// Get Alarm is sent repeatedly until the device reports an empty log, for at most 256
+    /// entries. Entries that were removed before a failure are lost.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if any Get Alarm exchange fails, or [`Error::AlarmLogTooLong`] if the
+    /// device still reports entries after 256 have been drained.
+    fn alarm_log(
+        &self,
+        destination: NetworkDestination,
+        source_endpoint: IndividualEndpoint,
+    ) -> impl Future<Output = Result<Vec<AlarmLogEntry>, Error>> + Send;
+}
+
+impl<T> Alarms for T
+where
+    T: Zcl + Sync,
+{
+    async fn reset_alarm(
+        &self,
+        destination: NetworkDestination,
+        source_endpoint: IndividualEndpoint,
+        key: AlarmKey,
+    ) -> Result<(), Error> {
+        self.communicate_default(request(
+            destination.into(),
+            source_endpoint,
+            ResetAlarm::new(key.code(), key.cluster_id()),
+        ))
+        .await
+    }
+
+    async fn reset_all_alarms(
+        &self,
+        destination: NetworkDestination,
+        source_endpoint: IndividualEndpoint,
+    ) -> Result<(), Error> {
+        self.communicate_default(request(destination.into(), source_endpoint, ResetAllAlarms))
+            .await
+    }
+
+    async fn reset_alarm_log(
+        &self,
+        destination: NetworkDestination,
+        source_endpoint: IndividualEndpoint,
+    ) -> Result<(), Error> {
+        self.communicate_default(request(destination.into(), source_endpoint, ResetAlarmLog))
+            .await
+    }
+
+    async fn get_alarm(
+        &self,
+        destination: NetworkDestination,
+        source_endpoint: IndividualEndpoint,
+    ) -> Result<Option<AlarmLogEntry>, Error> {
+        let response = self
+            .communicate::<GetAlarmResponse>(request(destination.into(), source_endpoint, GetAlarm))
+            .await?
+            .await?;
+
+        if response.status() == Ok(Status::NotFound) {
+            return Ok(None);
+        }
+        response.status().ensure_success()?;
+        response
+            .entry()
+            .map(AlarmLogEntry::from)
+            .map(Some)
+            .ok_or(Error::Zcl(Ok(Status::MalformedCommand)))
+    }
+
+    async fn alarm_log(
+        &self,
+        destination: NetworkDestination,
+        source_endpoint: IndividualEndpoint,
+    ) -> Result<Vec<AlarmLogEntry>, Error> {
+        let mut entries = Vec::new();
+        while let Some(entry) = self.get_alarm(destination, source_endpoint).await? {
+            if entries.len() == MAX_ALARM_LOG_ENTRIES {
+                return Err(Error::AlarmLogTooLong(MAX_ALARM_LOG_ENTRIES));
+            }
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+}
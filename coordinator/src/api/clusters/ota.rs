@@ -1,9 +1,16 @@
+use std::io::{Seek, Write};
+
 use tokio::sync::mpsc::Sender;
+use tokio::sync::oneshot;
 use zb_aps::apsde::IndividualEndpoint;
 use zb_core::FullAddress;
+use zb_zcl::ota_upgrade::ImageId;
 
-pub use crate::ota::CancellableOtaUpdate;
-use crate::ota::{Image, Message, Update, UpdateTimeouts};
+use crate::ota::{
+    Campaign, CampaignHandle, ClientConfig, Download, Image, Message, RepositoryImage, Update,
+    UpdateTimeouts,
+};
+pub use crate::ota::{CancellableOtaUpdate, ObservableOtaUpdate};
 use crate::{Coordinator, Error};
 
 /// API for scheduling OTA updates through the coordinator-owned server.
@@ -20,7 +27,8 @@ pub trait Ota {
     /// future remains pending while the OTA exchange runs and resolves after the client reports
     /// success or the server observes a terminal update failure. Dropping the future cancels the
     /// offer and releases its transfer resources. [`CancellableOtaUpdate::cancel`] provides the
-    /// equivalent explicit operation.
+    /// equivalent explicit operation, and [`ObservableOtaUpdate::progress`] observes the data
+    /// served to the client.
     ///
     /// This method uses [`UpdateTimeouts::default`]. Use [`Self::update_with_timeouts`] to select
     /// deadlines for an individual offer.
@@ -36,7 +44,8 @@ pub trait Ota {
         target_endpoint: IndividualEndpoint,
         source_endpoint: IndividualEndpoint,
         image: Image,
-    ) -> impl Future<Output = Result<(), Error>> + CancellableOtaUpdate + Send {
+    ) -> impl Future<Output = Result<(), Error>> + CancellableOtaUpdate + ObservableOtaUpdate + Send
+    {
         self.update_with_timeouts(
             target,
             target_endpoint,
@@ -56,7 +65,53 @@ pub trait Ota {
         source_endpoint: IndividualEndpoint,
         image: Image,
         timeouts: UpdateTimeouts,
-    ) -> impl Future<Output = Result<(), Error>> + CancellableOtaUpdate + Send;
+    ) -> impl Future<Output = Result<(), Error>> + CancellableOtaUpdate + ObservableOtaUpdate + Send;
+
+    /// Add `image` to the repository served to devices without a scheduled update.
+    ///
+    /// When a device queries for its next image and no update is scheduled for its endpoint, the
+    /// server offers the newest eligible repository image whose file version is newer than the
+    /// device's current version and emits [`crate::OtaEvent`]s when the transfer starts and
+    /// finishes. An image with the same manufacturer code, image type, and file version replaces
+    /// the registered one. Repository transfers count towards the concurrent update-task limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SendError`] if the image cannot be queued, [`Error::ReceiveError`] if the
+    /// server stops before confirming the registration, or [`Error::Ota`] when the subscription
+    /// registration fails.
+    fn register_image(
+        &self,
+        image: RepositoryImage,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Remove the repository image identified by `image`.
+    ///
+    /// Transfers that already serve the image continue until they finish.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SendError`] if the removal cannot be queued.
+    fn unregister_image(&self, image: ImageId) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Distribute one image to a set of devices or to the members of a group.
+    ///
+    /// The server staggers Image Notify commands at the campaign's notify interval. It starts a
+    /// transfer when a targeted device queries while the campaign has a free transfer slot. All
+    /// image data served by the campaign's transfers shares its airtime limit. Campaign transfers
+    /// count towards the concurrent update-task limit. Devices that query while no slot is free
+    /// are told that no image is available and are notified again in a later round.
+    ///
+    /// The returned handle pauses, resumes, or cancels the campaign, and observes its counters.
+    /// It resolves with the outcome of every device once the campaign ends. Dropping the handle
+    /// cancels the campaign.
+    ///
+    /// # Errors
+    ///
+    /// The handle resolves to [`Error::SendError`] if the campaign cannot be queued,
+    /// [`Error::ReceiveError`] if the server stops before reporting an outcome, or [`Error::Ota`]
+    /// when the subscription registration fails.
+    fn start_campaign(&self, campaign: Campaign) -> CampaignHandle;
 }
 
 impl Ota for Sender<Message> {
@@ -67,7 +122,8 @@ impl Ota for Sender<Message> {
         source_endpoint: IndividualEndpoint,
         image: Image,
         timeouts: UpdateTimeouts,
-    ) -> impl Future<Output = Result<(), Error>> + CancellableOtaUpdate + Send {
+    ) -> impl Future<Output = Result<(), Error>> + CancellableOtaUpdate + ObservableOtaUpdate + Send
+    {
         Update::new(
             self.clone(),
             target,
@@ -77,6 +133,26 @@ impl Ota for Sender<Message> {
             timeouts,
         )
     }
+
+    async fn register_image(&self, image: RepositoryImage) -> Result<(), Error> {
+        let (response, result) = oneshot::channel();
+        self.send(Message::RegisterImage {
+            image: Box::new(image),
+            response,
+        })
+        .await?;
+        result.await??;
+        Ok(())
+    }
+
+    async fn unregister_image(&self, image: ImageId) -> Result<(), Error> {
+        self.send(Message::UnregisterImage { image }).await?;
+        Ok(())
+    }
+
+    fn start_campaign(&self, campaign: Campaign) -> CampaignHandle {
+        CampaignHandle::new(self.clone(), campaign)
+    }
 }
 
 impl Ota for Coordinator {
@@ -87,8 +163,51 @@ impl Ota for Coordinator {
         source_endpoint: IndividualEndpoint,
         image: Image,
         timeouts: UpdateTimeouts,
-    ) -> impl Future<Output = Result<(), Error>> + CancellableOtaUpdate + Send {
+    ) -> impl Future<Output = Result<(), Error>> + CancellableOtaUpdate + ObservableOtaUpdate + Send
+    {
         self.ota
             .update_with_timeouts(target, target_endpoint, source_endpoint, image, timeouts)
     }
+
+    fn register_image(
+        &self,
+        image: RepositoryImage,
+    ) -> impl Future<Output = Result<(), Error>> + Send {
+        self.ota.register_image(image)
+    }
+
+    fn unregister_image(&self, image: ImageId) -> impl Future<Output = Result<(), Error>> + Send {
+        self.ota.unregister_image(image)
+    }
+
+    fn start_campaign(&self, campaign: Campaign) -> CampaignHandle {
+        self.ota.start_campaign(campaign)
+    }
+}
+
+/// API for updating the local node from an upstream OTA server.
+pub trait OtaClient {
+    /// Start an OTA Upgrade client that polls `config`'s server and downloads new images into
+    /// `sink`.
+    ///
+    /// The client queries the server after the configured jitter and query interval, and
+    /// immediately when the server sends a matching Image Notify. Offered images are written to
+    /// `sink` from its start while their header, sub-element framing, and image integrity code are
+    /// verified. Failed downloads are reported to the server and through
+    /// [`OtaClientEvent`](crate::OtaClientEvent), after which the client resumes polling.
+    ///
+    /// The returned future resolves once a verified image is due for activation, and yields the
+    /// sink so that the caller can apply it. Dropping the future stops the client.
+    fn start_ota_client<W>(&self, config: ClientConfig, sink: W) -> Download<W>
+    where
+        W: Write + Seek + Send + 'static;
+}
+
+impl OtaClient for Coordinator {
+    fn start_ota_client<W>(&self, config: ClientConfig, sink: W) -> Download<W>
+    where
+        W: Write + Seek + Send + 'static,
+    {
+        Download::spawn(self.zcl.clone(), self.events.clone(), config, sink)
+    }
 }
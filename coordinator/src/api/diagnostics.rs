@@ -0,0 +1,61 @@
+use zb_aps::apsde::{IndividualEndpoint, NetworkDestination};
+use zb_hw::Counters;
+
+use crate::api::Attributes;
+use crate::diagnostics::remote::{ATTRIBUTES, ATTRIBUTES_PER_READ, counter};
+use crate::{Coordinator, Error};
+
+/// Trait for reading diagnostic counters of the NCP and of remote devices.
+///
+/// Both sources report [`Counters`], so that the counters of the NCP and those of a device's
+/// Diagnostics cluster share the same names. Use a [`Sampler`](crate::diagnostics::Sampler) to
+/// collect them periodically.
+pub trait Diagnostics {
+    /// Return the diagnostic counters of the NCP.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the hardware request fails or the backend does not provide
+    /// counters.
+    fn get_counters(&self) -> impl Future<Output = Result<Counters, Error>> + Send;
+
+    /// Read the counters of a device's Diagnostics cluster.
+    ///
+    /// Attributes that the device does not support or reports as invalid are omitted. The
+    /// attributes are read in several requests to keep each response within one APS frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if communication fails or a response is invalid.
+    fn read_diagnostics(
+        &self,
+        destination: NetworkDestination,
+        source_endpoint: IndividualEndpoint,
+    ) -> impl Future<Output = Result<Counters, Error>> + Send;
+}
+
+impl Diagnostics for Coordinator {
+    async fn get_counters(&self) -> Result<Counters, Error> {
+        Ok(self.ncp.get_counters().await?)
+    }
+
+    async fn read_diagnostics(
+        &self,
+        destination: NetworkDestination,
+        source_endpoint: IndividualEndpoint,
+    ) -> Result<Counters, Error> {
+        let mut counters = Vec::new();
+
+        for attributes in ATTRIBUTES.chunks(ATTRIBUTES_PER_READ) {
+            counters.extend(
+                self.read(destination, source_endpoint, attributes.iter().copied())
+                    .await?
+                    .into_iter()
+                    .filter_map(Result::ok)
+                    .filter_map(|attribute| counter(&attribute)),
+            );
+        }
+
+        Ok(counters.into_iter().collect())
+    }
+}
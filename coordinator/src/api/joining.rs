@@ -1,5 +1,8 @@
 use std::time::Duration;
 
+use zb_core::IeeeAddress;
+use zb_core::security::InstallCode;
+
 use crate::{Coordinator, Error};
 
 /// Trait to manage joining the network.
@@ -19,10 +22,36 @@ pub trait Joining {
         &self,
         duration: Duration,
     ) -> impl Future<Output = Result<Duration, Error>> + Send;
+
+    /// Admit a device that authenticates its join with an install code.
+    ///
+    /// The device's link key is derived from `install_code` and stored on the NCP as a transient
+    /// trust-center link key for `ieee_address`. Joining must still be allowed separately through
+    /// [`Self::allow_joining`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP cannot store the derived link key.
+    fn allow_install_code(
+        &self,
+        ieee_address: IeeeAddress,
+        install_code: &InstallCode,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
 }
 
 impl Joining for Coordinator {
     async fn allow_joining(&self, duration: Duration) -> Result<Duration, Error> {
         Ok(self.ncp.allow_joins(duration).await?)
     }
+
+    async fn allow_install_code(
+        &self,
+        ieee_address: IeeeAddress,
+        install_code: &InstallCode,
+    ) -> Result<(), Error> {
+        Ok(self
+            .ncp
+            .add_transient_link_key(ieee_address, install_code.link_key())
+            .await?)
+    }
 }
@@ -0,0 +1,73 @@
+use tokio::sync::oneshot::channel;
+use zb_core::IeeeAddress;
+use zb_core::security::InstallCode;
+use zb_zdp::SecurityGetAuthenticationLevelRsp;
+
+use crate::zdp::Message;
+use crate::{Coordinator, Error};
+
+/// Trait to manage Zigbee 3.0 dynamic link key negotiation.
+///
+/// The coordinator answers `Security_Start_Key_Negotiation_req` from joining devices as the
+/// responder of a Curve25519 SPEKE exchange. Devices with a registered install code negotiate
+/// with it as the passphrase; all other devices use the well-known passphrase. The negotiated link
+/// key is stored on the NCP and verified with an APS frame counter challenge before the device's
+/// authentication level is updated.
+pub trait KeyNegotiation {
+    /// Use an install code as the passphrase when `ieee_address` negotiates its link key.
+    ///
+    /// A negotiation authenticated with an install code raises the device to the authenticated
+    /// key negotiation level.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the ZDP actor is unavailable.
+    fn add_negotiation_install_code(
+        &self,
+        ieee_address: IeeeAddress,
+        install_code: &InstallCode,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Return the authentication level the coordinator reports for a device.
+    ///
+    /// The response is identical to the `Security_Get_Authentication_Level_rsp` sent to remote
+    /// devices that ask for it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the ZDP actor is unavailable.
+    fn get_authentication_level(
+        &self,
+        ieee_address: IeeeAddress,
+    ) -> impl Future<Output = Result<SecurityGetAuthenticationLevelRsp, Error>> + Send;
+}
+
+impl KeyNegotiation for Coordinator {
+    async fn add_negotiation_install_code(
+        &self,
+        ieee_address: IeeeAddress,
+        install_code: &InstallCode,
+    ) -> Result<(), Error> {
+        self.zdp
+            .send(Message::AddInstallCode {
+                ieee_address,
+                install_code: install_code.clone(),
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn get_authentication_level(
+        &self,
+        ieee_address: IeeeAddress,
+    ) -> Result<SecurityGetAuthenticationLevelRsp, Error> {
+        let (response, result) = channel();
+        self.zdp
+            .send(Message::GetAuthenticationLevel {
+                ieee_address,
+                response,
+            })
+            .await?;
+        Ok(result.await?)
+    }
+}
@@ -105,6 +105,7 @@ mod tests {
         let Message::Communicate {
             device,
             request,
+            timeout: _,
             response: _,
         } = messages
             .try_recv()
@@ -0,0 +1,93 @@
+pub use zb_hw::{Formation, JoinPolicy, NetworkParameters, TrustCenterPolicy};
+
+use crate::backup::NetworkBackup;
+use crate::{Coordinator, Error};
+
+/// Trait for forming, leaving, securing, and backing up the coordinator's network.
+///
+/// The operations are delegated to the hardware/NCP. Backends that cannot perform an operation
+/// report it as unsupported.
+pub trait Network {
+    /// Form a new network with the coordinator as its trust center.
+    ///
+    /// The NCP reports [`crate::Network::Up`] once the network is operational.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP cannot form the network.
+    fn form_network(&self, formation: Formation) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Leave the current network and forget its parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP cannot leave the network.
+    fn leave_network(&self) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Return the PAN ID, extended PAN ID, and channel of the current network.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP is not part of a network or the hardware request fails.
+    fn get_network_parameters(
+        &self,
+    ) -> impl Future<Output = Result<NetworkParameters, Error>> + Send;
+
+    /// Set the security policy that the trust center applies to joining devices.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP cannot apply the policy.
+    fn set_trust_center_policy(
+        &self,
+        policy: TrustCenterPolicy,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Export the state needed to move the network to another NCP.
+    ///
+    /// Serialize the backup with [`crate::backup::to_json`] to store it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP is not part of a network or cannot export its state.
+    fn backup_network(&self) -> impl Future<Output = Result<NetworkBackup, Error>> + Send;
+
+    /// Import a network exported by [`Network::backup_network`], possibly on another backend.
+    ///
+    /// The NCP must have left its network. It reports [`crate::Network::Up`] once the restored
+    /// network is operational.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP is part of a network or cannot import the state.
+    fn restore_network(
+        &self,
+        backup: NetworkBackup,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+}
+
+impl Network for Coordinator {
+    async fn form_network(&self, formation: Formation) -> Result<(), Error> {
+        Ok(self.ncp.form_network(formation).await?)
+    }
+
+    async fn leave_network(&self) -> Result<(), Error> {
+        Ok(self.ncp.leave_network().await?)
+    }
+
+    async fn get_network_parameters(&self) -> Result<NetworkParameters, Error> {
+        Ok(self.ncp.get_network_parameters().await?)
+    }
+
+    async fn set_trust_center_policy(&self, policy: TrustCenterPolicy) -> Result<(), Error> {
+        Ok(self.ncp.set_trust_center_policy(policy).await?)
+    }
+
+    async fn backup_network(&self) -> Result<NetworkBackup, Error> {
+        Ok(self.ncp.backup_network().await?)
+    }
+
+    async fn restore_network(&self, backup: NetworkBackup) -> Result<(), Error> {
+        Ok(self.ncp.restore_network(backup).await?)
+    }
+}
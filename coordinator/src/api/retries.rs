@@ -0,0 +1,21 @@
+use crate::Coordinator;
+use crate::retry::RetryPolicy;
+
+/// Trait for configuring how failed ZCL and ZDP transmissions are retried.
+pub trait Retries {
+    /// Return the current retry policy.
+    fn retry_policy(&self) -> RetryPolicy;
+
+    /// Replace the retry policy for subsequently submitted requests.
+    fn set_retry_policy(&self, policy: RetryPolicy);
+}
+
+impl Retries for Coordinator {
+    fn retry_policy(&self) -> RetryPolicy {
+        self.retrier.policy()
+    }
+
+    fn set_retry_policy(&self, policy: RetryPolicy) {
+        self.retrier.set_policy(policy);
+    }
+}
@@ -0,0 +1,82 @@
+use bytes::Bytes;
+use zb_aps::apsde::DataRequest;
+use zb_core::short_id::Device;
+use zb_zcl::UnsequencedFrame;
+
+use crate::api::{Node, Zcl};
+use crate::{Coordinator, Error};
+
+/// Trait for holding ZCL commands until a sleepy end device is awake.
+///
+/// Requests to a device marked as sleepy are queued by the coordinator and transmitted in order
+/// as soon as any indication, such as a Poll Control Check-in, arrives from the device.
+pub trait SleepyDevices {
+    /// Mark or unmark a device as sleepy.
+    ///
+    /// Requests still queued for a device that is no longer sleepy are transmitted immediately.
+    fn set_sleepy(&self, device: Device, sleepy: bool);
+
+    /// Return whether a device is marked as sleepy.
+    fn is_sleepy(&self, device: Device) -> bool;
+
+    /// Return the number of requests queued for a device.
+    fn queued(&self, device: Device) -> usize;
+
+    /// Read the node descriptor of a device and mark it as sleepy if its receiver is off when idle.
+    ///
+    /// Call this while the device is awake, for example right after it joined.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the node descriptor cannot be read.
+    fn detect_sleepy(&self, device: Device) -> impl Future<Output = Result<bool, Error>> + Send;
+
+    /// Send a ZCL command once its destination is awake.
+    ///
+    /// Requests addressed to the NWK address of a sleepy device are queued until the device is
+    /// heard from; all other requests are transmitted immediately, as with [`Zcl::transmit`].
+    /// Dropping the returned future before the device wakes up withdraws the request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the request cannot be transmitted, or
+    /// [`Error::SleepyDeviceLeft`] if the device leaves the network first.
+    fn transmit_when_awake(
+        &self,
+        request: DataRequest<UnsequencedFrame<Bytes>>,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+}
+
+impl SleepyDevices for Coordinator {
+    fn set_sleepy(&self, device: Device, sleepy: bool) {
+        self.sleepy.set_sleepy(device, sleepy);
+    }
+
+    fn is_sleepy(&self, device: Device) -> bool {
+        self.sleepy.is_sleepy(device)
+    }
+
+    fn queued(&self, device: Device) -> usize {
+        self.sleepy.queued(device)
+    }
+
+    async fn detect_sleepy(&self, device: Device) -> Result<bool, Error> {
+        let sleepy = !self
+            .descriptor(device, None)
+            .await?
+            .mac_capability_flags()
+            .is_receiver_on_when_idle();
+        self.set_sleepy(device, sleepy);
+        Ok(sleepy)
+    }
+
+    async fn transmit_when_awake(
+        &self,
+        request: DataRequest<UnsequencedFrame<Bytes>>,
+    ) -> Result<(), Error> {
+        match self.sleepy.enqueue(request) {
+            Ok(result) => result.await?,
+            Err(request) => self.transmit(request).await,
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use zb_hw::{ChannelMask, InterPanDestination};
+use zb_zcl::touchlink::{IdentifyRequest, ResetToFactoryNewRequest};
+
+use crate::touchlink::{Session, Target};
+use crate::{Coordinator, Error};
+
+/// Trait for commissioning devices in close proximity through Touchlink.
+///
+/// Touchlink commands are exchanged as inter-PAN frames on the target's channel. The radio leaves
+/// the network channel for the duration of each call, and concurrent calls run one at a time.
+pub trait Touchlink {
+    /// Scan `channels` for Touchlink targets in radio range.
+    ///
+    /// Use [`PRIMARY_CHANNELS`](crate::touchlink::PRIMARY_CHANNELS) for a regular scan. The
+    /// targets are sorted by descending link quality, so the closest device usually comes first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the hardware cannot transmit inter-PAN frames.
+    fn touchlink_scan(
+        &self,
+        channels: ChannelMask,
+    ) -> impl Future<Output = Result<Vec<Target>, Error>> + Send;
+
+    /// Ask a scanned target to identify itself for `seconds`.
+    ///
+    /// Pass [`IdentifyRequest::STOP`] to stop identifying, or [`IdentifyRequest::DEFAULT_DURATION`]
+    /// for the target's default duration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the hardware cannot transmit inter-PAN frames.
+    fn touchlink_identify(
+        &self,
+        target: Target,
+        seconds: u16,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Reset a scanned target to factory defaults, making it leave its network.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the hardware cannot transmit inter-PAN frames.
+    fn touchlink_reset_to_factory_new(
+        &self,
+        target: Target,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+}
+
+impl Touchlink for Coordinator {
+    async fn touchlink_scan(&self, channels: ChannelMask) -> Result<Vec<Target>, Error> {
+        let mut session = self.inter_pan.session(self.ncp.clone()).await;
+        let targets = session
+            .scan(channels, rand::random_range(1..=u32::MAX))
+            .await;
+        finish(session, targets).await
+    }
+
+    async fn touchlink_identify(&self, target: Target, seconds: u16) -> Result<(), Error> {
+        let mut session = self.inter_pan.session(self.ncp.clone()).await;
+        let result = session
+            .transmit(
+                target.channel(),
+                InterPanDestination::device(target.ieee_address()),
+                IdentifyRequest::new(target.transaction_id(), seconds),
+            )
+            .await;
+        finish(session, result).await
+    }
+
+    async fn touchlink_reset_to_factory_new(&self, target: Target) -> Result<(), Error> {
+        let mut session = self.inter_pan.session(self.ncp.clone()).await;
+        let result = session
+            .transmit(
+                target.channel(),
+                InterPanDestination::device(target.ieee_address()),
+                ResetToFactoryNewRequest::new(target.transaction_id()),
+            )
+            .await;
+        finish(session, result).await
+    }
+}
+
+/// End `session` even if the exchange failed, reporting the first error.
+async fn finish<T>(session: Session, result: Result<T, Error>) -> Result<T, Error> {
+    let end = session.end().await;
+    let value = result?;
+    end.map(|()| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::sync::{Arc, Mutex, PoisonError};
+
+    use bytes::Bytes;
+    use le_stream::ToLeStream;
+    use tokio::runtime::Builder;
+    use tokio::sync::mpsc::channel;
+    use zb_core::node::{Descriptor, Flags, MacCapabilityFlags, ServerMask};
+    use zb_core::short_id::Device;
+    use zb_core::{Cluster as ClusterId, IeeeAddress};
+    use zb_hw::sim::{Link, VirtualDevice, VirtualNetwork};
+    use zb_hw::{Channel, ChannelMask, Driver};
+    use zb_zcl::touchlink::{
+        Command, IdentifyRequest, ScanResponse, TouchlinkInformation, ZigbeeInformation,
+    };
+    use zb_zcl::{Cluster, Frame, UnsequencedFrame};
+
+    use super::Touchlink;
+    use crate::{Coordinator, CoordinatorConfig};
+
+    const CAPACITY: NonZeroUsize = NonZeroUsize::new(16).expect("capacity is non-zero");
+    const TARGET: IeeeAddress = IeeeAddress::new(0, 0x17, 0x88, 0x01, 0, 0, 0, 1);
+    const PAN_ID: u16 = 0x1A62;
+    const RESPONSE_ID: u32 = 0x0BAD_CAFE;
+    const LINK_QUALITY: u8 = 200;
+
+    /// Answer Scan Requests and record the transaction identifiers of other commands.
+    fn respond(asdu: &[u8], received: &Mutex<Vec<(u8, u32)>>) -> Option<Bytes> {
+        let frame = Frame::parse(
+            ClusterId::TouchlinkCommissioning.as_u16(),
+            asdu.iter().copied(),
+        )
+        .ok()?;
+        let sequence = frame.header().seq();
+        let Cluster::Touchlink(command) = frame.into_payload() else {
+            return None;
+        };
+
+        let mut received = received.lock().unwrap_or_else(PoisonError::into_inner);
+        match command {
+            Command::ScanRequest(request) => {
+                let response = ScanResponse::new(
+                    request.transaction_id(),
+                    RESPONSE_ID,
+                    ZigbeeInformation::ROUTER | ZigbeeInformation::RX_ON_WHEN_IDLE,
+                    TouchlinkInformation::FACTORY_NEW,
+                );
+                return Some(
+                    UnsequencedFrame::from_command(response)
+                        .into_frame(sequence)
+                        .to_le_stream()
+                        .collect(),
+                );
+            }
+            Command::IdentifyRequest(request) => {
+                received.push((0x06, request.transaction_id()));
+            }
+            Command::ResetToFactoryNewRequest(request) => {
+                received.push((0x07, request.transaction_id()));
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn descriptor() -> Descriptor {
+        Descriptor::new(
+            Flags::default(),
+            MacCapabilityFlags::default(),
+            0,
+            82,
+            82,
+            ServerMask::empty(),
+            82,
+        )
+    }
+
+    #[test]
+    fn scans_identifies_and_resets_a_target_on_its_channel() {
+        Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("runtime must be available")
+            .block_on(async {
+                let radio_channel = Channel::new(15).expect("channel 15 is valid");
+                let received = Arc::new(Mutex::new(Vec::new()));
+                let recorded = Arc::clone(&received);
+                let (ncp, hw_events) = VirtualNetwork::new(IeeeAddress::default(), PAN_ID)
+                    .with_device(
+                        VirtualDevice::new(
+                            TARGET,
+                            Device::new(0x1234).expect("test short ID is valid"),
+                        )
+                        .with_radio_channel(radio_channel)
+                        .with_link(Link::new(LINK_QUALITY))
+                        .with_inter_pan_responder(
+                            ClusterId::TouchlinkCommissioning.as_u16(),
+                            move |asdu| respond(asdu, &recorded),
+                        ),
+                    )
+                    .start(CAPACITY);
+                let (ncp, actor) = ncp.into_actor(CAPACITY);
+                tokio::spawn(actor);
+                let (events_out, _events) = channel(CAPACITY.get());
+                let coordinator = Coordinator::start(
+                    ncp,
+                    descriptor(),
+                    hw_events,
+                    events_out,
+                    CoordinatorConfig::new(),
+                )
+                .expect("coordinator must start");
+
+                let channels = [Channel::MIN, radio_channel]
+                    .into_iter()
+                    .collect::<ChannelMask>();
+                let [target] = coordinator
+                    .touchlink_scan(channels)
+                    .await
+                    .expect("simulated NCP must scan")[..]
+                else {
+                    panic!("exactly one target must answer");
+                };
+                assert_eq!(target.ieee_address(), TARGET);
+                assert_eq!(target.channel(), radio_channel);
+                assert_eq!(target.link_quality(), LINK_QUALITY);
+                assert_eq!(target.response().response_id(), RESPONSE_ID);
+                assert!(target.is_factory_new());
+
+                coordinator
+                    .touchlink_identify(target, IdentifyRequest::DEFAULT_DURATION)
+                    .await
+                    .expect("simulated NCP must send Identify Request");
+                coordinator
+                    .touchlink_reset_to_factory_new(target)
+                    .await
+                    .expect("simulated NCP must send Reset to Factory New Request");
+                tokio::task::yield_now().await;
+
+                assert_eq!(
+                    *received.lock().unwrap_or_else(PoisonError::into_inner),
+                    [
+                        (0x06, target.transaction_id()),
+                        (0x07, target.transaction_id())
+                    ]
+                );
+            });
+    }
+}
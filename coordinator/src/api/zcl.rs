@@ -1,4 +1,5 @@
 use std::fmt::Debug;
+use std::time::Duration;
 
 use bytes::Bytes;
 use le_stream::ToLeStream;
@@ -123,6 +124,23 @@ pub trait Zcl {
     ) -> impl Future<Output = Result<ZclResponse<T>, Error>> + Send
     where
         T: TryFrom<Cluster, Error: Debug> + Send;
+
+    /// Send a ZCL command and wait up to `timeout` for its typed response.
+    ///
+    /// This behaves like [`Self::communicate`], but replaces the configured response timeout for
+    /// this request only. Use it for devices that answer slowly, such as sleepy end devices.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::communicate`]. Awaiting the returned [`ZclResponse`] returns
+    /// [`Error::ProtocolResponseTimeout`] if no response arrives within `timeout`.
+    fn communicate_with_timeout<T>(
+        &self,
+        request: DataRequest<UnsequencedFrame<Bytes>>,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<ZclResponse<T>, Error>> + Send
+    where
+        T: TryFrom<Cluster, Error: Debug> + Send;
 }
 
 impl Zcl for Sender<Message> {
@@ -160,14 +178,18 @@ impl Zcl for Sender<Message> {
     where
         T: TryFrom<Cluster, Error: Debug> + Send,
     {
-        let (response, result) = channel();
-
-        async move {
-            self.send(Message::Communicate { request, response })
-                .await?;
+        communicate(self, request, None)
+    }
 
-            Ok(result.await??.into())
-        }
+    fn communicate_with_timeout<T>(
+        &self,
+        request: DataRequest<UnsequencedFrame<Bytes>>,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<ZclResponse<T>, Error>> + Send
+    where
+        T: TryFrom<Cluster, Error: Debug> + Send,
+    {
+        communicate(self, request, Some(timeout))
     }
 }
 
@@ -195,6 +217,36 @@ impl Zcl for Coordinator {
     {
         self.zcl.communicate(request)
     }
+
+    fn communicate_with_timeout<T>(
+        &self,
+        request: DataRequest<UnsequencedFrame<Bytes>>,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<ZclResponse<T>, Error>> + Send
+    where
+        T: TryFrom<Cluster, Error: Debug> + Send,
+    {
+        self.zcl.communicate_with_timeout(request, timeout)
+    }
+}
+
+async fn communicate<T>(
+    zcl: &Sender<Message>,
+    request: DataRequest<UnsequencedFrame<Bytes>>,
+    timeout: Option<Duration>,
+) -> Result<ZclResponse<T>, Error>
+where
+    T: TryFrom<Cluster, Error: Debug> + Send,
+{
+    let (response, result) = channel();
+    zcl.send(Message::Communicate {
+        request,
+        timeout,
+        response,
+    })
+    .await?;
+
+    Ok(result.await??.into())
 }
 
 fn validate_default_response(command_id: u8, response: &DefaultResponse) -> Result<(), Error> {
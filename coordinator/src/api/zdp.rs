@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use bytes::Bytes;
 use le_stream::ToLeStream;
 use tokio::sync::mpsc::Sender;
@@ -42,6 +44,24 @@ pub trait Zdp {
     ) -> impl Future<Output = Result<ZdpResponse<T::Response>, Error>> + Send
     where
         T: ClusterSpecific + ExpectResponse<Command> + ToLeStream;
+
+    /// Send a ZDP request to a device and wait up to `timeout` for its typed response.
+    ///
+    /// This behaves like [`Self::communicate`], but replaces the configured response timeout for
+    /// this request only.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::communicate`]. Awaiting the returned [`ZdpResponse`] returns
+    /// [`Error::ProtocolResponseTimeout`] if no response arrives within `timeout`.
+    fn communicate_with_timeout<T>(
+        &self,
+        device: Device,
+        request: T,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<ZdpResponse<T::Response>, Error>> + Send
+    where
+        T: ClusterSpecific + ExpectResponse<Command> + ToLeStream;
 }
 
 impl Zdp for Sender<Message> {
@@ -53,29 +73,19 @@ impl Zdp for Sender<Message> {
     where
         T: ClusterSpecific + ExpectResponse<Command> + ToLeStream,
     {
-        let (response, result) = channel();
-        let destination: RequestDestination = NetworkDestination::new(
-            NetworkAddress::new(device.as_u16())
-                .expect("device short addresses are valid APSDE network addresses"),
-            IndividualEndpoint::new(Endpoint::Data).expect("ZDO endpoint is individual"),
-        )
-        .into();
-        let request: DataRequest<Bytes> = crate::aps::data_request(
-            destination,
-            IndividualEndpoint::new(Endpoint::Data).expect("ZDO endpoint is individual"),
-            Metadata::new(Profile::Network, T::ID),
-            command.to_le_stream().collect(),
-        );
+        communicate(self, device, command, None)
+    }
 
-        async move {
-            self.send(Message::Communicate {
-                device,
-                request,
-                response,
-            })
-            .await?;
-            Ok(result.await??.into())
-        }
+    fn communicate_with_timeout<T>(
+        &self,
+        device: Device,
+        command: T,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<ZdpResponse<T::Response>, Error>> + Send
+    where
+        T: ClusterSpecific + ExpectResponse<Command> + ToLeStream,
+    {
+        communicate(self, device, command, Some(timeout))
     }
 }
 
@@ -90,4 +100,51 @@ impl Zdp for Coordinator {
     {
         self.zdp.communicate(device, command)
     }
+
+    fn communicate_with_timeout<T>(
+        &self,
+        device: Device,
+        command: T,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<ZdpResponse<T::Response>, Error>> + Send
+    where
+        T: ClusterSpecific + ExpectResponse<Command> + ToLeStream,
+    {
+        self.zdp.communicate_with_timeout(device, command, timeout)
+    }
+}
+
+fn communicate<T>(
+    zdp: &Sender<Message>,
+    device: Device,
+    command: T,
+    timeout: Option<Duration>,
+) -> impl Future<Output = Result<ZdpResponse<T::Response>, Error>> + Send
+where
+    T: ClusterSpecific + ExpectResponse<Command> + ToLeStream,
+{
+    let (response, result) = channel();
+    let destination: RequestDestination = NetworkDestination::new(
+        NetworkAddress::new(device.as_u16())
+            .expect("device short addresses are valid APSDE network addresses"),
+        IndividualEndpoint::new(Endpoint::Data).expect("ZDO endpoint is individual"),
+    )
+    .into();
+    let request: DataRequest<Bytes> = crate::aps::data_request(
+        destination,
+        IndividualEndpoint::new(Endpoint::Data).expect("ZDO endpoint is individual"),
+        Metadata::new(Profile::Network, T::ID),
+        command.to_le_stream().collect(),
+    );
+
+    async move {
+        zdp.send(Message::Communicate {
+            device,
+            request,
+            timeout,
+            response,
+        })
+        .await?;
+        Ok(result.await??.into())
+    }
 }
@@ -1,6 +1,7 @@
 //! Actor for transmitting APS data frames.
 
 use std::collections::BTreeMap;
+use std::num::{NonZeroU8, NonZeroUsize};
 use std::time::Duration;
 
 use bytes::Bytes;
@@ -10,19 +11,29 @@ use tokio::sync::mpsc::{Receiver, Sender, WeakSender};
 use tokio::sync::oneshot::{Sender as OneshotSender, channel};
 use tokio::time::sleep;
 use zb_aps::TxOptions;
-use zb_aps::apsde::{ConfirmStatus, DataRequest, IndividualEndpoint, RequestDestination};
+use zb_aps::apsde::{
+    ConfirmStatus, DataRequest, IndividualEndpoint, RequestDestination, Status as ApsStatus,
+};
 use zb_hw::NcpHandle;
 
 pub use self::message::Message;
 pub use self::metadata::Metadata;
+use self::transfer::Transfer;
+pub use self::transfer_limits::TransferLimits;
 pub use self::transmission_response::TransmissionResponse;
-use crate::MPSC_CHANNEL_SIZE;
+use crate::CoordinatorConfig;
 
 mod message;
 mod metadata;
+mod transfer;
+mod transfer_limits;
 mod transmission_response;
 
 const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(30);
+/// Octets of the APS extended header that every fragment block carries.
+const EXTENDED_HEADER_LENGTH: u8 = 2;
+/// Confirmation round of transmissions that are not fragmented.
+const UNFRAGMENTED_ROUND: u16 = 0;
 const APS_COUNTER_COUNT: usize = 1_usize << u8::BITS;
 const INITIAL_COUNTER: u8 = 0;
 const INITIAL_GENERATION: u64 = 0;
@@ -65,26 +76,40 @@ const fn acknowledged<T>(request: &DataRequest<T>) -> bool {
 
 /// Handle for sending commands to the APS actor.
 #[derive(Clone, Debug)]
-pub struct Aps(Sender<Message>);
+pub struct Aps {
+    sender: Sender<Message>,
+    transfer_limits: TransferLimits,
+}
 
 impl Aps {
     /// Wrap an APS actor sender.
     #[must_use]
-    pub const fn new(sender: Sender<Message>) -> Self {
-        Self(sender)
+    pub fn new(sender: Sender<Message>) -> Self {
+        Self {
+            sender,
+            transfer_limits: TransferLimits::default(),
+        }
+    }
+
+    /// Return the transfer limits of remote devices that the APS actor enforces.
+    #[must_use]
+    pub const fn transfer_limits(&self) -> &TransferLimits {
+        &self.transfer_limits
     }
 
     /// Queue an APS frame and return its deferred transmission result.
     ///
     /// The returned response first waits for backend acceptance. For an acknowledged unicast, it
-    /// then waits for the corresponding hardware completion event.
+    /// then waits for the corresponding hardware completion event. Acknowledged unicasts that
+    /// exceed the NCP's maximum payload length are fragmented, and their response completes once
+    /// every window of blocks has been confirmed.
     pub async fn transmit(
         &self,
         request: DataRequest<Bytes>,
     ) -> Result<TransmissionResponse, crate::Error> {
         let (response, result) = channel();
 
-        self.0
+        self.sender
             .send(Message::Transmit { request, response })
             .await
             .map_err(|_| crate::Error::from(zb_hw::Error::ActorUnavailable))?;
@@ -96,7 +121,7 @@ impl Aps {
 
     /// Forward a hardware APS data confirmation to the APS actor.
     pub async fn confirm(&self, counter: u8, status: ConfirmStatus) -> Result<(), zb_hw::Error> {
-        self.0
+        self.sender
             .send(Message::Confirm { counter, status })
             .await
             .map_err(|_| zb_hw::Error::ActorUnavailable)
@@ -104,15 +129,15 @@ impl Aps {
 
     /// Notify the APS actor that the Zigbee network is down.
     pub async fn network_down(&self) -> Result<(), zb_hw::Error> {
-        self.0
+        self.sender
             .send(Message::NetworkDown)
             .await
             .map_err(|_| zb_hw::Error::ActorUnavailable)
     }
 
-    /// Notify the APS actor that its hardware event source has terminated.
+    /// Notify the APS actor that its hardware event source has terminated or been replaced.
     pub async fn hardware_unavailable(&self) -> Result<(), zb_hw::Error> {
-        self.0
+        self.sender
             .send(Message::HardwareUnavailable)
             .await
             .map_err(|_| zb_hw::Error::ActorUnavailable)
@@ -125,6 +150,9 @@ pub struct Transceiver {
     ncp: NcpHandle,
     state: TransmissionState,
     inbox: WeakSender<Message>,
+    transfer_limits: TransferLimits,
+    maximum_payload_length: Option<u8>,
+    window_size: NonZeroU8,
 }
 
 #[derive(Debug)]
@@ -133,6 +161,7 @@ struct TransmissionState {
     next_generation: u64,
     responses: BTreeMap<u8, PendingTransmission>,
     quarantined: BTreeMap<u8, u64>,
+    windows: Vec<Window>,
 }
 
 #[derive(Debug)]
@@ -141,6 +170,14 @@ struct PendingTransmission {
     acknowledged: bool,
     phase: TransmissionPhase,
     response: Option<PendingResponse>,
+    transfer: Option<Transfer>,
+}
+
+/// Next window of a fragmented transmission, ready to be submitted.
+#[derive(Debug)]
+struct Window {
+    token: TransmissionToken,
+    requests: Vec<DataRequest<Bytes>>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -154,12 +191,14 @@ impl PendingTransmission {
         token: TransmissionToken,
         acknowledged: bool,
         response: PendingResponse,
+        transfer: Option<Transfer>,
     ) -> Self {
         Self {
             generation: token.generation,
             acknowledged,
             phase: TransmissionPhase::Submitting { confirmation: None },
             response: Some(response),
+            transfer,
         }
     }
 
@@ -170,9 +209,16 @@ impl PendingTransmission {
             acknowledged: true,
             phase: TransmissionPhase::AwaitingConfirmation,
             response: Some(response),
+            transfer: None,
         }
     }
 
+    fn round(&self) -> u16 {
+        self.transfer
+            .as_ref()
+            .map_or(UNFRAGMENTED_ROUND, Transfer::round)
+    }
+
     fn complete(&mut self, result: Result<(), zb_hw::Error>) {
         if let Some(response) = self.response.take() {
             response.send(result).unwrap_or_else(drop);
@@ -200,6 +246,7 @@ impl TransmissionState {
             next_generation: INITIAL_GENERATION,
             responses: BTreeMap::new(),
             quarantined: BTreeMap::new(),
+            windows: Vec::new(),
         }
     }
 
@@ -248,23 +295,68 @@ impl TransmissionState {
             TransmissionPhase::AwaitingConfirmation => {}
         }
 
-        let mut pending = self
+        let pending = self
             .responses
             .remove(&counter)
             .expect("pending confirmation remains present");
+        self.conclude(counter, pending, status);
+    }
+
+    /// Complete a confirmed transmission, or queue the next window of a fragmented one.
+    ///
+    /// A failed window is queued again while it has retries left, unless the destination cannot
+    /// receive fragmented transmissions at all.
+    fn conclude(&mut self, counter: u8, mut pending: PendingTransmission, status: ConfirmStatus) {
+        if pending.response.is_some()
+            && let Some(transfer) = &mut pending.transfer
+        {
+            let proceed = if status.is_success() {
+                transfer.advance()
+            } else {
+                status != ConfirmStatus::Aps(ApsStatus::PeerCannotFragment) && transfer.retry()
+            };
+
+            if proceed {
+                self.windows.push(Window {
+                    token: TransmissionToken {
+                        counter,
+                        generation: pending.generation,
+                    },
+                    requests: transfer.requests(),
+                });
+                pending.phase = TransmissionPhase::Submitting { confirmation: None };
+                self.responses.insert(counter, pending);
+                return;
+            }
+        }
+
         pending.complete_confirmation(status);
     }
 
+    /// Take the windows of fragmented transmissions that are ready to be submitted.
+    fn take_windows(&mut self) -> Vec<Window> {
+        std::mem::take(&mut self.windows)
+    }
+
+    /// Return the confirmation round of a pending transmission.
+    fn round(&self, token: TransmissionToken) -> u16 {
+        self.responses
+            .get(&token.counter)
+            .filter(|pending| pending.generation == token.generation)
+            .map_or(UNFRAGMENTED_ROUND, PendingTransmission::round)
+    }
+
     /// Store a backend submission under its allocated Zigbee APS counter.
     fn store_submission(
         &mut self,
         token: TransmissionToken,
         acknowledged: bool,
         response: PendingResponse,
+        transfer: Option<Transfer>,
     ) {
         let previous = self.responses.insert(
             token.counter,
-            PendingTransmission::submitting(token, acknowledged, response),
+            PendingTransmission::submitting(token, acknowledged, response, transfer),
         );
         debug_assert!(previous.is_none());
         debug_assert!(!self.quarantined.contains_key(&token.counter));
@@ -313,7 +405,7 @@ impl TransmissionState {
             return false;
         };
         if let Some(status) = confirmation {
-            pending.complete_confirmation(status);
+            self.conclude(token.counter, pending, status);
             return false;
         }
 
@@ -349,8 +441,14 @@ impl TransmissionState {
     }
 
     /// Expire an accepted transmission and report whether its confirmation is still missing.
-    fn timeout(&mut self, token: TransmissionToken) -> bool {
+    ///
+    /// Deadlines of earlier rounds of a fragmented transmission are ignored.
+    fn timeout(&mut self, token: TransmissionToken, round: u16) -> bool {
         if self.pending_generation_matches(token) {
+            if self.round(token) != round {
+                return false;
+            }
+
             let mut pending = self
                 .responses
                 .remove(&token.counter)
@@ -380,44 +478,67 @@ impl TransmissionState {
         }
     }
 
-    fn stop(&mut self, error: &zb_hw::Error) {
+    /// Fail every transmission of a hardware session that can no longer confirm it.
+    ///
+    /// Quarantined counters are released as well, because their late confirmations could only
+    /// have arrived through the closed session's event stream.
+    fn reset(&mut self, error: &zb_hw::Error) {
         for mut pending in std::mem::take(&mut self.responses).into_values() {
             pending.fail(error);
         }
+        self.quarantined.clear();
     }
 }
 
 impl Transceiver {
     /// Create an APS actor with its Zigbee APS counter allocator initialized to zero.
+    ///
+    /// Acknowledged unicasts are fragmented into windows of `window_size` blocks once the NCP has
+    /// reported its maximum payload length.
     #[must_use]
-    pub const fn new(ncp: NcpHandle, inbox: WeakSender<Message>) -> Self {
+    pub const fn new(
+        ncp: NcpHandle,
+        inbox: WeakSender<Message>,
+        transfer_limits: TransferLimits,
+        window_size: NonZeroU8,
+    ) -> Self {
         Self {
             ncp,
             state: TransmissionState::new(),
             inbox,
+            transfer_limits,
+            maximum_payload_length: None,
+            window_size,
         }
     }
 
     /// Run the APS actor.
     pub async fn run(mut self, mut messages: Receiver<Message>) {
+        self.query_maximum_payload_length();
+
         while let Some(message) = messages.recv().await {
-            if !self.handle_actor_message(message) {
-                break;
+            self.handle_actor_message(message);
+
+            for window in self.state.take_windows() {
+                self.spawn_transmission(window.requests, window.token);
             }
         }
     }
 
-    fn handle_actor_message(&mut self, message: Message) -> bool {
+    fn handle_actor_message(&mut self, message: Message) {
         match message {
             Message::Transmit { request, response } => {
                 self.transmit(request, response);
             }
             Message::SubmissionFinished { token, result } => {
                 if self.state.finish_submission(token, result) {
-                    self.schedule_confirmation_timeout(token);
+                    self.schedule_confirmation_timeout(token, self.state.round(token));
                 }
             }
             Message::Confirm { counter, status } => {
+                if status == ConfirmStatus::Aps(ApsStatus::PeerCannotFragment) {
+                    self.reject_fragmentation(counter);
+                }
                 self.state.handle_confirm(counter, status);
             }
             Message::NetworkDown => {
@@ -425,24 +546,33 @@ impl Transceiver {
                     .network_down(&zb_hw::TransmissionError::NoRoute.into());
             }
             Message::HardwareUnavailable => {
-                self.state.stop(&zb_hw::Error::ActorUnavailable);
-                return false;
+                self.state.reset(&zb_hw::Error::ActorUnavailable);
+                self.maximum_payload_length = None;
+                self.query_maximum_payload_length();
             }
             Message::Cancel { token } => {
                 self.state.cancel(token);
             }
-            Message::ConfirmationTimeout { token } => {
-                if self.state.timeout(token) {
+            Message::ConfirmationTimeout { token, round } => {
+                if self.state.timeout(token, round) {
                     warn!(
                         "APS confirmation for counter {} did not arrive before its deadline; \
-                         stopping the APS actor to prevent unsafe counter reuse",
+                         keeping the counter quarantined until a late confirmation or restart",
                         token.counter
                     );
-                    return false;
                 }
             }
+            Message::MaximumPayloadLength { result } => {
+                self.maximum_payload_length = match result {
+                    Ok(length) => Some(length),
+                    Err(zb_hw::Error::Unsupported(_)) => None,
+                    Err(error) => {
+                        warn!("Failed to read the NCP's maximum payload length: {error}");
+                        None
+                    }
+                };
+            }
         }
-        true
     }
 
     /// Assign an APS counter and submit a data-service request to the hardware actor.
@@ -451,6 +581,13 @@ impl Transceiver {
         request: DataRequest<Bytes>,
         response: OneshotSender<Result<TransmissionResponse, crate::Error>>,
     ) {
+        let transfer = match self.fragment(&request) {
+            Ok(transfer) => transfer,
+            Err(error) => {
+                response.send(Err(error)).unwrap_or_else(drop);
+                return;
+            }
+        };
         let acknowledged = acknowledged(&request);
         let Some(token) = self.state.allocate() else {
             response
@@ -465,15 +602,80 @@ impl Transceiver {
             return;
         }
 
-        self.state.store_submission(token, acknowledged, completion);
-        self.spawn_transmission(request, token);
+        let requests = transfer
+            .as_ref()
+            .map_or_else(|| vec![request], Transfer::requests);
+        self.state
+            .store_submission(token, acknowledged, completion, transfer);
+        self.spawn_transmission(requests, token);
     }
 
-    fn spawn_transmission(&self, request: DataRequest<Bytes>, token: TransmissionToken) {
+    /// Check a request against the destination's transfer limit and split it into blocks if it
+    /// exceeds the NCP's maximum payload length.
+    ///
+    /// Only acknowledged unicasts are fragmented. Without a known maximum payload length, requests
+    /// are submitted unchanged.
+    fn fragment(&self, request: &DataRequest<Bytes>) -> Result<Option<Transfer>, crate::Error> {
+        let length = request.asdu_length();
+        let address = network_address(request);
+
+        if let Some(limit) =
+            address.and_then(|address| self.transfer_limits.maximum_incoming(address))
+            && length > limit
+        {
+            return Err(crate::Error::PayloadTooLarge { length, limit });
+        }
+
+        let Some(maximum) = self.maximum_payload_length else {
+            return Ok(None);
+        };
+        if !acknowledged(request) || length <= usize::from(maximum) {
+            return Ok(None);
+        }
+        let Some(block_size) =
+            NonZeroUsize::new(usize::from(maximum.saturating_sub(EXTENDED_HEADER_LENGTH)))
+        else {
+            return Ok(None);
+        };
+        let too_large = crate::Error::PayloadTooLarge {
+            length,
+            limit: usize::from(maximum),
+        };
+
+        if !address.is_none_or(|address| self.transfer_limits.accepts_fragmentation(address)) {
+            return Err(too_large);
+        }
+
+        Transfer::new(request.clone(), block_size, self.window_size)
+            .map(Some)
+            .ok_or(too_large)
+    }
+
+    /// Remember that the destination of a fragmented transmission cannot receive fragments.
+    fn reject_fragmentation(&self, counter: u8) {
+        if let Some(address) = self
+            .state
+            .responses
+            .get(&counter)
+            .and_then(|pending| pending.transfer.as_ref())
+            .and_then(|transfer| network_address(transfer.request()))
+        {
+            self.transfer_limits.reject_fragmentation(address);
+        }
+    }
+
+    /// Submit the requests of one transmission in order, stopping at the first rejection.
+    fn spawn_transmission(&self, requests: Vec<DataRequest<Bytes>>, token: TransmissionToken) {
         let ncp = self.ncp.clone();
         let inbox = self.inbox.clone();
         spawn(async move {
-            let result = ncp.transmit(request, token.counter).await;
+            let mut result = Ok(());
+            for request in requests {
+                result = ncp.transmit(request, token.counter).await;
+                if result.is_err() {
+                    break;
+                }
+            }
             let Some(inbox) = inbox.upgrade() else {
                 return;
             };
@@ -486,7 +688,7 @@ impl Transceiver {
         });
     }
 
-    fn schedule_confirmation_timeout(&self, token: TransmissionToken) {
+    fn schedule_confirmation_timeout(&self, token: TransmissionToken, round: u16) {
         let inbox = self.inbox.clone();
         spawn(async move {
             sleep(CONFIRMATION_TIMEOUT).await;
@@ -494,7 +696,7 @@ impl Transceiver {
                 return;
             };
             inbox
-                .send(Message::ConfirmationTimeout { token })
+                .send(Message::ConfirmationTimeout { token, round })
                 .await
                 .unwrap_or_else(|error| {
                     log::debug!("Failed to enqueue APS confirmation timeout: {error}");
@@ -502,29 +704,63 @@ impl Transceiver {
         });
     }
 
+    /// Ask the NCP for the longest payload it transmits in a single frame.
+    fn query_maximum_payload_length(&self) {
+        let ncp = self.ncp.clone();
+        let inbox = self.inbox.clone();
+        spawn(async move {
+            let result = ncp.get_maximum_payload_length().await;
+            let Some(inbox) = inbox.upgrade() else {
+                return;
+            };
+            inbox
+                .send(Message::MaximumPayloadLength { result })
+                .await
+                .unwrap_or_else(|error| {
+                    log::debug!("Failed to enqueue the NCP's maximum payload length: {error}");
+                });
+        });
+    }
+
     /// Spawn the APS actor.
-    pub fn spawn(ncp: NcpHandle) -> Aps {
-        let (aps_tx, aps_rx) = tokio::sync::mpsc::channel(MPSC_CHANNEL_SIZE);
-        spawn(Self::new(ncp, aps_tx.downgrade()).run(aps_rx));
-        Aps::new(aps_tx)
+    pub fn spawn(ncp: NcpHandle, config: &CoordinatorConfig) -> Aps {
+        let (aps_tx, aps_rx) = tokio::sync::mpsc::channel(config.channel_size().get());
+        let aps = Aps::new(aps_tx);
+        let transceiver = Self::new(
+            ncp,
+            aps.sender.downgrade(),
+            aps.transfer_limits.clone(),
+            config.fragmentation_window(),
+        );
+        spawn(transceiver.run(aps_rx));
+        aps
+    }
+}
+
+/// Return the network address of a request addressed to one device by its network address.
+const fn network_address<T>(request: &DataRequest<T>) -> Option<u16> {
+    match request.destination() {
+        RequestDestination::Network { address, .. } => Some(address.as_u16()),
+        _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::num::NonZeroUsize;
+    use std::num::{NonZeroU8, NonZeroUsize};
     use std::time::Duration;
 
     use bytes::Bytes;
     use tokio::runtime::Runtime;
-    use tokio::sync::mpsc::channel;
+    use tokio::sync::mpsc::{UnboundedSender, channel, unbounded_channel};
     use zb_aps::TxOptions;
     use zb_aps::apsde::{
-        BroadcastAddress, ConfirmStatus, IndividualEndpoint, NetworkAddress, NetworkDestination,
-        RequestDestination, Status as ApsStatus,
+        Block, BroadcastAddress, ConfirmStatus, IndividualEndpoint, NetworkAddress,
+        NetworkDestination, RequestDestination, Status as ApsStatus,
     };
     use zb_core::endpoint::Application;
     use zb_core::short_id::Device as ShortDevice;
+    use zb_core::types::tlv::FragmentationParameters;
     use zb_core::{Endpoint, GroupId, IeeeAddress, Profile, short_id};
     use zb_hw::{
         ChannelMask, Driver, Error as HardwareError, FoundNetwork, Operation, ScanDuration,
@@ -534,8 +770,9 @@ mod tests {
 
     use super::{
         Aps, INITIAL_COUNTER, INITIAL_GENERATION, Message, PendingResponse, Transceiver,
-        TransmissionState, TransmissionToken, acknowledged, data_request,
+        TransmissionState, TransmissionToken, UNFRAGMENTED_ROUND, acknowledged, data_request,
     };
+    use crate::CoordinatorConfig;
     use crate::aps::{Metadata, TransmissionResponse};
 
     const CHANNEL_SIZE: usize = 1;
@@ -545,16 +782,22 @@ mod tests {
     const SECOND_GENERATION: u64 = 1;
     const GROUP_ID: u16 = 0x2345;
     const SECOND_COUNTER: u8 = 2;
+    const THIRD_COUNTER: u8 = 3;
     const PAYLOAD: &[u8] = &[0x12, 0x34];
+    const LARGE_PAYLOAD: &[u8] = &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+    const MAXIMUM_PAYLOAD_LENGTH: u8 = 6;
+    const FRAGMENTATION_WINDOW: NonZeroU8 = NonZeroU8::new(2).expect("window size is non-zero");
     const NCP_CHANNEL_SIZE: NonZeroUsize = NonZeroUsize::MIN;
     const TEST_TIMEOUT: Duration = Duration::from_millis(100);
 
-    #[derive(Debug)]
-    struct DelayedDriver {
+    #[derive(Debug, Default)]
+    struct TestDriver {
         acceptance: Option<tokio::sync::oneshot::Receiver<()>>,
+        maximum_payload_length: Option<u8>,
+        transmissions: Option<UnboundedSender<(zb_aps::apsde::DataRequest<Bytes>, u8)>>,
     }
 
-    impl Driver for DelayedDriver {
+    impl Driver for TestDriver {
         async fn get_endpoints(&self) -> Result<Box<[SimpleDescriptor]>, HardwareError> {
             unsupported(Operation::GetEndpoints)
         }
@@ -607,16 +850,24 @@ mod tests {
 
         async fn transmit(
             &mut self,
-            _request: zb_aps::apsde::DataRequest<Bytes>,
-            _counter: u8,
+            request: zb_aps::apsde::DataRequest<Bytes>,
+            counter: u8,
         ) -> Result<(), HardwareError> {
-            self.acceptance
-                .take()
-                .expect("test driver receives one transmission")
-                .await
-                .expect("test controls backend acceptance");
+            if let Some(acceptance) = self.acceptance.take() {
+                acceptance.await.expect("test controls backend acceptance");
+            }
+            if let Some(transmissions) = &self.transmissions {
+                transmissions
+                    .send((request, counter))
+                    .expect("test records transmissions");
+            }
             Ok(())
         }
+
+        async fn get_maximum_payload_length(&mut self) -> Result<u8, HardwareError> {
+            self.maximum_payload_length
+                .map_or_else(|| unsupported(Operation::GetMaximumPayloadLength), Ok)
+        }
     }
 
     fn unsupported<T>(operation: Operation) -> Result<T, HardwareError> {
@@ -870,12 +1121,13 @@ mod tests {
             .expect("runtime must be available")
             .block_on(async {
                 let (acceptance, accepted) = tokio::sync::oneshot::channel();
-                let (ncp, driver) = DelayedDriver {
+                let (ncp, driver) = TestDriver {
                     acceptance: Some(accepted),
+                    ..TestDriver::default()
                 }
                 .into_actor(NCP_CHANNEL_SIZE);
                 let driver = tokio::spawn(driver);
-                let aps = Transceiver::spawn(ncp);
+                let aps = Transceiver::spawn(ncp, &CoordinatorConfig::new());
                 let transmission = aps
                     .transmit(request(
                         unicast_destination(),
@@ -908,6 +1160,114 @@ mod tests {
             });
     }
 
+    #[test]
+    fn fragments_oversized_unicasts_and_completes_after_the_last_window() {
+        Runtime::new()
+            .expect("runtime must be available")
+            .block_on(async {
+                let (transmissions, mut transmitted) = unbounded_channel();
+                let (ncp, driver) = TestDriver {
+                    maximum_payload_length: Some(MAXIMUM_PAYLOAD_LENGTH),
+                    transmissions: Some(transmissions),
+                    ..TestDriver::default()
+                }
+                .into_actor(NCP_CHANNEL_SIZE);
+                tokio::spawn(driver);
+                let (sender, receiver) = channel(CHANNEL_SIZE);
+                let aps = Aps::new(sender);
+                let mut transceiver = Transceiver::new(
+                    ncp,
+                    aps.sender.downgrade(),
+                    aps.transfer_limits().clone(),
+                    FRAGMENTATION_WINDOW,
+                );
+                transceiver.handle_actor_message(Message::MaximumPayloadLength {
+                    result: Ok(MAXIMUM_PAYLOAD_LENGTH),
+                });
+                tokio::spawn(transceiver.run(receiver));
+
+                let transmission = tokio::spawn(
+                    aps.transmit(request(
+                        unicast_destination(),
+                        TxOptions::ACKNOWLEDGED_TRANSMISSION,
+                        Bytes::from_static(LARGE_PAYLOAD),
+                    ))
+                    .await
+                    .expect("APS actor accepts the transmission"),
+                );
+
+                let mut first_window = Vec::new();
+                for _ in 0..FRAGMENTATION_WINDOW.get() {
+                    first_window.push(transmitted.recv().await.expect("block is transmitted"));
+                }
+                let blocks: Vec<_> = first_window
+                    .iter()
+                    .map(|(request, _)| request.block().map(Block::index))
+                    .collect();
+                assert_eq!(blocks, [Some(0), Some(1)]);
+                assert_eq!(first_window[0].0.asdu().as_ref(), &LARGE_PAYLOAD[..4]);
+                assert!(
+                    !first_window[0]
+                        .0
+                        .tx_options()
+                        .contains(TxOptions::ACKNOWLEDGED_TRANSMISSION)
+                );
+                let counter = first_window[1].1;
+                assert_eq!(first_window[0].1, counter);
+
+                aps.confirm(counter, ConfirmStatus::success())
+                    .await
+                    .expect("APS actor remains available");
+                let (last_block, last_counter) =
+                    transmitted.recv().await.expect("last block is transmitted");
+                assert_eq!(last_counter, counter);
+                assert_eq!(last_block.block().map(Block::index), Some(2));
+                assert_eq!(last_block.asdu().as_ref(), &LARGE_PAYLOAD[8..]);
+                assert!(!transmission.is_finished());
+
+                aps.confirm(counter, ConfirmStatus::success())
+                    .await
+                    .expect("APS actor remains available");
+                tokio::time::timeout(TEST_TIMEOUT, transmission)
+                    .await
+                    .expect("transmission completes after the last window")
+                    .expect("transmission task must not panic")
+                    .expect("every window is confirmed");
+            });
+    }
+
+    #[test]
+    fn rejects_payloads_exceeding_the_destination_transfer_limit() {
+        Runtime::new()
+            .expect("runtime must be available")
+            .block_on(async {
+                let (ncp, driver) = TestDriver::default().into_actor(NCP_CHANNEL_SIZE);
+                tokio::spawn(driver);
+                let aps = Transceiver::spawn(ncp, &CoordinatorConfig::new());
+                aps.transfer_limits()
+                    .learn_fragmentation(FragmentationParameters::new(
+                        DEVICE_ID,
+                        None,
+                        Some(u16::from(MAXIMUM_PAYLOAD_LENGTH)),
+                    ));
+
+                let result = aps
+                    .transmit(request(
+                        unicast_destination(),
+                        TxOptions::ACKNOWLEDGED_TRANSMISSION,
+                        Bytes::from_static(LARGE_PAYLOAD),
+                    ))
+                    .await;
+
+                assert!(matches!(
+                    result,
+                    Err(crate::Error::PayloadTooLarge { length, limit })
+                        if length == LARGE_PAYLOAD.len()
+                            && limit == usize::from(MAXIMUM_PAYLOAD_LENGTH)
+                ));
+            });
+    }
+
     #[test]
     fn backend_acceptance_completes_unacknowledged_transmission() {
         Runtime::new()
@@ -918,7 +1278,7 @@ mod tests {
                 let (response, result) = tokio::sync::oneshot::channel();
                 state.store_pending_response(transmission_token(FIRST_COUNTER), pending_response);
                 let token = transmission_token(SECOND_COUNTER);
-                state.store_submission(token, false, response);
+                state.store_submission(token, false, response, None);
 
                 assert!(!state.finish_submission(token, Ok(())));
 
@@ -935,7 +1295,7 @@ mod tests {
                 let (response, result) = tokio::sync::oneshot::channel();
                 let mut state = TransmissionState::new();
                 let token = transmission_token(FIRST_COUNTER);
-                state.store_submission(token, false, response);
+                state.store_submission(token, false, response, None);
 
                 assert!(
                     !state
@@ -959,7 +1319,7 @@ mod tests {
                 let mut state = TransmissionState::new();
                 let token = transmission_token(FIRST_COUNTER);
                 let (response, mut result) = tokio::sync::oneshot::channel();
-                state.store_submission(token, true, response);
+                state.store_submission(token, true, response, None);
 
                 state.handle_confirm(FIRST_COUNTER, ConfirmStatus::success());
 
@@ -975,7 +1335,7 @@ mod tests {
         let mut state = TransmissionState::new();
         let token = transmission_token(FIRST_COUNTER);
         let (response, _result) = tokio::sync::oneshot::channel();
-        state.store_submission(token, true, response);
+        state.store_submission(token, true, response, None);
 
         state.cancel(token);
 
@@ -994,7 +1354,7 @@ mod tests {
         let mut state = TransmissionState::new();
         let token = transmission_token(FIRST_COUNTER);
         let (response, _result) = tokio::sync::oneshot::channel();
-        state.store_submission(token, false, response);
+        state.store_submission(token, false, response, None);
 
         state.cancel(token);
 
@@ -1011,7 +1371,7 @@ mod tests {
                 let mut state = TransmissionState::new();
                 let token = transmission_token(FIRST_COUNTER);
                 let (response, result) = tokio::sync::oneshot::channel();
-                state.store_submission(token, true, response);
+                state.store_submission(token, true, response, None);
 
                 state.network_down(&zb_hw::TransmissionError::NoRoute.into());
 
@@ -1116,7 +1476,7 @@ mod tests {
         state.handle_confirm(FIRST_COUNTER, ConfirmStatus::success());
 
         assert!(!state.quarantined.contains_key(&FIRST_COUNTER));
-        assert!(!state.timeout(token));
+        assert!(!state.timeout(token, UNFRAGMENTED_ROUND));
         state.next_counter = FIRST_COUNTER;
         assert_eq!(
             state.allocate().map(|allocated| allocated.counter),
@@ -1125,7 +1485,7 @@ mod tests {
     }
 
     #[test]
-    fn missing_confirmation_at_deadline_is_terminal() {
+    fn missing_confirmation_at_deadline_quarantines_the_counter() {
         Runtime::new()
             .expect("runtime must be available")
             .block_on(async {
@@ -1134,7 +1494,7 @@ mod tests {
                 let (response, result) = tokio::sync::oneshot::channel();
                 state.store_pending_response(token, response);
 
-                assert!(state.timeout(token));
+                assert!(state.timeout(token, UNFRAGMENTED_ROUND));
                 assert!(matches!(
                     result.await.expect("response is available"),
                     Err(zb_hw::Error::Transmission(
@@ -1145,14 +1505,14 @@ mod tests {
     }
 
     #[test]
-    fn cancelled_transmission_with_missing_confirmation_is_terminal() {
+    fn cancelled_transmission_with_missing_confirmation_stays_quarantined() {
         let mut state = TransmissionState::new();
         let token = transmission_token(FIRST_COUNTER);
         let (response, _result) = tokio::sync::oneshot::channel();
         state.store_pending_response(token, response);
         state.cancel(token);
 
-        assert!(state.timeout(token));
+        assert!(state.timeout(token, UNFRAGMENTED_ROUND));
     }
 
     #[test]
@@ -1167,7 +1527,7 @@ mod tests {
         state.store_pending_response(current, response);
 
         state.cancel(stale_token);
-        assert!(!state.timeout(stale_token));
+        assert!(!state.timeout(stale_token, UNFRAGMENTED_ROUND));
 
         assert!(state.responses.contains_key(&FIRST_COUNTER));
         assert!(result.try_recv().is_err());
@@ -1185,7 +1545,7 @@ mod tests {
             .quarantined
             .insert(current.counter, current.generation);
 
-        assert!(!state.timeout(stale_token));
+        assert!(!state.timeout(stale_token, UNFRAGMENTED_ROUND));
         assert_eq!(
             state.quarantined.get(&current.counter),
             Some(&current.generation)
@@ -1239,8 +1599,9 @@ mod tests {
                 let (second_response, second_result) = tokio::sync::oneshot::channel();
                 state.store_pending_response(transmission_token(FIRST_COUNTER), first_response);
                 state.store_pending_response(transmission_token(SECOND_COUNTER), second_response);
+                state.quarantined.insert(THIRD_COUNTER, INITIAL_GENERATION);
 
-                state.stop(&zb_hw::Error::ActorUnavailable);
+                state.reset(&zb_hw::Error::ActorUnavailable);
 
                 for result in [first_result, second_result] {
                     assert!(matches!(
@@ -40,13 +40,23 @@ pub enum Message {
 
     /// Check an accepted transmission at its hardware-confirmation deadline.
     ConfirmationTimeout {
-        /// Coordinator-private identity that makes the actor terminal if still unconfirmed.
+        /// Coordinator-private identity whose counter stays quarantined if still unconfirmed.
         token: TransmissionToken,
+        /// Submission round whose confirmation is due; fragmented transmissions submit one round
+        /// per window attempt.
+        round: u16,
     },
 
     /// Fail every pending acknowledged transmission because the network went down.
     NetworkDown,
 
-    /// Fail every pending transmission and stop because hardware events are unavailable.
+    /// Fail every pending transmission and release quarantined counters because the hardware
+    /// session ended.
     HardwareUnavailable,
+
+    /// Report the NCP's maximum payload length, which sizes fragment blocks.
+    MaximumPayloadLength {
+        /// Payload length in octets, or the error that prevented reading it.
+        result: Result<u8, zb_hw::Error>,
+    },
 }
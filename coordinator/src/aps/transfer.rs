@@ -0,0 +1,191 @@
+use std::num::{NonZeroU8, NonZeroUsize};
+
+use bytes::Bytes;
+use zb_aps::TxOptions;
+use zb_aps::apsde::{Block, DataRequest};
+
+/// Number of times a window whose acknowledgement failed is sent again.
+///
+/// This matches the APS constant `apscMaxFrameRetries`.
+const MAX_WINDOW_RETRIES: u8 = 3;
+
+/// An acknowledged unicast whose ASDU is sent as consecutive blocks.
+///
+/// Blocks are submitted in windows. Only the last block of a window requests an APS
+/// acknowledgement, so the destination confirms each window as a whole. A window whose
+/// acknowledgement fails is sent again up to [`MAX_WINDOW_RETRIES`] times.
+#[derive(Debug)]
+pub struct Transfer {
+    request: DataRequest<Bytes>,
+    block_size: NonZeroUsize,
+    blocks: u8,
+    window_size: NonZeroU8,
+    window: u8,
+    retries: u8,
+    round: u16,
+}
+
+impl Transfer {
+    /// Split `request` into blocks of at most `block_size` octets.
+    ///
+    /// Returns `None` if the ASDU needs more blocks than the APS extended header can count.
+    pub fn new(
+        request: DataRequest<Bytes>,
+        block_size: NonZeroUsize,
+        window_size: NonZeroU8,
+    ) -> Option<Self> {
+        let blocks = request
+            .asdu_length()
+            .div_ceil(block_size.get())
+            .try_into()
+            .ok()?;
+        Some(Self {
+            request,
+            block_size,
+            blocks,
+            window_size,
+            window: 0,
+            retries: 0,
+            round: 0,
+        })
+    }
+
+    /// Return how many windows, including retried ones, preceded the current submission.
+    ///
+    /// Confirmation deadlines carry the round so that the deadline of an earlier window cannot
+    /// expire a later one.
+    pub const fn round(&self) -> u16 {
+        self.round
+    }
+
+    /// Return the data requests of the blocks in the current window.
+    pub fn requests(&self) -> Vec<DataRequest<Bytes>> {
+        let first = self.window.saturating_mul(self.window_size.get());
+        let last = first
+            .saturating_add(self.window_size.get())
+            .min(self.blocks);
+
+        (first..last)
+            .map(|index| {
+                let block = Block::new(index, self.blocks).expect("window lies within the blocks");
+                let tx_options = if index + 1 == last {
+                    self.request.tx_options()
+                } else {
+                    self.request.tx_options() - TxOptions::ACKNOWLEDGED_TRANSMISSION
+                };
+                let start = usize::from(index) * self.block_size.get();
+                let end = (start + self.block_size.get()).min(self.request.asdu_length());
+                self.request
+                    .clone()
+                    .map_asdu(|asdu| asdu.slice(start..end))
+                    .with_tx_options(tx_options)
+                    .with_block(block)
+            })
+            .collect()
+    }
+
+    /// Move to the next window and report whether one remains.
+    pub fn advance(&mut self) -> bool {
+        self.window = self.window.saturating_add(1);
+        self.retries = 0;
+        self.round = self.round.wrapping_add(1);
+        usize::from(self.window) * usize::from(self.window_size.get()) < usize::from(self.blocks)
+    }
+
+    /// Prepare the current window to be sent again and report whether retries remain.
+    pub const fn retry(&mut self) -> bool {
+        if self.retries >= MAX_WINDOW_RETRIES {
+            return false;
+        }
+
+        self.retries += 1;
+        self.round = self.round.wrapping_add(1);
+        true
+    }
+
+    /// Return the fragmented request.
+    pub const fn request(&self) -> &DataRequest<Bytes> {
+        &self.request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::{NonZeroU8, NonZeroUsize};
+
+    use bytes::Bytes;
+    use zb_aps::TxOptions;
+    use zb_aps::apsde::{Block, DataRequest, IndividualEndpoint, RequestDestination};
+    use zb_core::endpoint::Application;
+    use zb_core::{Endpoint, Profile};
+
+    use super::{MAX_WINDOW_RETRIES, Transfer};
+
+    const BLOCK_SIZE: NonZeroUsize = NonZeroUsize::new(2).expect("block size is non-zero");
+    const WINDOW_SIZE: NonZeroU8 = NonZeroU8::new(2).expect("window size is non-zero");
+    const PAYLOAD: &[u8] = &[1, 2, 3, 4, 5];
+
+    fn request(payload: &'static [u8]) -> DataRequest<Bytes> {
+        let endpoint = IndividualEndpoint::new(Endpoint::Application(Application::MIN))
+            .expect("application endpoint is individual");
+        DataRequest::new(
+            RequestDestination::Bound,
+            Profile::ZigbeeHomeAutomation.as_u16(),
+            0x0019,
+            endpoint,
+            Bytes::from_static(payload),
+        )
+        .with_tx_options(TxOptions::ACKNOWLEDGED_TRANSMISSION)
+    }
+
+    #[test]
+    fn acknowledges_only_the_last_block_of_each_window() {
+        let mut transfer = Transfer::new(request(PAYLOAD), BLOCK_SIZE, WINDOW_SIZE)
+            .expect("three blocks fit the extended header");
+
+        let first = transfer.requests();
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].asdu().as_ref(), [1, 2]);
+        assert!(
+            !first[0]
+                .tx_options()
+                .contains(TxOptions::ACKNOWLEDGED_TRANSMISSION)
+        );
+        assert!(
+            first[1]
+                .tx_options()
+                .contains(TxOptions::ACKNOWLEDGED_TRANSMISSION)
+        );
+        assert_eq!(
+            first[0].block().map(|block| (block.index(), block.count())),
+            Some((0, 3))
+        );
+
+        assert!(transfer.advance());
+        let second = transfer.requests();
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].asdu().as_ref(), [5]);
+        assert_eq!(second[0].block().map(Block::index), Some(2));
+        assert!(!transfer.advance());
+    }
+
+    #[test]
+    fn retries_a_window_a_bounded_number_of_times() {
+        let mut transfer = Transfer::new(request(PAYLOAD), BLOCK_SIZE, WINDOW_SIZE)
+            .expect("three blocks fit the extended header");
+
+        for _ in 0..MAX_WINDOW_RETRIES {
+            assert!(transfer.retry());
+        }
+        assert!(!transfer.retry());
+        assert_eq!(transfer.round(), u16::from(MAX_WINDOW_RETRIES));
+    }
+
+    #[test]
+    fn rejects_payloads_with_more_blocks_than_the_extended_header_counts() {
+        static LARGE: [u8; 257] = [0; 257];
+        let block_size = NonZeroUsize::MIN;
+
+        assert!(Transfer::new(request(&LARGE), block_size, WINDOW_SIZE).is_none());
+    }
+}
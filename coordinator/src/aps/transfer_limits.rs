@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use zb_core::node::Descriptor;
+use zb_core::types::tlv::{FragmentationOptions, FragmentationParameters};
+
+/// Transfer limits of remote devices, shared by the ZDP actor, which learns them, and the APS
+/// actor, which enforces them.
+///
+/// Limits are keyed by network address and learned from Node Descriptor responses, including
+/// their fragmentation parameters TLV.
+#[derive(Clone, Debug, Default)]
+pub struct TransferLimits(Arc<Mutex<BTreeMap<u16, TransferLimit>>>);
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct TransferLimit {
+    maximum_incoming: Option<u16>,
+    fragmentation: Option<bool>,
+}
+
+impl TransferLimits {
+    /// Record the maximum incoming transfer size advertised by a node descriptor.
+    ///
+    /// A size of zero is treated as unknown.
+    pub fn learn_descriptor(&self, address: u16, descriptor: &Descriptor) {
+        let size = descriptor.maximum_incoming_transfer_size();
+
+        if size != 0 {
+            self.update(address, |limit| limit.maximum_incoming = Some(size));
+        }
+    }
+
+    /// Record the fragmentation support and incoming transfer unit of a fragmentation
+    /// parameters TLV.
+    pub fn learn_fragmentation(&self, parameters: FragmentationParameters) {
+        let unit = parameters
+            .maximum_incoming_transfer_unit()
+            .filter(|&unit| unit != 0);
+        let fragmentation = parameters
+            .options()
+            .map(|options| options.contains(FragmentationOptions::FRAGMENTATION_SUPPORTED));
+        self.update(parameters.node_id(), |limit| {
+            limit.maximum_incoming = unit.or(limit.maximum_incoming);
+            limit.fragmentation = fragmentation.or(limit.fragmentation);
+        });
+    }
+
+    /// Record that a device rejected a fragmented transmission.
+    pub fn reject_fragmentation(&self, address: u16) {
+        self.update(address, |limit| limit.fragmentation = Some(false));
+    }
+
+    /// Return the largest ASDU, in octets, that the device at `address` accepts, if known.
+    pub fn maximum_incoming(&self, address: u16) -> Option<usize> {
+        self.lock()
+            .get(&address)
+            .and_then(|limit| limit.maximum_incoming)
+            .map(usize::from)
+    }
+
+    /// Return whether the device at `address` may receive fragmented transmissions.
+    ///
+    /// Devices are assumed to support fragmentation until they report otherwise.
+    pub fn accepts_fragmentation(&self, address: u16) -> bool {
+        self.lock()
+            .get(&address)
+            .and_then(|limit| limit.fragmentation)
+            .unwrap_or(true)
+    }
+
+    fn update<F>(&self, address: u16, update: F)
+    where
+        F: FnOnce(&mut TransferLimit),
+    {
+        update(self.lock().entry(address).or_default());
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<u16, TransferLimit>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zb_core::types::tlv::{FragmentationOptions, FragmentationParameters};
+
+    use super::TransferLimits;
+
+    const ADDRESS: u16 = 0x1234;
+    const TRANSFER_UNIT: u16 = 300;
+
+    #[test]
+    fn fragmentation_parameters_set_the_limit_and_support() {
+        let limits = TransferLimits::default();
+        assert!(limits.accepts_fragmentation(ADDRESS));
+        assert_eq!(limits.maximum_incoming(ADDRESS), None);
+
+        limits.learn_fragmentation(FragmentationParameters::new(
+            ADDRESS,
+            Some(FragmentationOptions::empty()),
+            Some(TRANSFER_UNIT),
+        ));
+
+        assert!(!limits.accepts_fragmentation(ADDRESS));
+        assert_eq!(
+            limits.maximum_incoming(ADDRESS),
+            Some(usize::from(TRANSFER_UNIT))
+        );
+    }
+}
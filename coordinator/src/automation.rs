@@ -0,0 +1,40 @@
+//! Occupancy and illuminance driven automation.
+//!
+//! An [`Engine`] evaluates [`Rule`]s on the coordinator itself. Each rule switches an APS group on
+//! when its occupancy sensor reports occupied and, optionally, its illuminance sensor measures less
+//! than a threshold. It switches the group off once the sensor reports unoccupied and the rule's
+//! additional off delay has elapsed. Rules are serializable, so they can be stored alongside the
+//! application's configuration:
+//!
+//! ```no_run
+//! use apis_saltans_coordinator::automation::{Engine, Rule};
+//! use apis_saltans_coordinator::{Coordinator, Error};
+//! use zb_aps::apsde::IndividualEndpoint;
+//!
+//! async fn automate(
+//!     coordinator: &Coordinator,
+//!     source_endpoint: IndividualEndpoint,
+//!     configuration: &str,
+//! ) -> Result<(), Error> {
+//!     let rules: Vec<Rule> = serde_json::from_str(configuration).expect("valid rules");
+//!     let automation = Engine::new(source_endpoint)
+//!         .with_rules(rules)
+//!         .spawn(coordinator)
+//!         .await?;
+//!
+//!     println!("evaluating {} rules", automation.rules().len());
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Sensors must be configured to report `Occupancy` and `MeasuredValue`, for example through
+//! [`Attributes::configure_reporting`](crate::Attributes::configure_reporting). The engine observes
+//! their reports without consuming them, so they are still delivered as
+//! [`Event::Zcl`](crate::Event::Zcl).
+
+pub use self::engine::{Automation, Engine};
+pub use self::rule::{Darkness, Rule, Sensor};
+
+mod engine;
+mod rule;
+mod state;
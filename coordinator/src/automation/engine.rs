@@ -0,0 +1,390 @@
+use log::{debug, warn};
+use tokio::spawn;
+use tokio::sync::mpsc::{Receiver, Sender, WeakSender, channel};
+use tokio::task::AbortHandle;
+use tokio::time::sleep;
+use zb_aps::apsde::{BroadcastAddress, IndividualEndpoint, RequestDestination, Source};
+use zb_core::{Cluster, Direction, GroupId, IeeeAddress, short_id};
+use zb_zcl::{Cluster as ZclCluster, Scope, global, illuminance_measurement, occupancy_sensing};
+
+use super::Rule;
+use super::state::{Action, State};
+use crate::{AddressTranslation, Coordinator, Error, MPSC_CHANNEL_SIZE, OnOff, zcl};
+
+/// Input of the rule task.
+#[derive(Debug)]
+enum Input {
+    Report(zcl::SubscriptionMessage),
+    OffDelayElapsed { rule: usize, generation: u64 },
+}
+
+/// Occupancy automation rules evaluated on the coordinator.
+///
+/// The engine sends group commands from the configured local source endpoint.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Engine {
+    source_endpoint: IndividualEndpoint,
+    rules: Vec<Rule>,
+}
+
+impl Engine {
+    /// Create an engine without rules that sends from `source_endpoint`.
+    #[must_use]
+    pub const fn new(source_endpoint: IndividualEndpoint) -> Self {
+        Self {
+            source_endpoint,
+            rules: Vec::new(),
+        }
+    }
+
+    /// Add a rule.
+    #[must_use]
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Add several rules, such as those loaded from a configuration document.
+    #[must_use]
+    pub fn with_rules<T>(mut self, rules: T) -> Self
+    where
+        T: IntoIterator<Item = Rule>,
+    {
+        self.rules.extend(rules);
+        self
+    }
+
+    /// Start evaluating the rules on the reports received by `coordinator`.
+    ///
+    /// Rules start unoccupied and without illuminance readings. Evaluation stops when the returned
+    /// [`Automation`] is dropped; groups that were switched on stay on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the coordinator's ZCL actor is unavailable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub async fn spawn(self, coordinator: &Coordinator) -> Result<Automation, Error> {
+        let (inputs, inbox) = channel(MPSC_CHANNEL_SIZE);
+        let mut tasks = Vec::new();
+
+        for cluster in [Cluster::OccupancySensing, Cluster::IlluminanceMeasurement] {
+            let (subscription, mut reports) = zcl::Subscription::observer(
+                zcl::SubscriptionFilter::new(cluster, Scope::Global, Direction::ServerToClient),
+            );
+            coordinator
+                .zcl
+                .send(zcl::Message::Subscribe { subscription })
+                .await?;
+
+            let inputs = inputs.downgrade();
+            tasks.push(
+                spawn(async move {
+                    while let Some(report) = reports.recv().await {
+                        let Some(inputs) = inputs.upgrade() else {
+                            return;
+                        };
+                        if inputs.send(Input::Report(report)).await.is_err() {
+                            return;
+                        }
+                    }
+                })
+                .abort_handle(),
+            );
+        }
+
+        let rules = self.rules.clone();
+        tasks.push(
+            spawn(
+                Task {
+                    coordinator: coordinator.clone(),
+                    source_endpoint: self.source_endpoint,
+                    state: State::new(self.rules),
+                    inputs: inputs.downgrade(),
+                }
+                .run(inputs, inbox),
+            )
+            .abort_handle(),
+        );
+
+        Ok(Automation { rules, tasks })
+    }
+}
+
+/// Running rules started by [`Engine::spawn`].
+///
+/// Dropping the handle stops evaluation.
+#[derive(Debug)]
+pub struct Automation {
+    rules: Vec<Rule>,
+    tasks: Vec<AbortHandle>,
+}
+
+impl Automation {
+    /// Return the evaluated rules.
+    #[must_use]
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+}
+
+impl Drop for Automation {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// Rule task applying reports and off delays to the rule state.
+struct Task {
+    coordinator: Coordinator,
+    source_endpoint: IndividualEndpoint,
+    state: State,
+    inputs: WeakSender<Input>,
+}
+
+impl Task {
+    /// Process inputs until the handle is dropped.
+    ///
+    /// The strong sender keeps the inbox open while only off-delay timers, which hold weak
+    /// senders, are pending.
+    async fn run(mut self, _inputs: Sender<Input>, mut inbox: Receiver<Input>) {
+        while let Some(input) = inbox.recv().await {
+            let actions = match input {
+                Input::Report(report) => {
+                    match self.resolve(report.indication.metadata().source()).await {
+                        Some(device) => apply(&mut self.state, device, report),
+                        None => Vec::new(),
+                    }
+                }
+                Input::OffDelayElapsed { rule, generation } => self
+                    .state
+                    .off_delay_elapsed(rule, generation)
+                    .into_iter()
+                    .collect(),
+            };
+
+            for action in actions {
+                self.execute(action).await;
+            }
+        }
+    }
+
+    /// Resolve the IEEE address of a frame's network source through the NCP's address table.
+    async fn resolve(&self, source: Source) -> Option<IeeeAddress> {
+        let Source::Network { address, .. } = source else {
+            return None;
+        };
+        let short_id = short_id::Device::try_from(address.as_u16()).ok()?;
+        self.coordinator
+            .short_id_to_ieee_address(short_id)
+            .await
+            .inspect_err(|error| {
+                debug!("Automation failed to resolve the IEEE address of {short_id}: {error}");
+            })
+            .ok()
+    }
+
+    async fn execute(&self, action: Action) {
+        let (group, result) = match action {
+            Action::On(group) => (
+                group,
+                self.coordinator
+                    .on(destination(group), self.source_endpoint)
+                    .await,
+            ),
+            Action::Off(group) => (
+                group,
+                self.coordinator
+                    .off(destination(group), self.source_endpoint)
+                    .await,
+            ),
+            Action::ScheduleOff {
+                rule,
+                generation,
+                delay,
+            } => {
+                let inputs = self.inputs.clone();
+                spawn(async move {
+                    sleep(delay).await;
+                    if let Some(inputs) = inputs.upgrade() {
+                        let _result = inputs
+                            .send(Input::OffDelayElapsed { rule, generation })
+                            .await;
+                    }
+                });
+                return;
+            }
+        };
+
+        match result {
+            Ok(()) => debug!("Automation switched group {group}: {action:?}"),
+            Err(error) => warn!("Automation failed to switch group {group}: {error}"),
+        }
+    }
+}
+
+/// Apply the occupancy and illuminance attributes of a Report Attributes command sent by `device`.
+fn apply(state: &mut State, device: IeeeAddress, report: zcl::SubscriptionMessage) -> Vec<Action> {
+    let (metadata, frame) = report.indication.into_parts();
+    let Source::Network { endpoint, .. } = metadata.source() else {
+        return Vec::new();
+    };
+    let Some(sensor) = state.sensor(device, endpoint.get()) else {
+        return Vec::new();
+    };
+    let ZclCluster::Global(global::Command::ReportAttributes(command)) = frame.into_payload()
+    else {
+        return Vec::new();
+    };
+
+    let cluster = metadata.cluster_id();
+    let mut actions = Vec::new();
+    for report in command.into_reports() {
+        let parts = report.into_parts();
+
+        if cluster == Cluster::OccupancySensing.as_u16() {
+            if let Ok(occupancy_sensing::Reportable::Occupancy(occupancy)) =
+                occupancy_sensing::Reportable::try_from(parts)
+            {
+                actions.extend(state.occupancy(sensor, occupancy.into()));
+            }
+        } else if cluster == Cluster::IlluminanceMeasurement.as_u16()
+            && let Ok(illuminance_measurement::Reportable::MeasuredValue(measured)) =
+                illuminance_measurement::Reportable::try_from(parts)
+        {
+            actions.extend(state.illuminance(sensor, lux(measured)));
+        }
+    }
+    actions
+}
+
+/// Return the illuminance of a measured value in lux, or `None` for an invalid measurement.
+///
+/// A measured value of zero is too low to be measured and counts as darkness.
+fn lux(measured: illuminance_measurement::MeasuredValue) -> Option<u32> {
+    match measured.raw_value()? {
+        0 => Some(0),
+        _ => measured.lux().map(illuminance_measurement::Lux::into_inner),
+    }
+}
+
+const fn destination(group: GroupId) -> RequestDestination {
+    RequestDestination::Group {
+        address: group,
+        broadcast_address: BroadcastAddress::new(short_id::Broadcast::RxOnWhenIdle.as_u16())
+            .expect("rx-on-when-idle is a valid APSDE group broadcast selector"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zb_aps::apsde::{
+        DataIndication, IndicationMetadata, IndicationStatus, IndividualEndpoint, NetworkAddress,
+        ReceivedDestination, Security, Source,
+    };
+    use zb_core::{Application, Endpoint, GroupId, IeeeAddress};
+    use zb_zcl::Frame;
+
+    use super::apply;
+    use crate::automation::state::{Action, State};
+    use crate::automation::{Rule, Sensor};
+    use crate::zcl;
+
+    const OCCUPANCY_SENSOR: IeeeAddress = IeeeAddress::new(0x00, 0x12, 0x4B, 0, 0, 0, 0x12, 0x34);
+    const LIGHT_SENSOR: IeeeAddress = IeeeAddress::new(0x00, 0x12, 0x4B, 0, 0, 0, 0x56, 0x78);
+    const SENSOR_ADDRESS: u16 = 0x1234;
+    const OCCUPANCY_SENSING: u16 = 0x0406;
+    const ILLUMINANCE_MEASUREMENT: u16 = 0x0400;
+
+    const fn sensor(device: IeeeAddress) -> Sensor {
+        Sensor::new(device, Application::MIN)
+    }
+
+    fn group() -> GroupId {
+        GroupId::new(0x0010).expect("test group is valid")
+    }
+
+    fn report(cluster_id: u16, asdu: &[u8]) -> zcl::SubscriptionMessage {
+        let endpoint = IndividualEndpoint::new(Endpoint::Application(Application::MIN))
+            .expect("application endpoint is individual");
+        let metadata = IndicationMetadata::new(
+            ReceivedDestination::Network {
+                address: NetworkAddress::new(0x0000).expect("coordinator address is valid"),
+                endpoint,
+            },
+            Source::Network {
+                address: NetworkAddress::new(SENSOR_ADDRESS).expect("test address is valid"),
+                endpoint,
+            },
+            0x0104,
+            cluster_id,
+            IndicationStatus::success(),
+            Security::NetworkKey,
+            255,
+            (),
+        );
+        let frame = Frame::parse(cluster_id, asdu.iter().copied()).expect("report must parse");
+
+        zcl::SubscriptionMessage {
+            indication: DataIndication::new(metadata, frame),
+        }
+    }
+
+    #[test]
+    fn applies_reports_of_rule_sensors() {
+        let mut state = State::new(vec![
+            Rule::new(sensor(OCCUPANCY_SENSOR), group())
+                .with_illuminance_below(sensor(LIGHT_SENSOR), 50),
+        ]);
+
+        // 20 lx is reported as 10 000 * log10(20) + 1 = 13 011.
+        assert!(
+            apply(
+                &mut state,
+                LIGHT_SENSOR,
+                report(
+                    ILLUMINANCE_MEASUREMENT,
+                    &[0x18, 0x01, 0x0A, 0x00, 0x00, 0x21, 0xD3, 0x32],
+                ),
+            )
+            .is_empty()
+        );
+        assert!(
+            apply(
+                &mut state,
+                LIGHT_SENSOR,
+                report(
+                    OCCUPANCY_SENSING,
+                    &[0x18, 0x02, 0x0A, 0x00, 0x00, 0x18, 0x01],
+                ),
+            )
+            .is_empty()
+        );
+        assert_eq!(
+            apply(
+                &mut state,
+                OCCUPANCY_SENSOR,
+                report(
+                    OCCUPANCY_SENSING,
+                    &[0x18, 0x03, 0x0A, 0x00, 0x00, 0x18, 0x01],
+                ),
+            ),
+            vec![Action::On(group())]
+        );
+        assert_eq!(
+            apply(
+                &mut state,
+                OCCUPANCY_SENSOR,
+                report(
+                    OCCUPANCY_SENSING,
+                    &[0x18, 0x04, 0x0A, 0x00, 0x00, 0x18, 0x00],
+                ),
+            ),
+            vec![Action::Off(group())]
+        );
+    }
+}
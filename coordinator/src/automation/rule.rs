@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+use zb_core::{Application, Endpoint, GroupId, IeeeAddress};
+
+/// Sensor endpoint whose reports drive a [`Rule`].
+///
+/// Sensors are identified by their IEEE address, so rules keep matching after a device rejoins
+/// with a new NWK short address.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub struct Sensor {
+    device: IeeeAddress,
+    endpoint: Application,
+}
+
+impl Sensor {
+    /// Create a sensor from the device's IEEE address and its application endpoint.
+    #[must_use]
+    pub const fn new(device: IeeeAddress, endpoint: Application) -> Self {
+        Self { device, endpoint }
+    }
+
+    /// Return the sensor device's IEEE address.
+    #[must_use]
+    pub const fn device(self) -> IeeeAddress {
+        self.device
+    }
+
+    /// Return the sensor's application endpoint.
+    #[must_use]
+    pub const fn endpoint(self) -> Application {
+        self.endpoint
+    }
+
+    /// Return whether a frame from `endpoint` of `device` was sent by this sensor.
+    pub(super) fn is_source(self, device: IeeeAddress, endpoint: Endpoint) -> bool {
+        device == self.device && endpoint == Endpoint::Application(self.endpoint)
+    }
+}
+
+/// Illuminance condition of a [`Rule`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Darkness {
+    sensor: Sensor,
+    below_lux: u32,
+}
+
+impl Darkness {
+    /// Create a condition that holds while `sensor` measures less than `below_lux`.
+    #[must_use]
+    pub const fn new(sensor: Sensor, below_lux: u32) -> Self {
+        Self { sensor, below_lux }
+    }
+
+    /// Return the illuminance sensor.
+    #[must_use]
+    pub const fn sensor(self) -> Sensor {
+        self.sensor
+    }
+
+    /// Return the illuminance, in lux, below which the condition holds.
+    #[must_use]
+    pub const fn below_lux(self) -> u32 {
+        self.below_lux
+    }
+}
+
+/// Occupancy-driven light rule.
+///
+/// When the occupancy sensor reports occupied and the optional illuminance condition holds, the
+/// rule sends On/Off On to its group. Once the sensor reports unoccupied, which it does only after
+/// its own occupied-to-unoccupied delay, the rule waits its additional off delay and sends
+/// On/Off Off. Rules serialize to and from plain configuration documents.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Rule {
+    occupancy: Sensor,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    darkness: Option<Darkness>,
+    group: GroupId,
+    #[serde(default)]
+    off_delay: u16,
+}
+
+impl Rule {
+    /// Create a rule that switches `group` by the reports of the `occupancy` sensor.
+    #[must_use]
+    pub const fn new(occupancy: Sensor, group: GroupId) -> Self {
+        Self {
+            occupancy,
+            darkness: None,
+            group,
+            off_delay: 0,
+        }
+    }
+
+    /// Turn the group on only while the illuminance sensor measures less than `below_lux`.
+    #[must_use]
+    pub const fn with_illuminance_below(mut self, sensor: Sensor, below_lux: u32) -> Self {
+        self.darkness = Some(Darkness::new(sensor, below_lux));
+        self
+    }
+
+    /// Wait `seconds` after the sensor reports unoccupied before turning the group off.
+    #[must_use]
+    pub const fn with_off_delay(mut self, seconds: u16) -> Self {
+        self.off_delay = seconds;
+        self
+    }
+
+    /// Return the occupancy sensor.
+    #[must_use]
+    pub const fn occupancy(self) -> Sensor {
+        self.occupancy
+    }
+
+    /// Return the illuminance condition, if any.
+    #[must_use]
+    pub const fn darkness(self) -> Option<Darkness> {
+        self.darkness
+    }
+
+    /// Return the switched group.
+    #[must_use]
+    pub const fn group(self) -> GroupId {
+        self.group
+    }
+
+    /// Return the additional off delay in seconds.
+    #[must_use]
+    pub const fn off_delay(self) -> u16 {
+        self.off_delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zb_core::{Application, GroupId, IeeeAddress};
+
+    use super::{Rule, Sensor};
+
+    fn sensor(device: u8) -> Sensor {
+        Sensor::new(
+            IeeeAddress::new(0x00, 0x12, 0x4B, 0x00, 0x00, 0x00, 0x00, device),
+            Application::MIN,
+        )
+    }
+
+    #[test]
+    fn serializes_rules_as_plain_configuration() {
+        let rule = Rule::new(
+            sensor(0x34),
+            GroupId::new(0x0010).expect("test group is valid"),
+        )
+        .with_illuminance_below(sensor(0x78), 50)
+        .with_off_delay(30);
+
+        let json = serde_json::to_value(rule).expect("rule serializes");
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "occupancy": { "device": "00:12:4B:00:00:00:00:34", "endpoint": 1 },
+                "darkness": {
+                    "sensor": { "device": "00:12:4B:00:00:00:00:78", "endpoint": 1 },
+                    "below_lux": 50
+                },
+                "group": 0x0010,
+                "off_delay": 30
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<Rule>(json).expect("rule deserializes"),
+            rule
+        );
+        assert_eq!(
+            serde_json::from_str::<Rule>(
+                r#"{
+                    "occupancy": { "device": "00:12:4B:00:00:00:00:34", "endpoint": 1 },
+                    "group": 16
+                }"#
+            )
+            .expect("optional fields default"),
+            Rule::new(
+                sensor(0x34),
+                GroupId::new(0x0010).expect("test group is valid")
+            )
+        );
+    }
+}
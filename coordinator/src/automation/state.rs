@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use zb_core::{Endpoint, GroupId, IeeeAddress};
+
+use super::{Darkness, Rule, Sensor};
+
+/// Command decided by the rule state machine.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum Action {
+    /// Send On/Off On to the group.
+    On(GroupId),
+    /// Send On/Off Off to the group.
+    Off(GroupId),
+    /// Call [`State::off_delay_elapsed`] with the rule index and generation after the delay.
+    ScheduleOff {
+        rule: usize,
+        generation: u64,
+        delay: Duration,
+    },
+}
+
+/// Runtime state of one rule.
+#[derive(Clone, Copy, Debug, Default)]
+struct Progress {
+    occupied: bool,
+    lit: bool,
+    /// Invalidates scheduled Off actions whenever the occupancy changes.
+    generation: u64,
+}
+
+/// Sensor readings and rule progress, independent of transmission and timers.
+#[derive(Debug)]
+pub(super) struct State {
+    rules: Vec<(Rule, Progress)>,
+    illuminance: BTreeMap<Sensor, u32>,
+}
+
+impl State {
+    pub(super) fn new(rules: Vec<Rule>) -> Self {
+        Self {
+            rules: rules
+                .into_iter()
+                .map(|rule| (rule, Progress::default()))
+                .collect(),
+            illuminance: BTreeMap::new(),
+        }
+    }
+
+    /// Return the sensor of any rule that sent a frame from `endpoint` of `device`.
+    pub(super) fn sensor(&self, device: IeeeAddress, endpoint: Endpoint) -> Option<Sensor> {
+        self.rules
+            .iter()
+            .flat_map(|(rule, _)| {
+                [
+                    Some(rule.occupancy()),
+                    rule.darkness().map(Darkness::sensor),
+                ]
+            })
+            .flatten()
+            .find(|sensor| sensor.is_source(device, endpoint))
+    }
+
+    /// Apply an occupancy report of `sensor`.
+    pub(super) fn occupancy(&mut self, sensor: Sensor, occupied: bool) -> Vec<Action> {
+        let mut actions = Vec::new();
+
+        for (index, (rule, progress)) in self.rules.iter_mut().enumerate() {
+            if rule.occupancy() != sensor {
+                continue;
+            }
+            let changed = progress.occupied != occupied;
+            if changed {
+                progress.occupied = occupied;
+                progress.generation = progress.generation.wrapping_add(1);
+            }
+
+            if occupied {
+                if !progress.lit && is_dark(&self.illuminance, rule.darkness()) {
+                    progress.lit = true;
+                    actions.push(Action::On(rule.group()));
+                }
+            } else if changed && progress.lit {
+                if rule.off_delay() == 0 {
+                    progress.lit = false;
+                    actions.push(Action::Off(rule.group()));
+                } else {
+                    actions.push(Action::ScheduleOff {
+                        rule: index,
+                        generation: progress.generation,
+                        delay: Duration::from_secs(rule.off_delay().into()),
+                    });
+                }
+            }
+        }
+
+        actions
+    }
+
+    /// Apply an illuminance report of `sensor`, where `None` marks an invalid measurement.
+    ///
+    /// Rules that are occupied but were too bright to switch on are re-evaluated. Rising
+    /// illuminance never turns a group off, since the switched lights themselves raise it.
+    pub(super) fn illuminance(&mut self, sensor: Sensor, lux: Option<u32>) -> Vec<Action> {
+        match lux {
+            Some(lux) => self.illuminance.insert(sensor, lux),
+            None => self.illuminance.remove(&sensor),
+        };
+
+        self.rules
+            .iter_mut()
+            .filter(|(rule, progress)| {
+                progress.occupied
+                    && !progress.lit
+                    && rule
+                        .darkness()
+                        .is_some_and(|darkness| darkness.sensor() == sensor)
+                    && is_dark(&self.illuminance, rule.darkness())
+            })
+            .map(|(rule, progress)| {
+                progress.lit = true;
+                Action::On(rule.group())
+            })
+            .collect()
+    }
+
+    /// Complete the off delay of a rule, unless its occupancy changed since it was scheduled.
+    pub(super) fn off_delay_elapsed(&mut self, rule: usize, generation: u64) -> Option<Action> {
+        let (rule, progress) = self.rules.get_mut(rule)?;
+        if progress.generation != generation || progress.occupied || !progress.lit {
+            return None;
+        }
+        progress.lit = false;
+        Some(Action::Off(rule.group()))
+    }
+}
+
+/// Return whether the illuminance condition holds.
+///
+/// A condition whose sensor has not reported a valid measurement yet holds, so that a missing
+/// reading does not keep the lights off.
+fn is_dark(illuminance: &BTreeMap<Sensor, u32>, darkness: Option<Darkness>) -> bool {
+    darkness.is_none_or(|darkness| {
+        illuminance
+            .get(&darkness.sensor())
+            .is_none_or(|lux| *lux < darkness.below_lux())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use zb_core::{Application, GroupId, IeeeAddress};
+
+    use super::{Action, State};
+    use crate::automation::{Rule, Sensor};
+
+    fn sensor(device: u8) -> Sensor {
+        Sensor::new(
+            IeeeAddress::new(0x00, 0x12, 0x4B, 0x00, 0x00, 0x00, 0x00, device),
+            Application::MIN,
+        )
+    }
+
+    fn group() -> GroupId {
+        GroupId::new(0x0010).expect("test group is valid")
+    }
+
+    #[test]
+    fn switches_on_in_the_dark_and_off_after_the_delay() {
+        let rule = Rule::new(sensor(1), group())
+            .with_illuminance_below(sensor(2), 50)
+            .with_off_delay(30);
+        let mut state = State::new(vec![rule]);
+
+        assert!(state.illuminance(sensor(2), Some(400)).is_empty());
+        assert!(state.occupancy(sensor(1), true).is_empty());
+        assert_eq!(
+            state.illuminance(sensor(2), Some(20)),
+            vec![Action::On(group())]
+        );
+        assert!(state.occupancy(sensor(1), true).is_empty());
+
+        let [
+            Action::ScheduleOff {
+                rule: 0,
+                generation,
+                delay,
+            },
+        ] = state.occupancy(sensor(1), false)[..]
+        else {
+            panic!("unoccupied rule must schedule its off delay");
+        };
+        assert_eq!(delay, Duration::from_secs(30));
+        assert_eq!(
+            state.off_delay_elapsed(0, generation),
+            Some(Action::Off(group()))
+        );
+        assert_eq!(state.off_delay_elapsed(0, generation), None);
+    }
+
+    #[test]
+    fn renewed_occupancy_cancels_the_off_delay() {
+        let mut state = State::new(vec![Rule::new(sensor(1), group()).with_off_delay(30)]);
+
+        assert_eq!(state.occupancy(sensor(1), true), vec![Action::On(group())]);
+        let [Action::ScheduleOff { generation, .. }] = state.occupancy(sensor(1), false)[..] else {
+            panic!("unoccupied rule must schedule its off delay");
+        };
+        assert!(state.occupancy(sensor(1), true).is_empty());
+        assert_eq!(state.off_delay_elapsed(0, generation), None);
+    }
+
+    #[test]
+    fn stays_off_while_bright_and_turns_off_immediately_without_delay() {
+        let mut state = State::new(vec![
+            Rule::new(sensor(1), group()).with_illuminance_below(sensor(2), 50),
+        ]);
+
+        assert!(state.illuminance(sensor(2), Some(50)).is_empty());
+        assert!(state.occupancy(sensor(1), true).is_empty());
+        assert!(state.occupancy(sensor(1), false).is_empty());
+
+        assert!(state.illuminance(sensor(2), None).is_empty());
+        assert_eq!(state.occupancy(sensor(1), true), vec![Action::On(group())]);
+        assert!(state.illuminance(sensor(2), Some(800)).is_empty());
+        assert_eq!(
+            state.occupancy(sensor(1), false),
+            vec![Action::Off(group())]
+        );
+    }
+}
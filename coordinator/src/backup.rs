@@ -0,0 +1,225 @@
+//! Portable network backups.
+//!
+//! [`to_json`] and [`from_json`] convert a [`NetworkBackup`] exported by
+//! [`Network::backup_network`](crate::api::Network::backup_network) to and from version 1 of the
+//! Open Coordinator Backup format, which other Zigbee stacks read and write as well. A backup
+//! taken from one backend can therefore be restored on another with
+//! [`Network::restore_network`](crate::api::Network::restore_network), provided both backends
+//! implement network backup and restore. Of the bundled backends, only the simulator does so far;
+//! the EZSP and ZNP backends report an unsupported operation.
+//!
+//! The document stores identifiers as lowercase hexadecimal strings in display order:
+//!
+//! ```json
+//! {
+//!   "metadata": {
+//!     "format": "zigpy/open-coordinator-backup",
+//!     "version": 1,
+//!     "source": "apis-saltans-coordinator@0.14.0",
+//!     "internal": {}
+//!   },
+//!   "coordinator_ieee": "00124b0001aabbcc",
+//!   "pan_id": "1a62",
+//!   "extended_pan_id": "dddddddddddddddd",
+//!   "nwk_update_id": 0,
+//!   "security_level": 5,
+//!   "channel": 15,
+//!   "channel_mask": [15],
+//!   "network_key": {
+//!     "key": "01030507090b0d0f00020406080a0c0d",
+//!     "sequence_number": 0,
+//!     "frame_counter": 4096
+//!   },
+//!   "devices": [
+//!     {
+//!       "nwk_address": "1234",
+//!       "ieee_address": "00158d0001020304",
+//!       "is_child": true,
+//!       "link_key": {
+//!         "key": "5a6967426565416c6c69616e63653039",
+//!         "tx_counter": 0,
+//!         "rx_counter": 0
+//!       }
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Stack-specific sections and unknown fields of imported documents are ignored. Documents of
+//! networks with a security level other than 5, AES-128-CCM* with 32-bit MICs, are rejected.
+
+pub use zb_hw::{BackedUpDevice, LinkKeyState, NetworkBackup, NetworkKeyState};
+
+use self::document::Document;
+pub use self::error::BackupError;
+
+mod document;
+mod error;
+
+/// Identifier of the Open Coordinator Backup format.
+pub const FORMAT: &str = "zigpy/open-coordinator-backup";
+
+/// Version of the Open Coordinator Backup format written and read by this module.
+pub const VERSION: u32 = 1;
+
+/// Serialize a network backup to an Open Coordinator Backup JSON document.
+///
+/// # Errors
+///
+/// Returns a [`BackupError`] if the JSON serializer fails.
+pub fn to_json(backup: &NetworkBackup) -> Result<String, BackupError> {
+    Ok(serde_json::to_string_pretty(&Document::from(backup))?)
+}
+
+/// Parse a network backup from an Open Coordinator Backup JSON document.
+///
+/// # Errors
+///
+/// Returns a [`BackupError`] if the document is not valid JSON, is not a version 1 Open
+/// Coordinator Backup, or contains an invalid field.
+pub fn from_json(json: &str) -> Result<NetworkBackup, BackupError> {
+    serde_json::from_str::<Document>(json)?.try_into()
+}
+
+#[cfg(test)]
+mod tests {
+    use zb_core::IeeeAddress;
+    use zb_core::security::Key;
+    use zb_core::short_id::Device;
+    use zb_hw::{Channel, ChannelMask, NetworkParameters};
+
+    use super::{
+        BackedUpDevice, BackupError, LinkKeyState, NetworkBackup, NetworkKeyState, from_json,
+        to_json,
+    };
+
+    const COORDINATOR: IeeeAddress =
+        IeeeAddress::new(0x00, 0x12, 0x4B, 0x00, 0x01, 0xAA, 0xBB, 0xCC);
+    const DEVICE: IeeeAddress = IeeeAddress::new(0x00, 0x15, 0x8D, 0x00, 0x01, 0x02, 0x03, 0x04);
+
+    fn backup() -> NetworkBackup {
+        let channel = Channel::new(15).expect("channel 15 is valid");
+
+        NetworkBackup::new(
+            COORDINATOR,
+            NetworkParameters::new(
+                0x1A62,
+                IeeeAddress::new(0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD),
+                channel,
+            ),
+            NetworkKeyState::new(Key::new([0xAB; Key::SIZE]), 1, 4096),
+        )
+        .with_channel_mask(
+            ChannelMask::new(0x0210_8800).expect("channels 11, 15, 20, and 25 are valid"),
+        )
+        .with_nwk_update_id(2)
+        .with_device(
+            BackedUpDevice::new(DEVICE)
+                .with_short_id(Device::new(0x1234).expect("short ID is valid"))
+                .with_child(true)
+                .with_link_key(LinkKeyState::new(Key::DEFAULT_TRUST_CENTER_LINK_KEY, 3, 7)),
+        )
+        .with_device(BackedUpDevice::new(IeeeAddress::new(
+            0, 0, 0, 0, 0, 0, 0, 1,
+        )))
+    }
+
+    #[test]
+    fn round_trips_backups() {
+        let backup = backup();
+
+        assert_eq!(
+            from_json(&to_json(&backup).expect("backup serializes")).expect("document is valid"),
+            backup
+        );
+    }
+
+    #[test]
+    fn writes_open_coordinator_backup_fields() {
+        let document: serde_json::Value =
+            serde_json::from_str(&to_json(&backup()).expect("backup serializes"))
+                .expect("document is valid JSON");
+
+        assert_eq!(
+            document["metadata"]["format"],
+            "zigpy/open-coordinator-backup"
+        );
+        assert_eq!(document["metadata"]["version"], 1);
+        assert_eq!(document["coordinator_ieee"], "00124b0001aabbcc");
+        assert_eq!(document["pan_id"], "1a62");
+        assert_eq!(document["channel"], 15);
+        assert_eq!(
+            document["channel_mask"],
+            serde_json::json!([11, 15, 20, 25])
+        );
+        assert_eq!(
+            document["network_key"]["key"],
+            "abababababababababababababababab"
+        );
+        assert_eq!(document["network_key"]["frame_counter"], 4096);
+        assert_eq!(document["devices"][0]["nwk_address"], "1234");
+        assert_eq!(
+            document["devices"][0]["link_key"]["key"],
+            "5a6967426565416c6c69616e63653039"
+        );
+        assert!(document["devices"][1]["nwk_address"].is_null());
+    }
+
+    #[test]
+    fn reads_documents_of_other_stacks() {
+        let backup = from_json(
+            r#"{
+                "metadata": {
+                    "format": "zigpy/open-coordinator-backup",
+                    "version": 1,
+                    "source": "zigbee-herdsman@0.50.0",
+                    "internal": {"date": "2024-01-01T00:00:00.000Z"}
+                },
+                "stack_specific": {"zstack": {"tclk_seed": "00000000000000000000000000000000"}},
+                "coordinator_ieee": "00124b0001aabbcc",
+                "pan_id": "1A62",
+                "extended_pan_id": "dddddddddddddddd",
+                "nwk_update_id": 0,
+                "security_level": 5,
+                "channel": 11,
+                "channel_mask": [11],
+                "network_key": {
+                    "key": "01030507090b0d0f00020406080a0c0d",
+                    "sequence_number": 0,
+                    "frame_counter": 0
+                },
+                "devices": [
+                    {"nwk_address": null, "ieee_address": "00158d0001020304", "is_child": false}
+                ]
+            }"#,
+        )
+        .expect("document is valid");
+
+        assert_eq!(backup.ieee_address(), COORDINATOR);
+        assert_eq!(backup.parameters().pan_id(), 0x1A62);
+        assert_eq!(backup.devices(), [BackedUpDevice::new(DEVICE)]);
+    }
+
+    #[test]
+    fn rejects_other_versions_and_invalid_fields() {
+        let json = to_json(&backup()).expect("backup serializes");
+
+        assert!(matches!(
+            from_json(&json.replace("\"version\": 1", "\"version\": 2")),
+            Err(BackupError::UnsupportedVersion(2))
+        ));
+        assert!(matches!(
+            from_json(&json.replace("\"1a62\"", "\"1a6\"")),
+            Err(BackupError::InvalidField("pan_id"))
+        ));
+        assert!(matches!(
+            from_json(&json.replace("\"channel\": 15", "\"channel\": 27")),
+            Err(BackupError::InvalidField("channel"))
+        ));
+        assert!(matches!(
+            from_json(&json.replace("\"security_level\": 5", "\"security_level\": 0")),
+            Err(BackupError::InvalidField("security_level"))
+        ));
+        assert!(matches!(from_json("{}"), Err(BackupError::Json(_))));
+    }
+}
@@ -0,0 +1,221 @@
+//! Serde representation of Open Coordinator Backup documents.
+
+use le_stream::FromLeStream;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use zb_core::IeeeAddress;
+use zb_core::short_id::Device;
+use zb_hw::{
+    BackedUpDevice, Channel, ChannelMask, LinkKeyState, NetworkBackup, NetworkKeyState,
+    NetworkParameters,
+};
+
+use super::{BackupError, FORMAT, VERSION};
+
+/// Security level of networks using AES-128-CCM* encryption with 32-bit MICs.
+const SECURITY_LEVEL: u8 = 5;
+
+/// Name and version of this crate, recorded as the source of written documents.
+const SOURCE: &str = concat!(env!("CARGO_PKG_NAME"), "@", env!("CARGO_PKG_VERSION"));
+
+#[derive(Deserialize, Serialize)]
+pub struct Document {
+    metadata: Metadata,
+    coordinator_ieee: String,
+    pan_id: String,
+    extended_pan_id: String,
+    nwk_update_id: u8,
+    security_level: u8,
+    channel: u8,
+    channel_mask: Vec<u8>,
+    network_key: NetworkKey,
+    devices: Vec<DeviceEntry>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct Metadata {
+    format: String,
+    version: u32,
+    source: String,
+    #[serde(default)]
+    internal: Map<String, Value>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct NetworkKey {
+    key: String,
+    sequence_number: u8,
+    frame_counter: u32,
+}
+
+#[derive(Deserialize, Serialize)]
+struct DeviceEntry {
+    nwk_address: Option<String>,
+    ieee_address: String,
+    is_child: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    link_key: Option<LinkKey>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct LinkKey {
+    key: String,
+    tx_counter: u32,
+    rx_counter: u32,
+}
+
+impl From<&NetworkBackup> for Document {
+    fn from(backup: &NetworkBackup) -> Self {
+        let parameters = backup.parameters();
+        let network_key = backup.network_key();
+
+        Self {
+            metadata: Metadata {
+                format: FORMAT.to_owned(),
+                version: VERSION,
+                source: SOURCE.to_owned(),
+                internal: Map::new(),
+            },
+            coordinator_ieee: ieee_address_to_hex(backup.ieee_address()),
+            pan_id: format!("{:04x}", parameters.pan_id()),
+            extended_pan_id: ieee_address_to_hex(parameters.extended_pan_id()),
+            nwk_update_id: backup.nwk_update_id(),
+            security_level: SECURITY_LEVEL,
+            channel: parameters.channel().as_u8(),
+            channel_mask: backup
+                .channel_mask()
+                .channels()
+                .map(Channel::as_u8)
+                .collect(),
+            network_key: NetworkKey {
+                key: format!("{:x}", network_key.key()),
+                sequence_number: network_key.sequence_number(),
+                frame_counter: network_key.frame_counter(),
+            },
+            devices: backup.devices().iter().map(DeviceEntry::from).collect(),
+        }
+    }
+}
+
+impl TryFrom<Document> for NetworkBackup {
+    type Error = BackupError;
+
+    fn try_from(document: Document) -> Result<Self, Self::Error> {
+        if document.metadata.format != FORMAT {
+            return Err(BackupError::UnsupportedFormat(document.metadata.format));
+        }
+
+        if document.metadata.version != VERSION {
+            return Err(BackupError::UnsupportedVersion(document.metadata.version));
+        }
+
+        if document.security_level != SECURITY_LEVEL {
+            return Err(BackupError::InvalidField("security_level"));
+        }
+
+        let parameters = NetworkParameters::new(
+            u16_from_hex(&document.pan_id).ok_or(BackupError::InvalidField("pan_id"))?,
+            ieee_address_from_hex(&document.extended_pan_id)
+                .ok_or(BackupError::InvalidField("extended_pan_id"))?,
+            Channel::new(document.channel).ok_or(BackupError::InvalidField("channel"))?,
+        );
+        let network_key = NetworkKeyState::new(
+            document
+                .network_key
+                .key
+                .parse()
+                .map_err(|_| BackupError::InvalidField("network_key"))?,
+            document.network_key.sequence_number,
+            document.network_key.frame_counter,
+        );
+        let channel_mask = document
+            .channel_mask
+            .into_iter()
+            .map(Channel::new)
+            .collect::<Option<ChannelMask>>()
+            .ok_or(BackupError::InvalidField("channel_mask"))?;
+
+        document.devices.into_iter().try_fold(
+            Self::new(
+                ieee_address_from_hex(&document.coordinator_ieee)
+                    .ok_or(BackupError::InvalidField("coordinator_ieee"))?,
+                parameters,
+                network_key,
+            )
+            .with_channel_mask(channel_mask)
+            .with_nwk_update_id(document.nwk_update_id),
+            |backup, device| Ok(backup.with_device(device.try_into()?)),
+        )
+    }
+}
+
+impl From<&BackedUpDevice> for DeviceEntry {
+    fn from(device: &BackedUpDevice) -> Self {
+        Self {
+            nwk_address: device
+                .short_id()
+                .map(|short_id| format!("{:04x}", short_id.as_u16())),
+            ieee_address: ieee_address_to_hex(device.ieee_address()),
+            is_child: device.is_child(),
+            link_key: device.link_key().map(|link_key| LinkKey {
+                key: format!("{:x}", link_key.key()),
+                tx_counter: link_key.tx_counter(),
+                rx_counter: link_key.rx_counter(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<DeviceEntry> for BackedUpDevice {
+    type Error = BackupError;
+
+    fn try_from(entry: DeviceEntry) -> Result<Self, Self::Error> {
+        let mut device = Self::new(
+            ieee_address_from_hex(&entry.ieee_address)
+                .ok_or(BackupError::InvalidField("ieee_address"))?,
+        )
+        .with_child(entry.is_child);
+
+        if let Some(nwk_address) = entry.nwk_address {
+            device = device.with_short_id(
+                u16_from_hex(&nwk_address)
+                    .and_then(Device::new)
+                    .ok_or(BackupError::InvalidField("nwk_address"))?,
+            );
+        }
+
+        if let Some(link_key) = entry.link_key {
+            device = device.with_link_key(LinkKeyState::new(
+                link_key
+                    .key
+                    .parse()
+                    .map_err(|_| BackupError::InvalidField("link_key"))?,
+                link_key.tx_counter,
+                link_key.rx_counter,
+            ));
+        }
+
+        Ok(device)
+    }
+}
+
+/// Return the address as 16 lowercase hexadecimal digits in display order.
+fn ieee_address_to_hex(address: IeeeAddress) -> String {
+    address.to_string().replace(':', "").to_ascii_lowercase()
+}
+
+/// Parse 16 hexadecimal digits in display order.
+fn ieee_address_from_hex(text: &str) -> Option<IeeeAddress> {
+    let address = is_hex(text, 16).then(|| u64::from_str_radix(text, 16).ok())??;
+    IeeeAddress::from_le_stream(address.to_le_bytes().into_iter())
+}
+
+/// Parse four hexadecimal digits.
+fn u16_from_hex(text: &str) -> Option<u16> {
+    is_hex(text, 4).then(|| u16::from_str_radix(text, 16).ok())?
+}
+
+/// Return whether the text consists of exactly `digits` hexadecimal digits.
+fn is_hex(text: &str, digits: usize) -> bool {
+    text.len() == digits && text.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
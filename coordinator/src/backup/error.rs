@@ -0,0 +1,20 @@
+//! Errors of network backup documents.
+
+use thiserror::Error;
+
+/// Error returned when writing or parsing a network backup document.
+#[derive(Debug, Error)]
+pub enum BackupError {
+    /// The document is not valid JSON or lacks a required field, or serialization failed.
+    #[error("invalid backup document: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The document is not an Open Coordinator Backup.
+    #[error("unsupported backup format {0:?}")]
+    UnsupportedFormat(String),
+    /// The document uses an unsupported version of the format.
+    #[error("unsupported backup format version {0}")]
+    UnsupportedVersion(u32),
+    /// A field holds a value outside its valid range or with an invalid encoding.
+    #[error("invalid backup field {0:?}")]
+    InvalidField(&'static str),
+}
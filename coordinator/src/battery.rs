@@ -0,0 +1,37 @@
+//! Battery monitoring of sleepy and battery-powered devices.
+//!
+//! A [`Monitor`] observes the Power Configuration reports received by the coordinator without
+//! consuming them, so applications still receive them as [`Event::Zcl`](crate::Event::Zcl).
+//! Devices are enrolled per endpoint through a running [`Monitoring`]: mains-powered devices are
+//! skipped, while battery-powered devices are configured to report `BatteryPercentageRemaining`
+//! and `BatteryVoltage`. Readings are normalized into a [`Level`], estimated from the voltage for
+//! devices that do not report a percentage, and every change of the threshold [`State`] is
+//! reported as a [`BatteryEvent`](crate::BatteryEvent):
+//!
+//! ```no_run
+//! use apis_saltans_coordinator::battery::{Enrollment, Monitor};
+//! use apis_saltans_coordinator::{Coordinator, Error};
+//! use zb_aps::apsde::{IndividualEndpoint, NetworkDestination};
+//!
+//! async fn monitor(
+//!     coordinator: &Coordinator,
+//!     device: NetworkDestination,
+//!     source_endpoint: IndividualEndpoint,
+//! ) -> Result<(), Error> {
+//!     let monitoring = Monitor::new()
+//!         .with_low_threshold(15)
+//!         .spawn(coordinator)
+//!         .await?;
+//!
+//!     if let Enrollment::Monitored(status) = monitoring.enroll(device, source_endpoint).await? {
+//!         println!("{:?}: {:?}", status.level().map(|level| level.percent()), status.state());
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+pub use self::level::{Level, State, Status};
+pub use self::monitor::{Enrollment, Monitor, Monitoring};
+
+mod level;
+mod monitor;
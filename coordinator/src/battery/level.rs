@@ -0,0 +1,158 @@
+use zb_core::types::Uint8;
+
+const FULL_HALF_PERCENT: u8 = 200;
+const VOLTAGE_UNIT_MILLIVOLTS: u16 = 100;
+
+/// Remaining battery charge with half-percent resolution.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Level {
+    half_percent: u8,
+}
+
+impl Level {
+    /// An empty battery.
+    pub const EMPTY: Self = Self { half_percent: 0 };
+
+    /// A full battery.
+    pub const FULL: Self = Self {
+        half_percent: FULL_HALF_PERCENT,
+    };
+
+    /// Create a level from a whole percentage, saturating at 100 %.
+    #[must_use]
+    pub const fn from_percent(percent: u8) -> Self {
+        Self::from_half_percent(percent.saturating_mul(2))
+    }
+
+    /// Create a level from a `BatteryPercentageRemaining` value in half-percent units.
+    ///
+    /// Returns `None` for the attribute's non-value. Values above 100 % are clamped.
+    #[must_use]
+    pub const fn from_percentage_remaining(value: Uint8) -> Option<Self> {
+        match value.as_option() {
+            Some(half_percent) => Some(Self::from_half_percent(half_percent)),
+            None => None,
+        }
+    }
+
+    /// Estimate a level from a `BatteryVoltage` value in units of 100 mV.
+    ///
+    /// The voltage is interpolated linearly between `empty_millivolts` and `full_millivolts`.
+    /// Returns `None` for the attribute's non-value.
+    #[must_use]
+    pub fn from_voltage(value: Uint8, empty_millivolts: u16, full_millivolts: u16) -> Option<Self> {
+        let millivolts = u16::from(value.as_option()?) * VOLTAGE_UNIT_MILLIVOLTS;
+        if millivolts <= empty_millivolts {
+            return Some(Self::EMPTY);
+        }
+        if millivolts >= full_millivolts {
+            return Some(Self::FULL);
+        }
+        let charged = u32::from(millivolts - empty_millivolts) * u32::from(FULL_HALF_PERCENT);
+        let half_percent = charged / u32::from(full_millivolts - empty_millivolts);
+        Some(Self::from_half_percent(
+            u8::try_from(half_percent).unwrap_or(FULL_HALF_PERCENT),
+        ))
+    }
+
+    const fn from_half_percent(half_percent: u8) -> Self {
+        Self {
+            half_percent: if half_percent > FULL_HALF_PERCENT {
+                FULL_HALF_PERCENT
+            } else {
+                half_percent
+            },
+        }
+    }
+
+    /// Return the level in half-percent units, from 0 to 200.
+    #[must_use]
+    pub const fn half_percent(self) -> u8 {
+        self.half_percent
+    }
+
+    /// Return the level as a percentage from 0 to 100.
+    #[must_use]
+    pub fn percent(self) -> f32 {
+        f32::from(self.half_percent) / 2.0
+    }
+}
+
+/// Battery state of a monitored device, ordered by severity.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum State {
+    /// The level is above the low-battery threshold, or has not been reported yet.
+    #[default]
+    Normal,
+    /// The level is at or below the low-battery threshold.
+    Low,
+    /// The level is at or below the critical-battery threshold.
+    Critical,
+}
+
+/// Latest battery readings of a monitored device.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Status {
+    pub(super) level: Option<Level>,
+    pub(super) voltage: Option<u16>,
+    pub(super) state: State,
+    /// Whether the level was reported as `BatteryPercentageRemaining` rather than estimated.
+    pub(super) percentage_reported: bool,
+}
+
+impl Status {
+    /// Return the normalized battery level.
+    ///
+    /// The level is taken from `BatteryPercentageRemaining` once the device has reported it, and
+    /// is estimated from `BatteryVoltage` otherwise.
+    #[must_use]
+    pub const fn level(self) -> Option<Level> {
+        self.level
+    }
+
+    /// Return the last reported battery voltage in millivolts.
+    #[must_use]
+    pub const fn voltage_millivolts(self) -> Option<u16> {
+        self.voltage
+    }
+
+    /// Return the threshold state of the battery level.
+    #[must_use]
+    pub const fn state(self) -> State {
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zb_core::types::Uint8;
+
+    use super::Level;
+
+    #[test]
+    fn normalizes_half_percent_and_voltage_readings() {
+        assert_eq!(
+            Level::from_percentage_remaining(Uint8::new(41)).map(Level::percent),
+            Some(20.5)
+        );
+        assert_eq!(
+            Level::from_percentage_remaining(Uint8::new(250)),
+            Some(Level::FULL)
+        );
+        assert_eq!(Level::from_percentage_remaining(Uint8::NONE), None);
+
+        assert_eq!(
+            Level::from_voltage(Uint8::new(27), 2100, 3000).map(Level::half_percent),
+            Some(133)
+        );
+        assert_eq!(
+            Level::from_voltage(Uint8::new(20), 2100, 3000),
+            Some(Level::EMPTY)
+        );
+        assert_eq!(
+            Level::from_voltage(Uint8::new(31), 2100, 3000),
+            Some(Level::FULL)
+        );
+        assert_eq!(Level::from_voltage(Uint8::NONE, 2100, 3000), None);
+    }
+}
@@ -0,0 +1,609 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use log::{debug, trace, warn};
+use tokio::spawn;
+use tokio::task::AbortHandle;
+use zb_aps::apsde::{IndividualEndpoint, NetworkDestination, Source};
+use zb_core::types::Uint8;
+use zb_core::{Cluster, Direction, short_id};
+use zb_zcl::power_configuration::{Id, Readable, Reportable, SendReport};
+use zb_zcl::{Analog, Cluster as ZclCluster, Scope, global};
+
+use super::{Level, State, Status};
+use crate::event::{BatteryEvent, EventSink};
+use crate::{Attributes, Coordinator, Error, Event, Node, zcl};
+
+const DEFAULT_LOW_THRESHOLD: u8 = 20;
+const DEFAULT_CRITICAL_THRESHOLD: u8 = 5;
+/// Margin in half-percent units by which a level must exceed a threshold to leave its state.
+const HYSTERESIS: u8 = 4;
+const DEFAULT_MINIMUM_REPORTING_INTERVAL: u16 = 3600;
+const DEFAULT_MAXIMUM_REPORTING_INTERVAL: u16 = 21600;
+/// Reportable change of `BatteryPercentageRemaining`: one percent.
+const PERCENTAGE_REPORTABLE_CHANGE: u8 = 2;
+/// Reportable change of `BatteryVoltage`: 100 mV.
+const VOLTAGE_REPORTABLE_CHANGE: u8 = 1;
+const DEFAULT_EMPTY_MILLIVOLTS: u16 = 2100;
+const DEFAULT_FULL_MILLIVOLTS: u16 = 3000;
+
+/// Configuration of battery monitoring.
+///
+/// The defaults report a low battery at 20 % and a critical battery at 5 %, request reports at
+/// most hourly and at least every six hours, and estimate the level of devices that only report
+/// their voltage for a 3 V lithium cell.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Monitor {
+    low_threshold: Level,
+    critical_threshold: Level,
+    minimum_reporting_interval: u16,
+    maximum_reporting_interval: u16,
+    empty_millivolts: u16,
+    full_millivolts: u16,
+}
+
+impl Monitor {
+    /// Create a battery monitor with the default configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            low_threshold: Level::from_percent(DEFAULT_LOW_THRESHOLD),
+            critical_threshold: Level::from_percent(DEFAULT_CRITICAL_THRESHOLD),
+            minimum_reporting_interval: DEFAULT_MINIMUM_REPORTING_INTERVAL,
+            maximum_reporting_interval: DEFAULT_MAXIMUM_REPORTING_INTERVAL,
+            empty_millivolts: DEFAULT_EMPTY_MILLIVOLTS,
+            full_millivolts: DEFAULT_FULL_MILLIVOLTS,
+        }
+    }
+
+    /// Report a low battery once the level falls to `percent` or below.
+    #[must_use]
+    pub const fn with_low_threshold(mut self, percent: u8) -> Self {
+        self.low_threshold = Level::from_percent(percent);
+        self
+    }
+
+    /// Report a critical battery once the level falls to `percent` or below.
+    #[must_use]
+    pub const fn with_critical_threshold(mut self, percent: u8) -> Self {
+        self.critical_threshold = Level::from_percent(percent);
+        self
+    }
+
+    /// Select the reporting intervals, in seconds, configured on enrolled devices.
+    #[must_use]
+    pub const fn with_reporting_intervals(mut self, minimum: u16, maximum: u16) -> Self {
+        self.minimum_reporting_interval = minimum;
+        self.maximum_reporting_interval = maximum;
+        self
+    }
+
+    /// Select the voltages of an empty and a full battery, used to estimate the level of devices
+    /// that do not report `BatteryPercentageRemaining`.
+    #[must_use]
+    pub const fn with_voltage_range(mut self, empty_millivolts: u16, full_millivolts: u16) -> Self {
+        self.empty_millivolts = empty_millivolts;
+        self.full_millivolts = full_millivolts;
+        self
+    }
+
+    /// Start monitoring the battery reports received by `coordinator`.
+    ///
+    /// Devices are monitored once enrolled through [`Monitoring::enroll`]. Monitoring stops when
+    /// the returned [`Monitoring`] is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the coordinator's ZCL actor is unavailable.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    pub async fn spawn(self, coordinator: &Coordinator) -> Result<Monitoring, Error> {
+        let (subscription, mut reports) =
+            zcl::Subscription::observer(zcl::SubscriptionFilter::new(
+                Cluster::PowerConfiguration,
+                Scope::Global,
+                Direction::ServerToClient,
+            ));
+        coordinator
+            .zcl
+            .send(zcl::Message::Subscribe { subscription })
+            .await?;
+
+        let shared = Arc::new(Shared {
+            monitor: self,
+            devices: Mutex::new(BTreeMap::new()),
+            events: coordinator.events.clone(),
+        });
+        let task = {
+            let shared = shared.clone();
+
+            spawn(async move {
+                while let Some(received) = reports.recv().await {
+                    shared.received(received);
+                }
+            })
+            .abort_handle()
+        };
+
+        Ok(Monitoring {
+            coordinator: coordinator.clone(),
+            shared,
+            task,
+        })
+    }
+
+    /// Return the state of `level`, given the state of the previous reading.
+    ///
+    /// A lower level takes effect immediately, while a device only returns to a less severe state
+    /// once its level exceeds the threshold by two percent.
+    fn state(&self, level: Level, previous: State) -> State {
+        let classify = |half_percent: u8| {
+            if half_percent <= self.critical_threshold.half_percent() {
+                State::Critical
+            } else if half_percent <= self.low_threshold.half_percent() {
+                State::Low
+            } else {
+                State::Normal
+            }
+        };
+
+        let current = classify(level.half_percent());
+        if current >= previous {
+            current
+        } else {
+            classify(level.half_percent().saturating_sub(HYSTERESIS))
+        }
+    }
+
+    fn voltage_level(&self, voltage: Uint8) -> Option<Level> {
+        Level::from_voltage(voltage, self.empty_millivolts, self.full_millivolts)
+    }
+
+    const fn reporting(&self) -> [SendReport; 2] {
+        [
+            SendReport::BatteryPercentageRemaining(Analog::new(
+                self.minimum_reporting_interval,
+                self.maximum_reporting_interval,
+                Uint8::new(PERCENTAGE_REPORTABLE_CHANGE),
+            )),
+            SendReport::BatteryVoltage(Analog::new(
+                self.minimum_reporting_interval,
+                self.maximum_reporting_interval,
+                Uint8::new(VOLTAGE_REPORTABLE_CHANGE),
+            )),
+        ]
+    }
+}
+
+impl Default for Monitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Result of enrolling a device endpoint for battery monitoring.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Enrollment {
+    /// The device is mains powered and is not monitored.
+    MainsPowered,
+    /// The device is battery powered and its endpoint is monitored.
+    Monitored(Status),
+}
+
+/// A running battery monitor started by [`Monitor::spawn`].
+///
+/// Dropping the handle stops monitoring.
+#[derive(Debug)]
+pub struct Monitoring {
+    coordinator: Coordinator,
+    shared: Arc<Shared>,
+    task: AbortHandle,
+}
+
+impl Monitoring {
+    /// Enroll a device's Power Configuration cluster endpoint.
+    ///
+    /// The device's node descriptor decides whether it is battery powered. Battery-powered devices
+    /// are configured to report `BatteryPercentageRemaining` and `BatteryVoltage`, and both
+    /// attributes are read once. Attributes that the device does not support are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the node descriptor cannot be read or the attribute reporting
+    /// cannot be configured.
+    pub async fn enroll(
+        &self,
+        destination: NetworkDestination,
+        source_endpoint: IndividualEndpoint,
+    ) -> Result<Enrollment, Error> {
+        let device = short_id::Device::new(destination.address().as_u16()).ok_or(
+            Error::InvalidZclCommunicationDestination(destination.into()),
+        )?;
+        let descriptor = self.coordinator.descriptor(device, None).await?;
+        if descriptor.mac_capability_flags().is_mains_power() {
+            self.shared.lock().remove(&destination);
+            return Ok(Enrollment::MainsPowered);
+        }
+
+        self.shared.lock().entry(destination).or_default();
+        if let Err(error) = self.configure(destination, source_endpoint).await {
+            self.shared.lock().remove(&destination);
+            return Err(error);
+        }
+
+        match self
+            .coordinator
+            .read(
+                destination,
+                source_endpoint,
+                [Id::BatteryPercentageRemaining, Id::BatteryVoltage],
+            )
+            .await
+        {
+            Ok(attributes) => {
+                for attribute in attributes.into_iter().filter_map(Result::ok) {
+                    match attribute {
+                        Readable::BatteryPercentageRemaining(value) => {
+                            self.shared.percentage(destination, value);
+                        }
+                        Readable::BatteryVoltage(value) => {
+                            self.shared.voltage(destination, value);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Err(error) => debug!("Failed to read the battery of {destination:?}: {error}"),
+        }
+
+        Ok(Enrollment::Monitored(
+            self.status(destination).unwrap_or_default(),
+        ))
+    }
+
+    /// Stop monitoring a device endpoint.
+    ///
+    /// Returns whether the endpoint was monitored. The device keeps sending reports.
+    #[must_use]
+    pub fn remove(&self, destination: NetworkDestination) -> bool {
+        self.shared.lock().remove(&destination).is_some()
+    }
+
+    /// Return the latest readings of a monitored device endpoint.
+    #[must_use]
+    pub fn status(&self, destination: NetworkDestination) -> Option<Status> {
+        self.shared.lock().get(&destination).copied()
+    }
+
+    /// Return the latest readings of every monitored device endpoint.
+    #[must_use]
+    pub fn statuses(&self) -> Vec<(NetworkDestination, Status)> {
+        self.shared
+            .lock()
+            .iter()
+            .map(|(destination, status)| (*destination, *status))
+            .collect()
+    }
+
+    async fn configure(
+        &self,
+        destination: NetworkDestination,
+        source_endpoint: IndividualEndpoint,
+    ) -> Result<(), Error> {
+        let response = self
+            .coordinator
+            .configure_reporting(
+                destination,
+                source_endpoint,
+                self.shared.monitor.reporting(),
+            )
+            .await?
+            .await?;
+
+        for status in response.status() {
+            if status.status() != u8::from(zb_zcl::Status::Success) {
+                debug!(
+                    "{destination:?} rejected battery reporting of attribute {:?}: {:#04X}",
+                    status.attribute_id(),
+                    status.status()
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Monitoring {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// State shared between a [`Monitoring`] handle and its report task.
+#[derive(Debug)]
+struct Shared {
+    monitor: Monitor,
+    devices: Mutex<BTreeMap<NetworkDestination, Status>>,
+    events: EventSink,
+}
+
+impl Shared {
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<NetworkDestination, Status>> {
+        self.devices.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Apply the battery attributes of a received Report Attributes command.
+    fn received(&self, received: zcl::SubscriptionMessage) {
+        let (metadata, frame) = received.indication.into_parts();
+        let Source::Network { address, endpoint } = metadata.source() else {
+            return;
+        };
+        let ZclCluster::Global(global::Command::ReportAttributes(command)) = frame.into_payload()
+        else {
+            return;
+        };
+        let destination = NetworkDestination::new(address, endpoint);
+        if !self.lock().contains_key(&destination) {
+            trace!("Ignoring battery report of unmonitored {destination:?}");
+            return;
+        }
+
+        for report in command.into_reports() {
+            match Reportable::try_from(report.into_parts()) {
+                Ok(Reportable::BatteryPercentageRemaining(value)) => {
+                    self.percentage(destination, value);
+                }
+                Ok(Reportable::BatteryVoltage(value)) => self.voltage(destination, value),
+                Ok(_) => {}
+                Err(error) => warn!("Invalid battery report from {destination:?}: {error}"),
+            }
+        }
+    }
+
+    fn percentage(&self, destination: NetworkDestination, value: Uint8) {
+        let Some(level) = Level::from_percentage_remaining(value) else {
+            return;
+        };
+        self.update(destination, |status| {
+            status.level = Some(level);
+            status.percentage_reported = true;
+            true
+        });
+    }
+
+    fn voltage(&self, destination: NetworkDestination, value: Uint8) {
+        let Some(decivolts) = value.as_option() else {
+            return;
+        };
+        let level = self.monitor.voltage_level(value);
+        self.update(destination, |status| {
+            status.voltage = Some(u16::from(decivolts) * 100);
+            if status.percentage_reported {
+                return false;
+            }
+            status.level = level;
+            true
+        });
+    }
+
+    /// Update the readings of a monitored device and report a changed battery state.
+    ///
+    /// `apply` returns whether the update replaced the device's battery level.
+    fn update<F>(&self, destination: NetworkDestination, apply: F)
+    where
+        F: FnOnce(&mut Status) -> bool,
+    {
+        let event = {
+            let mut devices = self.lock();
+            let Some(status) = devices.get_mut(&destination) else {
+                return;
+            };
+            let previous = status.state;
+            if !apply(status) {
+                return;
+            }
+            let Some(level) = status.level else {
+                return;
+            };
+            status.state = self.monitor.state(level, previous);
+            let state = status.state;
+            drop(devices);
+            (state != previous).then(|| BatteryEvent::new(destination, level, previous, state))
+        };
+
+        if let Some(event) = event {
+            self.events.emit(Event::Battery(event));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use tokio::runtime::Builder;
+    use tokio::sync::mpsc::channel;
+    use zb_aps::apsde::{IndividualEndpoint, NetworkAddress, NetworkDestination};
+    use zb_core::node::{Descriptor, Flags, MacCapabilityFlags, ServerMask};
+    use zb_core::short_id::Device;
+    use zb_core::types::Uint8;
+    use zb_core::{Application, Endpoint, IeeeAddress, Profile};
+    use zb_hw::Driver;
+    use zb_hw::sim::{VirtualDevice, VirtualNetwork};
+    use zb_zdp::{AppFlags, Clusters, SimpleDescriptor};
+
+    use super::{Enrollment, Monitor};
+    use crate::battery::{Level, State};
+    use crate::{Coordinator, CoordinatorConfig, Event};
+
+    const CAPACITY: NonZeroUsize = NonZeroUsize::new(16).expect("capacity is non-zero");
+    const BATTERY_SHORT_ID: u16 = 0x1234;
+    const MAINS_SHORT_ID: u16 = 0x5678;
+    const POWER_CONFIGURATION: u16 = 0x0001;
+    const BATTERY_VOLTAGE: u16 = 0x0020;
+    const BATTERY_PERCENTAGE_REMAINING: u16 = 0x0021;
+    const ENDPOINT: Endpoint = Endpoint::Application(Application::MIN);
+
+    fn device(ieee_address: IeeeAddress, short_id: u16) -> VirtualDevice {
+        VirtualDevice::new(
+            ieee_address,
+            Device::new(short_id).expect("test short ID is valid"),
+        )
+        .with_endpoint(SimpleDescriptor::new(
+            ENDPOINT,
+            Profile::ZigbeeHomeAutomation,
+            0x0402,
+            AppFlags::empty(),
+            Clusters::from_slice(&[POWER_CONFIGURATION]).expect("one cluster fits"),
+            Clusters::new(),
+        ))
+        .with_attribute(
+            ENDPOINT,
+            POWER_CONFIGURATION,
+            BATTERY_VOLTAGE,
+            Uint8::new(29),
+        )
+    }
+
+    fn network() -> VirtualNetwork {
+        VirtualNetwork::new(IeeeAddress::new(0, 0, 0, 0, 0, 0, 0, 0xAA), 0x1A62)
+            .with_device(
+                device(IeeeAddress::new(0, 0, 0, 0, 0, 0, 0, 1), BATTERY_SHORT_ID)
+                    .with_capabilities(
+                        MacCapabilityFlags::RECEIVER_ON_WHEN_IDLE
+                            | MacCapabilityFlags::ALLOCATE_ADDRESS,
+                    )
+                    .with_attribute(
+                        ENDPOINT,
+                        POWER_CONFIGURATION,
+                        BATTERY_PERCENTAGE_REMAINING,
+                        Uint8::new(30),
+                    ),
+            )
+            .with_device(device(
+                IeeeAddress::new(0, 0, 0, 0, 0, 0, 0, 2),
+                MAINS_SHORT_ID,
+            ))
+    }
+
+    fn descriptor() -> Descriptor {
+        Descriptor::new(
+            Flags::default(),
+            MacCapabilityFlags::default(),
+            0,
+            82,
+            82,
+            ServerMask::empty(),
+            82,
+        )
+    }
+
+    fn destination(short_id: u16) -> NetworkDestination {
+        NetworkDestination::new(
+            NetworkAddress::new(short_id).expect("test address is valid"),
+            source_endpoint(),
+        )
+    }
+
+    fn source_endpoint() -> IndividualEndpoint {
+        IndividualEndpoint::new(ENDPOINT).expect("application endpoint is individual")
+    }
+
+    #[test]
+    fn leaves_a_state_only_beyond_the_hysteresis_margin() {
+        let monitor = Monitor::new();
+
+        assert_eq!(
+            monitor.state(Level::from_percent(20), State::Normal),
+            State::Low
+        );
+        assert_eq!(
+            monitor.state(Level::from_percent(5), State::Low),
+            State::Critical
+        );
+        assert_eq!(
+            monitor.state(Level::from_percent(6), State::Critical),
+            State::Critical
+        );
+        assert_eq!(
+            monitor.state(Level::from_percent(8), State::Critical),
+            State::Low
+        );
+        assert_eq!(
+            monitor.state(Level::from_percent(21), State::Low),
+            State::Low
+        );
+        assert_eq!(
+            monitor.state(Level::from_percent(23), State::Low),
+            State::Normal
+        );
+        assert_eq!(
+            monitor.state(Level::from_percent(50), State::Critical),
+            State::Normal
+        );
+    }
+
+    #[test]
+    fn enrolls_battery_devices_and_reports_their_state() {
+        Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("runtime must be available")
+            .block_on(async {
+                let (ncp, hw_events) = network().start(CAPACITY);
+                let (ncp, actor) = ncp.into_actor(CAPACITY);
+                tokio::spawn(actor);
+                let (events_out, mut events) = channel(CAPACITY.get());
+                let coordinator = Coordinator::start(
+                    ncp,
+                    descriptor(),
+                    hw_events,
+                    events_out,
+                    CoordinatorConfig::new(),
+                )
+                .expect("coordinator must start");
+                let monitoring = Monitor::new()
+                    .spawn(&coordinator)
+                    .await
+                    .expect("monitor must start");
+
+                let battery = destination(BATTERY_SHORT_ID);
+                let Enrollment::Monitored(status) = monitoring
+                    .enroll(battery, source_endpoint())
+                    .await
+                    .expect("battery device must be enrolled")
+                else {
+                    panic!("battery device must be monitored");
+                };
+                assert_eq!(status.level(), Some(Level::from_percent(15)));
+                assert_eq!(status.voltage_millivolts(), Some(2900));
+                assert_eq!(status.state(), State::Low);
+                assert_eq!(monitoring.status(battery), Some(status));
+
+                assert_eq!(
+                    monitoring
+                        .enroll(destination(MAINS_SHORT_ID), source_endpoint())
+                        .await
+                        .expect("mains device must be classified"),
+                    Enrollment::MainsPowered
+                );
+                assert_eq!(monitoring.statuses(), vec![(battery, status)]);
+
+                let event = loop {
+                    if let Event::Battery(event) =
+                        events.recv().await.expect("event channel must stay open")
+                    {
+                        break event;
+                    }
+                };
+                assert_eq!(event.destination(), battery);
+                assert_eq!(event.previous(), State::Normal);
+                assert_eq!(event.state(), State::Low);
+
+                assert!(monitoring.remove(battery));
+                assert_eq!(monitoring.status(battery), None);
+            });
+    }
+}
@@ -0,0 +1,160 @@
+use std::num::{NonZeroU8, NonZeroUsize};
+use std::time::Duration;
+
+use crate::MPSC_CHANNEL_SIZE;
+use crate::correlation::Timeouts;
+use crate::retry::RetryPolicy;
+
+const DEFAULT_CHANNEL_SIZE: NonZeroUsize =
+    NonZeroUsize::new(MPSC_CHANNEL_SIZE).expect("the default channel size is non-zero");
+const DEFAULT_SILENCE_THRESHOLD: Duration = Duration::from_hours(2);
+const DEFAULT_FRAGMENTATION_WINDOW: NonZeroU8 = NonZeroU8::MIN;
+
+/// Runtime configuration of a [`Coordinator`](crate::Coordinator).
+///
+/// The default configuration uses inboxes of 128 messages, 30-second response and quarantine
+/// timeouts, a two-hour silence threshold, up to 128 concurrent OTA transfers, the default
+/// [`RetryPolicy`], and fragmentation windows of one block.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CoordinatorConfig {
+    channel_size: NonZeroUsize,
+    timeouts: Timeouts,
+    silence_threshold: Option<Duration>,
+    ota_update_task_limit: usize,
+    retry_policy: RetryPolicy,
+    fragmentation_window: NonZeroU8,
+}
+
+impl CoordinatorConfig {
+    /// Create the default configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            channel_size: DEFAULT_CHANNEL_SIZE,
+            timeouts: Timeouts::new(),
+            silence_threshold: Some(DEFAULT_SILENCE_THRESHOLD),
+            ota_update_task_limit: MPSC_CHANNEL_SIZE,
+            retry_policy: RetryPolicy::new(),
+            fragmentation_window: DEFAULT_FRAGMENTATION_WINDOW,
+        }
+    }
+
+    /// Set the capacity of each actor inbox.
+    #[must_use]
+    pub const fn with_channel_size(mut self, channel_size: NonZeroUsize) -> Self {
+        self.channel_size = channel_size;
+        self
+    }
+
+    /// Set how long a correlated ZCL or ZDP response may remain pending.
+    ///
+    /// Individual requests may override this through [`Zcl::communicate_with_timeout`] and
+    /// [`Zdp::communicate_with_timeout`].
+    ///
+    /// [`Zcl::communicate_with_timeout`]: crate::Zcl::communicate_with_timeout
+    /// [`Zdp::communicate_with_timeout`]: crate::Zdp::communicate_with_timeout
+    #[must_use]
+    pub const fn with_response_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts = self.timeouts.with_response(timeout);
+        self
+    }
+
+    /// Set how long a timed-out or cancelled correlation remains quarantined against late
+    /// responses.
+    #[must_use]
+    pub const fn with_quarantine_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts = self.timeouts.with_quarantine(timeout);
+        self
+    }
+
+    /// Set the initial silence threshold after which a device is reported as unresponsive.
+    ///
+    /// `None` disables the reports until [`Activity::set_silence_threshold`] enables them.
+    ///
+    /// [`Activity::set_silence_threshold`]: crate::Activity::set_silence_threshold
+    #[must_use]
+    pub const fn with_silence_threshold(mut self, threshold: Option<Duration>) -> Self {
+        self.silence_threshold = threshold;
+        self
+    }
+
+    /// Set the maximum number of concurrent destination OTA transfer tasks.
+    ///
+    /// Each destination with an accepted [`crate::ota::Message::Update`] holds one slot for the
+    /// complete exchange. Replacing an update for the same destination reuses its task. A limit of
+    /// zero rejects every OTA update.
+    #[must_use]
+    pub const fn with_ota_update_task_limit(mut self, limit: usize) -> Self {
+        self.ota_update_task_limit = limit;
+        self
+    }
+
+    /// Set the initial retry policy for failed ZCL and ZDP transmissions.
+    #[must_use]
+    pub const fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Set how many blocks of a fragmented transmission are sent per acknowledged window.
+    ///
+    /// The destination acknowledges the last block of each window. Larger windows need fewer
+    /// acknowledgements but resend more blocks when one is lost.
+    #[must_use]
+    pub const fn with_fragmentation_window(mut self, blocks: NonZeroU8) -> Self {
+        self.fragmentation_window = blocks;
+        self
+    }
+
+    /// Return the capacity of each actor inbox.
+    #[must_use]
+    pub const fn channel_size(&self) -> NonZeroUsize {
+        self.channel_size
+    }
+
+    /// Return the default time a correlated response may remain pending.
+    #[must_use]
+    pub const fn response_timeout(&self) -> Duration {
+        self.timeouts.response()
+    }
+
+    /// Return the time a timed-out or cancelled correlation remains quarantined.
+    #[must_use]
+    pub const fn quarantine_timeout(&self) -> Duration {
+        self.timeouts.quarantine()
+    }
+
+    /// Return the initial silence threshold.
+    #[must_use]
+    pub const fn silence_threshold(&self) -> Option<Duration> {
+        self.silence_threshold
+    }
+
+    /// Return the maximum number of concurrent destination OTA transfer tasks.
+    #[must_use]
+    pub const fn ota_update_task_limit(&self) -> usize {
+        self.ota_update_task_limit
+    }
+
+    /// Return the initial retry policy.
+    #[must_use]
+    pub const fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    /// Return how many blocks of a fragmented transmission are sent per acknowledged window.
+    #[must_use]
+    pub const fn fragmentation_window(&self) -> NonZeroU8 {
+        self.fragmentation_window
+    }
+
+    pub(crate) const fn timeouts(&self) -> Timeouts {
+        self.timeouts
+    }
+}
+
+impl Default for CoordinatorConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,12 +1,16 @@
 use std::fmt::Debug;
+use std::sync::Arc;
 
+use tokio::sync::Mutex;
 use tokio::sync::mpsc::{Receiver, Sender};
+use tokio::task::AbortHandle;
 use zb_core::node::Descriptor;
 use zb_hw::{Error, NcpHandle};
 
 use crate::event::EventSink;
 use crate::mux::Mux;
-use crate::{DEFAULT_OTA_UPDATE_TASK_LIMIT, Event, aps, ota, zcl, zdp};
+use crate::retry::Retrier;
+use crate::{CoordinatorConfig, Event, Network, activity, aps, ota, sleepy, touchlink, zcl, zdp};
 
 /// External Zigbee API struct.
 #[derive(Clone, Debug)]
@@ -15,10 +19,17 @@ pub struct Coordinator {
     pub(crate) ota: Sender<ota::Message>,
     pub(crate) zcl: Sender<zcl::Message>,
     pub(crate) zdp: Sender<zdp::Message>,
+    pub(crate) activity: activity::Table,
+    pub(crate) sleepy: sleepy::Queue,
+    pub(crate) inter_pan: touchlink::InterPan,
+    pub(crate) retrier: Retrier,
+    aps: aps::Aps,
+    pub(crate) events: EventSink,
+    mux: Arc<Mutex<AbortHandle>>,
 }
 
 impl Coordinator {
-    /// Start the coordinator on the given hardware.
+    /// Start the coordinator on the given hardware with the given configuration.
     ///
     /// Local endpoint descriptors are obtained through [`NcpHandle::get_endpoints`] when needed;
     /// callers do not supply them during startup. The hardware event timestamp and link-key
@@ -32,54 +43,336 @@ impl Coordinator {
         descriptor: Descriptor,
         hw_events: Receiver<zb_hw::Event<T, K>>,
         events_out: Sender<Event>,
+        config: CoordinatorConfig,
     ) -> Result<Self, Error>
     where
         T: Send + 'static,
         K: Send + 'static,
     {
-        Self::start_with_ota_update_task_limit(
-            ncp,
+        let events = EventSink::new(events_out);
+        let aps = aps::Transceiver::spawn(ncp.clone(), &config);
+        let retrier = Retrier::new(config.retry_policy(), ncp.clone());
+        let zcl = zcl::Transceiver::spawn(aps.clone(), events.clone(), retrier.clone(), &config);
+        let ota = ota::Server::spawn(
+            ncp.clone(),
+            zcl.clone(),
+            events.clone(),
+            config.ota_update_task_limit(),
+            config.channel_size(),
+        );
+        let zdp = zdp::Transceiver::spawn(
+            ncp.clone(),
+            aps.clone(),
+            events.clone(),
             descriptor,
-            hw_events,
-            events_out,
-            DEFAULT_OTA_UPDATE_TASK_LIMIT,
+            retrier.clone(),
+            &config,
+        );
+        let activity = activity::Table::new(config.silence_threshold());
+        activity.spawn_watchdog(events.clone());
+        let sleepy = sleepy::Queue::new(zcl.clone());
+        let inter_pan = touchlink::InterPan::default();
+        let mux = Mux::new(
+            events.clone(),
+            aps.clone(),
+            ota.clone(),
+            zcl.clone(),
+            zdp.clone(),
+            activity.clone(),
+            sleepy.clone(),
+            inter_pan.clone(),
         )
+        .spawn(hw_events);
+        Ok(Self {
+            ncp,
+            ota,
+            zcl,
+            zdp,
+            activity,
+            sleepy,
+            inter_pan,
+            retrier,
+            aps,
+            events,
+            mux: Arc::new(Mutex::new(mux)),
+        })
     }
 
-    /// Start the coordinator with a limit on concurrent destination OTA transfer tasks.
-    ///
-    /// Each destination with an accepted [`crate::ota::Message::Update`] holds one slot for the
-    /// complete exchange. Replacing an update for the same destination reuses its task. A limit of
-    /// zero rejects every OTA update. The hardware event timestamp and link-key device-pair handle
-    /// types may be selected by the backend.
-    ///
-    /// # Errors
+    /// Re-wire the coordinator to the event stream of a restarted hardware session.
     ///
-    /// Returns an [`Error`] if setting up the actor network fails.
-    pub fn start_with_ota_update_task_limit<T, K>(
-        ncp: NcpHandle,
-        descriptor: Descriptor,
-        hw_events: Receiver<zb_hw::Event<T, K>>,
-        events_out: Sender<Event>,
-        ota_update_task_limit: usize,
-    ) -> Result<Self, Error>
+    /// Restart the hardware actor through [`NcpHandle::restart`] first. This method stops
+    /// processing the previous hardware event stream and fails every pending request of the
+    /// previous session, including unconfirmed APS transmissions, outstanding ZCL and ZDP
+    /// responses, and active OTA transfers. It then processes `hw_events` and emits
+    /// [`Network::Restarted`]. All clones of this coordinator remain usable.
+    pub async fn restart<T, K>(&self, hw_events: Receiver<zb_hw::Event<T, K>>)
     where
         T: Send + 'static,
         K: Send + 'static,
     {
-        let events = EventSink::new(events_out);
-        let aps = aps::Transceiver::spawn(ncp.clone());
-        let zcl = zcl::Transceiver::spawn(aps.clone(), events.clone());
-        let ota = ota::Server::spawn(ncp.clone(), zcl.clone(), ota_update_task_limit);
-        let zdp = zdp::Transceiver::spawn(ncp.clone(), aps.clone(), events.clone(), descriptor);
-        Mux::spawn(
-            hw_events,
-            events,
-            aps,
-            ota.clone(),
-            zcl.clone(),
-            zdp.clone(),
+        let mut current = self.mux.lock().await;
+        current.abort();
+        let mux = Mux::new(
+            self.events.clone(),
+            self.aps.clone(),
+            self.ota.clone(),
+            self.zcl.clone(),
+            self.zdp.clone(),
+            self.activity.clone(),
+            self.sleepy.clone(),
+            self.inter_pan.clone(),
         );
-        Ok(Self { ncp, ota, zcl, zdp })
+        mux.hardware_unavailable().await;
+        *current = mux.spawn(hw_events);
+        drop(current);
+        self.events.emit(Event::Network(Network::Restarted));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::time::Duration;
+
+    use bytes::Bytes;
+    use tokio::runtime::Builder;
+    use tokio::sync::mpsc::channel;
+    use tokio::time::timeout;
+    use zb_aps::apsde::{IndividualEndpoint, NetworkAddress, NetworkDestination};
+    use zb_core::node::{Descriptor, Flags, MacCapabilityFlags, ServerMask};
+    use zb_core::short_id::Device;
+    use zb_core::{Application, Endpoint, IeeeAddress, Profile};
+    use zb_hw::sim::{VirtualDevice, VirtualNetwork};
+    use zb_hw::{Driver, TransmissionError};
+    use zb_zdp::{AppFlags, Clusters, SimpleDescriptor};
+
+    use super::Coordinator;
+    use crate::aps::{Metadata, data_request};
+    use crate::{Activity, CoordinatorConfig, Device as DeviceEvent, Endpoints, Event, Network};
+
+    const CAPACITY: NonZeroUsize = NonZeroUsize::new(16).expect("capacity is non-zero");
+    const DEVICE_SHORT_ID: u16 = 0x1234;
+    const ENDPOINT: Endpoint = Endpoint::Application(Application::MIN);
+    const MAXIMUM_BUFFER_SIZE: u8 = 82;
+    const MAXIMUM_TRANSFER_SIZE: u16 = 82;
+    const ON_OFF: u16 = 0x0006;
+    const PAYLOAD: &[u8] = &[0x01, 0x2A, 0x02];
+    const SILENCE_THRESHOLD: Duration = Duration::from_millis(50);
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn discovers_endpoints_of_a_simulated_device() {
+        Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("runtime must be available")
+            .block_on(async {
+                let device = Device::new(DEVICE_SHORT_ID).expect("test short ID is valid");
+                let (ncp, hw_events) = network(device).start(CAPACITY);
+                let (ncp, actor) = ncp.into_actor(CAPACITY);
+                tokio::spawn(actor);
+                let (events_out, _events) = channel(CAPACITY.get());
+                let coordinator = Coordinator::start(
+                    ncp,
+                    descriptor(),
+                    hw_events,
+                    events_out,
+                    CoordinatorConfig::new(),
+                )
+                .expect("coordinator must start");
+
+                assert_eq!(
+                    coordinator
+                        .endpoints(device)
+                        .await
+                        .expect("simulated device must answer Active_EP_req")
+                        .into_iter()
+                        .collect::<Vec<_>>(),
+                    [ENDPOINT]
+                );
+            });
+    }
+
+    #[test]
+    fn restart_rewires_the_coordinator_to_a_new_hardware_session() {
+        Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("runtime must be available")
+            .block_on(async {
+                let device = Device::new(DEVICE_SHORT_ID).expect("test short ID is valid");
+                let (ncp, hw_events) = network(device).start(CAPACITY);
+                let (ncp, actor) = ncp.into_actor(CAPACITY);
+                tokio::spawn(actor);
+                let (events_out, mut events) = channel(CAPACITY.get());
+                let coordinator = Coordinator::start(
+                    ncp.clone(),
+                    descriptor(),
+                    hw_events,
+                    events_out,
+                    CoordinatorConfig::new(),
+                )
+                .expect("coordinator must start");
+                let handle = coordinator.clone();
+
+                ncp.stop();
+                let (restarted, hw_events) = network(device).start(CAPACITY);
+                tokio::spawn(ncp.restart(restarted, CAPACITY));
+                coordinator.restart(hw_events).await;
+
+                timeout(TIMEOUT, async {
+                    while !matches!(
+                        events.recv().await,
+                        Some(Event::Network(Network::Restarted))
+                    ) {}
+                })
+                .await
+                .expect("coordinator must report the restart");
+                assert_eq!(
+                    handle
+                        .endpoints(device)
+                        .await
+                        .expect("restarted device must answer Active_EP_req")
+                        .into_iter()
+                        .collect::<Vec<_>>(),
+                    [ENDPOINT]
+                );
+            });
+    }
+
+    #[test]
+    fn transmits_after_restarting_from_a_confirmation_timeout() {
+        Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .expect("runtime must be available")
+            .block_on(async {
+                let device = Device::new(DEVICE_SHORT_ID).expect("test short ID is valid");
+                let (ncp, _lost_events) = network(device).start(CAPACITY);
+                let (ncp, actor) = ncp.into_actor(CAPACITY);
+                tokio::spawn(actor);
+                let (_silent, hw_events) = channel::<zb_hw::Event>(CAPACITY.get());
+                let (events_out, _events) = channel(CAPACITY.get());
+                let coordinator = Coordinator::start(
+                    ncp.clone(),
+                    descriptor(),
+                    hw_events,
+                    events_out,
+                    CoordinatorConfig::new(),
+                )
+                .expect("coordinator must start");
+                let request = || {
+                    data_request(
+                        NetworkDestination::new(
+                            NetworkAddress::new(DEVICE_SHORT_ID).expect("test short ID is valid"),
+                            IndividualEndpoint::new(ENDPOINT).expect("endpoint is individual"),
+                        )
+                        .into(),
+                        IndividualEndpoint::new(ENDPOINT).expect("endpoint is individual"),
+                        Metadata::new(Profile::ZigbeeHomeAutomation, ON_OFF),
+                        Bytes::from_static(PAYLOAD),
+                    )
+                };
+
+                let unconfirmed = coordinator
+                    .aps
+                    .transmit(request())
+                    .await
+                    .expect("APS actor must accept the transmission")
+                    .await;
+                assert!(matches!(
+                    unconfirmed,
+                    Err(zb_hw::Error::Transmission(TransmissionError::Timeout))
+                ));
+
+                ncp.stop();
+                let (restarted, hw_events) = network(device).start(CAPACITY);
+                tokio::spawn(ncp.restart(restarted, CAPACITY));
+                coordinator.restart(hw_events).await;
+
+                coordinator
+                    .aps
+                    .transmit(request())
+                    .await
+                    .expect("APS actor must survive the confirmation timeout")
+                    .await
+                    .expect("restarted hardware session must confirm the transmission");
+            });
+    }
+
+    #[test]
+    fn reports_a_device_that_falls_silent_after_answering() {
+        Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("runtime must be available")
+            .block_on(async {
+                let device = Device::new(DEVICE_SHORT_ID).expect("test short ID is valid");
+                let (ncp, hw_events) = network(device).start(CAPACITY);
+                let (ncp, actor) = ncp.into_actor(CAPACITY);
+                tokio::spawn(actor);
+                let (events_out, mut events) = channel(CAPACITY.get());
+                let coordinator = Coordinator::start(
+                    ncp,
+                    descriptor(),
+                    hw_events,
+                    events_out,
+                    CoordinatorConfig::new(),
+                )
+                .expect("coordinator must start");
+
+                coordinator
+                    .endpoints(device)
+                    .await
+                    .expect("simulated device must answer Active_EP_req");
+                let activity = coordinator
+                    .device_activity(device)
+                    .expect("answering device must be tracked");
+                assert!(activity.last_seen().is_some());
+                assert_eq!(activity.transmission_failures(), 0);
+
+                coordinator.set_silence_threshold(Some(SILENCE_THRESHOLD));
+                timeout(TIMEOUT, async {
+                    while !matches!(
+                        events.recv().await,
+                        Some(Event::Device(DeviceEvent::Unresponsive(silent))) if silent == device
+                    ) {}
+                })
+                .await
+                .expect("coordinator must report the silent device");
+                assert!(
+                    coordinator
+                        .device_activity(device)
+                        .is_some_and(|activity| activity.is_unresponsive())
+                );
+            });
+    }
+
+    fn network(device: Device) -> VirtualNetwork {
+        VirtualNetwork::new(IeeeAddress::new(0, 0, 0, 0, 0, 0, 0, 0xAA), 0x1A62).with_device(
+            VirtualDevice::new(IeeeAddress::new(0, 0, 0, 0, 0, 0, 0, 1), device).with_endpoint(
+                SimpleDescriptor::new(
+                    ENDPOINT,
+                    Profile::ZigbeeHomeAutomation,
+                    0x0101,
+                    AppFlags::empty(),
+                    Clusters::new(),
+                    Clusters::new(),
+                ),
+            ),
+        )
+    }
+
+    fn descriptor() -> Descriptor {
+        Descriptor::new(
+            Flags::default(),
+            MacCapabilityFlags::default(),
+            0,
+            MAXIMUM_BUFFER_SIZE,
+            MAXIMUM_TRANSFER_SIZE,
+            ServerMask::empty(),
+            MAXIMUM_TRANSFER_SIZE,
+        )
     }
 }
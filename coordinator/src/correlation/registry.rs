@@ -119,6 +119,11 @@ impl<T> Registry<T> {
         }
     }
 
+    /// Return whether the response identified by a token is still awaited.
+    pub fn is_pending(&self, token: Token) -> bool {
+        self.pending_generation_matches(token)
+    }
+
     /// Consume a late response and release its quarantined transaction identity.
     pub fn release_quarantine(&mut self, key: Key) -> bool {
         self.quarantined.remove(&key).is_some()
@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+const DEFAULT_RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_QUARANTINE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Lifetimes of pending and quarantined ZCL and ZDP correlations.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Timeouts {
+    response: Duration,
+    quarantine: Duration,
+}
+
+impl Timeouts {
+    /// Create the default 30-second response and quarantine timeouts.
+    pub const fn new() -> Self {
+        Self {
+            response: DEFAULT_RESPONSE_TIMEOUT,
+            quarantine: DEFAULT_QUARANTINE_TIMEOUT,
+        }
+    }
+
+    /// Set the maximum time retained for a pending response.
+    pub const fn with_response(mut self, timeout: Duration) -> Self {
+        self.response = timeout;
+        self
+    }
+
+    /// Set the maximum additional time retained for a late response.
+    pub const fn with_quarantine(mut self, timeout: Duration) -> Self {
+        self.quarantine = timeout;
+        self
+    }
+
+    /// Return the maximum time retained for a pending response.
+    pub const fn response(self) -> Duration {
+        self.response
+    }
+
+    /// Return the maximum additional time retained for a late response.
+    pub const fn quarantine(self) -> Duration {
+        self.quarantine
+    }
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,43 @@
+//! Periodic sampling of diagnostic counters.
+//!
+//! A [`Sampler`] reads the counters of the NCP through
+//! [`Diagnostics::get_counters`](crate::api::Diagnostics::get_counters) and those of selected
+//! devices through [`Diagnostics::read_diagnostics`](crate::api::Diagnostics::read_diagnostics).
+//! Each round yields a timestamped [`Sample`]. A running [`Sampling`] keeps the most recent samples
+//! in a bounded [`TimeSeries`], whose [`Metric`]s can be handed to a metrics exporter:
+//!
+//! ```no_run
+//! use std::num::NonZeroUsize;
+//! use std::time::Duration;
+//!
+//! use apis_saltans_coordinator::Coordinator;
+//! use apis_saltans_coordinator::diagnostics::{Origin, Sampler};
+//!
+//! fn export(coordinator: Coordinator) {
+//!     let sampling = Sampler::new(Duration::from_secs(60), NonZeroUsize::MIN).spawn(coordinator);
+//!
+//!     for metric in sampling.time_series().metrics() {
+//!         let device = match metric.origin() {
+//!             Origin::Ncp => "ncp".to_owned(),
+//!             Origin::Device(destination) => format!("{:?}", destination.address()),
+//!         };
+//!         println!("zigbee_{}{{device=\"{device}\"}} {}", metric.name(), metric.value());
+//!     }
+//! }
+//! ```
+//!
+//! Counter values are cumulative and wrap around, so exporters should treat them as counters
+//! rather than gauges.
+
+pub use zb_hw::{Counter, Counters};
+
+pub use self::metric::{Metric, Origin};
+pub use self::sample::Sample;
+pub use self::sampler::{Sampler, Sampling};
+pub use self::time_series::TimeSeries;
+
+mod metric;
+pub(crate) mod remote;
+mod sample;
+mod sampler;
+mod time_series;
@@ -0,0 +1,69 @@
+use std::time::SystemTime;
+
+use zb_aps::apsde::NetworkDestination;
+use zb_hw::Counter;
+
+/// The source of a counter value.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Origin {
+    /// The coordinator's NCP.
+    Ncp,
+
+    /// The Diagnostics cluster of a remote device endpoint.
+    Device(NetworkDestination),
+}
+
+/// A single counter value of a [`Sample`](super::Sample).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Metric {
+    taken_at: SystemTime,
+    origin: Origin,
+    counter: Counter,
+    value: u32,
+}
+
+impl Metric {
+    pub(super) const fn new(
+        taken_at: SystemTime,
+        origin: Origin,
+        counter: Counter,
+        value: u32,
+    ) -> Self {
+        Self {
+            taken_at,
+            origin,
+            counter,
+            value,
+        }
+    }
+
+    /// Return when the value was sampled.
+    #[must_use]
+    pub const fn taken_at(&self) -> SystemTime {
+        self.taken_at
+    }
+
+    /// Return where the value was read from.
+    #[must_use]
+    pub const fn origin(&self) -> Origin {
+        self.origin
+    }
+
+    /// Return the counter.
+    #[must_use]
+    pub const fn counter(&self) -> Counter {
+        self.counter
+    }
+
+    /// Return the metric name of the counter, for example `aps_tx_unicast_failure`.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.counter.name()
+    }
+
+    /// Return the counter value.
+    #[must_use]
+    pub const fn value(&self) -> u32 {
+        self.value
+    }
+}
@@ -0,0 +1,96 @@
+//! Mapping of Diagnostics cluster attributes onto hardware counters.
+
+use zb_hw::Counter;
+use zb_zcl::diagnostics::{Id, Readable};
+
+/// Maximum number of attributes read per request.
+///
+/// Eight 32-bit attribute records fit into a single unfragmented APS frame.
+pub const ATTRIBUTES_PER_READ: usize = 8;
+
+/// Diagnostics attributes with a hardware counter equivalent.
+pub const ATTRIBUTES: [Id; 23] = [
+    Id::MacRxBcast,
+    Id::MacTxBcast,
+    Id::MacRxUcast,
+    Id::MacTxUcastRetry,
+    Id::MacTxUcastFail,
+    Id::ApsRxBcast,
+    Id::ApsTxBcast,
+    Id::ApsRxUcast,
+    Id::ApsTxUcastSuccess,
+    Id::ApsTxUcastRetry,
+    Id::ApsTxUcastFail,
+    Id::RouteDiscInitiated,
+    Id::NeighborAdded,
+    Id::NeighborRemoved,
+    Id::NeighborStale,
+    Id::JoinIndication,
+    Id::NwkFcFailure,
+    Id::ApsFcFailure,
+    Id::ApsUnauthorizedKey,
+    Id::NwkDecryptFailures,
+    Id::ApsDecryptFailures,
+    Id::PacketBufferAllocateFailures,
+    Id::RelayedUcast,
+];
+
+/// Return the hardware counter and value of a Diagnostics attribute.
+///
+/// Returns `None` for attributes without a counter equivalent and for invalid values.
+pub fn counter(attribute: &Readable) -> Option<(Counter, u32)> {
+    match attribute {
+        Readable::MacRxBcast(value) => Some((Counter::MacRxBroadcast, value.as_option()?)),
+        Readable::MacTxBcast(value) => Some((Counter::MacTxBroadcast, value.as_option()?)),
+        Readable::MacRxUcast(value) => Some((Counter::MacRxUnicast, value.as_option()?)),
+        Readable::MacTxUcastRetry(value) => {
+            Some((Counter::MacTxUnicastRetry, value.as_option()?.into()))
+        }
+        Readable::MacTxUcastFail(value) => {
+            Some((Counter::MacTxUnicastFailure, value.as_option()?.into()))
+        }
+        Readable::ApsRxBcast(value) => Some((Counter::ApsRxBroadcast, value.as_option()?.into())),
+        Readable::ApsTxBcast(value) => Some((Counter::ApsTxBroadcast, value.as_option()?.into())),
+        Readable::ApsRxUcast(value) => Some((Counter::ApsRxUnicast, value.as_option()?.into())),
+        Readable::ApsTxUcastSuccess(value) => {
+            Some((Counter::ApsTxUnicastSuccess, value.as_option()?.into()))
+        }
+        Readable::ApsTxUcastRetry(value) => {
+            Some((Counter::ApsTxUnicastRetry, value.as_option()?.into()))
+        }
+        Readable::ApsTxUcastFail(value) => {
+            Some((Counter::ApsTxUnicastFailure, value.as_option()?.into()))
+        }
+        Readable::RouteDiscInitiated(value) => {
+            Some((Counter::RouteDiscoveryInitiated, value.as_option()?.into()))
+        }
+        Readable::NeighborAdded(value) => Some((Counter::NeighborAdded, value.as_option()?.into())),
+        Readable::NeighborRemoved(value) => {
+            Some((Counter::NeighborRemoved, value.as_option()?.into()))
+        }
+        Readable::NeighborStale(value) => Some((Counter::NeighborStale, value.as_option()?.into())),
+        Readable::JoinIndication(value) => {
+            Some((Counter::JoinIndication, value.as_option()?.into()))
+        }
+        Readable::NwkFcFailure(value) => {
+            Some((Counter::NwkFrameCounterFailure, value.as_option()?.into()))
+        }
+        Readable::ApsFcFailure(value) => {
+            Some((Counter::ApsFrameCounterFailure, value.as_option()?.into()))
+        }
+        Readable::ApsUnauthorizedKey(value) => {
+            Some((Counter::ApsUnauthorizedKey, value.as_option()?.into()))
+        }
+        Readable::NwkDecryptFailures(value) => {
+            Some((Counter::NwkDecryptionFailure, value.as_option()?.into()))
+        }
+        Readable::ApsDecryptFailures(value) => {
+            Some((Counter::ApsDecryptionFailure, value.as_option()?.into()))
+        }
+        Readable::PacketBufferAllocateFailures(value) => {
+            Some((Counter::BufferAllocationFailure, value.as_option()?.into()))
+        }
+        Readable::RelayedUcast(value) => Some((Counter::RelayedUnicast, value.as_option()?.into())),
+        _ => None,
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+use zb_aps::apsde::NetworkDestination;
+use zb_hw::Counters;
+
+use super::{Metric, Origin};
+
+/// The counters collected in one sampling round.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Sample {
+    taken_at: SystemTime,
+    ncp: Option<Counters>,
+    devices: BTreeMap<NetworkDestination, Counters>,
+}
+
+impl Sample {
+    /// Create an empty sample taken at the given time.
+    #[must_use]
+    pub const fn new(taken_at: SystemTime) -> Self {
+        Self {
+            taken_at,
+            ncp: None,
+            devices: BTreeMap::new(),
+        }
+    }
+
+    /// Set the counters of the NCP.
+    #[must_use]
+    pub fn with_ncp(mut self, counters: Counters) -> Self {
+        self.ncp.replace(counters);
+        self
+    }
+
+    /// Add the counters of a device endpoint.
+    #[must_use]
+    pub fn with_device(mut self, destination: NetworkDestination, counters: Counters) -> Self {
+        self.devices.insert(destination, counters);
+        self
+    }
+
+    /// Return when the sampling round started.
+    #[must_use]
+    pub const fn taken_at(&self) -> SystemTime {
+        self.taken_at
+    }
+
+    /// Return the counters of the NCP, unless they could not be read.
+    #[must_use]
+    pub const fn ncp(&self) -> Option<&Counters> {
+        self.ncp.as_ref()
+    }
+
+    /// Return the counters of a device endpoint, unless they could not be read.
+    #[must_use]
+    pub fn device(&self, destination: NetworkDestination) -> Option<&Counters> {
+        self.devices.get(&destination)
+    }
+
+    /// Iterate over the sampled counter values, starting with those of the NCP.
+    pub fn metrics(&self) -> impl Iterator<Item = Metric> + '_ {
+        self.ncp
+            .iter()
+            .map(|counters| (Origin::Ncp, counters))
+            .chain(
+                self.devices
+                    .iter()
+                    .map(|(&destination, counters)| (Origin::Device(destination), counters)),
+            )
+            .flat_map(move |(origin, counters)| {
+                counters
+                    .iter()
+                    .map(move |(counter, value)| Metric::new(self.taken_at, origin, counter, value))
+            })
+    }
+}
@@ -0,0 +1,283 @@
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, SystemTime};
+
+use log::warn;
+use tokio::spawn;
+use tokio::task::AbortHandle;
+use tokio::time::{MissedTickBehavior, interval};
+use zb_aps::apsde::{IndividualEndpoint, NetworkDestination};
+
+use super::{Sample, TimeSeries};
+use crate::api::Diagnostics;
+
+/// Shortest time between the starts of two sampling rounds.
+const MIN_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Configuration of periodic counter sampling.
+///
+/// Every sampling round reads the NCP's counters and then the Diagnostics cluster of each selected
+/// device in turn. Counters that cannot be read are missing from the round's [`Sample`]; the
+/// failure is logged.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Sampler {
+    interval: Duration,
+    capacity: NonZeroUsize,
+    devices: Vec<(NetworkDestination, IndividualEndpoint)>,
+}
+
+impl Sampler {
+    /// Create a sampler taking a sample every `interval` and keeping the latest `capacity`
+    /// samples.
+    ///
+    /// Intervals shorter than 10 ms, including zero, are raised to 10 ms when sampling starts.
+    #[must_use]
+    pub const fn new(interval: Duration, capacity: NonZeroUsize) -> Self {
+        Self {
+            interval,
+            capacity,
+            devices: Vec::new(),
+        }
+    }
+
+    /// Also sample the Diagnostics cluster of a device endpoint, read from the given local source
+    /// endpoint.
+    #[must_use]
+    pub fn with_device(
+        mut self,
+        destination: NetworkDestination,
+        source_endpoint: IndividualEndpoint,
+    ) -> Self {
+        self.devices.push((destination, source_endpoint));
+        self
+    }
+
+    /// Take a single sample.
+    pub async fn sample<T>(&self, api: &T) -> Sample
+    where
+        T: Diagnostics + Sync,
+    {
+        let mut sample = Sample::new(SystemTime::now());
+
+        match api.get_counters().await {
+            Ok(counters) => sample = sample.with_ncp(counters),
+            Err(error) => warn!("Failed to read NCP counters: {error}"),
+        }
+
+        for &(destination, source_endpoint) in &self.devices {
+            match api.read_diagnostics(destination, source_endpoint).await {
+                Ok(counters) => sample = sample.with_device(destination, counters),
+                Err(error) => warn!("Failed to read diagnostics of {destination:?}: {error}"),
+            }
+        }
+
+        sample
+    }
+
+    /// Start sampling in a background task.
+    ///
+    /// The first sample is taken immediately. If a round takes longer than the interval, the next
+    /// one starts as soon as it completes. Sampling stops when the returned [`Sampling`] is
+    /// dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    #[must_use]
+    pub fn spawn<T>(self, api: T) -> Sampling
+    where
+        T: Diagnostics + Send + Sync + 'static,
+    {
+        let series = Arc::new(Mutex::new(TimeSeries::new(self.capacity)));
+        let mut ticks = interval(self.interval.max(MIN_INTERVAL));
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let task = {
+            let series = series.clone();
+
+            spawn(async move {
+                loop {
+                    ticks.tick().await;
+                    let sample = self.sample(&api).await;
+                    series
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .push(sample);
+                }
+            })
+            .abort_handle()
+        };
+
+        Sampling { series, task }
+    }
+}
+
+/// A running sampling task started by [`Sampler::spawn`].
+///
+/// Dropping the handle stops sampling.
+#[derive(Debug)]
+pub struct Sampling {
+    series: Arc<Mutex<TimeSeries>>,
+    task: AbortHandle,
+}
+
+impl Sampling {
+    /// Return a copy of the samples taken so far.
+    #[must_use]
+    pub fn time_series(&self) -> TimeSeries {
+        self.series
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl Drop for Sampling {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::time::Duration;
+
+    use tokio::runtime::Builder;
+    use tokio::sync::mpsc::channel;
+    use tokio::time::sleep;
+    use zb_aps::apsde::{IndividualEndpoint, NetworkAddress, NetworkDestination};
+    use zb_core::node::{Descriptor, Flags, MacCapabilityFlags, ServerMask};
+    use zb_core::short_id::Device;
+    use zb_core::types::{Uint16, Uint32};
+    use zb_core::{Application, Endpoint, IeeeAddress, Profile};
+    use zb_hw::Driver;
+    use zb_hw::sim::{VirtualDevice, VirtualNetwork};
+    use zb_zdp::{AppFlags, Clusters, SimpleDescriptor};
+
+    use super::{MIN_INTERVAL, Sampler};
+    use crate::diagnostics::{Counter, Origin};
+    use crate::{Coordinator, CoordinatorConfig};
+
+    const CAPACITY: NonZeroUsize = NonZeroUsize::new(16).expect("capacity is non-zero");
+    const SAMPLES: NonZeroUsize = NonZeroUsize::new(2).expect("capacity is non-zero");
+    const DEVICE_SHORT_ID: u16 = 0x1234;
+    const DIAGNOSTICS: u16 = 0x0B05;
+    const ENDPOINT: Endpoint = Endpoint::Application(Application::MIN);
+    const INTERVAL: Duration = Duration::from_millis(50);
+    const MAXIMUM_TRANSFER_SIZE: u16 = 82;
+
+    fn network() -> VirtualNetwork {
+        VirtualNetwork::new(IeeeAddress::new(0, 0, 0, 0, 0, 0, 0, 0xAA), 0x1A62).with_device(
+            VirtualDevice::new(
+                IeeeAddress::new(0, 0, 0, 0, 0, 0, 0, 1),
+                Device::new(DEVICE_SHORT_ID).expect("test short ID is valid"),
+            )
+            .with_endpoint(SimpleDescriptor::new(
+                ENDPOINT,
+                Profile::ZigbeeHomeAutomation,
+                0x0101,
+                AppFlags::empty(),
+                Clusters::from_slice(&[DIAGNOSTICS]).expect("one cluster fits"),
+                Clusters::new(),
+            ))
+            .with_attribute(ENDPOINT, DIAGNOSTICS, 0x0100, Uint32::new(7))
+            .with_attribute(ENDPOINT, DIAGNOSTICS, 0x0104, Uint16::new(3))
+            .with_attribute(ENDPOINT, DIAGNOSTICS, 0x0117, Uint16::new(1)),
+        )
+    }
+
+    fn descriptor() -> Descriptor {
+        Descriptor::new(
+            Flags::default(),
+            MacCapabilityFlags::default(),
+            0,
+            82,
+            MAXIMUM_TRANSFER_SIZE,
+            ServerMask::empty(),
+            MAXIMUM_TRANSFER_SIZE,
+        )
+    }
+
+    fn destination() -> NetworkDestination {
+        NetworkDestination::new(
+            NetworkAddress::new(DEVICE_SHORT_ID).expect("test address is valid"),
+            IndividualEndpoint::new(ENDPOINT).expect("application endpoint is individual"),
+        )
+    }
+
+    /// Start a coordinator on the virtual network inside the current runtime.
+    fn coordinator() -> Coordinator {
+        let (ncp, hw_events) = network().start(CAPACITY);
+        let (ncp, actor) = ncp.into_actor(CAPACITY);
+        tokio::spawn(actor);
+        let (events_out, _events) = channel(CAPACITY.get());
+        Coordinator::start(
+            ncp,
+            descriptor(),
+            hw_events,
+            events_out,
+            CoordinatorConfig::new(),
+        )
+        .expect("coordinator must start")
+    }
+
+    #[test]
+    fn samples_ncp_and_device_counters_into_a_bounded_series() {
+        Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("runtime must be available")
+            .block_on(async {
+                let coordinator = coordinator();
+                let sampling = Sampler::new(INTERVAL, SAMPLES)
+                    .with_device(
+                        destination(),
+                        IndividualEndpoint::new(ENDPOINT)
+                            .expect("application endpoint is individual"),
+                    )
+                    .spawn(coordinator);
+
+                sleep(INTERVAL * 2 + INTERVAL / 2).await;
+                let series = sampling.time_series();
+                assert_eq!(series.len(), SAMPLES.get());
+
+                let latest = series.latest().expect("series must contain samples");
+                let device = latest
+                    .device(destination())
+                    .expect("device counters must be sampled");
+                assert_eq!(device.get(Counter::MacRxBroadcast), Some(7));
+                assert_eq!(device.get(Counter::MacTxUnicastRetry), Some(3));
+                assert_eq!(device.get(Counter::BufferAllocationFailure), Some(1));
+                assert_eq!(device.len(), 3);
+
+                let ncp = latest.ncp().expect("NCP counters must be sampled");
+                let first = series
+                    .iter()
+                    .next()
+                    .and_then(|sample| sample.ncp())
+                    .expect("NCP counters must be sampled");
+                assert!(
+                    ncp.get(Counter::ApsTxUnicastSuccess) > first.get(Counter::ApsTxUnicastSuccess)
+                );
+                assert!(series.metrics().any(|metric| {
+                    metric.origin() == Origin::Device(destination())
+                        && metric.name() == "mac_tx_unicast_retry"
+                        && metric.value() == 3
+                }));
+            });
+    }
+
+    #[test]
+    fn raises_a_zero_interval_to_the_minimum() {
+        Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("runtime must be available")
+            .block_on(async {
+                let sampling = Sampler::new(Duration::ZERO, SAMPLES).spawn(coordinator());
+
+                sleep(MIN_INTERVAL * 5).await;
+                assert_eq!(sampling.time_series().len(), SAMPLES.get());
+            });
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::VecDeque;
+use std::num::NonZeroUsize;
+
+use super::{Metric, Sample};
+
+/// A bounded series of samples in chronological order.
+///
+/// Pushing a sample into a full series discards the oldest one.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimeSeries {
+    capacity: NonZeroUsize,
+    samples: VecDeque<Sample>,
+}
+
+impl TimeSeries {
+    /// Create an empty series holding at most `capacity` samples.
+    #[must_use]
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            capacity,
+            samples: VecDeque::with_capacity(capacity.get()),
+        }
+    }
+
+    /// Append a sample, discarding the oldest one if the series is full.
+    pub fn push(&mut self, sample: Sample) {
+        if self.samples.len() == self.capacity.get() {
+            self.samples.pop_front();
+        }
+
+        self.samples.push_back(sample);
+    }
+
+    /// Return the maximum number of samples.
+    #[must_use]
+    pub const fn capacity(&self) -> NonZeroUsize {
+        self.capacity
+    }
+
+    /// Return the number of samples.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Return whether the series contains no sample.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Return the most recent sample.
+    #[must_use]
+    pub fn latest(&self) -> Option<&Sample> {
+        self.samples.back()
+    }
+
+    /// Iterate over the samples from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &Sample> {
+        self.samples.iter()
+    }
+
+    /// Iterate over the counter values of all samples from oldest to newest.
+    pub fn metrics(&self) -> impl Iterator<Item = Metric> + '_ {
+        self.samples.iter().flat_map(Sample::metrics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::time::{Duration, SystemTime};
+
+    use zb_hw::{Counter, Counters};
+
+    use super::TimeSeries;
+    use crate::diagnostics::{Origin, Sample};
+
+    const CAPACITY: NonZeroUsize = NonZeroUsize::new(2).expect("capacity is non-zero");
+
+    fn sample(seconds: u64, retries: u32) -> Sample {
+        Sample::new(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+            .with_ncp(Counters::new().with_counter(Counter::MacTxUnicastRetry, retries))
+    }
+
+    #[test]
+    fn discards_the_oldest_sample_when_full() {
+        let mut series = TimeSeries::new(CAPACITY);
+
+        series.push(sample(0, 1));
+        series.push(sample(60, 2));
+        series.push(sample(120, 3));
+
+        assert_eq!(series.len(), CAPACITY.get());
+        assert_eq!(series.iter().next(), Some(&sample(60, 2)));
+        assert_eq!(series.latest(), Some(&sample(120, 3)));
+    }
+
+    #[test]
+    fn flattens_samples_into_metrics() {
+        let mut series = TimeSeries::new(CAPACITY);
+
+        series.push(sample(0, 1));
+        series.push(sample(60, 2));
+
+        let metrics: Vec<_> = series
+            .metrics()
+            .map(|metric| (metric.origin(), metric.name(), metric.value()))
+            .collect();
+        assert_eq!(
+            metrics,
+            [
+                (Origin::Ncp, "mac_tx_unicast_retry", 1),
+                (Origin::Ncp, "mac_tx_unicast_retry", 2),
+            ]
+        );
+    }
+}
@@ -8,7 +8,7 @@ use tokio::sync::mpsc::error::SendError;
 use tokio::sync::oneshot::error::RecvError;
 use tokio::time::error::Elapsed;
 use zb_aps::apsde::RequestDestination;
-use zb_core::IeeeAddress;
+use zb_core::{IeeeAddress, short_id};
 
 pub use self::optional::Optional;
 pub use self::status_ext::StatusExt;
@@ -39,6 +39,10 @@ pub enum Error {
     #[error("Unknown device: {0}")]
     UnknownDevice(IeeeAddress),
 
+    /// A sleepy device left the network before its queued request was transmitted.
+    #[error("Sleepy device {0} left the network before its queued request was sent")]
+    SleepyDeviceLeft(short_id::Device),
+
     /// Invalid application endpoint.
     #[error("Invalid application endpoint: {0:#04X}")]
     InvalidApplicationEndpoint(u8),
@@ -71,6 +75,15 @@ pub enum Error {
     #[error("No protocol transaction sequence is available")]
     TransactionSequenceExhausted,
 
+    /// An APS payload exceeds what the destination or the NCP can carry.
+    #[error("APS payload of {length} octets exceeds the limit of {limit} octets")]
+    PayloadTooLarge {
+        /// Length of the rejected payload in octets.
+        length: usize,
+        /// Largest payload the transmission allows in octets.
+        limit: usize,
+    },
+
     /// Every APS counter is pending or quarantined.
     #[error("No APS counter is available")]
     ApsCounterExhausted,
@@ -79,6 +92,10 @@ pub enum Error {
     #[error("Protocol response timed out")]
     ProtocolResponseTimeout,
 
+    /// A device reported more alarm log entries than a drain accepts.
+    #[error("Alarm log exceeds {0} entries")]
+    AlarmLogTooLong(usize),
+
     /// A coordinator-managed OTA update failed.
     #[error("OTA update failed: {0}")]
     Ota(#[from] crate::ota::UpdateError),
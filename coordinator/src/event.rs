@@ -1,12 +1,20 @@
 use zb_aps::apsde::DataIndication;
 use zb_zcl::{Cluster, Frame};
 
+pub use self::alarm::{Alarm as AlarmEvent, AlarmKey};
+pub use self::battery::Battery as BatteryEvent;
 pub use self::device::{Device, KeepAlive};
 pub use self::network::{Error as NetworkError, Network};
+pub use self::ota::Ota as OtaEvent;
+pub use self::ota_client::OtaClient as OtaClientEvent;
 pub use self::sink::EventSink;
 
+mod alarm;
+mod battery;
 mod device;
 mod network;
+mod ota;
+mod ota_client;
 mod sink;
 
 /// Event emitted by the coordinator runtime.
@@ -18,6 +26,18 @@ pub enum Event {
     /// Device lifecycle or activity notification.
     Device(Device),
 
+    /// OTA repository transfer notification.
+    Ota(OtaEvent),
+
+    /// Local OTA client notification.
+    OtaClient(OtaClientEvent),
+
+    /// Alarm raised by a device's Alarms cluster server.
+    Alarm(AlarmEvent),
+
+    /// Battery state change of a device monitored by a [`battery::Monitor`](crate::battery::Monitor).
+    Battery(BatteryEvent),
+
     /// Unmatched inbound ZCL indication.
     Zcl {
         /// Normalized APSDE indication containing the parsed ZCL frame and receive metadata.
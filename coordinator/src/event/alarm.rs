@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use zb_aps::apsde::IndividualEndpoint;
+use zb_core::short_id;
+
+/// Alarm condition identified by the cluster that raised it and its cluster-specific code.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub struct AlarmKey {
+    cluster_id: u16,
+    code: u8,
+}
+
+impl AlarmKey {
+    /// Create an alarm key from the raw source cluster identifier and alarm code.
+    #[must_use]
+    pub const fn new(cluster_id: u16, code: u8) -> Self {
+        Self { cluster_id, code }
+    }
+
+    /// Return the identifier of the cluster whose attribute raised the alarm.
+    #[must_use]
+    pub const fn cluster_id(self) -> u16 {
+        self.cluster_id
+    }
+
+    /// Return the cluster-specific alarm code.
+    #[must_use]
+    pub const fn code(self) -> u8 {
+        self.code
+    }
+}
+
+/// Alarm notification received from a device's Alarms cluster server.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Alarm {
+    device: short_id::Device,
+    endpoint: IndividualEndpoint,
+    key: AlarmKey,
+}
+
+impl Alarm {
+    /// Create an alarm notification from the sending device, endpoint, and alarm key.
+    #[must_use]
+    pub const fn new(
+        device: short_id::Device,
+        endpoint: IndividualEndpoint,
+        key: AlarmKey,
+    ) -> Self {
+        Self {
+            device,
+            endpoint,
+            key,
+        }
+    }
+
+    /// Return the sending device's NWK short address.
+    #[must_use]
+    pub const fn device(self) -> short_id::Device {
+        self.device
+    }
+
+    /// Return the sending Alarms cluster endpoint.
+    #[must_use]
+    pub const fn endpoint(self) -> IndividualEndpoint {
+        self.endpoint
+    }
+
+    /// Return the source cluster and alarm code of the raised alarm.
+    #[must_use]
+    pub const fn key(self) -> AlarmKey {
+        self.key
+    }
+}
@@ -0,0 +1,54 @@
+use zb_aps::apsde::NetworkDestination;
+
+use crate::battery::{Level, State};
+
+/// Change of the battery state of a monitored device endpoint.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Battery {
+    destination: NetworkDestination,
+    level: Level,
+    previous: State,
+    state: State,
+}
+
+impl Battery {
+    /// Create a battery state change from the monitored endpoint, its level, and both states.
+    #[must_use]
+    pub const fn new(
+        destination: NetworkDestination,
+        level: Level,
+        previous: State,
+        state: State,
+    ) -> Self {
+        Self {
+            destination,
+            level,
+            previous,
+            state,
+        }
+    }
+
+    /// Return the monitored device endpoint.
+    #[must_use]
+    pub const fn destination(self) -> NetworkDestination {
+        self.destination
+    }
+
+    /// Return the battery level that caused the change.
+    #[must_use]
+    pub const fn level(self) -> Level {
+        self.level
+    }
+
+    /// Return the battery state before the change.
+    #[must_use]
+    pub const fn previous(self) -> State {
+        self.previous
+    }
+
+    /// Return the new battery state.
+    #[must_use]
+    pub const fn state(self) -> State {
+        self.state
+    }
+}
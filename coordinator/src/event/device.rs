@@ -39,6 +39,11 @@ pub enum Device {
 
     /// A device sent a Keep-Alive packet from the contained short address and endpoint.
     KeepAlive(KeepAlive),
+
+    /// A device has not been heard from for longer than the silence threshold.
+    ///
+    /// The event is emitted once until the device is heard from again.
+    Unresponsive(short_id::Device),
 }
 
 impl KeepAlive {
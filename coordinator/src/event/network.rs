@@ -17,6 +17,11 @@ pub enum Network {
     /// Joining has been closed.
     Closed,
 
+    /// The coordinator has been re-wired to a restarted hardware session.
+    ///
+    /// Requests pending before the restart have failed.
+    Restarted,
+
     /// A network-level error occurred.
     Error(Error),
 }
@@ -30,7 +35,10 @@ pub enum Error {
     #[error("{0}")]
     Route(#[from] RouteError),
 
-    /// The hardware event stream closed and the coordinator runtime can no longer make progress.
+    /// The hardware event stream closed.
+    ///
+    /// Pending requests have failed. The coordinator cannot make progress until it is re-wired to
+    /// a restarted hardware session through [`crate::Coordinator::restart`].
     #[error("hardware event stream closed")]
     HardwareEventStreamClosed,
 }
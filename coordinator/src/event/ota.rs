@@ -0,0 +1,36 @@
+use zb_aps::apsde::IndividualEndpoint;
+use zb_core::FullAddress;
+use zb_zcl::ota_upgrade::ImageId;
+
+use crate::ota::UpdateResult;
+
+/// OTA repository transfer event.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Ota {
+    /// A device queried for a newer image and the repository started serving it.
+    TransferStarted {
+        /// Complete address of the querying device.
+        target: FullAddress,
+
+        /// Remote OTA client endpoint.
+        endpoint: IndividualEndpoint,
+
+        /// Repository image offered to the device.
+        image: ImageId,
+    },
+
+    /// A transfer started by the repository reached its terminal outcome.
+    TransferFinished {
+        /// Complete address of the updated device.
+        target: FullAddress,
+
+        /// Remote OTA client endpoint.
+        endpoint: IndividualEndpoint,
+
+        /// Repository image offered to the device.
+        image: ImageId,
+
+        /// Terminal result of the transfer.
+        result: UpdateResult,
+    },
+}
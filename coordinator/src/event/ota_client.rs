@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use zb_zcl::ota_upgrade::ImageId;
+
+use crate::ota::DownloadFailure;
+
+/// Local OTA client event.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OtaClient {
+    /// The upstream server offered a new image and the client started downloading it.
+    ImageAvailable {
+        /// Offered image.
+        image: ImageId,
+
+        /// Size of the complete OTA file in bytes.
+        image_size: u32,
+    },
+
+    /// A download ended without a verified image.
+    DownloadFailed {
+        /// Image that was being downloaded.
+        image: ImageId,
+
+        /// Reason why the download was abandoned.
+        failure: DownloadFailure,
+    },
+
+    /// The server answered the successful download with an activation time.
+    UpgradeScheduled {
+        /// Downloaded image.
+        image: ImageId,
+
+        /// Delay until activation, or `None` while the server asks the client to wait.
+        delay: Option<Duration>,
+    },
+
+    /// The downloaded image is due for activation.
+    ActivationDue {
+        /// Downloaded image.
+        image: ImageId,
+    },
+}
@@ -3,16 +3,42 @@
 //! This library provides a fully abstracted interface to expose an interface to communicate with
 //! a Zigbee transceiver regardless of the underlying hardware.
 //!
+//! The coordinator is started with a [`CoordinatorConfig`] that selects inbox capacities, protocol
+//! timeouts, and other runtime limits.
 //! The application supplies a `tokio::sync::mpsc::Sender<Event>` at startup to receive coordinator
 //! [`Event`] values. Delivery is non-blocking: an event is dropped if that channel is full or
 //! closed, so application backpressure cannot stall protocol processing. Discovery, binding,
 //! address resolution, and persistence are application-owned workflows built from traits such as
 //! [`Node`], [`Endpoints`], [`Binding`], [`Leaving`], [`AddressTranslation`], [`Zcl`], and [`Zdp`].
-//! Closing the hardware event stream is fatal: protocol actors fail pending work and stop, and the
-//! coordinator emits [`NetworkError::HardwareEventStreamClosed`]. Applications must start a new
-//! coordinator with a live hardware event stream after that boundary.
+//! Network formation, leaving, and trust-center policy are available through [`api::Network`].
+//! Closing the hardware event stream fails pending work and emits
+//! [`NetworkError::HardwareEventStreamClosed`]. After restarting the hardware through
+//! [`zb_hw::NcpHandle::restart`], [`Coordinator::restart`] re-wires every coordinator handle to the
+//! new hardware event stream and emits [`Network::Restarted`].
+//! The coordinator tracks when each device was last seen, its link quality, and its transmission
+//! failures; [`Activity`] queries the table, and [`Device::Unresponsive`] reports devices that stay
+//! silent for too long.
+//! Alarm notifications are reported as [`AlarmEvent`]s keyed by source cluster and alarm code, and
+//! [`Alarms`] resets device alarms and drains their alarm logs.
+//! A [`battery::Monitor`] configures battery reporting on enrolled devices and reports threshold
+//! crossings as [`BatteryEvent`]s. An [`automation::Engine`] runs serializable occupancy and
+//! illuminance rules that switch groups on and off.
+//! [`SleepyDevices`] holds ZCL commands for sleepy end devices until they are heard from, and the
+//! coordinator answers their Poll Control Check-ins to keep them awake while the queue drains.
+//! [`Diagnostics`] reads the counters of the NCP and of remote Diagnostics clusters, and a
+//! [`diagnostics::Sampler`] collects both periodically into a bounded time series for metrics
+//! export.
 //! The built-in [`Ota`] service validates complete OTA image files and automatically serves the
-//! OTA Upgrade cluster exchange for individually scheduled device endpoints.
+//! OTA Upgrade cluster exchange for individually scheduled device endpoints. Images registered in
+//! its repository are served to any eligible device that queries for a newer file version, and
+//! each such transfer is reported through [`OtaEvent`]. [`OtaImageVerifier`] checks an image's
+//! sub-elements and integrity code, and [`OtaImageBuilder`] creates OTA files from raw firmware.
+//! [`OtaClient`] updates the local node from an upstream OTA server, streaming verified downloads
+//! into a caller-supplied sink and reporting their progress through [`OtaClientEvent`].
+//! [`OtaCampaign`]s roll one image out to a device set or a group, staggering notifications and
+//! capping concurrent transfers and airtime.
+//! [`Touchlink`] scans for devices in close proximity over inter-PAN frames, and asks the
+//! [`TouchlinkTarget`]s found to identify themselves or reset to factory defaults.
 //!
 //! The hardware NCP is responsible for providing its complete local endpoint descriptors through
 //! [`zb_hw::NcpHandle::get_endpoints`]. The coordinator queries those descriptors when serving ZDP
@@ -22,43 +48,64 @@
 //! ZCL transmissions await a deferred APS completion outside the protocol actor. ZCL and ZDP
 //! communication methods return a protocol-specific [`ZclResponse`] or [`ZdpResponse`] that first
 //! completes APS transmission and then waits for the correlated command. All operations report
-//! failures through the coordinator's [`Error`] type.
-
-use const_env::env_item;
+//! failures through the coordinator's [`Error`] type. Failed transmissions of communicating requests
+//! are retried with the same transaction sequence according to the [`retry::RetryPolicy`]
+//! configured through [`Retries`].
 
 pub use self::api::{
-    AddressTranslation, Attributes, Binding, CancellableOtaUpdate, Channel, ChannelMask,
-    ColorControl, Endpoints, FoundNetwork, Groups, Joining, Leaving, Level, LocalNode,
-    NetworkDescriptor, Node, OnOff, Ota, ReadAttributeResult, Routing, ScanDuration,
-    ScannedChannel, Scanning, SimpleDescriptor, WriteAttributeResult, Zcl, ZclResponse, Zdp,
-    ZdpResponse,
+    Activity, AddressTranslation, AlarmLogEntry, Alarms, Attributes, Binding, CancellableOtaUpdate,
+    Channel, ChannelMask, ColorControl, Diagnostics, Endpoints, Formation, FoundNetwork, Groups,
+    JoinPolicy, Joining, KeyNegotiation, Leaving, Level, LocalNode, NetworkDescriptor,
+    NetworkParameters, Node, ObservableOtaUpdate, OnOff, Ota, OtaClient, ReadAttributeResult,
+    Retries, Routing, ScanDuration, ScannedChannel, Scanning, SimpleDescriptor, SleepyDevices,
+    Touchlink, TrustCenterPolicy, WriteAttributeResult, Zcl, ZclResponse, Zdp, ZdpResponse,
 };
+pub use self::config::CoordinatorConfig;
 pub use self::coordinator::Coordinator;
 pub use self::error::{Error, Optional, StatusExt};
-pub use self::event::{Device, Event, KeepAlive, Network, NetworkError};
+pub use self::event::{
+    AlarmEvent, AlarmKey, BatteryEvent, Device, Event, KeepAlive, Network, NetworkError,
+    OtaClientEvent, OtaEvent,
+};
 pub use self::ota::{
-    BaseHeaderBytes as OtaBaseHeaderBytes, FieldControl as OtaFieldControl, Header as OtaHeader,
-    HeaderString as OtaHeaderString, Image as OtaImage, Message as OtaMessage, ParseImage,
-    ParseImageError, UpdateError as OtaUpdateError, UpdateResult as OtaUpdateResult,
-    UpdateTimeouts as OtaUpdateTimeouts,
+    Activation as OtaActivation, BaseHeaderBytes as OtaBaseHeaderBytes, BuildImageError,
+    Campaign as OtaCampaign, CampaignControl as OtaCampaignControl,
+    CampaignHandle as OtaCampaignHandle, CampaignReport as OtaCampaignReport,
+    CampaignSummary as OtaCampaignSummary, ClientConfig as OtaClientConfig,
+    CryptoSuite as OtaCryptoSuite, DeviceOutcome as OtaDeviceOutcome,
+    DeviceReport as OtaDeviceReport, Download as OtaDownload,
+    DownloadFailure as OtaDownloadFailure, EcdsaSignature as OtaEcdsaSignature,
+    FieldControl as OtaFieldControl, Header as OtaHeader, HeaderString as OtaHeaderString,
+    Image as OtaImage, ImageBuilder as OtaImageBuilder, ImageVerifier as OtaImageVerifier,
+    IntegrityCode as OtaIntegrityCode, Message as OtaMessage, ParseImage, ParseImageError,
+    Progress as OtaProgress, RepositoryImage as OtaRepositoryImage,
+    SigningCertificate as OtaSigningCertificate, SubElement as OtaSubElement,
+    UpdateError as OtaUpdateError, UpdateResult as OtaUpdateResult,
+    UpdateTimeouts as OtaUpdateTimeouts, VerifyImageError,
 };
 pub use self::response::CommunicationResponse;
+pub use self::touchlink::Target as TouchlinkTarget;
 
-mod api;
+pub mod activity;
+pub mod api;
 mod aps;
+pub mod automation;
+pub mod backup;
+pub mod battery;
+mod config;
 mod coordinator;
 mod correlation;
+pub mod diagnostics;
 mod error;
 mod event;
 mod mux;
 pub mod ota;
 mod response;
+pub mod retry;
+mod sleepy;
+pub mod touchlink;
 mod zcl;
 mod zdp;
 
-/// Capacity of each coordinator actor inbox.
-#[env_item("ZIGBEE_COORDINATOR_MPSC_CHANNEL_SIZE")]
+/// Default capacity of each actor inbox and of the coordinator's internal channels.
 const MPSC_CHANNEL_SIZE: usize = 128;
-
-/// Default maximum number of concurrent destination OTA transfer tasks.
-const DEFAULT_OTA_UPDATE_TASK_LIMIT: usize = MPSC_CHANNEL_SIZE;
@@ -3,15 +3,22 @@ use log::{trace, warn};
 use tokio::spawn;
 use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::mpsc::{Receiver, Sender};
-use zb_aps::apsde::DataIndication;
+use tokio::task::AbortHandle;
+use zb_aps::apsde::{DataIndication, IndicationMetadata};
+use zb_core::{FullAddress, short_id};
 use zb_hw::{
     ApsdeEvent as HardwareApsdeEvent, DeviceEvent as HardwareDeviceEvent, Event as HardwareEvent,
     NetworkEvent as HardwareNetworkEvent,
 };
+use zb_zcl::Cluster as ZclCluster;
+use zb_zcl::poll_control::Command as PollControlCommand;
+use zb_zdp::{Command as ZdpCommand, DeviceAndServiceDiscovery};
 
 use self::aps_payload::ApsPayload;
 use crate::event::EventSink;
-use crate::{Device, Event, Network, NetworkError, aps, ota, zcl, zdp};
+use crate::{
+    Device, Event, Network, NetworkError, activity, aps, ota, sleepy, touchlink, zcl, zdp,
+};
 
 mod aps_payload;
 
@@ -23,16 +30,26 @@ pub struct Mux {
     ota: Sender<ota::Message>,
     zcl: Sender<zcl::Message>,
     zdp: Sender<zdp::Message>,
+    activity: activity::Table,
+    sleepy: sleepy::Queue,
+    inter_pan: touchlink::InterPan,
 }
 
 impl Mux {
     /// Create a new multiplexer.
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "the multiplexer routes hardware events to every protocol actor"
+    )]
     pub const fn new(
         events: EventSink,
         aps: aps::Aps,
         ota: Sender<ota::Message>,
         zcl: Sender<zcl::Message>,
         zdp: Sender<zdp::Message>,
+        activity: activity::Table,
+        sleepy: sleepy::Queue,
+        inter_pan: touchlink::InterPan,
     ) -> Self {
         Self {
             events,
@@ -40,22 +57,19 @@ impl Mux {
             ota,
             zcl,
             zdp,
+            activity,
+            sleepy,
+            inter_pan,
         }
     }
 
-    /// Start the multiplexer.
-    pub fn spawn<T, K>(
-        hw_events: Receiver<HardwareEvent<T, K>>,
-        events: EventSink,
-        aps: aps::Aps,
-        ota_tx: Sender<ota::Message>,
-        zcl_tx: Sender<zcl::Message>,
-        zdp_tx: Sender<zdp::Message>,
-    ) where
+    /// Start the multiplexer and return a handle for aborting it.
+    pub fn spawn<T, K>(self, hw_events: Receiver<HardwareEvent<T, K>>) -> AbortHandle
+    where
         T: Send + 'static,
         K: Send + 'static,
     {
-        spawn(Self::new(events, aps, ota_tx, zcl_tx, zdp_tx).run(hw_events));
+        spawn(self.run(hw_events)).abort_handle()
     }
 
     /// Run the multiplexer.
@@ -71,43 +85,55 @@ impl Mux {
     }
 
     async fn hardware_event_stream_closed(&self) {
-        warn!("Hardware event stream closed; stopping coordinator protocol actors");
+        warn!("Hardware event stream closed; failing pending coordinator work until restart");
         self.events.emit(Event::Network(Network::Error(
             NetworkError::HardwareEventStreamClosed,
         )));
+        self.hardware_unavailable().await;
+    }
+
+    /// Make every protocol actor fail the work that depends on the previous hardware session.
+    ///
+    /// Each actor is notified by its own task, so one full inbox cannot delay the others.
+    pub async fn hardware_unavailable(&self) {
         let aps = self.aps.clone();
-        let aps_shutdown = spawn(async move {
+        let aps_notification = spawn(async move {
             aps.hardware_unavailable().await.unwrap_or_else(|error| {
-                trace!("Failed to stop APS actor after hardware stream closure: {error}");
+                trace!("Failed to notify APS actor that the hardware is unavailable: {error}");
             });
         });
         let zcl = self.zcl.clone();
-        let zcl_shutdown = spawn(async move {
+        let zcl_notification = spawn(async move {
             zcl.send(zcl::Message::HardwareUnavailable)
                 .await
                 .unwrap_or_else(|error| {
-                    trace!("Failed to stop ZCL actor after hardware stream closure: {error}");
+                    trace!("Failed to notify ZCL actor that the hardware is unavailable: {error}");
                 });
         });
         let zdp = self.zdp.clone();
-        let zdp_shutdown = spawn(async move {
+        let zdp_notification = spawn(async move {
             zdp.send(zdp::Message::HardwareUnavailable)
                 .await
                 .unwrap_or_else(|error| {
-                    trace!("Failed to stop ZDP actor after hardware stream closure: {error}");
+                    trace!("Failed to notify ZDP actor that the hardware is unavailable: {error}");
                 });
         });
         let ota = self.ota.clone();
-        let ota_shutdown = spawn(async move {
+        let ota_notification = spawn(async move {
             ota.send(ota::Message::HardwareUnavailable)
                 .await
                 .unwrap_or_else(|error| {
-                    trace!("Failed to stop OTA actor after hardware stream closure: {error}");
+                    trace!("Failed to notify OTA actor that the hardware is unavailable: {error}");
                 });
         });
-        for shutdown in [aps_shutdown, zcl_shutdown, zdp_shutdown, ota_shutdown] {
-            shutdown.await.unwrap_or_else(|error| {
-                trace!("Terminal notification task failed: {error}");
+        for notification in [
+            aps_notification,
+            zcl_notification,
+            zdp_notification,
+            ota_notification,
+        ] {
+            notification.await.unwrap_or_else(|error| {
+                trace!("Hardware unavailability notification task failed: {error}");
             });
         }
     }
@@ -117,6 +143,7 @@ impl Mux {
             HardwareEvent::Network(event) => self.multiplex_network_event(event).await,
             HardwareEvent::Device(event) => self.multiplex_device_event(&event),
             HardwareEvent::Apsde(event) => self.multiplex_apsde_event(event).await,
+            HardwareEvent::InterPan(indication) => self.inter_pan.indicate(indication),
             _ => trace!("Ignoring unsupported hardware event"),
         }
     }
@@ -167,10 +194,12 @@ impl Mux {
         match event {
             HardwareDeviceEvent::Joined(address) => {
                 trace!("Device joined: {address}");
+                self.activity.joined(*address);
                 self.events.emit(Event::Device(Device::Joined(*address)));
             }
             HardwareDeviceEvent::Rejoined { address, secured } => {
                 trace!("Device joined: {address} (secured: {secured})");
+                self.activity.joined(*address);
                 self.events.emit(Event::Device(Device::Rejoined {
                     address: *address,
                     secured: *secured,
@@ -178,6 +207,8 @@ impl Mux {
             }
             HardwareDeviceEvent::Left(address) => {
                 trace!("Device left: {address}");
+                self.activity.left(*address);
+                self.sleepy.left(address.short_id());
                 self.events.emit(Event::Device(Device::Left(*address)));
             }
             _ => trace!("Ignoring unsupported hardware device event"),
@@ -198,6 +229,7 @@ impl Mux {
                     "APS data confirmation for counter {counter}, destination {:?}: {status}",
                     confirmation.destination()
                 );
+                self.activity.confirmed(confirmation.destination(), status);
                 self.aps
                     .confirm(counter, status)
                     .await
@@ -219,9 +251,12 @@ impl Mux {
             return;
         }
 
+        self.activity.received(indication.metadata());
         let (metadata, asdu) = indication.into_parts();
         match ApsPayload::parse(&metadata, asdu) {
             Ok(payload) => {
+                self.announced(&payload);
+                self.wake(&metadata, &payload);
                 self.forward_received_message(DataIndication::new(metadata, payload))
                     .await;
             }
@@ -229,6 +264,40 @@ impl Mux {
         }
     }
 
+    /// Move the activity of a device that announced itself under a new short address.
+    fn announced(&self, payload: &ApsPayload) {
+        if let ApsPayload::Zdp(frame) = payload
+            && let ZdpCommand::DeviceAndServiceDiscovery(DeviceAndServiceDiscovery::DeviceAnnce(
+                device_annce,
+            )) = frame.data()
+            && let Some(device) = short_id::Device::new(device_annce.nwk_addr())
+        {
+            self.activity
+                .joined(FullAddress::new(device_annce.ieee_addr(), device));
+        }
+    }
+
+    /// Release the requests queued for the sending device, answering the Poll Control Check-ins of
+    /// sleepy devices.
+    fn wake(&self, metadata: &IndicationMetadata<(), ()>, payload: &ApsPayload) {
+        if let ApsPayload::Zcl(frame) = payload
+            && let ZclCluster::PollControl(PollControlCommand::CheckIn(_)) = frame.payload()
+        {
+            if let Some(check_in) = sleepy::CheckIn::new(metadata, frame.header().seq()) {
+                self.sleepy.checked_in(check_in);
+            }
+            return;
+        }
+
+        if let Some(device) = metadata
+            .source()
+            .network_address()
+            .and_then(|address| short_id::Device::new(address.as_u16()))
+        {
+            self.sleepy.awake(device);
+        }
+    }
+
     async fn forward_received_message(&self, indication: DataIndication<ApsPayload, (), ()>) {
         let (metadata, payload) = indication.into_parts();
 
@@ -306,7 +375,9 @@ mod tests {
     use super::Mux;
     use crate::aps::{Aps, Message as ApsMessage};
     use crate::event::EventSink;
-    use crate::{Event, MPSC_CHANNEL_SIZE, Network, NetworkError, ota, zcl, zdp};
+    use crate::{
+        Event, MPSC_CHANNEL_SIZE, Network, NetworkError, activity, ota, sleepy, touchlink, zcl, zdp,
+    };
 
     const TEST_TIMEOUT: Duration = Duration::from_millis(100);
     const APPLICATION_EVENT_CHANNEL_SIZE: usize = 1;
@@ -491,6 +562,9 @@ mod tests {
                     ota_messages,
                     zcl_messages,
                     zdp_messages,
+                    activity::Table::new(None),
+                    sleepy::Queue::new(channel(1).0),
+                    touchlink::InterPan::default(),
                 );
                 let confirmation = DataConfirm::new(
                     Destination::Network {
@@ -543,6 +617,9 @@ mod tests {
                     ota_messages,
                     zcl_messages,
                     zdp_messages,
+                    activity::Table::new(None),
+                    sleepy::Queue::new(channel(1).0),
+                    touchlink::InterPan::default(),
                 );
                 let source_endpoint =
                     IndividualEndpoint::new(Endpoint::Data).expect("data endpoint is individual");
@@ -602,7 +679,7 @@ mod tests {
     }
 
     #[test]
-    fn hardware_event_stream_closure_stops_every_actor_and_emits_a_failure() {
+    fn hardware_event_stream_closure_notifies_every_actor_and_emits_a_failure() {
         Runtime::new()
             .expect("runtime must be available")
             .block_on(async {
@@ -618,6 +695,9 @@ mod tests {
                     ota_messages,
                     zcl_messages,
                     zdp_messages,
+                    activity::Table::new(None),
+                    sleepy::Queue::new(channel(1).0),
+                    touchlink::InterPan::default(),
                 );
                 drop(hardware_events);
 
@@ -649,7 +729,7 @@ mod tests {
     }
 
     #[test]
-    fn full_aps_inbox_does_not_delay_other_terminal_notifications() {
+    fn full_aps_inbox_does_not_delay_other_unavailability_notifications() {
         Runtime::new()
             .expect("runtime must be available")
             .block_on(async {
@@ -668,9 +748,12 @@ mod tests {
                     ota_messages,
                     zcl_messages,
                     zdp_messages,
+                    activity::Table::new(None),
+                    sleepy::Queue::new(channel(1).0),
+                    touchlink::InterPan::default(),
                 );
 
-                let shutdown = tokio::spawn(async move {
+                let closure = tokio::spawn(async move {
                     mux.hardware_event_stream_closed().await;
                 });
 
@@ -689,8 +772,8 @@ mod tests {
                     ));
                 })
                 .await
-                .expect("one full actor inbox must not delay the other terminal notifications");
-                assert!(!shutdown.is_finished());
+                .expect("one full actor inbox must not delay the other notifications");
+                assert!(!closure.is_finished());
 
                 assert!(matches!(
                     aps_receiver.recv().await,
@@ -700,7 +783,9 @@ mod tests {
                     aps_receiver.recv().await,
                     Some(ApsMessage::HardwareUnavailable)
                 ));
-                shutdown.await.expect("mux shutdown must complete");
+                closure
+                    .await
+                    .expect("stream closure handling must complete");
             });
     }
 
@@ -722,6 +807,9 @@ mod tests {
                 ota_messages,
                 zcl_messages,
                 zdp_messages,
+                activity::Table::new(None),
+                sleepy::Queue::new(channel(1).0),
+                touchlink::InterPan::default(),
             ),
             aps_receiver,
             zcl_receiver,
@@ -12,20 +12,33 @@ use zb_aps::apsde::{
 use zb_core::{Cluster, Direction, Profile};
 use zb_zcl::{Command, Directed, Scope, Scoped, UnsequencedFrame};
 
+pub use self::campaign::{
+    Campaign, CampaignControl, CampaignHandle, CampaignReport, CampaignSummary, DeviceOutcome,
+    DeviceReport,
+};
+pub use self::client::{Activation, ClientConfig, Download, DownloadFailure};
 pub use self::image::{
-    BaseHeaderBytes, FieldControl, Header, HeaderString, Image, ParseImage, ParseImageError,
+    BaseHeaderBytes, BuildImageError, CryptoSuite, EcdsaSignature, FieldControl, Header,
+    HeaderString, Image, ImageBuilder, ImageVerifier, IntegrityCode, ParseImage, ParseImageError,
+    SigningCertificate, SubElement, VerifyImageError,
 };
 pub use self::message::{Message, UpdateError, UpdateResult};
+pub use self::progress::Progress;
+pub use self::repository::RepositoryImage;
 pub use self::server::Server;
 pub use self::timeouts::UpdateTimeouts;
-pub use self::update::CancellableOtaUpdate;
 pub(crate) use self::update::Update;
+pub use self::update::{CancellableOtaUpdate, ObservableOtaUpdate};
 use crate::aps::TransmissionResponse;
 use crate::{Error, zcl};
 
+mod campaign;
+mod client;
 mod image;
 mod message;
 mod page_transfer;
+mod progress;
+mod repository;
 mod server;
 mod state;
 mod timeouts;
@@ -165,20 +178,25 @@ mod tests {
         NetworkDestination, ReceivedDestination, Security, Source,
     };
     use zb_core::endpoint::Application;
-    use zb_core::{Cluster, Direction, Endpoint, FullAddress, IeeeAddress, Profile, short_id};
+    use zb_core::{
+        Cluster, Direction, Endpoint, FullAddress, GroupId, IeeeAddress, Profile, short_id,
+    };
     use zb_zcl::ota_upgrade::{
         Command as OtaCommand, ImageBlockRequest, ImageBlockResponse, ImageBlockResponsePayload,
-        ImageId, ImageNotify, ImageNotifyPayload, ImagePageRequest, QueryNextImageRequest,
-        QueryNextImageResponse, QueryResponse, QuerySpecificFileRequest, QuerySpecificFileResponse,
-        UpgradeEndRequest, UpgradeEndResponse, UpgradeEndStatus,
+        ImageId, ImageNotify, ImageNotifyPayload, ImagePageRequest, QueryJitter,
+        QueryNextImageRequest, QueryNextImageResponse, QueryResponse, QuerySpecificFileRequest,
+        QuerySpecificFileResponse, UpgradeEndRequest, UpgradeEndResponse, UpgradeEndStatus,
     };
     use zb_zcl::{Cluster as ZclCluster, Command, Frame, Header, Scope};
 
     use super::{
-        FieldControl, Image, Message, OTA_PROFILE, ParseImage, Request, Server, TEST_IEEE_ADDRESS,
-        TransmissionResponse, UpdateError, UpdateResult, UpdateTimeouts,
+        Campaign, CampaignControl, CampaignReport, CampaignSummary, DeviceOutcome, FieldControl,
+        Image, Message, OTA_PROFILE, ObservableOtaUpdate, ParseImage, Progress, RepositoryImage,
+        Request, Server, TEST_IEEE_ADDRESS, TransmissionResponse, Update, UpdateError,
+        UpdateResult, UpdateTimeouts,
     };
-    use crate::{Error, Ota, zcl};
+    use crate::event::EventSink;
+    use crate::{Error, Event, Ota, OtaEvent, zcl};
 
     const TEST_TIMEOUT: Duration = Duration::from_secs(1);
     const TEST_LIFECYCLE_TIMEOUT: Duration = Duration::from_millis(100);
@@ -187,6 +205,8 @@ mod tests {
     const IMAGE_TYPE: u16 = 0x5678;
     const FILE_VERSION: u32 = 0x0102_0304;
     const STACK_VERSION: u16 = 0x0002;
+    const HARDWARE_VERSION: u16 = 0x0001;
+    const BLOCK_SIZE: u8 = 4;
     const OTA_FILE_IDENTIFIER: u32 = 0x0bee_f11e;
     const SUPPORTED_HEADER_VERSION: u16 = 0x0100;
     const BASE_HEADER_LENGTH: usize = 56;
@@ -206,6 +226,10 @@ mod tests {
     const OTHER_IEEE_ADDRESS: IeeeAddress =
         IeeeAddress::new(0x00, 0x12, 0x4b, 0x00, 0x02, 0xdd, 0xee, 0xff);
     const ENDPOINT: Endpoint = Endpoint::Application(Application::MIN);
+    const CAMPAIGN_GROUP: u16 = 0x0042;
+    const CAMPAIGN_QUERY_JITTER: u8 = 10;
+    const CAMPAIGN_AIRTIME_LIMIT: std::num::NonZeroU32 =
+        std::num::NonZeroU32::new(1).expect("the test airtime limit is non-zero");
 
     enum ObservedZcl {
         Transmit {
@@ -217,6 +241,12 @@ mod tests {
         },
     }
 
+    struct ScheduledCampaign {
+        control: tokio::sync::watch::Sender<CampaignControl>,
+        summary: tokio::sync::watch::Receiver<CampaignSummary>,
+        completion: tokio::sync::oneshot::Receiver<Result<CampaignReport, UpdateError>>,
+    }
+
     struct ScheduledUpdate {
         completion: tokio::sync::oneshot::Receiver<UpdateResult>,
         _cancellation: tokio::sync::oneshot::Sender<()>,
@@ -273,16 +303,19 @@ mod tests {
             let destination = test_destination();
             let (completion, _completion_result) = tokio::sync::oneshot::channel();
             let (_cancellation, cancelled) = tokio::sync::oneshot::channel();
+            let image = test_image();
+            let (progress, _progress) = tokio::sync::watch::channel(Progress::new(image.len()));
 
             ota_sender
                 .send(Message::Update {
                     target: test_address(),
                     target_endpoint: test_target_endpoint(),
                     source_endpoint: test_source_endpoint(),
-                    image: test_image(),
+                    image,
                     timeouts: UpdateTimeouts::default(),
                     cancellation: cancelled,
                     completion,
+                    progress,
                 })
                 .await
                 .expect("OTA server is running");
@@ -816,11 +849,11 @@ mod tests {
     }
 
     #[test]
-    fn hardware_unavailability_fails_an_active_update_and_stops_the_server() {
+    fn hardware_unavailability_fails_an_active_update_and_keeps_the_server_running() {
         run_test(async {
             let (zcl_sender, mut zcl_receiver) = tokio::sync::mpsc::channel(TEST_CHANNEL_SIZE);
             let (ota_sender, server) = Server::test_new(zcl_sender, TEST_UPDATE_LIMIT);
-            let server = tokio::spawn(server.run());
+            tokio::spawn(server.run());
             let completion = update_via_api(ota_sender.clone(), test_image());
             receive_zcl(&mut zcl_receiver).await;
 
@@ -837,10 +870,12 @@ mod tests {
                 result,
                 Err(Error::Ota(UpdateError::HardwareEventStreamClosed))
             ));
-            timeout(TEST_TIMEOUT, server)
-                .await
-                .expect("OTA server did not stop after hardware became unavailable")
-                .expect("OTA server task completed normally");
+
+            let _completion = update_via_api(ota_sender, test_image());
+            assert!(matches!(
+                receive_zcl(&mut zcl_receiver).await,
+                ObservedZcl::Transmit { .. }
+            ));
         });
     }
 
@@ -913,11 +948,532 @@ mod tests {
         });
     }
 
+    #[test]
+    fn repository_serves_the_newest_eligible_image_to_an_unscheduled_query() {
+        run_test(async {
+            let (zcl_sender, mut zcl_receiver) = tokio::sync::mpsc::channel(TEST_CHANNEL_SIZE);
+            let (events, mut received_events) = tokio::sync::mpsc::channel(TEST_CHANNEL_SIZE);
+            let (ota_sender, server) = Server::test_new_with_events(
+                zcl_sender,
+                EventSink::new(events),
+                TEST_UPDATE_LIMIT,
+                TEST_IEEE_ADDRESS,
+            );
+            tokio::spawn(server.run());
+            let newest = test_image_with(None, FILE_VERSION + 1);
+            let newest_id = newest.id();
+            for image in [test_image(), newest] {
+                timeout(
+                    TEST_TIMEOUT,
+                    ota_sender.register_image(RepositoryImage::new(image)),
+                )
+                .await
+                .expect("OTA server confirms the registration")
+                .expect("repository image is registered");
+            }
+
+            let current_image = ImageId::new(MANUFACTURER_CODE, IMAGE_TYPE, FILE_VERSION - 1);
+            ota_sender
+                .send(incoming(
+                    TEST_SEQUENCE_NUMBER,
+                    QueryNextImageRequest::new(current_image, None),
+                ))
+                .await
+                .expect("OTA server is running");
+            let (sequence_number, bytes) = reply_bytes(receive_zcl(&mut zcl_receiver).await);
+            assert_eq!(sequence_number, TEST_SEQUENCE_NUMBER);
+            let response = QueryNextImageResponse::from_le_stream(bytes.into_iter())
+                .expect("valid Query Next Image Response");
+            assert!(matches!(
+                response.response(),
+                QueryResponse::Success { image, .. } if image == newest_id
+            ));
+            assert!(matches!(
+                received_events.try_recv(),
+                Ok(Event::Ota(OtaEvent::TransferStarted { target, image, .. }))
+                    if target == test_address() && image == newest_id
+            ));
+
+            ota_sender
+                .send(incoming(
+                    TEST_SEQUENCE_NUMBER,
+                    UpgradeEndRequest::new(UpgradeEndStatus::Success, newest_id),
+                ))
+                .await
+                .expect("OTA server is running");
+            receive_zcl(&mut zcl_receiver).await;
+            let event = timeout(TEST_TIMEOUT, received_events.recv())
+                .await
+                .expect("repository transfer reports its outcome");
+            assert!(matches!(
+                event,
+                Some(Event::Ota(OtaEvent::TransferFinished { image, result: Ok(()), .. }))
+                    if image == newest_id
+            ));
+        });
+    }
+
+    #[test]
+    fn repository_offers_no_image_to_ineligible_devices() {
+        run_test(async {
+            let (zcl_sender, mut zcl_receiver) = tokio::sync::mpsc::channel(TEST_CHANNEL_SIZE);
+            let (ota_sender, server) = Server::test_new(zcl_sender, TEST_UPDATE_LIMIT);
+            tokio::spawn(server.run());
+            let restricted = RepositoryImage::new(test_image())
+                .with_ieee_addresses([OTHER_IEEE_ADDRESS])
+                .with_hardware_versions([HARDWARE_VERSION]);
+            ota_sender
+                .register_image(restricted)
+                .await
+                .expect("repository image is registered");
+
+            let outdated = ImageId::new(MANUFACTURER_CODE, IMAGE_TYPE, FILE_VERSION - 1);
+            let current = ImageId::new(MANUFACTURER_CODE, IMAGE_TYPE, FILE_VERSION);
+            for request in [
+                QueryNextImageRequest::new(outdated, Some(HARDWARE_VERSION)),
+                QueryNextImageRequest::new(current, Some(HARDWARE_VERSION)),
+            ] {
+                ota_sender
+                    .send(incoming(TEST_SEQUENCE_NUMBER, request))
+                    .await
+                    .expect("OTA server is running");
+                let (_, bytes) = reply_bytes(receive_zcl(&mut zcl_receiver).await);
+                let response = QueryNextImageResponse::from_le_stream(bytes.into_iter())
+                    .expect("valid Query Next Image Response");
+                assert_eq!(response.response(), QueryResponse::NoImageAvailable);
+            }
+
+            ota_sender
+                .unregister_image(current)
+                .await
+                .expect("OTA server is running");
+            ota_sender
+                .send(incoming(
+                    TEST_SEQUENCE_NUMBER,
+                    QueryNextImageRequest::new(outdated, Some(HARDWARE_VERSION)),
+                ))
+                .await
+                .expect("OTA server is running");
+            let (_, bytes) = reply_bytes(receive_zcl(&mut zcl_receiver).await);
+            let response = QueryNextImageResponse::from_le_stream(bytes.into_iter())
+                .expect("valid Query Next Image Response");
+            assert_eq!(response.response(), QueryResponse::NotAuthorized);
+        });
+    }
+
+    #[test]
+    fn reports_progress_and_serves_a_block_request_at_an_earlier_offset() {
+        run_test(async {
+            let (zcl_sender, mut zcl_receiver) = tokio::sync::mpsc::channel(TEST_CHANNEL_SIZE);
+            let (ota_sender, server) = Server::test_new(zcl_sender, TEST_UPDATE_LIMIT);
+            tokio::spawn(server.run());
+            let image = test_image();
+            let image_id = image.id();
+            let image_size = image.len();
+            let update = Update::new(
+                ota_sender.clone(),
+                test_address(),
+                test_target_endpoint(),
+                test_source_endpoint(),
+                image,
+                UpdateTimeouts::default(),
+            );
+            let mut progress = update.progress();
+            tokio::spawn(update);
+            receive_zcl(&mut zcl_receiver).await;
+            assert_eq!(progress.borrow_and_update().image_size(), image_size);
+
+            let header = u32::try_from(BASE_HEADER_LENGTH).expect("offset fits u32");
+            let second_block = header + u32::from(BLOCK_SIZE);
+            for (served, file_offset) in (1..).zip([second_block, header]) {
+                ota_sender
+                    .send(incoming(
+                        TEST_SEQUENCE_NUMBER,
+                        ImageBlockRequest::new(image_id, file_offset, BLOCK_SIZE, None, None),
+                    ))
+                    .await
+                    .expect("OTA server is running");
+                let (_, bytes) = reply_bytes(receive_zcl(&mut zcl_receiver).await);
+                let response = ImageBlockResponse::from_le_stream(bytes.into_iter())
+                    .expect("valid Image Block Response");
+                assert!(matches!(
+                    response.payload(),
+                    ImageBlockResponsePayload::Success(block) if block.file_offset() == file_offset
+                ));
+                timeout(
+                    TEST_TIMEOUT,
+                    progress.wait_for(|progress| progress.blocks_served() == served),
+                )
+                .await
+                .expect("served block updates the progress")
+                .expect("transfer keeps the progress sender");
+            }
+
+            let progress = *progress.borrow();
+            assert_eq!(progress.blocks_served(), 2);
+            assert_eq!(progress.bytes_served(), 2 * u64::from(BLOCK_SIZE));
+            assert_eq!(progress.resumptions(), 1);
+            assert_eq!(
+                progress.offset(),
+                BASE_HEADER_LENGTH + usize::from(BLOCK_SIZE)
+            );
+        });
+    }
+
+    #[test]
+    fn follows_a_target_that_rejoined_under_a_new_short_address() {
+        run_test(async {
+            let (zcl_sender, mut zcl_receiver) = tokio::sync::mpsc::channel(TEST_CHANNEL_SIZE);
+            let (ota_sender, server) = Server::test_new(zcl_sender, TEST_UPDATE_LIMIT);
+            tokio::spawn(server.run());
+            let image = test_image();
+            let image_id = image.id();
+            let completion = schedule(&ota_sender, image).await;
+            receive_zcl(&mut zcl_receiver).await;
+
+            let rejoined = Source::Network {
+                address: second_test_destination().address(),
+                endpoint: test_target_endpoint(),
+            };
+            let offset = u32::try_from(BASE_HEADER_LENGTH).expect("offset fits u32");
+            ota_sender
+                .send(incoming_from(
+                    rejoined,
+                    TEST_SEQUENCE_NUMBER,
+                    ImageBlockRequest::new(
+                        image_id,
+                        offset,
+                        BLOCK_SIZE,
+                        Some(TEST_IEEE_ADDRESS),
+                        None,
+                    ),
+                ))
+                .await
+                .expect("OTA server is running");
+            let ObservedZcl::Reply { request, .. } = receive_zcl(&mut zcl_receiver).await else {
+                panic!("expected OTA reply");
+            };
+            assert_eq!(request.destination(), second_test_destination().into());
+            let (_, bytes) = request.into_asdu().into_parts();
+            let response = ImageBlockResponse::from_le_stream(bytes.into_iter())
+                .expect("valid Image Block Response");
+            assert!(matches!(
+                response.payload(),
+                ImageBlockResponsePayload::Success(_)
+            ));
+
+            ota_sender
+                .send(incoming_from(
+                    rejoined,
+                    TEST_SEQUENCE_NUMBER,
+                    UpgradeEndRequest::new(UpgradeEndStatus::Success, image_id),
+                ))
+                .await
+                .expect("OTA server is running");
+            receive_zcl(&mut zcl_receiver).await;
+            assert!(matches!(completion.await, Ok(Ok(()))));
+        });
+    }
+
+    #[test]
+    fn device_campaign_notifies_serves_and_reports_each_device() {
+        run_test(async {
+            let (zcl_sender, mut zcl_receiver) = tokio::sync::mpsc::channel(TEST_CHANNEL_SIZE);
+            let (ota_sender, server) = Server::test_new(zcl_sender, TEST_UPDATE_LIMIT);
+            tokio::spawn(server.run());
+            let image = test_image();
+            let image_id = image.id();
+            let campaign = ota_sender.start_campaign(Campaign::devices(
+                image,
+                test_source_endpoint(),
+                [(test_address(), test_target_endpoint())],
+            ));
+            let summary = campaign.summary();
+            let campaign = tokio::spawn(campaign);
+
+            let ObservedZcl::Transmit { request } = receive_zcl(&mut zcl_receiver).await else {
+                panic!("expected Image Notify transmission");
+            };
+            assert_eq!(request.destination(), test_destination().into());
+            let (_, bytes) = request.into_asdu().into_parts();
+            let notification =
+                ImageNotify::from_le_stream(bytes.into_iter()).expect("valid Image Notify payload");
+            assert!(matches!(
+                notification.payload(),
+                ImageNotifyPayload::FileVersion { image, .. } if image == image_id
+            ));
+
+            let current_image = ImageId::new(MANUFACTURER_CODE, IMAGE_TYPE, FILE_VERSION - 1);
+            ota_sender
+                .send(incoming(
+                    TEST_SEQUENCE_NUMBER,
+                    QueryNextImageRequest::new(current_image, None),
+                ))
+                .await
+                .expect("OTA server is running");
+            let (_, bytes) = reply_bytes(receive_zcl(&mut zcl_receiver).await);
+            let response = QueryNextImageResponse::from_le_stream(bytes.into_iter())
+                .expect("valid Query Next Image Response");
+            assert!(matches!(
+                response.response(),
+                QueryResponse::Success { image, .. } if image == image_id
+            ));
+            assert_eq!(summary.borrow().active(), 1);
+
+            ota_sender
+                .send(incoming(
+                    TEST_SEQUENCE_NUMBER,
+                    UpgradeEndRequest::new(UpgradeEndStatus::Success, image_id),
+                ))
+                .await
+                .expect("OTA server is running");
+            receive_zcl(&mut zcl_receiver).await;
+
+            let report = timeout(TEST_TIMEOUT, campaign)
+                .await
+                .expect("the campaign completes once every device has an outcome")
+                .expect("campaign task completed normally")
+                .expect("the campaign reports its outcome");
+            assert_eq!(report.image(), image_id);
+            let [device] = report.devices() else {
+                panic!("expected the outcome of the single target");
+            };
+            assert_eq!(device.target(), test_address());
+            assert_eq!(device.outcome(), DeviceOutcome::Updated);
+            assert_eq!(report.summary().updated(), 1);
+            assert_eq!(report.summary().pending(), 0);
+        });
+    }
+
+    #[test]
+    fn group_campaign_group_casts_image_notify_and_records_current_members() {
+        run_test(async {
+            let (zcl_sender, mut zcl_receiver) = tokio::sync::mpsc::channel(TEST_CHANNEL_SIZE);
+            let (ota_sender, server) = Server::test_new(zcl_sender, TEST_UPDATE_LIMIT);
+            tokio::spawn(server.run());
+            let image = test_image();
+            let image_id = image.id();
+            let group = GroupId::new(CAMPAIGN_GROUP).expect("valid test group");
+            let query_jitter =
+                QueryJitter::new(CAMPAIGN_QUERY_JITTER).expect("valid test query jitter");
+            let campaign = start_campaign(
+                &ota_sender,
+                Campaign::group(
+                    image,
+                    test_source_endpoint(),
+                    group,
+                    [(test_address(), test_target_endpoint())],
+                )
+                .with_query_jitter(query_jitter),
+            )
+            .await;
+
+            let ObservedZcl::Transmit { request } = receive_zcl(&mut zcl_receiver).await else {
+                panic!("expected Image Notify transmission");
+            };
+            assert!(matches!(
+                request.destination(),
+                zb_aps::apsde::RequestDestination::Group { address, .. } if address == group
+            ));
+            assert_eq!(request.tx_options(), zb_aps::TxOptions::empty());
+            let (_, bytes) = request.into_asdu().into_parts();
+            let notification =
+                ImageNotify::from_le_stream(bytes.into_iter()).expect("valid Image Notify payload");
+            assert_eq!(
+                notification.payload(),
+                ImageNotifyPayload::FileVersion {
+                    query_jitter,
+                    image: image_id,
+                }
+            );
+
+            ota_sender
+                .send(incoming(
+                    TEST_SEQUENCE_NUMBER,
+                    QueryNextImageRequest::new(image_id, None),
+                ))
+                .await
+                .expect("OTA server is running");
+            let (_, bytes) = reply_bytes(receive_zcl(&mut zcl_receiver).await);
+            let response = QueryNextImageResponse::from_le_stream(bytes.into_iter())
+                .expect("valid Query Next Image Response");
+            assert_eq!(response.response(), QueryResponse::NoImageAvailable);
+            assert_eq!(campaign.summary.borrow().already_current(), 1);
+
+            campaign.control.send_replace(CampaignControl::Cancel);
+            let report = timeout(TEST_TIMEOUT, campaign.completion)
+                .await
+                .expect("a cancelled campaign reports its outcome")
+                .expect("OTA server is running")
+                .expect("the campaign was registered");
+            let [device] = report.devices() else {
+                panic!("expected the outcome of the querying member");
+            };
+            assert_eq!(device.outcome(), DeviceOutcome::AlreadyCurrent);
+        });
+    }
+
+    #[test]
+    fn group_campaign_ignores_queries_from_non_members() {
+        run_test(async {
+            let (zcl_sender, mut zcl_receiver) = tokio::sync::mpsc::channel(TEST_CHANNEL_SIZE);
+            let (ota_sender, server) = Server::test_new(zcl_sender, TEST_UPDATE_LIMIT);
+            tokio::spawn(server.run());
+            let group = GroupId::new(CAMPAIGN_GROUP).expect("valid test group");
+            let member = FullAddress::new(OTHER_IEEE_ADDRESS, second_test_short_id());
+            let campaign = start_campaign(
+                &ota_sender,
+                Campaign::group(
+                    test_image(),
+                    test_source_endpoint(),
+                    group,
+                    [(member, test_target_endpoint())],
+                ),
+            )
+            .await;
+            assert!(matches!(
+                receive_zcl(&mut zcl_receiver).await,
+                ObservedZcl::Transmit { .. }
+            ));
+
+            let current_image = ImageId::new(MANUFACTURER_CODE, IMAGE_TYPE, FILE_VERSION - 1);
+            ota_sender
+                .send(incoming(
+                    TEST_SEQUENCE_NUMBER,
+                    QueryNextImageRequest::new(current_image, None),
+                ))
+                .await
+                .expect("OTA server is running");
+            let (_, bytes) = reply_bytes(receive_zcl(&mut zcl_receiver).await);
+            let response = QueryNextImageResponse::from_le_stream(bytes.into_iter())
+                .expect("valid Query Next Image Response");
+            assert_eq!(response.response(), QueryResponse::NotAuthorized);
+            assert_eq!(campaign.summary.borrow().active(), 0);
+            assert_eq!(campaign.summary.borrow().targeted(), 1);
+
+            campaign.control.send_replace(CampaignControl::Cancel);
+            let report = timeout(TEST_TIMEOUT, campaign.completion)
+                .await
+                .expect("a cancelled campaign reports its outcome")
+                .expect("OTA server is running")
+                .expect("the campaign was registered");
+            let [device] = report.devices() else {
+                panic!("expected the outcome of the listed member");
+            };
+            assert_eq!(device.target(), member);
+            assert_eq!(device.outcome(), DeviceOutcome::NotReached);
+        });
+    }
+
+    #[test]
+    fn paused_campaign_defers_queries_and_throttles_blocks_after_resuming() {
+        run_test(async {
+            let (zcl_sender, mut zcl_receiver) = tokio::sync::mpsc::channel(TEST_CHANNEL_SIZE);
+            let (ota_sender, server) = Server::test_new(zcl_sender, TEST_UPDATE_LIMIT);
+            tokio::spawn(server.run());
+            let image = test_image();
+            let image_id = image.id();
+            let (control, control_receiver) = tokio::sync::watch::channel(CampaignControl::Pause);
+            let campaign = start_campaign_with(
+                &ota_sender,
+                Campaign::devices(
+                    image,
+                    test_source_endpoint(),
+                    [(test_address(), test_target_endpoint())],
+                )
+                .with_airtime_limit(CAMPAIGN_AIRTIME_LIMIT),
+                control,
+                control_receiver,
+            )
+            .await;
+
+            let current_image = ImageId::new(MANUFACTURER_CODE, IMAGE_TYPE, FILE_VERSION - 1);
+            let query = || {
+                incoming(
+                    TEST_SEQUENCE_NUMBER,
+                    QueryNextImageRequest::new(current_image, None),
+                )
+            };
+            ota_sender
+                .send(query())
+                .await
+                .expect("OTA server is running");
+            let (_, bytes) = reply_bytes(receive_zcl(&mut zcl_receiver).await);
+            let response = QueryNextImageResponse::from_le_stream(bytes.into_iter())
+                .expect("valid Query Next Image Response");
+            assert_eq!(response.response(), QueryResponse::NoImageAvailable);
+            assert!(campaign.summary.borrow().is_paused());
+
+            campaign.control.send_replace(CampaignControl::Run);
+            assert!(matches!(
+                receive_zcl(&mut zcl_receiver).await,
+                ObservedZcl::Transmit { .. }
+            ));
+            ota_sender
+                .send(query())
+                .await
+                .expect("OTA server is running");
+            let (_, bytes) = reply_bytes(receive_zcl(&mut zcl_receiver).await);
+            let response = QueryNextImageResponse::from_le_stream(bytes.into_iter())
+                .expect("valid Query Next Image Response");
+            assert!(matches!(response.response(), QueryResponse::Success { .. }));
+
+            let offset = u32::try_from(BASE_HEADER_LENGTH).expect("fixed header length fits u32");
+            let block_request = || {
+                incoming(
+                    TEST_SEQUENCE_NUMBER,
+                    ImageBlockRequest::new(image_id, offset, BLOCK_SIZE, None, None),
+                )
+            };
+            ota_sender
+                .send(block_request())
+                .await
+                .expect("OTA server is running");
+            let (_, bytes) = reply_bytes(receive_zcl(&mut zcl_receiver).await);
+            let response = ImageBlockResponse::from_le_stream(bytes.into_iter())
+                .expect("valid Image Block Response");
+            assert!(matches!(
+                response.payload(),
+                ImageBlockResponsePayload::Success(_)
+            ));
+            ota_sender
+                .send(block_request())
+                .await
+                .expect("OTA server is running");
+            let (_, bytes) = reply_bytes(receive_zcl(&mut zcl_receiver).await);
+            let response = ImageBlockResponse::from_le_stream(bytes.into_iter())
+                .expect("valid Image Block Response");
+            assert!(matches!(
+                response.payload(),
+                ImageBlockResponsePayload::WaitForData(wait)
+                    if wait.current_time() == 0 && wait.request_time() == 1
+            ));
+
+            campaign.control.send_replace(CampaignControl::Cancel);
+            let report = timeout(TEST_TIMEOUT, campaign.completion)
+                .await
+                .expect("a cancelled campaign reports its outcome")
+                .expect("OTA server is running")
+                .expect("the campaign was registered");
+            let [device] = report.devices() else {
+                panic!("expected the outcome of the single target");
+            };
+            assert_eq!(
+                device.outcome(),
+                DeviceOutcome::Failed(UpdateError::Cancelled)
+            );
+        });
+    }
+
     fn test_image() -> Image {
         test_image_for(None)
     }
 
     fn test_image_for(destination: Option<IeeeAddress>) -> Image {
+        test_image_with(destination, FILE_VERSION)
+    }
+
+    fn test_image_with(destination: Option<IeeeAddress>, file_version: u32) -> Image {
         let optional_header_length = destination.map_or(0, |_| UPGRADE_FILE_DESTINATION_LENGTH);
         let header_length = BASE_HEADER_LENGTH + optional_header_length;
         let total_length = header_length + TEST_IMAGE_DATA.len();
@@ -931,7 +1487,7 @@ mod tests {
         bytes.put_u16_le(field_control.bits());
         bytes.put_u16_le(MANUFACTURER_CODE);
         bytes.put_u16_le(IMAGE_TYPE);
-        bytes.put_u32_le(FILE_VERSION);
+        bytes.put_u32_le(file_version);
         bytes.put_u16_le(STACK_VERSION);
         bytes.extend_from_slice(&[0; HEADER_STRING_LENGTH]);
         bytes.put_u32_le(u32::try_from(total_length).expect("test image length fits u32"));
@@ -995,6 +1551,7 @@ mod tests {
     ) -> ScheduledUpdate {
         let (completion, result) = tokio::sync::oneshot::channel();
         let (cancellation, cancelled) = tokio::sync::oneshot::channel();
+        let (progress, _progress) = tokio::sync::watch::channel(Progress::new(image.len()));
         sender
             .send(Message::Update {
                 target,
@@ -1004,6 +1561,7 @@ mod tests {
                 timeouts,
                 cancellation: cancelled,
                 completion,
+                progress,
             })
             .await
             .expect("OTA server is running");
@@ -1013,6 +1571,38 @@ mod tests {
         }
     }
 
+    async fn start_campaign(
+        sender: &tokio::sync::mpsc::Sender<Message>,
+        campaign: Campaign,
+    ) -> ScheduledCampaign {
+        let (control, control_receiver) = tokio::sync::watch::channel(CampaignControl::Run);
+        start_campaign_with(sender, campaign, control, control_receiver).await
+    }
+
+    async fn start_campaign_with(
+        sender: &tokio::sync::mpsc::Sender<Message>,
+        campaign: Campaign,
+        control: tokio::sync::watch::Sender<CampaignControl>,
+        control_receiver: tokio::sync::watch::Receiver<CampaignControl>,
+    ) -> ScheduledCampaign {
+        let (summary_sender, summary) = tokio::sync::watch::channel(CampaignSummary::default());
+        let (completion, result) = tokio::sync::oneshot::channel();
+        sender
+            .send(Message::StartCampaign {
+                campaign: Box::new(campaign),
+                control: control_receiver,
+                summary: summary_sender,
+                completion,
+            })
+            .await
+            .expect("OTA server is running");
+        ScheduledCampaign {
+            control,
+            summary,
+            completion: result,
+        }
+    }
+
     fn update_via_api(
         sender: tokio::sync::mpsc::Sender<Message>,
         image: Image,
@@ -1044,6 +1634,29 @@ mod tests {
         incoming_with_profile(OTA_PROFILE, sequence_number, command)
     }
 
+    fn incoming_from<T>(source: Source, sequence_number: u8, command: T) -> Message
+    where
+        T: Command + Into<OtaCommand>,
+    {
+        let Message::Received { indication } = incoming(sequence_number, command) else {
+            unreachable!("incoming commands are received messages");
+        };
+        let (metadata, frame) = indication.into_parts();
+        let metadata = IndicationMetadata::new(
+            metadata.destination(),
+            source,
+            OTA_PROFILE.as_u16(),
+            Cluster::OtaUpgrade.as_u16(),
+            IndicationStatus::success(),
+            Security::Unsecured,
+            u8::MAX,
+            (),
+        );
+        Message::Received {
+            indication: DataIndication::new(metadata, frame),
+        }
+    }
+
     fn incoming_with_profile<T>(profile: Profile, sequence_number: u8, command: T) -> Message
     where
         T: Command + Into<OtaCommand>,
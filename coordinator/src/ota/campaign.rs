@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::num::{NonZeroU32, NonZeroUsize};
+use std::time::Duration;
+
+use zb_aps::apsde::IndividualEndpoint;
+use zb_core::{FullAddress, GroupId, IeeeAddress};
+use zb_zcl::ota_upgrade::{ImageId, QueryJitter};
+
+pub(super) use self::active::{ActiveCampaign, Admission, CampaignEvent};
+pub use self::handle::{CampaignControl, CampaignHandle};
+pub use self::report::{CampaignReport, CampaignSummary, DeviceOutcome, DeviceReport};
+pub(super) use self::throttle::Throttle;
+use super::{Image, UpdateTimeouts};
+
+mod active;
+mod handle;
+mod report;
+mod throttle;
+
+const DEFAULT_MAX_CONCURRENT_TRANSFERS: NonZeroUsize =
+    NonZeroUsize::new(4).expect("the default transfer cap is non-zero");
+const DEFAULT_NOTIFY_INTERVAL: Duration = Duration::from_secs(30);
+const MINIMUM_NOTIFY_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_QUERY_JITTER: u8 = 25;
+const DEFAULT_DURATION: Duration = Duration::from_hours(24);
+
+/// One OTA image distributed to a set of devices or to the members of a group.
+///
+/// The coordinator staggers Image Notify commands at the notify interval and only starts a
+/// transfer when a notified device queries while a transfer slot is free. Device campaigns
+/// unicast the notification to the next pending devices. Group campaigns group-cast it with the
+/// configured query jitter, so that only a fraction of the members query at once. Both kinds only
+/// admit the device endpoints they list.
+#[derive(Debug)]
+pub struct Campaign {
+    image: Image,
+    source_endpoint: IndividualEndpoint,
+    /// Targeted device endpoints, indexed by IEEE address.
+    devices: BTreeMap<IeeeAddress, (FullAddress, IndividualEndpoint)>,
+    /// Group that a group campaign notifies.
+    group: Option<GroupId>,
+    max_concurrent_transfers: NonZeroUsize,
+    airtime_limit: Option<NonZeroU32>,
+    notify_interval: Duration,
+    query_jitter: QueryJitter,
+    timeouts: UpdateTimeouts,
+    duration: Duration,
+}
+
+impl Campaign {
+    /// Create a campaign offering `image` to each of the given device endpoints.
+    ///
+    /// A later entry for the same IEEE address replaces an earlier one.
+    #[must_use]
+    pub fn devices<T>(image: Image, source_endpoint: IndividualEndpoint, targets: T) -> Self
+    where
+        T: IntoIterator<Item = (FullAddress, IndividualEndpoint)>,
+    {
+        Self::new(image, source_endpoint, targets, None)
+    }
+
+    /// Create a campaign offering `image` to the given member endpoints of `group`.
+    ///
+    /// The campaign group-casts Image Notify to `group` but only admits queries from the listed
+    /// members, for example those that reported the group in a Groups cluster Get Group Membership
+    /// response. A later entry for the same IEEE address replaces an earlier one.
+    #[must_use]
+    pub fn group<T>(
+        image: Image,
+        source_endpoint: IndividualEndpoint,
+        group: GroupId,
+        members: T,
+    ) -> Self
+    where
+        T: IntoIterator<Item = (FullAddress, IndividualEndpoint)>,
+    {
+        Self::new(image, source_endpoint, members, Some(group))
+    }
+
+    fn new<T>(
+        image: Image,
+        source_endpoint: IndividualEndpoint,
+        targets: T,
+        group: Option<GroupId>,
+    ) -> Self
+    where
+        T: IntoIterator<Item = (FullAddress, IndividualEndpoint)>,
+    {
+        let devices = targets
+            .into_iter()
+            .map(|(target, endpoint)| (target.ieee_address(), (target, endpoint)))
+            .collect();
+        Self {
+            image,
+            source_endpoint,
+            devices,
+            group,
+            max_concurrent_transfers: DEFAULT_MAX_CONCURRENT_TRANSFERS,
+            airtime_limit: None,
+            notify_interval: DEFAULT_NOTIFY_INTERVAL,
+            query_jitter: QueryJitter::new(DEFAULT_QUERY_JITTER)
+                .expect("the default query jitter is valid"),
+            timeouts: UpdateTimeouts::default(),
+            duration: DEFAULT_DURATION,
+        }
+    }
+
+    /// Limit the number of transfers that run at the same time.
+    ///
+    /// Campaign transfers also count towards the coordinator's concurrent update-task limit.
+    #[must_use]
+    pub const fn with_max_concurrent_transfers(mut self, transfers: NonZeroUsize) -> Self {
+        self.max_concurrent_transfers = transfers;
+        self
+    }
+
+    /// Limit the image data served by all transfers of the campaign to `bytes_per_second`.
+    ///
+    /// Clients whose block request exceeds the budget are told to wait for data.
+    #[must_use]
+    pub const fn with_airtime_limit(mut self, bytes_per_second: NonZeroU32) -> Self {
+        self.airtime_limit = Some(bytes_per_second);
+        self
+    }
+
+    /// Select the delay between consecutive Image Notify rounds.
+    ///
+    /// Intervals shorter than one second are rounded up to one second.
+    #[must_use]
+    pub const fn with_notify_interval(mut self, interval: Duration) -> Self {
+        self.notify_interval = interval;
+        self
+    }
+
+    /// Select the percentage of group members that query after each group-cast Image Notify.
+    ///
+    /// Unicast notifications always use the maximum query jitter.
+    #[must_use]
+    pub const fn with_query_jitter(mut self, query_jitter: QueryJitter) -> Self {
+        self.query_jitter = query_jitter;
+        self
+    }
+
+    /// Select the deadlines of each device transfer.
+    #[must_use]
+    pub const fn with_timeouts(mut self, timeouts: UpdateTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Select how long the campaign notifies and admits devices.
+    ///
+    /// Transfers that are still running when the duration elapses continue until they finish.
+    #[must_use]
+    pub const fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Return the manufacturer, image type, and file version distributed by the campaign.
+    #[must_use]
+    pub const fn id(&self) -> ImageId {
+        self.image.id()
+    }
+
+    /// Return the delay between consecutive Image Notify rounds.
+    #[must_use]
+    pub fn notify_interval(&self) -> Duration {
+        self.notify_interval.max(MINIMUM_NOTIFY_INTERVAL)
+    }
+
+    /// Return how long the campaign notifies and admits devices.
+    #[must_use]
+    pub const fn duration(&self) -> Duration {
+        self.duration
+    }
+}
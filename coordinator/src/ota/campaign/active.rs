@@ -0,0 +1,383 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+use log::debug;
+use tokio::sync::{oneshot, watch};
+use tokio::task::AbortHandle;
+use zb_aps::TxOptions;
+use zb_aps::apsde::{BroadcastAddress, IndividualEndpoint, RequestDestination};
+use zb_core::{Cluster, FullAddress, GroupId, IeeeAddress, short_id};
+use zb_zcl::ota_upgrade::{ImageNotify, ImageNotifyPayload, QueryJitter, QueryNextImageRequest};
+
+use super::throttle::Throttle;
+use super::{
+    Campaign, CampaignControl, CampaignReport, CampaignSummary, DeviceOutcome, DeviceReport,
+};
+use crate::ota::image::ImageTransfer;
+use crate::ota::{
+    OTA_PROFILE, Request, UpdateError, UpdateResult, UpdateTimeouts, network_destination, request,
+};
+
+/// Event routed by the OTA server to one running campaign.
+pub(in crate::ota) enum CampaignEvent {
+    /// Send the next round of Image Notify commands.
+    Tick,
+    /// The caller changed the requested run state, or dropped its handle.
+    Control(CampaignControl),
+    /// The campaign duration elapsed.
+    Expired,
+    /// A campaign transfer reached its terminal outcome.
+    Transfer {
+        /// IEEE address of the updated device.
+        target: IeeeAddress,
+        /// Terminal result of the transfer.
+        result: UpdateResult,
+    },
+}
+
+/// Campaign decision for a Query Next Image request from a targeted device.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(in crate::ota) enum Admission {
+    /// The device already runs the campaign's file version.
+    AlreadyCurrent,
+    /// The campaign is paused or has no free transfer slot.
+    Deferred,
+    /// Start a transfer of the campaign image.
+    Admit,
+}
+
+/// Device endpoint with a running campaign transfer.
+#[derive(Debug)]
+struct ActiveDevice {
+    target: FullAddress,
+    endpoint: IndividualEndpoint,
+    /// Task that owns the transfer's cancellation sender and reports its result.
+    supervisor: AbortHandle,
+}
+
+/// Server-side state of one running OTA campaign.
+#[derive(Debug)]
+pub(in crate::ota) struct ActiveCampaign {
+    image: ImageTransfer,
+    source_endpoint: IndividualEndpoint,
+    group: Option<GroupId>,
+    pending: VecDeque<(FullAddress, IndividualEndpoint)>,
+    active: BTreeMap<IeeeAddress, ActiveDevice>,
+    outcomes: BTreeMap<IeeeAddress, DeviceReport>,
+    max_concurrent_transfers: NonZeroUsize,
+    query_jitter: QueryJitter,
+    timeouts: UpdateTimeouts,
+    throttle: Arc<Throttle>,
+    paused: bool,
+    admitting: bool,
+    summary: watch::Sender<CampaignSummary>,
+    completion: oneshot::Sender<Result<CampaignReport, UpdateError>>,
+    tasks: Vec<AbortHandle>,
+}
+
+impl ActiveCampaign {
+    /// Start tracking `campaign`, whose notifier, deadline, and control tasks are `tasks`.
+    pub(in crate::ota) fn new(
+        campaign: Campaign,
+        paused: bool,
+        summary: watch::Sender<CampaignSummary>,
+        completion: oneshot::Sender<Result<CampaignReport, UpdateError>>,
+        tasks: Vec<AbortHandle>,
+    ) -> Self {
+        let Campaign {
+            image,
+            source_endpoint,
+            devices,
+            group,
+            max_concurrent_transfers,
+            airtime_limit,
+            notify_interval: _,
+            query_jitter,
+            timeouts,
+            duration: _,
+        } = campaign;
+        let campaign = Self {
+            image: image.into_transfer(),
+            source_endpoint,
+            group,
+            pending: devices.into_values().collect(),
+            active: BTreeMap::new(),
+            outcomes: BTreeMap::new(),
+            max_concurrent_transfers,
+            query_jitter,
+            timeouts,
+            throttle: Arc::new(Throttle::new(airtime_limit, paused)),
+            paused,
+            admitting: true,
+            summary,
+            completion,
+            tasks,
+        };
+        campaign.publish();
+        campaign
+    }
+
+    /// Return the image offered to admitted devices.
+    pub(in crate::ota) fn image(&self) -> ImageTransfer {
+        self.image.clone()
+    }
+
+    /// Return the deadlines of each device transfer.
+    pub(in crate::ota) const fn timeouts(&self) -> UpdateTimeouts {
+        self.timeouts
+    }
+
+    /// Return the pause flag and airtime budget shared by the campaign's transfers.
+    pub(in crate::ota) fn throttle(&self) -> Arc<Throttle> {
+        self.throttle.clone()
+    }
+
+    /// Return whether the caller paused the campaign.
+    pub(in crate::ota) const fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Decide how to answer a query from `target`, or return `None` if the campaign does not
+    /// target the device.
+    ///
+    /// `slot_available` reports whether the server may start another destination transfer.
+    pub(in crate::ota) fn admission(
+        &self,
+        target: FullAddress,
+        endpoint: IndividualEndpoint,
+        request: &QueryNextImageRequest,
+        slot_available: bool,
+    ) -> Option<Admission> {
+        if !self.admitting {
+            return None;
+        }
+        let offered = self.image.id();
+        let current = request.image();
+        if offered.manufacturer_code() != current.manufacturer_code()
+            || offered.image_type() != current.image_type()
+        {
+            return None;
+        }
+        let ieee_address = target.ieee_address();
+        let targeted = self.pending.iter().any(|(device, device_endpoint)| {
+            device.ieee_address() == ieee_address && *device_endpoint == endpoint
+        });
+        if !targeted {
+            return None;
+        }
+        if offered.file_version() == current.file_version() {
+            return Some(Admission::AlreadyCurrent);
+        }
+        if self.image.upgrade_file_destination().is_some()
+            || !self.image.supports_hardware(request.hardware_version())
+        {
+            return None;
+        }
+        if self.paused
+            || !slot_available
+            || self.active.len() >= self.max_concurrent_transfers.get()
+        {
+            return Some(Admission::Deferred);
+        }
+        Some(Admission::Admit)
+    }
+
+    /// Record that `target` already runs the campaign image.
+    pub(in crate::ota) fn record_current(
+        &mut self,
+        target: FullAddress,
+        endpoint: IndividualEndpoint,
+    ) {
+        self.remove_pending(target.ieee_address());
+        self.record(target, endpoint, DeviceOutcome::AlreadyCurrent);
+        self.publish();
+    }
+
+    /// Record a transfer started for `target` and supervised by `supervisor`.
+    pub(in crate::ota) fn start(
+        &mut self,
+        target: FullAddress,
+        endpoint: IndividualEndpoint,
+        supervisor: AbortHandle,
+    ) {
+        self.remove_pending(target.ieee_address());
+        self.active.insert(
+            target.ieee_address(),
+            ActiveDevice {
+                target,
+                endpoint,
+                supervisor,
+            },
+        );
+        self.publish();
+    }
+
+    /// Record the terminal result of the transfer to `target`.
+    pub(in crate::ota) fn finish(&mut self, target: IeeeAddress, result: UpdateResult) {
+        let Some(device) = self.active.remove(&target) else {
+            debug!("Ignoring the result of a cancelled OTA campaign transfer to {target}");
+            return;
+        };
+        let outcome = result.map_or_else(DeviceOutcome::Failed, |()| DeviceOutcome::Updated);
+        self.record(device.target, device.endpoint, outcome);
+        self.publish();
+    }
+
+    /// Apply a run state requested by the caller.
+    pub(in crate::ota) fn control(&mut self, control: CampaignControl) {
+        match control {
+            CampaignControl::Run | CampaignControl::Pause => {
+                self.paused = control == CampaignControl::Pause;
+                self.throttle.set_paused(self.paused);
+            }
+            CampaignControl::Cancel => {
+                self.stop_admitting();
+                for (_, device) in std::mem::take(&mut self.active) {
+                    device.supervisor.abort();
+                    self.record(
+                        device.target,
+                        device.endpoint,
+                        DeviceOutcome::Failed(UpdateError::Cancelled),
+                    );
+                }
+            }
+        }
+        self.publish();
+    }
+
+    /// Stop notifying and admitting devices once the campaign duration has elapsed.
+    pub(in crate::ota) fn expire(&mut self) {
+        self.stop_admitting();
+        self.publish();
+    }
+
+    /// Return whether every device has an outcome, or the campaign stopped admitting devices and
+    /// its last transfer has finished.
+    pub(in crate::ota) fn is_complete(&self) -> bool {
+        self.active.is_empty() && (!self.admitting || self.pending.is_empty())
+    }
+
+    /// Stop the notifier, deadline, and control tasks of the campaign.
+    pub(in crate::ota) fn abort_tasks(&self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+
+    /// Stop the campaign's tasks and deliver its report.
+    pub(in crate::ota) fn complete(self) {
+        self.abort_tasks();
+        for device in self.active.values() {
+            device.supervisor.abort();
+        }
+        let summary = self.summarize();
+        let report = CampaignReport::new(
+            self.image.id(),
+            self.outcomes.into_values().collect(),
+            summary,
+        );
+        let _result = self.completion.send(Ok(report));
+    }
+
+    /// Return the Image Notify requests of the next notification round.
+    ///
+    /// Device campaigns notify as many pending devices as there are free transfer slots, moving
+    /// them to the back of the queue. Group campaigns group-cast one notification while a slot is
+    /// free.
+    pub(in crate::ota) fn notifications(&mut self) -> Vec<Request> {
+        if self.paused || !self.admitting {
+            return Vec::new();
+        }
+        let free = self
+            .max_concurrent_transfers
+            .get()
+            .saturating_sub(self.active.len());
+        if free == 0 {
+            return Vec::new();
+        }
+        if let Some(group) = self.group {
+            let destination = RequestDestination::Group {
+                address: group,
+                broadcast_address: BroadcastAddress::new(short_id::Broadcast::AllDevices.as_u16())
+                    .expect("all-devices is a valid APSDE group broadcast selector"),
+            };
+            return vec![
+                self.notification(destination, self.query_jitter)
+                    .with_tx_options(TxOptions::empty()),
+            ];
+        }
+        let query_jitter =
+            QueryJitter::new(QueryJitter::MAX).expect("the declared maximum query jitter is valid");
+        (0..free.min(self.pending.len()))
+            .filter_map(|_| {
+                let device = self.pending.pop_front()?;
+                self.pending.push_back(device);
+                let (target, endpoint) = device;
+                Some(self.notification(
+                    network_destination(target.short_id(), endpoint).into(),
+                    query_jitter,
+                ))
+            })
+            .collect()
+    }
+
+    fn notification(&self, destination: RequestDestination, query_jitter: QueryJitter) -> Request {
+        request(
+            destination,
+            self.source_endpoint,
+            OTA_PROFILE,
+            Cluster::OtaUpgrade.as_u16(),
+            ImageNotify::new(ImageNotifyPayload::FileVersion {
+                query_jitter,
+                image: self.image.id(),
+            }),
+        )
+    }
+
+    fn stop_admitting(&mut self) {
+        self.admitting = false;
+        for (target, endpoint) in std::mem::take(&mut self.pending) {
+            self.record(target, endpoint, DeviceOutcome::NotReached);
+        }
+    }
+
+    fn remove_pending(&mut self, ieee_address: IeeeAddress) {
+        self.pending
+            .retain(|(device, _)| device.ieee_address() != ieee_address);
+    }
+
+    fn record(
+        &mut self,
+        target: FullAddress,
+        endpoint: IndividualEndpoint,
+        outcome: DeviceOutcome,
+    ) {
+        self.outcomes.insert(
+            target.ieee_address(),
+            DeviceReport::new(target, endpoint, outcome),
+        );
+    }
+
+    fn publish(&self) {
+        self.summary.send_replace(self.summarize());
+    }
+
+    fn summarize(&self) -> CampaignSummary {
+        let mut summary = CampaignSummary {
+            targeted: self.pending.len() + self.active.len() + self.outcomes.len(),
+            active: self.active.len(),
+            paused: self.paused,
+            ..CampaignSummary::default()
+        };
+        for report in self.outcomes.values() {
+            match report.outcome() {
+                DeviceOutcome::Updated => summary.updated += 1,
+                DeviceOutcome::AlreadyCurrent => summary.already_current += 1,
+                DeviceOutcome::Failed(_) => summary.failed += 1,
+                DeviceOutcome::NotReached => summary.not_reached += 1,
+            }
+        }
+        summary
+    }
+}
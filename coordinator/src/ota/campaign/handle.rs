@@ -0,0 +1,89 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::sync::mpsc::Sender;
+use tokio::sync::{oneshot, watch};
+
+use super::{Campaign, CampaignReport, CampaignSummary};
+use crate::Error;
+use crate::ota::Message;
+
+/// Requested run state of an OTA campaign.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CampaignControl {
+    /// Notify devices and serve image data.
+    Run,
+    /// Stop notifying devices and defer all image data until resumed.
+    Pause,
+    /// Stop the campaign and its transfers.
+    Cancel,
+}
+
+/// Controllable future for one coordinator-managed OTA campaign.
+///
+/// The future resolves with the campaign report once every device has an outcome, or once the
+/// campaign duration has elapsed and its remaining transfers have finished.
+#[must_use = "dropping this handle cancels the OTA campaign"]
+pub struct CampaignHandle {
+    future: Pin<Box<dyn Future<Output = Result<CampaignReport, Error>> + Send>>,
+    control: watch::Sender<CampaignControl>,
+    summary: watch::Receiver<CampaignSummary>,
+}
+
+impl CampaignHandle {
+    /// Create a campaign handle that submits `campaign` when first polled.
+    pub(crate) fn new(sender: Sender<Message>, campaign: Campaign) -> Self {
+        let (control, control_receiver) = watch::channel(CampaignControl::Run);
+        let (summary_sender, summary) = watch::channel(CampaignSummary::default());
+        let (completion, result) = oneshot::channel();
+        let future = Box::pin(async move {
+            sender
+                .send(Message::StartCampaign {
+                    campaign: Box::new(campaign),
+                    control: control_receiver,
+                    summary: summary_sender,
+                    completion,
+                })
+                .await?;
+            Ok(result.await??)
+        });
+
+        Self {
+            future,
+            control,
+            summary,
+        }
+    }
+
+    /// Stop notifying devices and defer image data of running transfers until resumed.
+    pub fn pause(&self) {
+        self.control.send_replace(CampaignControl::Pause);
+    }
+
+    /// Resume a paused campaign.
+    pub fn resume(&self) {
+        self.control.send_replace(CampaignControl::Run);
+    }
+
+    /// Cancel running transfers and resolve with the report of the stopped campaign.
+    ///
+    /// Dropping the handle cancels the campaign without reporting its outcome.
+    pub fn cancel(&self) {
+        self.control.send_replace(CampaignControl::Cancel);
+    }
+
+    /// Return a receiver that observes the campaign counters.
+    #[must_use]
+    pub fn summary(&self) -> watch::Receiver<CampaignSummary> {
+        self.summary.clone()
+    }
+}
+
+impl Future for CampaignHandle {
+    type Output = Result<CampaignReport, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        self.future.as_mut().poll(context)
+    }
+}
@@ -0,0 +1,165 @@
+use zb_aps::apsde::IndividualEndpoint;
+use zb_core::FullAddress;
+use zb_zcl::ota_upgrade::ImageId;
+
+use crate::ota::UpdateError;
+
+/// Final state of one device endpoint reached by an OTA campaign.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DeviceOutcome {
+    /// The device downloaded and accepted the campaign image.
+    Updated,
+    /// The device already ran the campaign's file version.
+    AlreadyCurrent,
+    /// The transfer to the device ended with a terminal failure.
+    Failed(UpdateError),
+    /// The device did not query for the image before the campaign ended.
+    NotReached,
+}
+
+/// Outcome of the campaign for one device endpoint.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DeviceReport {
+    target: FullAddress,
+    endpoint: IndividualEndpoint,
+    outcome: DeviceOutcome,
+}
+
+impl DeviceReport {
+    pub(super) const fn new(
+        target: FullAddress,
+        endpoint: IndividualEndpoint,
+        outcome: DeviceOutcome,
+    ) -> Self {
+        Self {
+            target,
+            endpoint,
+            outcome,
+        }
+    }
+
+    /// Return the complete address of the device, as last seen by the campaign.
+    #[must_use]
+    pub const fn target(&self) -> FullAddress {
+        self.target
+    }
+
+    /// Return the remote OTA client endpoint.
+    #[must_use]
+    pub const fn endpoint(&self) -> IndividualEndpoint {
+        self.endpoint
+    }
+
+    /// Return the final state of the device.
+    #[must_use]
+    pub const fn outcome(&self) -> DeviceOutcome {
+        self.outcome
+    }
+}
+
+/// Aggregate counters of an OTA campaign.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CampaignSummary {
+    pub(super) targeted: usize,
+    pub(super) updated: usize,
+    pub(super) already_current: usize,
+    pub(super) failed: usize,
+    pub(super) not_reached: usize,
+    pub(super) active: usize,
+    pub(super) paused: bool,
+}
+
+impl CampaignSummary {
+    /// Return the number of known target devices.
+    #[must_use]
+    pub const fn targeted(&self) -> usize {
+        self.targeted
+    }
+
+    /// Return the number of devices that accepted the image.
+    #[must_use]
+    pub const fn updated(&self) -> usize {
+        self.updated
+    }
+
+    /// Return the number of devices that already ran the image.
+    #[must_use]
+    pub const fn already_current(&self) -> usize {
+        self.already_current
+    }
+
+    /// Return the number of devices whose transfer failed.
+    #[must_use]
+    pub const fn failed(&self) -> usize {
+        self.failed
+    }
+
+    /// Return the number of devices that never queried for the image.
+    #[must_use]
+    pub const fn not_reached(&self) -> usize {
+        self.not_reached
+    }
+
+    /// Return the number of transfers currently in progress.
+    #[must_use]
+    pub const fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Return the number of known devices without an outcome or a running transfer.
+    #[must_use]
+    pub const fn pending(&self) -> usize {
+        self.targeted
+            - self.updated
+            - self.already_current
+            - self.failed
+            - self.not_reached
+            - self.active
+    }
+
+    /// Return whether the campaign is paused.
+    #[must_use]
+    pub const fn is_paused(&self) -> bool {
+        self.paused
+    }
+}
+
+/// Per-device outcomes and summary of a finished OTA campaign.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CampaignReport {
+    image: ImageId,
+    devices: Vec<DeviceReport>,
+    summary: CampaignSummary,
+}
+
+impl CampaignReport {
+    pub(super) const fn new(
+        image: ImageId,
+        devices: Vec<DeviceReport>,
+        summary: CampaignSummary,
+    ) -> Self {
+        Self {
+            image,
+            devices,
+            summary,
+        }
+    }
+
+    /// Return the manufacturer, image type, and file version distributed by the campaign.
+    #[must_use]
+    pub const fn image(&self) -> ImageId {
+        self.image
+    }
+
+    /// Return the outcome of every device reached or targeted by the campaign.
+    #[must_use]
+    pub fn devices(&self) -> &[DeviceReport] {
+        &self.devices
+    }
+
+    /// Return the final counters of the campaign.
+    #[must_use]
+    pub const fn summary(&self) -> CampaignSummary {
+        self.summary
+    }
+}
@@ -0,0 +1,142 @@
+use std::num::NonZeroU32;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tokio::time::{Instant, sleep};
+
+const MILLIS_PER_SECOND: u64 = 1000;
+const PAUSED_RETRY_DELAY: Duration = Duration::from_mins(1);
+
+/// Pause flag and airtime budget shared by every transfer of one campaign.
+#[derive(Debug)]
+pub(in crate::ota) struct Throttle {
+    paused: watch::Sender<bool>,
+    bucket: Option<Mutex<Bucket>>,
+}
+
+/// Token bucket refilled at the campaign's airtime limit, holding at most one second of data.
+///
+/// Tokens are counted in thousandths of a byte so that millisecond refills stay exact.
+#[derive(Debug)]
+struct Bucket {
+    bytes_per_second: u64,
+    tokens: u64,
+    refilled: Instant,
+}
+
+impl Throttle {
+    /// Create a throttle limiting the combined image data served to `airtime_limit` bytes per
+    /// second, if any.
+    pub(in crate::ota) fn new(airtime_limit: Option<NonZeroU32>, paused: bool) -> Self {
+        Self {
+            paused: watch::Sender::new(paused),
+            bucket: airtime_limit.map(|limit| {
+                let bytes_per_second = u64::from(limit.get());
+                Mutex::new(Bucket {
+                    bytes_per_second,
+                    tokens: bytes_per_second * MILLIS_PER_SECOND,
+                    refilled: Instant::now(),
+                })
+            }),
+        }
+    }
+
+    /// Pause or resume serving image data.
+    pub(in crate::ota) fn set_paused(&self, paused: bool) {
+        self.paused.send_replace(paused);
+    }
+
+    /// Take `length` bytes of airtime, or return how long the client should wait before asking
+    /// again.
+    pub(in crate::ota) fn try_acquire(&self, length: usize) -> Result<(), Duration> {
+        if *self.paused.borrow() {
+            return Err(PAUSED_RETRY_DELAY);
+        }
+        self.take(length)
+    }
+
+    /// Wait until the campaign runs and `length` bytes of airtime are available, then take them.
+    pub(in crate::ota) async fn acquire(&self, length: usize) {
+        let mut paused = self.paused.subscribe();
+        loop {
+            if paused.wait_for(|paused| !paused).await.is_err() {
+                return;
+            }
+            match self.take(length) {
+                Ok(()) => return,
+                Err(delay) => sleep(delay).await,
+            }
+        }
+    }
+
+    fn take(&self, length: usize) -> Result<(), Duration> {
+        let Some(bucket) = &self.bucket else {
+            return Ok(());
+        };
+        bucket
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take(length)
+    }
+}
+
+impl Bucket {
+    fn take(&mut self, length: usize) -> Result<(), Duration> {
+        let now = Instant::now();
+        let elapsed =
+            u64::try_from(now.duration_since(self.refilled).as_millis()).unwrap_or(u64::MAX);
+        let capacity = self.bytes_per_second * MILLIS_PER_SECOND;
+        self.tokens = self
+            .tokens
+            .saturating_add(elapsed.saturating_mul(self.bytes_per_second))
+            .min(capacity);
+        self.refilled = now;
+
+        // A block larger than one second of airtime waits for a full bucket instead of forever.
+        let required = u64::try_from(length)
+            .unwrap_or(u64::MAX)
+            .saturating_mul(MILLIS_PER_SECOND)
+            .min(capacity);
+        if let Some(tokens) = self.tokens.checked_sub(required) {
+            self.tokens = tokens;
+            return Ok(());
+        }
+        let deficit = required - self.tokens;
+        Err(Duration::from_millis(
+            deficit.div_ceil(self.bytes_per_second),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+    use std::time::Duration;
+
+    use super::{PAUSED_RETRY_DELAY, Throttle};
+
+    const BYTES_PER_SECOND: NonZeroU32 = NonZeroU32::new(100).expect("the rate is non-zero");
+    const BLOCK_LENGTH: usize = 60;
+    const SECOND_BLOCK_DEFICIT: Duration = Duration::from_millis(200);
+
+    #[test]
+    fn limits_airtime_to_the_configured_rate() {
+        let throttle = Throttle::new(Some(BYTES_PER_SECOND), false);
+
+        assert_eq!(throttle.try_acquire(BLOCK_LENGTH), Ok(()));
+        let delay = throttle
+            .try_acquire(BLOCK_LENGTH)
+            .expect_err("the second block exceeds one second of airtime");
+        assert!(delay > Duration::ZERO && delay <= SECOND_BLOCK_DEFICIT);
+    }
+
+    #[test]
+    fn a_paused_throttle_defers_data_until_resumed() {
+        let throttle = Throttle::new(None, true);
+
+        assert_eq!(throttle.try_acquire(BLOCK_LENGTH), Err(PAUSED_RETRY_DELAY));
+        throttle.set_paused(false);
+        assert_eq!(throttle.try_acquire(BLOCK_LENGTH), Ok(()));
+    }
+}
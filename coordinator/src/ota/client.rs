@@ -0,0 +1,777 @@
+use std::fmt::Debug;
+use std::future::Future;
+use std::io::{Seek, SeekFrom, Write};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use log::{debug, warn};
+use tokio::spawn;
+use tokio::sync::mpsc::{Receiver, Sender, WeakSender};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, sleep, timeout_at};
+use zb_aps::apsde::Source;
+use zb_core::{Cluster, Direction};
+use zb_zcl::ota_upgrade::{
+    Command as OtaCommand, ImageBlock, ImageBlockRequest, ImageBlockResponse,
+    ImageBlockResponsePayload, ImageId, ImageNotifyPayload, ImagePageRequest,
+    QueryNextImageRequest, QueryNextImageResponse, QueryResponse, UpgradeEndRequest,
+    UpgradeEndResponse, UpgradeEndStatus,
+};
+use zb_zcl::{Cluster as ZclCluster, Command, Directed, Scope, Scoped};
+
+pub use self::config::ClientConfig;
+use super::image::StreamVerifier;
+use super::{OTA_PROFILE, Request, request, zcl};
+use crate::event::{Event, EventSink, OtaClientEvent};
+use crate::{Error, Zcl};
+
+mod config;
+#[cfg(test)]
+mod tests;
+
+/// Upgrade time telling the client to wait for another Upgrade End Response.
+const WAIT_FOR_UPGRADE: u32 = u32::MAX;
+/// Interval at which a waiting client asks the server again whether it may upgrade.
+const UPGRADE_END_POLL_INTERVAL: Duration = Duration::from_hours(1);
+/// Largest Image Notify query jitter; a client queries if its random draw does not exceed it.
+const MAXIMUM_QUERY_JITTER: u8 = 100;
+
+/// Reason why the OTA client abandoned a download.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DownloadFailure {
+    /// The server aborted the download, or the client was stopped during it.
+    Aborted,
+    /// The server did not answer a request within the configured attempts.
+    NoResponse,
+    /// Writing to or seeking the caller-supplied sink failed.
+    Sink,
+    /// The downloaded image failed verification.
+    InvalidImage,
+}
+
+/// Verified image that the server allowed the client to activate.
+#[derive(Debug)]
+pub struct Activation<W> {
+    image: ImageId,
+    sink: W,
+}
+
+impl<W> Activation<W> {
+    /// Return the identifier of the downloaded image.
+    #[must_use]
+    pub const fn image(&self) -> ImageId {
+        self.image
+    }
+
+    /// Return the sink that contains the complete OTA file.
+    #[must_use]
+    pub fn into_sink(self) -> W {
+        self.sink
+    }
+}
+
+/// Running local OTA client.
+///
+/// The future resolves once a downloaded and verified image is due for activation. Dropping it
+/// stops the client; a download in progress is aborted with an Upgrade End Request.
+#[must_use = "dropping this future stops the OTA client"]
+#[derive(Debug)]
+pub struct Download<W> {
+    messages: Sender<Message>,
+    completion: oneshot::Receiver<Result<Activation<W>, Error>>,
+}
+
+impl<W> Download<W>
+where
+    W: Write + Seek + Send + 'static,
+{
+    /// Spawn a client that streams downloads into `sink`.
+    pub(crate) fn spawn(
+        zcl: Sender<zcl::Message>,
+        events: EventSink,
+        config: ClientConfig,
+        sink: W,
+    ) -> Self {
+        let (messages, inbox) = tokio::sync::mpsc::channel(crate::MPSC_CHANNEL_SIZE);
+        let (completion, result) = oneshot::channel();
+        let client = Client {
+            upstream: Upstream {
+                zcl,
+                events,
+                config,
+            },
+            sink,
+            inbox,
+            sender: messages.downgrade(),
+            subscription: None,
+        };
+        spawn(client.run(completion));
+        Self {
+            messages,
+            completion: result,
+        }
+    }
+}
+
+impl<W> Download<W> {
+    /// Query the server immediately instead of waiting for the next scheduled query.
+    ///
+    /// The request is ignored while a download is in progress.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SendError`] if the client has stopped.
+    pub async fn query_now(&self) -> Result<(), Error> {
+        self.messages.send(Message::QueryNow).await?;
+        Ok(())
+    }
+}
+
+impl<W> Future for Download<W> {
+    type Output = Result<Activation<W>, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.completion)
+            .poll(context)
+            .map(|result| result?)
+    }
+}
+
+/// Messages processed by the client task.
+#[derive(Debug)]
+enum Message {
+    QueryNow,
+    Received { source: Source, command: OtaCommand },
+}
+
+/// Inbox item offered to a waiting client state.
+enum Inbound {
+    QueryNow,
+    Command(OtaCommand),
+}
+
+/// Outcome of waiting for an inbox item.
+enum Wait<T> {
+    Selected(T),
+    Elapsed,
+    Stopped,
+}
+
+/// Outcome of one block or page request.
+enum Step {
+    Received,
+    Stalled,
+}
+
+/// Outcome of one download attempt.
+enum Downloaded {
+    Complete,
+    Failed(DownloadFailure),
+}
+
+/// Registered OTA client subscription and its frame-forwarding task.
+#[derive(Debug)]
+struct ActiveSubscription {
+    messages: Sender<zcl::SubscriptionMessage>,
+    task: JoinHandle<()>,
+}
+
+/// Connection of the client to its upstream server, independent of the download sink.
+struct Upstream {
+    zcl: Sender<zcl::Message>,
+    events: EventSink,
+    config: ClientConfig,
+}
+
+/// Stateful OTA client task.
+struct Client<W> {
+    upstream: Upstream,
+    sink: W,
+    inbox: Receiver<Message>,
+    sender: WeakSender<Message>,
+    subscription: Option<ActiveSubscription>,
+}
+
+impl<W> Client<W>
+where
+    W: Write + Seek + Send + 'static,
+{
+    async fn run(mut self, completion: oneshot::Sender<Result<Activation<W>, Error>>) {
+        let result = self.serve().await;
+        self.unsubscribe().await;
+        let activation = match result {
+            Ok(Some(image)) => Ok(Activation {
+                image,
+                sink: self.sink,
+            }),
+            Ok(None) => return,
+            Err(error) => Err(error),
+        };
+        let _result = completion.send(activation);
+    }
+
+    /// Poll the server until an image is due for activation, returning `None` once stopped.
+    async fn serve(&mut self) -> Result<Option<ImageId>, Error> {
+        self.subscribe().await?;
+        let mut delay = jitter(self.upstream.config.query_jitter());
+        loop {
+            if !self.wait_for_query(delay).await {
+                return Ok(None);
+            }
+            delay =
+                self.upstream.config.query_interval() + jitter(self.upstream.config.query_jitter());
+
+            let Some((image, image_size)) = self.upstream.query().await? else {
+                continue;
+            };
+            self.upstream
+                .events
+                .emit(Event::OtaClient(OtaClientEvent::ImageAvailable {
+                    image,
+                    image_size,
+                }));
+            if let Downloaded::Failed(failure) = self.download(image, image_size).await? {
+                self.upstream.fail(image, failure).await?;
+                continue;
+            }
+
+            match self.upgrade(image).await? {
+                Wait::Selected(()) => {
+                    self.upstream
+                        .events
+                        .emit(Event::OtaClient(OtaClientEvent::ActivationDue { image }));
+                    return Ok(Some(image));
+                }
+                Wait::Elapsed => {
+                    self.upstream
+                        .fail(image, DownloadFailure::NoResponse)
+                        .await?;
+                }
+                Wait::Stopped => return Ok(None),
+            }
+        }
+    }
+
+    /// Wait for the scheduled query, an explicit query request, or a matching Image Notify.
+    async fn wait_for_query(&mut self, delay: Duration) -> bool {
+        let current = self.upstream.config.current_image();
+        let deadline = Instant::now() + delay;
+        match self
+            .receive_until(deadline, |inbound| match inbound {
+                Inbound::QueryNow => Some(()),
+                Inbound::Command(OtaCommand::ImageNotify(notify)) => {
+                    notifies(notify.payload(), current).then_some(())
+                }
+                Inbound::Command(_) => None,
+            })
+            .await
+        {
+            Wait::Selected(()) | Wait::Elapsed => true,
+            Wait::Stopped => false,
+        }
+    }
+
+    /// Download and verify `image` into the sink.
+    async fn download(&mut self, image: ImageId, image_size: u32) -> Result<Downloaded, Error> {
+        let Ok(length) = usize::try_from(image_size) else {
+            return Ok(Downloaded::Failed(DownloadFailure::InvalidImage));
+        };
+        if let Err(error) = self.sink.seek(SeekFrom::Start(0)) {
+            warn!("Failed to rewind the OTA download sink: {error}");
+            return Ok(Downloaded::Failed(DownloadFailure::Sink));
+        }
+
+        let mut verifier = StreamVerifier::new(self.upstream.config.verifier(), length);
+        let mut failed_attempts = 0;
+        while verifier.position() < length {
+            if self.inbox.is_closed() {
+                return Ok(Downloaded::Failed(DownloadFailure::Aborted));
+            }
+
+            let progress = match self.upstream.config.pages() {
+                Some((page_size, response_spacing)) => {
+                    let page_end = (verifier.position() + usize::from(page_size)).min(length);
+                    self.download_page(&mut verifier, image, page_size, page_end, response_spacing)
+                        .await?
+                }
+                None => self.download_block(&mut verifier, image).await?,
+            };
+            match progress {
+                Ok(Step::Received) => failed_attempts = 0,
+                Ok(Step::Stalled) => {
+                    failed_attempts += 1;
+                    if failed_attempts >= self.upstream.config.attempts() {
+                        return Ok(Downloaded::Failed(DownloadFailure::NoResponse));
+                    }
+                }
+                Err(failure) => return Ok(Downloaded::Failed(failure)),
+            }
+        }
+
+        if let Err(error) = self.sink.flush() {
+            warn!("Failed to flush the OTA download sink: {error}");
+            return Ok(Downloaded::Failed(DownloadFailure::Sink));
+        }
+        if let Err(error) = verifier.finish() {
+            warn!("Downloaded OTA image {image:?} is invalid: {error}");
+            return Ok(Downloaded::Failed(DownloadFailure::InvalidImage));
+        }
+        Ok(Downloaded::Complete)
+    }
+
+    /// Request and store the block at the current position.
+    ///
+    /// Requests are repeated by [`Upstream::communicate`], so a block request never stalls.
+    async fn download_block(
+        &mut self,
+        verifier: &mut StreamVerifier,
+        image: ImageId,
+    ) -> Result<Result<Step, DownloadFailure>, Error> {
+        let request = ImageBlockRequest::new(
+            image,
+            file_offset(verifier.position()),
+            self.upstream.config.maximum_data_size(),
+            self.upstream.config.ieee_address(),
+            None,
+        );
+        let Some(response) = self
+            .upstream
+            .communicate::<_, ImageBlockResponse>(request)
+            .await?
+        else {
+            return Ok(Err(DownloadFailure::NoResponse));
+        };
+        Ok(self
+            .handle_block_response(verifier, image, response)
+            .await
+            .map(|()| Step::Received))
+    }
+
+    /// Request one page and store the blocks that arrive for it in order.
+    ///
+    /// Blocks at unexpected offsets are ignored. If the page stops arriving, the caller requests
+    /// a new page from the first missing offset. The page stalls if no block arrives at all.
+    async fn download_page(
+        &mut self,
+        verifier: &mut StreamVerifier,
+        image: ImageId,
+        page_size: u16,
+        page_end: usize,
+        response_spacing: u16,
+    ) -> Result<Result<Step, DownloadFailure>, Error> {
+        let start = verifier.position();
+        let request = ImagePageRequest::new(
+            image,
+            file_offset(start),
+            self.upstream.config.maximum_data_size(),
+            page_size,
+            response_spacing,
+            self.upstream.config.ieee_address(),
+        );
+        let request = self
+            .upstream
+            .request(request)
+            .map_asdu(|frame| frame.with_disable_default_response(true));
+        if let Err(error) = self.upstream.zcl.transmit(request).await {
+            if self.upstream.zcl.is_closed() {
+                return Err(error);
+            }
+            warn!("Failed to send OTA Image Page Request: {error}");
+            return Ok(Ok(Step::Stalled));
+        }
+
+        let mut step = Step::Stalled;
+        let spacing = Duration::from_millis(u64::from(response_spacing));
+        while verifier.position() < page_end {
+            let deadline = Instant::now() + self.upstream.config.response_timeout() + spacing;
+            let response = match self
+                .receive_until(deadline, |inbound| match inbound {
+                    Inbound::Command(OtaCommand::ImageBlockResponse(response)) => Some(response),
+                    Inbound::Command(_) | Inbound::QueryNow => None,
+                })
+                .await
+            {
+                Wait::Selected(response) => response,
+                Wait::Elapsed => return Ok(Ok(step)),
+                Wait::Stopped => return Ok(Err(DownloadFailure::Aborted)),
+            };
+            step = Step::Received;
+
+            if let ImageBlockResponsePayload::Success(block) = response.payload()
+                && usize::try_from(block.file_offset()).ok() != Some(verifier.position())
+            {
+                debug!(
+                    "Ignoring OTA block at offset {} while expecting {}",
+                    block.file_offset(),
+                    verifier.position()
+                );
+                continue;
+            }
+            let waited = matches!(
+                response.payload(),
+                ImageBlockResponsePayload::WaitForData(_)
+            );
+            if let Err(failure) = self.handle_block_response(verifier, image, *response).await {
+                return Ok(Err(failure));
+            }
+            if waited {
+                return Ok(Ok(step));
+            }
+        }
+        Ok(Ok(step))
+    }
+
+    /// Store a successful block, wait as instructed, or report an abort.
+    async fn handle_block_response(
+        &mut self,
+        verifier: &mut StreamVerifier,
+        image: ImageId,
+        response: ImageBlockResponse,
+    ) -> Result<(), DownloadFailure> {
+        match response.payload() {
+            ImageBlockResponsePayload::Success(block) => self.store(verifier, image, block),
+            ImageBlockResponsePayload::WaitForData(wait) => {
+                sleep(relative_delay(wait.current_time(), wait.request_time())).await;
+                Ok(())
+            }
+            ImageBlockResponsePayload::Abort => Err(DownloadFailure::Aborted),
+        }
+    }
+
+    /// Write one block to the sink and feed it to the verifier.
+    fn store(
+        &mut self,
+        verifier: &mut StreamVerifier,
+        image: ImageId,
+        block: &ImageBlock,
+    ) -> Result<(), DownloadFailure> {
+        if block.image() != image
+            || usize::try_from(block.file_offset()).ok() != Some(verifier.position())
+            || block.image_data().is_empty()
+        {
+            warn!(
+                "OTA server returned an unexpected block at offset {}",
+                block.file_offset()
+            );
+            return Err(DownloadFailure::InvalidImage);
+        }
+
+        self.sink.write_all(block.image_data()).map_err(|error| {
+            warn!("Failed to write to the OTA download sink: {error}");
+            DownloadFailure::Sink
+        })?;
+        verifier.update(block.image_data()).map_err(|error| {
+            warn!("Downloaded OTA image {image:?} is invalid: {error}");
+            DownloadFailure::InvalidImage
+        })
+    }
+
+    /// Report a successful download and wait until the server allows activation.
+    ///
+    /// Returns [`Wait::Elapsed`] if the server does not answer the Upgrade End Request.
+    async fn upgrade(&mut self, image: ImageId) -> Result<Wait<()>, Error> {
+        let mut response = loop {
+            let request = UpgradeEndRequest::new(UpgradeEndStatus::Success, image);
+            let Some(response) = self
+                .upstream
+                .communicate::<_, UpgradeEndResponse>(request)
+                .await?
+            else {
+                return Ok(Wait::Elapsed);
+            };
+            if response.upgrade_time() != WAIT_FOR_UPGRADE {
+                break response;
+            }
+
+            self.upstream
+                .events
+                .emit(Event::OtaClient(OtaClientEvent::UpgradeScheduled {
+                    image,
+                    delay: None,
+                }));
+            let deadline = Instant::now() + UPGRADE_END_POLL_INTERVAL;
+            match self.wait_for_upgrade_end(image, deadline).await {
+                Wait::Selected(response) if response.upgrade_time() != WAIT_FOR_UPGRADE => {
+                    break response;
+                }
+                Wait::Selected(_) | Wait::Elapsed => {}
+                Wait::Stopped => return Ok(Wait::Stopped),
+            }
+        };
+
+        loop {
+            let delay = relative_delay(response.current_time(), response.upgrade_time());
+            self.upstream
+                .events
+                .emit(Event::OtaClient(OtaClientEvent::UpgradeScheduled {
+                    image,
+                    delay: Some(delay),
+                }));
+            match self
+                .wait_for_upgrade_end(image, Instant::now() + delay)
+                .await
+            {
+                Wait::Selected(rescheduled) if rescheduled.upgrade_time() != WAIT_FOR_UPGRADE => {
+                    response = rescheduled;
+                }
+                Wait::Selected(_) => {}
+                Wait::Elapsed => return Ok(Wait::Selected(())),
+                Wait::Stopped => return Ok(Wait::Stopped),
+            }
+        }
+    }
+
+    /// Wait for an unsolicited Upgrade End Response that reschedules `image`.
+    async fn wait_for_upgrade_end(
+        &mut self,
+        image: ImageId,
+        deadline: Instant,
+    ) -> Wait<UpgradeEndResponse> {
+        self.receive_until(deadline, |inbound| match inbound {
+            Inbound::Command(OtaCommand::UpgradeEndResponse(response))
+                if response.image() == image =>
+            {
+                Some(*response)
+            }
+            Inbound::Command(_) | Inbound::QueryNow => None,
+        })
+        .await
+    }
+
+    /// Wait until `deadline` for an inbox item from the server that `select` accepts.
+    async fn receive_until<T, F>(&mut self, deadline: Instant, mut select: F) -> Wait<T>
+    where
+        F: FnMut(Inbound) -> Option<T>,
+    {
+        let server = self.upstream.config.server().address();
+        loop {
+            let inbound = match timeout_at(deadline, self.inbox.recv()).await {
+                Err(_) => return Wait::Elapsed,
+                Ok(None) => return Wait::Stopped,
+                Ok(Some(Message::QueryNow)) => Inbound::QueryNow,
+                Ok(Some(Message::Received { source, command })) => {
+                    if !matches!(source, Source::Network { address, .. } if address == server) {
+                        debug!("Ignoring OTA command from {source:?}");
+                        continue;
+                    }
+                    Inbound::Command(command)
+                }
+            };
+            if let Some(selected) = select(inbound) {
+                return Wait::Selected(selected);
+            }
+        }
+    }
+
+    /// Register the subscription delivering server-to-client OTA commands to this client.
+    async fn subscribe(&mut self) -> Result<(), Error> {
+        let (subscription, frames) = zcl::Subscription::channel(zcl::SubscriptionFilter::new(
+            Cluster::OtaUpgrade,
+            Scope::ClusterSpecific,
+            Direction::ServerToClient,
+        ));
+        let messages = frames.sender();
+        self.upstream
+            .zcl
+            .send(zcl::Message::Subscribe { subscription })
+            .await?;
+        self.subscription = Some(ActiveSubscription {
+            messages,
+            task: spawn(forward_subscription_frames(frames, self.sender.clone())),
+        });
+        Ok(())
+    }
+
+    async fn unsubscribe(&mut self) {
+        let Some(subscription) = self.subscription.take() else {
+            return;
+        };
+        subscription.task.abort();
+        if self
+            .upstream
+            .zcl
+            .send(zcl::Message::Unsubscribe {
+                messages: subscription.messages,
+            })
+            .await
+            .is_err()
+        {
+            debug!("Failed to unregister the OTA client subscription");
+        }
+    }
+}
+
+impl Upstream {
+    /// Ask the server for the next image and return the offered image and its size.
+    async fn query(&self) -> Result<Option<(ImageId, u32)>, Error> {
+        let current = self.config.current_image();
+        let request = QueryNextImageRequest::new(current, self.config.hardware_version());
+        let Some(response) = self
+            .communicate::<_, QueryNextImageResponse>(request)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        match response.response() {
+            QueryResponse::Success { image, image_size }
+                if image.manufacturer_code() == current.manufacturer_code()
+                    && image.image_type() == current.image_type() =>
+            {
+                Ok(Some((image, image_size)))
+            }
+            QueryResponse::Success { image, .. } => {
+                warn!("Ignoring offered OTA image {image:?} for another device type");
+                Ok(None)
+            }
+            QueryResponse::NoImageAvailable | QueryResponse::NotAuthorized => Ok(None),
+        }
+    }
+
+    /// Tell the server that the download of `image` ended without success and report it.
+    async fn fail(&self, image: ImageId, failure: DownloadFailure) -> Result<(), Error> {
+        let status = match failure {
+            DownloadFailure::InvalidImage => UpgradeEndStatus::InvalidImage,
+            DownloadFailure::Aborted | DownloadFailure::NoResponse | DownloadFailure::Sink => {
+                UpgradeEndStatus::Abort
+            }
+        };
+        warn!("OTA download of {image:?} failed: {failure:?}");
+        let request = self.request(UpgradeEndRequest::new(status, image));
+        if let Err(error) = self.zcl.communicate_default(request).await {
+            if self.zcl.is_closed() {
+                return Err(error);
+            }
+            debug!("OTA server did not acknowledge the failed download: {error}");
+        }
+        self.events
+            .emit(Event::OtaClient(OtaClientEvent::DownloadFailed {
+                image,
+                failure,
+            }));
+        Ok(())
+    }
+
+    /// Send `command` to the server and wait for its response, repeating it up to the
+    /// configured number of attempts.
+    ///
+    /// Returns `Ok(None)` if every attempt failed, and an error only once the ZCL actor stopped.
+    async fn communicate<C, T>(&self, command: C) -> Result<Option<T>, Error>
+    where
+        C: Clone + Command + Directed + Scoped + le_stream::ToLeStream,
+        T: TryFrom<ZclCluster, Error: Debug> + Send + Unpin,
+    {
+        for _ in 0..self.config.attempts() {
+            let request = self.request(command.clone());
+            let result = match self
+                .zcl
+                .communicate_with_timeout::<T>(request, self.config.response_timeout())
+                .await
+            {
+                Ok(response) => response.await,
+                Err(error) => Err(error),
+            };
+            match result {
+                Ok(response) => return Ok(Some(response)),
+                Err(error) if self.zcl.is_closed() => return Err(error),
+                Err(error) => debug!("OTA client request failed: {error}"),
+            }
+        }
+        Ok(None)
+    }
+
+    fn request<C>(&self, command: C) -> Request
+    where
+        C: Command + Directed + Scoped + le_stream::ToLeStream,
+    {
+        request(
+            self.config.server().into(),
+            self.config.source_endpoint(),
+            OTA_PROFILE,
+            Cluster::OtaUpgrade.as_u16(),
+            command,
+        )
+    }
+}
+
+/// Forward subscribed server-to-client OTA commands into the client inbox.
+async fn forward_subscription_frames(
+    mut frames: zcl::SubscriptionReceiver,
+    sender: WeakSender<Message>,
+) {
+    while let Some(message) = frames.recv().await {
+        let zcl::SubscriptionMessage { indication } = message;
+        let source = indication.metadata().source();
+        let (_, zcl_frame) = indication.into_parts();
+        let (_, cluster) = zcl_frame.into_parts();
+        let ZclCluster::OtaUpgrade(command) = cluster else {
+            warn!("Discarding non-OTA command delivered by the OTA client subscription");
+            continue;
+        };
+        let Some(sender) = sender.upgrade() else {
+            return;
+        };
+        if sender
+            .send(Message::Received { source, command })
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+/// Return whether an Image Notify asks a client running `current` to query the server.
+fn notifies(payload: ImageNotifyPayload, current: ImageId) -> bool {
+    let (query_jitter, matches) = match payload {
+        ImageNotifyPayload::QueryJitter(query_jitter) => (query_jitter, true),
+        ImageNotifyPayload::Manufacturer {
+            query_jitter,
+            manufacturer_code,
+        } => (
+            query_jitter,
+            manufacturer_code == current.manufacturer_code(),
+        ),
+        ImageNotifyPayload::ImageType {
+            query_jitter,
+            manufacturer_code,
+            image_type,
+        } => (
+            query_jitter,
+            manufacturer_code == current.manufacturer_code() && image_type == current.image_type(),
+        ),
+        ImageNotifyPayload::FileVersion {
+            query_jitter,
+            image,
+        } => (
+            query_jitter,
+            image.manufacturer_code() == current.manufacturer_code()
+                && image.image_type() == current.image_type()
+                && image.file_version() != current.file_version(),
+        ),
+    };
+    matches && rand::random_range(1..=MAXIMUM_QUERY_JITTER) <= query_jitter.into_inner()
+}
+
+/// Return a random delay of at most `jitter`.
+fn jitter(jitter: Duration) -> Duration {
+    rand::random_range(Duration::ZERO..=jitter)
+}
+
+/// Return the delay until `requested`, given the server's `current` UTC time.
+///
+/// A current time of zero means that `requested` is an offset in seconds from now.
+fn relative_delay(current: u32, requested: u32) -> Duration {
+    let seconds = if current == 0 {
+        requested
+    } else {
+        requested.saturating_sub(current)
+    };
+    Duration::from_secs(u64::from(seconds))
+}
+
+fn file_offset(position: usize) -> u32 {
+    u32::try_from(position).expect("download positions are bounded by the 32-bit image size")
+}
@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use zb_aps::apsde::{IndividualEndpoint, NetworkDestination};
+use zb_core::IeeeAddress;
+use zb_zcl::ota_upgrade::ImageId;
+
+use crate::ota::ImageVerifier;
+
+const DEFAULT_QUERY_INTERVAL: Duration = Duration::from_hours(24);
+const DEFAULT_QUERY_JITTER: Duration = Duration::from_mins(5);
+const DEFAULT_MAXIMUM_DATA_SIZE: u8 = 64;
+const DEFAULT_RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_ATTEMPTS: u8 = 3;
+
+/// Paging parameters of a download that uses Image Page Request.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Paging {
+    page_size: u16,
+    response_spacing: u16,
+}
+
+/// Settings of the local OTA Upgrade client.
+///
+/// The client queries the upstream server after a random delay of up to the query jitter, and
+/// again after every query interval plus a fresh jitter.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ClientConfig {
+    server: NetworkDestination,
+    source_endpoint: IndividualEndpoint,
+    current_image: ImageId,
+    hardware_version: Option<u16>,
+    ieee_address: Option<IeeeAddress>,
+    query_interval: Duration,
+    query_jitter: Duration,
+    maximum_data_size: u8,
+    paging: Option<Paging>,
+    response_timeout: Duration,
+    attempts: u8,
+    integrity_code_required: bool,
+}
+
+impl ClientConfig {
+    /// Create the settings of a client on `source_endpoint` that runs `current_image` and
+    /// downloads from the OTA server endpoint `server`.
+    #[must_use]
+    pub const fn new(
+        server: NetworkDestination,
+        source_endpoint: IndividualEndpoint,
+        current_image: ImageId,
+    ) -> Self {
+        Self {
+            server,
+            source_endpoint,
+            current_image,
+            hardware_version: None,
+            ieee_address: None,
+            query_interval: DEFAULT_QUERY_INTERVAL,
+            query_jitter: DEFAULT_QUERY_JITTER,
+            maximum_data_size: DEFAULT_MAXIMUM_DATA_SIZE,
+            paging: None,
+            response_timeout: DEFAULT_RESPONSE_TIMEOUT,
+            attempts: DEFAULT_ATTEMPTS,
+            integrity_code_required: false,
+        }
+    }
+
+    /// Report `hardware_version` in queries and reject images that exclude it.
+    #[must_use]
+    pub const fn with_hardware_version(mut self, hardware_version: u16) -> Self {
+        self.hardware_version = Some(hardware_version);
+        self
+    }
+
+    /// Send `ieee_address` as the request node address and reject images for other devices.
+    #[must_use]
+    pub const fn with_ieee_address(mut self, ieee_address: IeeeAddress) -> Self {
+        self.ieee_address = Some(ieee_address);
+        self
+    }
+
+    /// Select the delay between two scheduled queries.
+    #[must_use]
+    pub const fn with_query_interval(mut self, query_interval: Duration) -> Self {
+        self.query_interval = query_interval;
+        self
+    }
+
+    /// Select the upper bound of the random delay added to every scheduled query.
+    #[must_use]
+    pub const fn with_query_jitter(mut self, query_jitter: Duration) -> Self {
+        self.query_jitter = query_jitter;
+        self
+    }
+
+    /// Select the largest amount of image data requested per block.
+    #[must_use]
+    pub const fn with_maximum_data_size(mut self, maximum_data_size: u8) -> Self {
+        self.maximum_data_size = maximum_data_size;
+        self
+    }
+
+    /// Download with Image Page Request instead of one Image Block Request per block.
+    ///
+    /// The server sends up to `page_size` bytes per request and waits `response_spacing`
+    /// milliseconds between the blocks of a page.
+    #[must_use]
+    pub const fn with_pages(mut self, page_size: u16, response_spacing: u16) -> Self {
+        self.paging = Some(Paging {
+            page_size,
+            response_spacing,
+        });
+        self
+    }
+
+    /// Select how long the client waits for each response before it repeats a request.
+    #[must_use]
+    pub const fn with_response_timeout(mut self, response_timeout: Duration) -> Self {
+        self.response_timeout = response_timeout;
+        self
+    }
+
+    /// Select how often a request is sent before the download is aborted.
+    #[must_use]
+    pub const fn with_attempts(mut self, attempts: u8) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Reject downloaded images that do not contain an image integrity code.
+    #[must_use]
+    pub const fn with_required_integrity_code(mut self) -> Self {
+        self.integrity_code_required = true;
+        self
+    }
+
+    /// Return the upstream OTA server endpoint.
+    #[must_use]
+    pub const fn server(&self) -> NetworkDestination {
+        self.server
+    }
+
+    /// Return the local endpoint hosting the client.
+    #[must_use]
+    pub const fn source_endpoint(&self) -> IndividualEndpoint {
+        self.source_endpoint
+    }
+
+    /// Return the image the client currently runs.
+    #[must_use]
+    pub const fn current_image(&self) -> ImageId {
+        self.current_image
+    }
+
+    /// Return the hardware version reported in queries.
+    #[must_use]
+    pub const fn hardware_version(&self) -> Option<u16> {
+        self.hardware_version
+    }
+
+    /// Return the IEEE address sent as the request node address.
+    #[must_use]
+    pub const fn ieee_address(&self) -> Option<IeeeAddress> {
+        self.ieee_address
+    }
+
+    /// Return the delay between two scheduled queries.
+    #[must_use]
+    pub const fn query_interval(&self) -> Duration {
+        self.query_interval
+    }
+
+    /// Return the upper bound of the random delay added to every scheduled query.
+    #[must_use]
+    pub const fn query_jitter(&self) -> Duration {
+        self.query_jitter
+    }
+
+    /// Return the largest amount of image data requested per block.
+    #[must_use]
+    pub const fn maximum_data_size(&self) -> u8 {
+        self.maximum_data_size
+    }
+
+    /// Return the page size and response spacing if the client downloads pages.
+    #[must_use]
+    pub const fn pages(&self) -> Option<(u16, u16)> {
+        match self.paging {
+            Some(paging) => Some((paging.page_size, paging.response_spacing)),
+            None => None,
+        }
+    }
+
+    /// Return how long the client waits for each response.
+    #[must_use]
+    pub const fn response_timeout(&self) -> Duration {
+        self.response_timeout
+    }
+
+    /// Return how often a request is sent before the download is aborted.
+    #[must_use]
+    pub const fn attempts(&self) -> u8 {
+        self.attempts
+    }
+
+    /// Return the verifier applied to downloaded images.
+    pub(super) const fn verifier(&self) -> ImageVerifier {
+        let mut verifier = ImageVerifier::new();
+        if let Some(ieee_address) = self.ieee_address {
+            verifier = verifier.with_device(ieee_address);
+        }
+        if let Some(hardware_version) = self.hardware_version {
+            verifier = verifier.with_hardware_version(hardware_version);
+        }
+        if self.integrity_code_required {
+            verifier = verifier.with_required_integrity_code();
+        }
+        verifier
+    }
+}
@@ -0,0 +1,365 @@
+use std::io::Cursor;
+use std::time::Duration;
+
+use bytes::Bytes;
+use le_stream::ToLeStream;
+use tokio::sync::mpsc::{Receiver, channel};
+use tokio::sync::oneshot;
+use tokio::time::timeout;
+use zb_aps::apsde::{
+    DataIndication, IndicationMetadata, IndicationStatus, IndividualEndpoint, NetworkAddress,
+    NetworkDestination, ReceivedDestination, Security, Source,
+};
+use zb_core::endpoint::Application;
+use zb_core::{Cluster, Direction, Endpoint};
+use zb_zcl::global::default_response::DefaultResponse;
+use zb_zcl::ota_upgrade::{
+    Command as OtaCommand, ImageBlock, ImageBlockResponse, ImageBlockResponsePayload, ImageId,
+    ImageNotify, ImageNotifyPayload, QueryJitter, QueryNextImageResponse, QueryResponse,
+    UpgradeEndRequest, UpgradeEndResponse, UpgradeEndStatus,
+};
+use zb_zcl::{Cluster as ZclCluster, Command, Frame, Header, Scope, global};
+
+use super::{ClientConfig, Download, DownloadFailure};
+use crate::aps::TransmissionResponse;
+use crate::correlation::{Cancellation, Key};
+use crate::event::{Event, EventSink, OtaClientEvent};
+use crate::ota::ImageBuilder;
+use crate::response::ApsProtocolResponse;
+use crate::zcl;
+
+const TEST_TIMEOUT: Duration = Duration::from_secs(1);
+const LONG_QUERY_JITTER: Duration = Duration::from_hours(1);
+const TEST_RESPONSE_TIMEOUT: Duration = Duration::from_millis(100);
+const TEST_CHANNEL_SIZE: usize = 16;
+const TEST_APS_COUNTER: u8 = 1;
+const MANUFACTURER_CODE: u16 = 0x1234;
+const IMAGE_TYPE: u16 = 0x5678;
+const CURRENT_FILE_VERSION: u32 = 0x0102_0304;
+const SERVER_ADDRESS: u16 = 0x0000;
+const LOCAL_ADDRESS: u16 = 0x1a2b;
+const MAXIMUM_DATA_SIZE: u8 = 16;
+const PAGE_SIZE: u16 = 48;
+const FIRMWARE: &[u8] = &[0x5a; 100];
+const QUERY_ALWAYS: u8 = 100;
+const ENDPOINT: Endpoint = Endpoint::Application(Application::MIN);
+
+/// Behaviour of the scripted upstream server.
+#[derive(Clone, Copy)]
+struct Script {
+    notify_on_subscribe: bool,
+    corrupt: bool,
+}
+
+#[test]
+fn image_notify_triggers_a_verified_block_download() {
+    run_test(async {
+        let file = test_file();
+        let (events, mut received_events) = channel(TEST_CHANNEL_SIZE);
+        let (zcl_sender, zcl_receiver) = channel(TEST_CHANNEL_SIZE);
+        let server = tokio::spawn(serve(
+            zcl_receiver,
+            file.clone(),
+            Script {
+                notify_on_subscribe: true,
+                corrupt: false,
+            },
+        ));
+        let download = Download::spawn(
+            zcl_sender,
+            EventSink::new(events),
+            test_config().with_query_jitter(LONG_QUERY_JITTER),
+            Cursor::new(Vec::new()),
+        );
+
+        let activation = timeout(TEST_TIMEOUT, download)
+            .await
+            .expect("notified client downloads before its scheduled query")
+            .expect("download completes");
+        assert_eq!(activation.image(), new_image());
+        assert_eq!(activation.into_sink().into_inner(), file.to_vec());
+
+        let statuses = server.await.expect("scripted server finishes");
+        assert_eq!(statuses, [UpgradeEndStatus::Success]);
+        let events = drain(&mut received_events);
+        assert!(matches!(
+            events.as_slice(),
+            [
+                OtaClientEvent::ImageAvailable { image, image_size },
+                OtaClientEvent::UpgradeScheduled { delay: Some(Duration::ZERO), .. },
+                OtaClientEvent::ActivationDue { .. },
+            ] if *image == new_image() && usize::try_from(*image_size).ok() == Some(file.len())
+        ));
+    });
+}
+
+#[test]
+fn page_download_of_a_corrupted_image_is_rejected() {
+    run_test(async {
+        let (events, mut received_events) = channel(TEST_CHANNEL_SIZE);
+        let (zcl_sender, zcl_receiver) = channel(TEST_CHANNEL_SIZE);
+        let server = tokio::spawn(serve(
+            zcl_receiver,
+            test_file(),
+            Script {
+                notify_on_subscribe: false,
+                corrupt: true,
+            },
+        ));
+        let download = Download::spawn(
+            zcl_sender,
+            EventSink::new(events),
+            test_config().with_pages(PAGE_SIZE, 0),
+            Cursor::new(Vec::new()),
+        );
+
+        let failure = loop {
+            let event = timeout(TEST_TIMEOUT, received_events.recv())
+                .await
+                .expect("client reports the failed download")
+                .expect("event channel is open");
+            if let Event::OtaClient(OtaClientEvent::DownloadFailed { image, failure }) = event {
+                assert_eq!(image, new_image());
+                break failure;
+            }
+        };
+        assert_eq!(failure, DownloadFailure::InvalidImage);
+
+        drop(download);
+        let statuses = timeout(TEST_TIMEOUT, server)
+            .await
+            .expect("stopped client releases the ZCL actor")
+            .expect("scripted server finishes");
+        assert_eq!(statuses, [UpgradeEndStatus::InvalidImage]);
+    });
+}
+
+/// Answer the client's ZCL messages until it stops, returning the reported Upgrade End statuses.
+async fn serve(
+    mut zcl: Receiver<zcl::Message>,
+    file: Bytes,
+    script: Script,
+) -> Vec<UpgradeEndStatus> {
+    let mut subscription = None;
+    let mut statuses = Vec::new();
+    while let Some(message) = zcl.recv().await {
+        match message {
+            zcl::Message::Subscribe {
+                subscription: registered,
+            } => {
+                if script.notify_on_subscribe {
+                    let jitter = QueryJitter::new(QUERY_ALWAYS).expect("valid query jitter");
+                    deliver(
+                        &registered,
+                        ImageNotify::new(ImageNotifyPayload::QueryJitter(jitter)),
+                    );
+                }
+                subscription = Some(registered);
+            }
+            zcl::Message::Unsubscribe { .. } => subscription = None,
+            zcl::Message::Communicate {
+                request, response, ..
+            } => {
+                let reply = match parse(request) {
+                    OtaCommand::QueryNextImageRequest(_) => {
+                        ZclCluster::OtaUpgrade(OtaCommand::QueryNextImageResponse(Box::new(
+                            QueryNextImageResponse::new(QueryResponse::Success {
+                                image: new_image(),
+                                image_size: u32::try_from(file.len())
+                                    .expect("test file length fits u32"),
+                            }),
+                        )))
+                    }
+                    OtaCommand::ImageBlockRequest(request) => {
+                        let offset =
+                            usize::try_from(request.file_offset()).expect("test offsets fit usize");
+                        let end =
+                            (offset + usize::from(request.maximum_data_size())).min(file.len());
+                        ZclCluster::OtaUpgrade(OtaCommand::ImageBlockResponse(Box::new(
+                            block_response(&file, offset, end, script.corrupt),
+                        )))
+                    }
+                    OtaCommand::UpgradeEndRequest(request) => {
+                        statuses.push(request.status());
+                        if request.status() == UpgradeEndStatus::Success {
+                            ZclCluster::OtaUpgrade(OtaCommand::UpgradeEndResponse(Box::new(
+                                UpgradeEndResponse::new(request.image(), 0, 0),
+                            )))
+                        } else {
+                            ZclCluster::Global(global::Command::DefaultResponse(Box::new(
+                                DefaultResponse::new(UpgradeEndRequest::ID, 0),
+                            )))
+                        }
+                    }
+                    other => panic!("unexpected OTA client request: {other:?}"),
+                };
+                assert!(response.send(Ok(protocol_response(reply))).is_ok());
+            }
+            zcl::Message::Transmit { request, response } => {
+                assert!(response.send(Ok(completed_transmission())).is_ok());
+                let OtaCommand::ImagePageRequest(request) = parse(request) else {
+                    panic!("expected an Image Page Request");
+                };
+                let subscription = subscription
+                    .as_ref()
+                    .expect("client subscribes before it downloads pages");
+                let start = usize::try_from(request.file_offset()).expect("test offsets fit usize");
+                let page_end = (start + usize::from(request.page_size())).min(file.len());
+                for offset in (start..page_end).step_by(usize::from(request.maximum_data_size())) {
+                    let end = (offset + usize::from(request.maximum_data_size())).min(page_end);
+                    deliver(
+                        subscription,
+                        block_response(&file, offset, end, script.corrupt),
+                    );
+                }
+            }
+            other => panic!("unexpected ZCL message: {other:?}"),
+        }
+    }
+    statuses
+}
+
+fn block_response(file: &Bytes, offset: usize, end: usize, corrupt: bool) -> ImageBlockResponse {
+    let mut data = file[offset..end].to_vec();
+    if corrupt && end == file.len() {
+        data[0] ^= 0xff;
+    }
+    let block = ImageBlock::try_new(
+        new_image(),
+        u32::try_from(offset).expect("test offsets fit u32"),
+        data.into_boxed_slice(),
+    )
+    .expect("test block fits the data-size field");
+    ImageBlockResponse::new(ImageBlockResponsePayload::Success(block))
+}
+
+fn parse(request: crate::ota::Request) -> OtaCommand {
+    let cluster_id = request.cluster_id();
+    let (header, payload) = request.into_asdu().into_parts();
+    let frame = Frame::parse(
+        cluster_id,
+        header.into_header(0).to_le_stream().chain(payload),
+    )
+    .expect("client sends valid ZCL frames");
+    let ZclCluster::OtaUpgrade(command) = frame.into_payload() else {
+        panic!("client sends OTA Upgrade commands");
+    };
+    command
+}
+
+fn deliver<T>(subscription: &zcl::Subscription, command: T)
+where
+    T: Command + Into<OtaCommand>,
+{
+    let header = Header::new(
+        Scope::ClusterSpecific,
+        Direction::ServerToClient,
+        true,
+        None,
+        0,
+        T::ID,
+    );
+    let endpoint = IndividualEndpoint::new(ENDPOINT).expect("test endpoint is individual");
+    let metadata = IndicationMetadata::new(
+        ReceivedDestination::Network {
+            address: NetworkAddress::new(LOCAL_ADDRESS).expect("valid local address"),
+            endpoint,
+        },
+        Source::Network {
+            address: server().address(),
+            endpoint,
+        },
+        crate::ota::OTA_PROFILE.as_u16(),
+        Cluster::OtaUpgrade.as_u16(),
+        IndicationStatus::success(),
+        Security::Unsecured,
+        u8::MAX,
+        (),
+    );
+    assert!(
+        subscription
+            .try_send(zcl::SubscriptionMessage {
+                indication: DataIndication::new(
+                    metadata,
+                    Frame::new(header, ZclCluster::OtaUpgrade(command.into())),
+                ),
+            })
+            .is_ok()
+    );
+}
+
+fn protocol_response(reply: ZclCluster) -> ApsProtocolResponse<ZclCluster> {
+    let (sender, receiver) = oneshot::channel();
+    assert!(sender.send(Ok(reply)).is_ok());
+    let key = Key::new(
+        SERVER_ADDRESS,
+        ENDPOINT,
+        Cluster::OtaUpgrade.as_u16(),
+        crate::ota::OTA_PROFILE.as_u16(),
+        None,
+        0,
+    );
+    ApsProtocolResponse::new(
+        completed_transmission().into(),
+        receiver,
+        Cancellation::test_new(key, drop),
+    )
+}
+
+fn completed_transmission() -> TransmissionResponse {
+    let (completion, result) = oneshot::channel();
+    assert!(completion.send(Ok(())).is_ok());
+    let (inbox, _messages) = channel(TEST_CHANNEL_SIZE);
+    TransmissionResponse::test_new(result, TEST_APS_COUNTER, inbox.downgrade())
+}
+
+fn drain(events: &mut Receiver<Event>) -> Vec<OtaClientEvent> {
+    let mut drained = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        if let Event::OtaClient(event) = event {
+            drained.push(event);
+        }
+    }
+    drained
+}
+
+fn test_file() -> Bytes {
+    ImageBuilder::new(new_image(), FIRMWARE)
+        .with_integrity_code()
+        .build()
+        .expect("valid OTA image")
+}
+
+fn test_config() -> ClientConfig {
+    ClientConfig::new(
+        server(),
+        IndividualEndpoint::new(ENDPOINT).expect("test endpoint is individual"),
+        ImageId::new(MANUFACTURER_CODE, IMAGE_TYPE, CURRENT_FILE_VERSION),
+    )
+    .with_query_jitter(Duration::ZERO)
+    .with_maximum_data_size(MAXIMUM_DATA_SIZE)
+    .with_response_timeout(TEST_RESPONSE_TIMEOUT)
+    .with_required_integrity_code()
+}
+
+fn server() -> NetworkDestination {
+    NetworkDestination::new(
+        NetworkAddress::new(SERVER_ADDRESS).expect("valid server address"),
+        IndividualEndpoint::new(ENDPOINT).expect("test endpoint is individual"),
+    )
+}
+
+const fn new_image() -> ImageId {
+    ImageId::new(MANUFACTURER_CODE, IMAGE_TYPE, CURRENT_FILE_VERSION + 1)
+}
+
+fn run_test<T>(future: T)
+where
+    T: Future<Output = ()>,
+{
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .expect("Tokio runtime")
+        .block_on(future);
+}
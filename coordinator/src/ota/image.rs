@@ -2,19 +2,31 @@
 
 use std::fmt::{self, Debug, Formatter};
 
-pub use self::error::ParseImageError;
+use bytes::Bytes;
+
+pub use self::builder::ImageBuilder;
+pub use self::error::{BuildImageError, ParseImageError, VerifyImageError};
 pub use self::field_control::FieldControl;
 pub use self::header::{BaseHeaderBytes, Header, HeaderString};
 pub use self::parser::ParseImage;
-use self::source::ImageSource;
+use self::source::{ImageSource, ReadRange};
+pub(super) use self::stream::StreamVerifier;
+pub use self::sub_element::{
+    CryptoSuite, EcdsaSignature, IntegrityCode, SigningCertificate, SubElement,
+};
 pub(super) use self::transfer::ImageTransfer;
+pub use self::verifier::ImageVerifier;
 
+mod builder;
 mod error;
 mod field_control;
 mod header;
 mod parser;
 mod source;
+mod stream;
+mod sub_element;
 mod transfer;
+mod verifier;
 
 #[cfg(test)]
 mod tests;
@@ -99,6 +111,31 @@ impl Image {
         self.header.hardware_versions()
     }
 
+    /// Read the image body and decode its tagged sub-elements.
+    ///
+    /// The body is read into memory; use [`ImageVerifier::verify`] to decode it and check the
+    /// integrity code in one pass.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseImageError`] if reading fails, a sub-element extends beyond the image, or a
+    /// fixed-size sub-element has the wrong length.
+    pub fn sub_elements(&mut self) -> Result<Vec<SubElement>, ParseImageError> {
+        let body = self.read_body()?;
+        Ok(sub_element::parse(&body)?
+            .into_iter()
+            .map(|located| located.element)
+            .collect())
+    }
+
+    fn read_body(&mut self) -> Result<Bytes, ParseImageError> {
+        let header_length = usize::from(self.header_length());
+        let body = self
+            .source
+            .read_range(header_length, self.len() - header_length)?;
+        Ok(Bytes::from(body))
+    }
+
     pub(super) fn into_transfer(self) -> ImageTransfer {
         let Self { header, source } = self;
         ImageTransfer::spawn(header, source)
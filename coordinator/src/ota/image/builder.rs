@@ -0,0 +1,210 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use le_stream::ToLeStream;
+use zb_core::IeeeAddress;
+use zb_zcl::ota_upgrade::ImageId;
+
+use super::header::{
+    BASE_HEADER_LENGTH, HEADER_STRING_LENGTH, OTA_FILE_IDENTIFIER, SUPPORTED_HEADER_VERSION,
+};
+use super::sub_element::{IntegrityCode, SUB_ELEMENT_HEADER_LENGTH};
+use super::verifier::MAX_HASHED_LENGTH;
+use super::{BuildImageError, FieldControl, SubElement};
+
+/// Zigbee PRO stack version written unless another version is selected.
+const ZIGBEE_PRO_STACK_VERSION: u16 = 0x0002;
+
+/// Assembles a Zigbee OTA upgrade file from raw firmware and header fields.
+///
+/// The firmware becomes the upgrade image sub-element. Additional sub-elements follow in insertion
+/// order, and the optional image integrity code is computed over the finished file and appended
+/// last.
+#[derive(Clone, Debug)]
+pub struct ImageBuilder {
+    id: ImageId,
+    firmware: Bytes,
+    zigbee_stack_version: u16,
+    header_string: String,
+    security_credential_version: Option<u8>,
+    upgrade_file_destination: Option<IeeeAddress>,
+    hardware_versions: Option<(u16, u16)>,
+    sub_elements: Vec<SubElement>,
+    integrity_code: bool,
+}
+
+impl ImageBuilder {
+    /// Create a builder for `firmware` identified by `id`.
+    #[must_use]
+    pub fn new(id: ImageId, firmware: impl Into<Bytes>) -> Self {
+        Self {
+            id,
+            firmware: firmware.into(),
+            zigbee_stack_version: ZIGBEE_PRO_STACK_VERSION,
+            header_string: String::new(),
+            security_credential_version: None,
+            upgrade_file_destination: None,
+            hardware_versions: None,
+            sub_elements: Vec::new(),
+            integrity_code: false,
+        }
+    }
+
+    /// Select the Zigbee stack version written to the header.
+    #[must_use]
+    pub const fn with_zigbee_stack_version(mut self, zigbee_stack_version: u16) -> Self {
+        self.zigbee_stack_version = zigbee_stack_version;
+        self
+    }
+
+    /// Select the human-readable header string.
+    ///
+    /// The string must be ASCII and at most 31 bytes long so that its null terminator fits.
+    #[must_use]
+    pub fn with_header_string(mut self, header_string: impl Into<String>) -> Self {
+        self.header_string = header_string.into();
+        self
+    }
+
+    /// Include a security credential version in the header.
+    #[must_use]
+    pub const fn with_security_credential_version(mut self, version: u8) -> Self {
+        self.security_credential_version = Some(version);
+        self
+    }
+
+    /// Restrict the file to the device with `destination`.
+    #[must_use]
+    pub const fn with_upgrade_file_destination(mut self, destination: IeeeAddress) -> Self {
+        self.upgrade_file_destination = Some(destination);
+        self
+    }
+
+    /// Restrict the file to the inclusive hardware-version range `minimum..=maximum`.
+    #[must_use]
+    pub const fn with_hardware_versions(mut self, minimum: u16, maximum: u16) -> Self {
+        self.hardware_versions = Some((minimum, maximum));
+        self
+    }
+
+    /// Append `sub_element` after the upgrade image.
+    #[must_use]
+    pub fn with_sub_element(mut self, sub_element: SubElement) -> Self {
+        self.sub_elements.push(sub_element);
+        self
+    }
+
+    /// Append an image integrity code computed over the finished file.
+    #[must_use]
+    pub const fn with_integrity_code(mut self) -> Self {
+        self.integrity_code = true;
+        self
+    }
+
+    /// Serialize the complete OTA file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildImageError`] if the header string or hardware-version range is invalid, an
+    /// integrity code was supplied as an explicit sub-element, or the file is too large.
+    pub fn build(&self) -> Result<Bytes, BuildImageError> {
+        if self
+            .sub_elements
+            .iter()
+            .any(|element| matches!(element, SubElement::ImageIntegrityCode(_)))
+        {
+            return Err(BuildImageError::ExplicitIntegrityCode);
+        }
+
+        let upgrade_image = SubElement::UpgradeImage(self.firmware.clone());
+        let elements = || std::iter::once(&upgrade_image).chain(&self.sub_elements);
+        let field_control = self.field_control();
+        let header_length = BASE_HEADER_LENGTH + field_control.optional_header_length();
+        let integrity_code_length = if self.integrity_code {
+            SUB_ELEMENT_HEADER_LENGTH + IntegrityCode::SIZE
+        } else {
+            0
+        };
+        let image_length = elements()
+            .try_fold(header_length + integrity_code_length, |length, element| {
+                length.checked_add(SUB_ELEMENT_HEADER_LENGTH + element.len())
+            })
+            .ok_or(BuildImageError::ImageTooLarge)?;
+        let total_image_size =
+            u32::try_from(image_length).map_err(|_| BuildImageError::ImageTooLarge)?;
+        if self.integrity_code && image_length - IntegrityCode::SIZE >= MAX_HASHED_LENGTH {
+            return Err(BuildImageError::ImageTooLarge);
+        }
+
+        let mut bytes = BytesMut::with_capacity(image_length);
+        self.write_header(&mut bytes, field_control, header_length, total_image_size)?;
+        for element in elements() {
+            element.encode(&mut bytes);
+        }
+        if self.integrity_code {
+            SubElement::ImageIntegrityCode(IntegrityCode::default()).encode(&mut bytes);
+            let hashed = bytes.len() - IntegrityCode::SIZE;
+            let hash = IntegrityCode::compute(&bytes[..hashed]);
+            bytes[hashed..].copy_from_slice(hash.as_bytes());
+        }
+        Ok(bytes.freeze())
+    }
+
+    fn field_control(&self) -> FieldControl {
+        let mut field_control = FieldControl::empty();
+        field_control.set(
+            FieldControl::SECURITY_CREDENTIAL_VERSION,
+            self.security_credential_version.is_some(),
+        );
+        field_control.set(
+            FieldControl::UPGRADE_FILE_DESTINATION,
+            self.upgrade_file_destination.is_some(),
+        );
+        field_control.set(
+            FieldControl::HARDWARE_VERSIONS,
+            self.hardware_versions.is_some(),
+        );
+        field_control
+    }
+
+    fn write_header(
+        &self,
+        bytes: &mut BytesMut,
+        field_control: FieldControl,
+        header_length: usize,
+        total_image_size: u32,
+    ) -> Result<(), BuildImageError> {
+        if !self.header_string.is_ascii()
+            || self.header_string.contains('\0')
+            || self.header_string.len() >= HEADER_STRING_LENGTH
+        {
+            return Err(BuildImageError::InvalidHeaderString);
+        }
+        if let Some((minimum, maximum)) = self.hardware_versions
+            && minimum > maximum
+        {
+            return Err(BuildImageError::InvalidHardwareVersionRange { minimum, maximum });
+        }
+
+        bytes.put_u32_le(OTA_FILE_IDENTIFIER);
+        bytes.put_u16_le(SUPPORTED_HEADER_VERSION);
+        bytes.put_u16_le(u16::try_from(header_length).map_err(|_| BuildImageError::ImageTooLarge)?);
+        bytes.put_u16_le(field_control.bits());
+        bytes.put_u16_le(self.id.manufacturer_code());
+        bytes.put_u16_le(self.id.image_type());
+        bytes.put_u32_le(self.id.file_version());
+        bytes.put_u16_le(self.zigbee_stack_version);
+        bytes.extend_from_slice(self.header_string.as_bytes());
+        bytes.put_bytes(0, HEADER_STRING_LENGTH - self.header_string.len());
+        bytes.put_u32_le(total_image_size);
+        if let Some(version) = self.security_credential_version {
+            bytes.put_u8(version);
+        }
+        if let Some(destination) = self.upgrade_file_destination {
+            bytes.extend(destination.to_le_stream());
+        }
+        if let Some((minimum, maximum)) = self.hardware_versions {
+            bytes.put_u16_le(minimum);
+            bytes.put_u16_le(maximum);
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 use std::io;
 
 use thiserror::Error;
+use zb_core::IeeeAddress;
+
+use super::IntegrityCode;
 
 /// Error returned while parsing a Zigbee OTA upgrade image.
 #[derive(Debug, Error)]
@@ -50,4 +53,82 @@ pub enum ParseImageError {
         /// Declared maximum hardware version.
         maximum: u16,
     },
+    /// A sub-element extends beyond the end of the image.
+    #[error("the OTA sub-element at body offset {offset} is truncated")]
+    TruncatedSubElement {
+        /// Offset of the sub-element tag within the image body.
+        offset: usize,
+    },
+    /// A fixed-size sub-element has an unexpected length.
+    #[error("invalid length {length} for OTA sub-element {tag:#06x}")]
+    InvalidSubElementLength {
+        /// Sub-element tag.
+        tag: u16,
+        /// Length of the sub-element value.
+        length: usize,
+    },
+}
+
+/// Error returned while verifying a Zigbee OTA upgrade image.
+#[derive(Debug, Error)]
+pub enum VerifyImageError {
+    /// The image body could not be read or split into sub-elements.
+    #[error(transparent)]
+    Parse(#[from] ParseImageError),
+    /// The verifier requires an image integrity code, but the image has none.
+    #[error("the OTA image has no integrity code")]
+    MissingIntegrityCode,
+    /// The image integrity code is followed by further sub-elements.
+    #[error("the OTA image integrity code is not the last sub-element")]
+    MisplacedIntegrityCode,
+    /// The image integrity code differs from the hash of the file data.
+    #[error("OTA image integrity code {declared} does not match computed hash {computed}")]
+    IntegrityCodeMismatch {
+        /// Integrity code stored in the image.
+        declared: IntegrityCode,
+        /// Hash computed over the file data.
+        computed: IntegrityCode,
+    },
+    /// The image is restricted to another device.
+    #[error("OTA image is restricted to {destination}, not {device}")]
+    DestinationMismatch {
+        /// Upgrade file destination from the image header.
+        destination: IeeeAddress,
+        /// IEEE address of the device the image was verified for.
+        device: IeeeAddress,
+    },
+    /// The device hardware version is outside the range of the image header.
+    #[error(
+        "hardware version {hardware_version} is outside the OTA image range {minimum}..={maximum}"
+    )]
+    UnsupportedHardwareVersion {
+        /// Hardware version of the device the image was verified for.
+        hardware_version: u16,
+        /// Minimum hardware version from the image header.
+        minimum: u16,
+        /// Maximum hardware version from the image header.
+        maximum: u16,
+    },
+}
+
+/// Error returned while building a Zigbee OTA upgrade image.
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+pub enum BuildImageError {
+    /// The header string is not ASCII or leaves no room for its null terminator.
+    #[error("the OTA header string must be ASCII and shorter than 32 bytes")]
+    InvalidHeaderString,
+    /// The hardware-version range is reversed.
+    #[error("invalid OTA hardware range {minimum}..={maximum}")]
+    InvalidHardwareVersionRange {
+        /// Requested minimum hardware version.
+        minimum: u16,
+        /// Requested maximum hardware version.
+        maximum: u16,
+    },
+    /// An explicit integrity code was supplied instead of being computed by the builder.
+    #[error("the OTA image integrity code is computed by the builder")]
+    ExplicitIntegrityCode,
+    /// The image exceeds the 32-bit OTA file-size field or the integrity hash input limit.
+    #[error("OTA image is larger than the supported file size")]
+    ImageTooLarge,
 }
@@ -6,10 +6,10 @@ use zb_zcl::ota_upgrade::ImageId;
 
 use super::{FieldControl, ParseImageError};
 
-const OTA_FILE_IDENTIFIER: u32 = 0x0bee_f11e;
-const SUPPORTED_HEADER_VERSION: u16 = 0x0100;
-const BASE_HEADER_LENGTH: usize = 56;
-const HEADER_STRING_LENGTH: usize = 32;
+pub(super) const OTA_FILE_IDENTIFIER: u32 = 0x0bee_f11e;
+pub(super) const SUPPORTED_HEADER_VERSION: u16 = 0x0100;
+pub(super) const BASE_HEADER_LENGTH: usize = 56;
+pub(super) const HEADER_STRING_LENGTH: usize = 32;
 
 /// Serialized bytes in the mandatory portion of an OTA image header.
 pub type BaseHeaderBytes = [u8; BASE_HEADER_LENGTH];
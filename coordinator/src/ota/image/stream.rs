@@ -0,0 +1,210 @@
+use le_stream::FromLeStream;
+use zb_core::security::MmoHasher;
+
+use super::header::{BASE_HEADER_LENGTH, HeaderBuilder};
+use super::sub_element::{IMAGE_INTEGRITY_CODE, IntegrityCode, SUB_ELEMENT_HEADER_LENGTH};
+use super::verifier::MAX_HASHED_LENGTH;
+use super::{BaseHeaderBytes, ImageVerifier, ParseImageError, VerifyImageError};
+
+/// Verifies an OTA image whose bytes arrive in file order, without retaining its body.
+///
+/// The verifier parses the header once it is complete, walks the sub-element framing, and feeds
+/// every byte before the integrity code value into an incremental AES-MMO hash.
+#[derive(Debug)]
+pub(in crate::ota) struct StreamVerifier {
+    verifier: ImageVerifier,
+    image_size: usize,
+    position: usize,
+    header: Vec<u8>,
+    header_target: usize,
+    header_length: Option<usize>,
+    next_element: usize,
+    element_header: Vec<u8>,
+    integrity_value: Option<usize>,
+    integrity_code: Vec<u8>,
+    hasher: MmoHasher,
+}
+
+impl StreamVerifier {
+    /// Start verifying an image of `image_size` bytes with the checks of `verifier`.
+    pub(in crate::ota) const fn new(verifier: ImageVerifier, image_size: usize) -> Self {
+        Self {
+            verifier,
+            image_size,
+            position: 0,
+            header: Vec::new(),
+            header_target: BASE_HEADER_LENGTH,
+            header_length: None,
+            next_element: 0,
+            element_header: Vec::new(),
+            integrity_value: None,
+            integrity_code: Vec::new(),
+            hasher: MmoHasher::new(),
+        }
+    }
+
+    /// Return the number of bytes verified so far.
+    pub(in crate::ota) const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Consume the next `data` bytes of the image.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VerifyImageError`] as soon as the header, a header restriction, or the
+    /// sub-element framing is invalid.
+    pub(in crate::ota) fn update(&mut self, mut data: &[u8]) -> Result<(), VerifyImageError> {
+        if self.position + data.len() > self.image_size {
+            return Err(ParseImageError::InvalidImageSize {
+                declared: u32::try_from(self.image_size).unwrap_or(u32::MAX),
+                actual: u32::try_from(self.position + data.len()).unwrap_or(u32::MAX),
+            }
+            .into());
+        }
+
+        while !data.is_empty() {
+            let taken = if self.header_length.is_none() {
+                self.take_header(data)?
+            } else if self.position < self.next_element {
+                self.take_element_value(data)
+            } else {
+                self.take_element_header(data)?
+            };
+            data = &data[taken..];
+        }
+        Ok(())
+    }
+
+    /// Check that the complete image was consumed and that its integrity code matches.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VerifyImageError`] if the image is incomplete, a required integrity code is
+    /// missing, or the integrity code does not match the hashed data.
+    pub(in crate::ota) fn finish(self) -> Result<(), VerifyImageError> {
+        if self.header_length.is_none() {
+            return Err(ParseImageError::TruncatedHeader.into());
+        }
+        if self.position != self.image_size
+            || !self.element_header.is_empty()
+            || self.next_element != self.image_size
+        {
+            return Err(ParseImageError::TruncatedSubElement {
+                offset: self.body_offset(self.next_element.min(self.position)),
+            }
+            .into());
+        }
+
+        if self.integrity_value.is_none() {
+            return if self.verifier.integrity_code_required() {
+                Err(VerifyImageError::MissingIntegrityCode)
+            } else {
+                Ok(())
+            };
+        }
+        if self.hasher.len() >= MAX_HASHED_LENGTH {
+            return Err(ParseImageError::ImageTooLarge.into());
+        }
+        let declared = <[u8; IntegrityCode::SIZE]>::try_from(self.integrity_code.as_slice())
+            .map(IntegrityCode::new)
+            .map_err(|_| ParseImageError::InvalidSubElementLength {
+                tag: IMAGE_INTEGRITY_CODE,
+                length: self.integrity_code.len(),
+            })?;
+        let computed = IntegrityCode::finalize(self.hasher);
+        if computed == declared {
+            Ok(())
+        } else {
+            Err(VerifyImageError::IntegrityCodeMismatch { declared, computed })
+        }
+    }
+
+    fn take_header(&mut self, data: &[u8]) -> Result<usize, VerifyImageError> {
+        let taken = data.len().min(self.header_target - self.header.len());
+        self.header.extend_from_slice(&data[..taken]);
+        self.consume(&data[..taken]);
+        if self.header.len() == self.header_target {
+            let builder = HeaderBuilder::parse(self.base_header())?;
+            let header_target = BASE_HEADER_LENGTH + builder.optional_header_length();
+            if self.header.len() < header_target {
+                self.header_target = header_target;
+            } else {
+                self.finish_header(builder)?;
+            }
+        }
+        Ok(taken)
+    }
+
+    fn base_header(&self) -> BaseHeaderBytes {
+        <BaseHeaderBytes>::try_from(&self.header[..BASE_HEADER_LENGTH])
+            .expect("the base header is complete")
+    }
+
+    fn finish_header(&mut self, builder: HeaderBuilder) -> Result<(), VerifyImageError> {
+        let header = builder.finish(&self.header[BASE_HEADER_LENGTH..], self.image_size)?;
+        self.verifier.verify_header(&header)?;
+        self.header_length = Some(usize::from(header.header_length()));
+        self.next_element = usize::from(header.header_length());
+        self.header = Vec::new();
+        Ok(())
+    }
+
+    fn take_element_value(&mut self, data: &[u8]) -> usize {
+        let taken = data.len().min(self.next_element - self.position);
+        let value = &data[..taken];
+        if self.integrity_value.is_some() {
+            self.integrity_code.extend_from_slice(value);
+            self.position += taken;
+        } else {
+            self.consume(value);
+        }
+        taken
+    }
+
+    fn take_element_header(&mut self, data: &[u8]) -> Result<usize, VerifyImageError> {
+        if self.integrity_value.is_some() {
+            return Err(VerifyImageError::MisplacedIntegrityCode);
+        }
+
+        let taken = data
+            .len()
+            .min(SUB_ELEMENT_HEADER_LENGTH - self.element_header.len());
+        self.element_header.extend_from_slice(&data[..taken]);
+        self.consume(&data[..taken]);
+        if self.element_header.len() < SUB_ELEMENT_HEADER_LENGTH {
+            return Ok(taken);
+        }
+
+        let element_offset = self.position - SUB_ELEMENT_HEADER_LENGTH;
+        let mut fields = self.element_header.drain(..);
+        let tag = u16::from_le_stream(&mut fields).expect("the sub-element header is complete");
+        let length = u32::from_le_stream(&mut fields).expect("the sub-element header is complete");
+        drop(fields);
+        let truncated = || ParseImageError::TruncatedSubElement {
+            offset: self.body_offset(element_offset),
+        };
+        let length = usize::try_from(length).map_err(|_| truncated())?;
+        self.next_element = self
+            .position
+            .checked_add(length)
+            .filter(|&end| end <= self.image_size)
+            .ok_or_else(truncated)?;
+        if tag == IMAGE_INTEGRITY_CODE {
+            if length != IntegrityCode::SIZE {
+                return Err(ParseImageError::InvalidSubElementLength { tag, length }.into());
+            }
+            self.integrity_value = Some(self.position);
+        }
+        Ok(taken)
+    }
+
+    fn consume(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+        self.position += data.len();
+    }
+
+    fn body_offset(&self, position: usize) -> usize {
+        position.saturating_sub(self.header_length.unwrap_or_default())
+    }
+}
@@ -0,0 +1,355 @@
+use std::fmt::{self, Display, Formatter};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use le_stream::{FromLeStream, ToLeStream};
+use zb_core::IeeeAddress;
+use zb_core::security::{Key, MmoHasher, mmo_hash};
+
+use super::ParseImageError;
+
+const UPGRADE_IMAGE: u16 = 0x0000;
+const ECDSA_SIGNATURE_SECT163K1: u16 = 0x0001;
+const SIGNING_CERTIFICATE_SECT163K1: u16 = 0x0002;
+pub(super) const IMAGE_INTEGRITY_CODE: u16 = 0x0003;
+const PICTURE_DATA: u16 = 0x0004;
+const ECDSA_SIGNATURE_SECT283K1: u16 = 0x0005;
+const SIGNING_CERTIFICATE_SECT283K1: u16 = 0x0006;
+const MANUFACTURER_SPECIFIC_TAGS: u16 = 0xf000;
+const SIGNER_ADDRESS_LENGTH: usize = 8;
+
+/// Length of the tag and length fields that precede every sub-element value.
+pub(super) const SUB_ELEMENT_HEADER_LENGTH: usize = 6;
+
+/// Elliptic-curve crypto suite used by OTA signatures and signing certificates.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CryptoSuite {
+    /// Crypto suite 1 on the `sect163k1` curve.
+    Sect163k1,
+    /// Crypto suite 2 on the `sect283k1` curve.
+    Sect283k1,
+}
+
+impl CryptoSuite {
+    /// Return the length of an ECDSA signature, excluding the signer address.
+    #[must_use]
+    pub const fn signature_length(self) -> usize {
+        match self {
+            Self::Sect163k1 => 42,
+            Self::Sect283k1 => 72,
+        }
+    }
+
+    /// Return the length of an implicit signing certificate.
+    #[must_use]
+    pub const fn certificate_length(self) -> usize {
+        match self {
+            Self::Sect163k1 => 48,
+            Self::Sect283k1 => 74,
+        }
+    }
+
+    const fn signature_tag(self) -> u16 {
+        match self {
+            Self::Sect163k1 => ECDSA_SIGNATURE_SECT163K1,
+            Self::Sect283k1 => ECDSA_SIGNATURE_SECT283K1,
+        }
+    }
+
+    const fn certificate_tag(self) -> u16 {
+        match self {
+            Self::Sect163k1 => SIGNING_CERTIFICATE_SECT163K1,
+            Self::Sect283k1 => SIGNING_CERTIFICATE_SECT283K1,
+        }
+    }
+}
+
+/// ECDSA signature over the OTA file data that precedes it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EcdsaSignature {
+    suite: CryptoSuite,
+    signer: IeeeAddress,
+    signature: Bytes,
+}
+
+impl EcdsaSignature {
+    /// Create a signature made by `signer` with `suite`.
+    ///
+    /// Returns `None` if `signature` does not have the length required by `suite`.
+    #[must_use]
+    pub fn new(
+        suite: CryptoSuite,
+        signer: IeeeAddress,
+        signature: impl Into<Bytes>,
+    ) -> Option<Self> {
+        let signature = signature.into();
+        (signature.len() == suite.signature_length()).then_some(Self {
+            suite,
+            signer,
+            signature,
+        })
+    }
+
+    /// Return the crypto suite of this signature.
+    #[must_use]
+    pub const fn suite(&self) -> CryptoSuite {
+        self.suite
+    }
+
+    /// Return the IEEE address of the signing device.
+    #[must_use]
+    pub const fn signer(&self) -> IeeeAddress {
+        self.signer
+    }
+
+    /// Return the raw signature bytes.
+    #[must_use]
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
+    }
+}
+
+/// Implicit ECQV certificate of the OTA image signer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SigningCertificate {
+    suite: CryptoSuite,
+    certificate: Bytes,
+}
+
+impl SigningCertificate {
+    /// Create a signing certificate for `suite`.
+    ///
+    /// Returns `None` if `certificate` does not have the length required by `suite`.
+    #[must_use]
+    pub fn new(suite: CryptoSuite, certificate: impl Into<Bytes>) -> Option<Self> {
+        let certificate = certificate.into();
+        (certificate.len() == suite.certificate_length()).then_some(Self { suite, certificate })
+    }
+
+    /// Return the crypto suite of this certificate.
+    #[must_use]
+    pub const fn suite(&self) -> CryptoSuite {
+        self.suite
+    }
+
+    /// Return the raw certificate bytes.
+    #[must_use]
+    pub fn certificate(&self) -> &[u8] {
+        &self.certificate
+    }
+}
+
+/// AES-MMO hash that protects an OTA file against corruption.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct IntegrityCode([u8; Self::SIZE]);
+
+impl IntegrityCode {
+    /// Size of an integrity code in bytes.
+    pub const SIZE: usize = 16;
+
+    /// Create an integrity code from its hash bytes.
+    #[must_use]
+    pub const fn new(bytes: [u8; Self::SIZE]) -> Self {
+        Self(bytes)
+    }
+
+    /// Return the hash bytes.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8; Self::SIZE] {
+        &self.0
+    }
+
+    /// Compute the integrity code of `data`.
+    pub(super) fn compute(data: &[u8]) -> Self {
+        Self::from(mmo_hash(data))
+    }
+
+    /// Compute the integrity code of the data fed into `hasher`.
+    pub(super) fn finalize(hasher: MmoHasher) -> Self {
+        Self::from(hasher.finalize())
+    }
+}
+
+impl From<Key> for IntegrityCode {
+    fn from(hash: Key) -> Self {
+        Self(hash.into())
+    }
+}
+
+impl Display for IntegrityCode {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        self.0
+            .iter()
+            .try_for_each(|byte| write!(formatter, "{byte:02X}"))
+    }
+}
+
+/// Typed tag-length-value element of an OTA image body.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SubElement {
+    /// Firmware consumed by the upgrading device.
+    UpgradeImage(Bytes),
+    /// ECDSA signature over the preceding file data.
+    EcdsaSignature(EcdsaSignature),
+    /// Certificate of the signer.
+    SigningCertificate(SigningCertificate),
+    /// AES-MMO hash over the preceding file data.
+    ImageIntegrityCode(IntegrityCode),
+    /// Picture data for devices with a display.
+    PictureData(Bytes),
+    /// Element with a manufacturer-specific tag in `0xf000..=0xffff`.
+    ManufacturerSpecific {
+        /// Manufacturer-specific tag.
+        tag: u16,
+        /// Uninterpreted element value.
+        data: Bytes,
+    },
+    /// Element with a tag reserved by the specification.
+    Reserved {
+        /// Reserved tag.
+        tag: u16,
+        /// Uninterpreted element value.
+        data: Bytes,
+    },
+}
+
+impl SubElement {
+    /// Return the tag identifying this element.
+    #[must_use]
+    pub const fn tag(&self) -> u16 {
+        match self {
+            Self::UpgradeImage(_) => UPGRADE_IMAGE,
+            Self::EcdsaSignature(signature) => signature.suite.signature_tag(),
+            Self::SigningCertificate(certificate) => certificate.suite.certificate_tag(),
+            Self::ImageIntegrityCode(_) => IMAGE_INTEGRITY_CODE,
+            Self::PictureData(_) => PICTURE_DATA,
+            Self::ManufacturerSpecific { tag, .. } | Self::Reserved { tag, .. } => *tag,
+        }
+    }
+
+    /// Return the length of the element value in bytes.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        match self {
+            Self::UpgradeImage(data)
+            | Self::PictureData(data)
+            | Self::ManufacturerSpecific { data, .. }
+            | Self::Reserved { data, .. } => data.len(),
+            Self::EcdsaSignature(signature) => SIGNER_ADDRESS_LENGTH + signature.signature.len(),
+            Self::SigningCertificate(certificate) => certificate.certificate.len(),
+            Self::ImageIntegrityCode(_) => IntegrityCode::SIZE,
+        }
+    }
+
+    /// Return whether the element value contains no bytes.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Decode the value of an element with `tag`.
+    fn decode(tag: u16, data: Bytes) -> Result<Self, ParseImageError> {
+        let length = data.len();
+        let invalid_length = || ParseImageError::InvalidSubElementLength { tag, length };
+        match tag {
+            UPGRADE_IMAGE => Ok(Self::UpgradeImage(data)),
+            ECDSA_SIGNATURE_SECT163K1 | ECDSA_SIGNATURE_SECT283K1 => {
+                let suite = if tag == ECDSA_SIGNATURE_SECT163K1 {
+                    CryptoSuite::Sect163k1
+                } else {
+                    CryptoSuite::Sect283k1
+                };
+                if data.len() != SIGNER_ADDRESS_LENGTH + suite.signature_length() {
+                    return Err(invalid_length());
+                }
+                let signer =
+                    IeeeAddress::from_le_stream(data.iter().copied()).ok_or_else(invalid_length)?;
+                Ok(Self::EcdsaSignature(EcdsaSignature {
+                    suite,
+                    signer,
+                    signature: data.slice(SIGNER_ADDRESS_LENGTH..),
+                }))
+            }
+            SIGNING_CERTIFICATE_SECT163K1 | SIGNING_CERTIFICATE_SECT283K1 => {
+                let suite = if tag == SIGNING_CERTIFICATE_SECT163K1 {
+                    CryptoSuite::Sect163k1
+                } else {
+                    CryptoSuite::Sect283k1
+                };
+                SigningCertificate::new(suite, data)
+                    .map(Self::SigningCertificate)
+                    .ok_or_else(invalid_length)
+            }
+            IMAGE_INTEGRITY_CODE => <[u8; IntegrityCode::SIZE]>::try_from(data.as_ref())
+                .map(|hash| Self::ImageIntegrityCode(IntegrityCode::new(hash)))
+                .map_err(|_| invalid_length()),
+            PICTURE_DATA => Ok(Self::PictureData(data)),
+            MANUFACTURER_SPECIFIC_TAGS.. => Ok(Self::ManufacturerSpecific { tag, data }),
+            _ => Ok(Self::Reserved { tag, data }),
+        }
+    }
+
+    /// Append the tag, length, and value of this element to `bytes`.
+    ///
+    /// The caller must have checked that the element length fits the 32-bit length field.
+    pub(super) fn encode(&self, bytes: &mut BytesMut) {
+        bytes.put_u16_le(self.tag());
+        bytes.put_u32_le(
+            u32::try_from(self.len()).expect("sub-element lengths are checked before encoding"),
+        );
+        match self {
+            Self::UpgradeImage(data)
+            | Self::PictureData(data)
+            | Self::ManufacturerSpecific { data, .. }
+            | Self::Reserved { data, .. } => bytes.extend_from_slice(data),
+            Self::EcdsaSignature(signature) => {
+                bytes.extend(signature.signer.to_le_stream());
+                bytes.extend_from_slice(&signature.signature);
+            }
+            Self::SigningCertificate(certificate) => {
+                bytes.extend_from_slice(&certificate.certificate);
+            }
+            Self::ImageIntegrityCode(hash) => bytes.extend_from_slice(hash.as_bytes()),
+        }
+    }
+}
+
+/// Sub-element decoded from an image body together with its position.
+#[derive(Debug)]
+pub(super) struct LocatedSubElement {
+    /// Offset of the element tag within the image body.
+    pub(super) offset: usize,
+    pub(super) element: SubElement,
+}
+
+/// Split an image body into its typed sub-elements.
+///
+/// # Errors
+///
+/// Returns [`ParseImageError::TruncatedSubElement`] if an element extends beyond the body, or
+/// [`ParseImageError::InvalidSubElementLength`] if a fixed-size element has the wrong length.
+pub(super) fn parse(body: &Bytes) -> Result<Vec<LocatedSubElement>, ParseImageError> {
+    let mut elements = Vec::new();
+    let mut offset = 0;
+    while offset < body.len() {
+        let truncated = || ParseImageError::TruncatedSubElement { offset };
+        let value_offset = offset + SUB_ELEMENT_HEADER_LENGTH;
+        let mut fields = body
+            .get(offset..value_offset)
+            .ok_or_else(truncated)?
+            .iter()
+            .copied();
+        let tag = u16::from_le_stream(&mut fields).ok_or_else(truncated)?;
+        let length = u32::from_le_stream(&mut fields).ok_or_else(truncated)?;
+        let end = usize::try_from(length)
+            .ok()
+            .and_then(|length| value_offset.checked_add(length))
+            .filter(|&end| end <= body.len())
+            .ok_or_else(truncated)?;
+        elements.push(LocatedSubElement {
+            offset,
+            element: SubElement::decode(tag, body.slice(value_offset..end))?,
+        });
+        offset = end;
+    }
+    Ok(elements)
+}
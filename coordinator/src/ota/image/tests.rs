@@ -4,8 +4,13 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use bytes::{BufMut, Bytes, BytesMut};
+use zb_core::IeeeAddress;
+use zb_zcl::ota_upgrade::ImageId;
 
-use super::{FieldControl, ParseImage, ParseImageError};
+use super::{
+    BuildImageError, CryptoSuite, EcdsaSignature, FieldControl, ImageBuilder, ImageVerifier,
+    ParseImage, ParseImageError, SigningCertificate, StreamVerifier, SubElement, VerifyImageError,
+};
 
 const MANUFACTURER_CODE: u16 = 0x1234;
 const IMAGE_TYPE: u16 = 0x5678;
@@ -18,10 +23,19 @@ const HEADER_STRING_LENGTH: usize = 32;
 const HEADER_STRING: &str = "Test OTA image";
 const FIELD_CONTROL_OFFSET: usize = 8;
 const HEADER_STRING_OFFSET: usize = 20;
+const TOTAL_IMAGE_SIZE_OFFSET: usize = 52;
 const HARDWARE_VERSIONS_LENGTH: usize = 4;
 const UNSUPPORTED_FIELD_CONTROL: u16 = 0x0008;
 const PAYLOAD: &[u8] = &[1, 2, 3, 4];
 const TEST_FILE_PREFIX: &str = "apis-saltans-ota-image";
+const FIRMWARE: &[u8] = &[0xde, 0xad, 0xbe, 0xef, 0x01, 0x02];
+const PICTURE: &[u8] = &[0x89, 0x50, 0x4e, 0x47];
+const DESTINATION: IeeeAddress = IeeeAddress::new(0x00, 0x12, 0x4b, 0x00, 0x01, 0x02, 0x03, 0x04);
+const OTHER_DEVICE: IeeeAddress = IeeeAddress::new(0x00, 0x12, 0x4b, 0x00, 0x0a, 0x0b, 0x0c, 0x0d);
+const HARDWARE_VERSIONS: (u16, u16) = (3, 5);
+const TRUNCATED_SUB_ELEMENT_LENGTH: u32 = 100;
+const INTEGRITY_CODE_ELEMENT_LENGTH: usize = 22;
+const STREAM_CHUNK_SIZES: [usize; 4] = [1, 7, 16, 64];
 
 static NEXT_TEST_FILE_ID: AtomicU64 = AtomicU64::new(0);
 
@@ -185,6 +199,163 @@ fn rejects_unterminated_header_string() {
     ));
 }
 
+#[test]
+fn builds_a_file_that_parses_and_verifies() {
+    let signature = EcdsaSignature::new(
+        CryptoSuite::Sect163k1,
+        DESTINATION,
+        vec![0x5a; CryptoSuite::Sect163k1.signature_length()],
+    )
+    .expect("signature length matches the crypto suite");
+    let certificate = SigningCertificate::new(
+        CryptoSuite::Sect283k1,
+        vec![0xc3; CryptoSuite::Sect283k1.certificate_length()],
+    )
+    .expect("certificate length matches the crypto suite");
+    let bytes = signed_builder()
+        .with_sub_element(SubElement::PictureData(Bytes::from_static(PICTURE)))
+        .with_sub_element(SubElement::EcdsaSignature(signature.clone()))
+        .with_sub_element(SubElement::SigningCertificate(certificate.clone()))
+        .with_integrity_code()
+        .build()
+        .expect("valid OTA image");
+
+    let mut image = Cursor::new(bytes.clone()).parse().expect("valid OTA image");
+    assert_eq!(image.header_string().as_str(), HEADER_STRING);
+    assert_eq!(image.security_credential_version(), Some(1));
+    assert_eq!(image.upgrade_file_destination(), Some(DESTINATION));
+    assert_eq!(image.hardware_versions(), Some(HARDWARE_VERSIONS));
+    assert_eq!(image.len(), bytes.len());
+
+    let elements = ImageVerifier::new()
+        .with_device(DESTINATION)
+        .with_hardware_version(HARDWARE_VERSIONS.1)
+        .with_required_integrity_code()
+        .verify(&mut image)
+        .expect("image verifies");
+
+    assert_eq!(elements.len(), 5);
+    assert_eq!(
+        elements[..4],
+        [
+            SubElement::UpgradeImage(Bytes::from_static(FIRMWARE)),
+            SubElement::PictureData(Bytes::from_static(PICTURE)),
+            SubElement::EcdsaSignature(signature),
+            SubElement::SigningCertificate(certificate),
+        ]
+    );
+    assert!(matches!(elements[4], SubElement::ImageIntegrityCode(_)));
+}
+
+#[test]
+fn rejects_a_corrupted_integrity_code() {
+    let mut bytes = signed_builder()
+        .with_integrity_code()
+        .build()
+        .expect("valid OTA image")
+        .to_vec();
+    let firmware_offset = bytes.len() - INTEGRITY_CODE_ELEMENT_LENGTH - FIRMWARE.len();
+    bytes[firmware_offset] ^= 0xff;
+    let mut image = Cursor::new(bytes).parse().expect("valid OTA image");
+
+    assert!(matches!(
+        ImageVerifier::new().verify(&mut image),
+        Err(VerifyImageError::IntegrityCodeMismatch { .. })
+    ));
+}
+
+#[test]
+fn rejects_images_for_another_device_or_hardware_version() {
+    let bytes = signed_builder().build().expect("valid OTA image");
+    let mut image = Cursor::new(bytes).parse().expect("valid OTA image");
+
+    assert!(matches!(
+        ImageVerifier::new().with_device(OTHER_DEVICE).verify(&mut image),
+        Err(VerifyImageError::DestinationMismatch { destination, device })
+            if destination == DESTINATION && device == OTHER_DEVICE
+    ));
+    assert!(matches!(
+        ImageVerifier::new()
+            .with_hardware_version(HARDWARE_VERSIONS.1 + 1)
+            .verify(&mut image),
+        Err(VerifyImageError::UnsupportedHardwareVersion { .. })
+    ));
+    assert!(matches!(
+        ImageVerifier::new()
+            .with_required_integrity_code()
+            .verify(&mut image),
+        Err(VerifyImageError::MissingIntegrityCode)
+    ));
+}
+
+#[test]
+fn rejects_truncated_and_malformed_sub_elements() {
+    let mut truncated = image_bytes(FieldControl::empty(), None).to_vec();
+    truncated.truncate(BASE_HEADER_LENGTH);
+    truncated.extend_from_slice(&0x0000_u16.to_le_bytes());
+    truncated.extend_from_slice(&TRUNCATED_SUB_ELEMENT_LENGTH.to_le_bytes());
+    set_total_image_size(&mut truncated);
+    let mut image = Cursor::new(truncated).parse().expect("valid OTA header");
+    assert!(matches!(
+        image.sub_elements(),
+        Err(ParseImageError::TruncatedSubElement { offset: 0 })
+    ));
+
+    let mut malformed = image_bytes(FieldControl::empty(), None).to_vec();
+    malformed.truncate(BASE_HEADER_LENGTH);
+    malformed.extend_from_slice(&0x0003_u16.to_le_bytes());
+    malformed.extend_from_slice(&1_u32.to_le_bytes());
+    malformed.push(0);
+    set_total_image_size(&mut malformed);
+    let mut image = Cursor::new(malformed).parse().expect("valid OTA header");
+    assert!(matches!(
+        image.sub_elements(),
+        Err(ParseImageError::InvalidSubElementLength {
+            tag: 0x0003,
+            length: 1
+        })
+    ));
+}
+
+#[test]
+fn builder_rejects_invalid_header_fields() {
+    let id = ImageId::new(MANUFACTURER_CODE, IMAGE_TYPE, FILE_VERSION);
+
+    assert_eq!(
+        ImageBuilder::new(id, FIRMWARE)
+            .with_header_string("x".repeat(HEADER_STRING_LENGTH))
+            .build(),
+        Err(BuildImageError::InvalidHeaderString)
+    );
+    assert_eq!(
+        ImageBuilder::new(id, FIRMWARE)
+            .with_hardware_versions(2, 1)
+            .build(),
+        Err(BuildImageError::InvalidHardwareVersionRange {
+            minimum: 2,
+            maximum: 1
+        })
+    );
+}
+
+fn signed_builder() -> ImageBuilder {
+    ImageBuilder::new(
+        ImageId::new(MANUFACTURER_CODE, IMAGE_TYPE, FILE_VERSION),
+        FIRMWARE,
+    )
+    .with_zigbee_stack_version(STACK_VERSION)
+    .with_header_string(HEADER_STRING)
+    .with_security_credential_version(1)
+    .with_upgrade_file_destination(DESTINATION)
+    .with_hardware_versions(HARDWARE_VERSIONS.0, HARDWARE_VERSIONS.1)
+}
+
+fn set_total_image_size(bytes: &mut [u8]) {
+    let total_size = u32::try_from(bytes.len()).expect("test image length fits u32");
+    bytes[TOTAL_IMAGE_SIZE_OFFSET..TOTAL_IMAGE_SIZE_OFFSET + size_of::<u32>()]
+        .copy_from_slice(&total_size.to_le_bytes());
+}
+
 fn image_bytes(field_control: FieldControl, hardware: Option<(u16, u16)>) -> Bytes {
     let header_length = BASE_HEADER_LENGTH + hardware.map_or(0, |_| HARDWARE_VERSIONS_LENGTH);
     let total_length = header_length + PAYLOAD.len();
@@ -207,3 +378,56 @@ fn image_bytes(field_control: FieldControl, hardware: Option<(u16, u16)>) -> Byt
     bytes.extend_from_slice(PAYLOAD);
     bytes.freeze()
 }
+
+#[test]
+fn stream_verifier_accepts_an_image_in_any_chunking() {
+    let bytes = signed_builder()
+        .with_sub_element(SubElement::PictureData(Bytes::from_static(PICTURE)))
+        .with_integrity_code()
+        .build()
+        .expect("valid OTA image");
+
+    for chunk_size in STREAM_CHUNK_SIZES {
+        let mut verifier = StreamVerifier::new(
+            ImageVerifier::new()
+                .with_device(DESTINATION)
+                .with_required_integrity_code(),
+            bytes.len(),
+        );
+        for chunk in bytes.chunks(chunk_size) {
+            verifier.update(chunk).expect("valid image data");
+        }
+        assert_eq!(verifier.position(), bytes.len());
+        verifier.finish().expect("streamed image verifies");
+    }
+}
+
+#[test]
+fn stream_verifier_rejects_corrupted_and_foreign_images() {
+    let bytes = signed_builder()
+        .with_integrity_code()
+        .build()
+        .expect("valid OTA image");
+    let mut corrupted = BytesMut::from(bytes.as_ref());
+    corrupted[bytes.len() - INTEGRITY_CODE_ELEMENT_LENGTH - 1] ^= 0xff;
+
+    let mut verifier = StreamVerifier::new(ImageVerifier::new(), corrupted.len());
+    verifier.update(&corrupted).expect("framing is intact");
+    assert!(matches!(
+        verifier.finish(),
+        Err(VerifyImageError::IntegrityCodeMismatch { .. })
+    ));
+
+    let mut verifier =
+        StreamVerifier::new(ImageVerifier::new().with_device(OTHER_DEVICE), bytes.len());
+    assert!(matches!(
+        verifier.update(&bytes),
+        Err(VerifyImageError::DestinationMismatch { .. })
+    ));
+
+    let mut verifier = StreamVerifier::new(ImageVerifier::new(), bytes.len());
+    verifier
+        .update(&bytes[..bytes.len() - 1])
+        .expect("valid image prefix");
+    assert!(verifier.finish().is_err());
+}
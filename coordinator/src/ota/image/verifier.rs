@@ -0,0 +1,133 @@
+use zb_core::IeeeAddress;
+
+use super::sub_element::{self, IntegrityCode, SUB_ELEMENT_HEADER_LENGTH};
+use super::{Header, Image, ParseImageError, SubElement, VerifyImageError};
+
+/// Largest input accepted by the AES-MMO hash, whose padding encodes the bit length in 32 bits.
+pub(super) const MAX_HASHED_LENGTH: usize = 1 << 29;
+
+/// Checks an OTA image against the device it is meant for and its integrity code.
+///
+/// Without further configuration, the verifier only checks an integrity code that the image
+/// contains. Header restrictions are checked once the corresponding device property is known.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ImageVerifier {
+    device: Option<IeeeAddress>,
+    hardware_version: Option<u16>,
+    integrity_code_required: bool,
+}
+
+impl ImageVerifier {
+    /// Create a verifier that checks only an integrity code present in the image.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            device: None,
+            hardware_version: None,
+            integrity_code_required: false,
+        }
+    }
+
+    /// Reject images whose upgrade file destination names another device.
+    #[must_use]
+    pub const fn with_device(mut self, device: IeeeAddress) -> Self {
+        self.device = Some(device);
+        self
+    }
+
+    /// Reject images whose hardware-version range excludes `hardware_version`.
+    #[must_use]
+    pub const fn with_hardware_version(mut self, hardware_version: u16) -> Self {
+        self.hardware_version = Some(hardware_version);
+        self
+    }
+
+    /// Reject images that do not contain an image integrity code.
+    #[must_use]
+    pub const fn with_required_integrity_code(mut self) -> Self {
+        self.integrity_code_required = true;
+        self
+    }
+
+    /// Verify `image` and return its decoded sub-elements.
+    ///
+    /// The integrity code must be the last sub-element. Its AES-MMO hash covers every file byte
+    /// from the start of the header up to the hash value, including the tag and length fields of
+    /// the integrity code element.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`VerifyImageError`] if a header restriction excludes the configured device, the
+    /// body cannot be read or decoded, or the integrity code is missing, misplaced, or wrong.
+    pub fn verify(&self, image: &mut Image) -> Result<Vec<SubElement>, VerifyImageError> {
+        self.verify_header(image.header())?;
+
+        let body = image.read_body()?;
+        let elements = sub_element::parse(&body)?;
+        let integrity_code = elements
+            .iter()
+            .enumerate()
+            .find_map(|(index, located)| match located.element {
+                SubElement::ImageIntegrityCode(declared) => Some((index, located.offset, declared)),
+                _ => None,
+            });
+        match integrity_code {
+            Some((index, ..)) if index + 1 != elements.len() => {
+                return Err(VerifyImageError::MisplacedIntegrityCode);
+            }
+            Some((_, offset, declared)) => {
+                let hashed_body = offset + SUB_ELEMENT_HEADER_LENGTH;
+                let header = image.header().as_bytes();
+                if header.len() + hashed_body >= MAX_HASHED_LENGTH {
+                    return Err(ParseImageError::ImageTooLarge.into());
+                }
+                let mut hashed = Vec::with_capacity(header.len() + hashed_body);
+                hashed.extend_from_slice(header);
+                hashed.extend_from_slice(&body[..hashed_body]);
+                let computed = IntegrityCode::compute(&hashed);
+                if computed != declared {
+                    return Err(VerifyImageError::IntegrityCodeMismatch { declared, computed });
+                }
+            }
+            None if self.integrity_code_required => {
+                return Err(VerifyImageError::MissingIntegrityCode);
+            }
+            None => {}
+        }
+
+        Ok(elements
+            .into_iter()
+            .map(|located| located.element)
+            .collect())
+    }
+
+    /// Return whether images without an integrity code are rejected.
+    pub(super) const fn integrity_code_required(&self) -> bool {
+        self.integrity_code_required
+    }
+
+    /// Check the destination and hardware-version restrictions of `header`.
+    pub(super) fn verify_header(&self, header: &Header) -> Result<(), VerifyImageError> {
+        if let (Some(destination), Some(device)) = (header.upgrade_file_destination(), self.device)
+            && destination != device
+        {
+            return Err(VerifyImageError::DestinationMismatch {
+                destination,
+                device,
+            });
+        }
+
+        if let (Some((minimum, maximum)), Some(hardware_version)) =
+            (header.hardware_versions(), self.hardware_version)
+            && !(minimum..=maximum).contains(&hardware_version)
+        {
+            return Err(VerifyImageError::UnsupportedHardwareVersion {
+                hardware_version,
+                minimum,
+                maximum,
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -1,11 +1,14 @@
 use thiserror::Error as ThisError;
-use tokio::sync::oneshot;
+use tokio::sync::{oneshot, watch};
 use zb_aps::apsde::{DataIndication, IndividualEndpoint};
 use zb_core::FullAddress;
 use zb_zcl::Frame;
-use zb_zcl::ota_upgrade::Command as OtaCommand;
+use zb_zcl::ota_upgrade::{Command as OtaCommand, ImageId};
 
-use super::{Image, UpdateTimeouts};
+use super::{
+    Campaign, CampaignControl, CampaignReport, CampaignSummary, Image, Progress, RepositoryImage,
+    UpdateTimeouts,
+};
 
 /// Terminal result delivered to the caller that scheduled an OTA update.
 pub type UpdateResult = Result<(), UpdateError>;
@@ -29,6 +32,31 @@ pub enum Message {
         cancellation: oneshot::Receiver<()>,
         /// Reports the terminal result of the scheduled update.
         completion: oneshot::Sender<UpdateResult>,
+        /// Publishes the data served to the client.
+        progress: watch::Sender<Progress>,
+    },
+    /// Serve an image to every eligible device that queries for a newer file version.
+    RegisterImage {
+        /// Repository image and its eligibility restrictions.
+        image: Box<RepositoryImage>,
+        /// Reports whether the OTA server could start serving queries.
+        response: oneshot::Sender<UpdateResult>,
+    },
+    /// Stop offering a repository image to devices that query for a newer file version.
+    UnregisterImage {
+        /// Manufacturer, image type, and file version of the registered image.
+        image: ImageId,
+    },
+    /// Distribute one image to a set of devices or to the members of a group.
+    StartCampaign {
+        /// Image, targets, and pacing of the campaign.
+        campaign: Box<Campaign>,
+        /// Run state requested by the caller. Closing the channel cancels the campaign.
+        control: watch::Receiver<CampaignControl>,
+        /// Publishes the campaign counters.
+        summary: watch::Sender<CampaignSummary>,
+        /// Reports the per-device outcomes once the campaign ends.
+        completion: oneshot::Sender<Result<CampaignReport, UpdateError>>,
     },
     /// A received OTA Upgrade cluster command.
     Received {
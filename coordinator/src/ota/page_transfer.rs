@@ -1,14 +1,18 @@
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use log::warn;
 use tokio::sync::mpsc::Sender;
+use tokio::sync::watch;
 use tokio::time::sleep;
 use zb_aps::TxOptions;
 use zb_aps::apsde::{IndividualEndpoint, NetworkDestination};
 use zb_core::Cluster;
 use zb_zcl::ota_upgrade::{ImageBlock, ImageBlockResponse, ImageBlockResponsePayload, ImageId};
 
+use super::campaign::Throttle;
 use super::image::ImageTransfer;
+use super::progress::Progress;
 use super::transfer::read_image_range;
 use super::{OTA_PROFILE, UpdateError, UpdateResult, reply_zcl, request, zcl};
 
@@ -19,6 +23,8 @@ use super::{OTA_PROFILE, UpdateError, UpdateResult, reply_zcl, request, zcl};
 pub(super) struct PageTransfer {
     pub(super) zcl: Sender<zcl::Message>,
     pub(super) image: ImageTransfer,
+    pub(super) progress: watch::Sender<Progress>,
+    pub(super) throttle: Option<Arc<Throttle>>,
     pub(super) destination: NetworkDestination,
     pub(super) source_endpoint: IndividualEndpoint,
     pub(super) image_id: ImageId,
@@ -36,6 +42,10 @@ impl PageTransfer {
         loop {
             let file_offset =
                 u32::try_from(self.offset).expect("validated OTA image offset fits u32");
+            let block_length = self.block_data.len();
+            if let Some(throttle) = &self.throttle {
+                throttle.acquire(block_length).await;
+            }
             let block = ImageBlock::try_new(self.image_id, file_offset, self.block_data)
                 .expect("requested OTA blocks never exceed the client's u8 maximum data size");
             let response = ImageBlockResponse::new(ImageBlockResponsePayload::Success(block));
@@ -50,6 +60,9 @@ impl PageTransfer {
             let Some(()) = reply_zcl(&self.zcl, self.sequence_number, request).await else {
                 return Err(UpdateError::Transmission);
             };
+            self.progress.send_modify(|progress| {
+                progress.record_block(self.offset, block_length, Instant::now());
+            });
 
             self.offset = self.offset.saturating_add(self.maximum_data_size);
             if self.offset >= self.page_end {
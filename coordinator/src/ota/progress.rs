@@ -0,0 +1,170 @@
+use std::time::{Duration, Instant};
+
+/// Snapshot of the image data served during one OTA update.
+///
+/// Rates and the remaining-time estimate are measured from the first served block, so the
+/// discovery phase does not distort them.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Progress {
+    image_size: usize,
+    offset: usize,
+    bytes_served: u64,
+    blocks_served: u32,
+    resumptions: u32,
+    first_block: Option<FirstBlock>,
+    last_block: Option<Instant>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct FirstBlock {
+    served: Instant,
+    length: usize,
+}
+
+impl Progress {
+    /// Create the progress of an update that has not served any data.
+    #[must_use]
+    pub const fn new(image_size: usize) -> Self {
+        Self {
+            image_size,
+            offset: 0,
+            bytes_served: 0,
+            blocks_served: 0,
+            resumptions: 0,
+            first_block: None,
+            last_block: None,
+        }
+    }
+
+    /// Return the complete image size in bytes, including the OTA header.
+    #[must_use]
+    pub const fn image_size(&self) -> usize {
+        self.image_size
+    }
+
+    /// Return the file offset following the most recently served block.
+    ///
+    /// The offset moves backwards when a client resumes from an earlier offset.
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Return the total number of bytes served, including data served again after a resumption.
+    #[must_use]
+    pub const fn bytes_served(&self) -> u64 {
+        self.bytes_served
+    }
+
+    /// Return the number of Image Block responses sent.
+    #[must_use]
+    pub const fn blocks_served(&self) -> u32 {
+        self.blocks_served
+    }
+
+    /// Return how often the client requested data before the current offset.
+    ///
+    /// This happens when a client resumes after a reconnection or repeats a request whose
+    /// response was lost.
+    #[must_use]
+    pub const fn resumptions(&self) -> u32 {
+        self.resumptions
+    }
+
+    /// Return the number of blocks served per second since the first block.
+    ///
+    /// Returns `None` until at least two blocks have been served at distinct instants.
+    #[must_use]
+    pub fn block_rate(&self) -> Option<f64> {
+        let elapsed = self.elapsed()?;
+        Some(f64::from(self.blocks_served - 1) / elapsed.as_secs_f64())
+    }
+
+    /// Return the estimated time until the client has received the remaining image data.
+    ///
+    /// The estimate extrapolates the byte rate since the first served block. It returns `None`
+    /// until at least two blocks have been served at distinct instants.
+    #[must_use]
+    pub fn eta(&self) -> Option<Duration> {
+        let elapsed = self.elapsed()?;
+        let first_length = self.first_block?.length;
+        let measured = u128::from(self.bytes_served).checked_sub(first_length as u128)?;
+        if measured == 0 {
+            return None;
+        }
+
+        let remaining = self.image_size.saturating_sub(self.offset) as u128;
+        let nanos = elapsed.as_nanos().saturating_mul(remaining) / measured;
+        Some(Duration::from_nanos(
+            u64::try_from(nanos).unwrap_or(u64::MAX),
+        ))
+    }
+
+    /// Record one served block that starts at `offset`.
+    pub(super) fn record_block(&mut self, offset: usize, length: usize, now: Instant) {
+        self.offset = offset.saturating_add(length);
+        self.bytes_served = self.bytes_served.saturating_add(length as u64);
+        self.blocks_served = self.blocks_served.saturating_add(1);
+        self.first_block.get_or_insert(FirstBlock {
+            served: now,
+            length,
+        });
+        self.last_block = Some(now);
+    }
+
+    /// Record that the client requested data before the current offset.
+    pub(super) const fn record_resumption(&mut self) {
+        self.resumptions = self.resumptions.saturating_add(1);
+    }
+
+    fn elapsed(&self) -> Option<Duration> {
+        let elapsed = self
+            .last_block?
+            .checked_duration_since(self.first_block?.served)?;
+        (self.blocks_served > 1 && !elapsed.is_zero()).then_some(elapsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::Progress;
+
+    const IMAGE_SIZE: usize = 100;
+    const BLOCK_SIZE: usize = 10;
+    const BLOCK_INTERVAL: Duration = Duration::from_millis(500);
+
+    #[test]
+    fn estimates_the_remaining_time_from_the_block_rate() {
+        let start = Instant::now();
+        let mut progress = Progress::new(IMAGE_SIZE);
+        progress.record_block(0, BLOCK_SIZE, start);
+        assert_eq!(progress.block_rate(), None);
+        assert_eq!(progress.eta(), None);
+
+        progress.record_block(BLOCK_SIZE, BLOCK_SIZE, start + BLOCK_INTERVAL);
+        progress.record_block(2 * BLOCK_SIZE, BLOCK_SIZE, start + 2 * BLOCK_INTERVAL);
+
+        assert_eq!(progress.offset(), 3 * BLOCK_SIZE);
+        assert_eq!(progress.bytes_served(), 30);
+        assert_eq!(progress.block_rate(), Some(2.0));
+        assert_eq!(progress.eta(), Some(7 * BLOCK_INTERVAL));
+    }
+
+    #[test]
+    fn resumption_moves_the_offset_back_without_losing_served_bytes() {
+        let start = Instant::now();
+        let mut progress = Progress::new(IMAGE_SIZE);
+        progress.record_block(0, BLOCK_SIZE, start);
+        progress.record_block(BLOCK_SIZE, BLOCK_SIZE, start + BLOCK_INTERVAL);
+
+        progress.record_resumption();
+        progress.record_block(0, BLOCK_SIZE, start + 2 * BLOCK_INTERVAL);
+
+        assert_eq!(progress.resumptions(), 1);
+        assert_eq!(progress.offset(), BLOCK_SIZE);
+        assert_eq!(progress.bytes_served(), 30);
+        assert_eq!(progress.eta(), Some(9 * BLOCK_INTERVAL));
+    }
+}
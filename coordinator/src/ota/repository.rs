@@ -0,0 +1,159 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use zb_core::IeeeAddress;
+use zb_zcl::ota_upgrade::{ImageId, QueryNextImageRequest};
+
+use super::image::ImageTransfer;
+use super::{Image, UpdateTimeouts};
+
+/// An OTA image served to every eligible device that queries for a newer file version.
+///
+/// Without allow-lists, the image is eligible for every device whose query names its manufacturer
+/// code and image type and whose hardware version lies within the range of the image header.
+#[derive(Debug)]
+pub struct RepositoryImage {
+    image: Image,
+    hardware_versions: Option<BTreeSet<u16>>,
+    ieee_addresses: Option<BTreeSet<IeeeAddress>>,
+    timeouts: UpdateTimeouts,
+}
+
+impl RepositoryImage {
+    /// Create a repository entry for `image` with [`UpdateTimeouts::default`].
+    #[must_use]
+    pub fn new(image: Image) -> Self {
+        Self {
+            image,
+            hardware_versions: None,
+            ieee_addresses: None,
+            timeouts: UpdateTimeouts::default(),
+        }
+    }
+
+    /// Restrict the image to devices that report one of the given hardware versions.
+    ///
+    /// Devices that omit their hardware version from the query are not eligible.
+    #[must_use]
+    pub fn with_hardware_versions<T>(mut self, hardware_versions: T) -> Self
+    where
+        T: IntoIterator<Item = u16>,
+    {
+        self.hardware_versions = Some(hardware_versions.into_iter().collect());
+        self
+    }
+
+    /// Restrict the image to devices with one of the given IEEE addresses.
+    #[must_use]
+    pub fn with_ieee_addresses<T>(mut self, ieee_addresses: T) -> Self
+    where
+        T: IntoIterator<Item = IeeeAddress>,
+    {
+        self.ieee_addresses = Some(ieee_addresses.into_iter().collect());
+        self
+    }
+
+    /// Select the deadlines of transfers started for this image.
+    #[must_use]
+    pub const fn with_timeouts(mut self, timeouts: UpdateTimeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Return the manufacturer, image type, and file version that index this image.
+    #[must_use]
+    pub const fn id(&self) -> ImageId {
+        self.image.id()
+    }
+}
+
+/// Registered repository image with its shared reader task.
+#[derive(Debug)]
+struct Entry {
+    image: ImageTransfer,
+    hardware_versions: Option<BTreeSet<u16>>,
+    ieee_addresses: Option<BTreeSet<IeeeAddress>>,
+    timeouts: UpdateTimeouts,
+}
+
+impl Entry {
+    fn is_eligible(&self, ieee_address: IeeeAddress, hardware_version: Option<u16>) -> bool {
+        self.image.upgrade_file_destination().is_none()
+            && self.image.supports_hardware(hardware_version)
+            && self.hardware_versions.as_ref().is_none_or(|versions| {
+                hardware_version.is_some_and(|version| versions.contains(&version))
+            })
+            && self
+                .ieee_addresses
+                .as_ref()
+                .is_none_or(|addresses| addresses.contains(&ieee_address))
+    }
+}
+
+/// Images indexed by manufacturer code, image type, and file version.
+#[derive(Debug)]
+pub(super) struct Repository {
+    images: BTreeMap<ImageId, Entry>,
+}
+
+impl Repository {
+    /// Create an empty repository.
+    pub(super) const fn new() -> Self {
+        Self {
+            images: BTreeMap::new(),
+        }
+    }
+
+    /// Register `image`, replacing a previous image with the same identifier.
+    ///
+    /// This spawns the reader task shared by every transfer of the image.
+    pub(super) fn insert(&mut self, image: RepositoryImage) {
+        let RepositoryImage {
+            image,
+            hardware_versions,
+            ieee_addresses,
+            timeouts,
+        } = image;
+        self.images.insert(
+            image.id(),
+            Entry {
+                image: image.into_transfer(),
+                hardware_versions,
+                ieee_addresses,
+                timeouts,
+            },
+        );
+    }
+
+    /// Remove the image with `id` and report whether it was registered.
+    ///
+    /// Transfers that already serve the image continue until they finish.
+    pub(super) fn remove(&mut self, id: ImageId) -> bool {
+        self.images.remove(&id).is_some()
+    }
+
+    /// Return whether no image is registered.
+    pub(super) fn is_empty(&self) -> bool {
+        self.images.is_empty()
+    }
+
+    /// Return the newest image eligible for the querying device and its transfer deadlines.
+    ///
+    /// Only file versions newer than the device's current version are offered. Images restricted
+    /// to an upgrade file destination are only served through explicitly scheduled updates.
+    pub(super) fn select(
+        &self,
+        ieee_address: IeeeAddress,
+        request: &QueryNextImageRequest,
+    ) -> Option<(ImageTransfer, UpdateTimeouts)> {
+        let current = request.image();
+        let newer = current.file_version().checked_add(1)?;
+        let first = ImageId::new(current.manufacturer_code(), current.image_type(), newer);
+        let last = ImageId::new(current.manufacturer_code(), current.image_type(), u32::MAX);
+        self.images
+            .range(first..=last)
+            .rev()
+            .map(|(_, entry)| entry)
+            .find(|entry| entry.is_eligible(ieee_address, request.hardware_version()))
+            .map(|entry| (entry.image.clone(), entry.timeouts))
+    }
+}
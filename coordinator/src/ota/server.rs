@@ -1,10 +1,14 @@
 use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::time::Duration;
 
 use le_stream::ToLeStream;
 use log::{debug, warn};
 use tokio::spawn;
 use tokio::sync::mpsc::{Receiver, Sender, WeakSender};
+use tokio::sync::{oneshot, watch};
 use tokio::task::{AbortHandle, Id, JoinError, JoinHandle};
+use tokio::time::sleep;
 use zb_aps::apsde::{
     DataIndication, IndividualEndpoint, NetworkDestination, ReceivedDestination, Source,
 };
@@ -12,19 +16,24 @@ use zb_core::{Cluster, Direction, FullAddress, IeeeAddress};
 use zb_hw::NcpHandle;
 use zb_zcl::global::default_response::DefaultResponse;
 use zb_zcl::ota_upgrade::{
-    Command as OtaCommand, ImageBlockRequest, ImagePageRequest, QueryNextImageResponse,
-    QueryResponse, QuerySpecificFileResponse, UpgradeEndRequest,
+    Command as OtaCommand, ImageBlockRequest, ImagePageRequest, QueryNextImageRequest,
+    QueryNextImageResponse, QueryResponse, QuerySpecificFileResponse, UpgradeEndRequest,
 };
 use zb_zcl::{
     Cluster as ZclCluster, Command, Frame, Scope, Status, UnsequencedFrame, UnsequencedHeader,
 };
 
+use super::campaign::{ActiveCampaign, Admission, CampaignEvent};
+use super::progress::Progress;
+use super::repository::Repository;
 use super::state::RequestContext;
 use super::transfer::{Offer, Transfer, TransferExit, TransferMessage};
 use super::{
-    Message, OTA_PROFILE, UpdateError, network_destination, reply_zcl,
-    request_from_unsequenced_frame, zcl,
+    Campaign, CampaignControl, CampaignReport, CampaignSummary, Message, OTA_PROFILE,
+    RepositoryImage, Request, UpdateError, UpdateResult, network_destination, reply_zcl,
+    request_from_unsequenced_frame, send_zcl, zcl,
 };
+use crate::event::{Event, EventSink, OtaEvent};
 
 /// Handle used by the OTA server to route messages to one destination transfer.
 #[derive(Debug)]
@@ -63,6 +72,7 @@ impl AddressResolver {
 }
 
 enum ServerEvent {
+    Campaign { id: u64, event: CampaignEvent },
     Message(Message),
     Shutdown,
     Transfer(Result<(Id, TransferExit), JoinError>),
@@ -73,10 +83,14 @@ enum ServerEvent {
 pub struct Server {
     addresses: AddressResolver,
     zcl: Sender<zcl::Message>,
+    events: EventSink,
     sender: WeakSender<ServerEvent>,
     inbound: Receiver<ServerEvent>,
     subscription: Option<ActiveSubscription>,
     transfers: BTreeMap<NetworkDestination, ActiveTransfer>,
+    repository: Repository,
+    campaigns: BTreeMap<u64, ActiveCampaign>,
+    next_campaign_id: u64,
     update_task_limit: usize,
 }
 
@@ -85,6 +99,7 @@ impl Server {
     const fn new(
         addresses: AddressResolver,
         zcl: Sender<zcl::Message>,
+        events: EventSink,
         sender: WeakSender<ServerEvent>,
         inbound: Receiver<ServerEvent>,
         update_task_limit: usize,
@@ -92,22 +107,28 @@ impl Server {
         Self {
             addresses,
             zcl,
+            events,
             sender,
             inbound,
             subscription: None,
             transfers: BTreeMap::new(),
+            repository: Repository::new(),
+            campaigns: BTreeMap::new(),
+            next_campaign_id: 0,
             update_task_limit,
         }
     }
 
     /// Process every OTA server event through one message inbox.
     pub async fn run(mut self) {
-        let mut hardware_unavailable = false;
         while let Some(event) = self.inbound.recv().await {
             match event {
                 ServerEvent::Transfer(result) => {
                     self.transfer_finished(result).await;
                 }
+                ServerEvent::Campaign { id, event } => {
+                    self.campaign_event(id, event).await;
+                }
                 ServerEvent::Message(message) => match message {
                     Message::Update {
                         target,
@@ -117,39 +138,49 @@ impl Server {
                         timeouts,
                         cancellation,
                         completion,
+                        progress,
                     } => {
                         let offer = Offer {
                             target,
                             target_endpoint,
                             source_endpoint,
-                            image,
+                            image: image.into_transfer(),
+                            announce: true,
                             timeouts,
                             cancellation,
                             completion,
+                            progress,
+                            throttle: None,
                         };
-                        if hardware_unavailable {
-                            let _result = offer
-                                .completion
-                                .send(Err(UpdateError::HardwareEventStreamClosed));
-                        } else {
-                            self.update(offer).await;
+                        self.update(offer).await;
+                    }
+                    Message::StartCampaign {
+                        campaign,
+                        control,
+                        summary,
+                        completion,
+                    } => {
+                        self.start_campaign(*campaign, control, summary, completion)
+                            .await;
+                    }
+                    Message::RegisterImage { image, response } => {
+                        let _result = response.send(self.register_image(*image).await);
+                    }
+                    Message::UnregisterImage { image } => {
+                        if !self.repository.remove(image) {
+                            debug!("Ignoring removal of unregistered OTA image {image:?}");
                         }
+                        self.unsubscribe_if_idle().await;
                     }
                     Message::Received { indication } => {
-                        if !hardware_unavailable {
-                            self.received_ota(indication).await;
-                        }
+                        self.received_ota(indication).await;
                     }
                     Message::HardwareUnavailable => {
-                        hardware_unavailable = true;
                         self.stop_transfers_for_hardware_failure().await;
                     }
                 },
                 ServerEvent::Shutdown => break,
             }
-            if hardware_unavailable && self.transfers.is_empty() {
-                break;
-            }
         }
 
         self.unsubscribe().await;
@@ -157,6 +188,9 @@ impl Server {
         for transfer in self.transfers.values() {
             transfer.task.abort();
         }
+        for campaign in self.campaigns.values() {
+            campaign.abort_tasks();
+        }
     }
 
     async fn stop_transfers_for_hardware_failure(&self) {
@@ -180,22 +214,32 @@ impl Server {
     pub(crate) fn spawn(
         ncp: NcpHandle,
         zcl: Sender<zcl::Message>,
+        events: EventSink,
         update_task_limit: usize,
+        channel_size: NonZeroUsize,
     ) -> Sender<Message> {
-        let (sender, messages) = tokio::sync::mpsc::channel(crate::MPSC_CHANNEL_SIZE);
-        let (events, inbound) = tokio::sync::mpsc::channel(crate::MPSC_CHANNEL_SIZE);
+        let (sender, messages) = tokio::sync::mpsc::channel(channel_size.get());
+        let (sender_events, inbound) = tokio::sync::mpsc::channel(crate::MPSC_CHANNEL_SIZE);
         let server = Self::new(
             AddressResolver::Ncp(ncp),
             zcl,
-            events.downgrade(),
+            events,
+            sender_events.downgrade(),
             inbound,
             update_task_limit,
         );
-        spawn(forward_api_messages(messages, events));
+        spawn(forward_api_messages(messages, sender_events));
         spawn(server.run());
         sender
     }
 
+    /// Add an image to the repository and listen for the queries it answers.
+    async fn register_image(&mut self, image: RepositoryImage) -> UpdateResult {
+        self.ensure_subscription().await?;
+        self.repository.insert(image);
+        Ok(())
+    }
+
     /// Replace an existing destination update or admit a new destination transfer task.
     async fn update(&mut self, offer: Offer) {
         let destination = network_destination(offer.target.short_id(), offer.target_endpoint);
@@ -257,9 +301,10 @@ impl Server {
         Ok(())
     }
 
-    /// Remove the OTA subscription when there are no active update offers.
+    /// Remove the OTA subscription when there are no active update offers, repository images, or
+    /// campaigns.
     async fn unsubscribe_if_idle(&mut self) {
-        if self.transfers.is_empty() {
+        if self.transfers.is_empty() && self.repository.is_empty() && self.campaigns.is_empty() {
             self.unsubscribe().await;
         }
     }
@@ -283,7 +328,9 @@ impl Server {
     }
 
     /// Spawn and register the sole destination task for a newly admitted update.
-    fn start_transfer(&mut self, offer: Offer) {
+    ///
+    /// Returns the inbox of the new destination task.
+    fn start_transfer(&mut self, offer: Offer) -> Sender<TransferMessage> {
         let destination = network_destination(offer.target.short_id(), offer.target_endpoint);
         let target = offer.target;
         let (messages, inbound) = tokio::sync::mpsc::channel(crate::MPSC_CHANNEL_SIZE);
@@ -300,11 +347,12 @@ impl Server {
             destination,
             ActiveTransfer {
                 target,
-                messages,
+                messages: messages.clone(),
                 task: abort,
                 task_id,
             },
         );
+        messages
     }
 
     /// Validate an inbound OTA frame and route its command to the matching destination task.
@@ -373,7 +421,7 @@ impl Server {
             .get(&context.destination)
             .map(|transfer| (transfer.messages.clone(), transfer.target))
         else {
-            self.reject_unauthorized(context, command).await;
+            self.serve_unscheduled(short_id, context, command).await;
             return;
         };
         let resolved_address = match self.addresses.resolve(short_id).await {
@@ -395,6 +443,16 @@ impl Server {
             self.reject_unauthorized(context, command).await;
             return;
         }
+        self.route(&messages, context, command).await;
+    }
+
+    /// Forward a validated request to its destination task.
+    async fn route(
+        &mut self,
+        messages: &Sender<TransferMessage>,
+        context: RequestContext,
+        command: OtaCommand,
+    ) {
         let request = TransferMessage::Request { context, command };
         if let Err(error) = messages.send(request).await {
             self.transfers.remove(&context.destination);
@@ -410,20 +468,14 @@ impl Server {
     async fn transfer_finished(&mut self, result: Result<(Id, TransferExit), JoinError>) {
         let completion = match result {
             Ok((task_id, exit)) => {
-                self.remove_transfer(exit.destination, task_id);
+                self.remove_transfer(task_id);
                 Some((exit.completion, exit.result))
             }
             Err(error) => {
-                let task_id = error.id();
                 if !error.is_cancelled() {
                     warn!("OTA destination transfer task failed: {error}");
                 }
-                let destination = self.transfers.iter().find_map(|(destination, transfer)| {
-                    (transfer.task_id == task_id).then_some(*destination)
-                });
-                if let Some(destination) = destination {
-                    self.remove_transfer(destination, task_id);
-                }
+                self.remove_transfer(error.id());
                 None
             }
         };
@@ -433,15 +485,341 @@ impl Server {
         }
     }
 
-    /// Remove `destination` only when it still names `task_id`.
-    fn remove_transfer(&mut self, destination: NetworkDestination, task_id: Id) {
-        let is_current = self
-            .transfers
-            .get(&destination)
-            .is_some_and(|transfer| transfer.task_id == task_id);
-        if is_current {
-            self.transfers.remove(&destination);
+    /// Remove the destination that still names `task_id`, wherever its target has moved.
+    fn remove_transfer(&mut self, task_id: Id) {
+        self.transfers
+            .retain(|_, transfer| transfer.task_id != task_id);
+    }
+
+    /// Route a request from an endpoint without a destination transfer.
+    ///
+    /// A target that rejoined under a new NWK address continues its transfer at that address.
+    /// Query Next Image requests from other devices are served by a campaign that targets them,
+    /// or else from the repository.
+    async fn serve_unscheduled(
+        &mut self,
+        short_id: zb_core::short_id::Device,
+        context: RequestContext,
+        command: OtaCommand,
+    ) {
+        if self.transfers.is_empty() && self.repository.is_empty() && self.campaigns.is_empty() {
+            self.reject_unauthorized(context, command).await;
+            return;
         }
+        let ieee_address = match self.addresses.resolve(short_id).await {
+            Ok(address) => address,
+            Err(error) => {
+                warn!(
+                    "Failed to resolve the IEEE address for OTA request source {short_id}: {error}"
+                );
+                self.reject_unauthorized(context, command).await;
+                return;
+            }
+        };
+        let target = FullAddress::new(ieee_address, short_id);
+        if let Some(messages) = self
+            .follow_rejoined_target(target, context.destination)
+            .await
+        {
+            self.route(&messages, context, command).await;
+            return;
+        }
+        if let OtaCommand::QueryNextImageRequest(request) = &command
+            && let Some((id, admission)) =
+                self.campaign_admission(target, context.destination.endpoint(), request)
+        {
+            self.serve_from_campaign(id, admission, target, context, command)
+                .await;
+            return;
+        }
+        self.serve_from_repository(target, context, command).await;
+    }
+
+    /// Move the transfer of a target that rejoined under a new NWK address to `destination`.
+    ///
+    /// Returns the inbox of the moved destination task, if the target has a transfer for the
+    /// same endpoint.
+    async fn follow_rejoined_target(
+        &mut self,
+        target: FullAddress,
+        destination: NetworkDestination,
+    ) -> Option<Sender<TransferMessage>> {
+        let previous = self.transfers.iter().find_map(|(previous, transfer)| {
+            (transfer.target.ieee_address() == target.ieee_address()
+                && previous.endpoint() == destination.endpoint())
+            .then_some(*previous)
+        })?;
+        let mut transfer = self.transfers.remove(&previous)?;
+        debug!("Following OTA transfer from {previous} to {destination}");
+        transfer.target = target;
+        let messages = transfer.messages.clone();
+        self.transfers.insert(destination, transfer);
+        if messages
+            .send(TransferMessage::Rejoined { target })
+            .await
+            .is_err()
+        {
+            debug!("OTA transfer for {destination} finished before following its target");
+        }
+        Some(messages)
+    }
+
+    /// Start a transfer of the newest eligible repository image for an unscheduled query.
+    async fn serve_from_repository(
+        &mut self,
+        target: FullAddress,
+        context: RequestContext,
+        command: OtaCommand,
+    ) {
+        let OtaCommand::QueryNextImageRequest(request) = &command else {
+            self.reject_unauthorized(context, command).await;
+            return;
+        };
+        if self.repository.is_empty() {
+            self.reject_unauthorized(context, command).await;
+            return;
+        }
+        let short_id = target.short_id();
+        let Some((image, timeouts)) = self.repository.select(target.ieee_address(), request) else {
+            self.reply_no_image_available(context).await;
+            return;
+        };
+        if self.transfers.len() >= self.update_task_limit {
+            debug!(
+                "Deferring OTA query from {short_id} because the update task limit of {} has \
+                 been reached",
+                self.update_task_limit
+            );
+            self.reply_no_image_available(context).await;
+            return;
+        }
+
+        let endpoint = context.destination.endpoint();
+        let image_id = image.id();
+        let (cancel, cancellation) = oneshot::channel();
+        let (completion, result) = oneshot::channel();
+        let (progress, _progress) = watch::channel(Progress::new(image.len()));
+        let messages = self.start_transfer(Offer {
+            target,
+            target_endpoint: endpoint,
+            source_endpoint: context.source_endpoint,
+            image,
+            announce: false,
+            timeouts,
+            cancellation,
+            completion,
+            progress,
+            throttle: None,
+        });
+        self.events.emit(Event::Ota(OtaEvent::TransferStarted {
+            target,
+            endpoint,
+            image: image_id,
+        }));
+        spawn(report_repository_transfer(
+            self.events.clone(),
+            target,
+            endpoint,
+            image_id,
+            result,
+            cancel,
+        ));
+
+        if messages
+            .send(TransferMessage::Request { context, command })
+            .await
+            .is_err()
+        {
+            warn!("Failed to forward OTA query to the repository transfer for {short_id}");
+        }
+    }
+
+    /// Register a campaign and start its notifier, deadline, and control tasks.
+    async fn start_campaign(
+        &mut self,
+        campaign: Campaign,
+        mut control: watch::Receiver<CampaignControl>,
+        summary: watch::Sender<CampaignSummary>,
+        completion: oneshot::Sender<Result<CampaignReport, UpdateError>>,
+    ) {
+        if let Err(error) = self.ensure_subscription().await {
+            let _result = completion.send(Err(error));
+            return;
+        }
+        let id = self.next_campaign_id;
+        self.next_campaign_id = id.wrapping_add(1);
+        let initial = *control.borrow_and_update();
+        let tasks = vec![
+            spawn(notify_campaign(
+                self.sender.clone(),
+                id,
+                campaign.notify_interval(),
+            ))
+            .abort_handle(),
+            spawn(expire_campaign(
+                self.sender.clone(),
+                id,
+                campaign.duration(),
+            ))
+            .abort_handle(),
+            spawn(forward_campaign_control(control, self.sender.clone(), id)).abort_handle(),
+        ];
+        self.campaigns.insert(
+            id,
+            ActiveCampaign::new(
+                campaign,
+                initial == CampaignControl::Pause,
+                summary,
+                completion,
+                tasks,
+            ),
+        );
+        if initial == CampaignControl::Cancel {
+            self.campaign_event(id, CampaignEvent::Control(initial))
+                .await;
+        } else {
+            self.complete_campaign_if_done(id).await;
+        }
+    }
+
+    /// Apply one event to its campaign and deliver the report once the campaign is done.
+    async fn campaign_event(&mut self, id: u64, event: CampaignEvent) {
+        let Some(campaign) = self.campaigns.get_mut(&id) else {
+            return;
+        };
+        let notify_now = match event {
+            CampaignEvent::Tick => true,
+            CampaignEvent::Control(control) => {
+                // Resuming starts a notification round instead of waiting for the next tick.
+                let resumed = campaign.is_paused() && control == CampaignControl::Run;
+                campaign.control(control);
+                resumed
+            }
+            CampaignEvent::Expired => {
+                campaign.expire();
+                false
+            }
+            CampaignEvent::Transfer { target, result } => {
+                campaign.finish(target, result);
+                false
+            }
+        };
+        if notify_now {
+            for request in campaign.notifications() {
+                spawn(notify(self.zcl.clone(), request));
+            }
+        }
+        self.complete_campaign_if_done(id).await;
+    }
+
+    /// Remove a campaign whose devices all have an outcome and report it to the caller.
+    async fn complete_campaign_if_done(&mut self, id: u64) {
+        if !self
+            .campaigns
+            .get(&id)
+            .is_some_and(ActiveCampaign::is_complete)
+        {
+            return;
+        }
+        if let Some(campaign) = self.campaigns.remove(&id) {
+            campaign.complete();
+        }
+        self.unsubscribe_if_idle().await;
+    }
+
+    /// Return the first campaign that targets a querying device, with its decision.
+    fn campaign_admission(
+        &self,
+        target: FullAddress,
+        endpoint: IndividualEndpoint,
+        request: &QueryNextImageRequest,
+    ) -> Option<(u64, Admission)> {
+        let slot_available = self.transfers.len() < self.update_task_limit;
+        self.campaigns.iter().find_map(|(id, campaign)| {
+            campaign
+                .admission(target, endpoint, request, slot_available)
+                .map(|admission| (*id, admission))
+        })
+    }
+
+    /// Answer a query from a device targeted by a campaign, starting its transfer if admitted.
+    async fn serve_from_campaign(
+        &mut self,
+        id: u64,
+        admission: Admission,
+        target: FullAddress,
+        context: RequestContext,
+        command: OtaCommand,
+    ) {
+        let endpoint = context.destination.endpoint();
+        let Some(campaign) = self.campaigns.get_mut(&id) else {
+            return;
+        };
+        match admission {
+            Admission::AlreadyCurrent => {
+                campaign.record_current(target, endpoint);
+                self.reply_no_image_available(context).await;
+                self.complete_campaign_if_done(id).await;
+                return;
+            }
+            Admission::Deferred => {
+                debug!(
+                    "Deferring OTA query from {} until the campaign has a free transfer slot",
+                    target.short_id()
+                );
+                self.reply_no_image_available(context).await;
+                return;
+            }
+            Admission::Admit => {}
+        }
+
+        let image = campaign.image();
+        let timeouts = campaign.timeouts();
+        let throttle = campaign.throttle();
+        let (cancel, cancellation) = oneshot::channel();
+        let (completion, result) = oneshot::channel();
+        let (progress, _progress) = watch::channel(Progress::new(image.len()));
+        let messages = self.start_transfer(Offer {
+            target,
+            target_endpoint: endpoint,
+            source_endpoint: context.source_endpoint,
+            image,
+            announce: false,
+            timeouts,
+            cancellation,
+            completion,
+            progress,
+            throttle: Some(throttle),
+        });
+        let supervisor = spawn(report_campaign_transfer(
+            self.sender.clone(),
+            id,
+            target.ieee_address(),
+            result,
+            cancel,
+        ));
+        if let Some(campaign) = self.campaigns.get_mut(&id) {
+            campaign.start(target, endpoint, supervisor.abort_handle());
+        }
+
+        if messages
+            .send(TransferMessage::Request { context, command })
+            .await
+            .is_err()
+        {
+            warn!(
+                "Failed to forward OTA query to the campaign transfer for {}",
+                target.short_id()
+            );
+        }
+    }
+
+    /// Tell a querying device that no newer image is available for it.
+    async fn reply_no_image_available(&self, context: RequestContext) {
+        let frame = UnsequencedFrame::from_command(QueryNextImageResponse::new(
+            QueryResponse::NoImageAvailable,
+        ));
+        self.reply(context, frame).await;
     }
 
     /// Reply to a request for which no destination transfer is active.
@@ -468,6 +846,11 @@ impl Server {
             | OtaCommand::UpgradeEndResponse(_)
             | OtaCommand::QuerySpecificFileResponse(_) => return,
         };
+        self.reply(context, frame).await;
+    }
+
+    /// Send a reply frame to the request source.
+    async fn reply(&self, context: RequestContext, frame: UnsequencedFrame<bytes::Bytes>) {
         let request = request_from_unsequenced_frame(
             context.destination.into(),
             context.source_endpoint,
@@ -484,20 +867,9 @@ impl Server {
 /// Forward public OTA API messages into the server's private event inbox.
 async fn forward_api_messages(mut messages: Receiver<Message>, events: Sender<ServerEvent>) {
     while let Some(message) = messages.recv().await {
-        let hardware_unavailable = matches!(&message, Message::HardwareUnavailable);
         if events.send(ServerEvent::Message(message)).await.is_err() {
             return;
         }
-        if hardware_unavailable {
-            messages.close();
-            while let Some(message) = messages.recv().await {
-                if let Message::Update { completion, .. } = message {
-                    let _result = completion.send(Err(UpdateError::HardwareEventStreamClosed));
-                }
-            }
-            events.closed().await;
-            return;
-        }
     }
     let _result = events.send(ServerEvent::Shutdown).await;
 }
@@ -527,6 +899,88 @@ async fn forward_subscription_frames(
     }
 }
 
+/// Report the outcome of a repository transfer, keeping the transfer uncancelled until then.
+async fn report_repository_transfer(
+    events: EventSink,
+    target: FullAddress,
+    endpoint: IndividualEndpoint,
+    image: zb_zcl::ota_upgrade::ImageId,
+    result: oneshot::Receiver<UpdateResult>,
+    _cancel: oneshot::Sender<()>,
+) {
+    let Ok(result) = result.await else {
+        return;
+    };
+    events.emit(Event::Ota(OtaEvent::TransferFinished {
+        target,
+        endpoint,
+        image,
+        result,
+    }));
+}
+
+/// Report the outcome of a campaign transfer, keeping the transfer uncancelled until then.
+///
+/// Aborting this task cancels the transfer.
+async fn report_campaign_transfer(
+    sender: WeakSender<ServerEvent>,
+    id: u64,
+    target: IeeeAddress,
+    result: oneshot::Receiver<UpdateResult>,
+    _cancel: oneshot::Sender<()>,
+) {
+    let result = result.await.unwrap_or(Err(UpdateError::TransferTask));
+    send_campaign_event(&sender, id, CampaignEvent::Transfer { target, result }).await;
+}
+
+/// Request a round of campaign notifications every `interval`, starting immediately.
+async fn notify_campaign(sender: WeakSender<ServerEvent>, id: u64, interval: Duration) {
+    while send_campaign_event(&sender, id, CampaignEvent::Tick).await {
+        sleep(interval).await;
+    }
+}
+
+/// Stop admitting devices to a campaign once its duration has elapsed.
+async fn expire_campaign(sender: WeakSender<ServerEvent>, id: u64, duration: Duration) {
+    sleep(duration).await;
+    send_campaign_event(&sender, id, CampaignEvent::Expired).await;
+}
+
+/// Forward run-state changes of a campaign handle, cancelling the campaign when it is dropped.
+async fn forward_campaign_control(
+    mut control: watch::Receiver<CampaignControl>,
+    sender: WeakSender<ServerEvent>,
+    id: u64,
+) {
+    while control.changed().await.is_ok() {
+        let value = *control.borrow_and_update();
+        if !send_campaign_event(&sender, id, CampaignEvent::Control(value)).await {
+            return;
+        }
+    }
+    send_campaign_event(&sender, id, CampaignEvent::Control(CampaignControl::Cancel)).await;
+}
+
+/// Deliver one campaign event and report whether the server is still running.
+async fn send_campaign_event(
+    sender: &WeakSender<ServerEvent>,
+    id: u64,
+    event: CampaignEvent,
+) -> bool {
+    let Some(sender) = sender.upgrade() else {
+        return false;
+    };
+    sender
+        .send(ServerEvent::Campaign { id, event })
+        .await
+        .is_ok()
+}
+
+/// Transmit one campaign Image Notify without blocking the server.
+async fn notify(zcl: Sender<zcl::Message>, request: Request) {
+    let _result = send_zcl(&zcl, request).await;
+}
+
 /// Forward one destination task's terminal result into the server event inbox.
 async fn forward_transfer_completion(
     task: JoinHandle<TransferExit>,
@@ -578,17 +1032,33 @@ impl Server {
         zcl: Sender<zcl::Message>,
         update_task_limit: usize,
         resolved_ieee_address: IeeeAddress,
+    ) -> (Sender<Message>, Self) {
+        let (events, _received) = tokio::sync::mpsc::channel(crate::MPSC_CHANNEL_SIZE);
+        Self::test_new_with_events(
+            zcl,
+            EventSink::new(events),
+            update_task_limit,
+            resolved_ieee_address,
+        )
+    }
+
+    pub(super) fn test_new_with_events(
+        zcl: Sender<zcl::Message>,
+        events: EventSink,
+        update_task_limit: usize,
+        resolved_ieee_address: IeeeAddress,
     ) -> (Sender<Message>, Self) {
         let (sender, messages) = tokio::sync::mpsc::channel(crate::MPSC_CHANNEL_SIZE);
-        let (events, inbound) = tokio::sync::mpsc::channel(crate::MPSC_CHANNEL_SIZE);
+        let (server_events, inbound) = tokio::sync::mpsc::channel(crate::MPSC_CHANNEL_SIZE);
         let server = Self::new(
             AddressResolver::Fixed(resolved_ieee_address),
             zcl,
-            events.downgrade(),
+            events,
+            server_events.downgrade(),
             inbound,
             update_task_limit,
         );
-        spawn(forward_api_messages(messages, events));
+        spawn(forward_api_messages(messages, server_events));
         (sender, server)
     }
 }
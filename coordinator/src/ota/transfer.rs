@@ -1,12 +1,13 @@
 use std::collections::HashMap;
 use std::future::{Future, poll_fn};
+use std::sync::Arc;
 use std::task::Poll;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use le_stream::ToLeStream;
 use log::{debug, trace, warn};
 use tokio::sync::mpsc::{Receiver, Sender, WeakSender};
-use tokio::sync::oneshot;
+use tokio::sync::{oneshot, watch};
 use tokio::task::{AbortHandle, Id, JoinError, JoinSet};
 use tokio::time::sleep;
 use zb_aps::apsde::{IndividualEndpoint, NetworkDestination};
@@ -17,15 +18,17 @@ use zb_zcl::ota_upgrade::{
     ImageBlockResponsePayload, ImageId, ImageNotify, ImageNotifyPayload, ImagePageRequest,
     QueryJitter, QueryNextImageRequest, QueryNextImageResponse, QueryResponse,
     QuerySpecificFileRequest, QuerySpecificFileResponse, UpgradeEndRequest, UpgradeEndResponse,
-    UpgradeEndStatus,
+    UpgradeEndStatus, WaitForData,
 };
 use zb_zcl::{Command, Scope, Status, UnsequencedFrame, UnsequencedHeader};
 
+use super::campaign::Throttle;
 use super::image::ImageTransfer;
 use super::page_transfer::PageTransfer;
+use super::progress::Progress;
 use super::state::RequestContext;
 use super::{
-    CURRENT_TIME_IMMEDIATE, Image, OTA_PROFILE, Request, UPGRADE_TIME_IMMEDIATE, UpdateError,
+    CURRENT_TIME_IMMEDIATE, OTA_PROFILE, Request, UPGRADE_TIME_IMMEDIATE, UpdateError,
     UpdateResult, UpdateTimeouts, network_destination, reply_zcl, request,
     request_from_unsequenced_frame, send_zcl, zcl,
 };
@@ -51,6 +54,11 @@ pub(super) enum TransferMessage {
     },
     /// Stop the update because the hardware event source is unavailable.
     HardwareUnavailable,
+    /// Continue the update at the new NWK address of a rejoined target.
+    Rejoined {
+        /// Target IEEE address with its new NWK short address.
+        target: FullAddress,
+    },
     /// Process an OTA request received from the destination.
     Request {
         context: RequestContext,
@@ -67,18 +75,23 @@ pub(super) struct Offer {
     /// Local OTA server endpoint used as the APS source.
     pub(super) source_endpoint: IndividualEndpoint,
     /// Offered image.
-    pub(super) image: Image,
+    pub(super) image: ImageTransfer,
+    /// Whether the image is announced with Image Notify rather than awaiting a client query.
+    pub(super) announce: bool,
     /// Deadlines selected for the offer.
     pub(super) timeouts: UpdateTimeouts,
     /// Resolves when the caller drops or cancels its update future.
     pub(super) cancellation: oneshot::Receiver<()>,
     /// Reports the update's terminal result.
     pub(super) completion: oneshot::Sender<UpdateResult>,
+    /// Publishes the data served to the client.
+    pub(super) progress: watch::Sender<Progress>,
+    /// Pause flag and airtime budget of the campaign that started the offer, if any.
+    pub(super) throttle: Option<Arc<Throttle>>,
 }
 
 /// Normal completion notification from a destination transfer task.
 pub(super) struct TransferExit {
-    pub(super) destination: NetworkDestination,
     pub(super) completion: oneshot::Sender<UpdateResult>,
     pub(super) result: UpdateResult,
 }
@@ -91,9 +104,12 @@ pub(super) struct Transfer {
     target_endpoint: IndividualEndpoint,
     source_endpoint: IndividualEndpoint,
     image: ImageTransfer,
+    announce: bool,
     timeouts: UpdateTimeouts,
     cancellation: Option<oneshot::Receiver<()>>,
     completion: Option<oneshot::Sender<UpdateResult>>,
+    progress: watch::Sender<Progress>,
+    throttle: Option<Arc<Throttle>>,
     messages: Receiver<TransferMessage>,
     operations: JoinSet<OperationResult>,
     operation_generations: HashMap<Id, u64>,
@@ -148,9 +164,12 @@ impl Transfer {
             target_endpoint,
             source_endpoint,
             image,
+            announce,
             timeouts,
             cancellation,
             completion,
+            progress,
+            throttle,
         } = offer;
         Self {
             zcl,
@@ -158,10 +177,13 @@ impl Transfer {
             target,
             target_endpoint,
             source_endpoint,
-            image: image.into_transfer(),
+            image,
+            announce,
             timeouts,
             cancellation: Some(cancellation),
             completion: Some(completion),
+            progress,
+            throttle,
             messages,
             operations: JoinSet::new(),
             operation_generations: HashMap::new(),
@@ -176,7 +198,9 @@ impl Transfer {
     /// Run the destination transfer until it reaches a terminal outcome.
     pub(super) async fn run(mut self) -> TransferExit {
         self.start_lifecycle();
-        self.notify();
+        if self.announce {
+            self.notify();
+        }
         let result = loop {
             let event = poll_fn(|context| {
                 if let Poll::Ready(message) = self.messages.poll_recv(context) {
@@ -240,7 +264,6 @@ impl Transfer {
         self.abort_lifecycle_tasks();
         self.operations.abort_all();
         TransferExit {
-            destination: self.destination(),
             completion: self
                 .completion
                 .take()
@@ -261,6 +284,14 @@ impl Transfer {
             TransferMessage::HardwareUnavailable => {
                 unreachable!("hardware shutdown is handled by the transfer loop");
             }
+            TransferMessage::Rejoined { target } => {
+                debug!(
+                    "Continuing OTA transfer for {} at its new address {}",
+                    target.ieee_address(),
+                    target.short_id()
+                );
+                self.target = target;
+            }
             TransferMessage::Request { context, command } => {
                 trace!(
                     "Processing OTA command from {}: {command:?}",
@@ -312,9 +343,12 @@ impl Transfer {
             target_endpoint,
             source_endpoint,
             image,
+            announce,
             timeouts,
             cancellation,
             completion,
+            progress,
+            throttle,
         } = offer;
         self.abort_lifecycle_tasks();
         self.operations.abort_all();
@@ -325,11 +359,16 @@ impl Transfer {
         self.target = target;
         self.target_endpoint = target_endpoint;
         self.source_endpoint = source_endpoint;
-        self.image = image.into_transfer();
+        self.image = image;
+        self.progress = progress;
+        self.throttle = throttle;
+        self.announce = announce;
         self.timeouts = timeouts;
         self.cancellation = Some(cancellation);
         self.start_lifecycle();
-        self.notify();
+        if self.announce {
+            self.notify();
+        }
     }
 
     /// Start cancellation, discovery, and total-transfer lifecycle tasks for this generation.
@@ -472,10 +511,23 @@ impl Transfer {
                 return false;
             }
         };
+        self.record_resumption(range.offset);
+        if let Some(Err(delay)) = self
+            .throttle
+            .as_ref()
+            .map(|throttle| throttle.try_acquire(range.length))
+        {
+            let response = ImageBlockResponse::new(ImageBlockResponsePayload::WaitForData(
+                wait_for_data(delay),
+            ));
+            self.spawn_reply(context, response, None);
+            return true;
+        }
         let zcl = self.zcl.clone();
         let image = self.image.clone();
+        let progress = self.progress.clone();
         self.spawn_operation(async move {
-            image_block_operation(&zcl, &image, context, request, range).await
+            image_block_operation(&zcl, &image, &progress, context, request, range).await
         });
         true
     }
@@ -508,14 +560,33 @@ impl Transfer {
                 return false;
             }
         };
+        self.record_resumption(range.offset);
         let zcl = self.zcl.clone();
         let image = self.image.clone();
+        let progress = self.progress.clone();
+        let throttle = self.throttle.clone();
         self.spawn_operation(async move {
-            image_page_operation(zcl, image, context, request, range).await
+            image_page_operation(zcl, image, progress, throttle, context, request, range).await
         });
         true
     }
 
+    /// Count a data request before the current offset as a resumption by a reconnected client.
+    ///
+    /// Such requests are served like any other, and their activity resets the block-inactivity
+    /// deadline of the current generation.
+    fn record_resumption(&self, offset: usize) {
+        let progress = *self.progress.borrow();
+        if offset < progress.offset() {
+            debug!(
+                "OTA client {} resumed at offset {offset} before {}",
+                self.target.ieee_address(),
+                progress.offset()
+            );
+            self.progress.send_modify(Progress::record_resumption);
+        }
+    }
+
     /// Complete or acknowledge an upgrade attempt according to the client status.
     fn upgrade_end(&mut self, context: RequestContext, request: UpgradeEndRequest) {
         let request_command_id = <UpgradeEndRequest as Command>::ID;
@@ -677,6 +748,7 @@ fn abort_task(task: &mut Option<AbortHandle>) {
 async fn image_block_operation(
     zcl: &Sender<zcl::Message>,
     image: &ImageTransfer,
+    progress: &watch::Sender<Progress>,
     context: RequestContext,
     block_request: ImageBlockRequest,
     range: ImageRange,
@@ -704,15 +776,20 @@ async fn image_block_operation(
         Cluster::OtaUpgrade.as_u16(),
         response,
     );
-    operation_outcome(
-        transmit_reply(zcl, context.sequence_number, request).await,
-        None,
-    )
+    let result = transmit_reply(zcl, context.sequence_number, request).await;
+    if result.is_ok() {
+        progress.send_modify(|progress| {
+            progress.record_block(range.offset, range.length, Instant::now());
+        });
+    }
+    operation_outcome(result, None)
 }
 
 async fn image_page_operation(
     zcl: Sender<zcl::Message>,
     image: ImageTransfer,
+    progress: watch::Sender<Progress>,
+    throttle: Option<Arc<Throttle>>,
     context: RequestContext,
     page_request: ImagePageRequest,
     range: ImageRange,
@@ -740,6 +817,8 @@ async fn image_page_operation(
     let operation = PageTransfer {
         zcl,
         image,
+        progress,
+        throttle,
         destination: context.destination,
         source_endpoint: context.source_endpoint,
         image_id,
@@ -793,6 +872,16 @@ fn default_response_request(
     )
 }
 
+/// Tell a throttled client to repeat its block request after `delay`.
+fn wait_for_data(delay: Duration) -> WaitForData {
+    let seconds = delay.as_secs() + u64::from(delay.subsec_nanos() > 0);
+    WaitForData::new(
+        CURRENT_TIME_IMMEDIATE,
+        u32::try_from(seconds).unwrap_or(u32::MAX),
+        0,
+    )
+}
+
 const fn query_success(image: &ImageTransfer) -> QueryResponse {
     QueryResponse::Success {
         image: image.id(),
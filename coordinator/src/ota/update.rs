@@ -3,11 +3,11 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use tokio::sync::mpsc::Sender;
-use tokio::sync::oneshot;
+use tokio::sync::{oneshot, watch};
 use zb_aps::apsde::IndividualEndpoint;
 use zb_core::FullAddress;
 
-use super::{Image, Message, UpdateResult, UpdateTimeouts};
+use super::{Image, Message, Progress, UpdateResult, UpdateTimeouts};
 use crate::Error;
 
 /// Cancellation behavior exposed by a coordinator-managed OTA update future.
@@ -18,11 +18,21 @@ pub trait CancellableOtaUpdate {
     fn cancel(self);
 }
 
+/// Progress reporting exposed by a coordinator-managed OTA update future.
+pub trait ObservableOtaUpdate {
+    /// Return a receiver that observes the data served to the client.
+    ///
+    /// The receiver is marked changed after every served block and after a replacement image is
+    /// offered. It keeps the final snapshot after the update completes.
+    fn progress(&self) -> watch::Receiver<Progress>;
+}
+
 /// Cancellation-aware future for one coordinator-managed OTA update.
 #[must_use = "dropping this future cancels the OTA update"]
 pub struct Update {
     future: Pin<Box<dyn Future<Output = Result<(), Error>> + Send>>,
     cancellation: Option<oneshot::Sender<()>>,
+    progress: watch::Receiver<Progress>,
 }
 
 impl Update {
@@ -37,6 +47,7 @@ impl Update {
     ) -> Self {
         let (completion, result) = oneshot::channel::<UpdateResult>();
         let (cancellation, cancelled) = oneshot::channel();
+        let (progress_sender, progress) = watch::channel(Progress::new(image.len()));
         let future = Box::pin(async move {
             sender
                 .send(Message::Update {
@@ -47,6 +58,7 @@ impl Update {
                     timeouts,
                     cancellation: cancelled,
                     completion,
+                    progress: progress_sender,
                 })
                 .await?;
             result.await??;
@@ -56,6 +68,7 @@ impl Update {
         Self {
             future,
             cancellation: Some(cancellation),
+            progress,
         }
     }
 }
@@ -68,6 +81,12 @@ impl CancellableOtaUpdate for Update {
     }
 }
 
+impl ObservableOtaUpdate for Update {
+    fn progress(&self) -> watch::Receiver<Progress> {
+        self.progress.clone()
+    }
+}
+
 impl Future for Update {
     type Output = Result<(), Error>;
 
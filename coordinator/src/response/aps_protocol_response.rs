@@ -5,18 +5,18 @@ use std::task::{Context, Poll};
 use tokio::sync::oneshot::Receiver;
 
 use crate::Error;
-use crate::aps::TransmissionResponse;
 use crate::correlation::Cancellation;
+use crate::retry::Transmission;
 
 /// Combined APS transmission and correlated protocol response.
 ///
-/// This future first waits for the deferred APS transmission and then for the correlated protocol
-/// response. Public callers receive a [`crate::CommunicationResponse`], which additionally
+/// This future first waits for the deferred APS transmission, including any retransmissions, and
+/// then for the correlated protocol response. Public callers receive a [`crate::CommunicationResponse`], which additionally
 /// converts the raw value to the command's declared response type.
 #[must_use = "futures do nothing unless polled"]
 #[derive(Debug)]
 pub struct ApsProtocolResponse<T> {
-    transmission: Option<TransmissionResponse>,
+    transmission: Option<Transmission>,
     response: Receiver<Result<T, Error>>,
     cancellation: Cancellation,
 }
@@ -24,7 +24,7 @@ pub struct ApsProtocolResponse<T> {
 impl<T> ApsProtocolResponse<T> {
     /// Create a response from its deferred APS result and protocol correlation channel.
     pub const fn new(
-        transmission: TransmissionResponse,
+        transmission: Transmission,
         response: Receiver<Result<T, Error>>,
         cancellation: Cancellation,
     ) -> Self {
@@ -48,7 +48,7 @@ impl<T> Future for ApsProtocolResponse<T> {
                 Poll::Ready(Ok(())) => {
                     this.transmission = None;
                 }
-                Poll::Ready(Err(error)) => return Poll::Ready(Err(error.into())),
+                Poll::Ready(Err(error)) => return Poll::Ready(Err(error)),
             }
         }
 
@@ -71,7 +71,8 @@ mod tests {
 
     use tokio::sync::oneshot::channel;
 
-    use super::{ApsProtocolResponse, TransmissionResponse};
+    use super::ApsProtocolResponse;
+    use crate::aps::TransmissionResponse;
     use crate::correlation::{Cancellation, Key};
 
     const CLUSTER_ID: u16 = 2;
@@ -146,7 +147,7 @@ mod tests {
         let cancellation = Cancellation::test_new(key(), drop);
         let (aps_inbox, _aps_messages) = tokio::sync::mpsc::channel(1);
         ApsProtocolResponse::new(
-            TransmissionResponse::test_new(transmission, APS_COUNTER, aps_inbox.downgrade()),
+            TransmissionResponse::test_new(transmission, APS_COUNTER, aps_inbox.downgrade()).into(),
             protocol,
             cancellation,
         )
@@ -0,0 +1,15 @@
+//! Automatic retries of correlated ZCL and ZDP requests.
+//!
+//! When the APS transmission of a request that expects a ZCL or ZDP response fails with a
+//! transient error, the owning protocol actor retransmits the same frame, with the same
+//! transaction sequence and correlation entry, according to a [`RetryPolicy`]. Because the
+//! correlation entry stays registered across attempts, response timeouts, cancellation, and
+//! quarantine apply to the exchange as a whole.
+
+pub use self::policy::RetryPolicy;
+pub(crate) use self::retrier::Retrier;
+pub(crate) use self::transmission::Transmission;
+
+mod policy;
+mod retrier;
+mod transmission;
@@ -0,0 +1,152 @@
+use std::num::NonZeroU8;
+use std::time::Duration;
+
+use zb_hw::TransmissionError;
+
+const DEFAULT_ATTEMPTS: NonZeroU8 = NonZeroU8::new(3).expect("default attempts are non-zero");
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(4);
+
+/// Policy for retransmitting correlated requests whose APS transmission failed.
+///
+/// A request is retried after APS timeouts, missing routes, and unsuccessful data confirmations.
+/// The delay before each retry starts at the initial backoff and doubles up to the maximum
+/// backoff. If route discovery is enabled, a route request is broadcast before each retry.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RetryPolicy {
+    attempts: NonZeroU8,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    route_discovery_radius: Option<u8>,
+}
+
+impl RetryPolicy {
+    /// Create the default policy of three attempts with a backoff from 500 ms to 4 s.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            attempts: DEFAULT_ATTEMPTS,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            route_discovery_radius: None,
+        }
+    }
+
+    /// Create a policy that transmits every request exactly once.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self::new().with_attempts(NonZeroU8::MIN)
+    }
+
+    /// Set the total number of transmission attempts, including the first one.
+    #[must_use]
+    pub const fn with_attempts(mut self, attempts: NonZeroU8) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Set the delay before the first retry and the upper bound of the doubling delay.
+    #[must_use]
+    pub const fn with_backoff(mut self, initial: Duration, maximum: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = maximum;
+        self
+    }
+
+    /// Broadcast a route request with the given radius before each retry.
+    #[must_use]
+    pub const fn with_route_discovery(mut self, radius: u8) -> Self {
+        self.route_discovery_radius = Some(radius);
+        self
+    }
+
+    /// Return the total number of transmission attempts.
+    #[must_use]
+    pub const fn attempts(&self) -> NonZeroU8 {
+        self.attempts
+    }
+
+    /// Return the delay before the first retry.
+    #[must_use]
+    pub const fn initial_backoff(&self) -> Duration {
+        self.initial_backoff
+    }
+
+    /// Return the upper bound of the delay between two attempts.
+    #[must_use]
+    pub const fn max_backoff(&self) -> Duration {
+        self.max_backoff
+    }
+
+    /// Return the route request radius, if route discovery is enabled.
+    #[must_use]
+    pub const fn route_discovery_radius(&self) -> Option<u8> {
+        self.route_discovery_radius
+    }
+
+    /// Return the delay before the given retry, counting from one.
+    #[must_use]
+    pub fn backoff(&self, retry: u8) -> Duration {
+        let factor = 1_u32
+            .checked_shl(u32::from(retry.saturating_sub(1)))
+            .unwrap_or(u32::MAX);
+
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Return whether a failed transmission may succeed when retried.
+    #[must_use]
+    pub const fn is_retryable(error: &zb_hw::Error) -> bool {
+        matches!(
+            error,
+            zb_hw::Error::Transmission(
+                TransmissionError::Timeout
+                    | TransmissionError::NoRoute
+                    | TransmissionError::Confirmation(_)
+            )
+        )
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use zb_hw::TransmissionError;
+
+    use super::RetryPolicy;
+
+    const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+    const MAX_BACKOFF: Duration = Duration::from_millis(350);
+
+    #[test]
+    fn doubles_the_backoff_up_to_the_maximum() {
+        let policy = RetryPolicy::new().with_backoff(INITIAL_BACKOFF, MAX_BACKOFF);
+
+        assert_eq!(policy.backoff(1), INITIAL_BACKOFF);
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), MAX_BACKOFF);
+        assert_eq!(policy.backoff(u8::MAX), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn retries_only_transient_transmission_failures() {
+        assert!(RetryPolicy::is_retryable(
+            &TransmissionError::NoRoute.into()
+        ));
+        assert!(RetryPolicy::is_retryable(
+            &TransmissionError::Timeout.into()
+        ));
+        assert!(!RetryPolicy::is_retryable(
+            &TransmissionError::Rejected.into()
+        ));
+    }
+}
@@ -0,0 +1,152 @@
+use std::future::{Future, poll_fn};
+use std::pin::pin;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::Poll;
+
+use log::{debug, warn};
+use tokio::spawn;
+use tokio::sync::oneshot::{Sender, channel};
+use tokio::time::sleep;
+use zb_hw::NcpHandle;
+
+use super::{RetryPolicy, Transmission};
+use crate::Error;
+use crate::aps::TransmissionResponse;
+
+/// Applies the shared [`RetryPolicy`] to the transmissions of the protocol actors.
+#[derive(Clone, Debug)]
+pub struct Retrier {
+    policy: Arc<Mutex<RetryPolicy>>,
+    ncp: Option<NcpHandle>,
+}
+
+impl Retrier {
+    /// Create a retrier that uses the NCP for route discovery between attempts.
+    pub fn new(policy: RetryPolicy, ncp: NcpHandle) -> Self {
+        Self {
+            policy: Arc::new(Mutex::new(policy)),
+            ncp: Some(ncp),
+        }
+    }
+
+    /// Create a retrier that transmits every request exactly once.
+    #[cfg(test)]
+    pub fn disabled() -> Self {
+        Self {
+            policy: Arc::new(Mutex::new(RetryPolicy::none())),
+            ncp: None,
+        }
+    }
+
+    /// Return the current policy.
+    pub fn policy(&self) -> RetryPolicy {
+        *self.policy.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Replace the policy for subsequently submitted requests.
+    pub fn set_policy(&self, policy: RetryPolicy) {
+        *self.policy.lock().unwrap_or_else(PoisonError::into_inner) = policy;
+    }
+
+    /// Supervise the first transmission of a correlated request.
+    ///
+    /// After a retryable failure, `retransmit` asks the owning actor to transmit the request
+    /// again. The actor returns `None` if the correlation entry is no longer pending, in which
+    /// case the outcome of the exchange is left to the protocol response channel.
+    pub fn supervise<F, R>(&self, first: TransmissionResponse, retransmit: F) -> Transmission
+    where
+        F: FnMut() -> R + Send + 'static,
+        R: Future<Output = Option<Result<TransmissionResponse, Error>>> + Send + 'static,
+    {
+        let policy = self.policy();
+
+        if policy.attempts().get() == 1 {
+            return first.into();
+        }
+
+        let (result, receiver) = channel();
+        spawn(supervise(
+            policy,
+            self.ncp.clone(),
+            first,
+            retransmit,
+            result,
+        ));
+        Transmission::Retried(receiver)
+    }
+}
+
+async fn supervise<F, R>(
+    policy: RetryPolicy,
+    ncp: Option<NcpHandle>,
+    mut transmission: TransmissionResponse,
+    mut retransmit: F,
+    mut result: Sender<Result<(), Error>>,
+) where
+    F: FnMut() -> R,
+    R: Future<Output = Option<Result<TransmissionResponse, Error>>>,
+{
+    let attempts = policy.attempts().get();
+    let mut retry = 0;
+
+    loop {
+        let Some(outcome) = until_closed(&mut result, transmission).await else {
+            return;
+        };
+
+        let error = match outcome {
+            Ok(()) => break,
+            Err(error) => error,
+        };
+
+        retry += 1;
+
+        if retry >= attempts || !RetryPolicy::is_retryable(&error) {
+            result.send(Err(error.into())).unwrap_or_else(drop);
+            return;
+        }
+
+        debug!("Retrying transmission after failure ({retry}/{attempts}): {error}");
+
+        if until_closed(&mut result, sleep(policy.backoff(retry)))
+            .await
+            .is_none()
+        {
+            return;
+        }
+
+        if let (Some(radius), Some(ncp)) = (policy.route_discovery_radius(), &ncp) {
+            ncp.route_request(radius).await.unwrap_or_else(|error| {
+                warn!("Failed to discover route before retransmission: {error}");
+            });
+        }
+
+        match retransmit().await {
+            Some(Ok(next)) => transmission = next,
+            Some(Err(error)) => {
+                result.send(Err(error)).unwrap_or_else(drop);
+                return;
+            }
+            None => break,
+        }
+    }
+
+    result.send(Ok(())).unwrap_or_else(drop);
+}
+
+/// Drive `future` until it completes or the receiver of `result` is dropped.
+async fn until_closed<T, F>(result: &mut Sender<T>, future: F) -> Option<F::Output>
+where
+    F: Future,
+{
+    let mut future = pin!(future);
+
+    poll_fn(|context| {
+        if let Poll::Ready(output) = future.as_mut().poll(context) {
+            return Poll::Ready(Some(output));
+        }
+
+        result.poll_closed(context).map(|()| None)
+    })
+    .await
+}
@@ -0,0 +1,37 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::sync::oneshot::Receiver;
+
+use crate::Error;
+use crate::aps::TransmissionResponse;
+
+/// Deferred APS result of a correlated request, possibly spanning several attempts.
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub enum Transmission {
+    /// A single APS transmission.
+    Once(TransmissionResponse),
+    /// The final result reported by the task retrying the transmission.
+    Retried(Receiver<Result<(), Error>>),
+}
+
+impl From<TransmissionResponse> for Transmission {
+    fn from(transmission: TransmissionResponse) -> Self {
+        Self::Once(transmission)
+    }
+}
+
+impl Future for Transmission {
+    type Output = Result<(), Error>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.get_mut() {
+            Self::Once(transmission) => Pin::new(transmission).poll(context).map_err(Into::into),
+            Self::Retried(result) => Pin::new(result)
+                .poll(context)
+                .map(|result| result.map_err(Into::into).and_then(|result| result)),
+        }
+    }
+}
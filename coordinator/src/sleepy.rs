@@ -0,0 +1,13 @@
+//! Send-when-awake queue for sleepy end devices.
+//!
+//! Sleepy end devices turn their receiver off between data polls, so a request sent at an
+//! arbitrary time usually expires in the parent's indirect transmission queue. Requests for devices
+//! marked as sleepy are therefore held by the coordinator and transmitted in order once the device
+//! is heard from. A Poll Control Check-in is answered with a Check-in Response that keeps the
+//! device in fast poll mode while queued requests are transmitted, followed by Fast Poll Stop.
+
+pub use self::check_in::CheckIn;
+pub use self::queue::Queue;
+
+mod check_in;
+mod queue;
@@ -0,0 +1,74 @@
+use log::warn;
+use zb_aps::apsde::{
+    IndicationMetadata, IndividualEndpoint, NetworkDestination, ReceivedDestination, Source,
+};
+use zb_core::short_id;
+
+/// A received Poll Control Check-in and the addressing needed to answer it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CheckIn {
+    device: short_id::Device,
+    destination: NetworkDestination,
+    source_endpoint: IndividualEndpoint,
+    profile_id: u16,
+    sequence_number: u8,
+}
+
+impl CheckIn {
+    /// Extract the reply addressing from the metadata of a received Check-in.
+    ///
+    /// Returns `None` if the Check-in did not arrive from an individual NWK endpoint or was not
+    /// addressed to an individual local endpoint.
+    pub fn new<T, K>(metadata: &IndicationMetadata<T, K>, sequence_number: u8) -> Option<Self> {
+        let Source::Network { address, endpoint } = metadata.source() else {
+            warn!(
+                "Ignoring Check-in from non-network source {:?}",
+                metadata.source()
+            );
+            return None;
+        };
+        let device = short_id::Device::new(address.as_u16())?;
+        let source_endpoint = match metadata.destination() {
+            ReceivedDestination::Network { endpoint, .. }
+            | ReceivedDestination::Extended { endpoint, .. } => endpoint,
+            ReceivedDestination::Broadcast { endpoint, .. } => IndividualEndpoint::new(endpoint)?,
+            ReceivedDestination::Group(_) | ReceivedDestination::ExtendedWithoutEndpoint(_) => {
+                warn!("Ignoring Check-in without an individual local endpoint from {device}");
+                return None;
+            }
+        };
+
+        Some(Self {
+            device,
+            destination: NetworkDestination::new(address, endpoint),
+            source_endpoint,
+            profile_id: metadata.profile_id(),
+            sequence_number,
+        })
+    }
+
+    /// Return the device that checked in.
+    pub const fn device(&self) -> short_id::Device {
+        self.device
+    }
+
+    /// Return the Poll Control server endpoint of the device.
+    pub const fn destination(&self) -> NetworkDestination {
+        self.destination
+    }
+
+    /// Return the local endpoint the Check-in was addressed to.
+    pub const fn source_endpoint(&self) -> IndividualEndpoint {
+        self.source_endpoint
+    }
+
+    /// Return the profile the Check-in was sent with.
+    pub const fn profile_id(&self) -> u16 {
+        self.profile_id
+    }
+
+    /// Return the ZCL sequence number of the Check-in.
+    pub const fn sequence_number(&self) -> u8 {
+        self.sequence_number
+    }
+}
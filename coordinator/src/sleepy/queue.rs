@@ -0,0 +1,535 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use bytes::Bytes;
+use log::{debug, trace, warn};
+use tokio::spawn;
+use tokio::sync::mpsc::Sender;
+use tokio::sync::oneshot;
+use zb_aps::TxOptions;
+use zb_aps::apsde::{DataRequest, RequestDestination};
+use zb_core::{Cluster, short_id};
+use zb_zcl::UnsequencedFrame;
+use zb_zcl::poll_control::{CheckInResponse, FastPollStop};
+
+use super::CheckIn;
+use crate::api::Zcl;
+use crate::{Error, zcl};
+
+type Request = DataRequest<UnsequencedFrame<Bytes>>;
+
+/// Fast poll timeout requesting the device's own `FastPollTimeout` attribute.
+const DEVICE_FAST_POLL_TIMEOUT: u16 = 0;
+
+/// Requests held for sleepy devices, shared by the mux, which wakes it, and the coordinator.
+#[derive(Clone, Debug)]
+pub struct Queue {
+    zcl: Sender<zcl::Message>,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    sleepy: BTreeSet<short_id::Device>,
+    flushing: BTreeSet<short_id::Device>,
+    pending: BTreeMap<short_id::Device, VecDeque<Pending>>,
+    /// Check-ins answered with fast polling while another task was flushing the device's queue.
+    fast_polling: BTreeMap<short_id::Device, CheckIn>,
+}
+
+#[derive(Debug)]
+struct Pending {
+    request: Request,
+    response: oneshot::Sender<Result<(), Error>>,
+}
+
+impl Queue {
+    /// Create an empty queue transmitting through the given ZCL actor.
+    pub fn new(zcl: Sender<zcl::Message>) -> Self {
+        Self {
+            zcl,
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    /// Mark or unmark a device as sleepy.
+    ///
+    /// Requests still queued for a device that is no longer sleepy are transmitted immediately.
+    pub fn set_sleepy(&self, device: short_id::Device, sleepy: bool) {
+        if sleepy {
+            self.lock().sleepy.insert(device);
+        } else {
+            self.lock().sleepy.remove(&device);
+            self.awake(device);
+        }
+    }
+
+    /// Return whether a device is marked as sleepy.
+    pub fn is_sleepy(&self, device: short_id::Device) -> bool {
+        self.lock().sleepy.contains(&device)
+    }
+
+    /// Return the number of requests queued for a device, excluding withdrawn requests.
+    pub fn queued(&self, device: short_id::Device) -> usize {
+        self.lock().pending.get(&device).map_or(0, |pending| {
+            pending
+                .iter()
+                .filter(|pending| !pending.response.is_closed())
+                .count()
+        })
+    }
+
+    /// Queue a request addressed to the NWK address of a sleepy device.
+    ///
+    /// The returned receiver completes once the request has been transmitted. Requests to other
+    /// destinations are handed back unchanged.
+    pub fn enqueue(
+        &self,
+        request: Request,
+    ) -> Result<oneshot::Receiver<Result<(), Error>>, Request> {
+        let RequestDestination::Network { address, .. } = request.destination() else {
+            return Err(request);
+        };
+        let Some(device) = short_id::Device::new(address.as_u16()) else {
+            return Err(request);
+        };
+        let mut state = self.lock();
+
+        if !state.sleepy.contains(&device) {
+            return Err(request);
+        }
+
+        let (response, result) = oneshot::channel();
+        state
+            .pending
+            .entry(device)
+            .or_default()
+            .push_back(Pending { request, response });
+        drop(state);
+        trace!("Queued request for sleepy device {device}");
+        Ok(result)
+    }
+
+    /// Transmit the requests queued for a device that has just been heard from.
+    pub fn awake(&self, device: short_id::Device) {
+        if self.claim(device) {
+            let queue = self.clone();
+            spawn(async move { queue.flush(device, None).await });
+        }
+    }
+
+    /// Answer a Poll Control Check-in of a sleepy device and transmit its queued requests.
+    ///
+    /// The device is asked to fast poll only while requests are queued for it, and is released with
+    /// Fast Poll Stop once they have been transmitted, whether by this Check-in or by a flush that
+    /// was already running. Check-ins of devices that are not marked as sleepy are left to the
+    /// application.
+    pub fn checked_in(&self, check_in: CheckIn) {
+        let device = check_in.device();
+
+        if !self.is_sleepy(device) {
+            trace!("Leaving the Check-in of {device} to the application");
+            return;
+        }
+
+        let fast_poll = self.queued(device) > 0;
+        let claimed = fast_poll && self.claim(device);
+        let queue = self.clone();
+
+        spawn(async move {
+            queue.answer(check_in, fast_poll).await;
+
+            if claimed {
+                queue.flush(device, Some(check_in)).await;
+            } else if fast_poll && !queue.defer_fast_poll_stop(check_in) {
+                queue.stop_fast_poll(check_in).await;
+            }
+        });
+    }
+
+    /// Forget a device that left the network and fail its queued requests.
+    pub fn left(&self, device: short_id::Device) {
+        let pending = {
+            let mut state = self.lock();
+            state.sleepy.remove(&device);
+            state.fast_polling.remove(&device);
+            state.pending.remove(&device).unwrap_or_default()
+        };
+
+        for Pending { response, .. } in pending {
+            response
+                .send(Err(Error::SleepyDeviceLeft(device)))
+                .unwrap_or_else(|_| trace!("Queued request for {device} was already dropped"));
+        }
+    }
+
+    /// Mark the queue of a device as being flushed, unless it is empty or already being flushed.
+    fn claim(&self, device: short_id::Device) -> bool {
+        let mut state = self.lock();
+
+        if state.pending.get(&device).is_none_or(VecDeque::is_empty) {
+            return false;
+        }
+
+        state.flushing.insert(device)
+    }
+
+    /// Leave the Fast Poll Stop of `check_in` to the task flushing its device.
+    ///
+    /// Returns `false` if no task is flushing the device's queue anymore.
+    fn defer_fast_poll_stop(&self, check_in: CheckIn) -> bool {
+        let mut state = self.lock();
+
+        if !state.flushing.contains(&check_in.device()) {
+            return false;
+        }
+
+        state.fast_polling.insert(check_in.device(), check_in);
+        true
+    }
+
+    /// Transmit queued requests in order until the queue of the device is empty.
+    ///
+    /// Afterwards, Fast Poll Stop is sent for `check_in` or for a Check-in deferred to this flush.
+    async fn flush(&self, device: short_id::Device, check_in: Option<CheckIn>) {
+        let fast_polling = loop {
+            let Pending { request, response } = match self.next(device) {
+                Ok(pending) => pending,
+                Err(fast_polling) => break fast_polling,
+            };
+
+            if response.is_closed() {
+                debug!("Skipping cancelled request for sleepy device {device}");
+                continue;
+            }
+
+            response
+                .send(self.zcl.transmit(request).await)
+                .unwrap_or_else(|_| trace!("Queued request for {device} was dropped in flight"));
+        };
+
+        if let Some(check_in) = check_in.or(fast_polling) {
+            self.stop_fast_poll(check_in).await;
+        }
+    }
+
+    /// Pop the next queued request.
+    ///
+    /// Once the queue is empty, the claim is released and any Check-in deferred to the flush is
+    /// returned as the error.
+    fn next(&self, device: short_id::Device) -> Result<Pending, Option<CheckIn>> {
+        let mut state = self.lock();
+
+        if let Some(next) = state.pending.get_mut(&device).and_then(VecDeque::pop_front) {
+            return Ok(next);
+        }
+
+        state.pending.remove(&device);
+        state.flushing.remove(&device);
+        Err(state.fast_polling.remove(&device))
+    }
+
+    async fn answer(&self, check_in: CheckIn, fast_poll: bool) {
+        let request = request(
+            check_in,
+            UnsequencedFrame::from_command(CheckInResponse::new(
+                fast_poll,
+                DEVICE_FAST_POLL_TIMEOUT,
+            )),
+        );
+        let (response, result) = oneshot::channel();
+
+        if let Err(error) = self
+            .zcl
+            .send(zcl::Message::Reply {
+                sequence_number: check_in.sequence_number(),
+                request,
+                response,
+            })
+            .await
+        {
+            warn!("Failed to queue Check-in Response: {error}");
+            return;
+        }
+
+        match result.await {
+            Ok(Ok(transmission)) => transmission.await.unwrap_or_else(|error| {
+                warn!("Failed to transmit Check-in Response: {error}");
+            }),
+            Ok(Err(error)) => warn!("Failed to queue Check-in Response: {error}"),
+            Err(error) => warn!("Failed to receive Check-in Response result: {error}"),
+        }
+    }
+
+    async fn stop_fast_poll(&self, check_in: CheckIn) {
+        let request = request(check_in, UnsequencedFrame::from_command(FastPollStop));
+
+        self.zcl.transmit(request).await.unwrap_or_else(|error| {
+            warn!("Failed to transmit Fast Poll Stop: {error}");
+        });
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn request(check_in: CheckIn, frame: UnsequencedFrame<Bytes>) -> Request {
+    DataRequest::new(
+        check_in.destination().into(),
+        check_in.profile_id(),
+        Cluster::PollControl.as_u16(),
+        check_in.source_endpoint(),
+        frame.with_disable_default_response(true),
+    )
+    .with_tx_options(TxOptions::ACKNOWLEDGED_TRANSMISSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use tokio::runtime::Builder;
+    use tokio::sync::mpsc::{Receiver, channel};
+    use tokio::sync::oneshot;
+    use zb_aps::apsde::{
+        DataRequest, IndicationMetadata, IndicationStatus, IndividualEndpoint, NetworkAddress,
+        NetworkDestination, ReceivedDestination, Security, Source,
+    };
+    use zb_core::endpoint::Application;
+    use zb_core::{Endpoint, short_id};
+    use zb_zcl::on_off::On;
+    use zb_zcl::{Command, UnsequencedFrame};
+
+    use super::{CheckIn, Queue, Request};
+    use crate::aps::TransmissionResponse;
+    use crate::{Error, zcl};
+
+    const DEVICE: u16 = 0x1234;
+    const OTHER_DEVICE: u16 = 0x5678;
+    const SEQUENCE_NUMBER: u8 = 0x2A;
+    const APS_COUNTER: u8 = 1;
+    const CHANNEL_SIZE: usize = 4;
+
+    #[test]
+    fn holds_requests_until_the_device_is_awake() {
+        run(async {
+            let (zcl, mut messages) = channel(CHANNEL_SIZE);
+            let queue = Queue::new(zcl);
+            queue.set_sleepy(device(), true);
+
+            assert!(queue.enqueue(on(OTHER_DEVICE)).is_err());
+            let result = queue
+                .enqueue(on(DEVICE))
+                .expect("request to a sleepy device must be queued");
+            assert_eq!(queue.queued(device()), 1);
+            assert!(messages.try_recv().is_err());
+
+            queue.awake(device());
+            let request = transmitted(&mut messages).await;
+            assert_eq!(request.asdu().header().command_id(), <On as Command>::ID);
+            assert!(matches!(result.await, Ok(Ok(()))));
+            assert_eq!(queue.queued(device()), 0);
+        });
+    }
+
+    #[test]
+    fn answers_check_in_with_fast_poll_and_stops_after_flushing() {
+        run(async {
+            let (zcl, mut messages) = channel(CHANNEL_SIZE);
+            let queue = Queue::new(zcl);
+            queue.set_sleepy(device(), true);
+            let result = queue
+                .enqueue(on(DEVICE))
+                .expect("request to a sleepy device must be queued");
+
+            queue.checked_in(check_in());
+            let Some(zcl::Message::Reply {
+                sequence_number,
+                request,
+                response,
+            }) = messages.recv().await
+            else {
+                panic!("expected Check-in Response reply");
+            };
+            assert_eq!(sequence_number, SEQUENCE_NUMBER);
+            assert_eq!(request.asdu().header().command_id(), 0x00);
+            assert_eq!(request.asdu().payload().as_ref(), [0x01, 0x00, 0x00]);
+            complete(response);
+
+            let request = transmitted(&mut messages).await;
+            assert_eq!(request.asdu().header().command_id(), <On as Command>::ID);
+            assert!(matches!(result.await, Ok(Ok(()))));
+
+            let request = transmitted(&mut messages).await;
+            assert_eq!(request.asdu().header().command_id(), 0x01);
+            assert!(request.asdu().payload().is_empty());
+        });
+    }
+
+    #[test]
+    fn stops_fast_polling_after_a_running_flush_when_the_claim_fails() {
+        run(async {
+            let (zcl, mut messages) = channel(CHANNEL_SIZE);
+            let queue = Queue::new(zcl);
+            queue.set_sleepy(device(), true);
+            let result = queue
+                .enqueue(on(DEVICE))
+                .expect("request to a sleepy device must be queued");
+
+            queue.awake(device());
+            queue.checked_in(check_in());
+            let Some(zcl::Message::Transmit {
+                request,
+                response: transmission,
+            }) = messages.recv().await
+            else {
+                panic!("expected the queued request from the running flush");
+            };
+            assert_eq!(request.asdu().header().command_id(), <On as Command>::ID);
+            let Some(zcl::Message::Reply {
+                request, response, ..
+            }) = messages.recv().await
+            else {
+                panic!("expected Check-in Response reply");
+            };
+            assert_eq!(request.asdu().payload().as_ref(), [0x01, 0x00, 0x00]);
+            complete(response);
+            complete(transmission);
+            assert!(matches!(result.await, Ok(Ok(()))));
+
+            let request = transmitted(&mut messages).await;
+            assert_eq!(request.asdu().header().command_id(), 0x01);
+            assert!(messages.try_recv().is_err());
+        });
+    }
+
+    #[test]
+    fn leaves_check_ins_of_other_devices_to_the_application() {
+        run(async {
+            let (zcl, mut messages) = channel(CHANNEL_SIZE);
+            let queue = Queue::new(zcl);
+
+            queue.checked_in(check_in());
+            tokio::task::yield_now().await;
+
+            assert!(messages.try_recv().is_err());
+        });
+    }
+
+    #[test]
+    fn does_not_count_withdrawn_requests() {
+        run(async {
+            let (zcl, _messages) = channel(CHANNEL_SIZE);
+            let queue = Queue::new(zcl);
+            queue.set_sleepy(device(), true);
+            let withdrawn = queue
+                .enqueue(on(DEVICE))
+                .expect("request to a sleepy device must be queued");
+            let _result = queue
+                .enqueue(on(DEVICE))
+                .expect("request to a sleepy device must be queued");
+
+            drop(withdrawn);
+
+            assert_eq!(queue.queued(device()), 1);
+        });
+    }
+
+    #[test]
+    fn fails_queued_requests_when_the_device_leaves() {
+        run(async {
+            let (zcl, _messages) = channel(CHANNEL_SIZE);
+            let queue = Queue::new(zcl);
+            queue.set_sleepy(device(), true);
+            let result = queue
+                .enqueue(on(DEVICE))
+                .expect("request to a sleepy device must be queued");
+
+            queue.left(device());
+
+            assert!(matches!(
+                result.await,
+                Ok(Err(Error::SleepyDeviceLeft(left))) if left == device()
+            ));
+            assert!(!queue.is_sleepy(device()));
+        });
+    }
+
+    async fn transmitted(messages: &mut Receiver<zcl::Message>) -> Request {
+        let Some(zcl::Message::Transmit { request, response }) = messages.recv().await else {
+            panic!("expected ZCL transmission");
+        };
+        complete(response);
+        request
+    }
+
+    fn complete(response: oneshot::Sender<Result<TransmissionResponse, Error>>) {
+        let (completion, result) = oneshot::channel();
+        let (inbox, _messages) = channel(CHANNEL_SIZE);
+        assert!(completion.send(Ok(())).is_ok());
+        assert!(
+            response
+                .send(Ok(TransmissionResponse::test_new(
+                    result,
+                    APS_COUNTER,
+                    inbox.downgrade(),
+                )))
+                .is_ok()
+        );
+    }
+
+    fn on(device: u16) -> DataRequest<UnsequencedFrame<Bytes>> {
+        DataRequest::new(
+            NetworkDestination::new(
+                NetworkAddress::new(device).expect("test address is valid"),
+                endpoint(),
+            )
+            .into(),
+            0x0104,
+            0x0006,
+            endpoint(),
+            UnsequencedFrame::from_command(On).with_disable_default_response(true),
+        )
+    }
+
+    fn check_in() -> CheckIn {
+        let metadata: IndicationMetadata<(), ()> = IndicationMetadata::new(
+            ReceivedDestination::Network {
+                address: NetworkAddress::new(0x0000).expect("coordinator address is valid"),
+                endpoint: endpoint(),
+            },
+            Source::Network {
+                address: NetworkAddress::new(DEVICE).expect("test address is valid"),
+                endpoint: endpoint(),
+            },
+            0x0104,
+            0x0020,
+            IndicationStatus::success(),
+            Security::NetworkKey,
+            u8::MAX,
+            (),
+        );
+
+        CheckIn::new(&metadata, SEQUENCE_NUMBER).expect("check-in is individually addressed")
+    }
+
+    fn device() -> short_id::Device {
+        short_id::Device::new(DEVICE).expect("test short ID is valid")
+    }
+
+    const fn endpoint() -> IndividualEndpoint {
+        IndividualEndpoint::new(Endpoint::Application(Application::MIN))
+            .expect("application endpoint is individual")
+    }
+
+    fn run<T>(future: T)
+    where
+        T: Future<Output = ()>,
+    {
+        Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("runtime must be available")
+            .block_on(future);
+    }
+}
@@ -0,0 +1,24 @@
+//! Touchlink commissioning over inter-PAN frames.
+//!
+//! Touchlink targets, typically factory-new lights, are not part of the coordinator's network.
+//! The coordinator reaches them with inter-PAN frames on their own channel: a scan broadcasts a
+//! Scan Request on each selected channel and collects the Scan Responses of the targets in radio
+//! range, and every subsequent command repeats the scan's transaction identifier. Only one
+//! Touchlink exchange runs at a time, since the radio leaves the network channel for its duration.
+
+use std::time::Duration;
+
+use zb_hw::ChannelMask;
+
+pub(crate) use self::inter_pan::{InterPan, Session};
+pub use self::target::Target;
+
+mod inter_pan;
+mod target;
+
+/// The Touchlink primary channels 11, 15, 20, and 25.
+pub const PRIMARY_CHANNELS: ChannelMask = ChannelMask::new(0x0210_8800)
+    .expect("channels 11, 15, 20, and 25 are valid IEEE 802.15.4 channels");
+
+/// Time a scan listens for Scan Responses on each channel.
+pub(crate) const SCAN_TIME_BASE: Duration = Duration::from_millis(250);
@@ -0,0 +1,261 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use le_stream::ToLeStream;
+use log::{debug, trace, warn};
+use tokio::runtime::Handle;
+use tokio::sync::mpsc::{Receiver, Sender, channel};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+use tokio::time::{Instant, timeout_at};
+use zb_core::{Cluster as ClusterId, Profile};
+use zb_hw::{
+    Channel, ChannelMask, InterPanDestination, InterPanIndication, InterPanRequest, NcpHandle,
+};
+use zb_zcl::touchlink::{Command, ScanRequest, TouchlinkInformation, ZigbeeInformation};
+use zb_zcl::{Cluster, Command as ZclCommand, Directed, Frame, Scoped, UnsequencedFrame};
+
+use super::{SCAN_TIME_BASE, Target};
+use crate::{Error, MPSC_CHANNEL_SIZE};
+
+/// Router of received inter-PAN frames to the running Touchlink session.
+#[derive(Clone, Debug, Default)]
+pub struct InterPan {
+    sessions: Arc<AsyncMutex<()>>,
+    listener: Arc<Mutex<Option<Sender<InterPanIndication>>>>,
+}
+
+impl InterPan {
+    /// Forward a received inter-PAN frame to the running session, if any.
+    pub(crate) fn indicate(&self, indication: InterPanIndication) {
+        let listener = self
+            .listener
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+
+        match listener {
+            Some(listener) => {
+                if let Err(error) = listener.try_send(indication) {
+                    trace!("Dropping inter-PAN frame: {error}");
+                }
+            }
+            None => trace!(
+                "Dropping inter-PAN frame from {} without a Touchlink session",
+                indication.source()
+            ),
+        }
+    }
+
+    /// Wait until no other session runs and start a new one.
+    pub(crate) async fn session(&self, ncp: NcpHandle) -> Session {
+        let guard = Arc::clone(&self.sessions).lock_owned().await;
+        let (sender, indications) = channel(MPSC_CHANNEL_SIZE);
+        self.listener
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .replace(sender);
+
+        Session {
+            ncp,
+            listener: Arc::clone(&self.listener),
+            indications,
+            sequence: 0,
+            runtime: Handle::try_current().ok(),
+            guard: Some(guard),
+        }
+    }
+}
+
+/// Exclusive Touchlink exchange with the targets in radio range.
+///
+/// Call [`Session::end`] to return the radio to the network channel. A session dropped without
+/// ending, such as one of a cancelled Touchlink call, returns the radio in a spawned task, and the
+/// next session waits until it has.
+#[derive(Debug)]
+pub struct Session {
+    ncp: NcpHandle,
+    listener: Arc<Mutex<Option<Sender<InterPanIndication>>>>,
+    indications: Receiver<InterPanIndication>,
+    sequence: u8,
+    runtime: Option<Handle>,
+    guard: Option<OwnedMutexGuard<()>>,
+}
+
+impl Session {
+    /// Send a Touchlink command on `channel`.
+    pub(crate) async fn transmit<T>(
+        &mut self,
+        channel: Channel,
+        destination: InterPanDestination,
+        command: T,
+    ) -> Result<(), Error>
+    where
+        T: ZclCommand + Directed + Scoped + ToLeStream,
+    {
+        let asdu = UnsequencedFrame::from_command(command)
+            .into_frame(self.sequence)
+            .to_le_stream()
+            .collect();
+        self.sequence = self.sequence.wrapping_add(1);
+
+        self.ncp
+            .transmit_inter_pan(InterPanRequest::new(
+                channel,
+                destination,
+                Profile::TouchLink.as_u16(),
+                ClusterId::TouchlinkCommissioning.as_u16(),
+                asdu,
+            ))
+            .await?;
+        Ok(())
+    }
+
+    /// Broadcast a Scan Request on each of `channels` and collect the targets that answered.
+    ///
+    /// Targets answering on several channels are reported once, on the channel with the best link
+    /// quality. The targets are sorted by descending link quality.
+    pub(crate) async fn scan(
+        &mut self,
+        channels: ChannelMask,
+        transaction_id: u32,
+    ) -> Result<Vec<Target>, Error> {
+        let request = ScanRequest::new(
+            transaction_id,
+            ZigbeeInformation::RX_ON_WHEN_IDLE,
+            TouchlinkInformation::INITIATOR | TouchlinkInformation::ADDRESS_ASSIGNMENT,
+        );
+        let mut targets = BTreeMap::new();
+
+        for channel in channels.channels() {
+            self.transmit(channel, InterPanDestination::Broadcast, request)
+                .await?;
+
+            let deadline = Instant::now() + SCAN_TIME_BASE;
+            while let Ok(Some(indication)) = timeout_at(deadline, self.indications.recv()).await {
+                let Some(target) = scan_response(channel, transaction_id, &indication) else {
+                    continue;
+                };
+                targets
+                    .entry(target.ieee_address())
+                    .and_modify(|known: &mut Target| {
+                        if target.link_quality() > known.link_quality() {
+                            *known = target;
+                        }
+                    })
+                    .or_insert(target);
+            }
+        }
+
+        let mut targets: Vec<_> = targets.into_values().collect();
+        targets.sort_by_key(|target| std::cmp::Reverse(target.link_quality()));
+        Ok(targets)
+    }
+
+    /// Return the radio to the network channel and end the session.
+    pub(crate) async fn end(mut self) -> Result<(), Error> {
+        let result = self.ncp.end_inter_pan().await;
+        self.guard.take();
+        result?;
+        Ok(())
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        self.listener
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+
+        let Some(guard) = self.guard.take() else {
+            return;
+        };
+        let Some(runtime) = &self.runtime else {
+            debug!("Failed to end the Touchlink session: runtime unavailable");
+            return;
+        };
+        let ncp = self.ncp.clone();
+        runtime.spawn(async move {
+            if let Err(error) = ncp.end_inter_pan().await {
+                warn!("Failed to return the radio to the network channel: {error}");
+            }
+            drop(guard);
+        });
+    }
+}
+
+/// Return the target of a Scan Response to the scan with `transaction_id`.
+fn scan_response(
+    channel: Channel,
+    transaction_id: u32,
+    indication: &InterPanIndication,
+) -> Option<Target> {
+    if indication.profile_id() != Profile::TouchLink.as_u16()
+        || indication.cluster_id() != ClusterId::TouchlinkCommissioning.as_u16()
+    {
+        return None;
+    }
+
+    let frame = Frame::parse(indication.cluster_id(), indication.asdu().iter().copied())
+        .inspect_err(|error| trace!("Ignoring malformed Touchlink frame: {error}"))
+        .ok()?;
+    let Cluster::Touchlink(Command::ScanResponse(response)) = frame.into_payload() else {
+        return None;
+    };
+
+    (response.transaction_id() == transaction_id).then(|| {
+        Target::new(
+            indication.source(),
+            channel,
+            indication.source_pan_id(),
+            indication.link_quality(),
+            *response,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use tokio::runtime::Builder;
+    use tokio::time::timeout;
+    use zb_core::IeeeAddress;
+    use zb_hw::sim::VirtualNetwork;
+    use zb_hw::{Channel, ChannelMask, Driver};
+
+    use super::InterPan;
+    use crate::touchlink::SCAN_TIME_BASE;
+
+    const CAPACITY: NonZeroUsize = NonZeroUsize::new(16).expect("capacity is non-zero");
+
+    #[test]
+    fn returns_the_radio_when_a_scan_is_cancelled() {
+        Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("runtime must be available")
+            .block_on(async {
+                let (ncp, _events) =
+                    VirtualNetwork::new(IeeeAddress::default(), 0x1A62).start(CAPACITY);
+                let (ncp, actor) = ncp.into_actor(CAPACITY);
+                let actor = tokio::spawn(actor);
+                let inter_pan = InterPan::default();
+                let channels = [Channel::MIN, Channel::new(15).expect("channel 15 is valid")]
+                    .into_iter()
+                    .collect::<ChannelMask>();
+
+                let mut session = inter_pan.session(ncp.clone()).await;
+                assert!(
+                    timeout(SCAN_TIME_BASE / 2, session.scan(channels, 1))
+                        .await
+                        .is_err()
+                );
+                drop(session);
+                drop(ncp);
+
+                let ncp = actor.await.expect("actor must finish");
+                assert_eq!(ncp.inter_pan_channel(), None);
+            });
+    }
+}
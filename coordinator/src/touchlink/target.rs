@@ -0,0 +1,77 @@
+use zb_core::IeeeAddress;
+use zb_hw::Channel;
+use zb_zcl::touchlink::{ScanResponse, TouchlinkInformation};
+
+/// A device that answered a Touchlink scan.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Target {
+    ieee_address: IeeeAddress,
+    channel: Channel,
+    pan_id: u16,
+    link_quality: u8,
+    response: ScanResponse,
+}
+
+impl Target {
+    /// Create a target from a Scan Response received on `channel`.
+    #[must_use]
+    pub const fn new(
+        ieee_address: IeeeAddress,
+        channel: Channel,
+        pan_id: u16,
+        link_quality: u8,
+        response: ScanResponse,
+    ) -> Self {
+        Self {
+            ieee_address,
+            channel,
+            pan_id,
+            link_quality,
+            response,
+        }
+    }
+
+    /// Return the IEEE address of the target.
+    #[must_use]
+    pub const fn ieee_address(self) -> IeeeAddress {
+        self.ieee_address
+    }
+
+    /// Return the channel the target answered on.
+    #[must_use]
+    pub const fn channel(self) -> Channel {
+        self.channel
+    }
+
+    /// Return the PAN ID the target answered from.
+    #[must_use]
+    pub const fn pan_id(self) -> u16 {
+        self.pan_id
+    }
+
+    /// Return the link quality of the Scan Response.
+    #[must_use]
+    pub const fn link_quality(self) -> u8 {
+        self.link_quality
+    }
+
+    /// Return the inter-PAN transaction identifier of the scan.
+    #[must_use]
+    pub const fn transaction_id(self) -> u32 {
+        self.response.transaction_id()
+    }
+
+    /// Return whether the target has not joined a network since its last reset.
+    #[must_use]
+    pub const fn is_factory_new(self) -> bool {
+        self.response
+            .touchlink_information()
+            .contains(TouchlinkInformation::FACTORY_NEW)
+    }
+
+    /// Return the Scan Response of the target.
+    #[must_use]
+    pub const fn response(self) -> ScanResponse {
+        self.response
+    }
+}
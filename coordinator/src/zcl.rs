@@ -1,5 +1,7 @@
 //! Transceiver to send and receive ZCL messages.
 
+use std::time::Duration;
+
 use bytes::Bytes;
 use le_stream::ToLeStream;
 use log::{debug, trace, warn};
@@ -7,8 +9,11 @@ use tokio::runtime::Handle;
 use tokio::spawn;
 use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::mpsc::{Receiver, Sender, WeakSender};
+use tokio::sync::oneshot;
 use tokio::time::sleep;
 use zb_aps::apsde::{DataIndication, DataRequest};
+use zb_core::short_id;
+use zb_zcl::alarms::Command as AlarmsCommand;
 use zb_zcl::{Cluster, Frame, UnsequencedFrame};
 
 pub use self::message::Message;
@@ -17,12 +22,11 @@ pub use self::subscription::{
     SubscriptionReceiver,
 };
 use crate::aps::{Aps, TransmissionResponse};
-use crate::correlation::{
-    Cancellation, Key, PROTOCOL_QUARANTINE_TIMEOUT, PROTOCOL_RESPONSE_TIMEOUT, Registry, Token,
-};
-use crate::event::EventSink;
+use crate::correlation::{Cancellation, Key, Registry, Timeouts, Token};
+use crate::event::{AlarmEvent, AlarmKey, EventSink};
 use crate::response::ApsProtocolResponse;
-use crate::{Error, Event, MPSC_CHANNEL_SIZE};
+use crate::retry::Retrier;
+use crate::{CoordinatorConfig, Error, Event};
 
 mod message;
 mod subscription;
@@ -35,38 +39,51 @@ pub struct Transceiver {
     subscriptions: Vec<Subscription>,
     responses: Registry<Cluster>,
     inbox: WeakSender<Message>,
+    retrier: Retrier,
+    timeouts: Timeouts,
 }
 
 /// Construction, startup, and actor-inbox processing.
 impl Transceiver {
     /// Create a ZCL transceiver.
-    pub const fn new(aps: Aps, events: EventSink, inbox: WeakSender<Message>) -> Self {
+    pub const fn new(
+        aps: Aps,
+        events: EventSink,
+        inbox: WeakSender<Message>,
+        retrier: Retrier,
+        timeouts: Timeouts,
+    ) -> Self {
         Self {
             aps,
             events,
             subscriptions: Vec::new(),
             responses: Registry::new(),
             inbox,
+            retrier,
+            timeouts,
         }
     }
 
     /// Start the ZCL transceiver.
-    pub fn spawn(aps: Aps, events: EventSink) -> Sender<Message> {
-        let (zcl_tx, zcl_rx) = tokio::sync::mpsc::channel(MPSC_CHANNEL_SIZE);
-        spawn(Self::new(aps, events, zcl_tx.downgrade()).run(zcl_rx));
+    pub fn spawn(
+        aps: Aps,
+        events: EventSink,
+        retrier: Retrier,
+        config: &CoordinatorConfig,
+    ) -> Sender<Message> {
+        let (zcl_tx, zcl_rx) = tokio::sync::mpsc::channel(config.channel_size().get());
+        spawn(Self::new(aps, events, zcl_tx.downgrade(), retrier, config.timeouts()).run(zcl_rx));
         zcl_tx
     }
 
     /// Run the transceiver.
     pub async fn run(mut self, mut messages: Receiver<Message>) {
         while let Some(message) = messages.recv().await {
-            if !self.handle_actor_message(message).await {
-                break;
-            }
+            self.handle_actor_message(message).await;
         }
     }
 
-    async fn handle_actor_message(&mut self, message: Message) -> bool {
+    async fn handle_actor_message(&mut self, message: Message) {
         match message {
             Message::Subscribe { subscription } => {
                 self.subscriptions.retain(Subscription::is_open);
@@ -86,7 +103,6 @@ impl Transceiver {
             }
             Message::HardwareUnavailable => {
                 self.responses.hardware_unavailable();
-                return false;
             }
             Message::Cancel { token } => {
                 if self.responses.cancel(token) {
@@ -119,15 +135,32 @@ impl Transceiver {
                         debug!("Failed to return ZCL reply transmission result: {error:?}");
                     });
             }
-            Message::Communicate { request, response } => {
+            Message::Retransmit {
+                token,
+                request,
+                response,
+            } => {
+                let transmission = if self.responses.is_pending(token) {
+                    Some(self.aps.transmit(request).await)
+                } else {
+                    None
+                };
+                response.send(transmission).unwrap_or_else(|error| {
+                    debug!("Failed to return ZCL retransmission result: {error:?}");
+                });
+            }
+            Message::Communicate {
+                request,
+                timeout,
+                response,
+            } => {
                 response
-                    .send(self.communicate(request).await)
+                    .send(self.communicate(request, timeout).await)
                     .unwrap_or_else(|error| {
                         debug!("Failed to send unicast response: {error:?}");
                     });
             }
         }
-        true
     }
 }
 
@@ -157,11 +190,17 @@ impl Transceiver {
         if self.forward_to_subscribers(&indication) {
             return;
         }
+        if let Some(alarm) = alarm(&indication) {
+            self.events.emit(Event::Alarm(alarm));
+            return;
+        }
 
         self.events.emit(Event::Zcl { indication });
     }
 
     /// Deliver a received frame to every matching live subscription.
+    ///
+    /// Returns whether a consuming subscription received the frame.
     fn forward_to_subscribers(
         &mut self,
         indication: &DataIndication<Frame<Cluster>, (), ()>,
@@ -183,7 +222,7 @@ impl Transceiver {
 
             match subscription.try_send(message) {
                 Ok(()) => {
-                    delivered = true;
+                    delivered |= subscription.consumes();
                     true
                 }
                 Err(TrySendError::Full(_)) => {
@@ -250,15 +289,16 @@ impl Transceiver {
     async fn communicate(
         &mut self,
         request: DataRequest<UnsequencedFrame<Bytes>>,
+        timeout: Option<Duration>,
     ) -> Result<ApsProtocolResponse<Cluster>, Error> {
         let (sequence_number, token, rx) = self
             .responses
             .try_register(|sequence| Self::request_key(&request, sequence))?;
-        self.schedule_response_timeout(token);
+        self.schedule_response_timeout(token, timeout.unwrap_or_else(|| self.timeouts.response()));
 
         let request = Self::encode_request(request, sequence_number);
 
-        let transmission = match self.aps.transmit(request).await {
+        let transmission = match self.aps.transmit(request.clone()).await {
             Ok(transmission) => transmission,
             Err(error) => {
                 self.responses.discard(token);
@@ -266,6 +306,25 @@ impl Transceiver {
             }
         };
 
+        let inbox = self.inbox.clone();
+        let transmission = self.retrier.supervise(transmission, move || {
+            let inbox = inbox.clone();
+            let request = request.clone();
+
+            async move {
+                let (response, result) = oneshot::channel();
+                inbox
+                    .upgrade()?
+                    .send(Message::Retransmit {
+                        token,
+                        request,
+                        response,
+                    })
+                    .await
+                    .ok()?;
+                result.await.ok().flatten()
+            }
+        });
         let cancellation = self.cancellation(token);
 
         Ok(ApsProtocolResponse::new(transmission, rx, cancellation))
@@ -298,10 +357,10 @@ impl Transceiver {
         })
     }
 
-    fn schedule_response_timeout(&self, token: Token) {
+    fn schedule_response_timeout(&self, token: Token, timeout: Duration) {
         let inbox = self.inbox.clone();
         spawn(async move {
-            sleep(PROTOCOL_RESPONSE_TIMEOUT).await;
+            sleep(timeout).await;
             let Some(inbox) = inbox.upgrade() else {
                 return;
             };
@@ -316,8 +375,9 @@ impl Transceiver {
 
     fn schedule_quarantine_timeout(&self, token: Token) {
         let inbox = self.inbox.clone();
+        let timeout = self.timeouts.quarantine();
         spawn(async move {
-            sleep(PROTOCOL_QUARANTINE_TIMEOUT).await;
+            sleep(timeout).await;
             let Some(inbox) = inbox.upgrade() else {
                 return;
             };
@@ -369,26 +429,52 @@ impl Transceiver {
     }
 }
 
+/// Return the typed alarm notification carried by `indication`, if any.
+fn alarm(indication: &DataIndication<Frame<Cluster>, (), ()>) -> Option<AlarmEvent> {
+    let Cluster::Alarms(AlarmsCommand::Alarm(alarm)) = indication.asdu().payload() else {
+        return None;
+    };
+    let source = indication.metadata().source();
+    let device = source
+        .network_address()
+        .and_then(|address| short_id::Device::new(address.as_u16()))?;
+    let endpoint = source.endpoint()?;
+    Some(AlarmEvent::new(
+        device,
+        endpoint,
+        AlarmKey::new(alarm.cluster_id(), alarm.code()),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
+    use std::num::NonZeroU8;
+    use std::time::Duration;
+
+    use le_stream::ToLeStream;
     use tokio::runtime::Builder;
     use tokio::sync::mpsc::channel;
+    use tokio::sync::oneshot;
+    use tokio::time::timeout;
     use zb_aps::TxOptions;
     use zb_aps::apsde::{
         Alias, DataIndication, DataRequest, IndicationMetadata, IndicationStatus,
-        IndividualEndpoint, NetworkAddress, ReceivedDestination, RequestDestination, Security,
-        Source,
+        IndividualEndpoint, NetworkAddress, NetworkDestination, ReceivedDestination,
+        RequestDestination, Security, Source,
     };
     use zb_core::endpoint::Application;
+    use zb_core::types::{Uint8, Uint32};
     use zb_core::{Cluster as ClusterId, Direction, Endpoint, Profile};
+    use zb_zcl::alarms::{Alarm, Command as AlarmsCommand, Entry, GetAlarmResponse};
     use zb_zcl::on_off::{Command as OnOffCommand, On};
-    use zb_zcl::{Cluster, Command, Frame, Header as ZclHeader, Scope, UnsequencedFrame};
+    use zb_zcl::{Cluster, Command, Frame, Header as ZclHeader, Scope, Status, UnsequencedFrame};
 
     use super::{Message, Subscription, SubscriptionFilter, SubscriptionMessage, Transceiver};
-    use crate::aps::Aps;
-    use crate::correlation::Key;
-    use crate::event::EventSink;
-    use crate::{Error, Event, MPSC_CHANNEL_SIZE};
+    use crate::aps::{Aps, Message as ApsMessage, TransmissionResponse};
+    use crate::correlation::{Key, Timeouts};
+    use crate::event::{AlarmKey, EventSink};
+    use crate::retry::{Retrier, RetryPolicy};
+    use crate::{AlarmLogEntry, Alarms, Error, Event, MPSC_CHANNEL_SIZE};
 
     const SOURCE_NODE_ID: u16 = 0x4321;
     const TRANSACTION_SEQUENCE: u8 = 7;
@@ -399,6 +485,11 @@ mod tests {
     const REMOTE_ENDPOINT_ID: u8 = 12;
     const RADIUS_COUNTER: u8 = 5;
     const ALIAS_SEQUENCE_NUMBER: u8 = 6;
+    const RETRY_BACKOFF: Duration = Duration::from_millis(1);
+    const PENDING_RESPONSE_WAIT: Duration = Duration::from_millis(20);
+    const REQUEST_TIMEOUT: Duration = Duration::from_millis(10);
+    const TEST_TIMEOUT: Duration = Duration::from_secs(1);
+    const ALARM_LOG_LIMIT: usize = 256;
 
     #[test]
     fn encoding_preserves_every_aps_request_field() {
@@ -505,6 +596,134 @@ mod tests {
             });
     }
 
+    #[test]
+    fn retransmits_a_failed_request_with_the_same_sequence() {
+        Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("Tokio runtime")
+            .block_on(async {
+                let (aps_sender, mut aps_messages) = channel(MPSC_CHANNEL_SIZE);
+                let (events, _application_events) = channel(MPSC_CHANNEL_SIZE);
+                let (transceiver, messages) = channel(MPSC_CHANNEL_SIZE);
+                let retrier = Retrier::disabled();
+                retrier.set_policy(
+                    RetryPolicy::new()
+                        .with_attempts(NonZeroU8::new(2).expect("attempts are non-zero"))
+                        .with_backoff(RETRY_BACKOFF, RETRY_BACKOFF),
+                );
+                tokio::spawn(
+                    Transceiver::new(
+                        Aps::new(aps_sender.clone()),
+                        EventSink::new(events),
+                        transceiver.downgrade(),
+                        retrier,
+                        Timeouts::new(),
+                    )
+                    .run(messages),
+                );
+                let (response, result) = oneshot::channel();
+                transceiver
+                    .send(Message::Communicate {
+                        request: network_request(),
+                        timeout: None,
+                        response,
+                    })
+                    .await
+                    .expect("ZCL transceiver remains available");
+
+                let mut sequences = Vec::new();
+                for outcome in [Err(zb_hw::TransmissionError::NoRoute.into()), Ok(())] {
+                    let Some(ApsMessage::Transmit { request, response }) =
+                        aps_messages.recv().await
+                    else {
+                        panic!("expected APS transmission");
+                    };
+                    let frame = Frame::<Cluster>::parse(
+                        ClusterId::OnOff.as_u16(),
+                        request.asdu().clone().into_iter(),
+                    )
+                    .expect("transmitted command is a valid ZCL frame");
+                    sequences.push(frame.header().seq());
+                    let (completion, deferred) = oneshot::channel();
+                    completion
+                        .send(outcome)
+                        .expect("deferred APS result is awaited");
+                    response
+                        .send(Ok(TransmissionResponse::test_new(
+                            deferred,
+                            APS_COUNTER,
+                            aps_sender.downgrade(),
+                        )))
+                        .expect("ZCL transceiver awaits the APS handoff");
+                }
+
+                let exchange = result
+                    .await
+                    .expect("ZCL transceiver answers")
+                    .expect("request is correlated");
+                assert_eq!(sequences[0], sequences[1]);
+                assert!(timeout(PENDING_RESPONSE_WAIT, exchange).await.is_err());
+            });
+    }
+
+    #[test]
+    fn request_timeout_overrides_the_configured_response_timeout() {
+        Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("Tokio runtime")
+            .block_on(async {
+                let (aps_sender, mut aps_messages) = channel(MPSC_CHANNEL_SIZE);
+                let (events, _application_events) = channel(MPSC_CHANNEL_SIZE);
+                let (transceiver, messages) = channel(MPSC_CHANNEL_SIZE);
+                tokio::spawn(
+                    Transceiver::new(
+                        Aps::new(aps_sender.clone()),
+                        EventSink::new(events),
+                        transceiver.downgrade(),
+                        Retrier::disabled(),
+                        Timeouts::new(),
+                    )
+                    .run(messages),
+                );
+                let (response, result) = oneshot::channel();
+                transceiver
+                    .send(Message::Communicate {
+                        request: network_request(),
+                        timeout: Some(REQUEST_TIMEOUT),
+                        response,
+                    })
+                    .await
+                    .expect("ZCL transceiver remains available");
+                let Some(ApsMessage::Transmit { response, .. }) = aps_messages.recv().await else {
+                    panic!("expected APS transmission");
+                };
+                let (completion, deferred) = oneshot::channel();
+                completion
+                    .send(Ok(()))
+                    .expect("deferred APS result is awaited");
+                response
+                    .send(Ok(TransmissionResponse::test_new(
+                        deferred,
+                        APS_COUNTER,
+                        aps_sender.downgrade(),
+                    )))
+                    .expect("ZCL transceiver awaits the APS handoff");
+
+                let exchange = result
+                    .await
+                    .expect("ZCL transceiver answers")
+                    .expect("request is correlated");
+                assert!(matches!(
+                    timeout(TEST_TIMEOUT, exchange)
+                        .await
+                        .expect("the request timeout elapses first"),
+                    Err(Error::ProtocolResponseTimeout)
+                ));
+            });
+    }
+
     #[test]
     fn routes_matching_frames_to_a_generic_subscription() {
         Builder::new_current_thread()
@@ -525,6 +744,8 @@ mod tests {
                         Aps::new(aps_sender),
                         EventSink::new(events),
                         transceiver.downgrade(),
+                        Retrier::disabled(),
+                        Timeouts::new(),
                     )
                     .run(messages),
                 );
@@ -645,6 +866,8 @@ mod tests {
                         Aps::new(aps_sender),
                         EventSink::new(events),
                         transceiver.downgrade(),
+                        Retrier::disabled(),
+                        Timeouts::new(),
                     )
                     .run(messages),
                 );
@@ -723,6 +946,178 @@ mod tests {
             });
     }
 
+    #[test]
+    fn routes_unsolicited_alarms_to_typed_alarm_events() {
+        let (mut transceiver, mut events) = unstarted_transceiver();
+        let alarm = Alarm::new(0x02, ClusterId::PowerConfiguration.as_u16());
+
+        transceiver.handle_message_received(received_indication(
+            ClusterId::Alarms,
+            Profile::ZigbeeHomeAutomation.as_u16(),
+            ZclHeader::new(
+                Scope::ClusterSpecific,
+                Direction::ServerToClient,
+                false,
+                None,
+                TRANSACTION_SEQUENCE,
+                <Alarm as Command>::ID,
+            ),
+            Cluster::Alarms(AlarmsCommand::from(alarm)),
+        ));
+
+        let Ok(Event::Alarm(event)) = events.try_recv() else {
+            panic!("expected typed alarm event");
+        };
+        assert_eq!(event.device().as_u16(), SOURCE_NODE_ID);
+        assert_eq!(event.endpoint().get().as_u8(), REMOTE_ENDPOINT_ID);
+        assert_eq!(
+            event.key(),
+            AlarmKey::new(ClusterId::PowerConfiguration.as_u16(), 0x02)
+        );
+    }
+
+    #[test]
+    fn alarm_log_drains_entries_until_the_device_reports_not_found() {
+        drain_alarm_log(
+            [
+                Some(Entry::new(Uint8::new(0x02), 0x0001, Uint32::new(0x10))),
+                Some(Entry::new(Uint8::new(0x05), 0x0402, Uint32::NONE)),
+                None,
+            ],
+            |log| {
+                let log = log.expect("alarm log is drained");
+                assert_eq!(log.len(), 2);
+                assert_eq!(log[0].key(), AlarmKey::new(0x0001, 0x02));
+                assert_eq!(log[0].timestamp(), Some(0x10));
+                assert_eq!(log[1].key(), AlarmKey::new(0x0402, 0x05));
+                assert_eq!(log[1].timestamp(), None);
+            },
+        );
+    }
+
+    #[test]
+    fn alarm_log_fails_once_the_device_reports_too_many_entries() {
+        let entry = Entry::new(Uint8::new(0x02), 0x0001, Uint32::NONE);
+
+        drain_alarm_log(
+            std::iter::repeat_n(Some(entry), ALARM_LOG_LIMIT + 1),
+            |log| assert!(matches!(log, Err(Error::AlarmLogTooLong(ALARM_LOG_LIMIT)))),
+        );
+    }
+
+    /// Run `Alarms::alarm_log`, answering its Get Alarm commands with `entries`, where `None`
+    /// reports an empty log, and check its result.
+    fn drain_alarm_log<T, F>(entries: T, check: F)
+    where
+        T: IntoIterator<Item = Option<Entry>>,
+        F: FnOnce(Result<Vec<AlarmLogEntry>, Error>),
+    {
+        Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("Tokio runtime")
+            .block_on(async {
+                let (aps_sender, mut aps_messages) = channel(MPSC_CHANNEL_SIZE);
+                let (events, _application_events) = channel(MPSC_CHANNEL_SIZE);
+                let (transceiver, messages) = channel(MPSC_CHANNEL_SIZE);
+                tokio::spawn(
+                    Transceiver::new(
+                        Aps::new(aps_sender.clone()),
+                        EventSink::new(events),
+                        transceiver.downgrade(),
+                        Retrier::disabled(),
+                        Timeouts::new(),
+                    )
+                    .run(messages),
+                );
+                let destination = NetworkDestination::new(
+                    NetworkAddress::new(SOURCE_NODE_ID).expect("test NWK address is valid"),
+                    IndividualEndpoint::new(
+                        Endpoint::try_from(REMOTE_ENDPOINT_ID).expect("remote endpoint is valid"),
+                    )
+                    .expect("application endpoint is individual"),
+                );
+                let source_endpoint = IndividualEndpoint::new(
+                    Endpoint::try_from(LOCAL_ENDPOINT_ID).expect("local endpoint is valid"),
+                )
+                .expect("application endpoint is individual");
+                let log = tokio::spawn({
+                    let transceiver = transceiver.clone();
+                    async move { transceiver.alarm_log(destination, source_endpoint).await }
+                });
+
+                for entry in entries {
+                    let Some(ApsMessage::Transmit { request, response }) =
+                        aps_messages.recv().await
+                    else {
+                        panic!("expected Get Alarm transmission");
+                    };
+                    let frame = Frame::<Cluster>::parse(
+                        ClusterId::Alarms.as_u16(),
+                        request.asdu().clone().into_iter(),
+                    )
+                    .expect("transmitted command is a valid ZCL frame");
+                    assert!(matches!(
+                        frame.payload(),
+                        Cluster::Alarms(AlarmsCommand::GetAlarm(_))
+                    ));
+                    acknowledge(response, &aps_sender);
+
+                    let status = if entry.is_some() {
+                        Status::Success
+                    } else {
+                        Status::NotFound
+                    };
+                    transceiver
+                        .send(Message::Received {
+                            indication: received_indication(
+                                ClusterId::Alarms,
+                                request.profile_id(),
+                                ZclHeader::new(
+                                    Scope::ClusterSpecific,
+                                    Direction::ServerToClient,
+                                    true,
+                                    None,
+                                    frame.header().seq(),
+                                    <GetAlarmResponse as Command>::ID,
+                                ),
+                                Cluster::Alarms(AlarmsCommand::from(GetAlarmResponse::new(
+                                    status, entry,
+                                ))),
+                            ),
+                        })
+                        .await
+                        .expect("ZCL transceiver remains available");
+                }
+
+                check(
+                    timeout(TEST_TIMEOUT, log)
+                        .await
+                        .expect("alarm log completes")
+                        .expect("alarm log task does not panic"),
+                );
+            });
+    }
+
+    fn network_request() -> DataRequest<UnsequencedFrame<bytes::Bytes>> {
+        let destination = RequestDestination::Network {
+            address: NetworkAddress::new(SOURCE_NODE_ID).expect("test NWK address is valid"),
+            endpoint: Endpoint::try_from(REMOTE_ENDPOINT_ID).expect("remote endpoint is valid"),
+        };
+        let source_endpoint = IndividualEndpoint::new(
+            Endpoint::try_from(LOCAL_ENDPOINT_ID).expect("local endpoint is valid"),
+        )
+        .expect("application endpoint is individual");
+        DataRequest::new(
+            destination,
+            Profile::ZigbeeHomeAutomation.as_u16(),
+            ClusterId::OnOff.as_u16(),
+            source_endpoint,
+            UnsequencedFrame::from_command(On)
+                .map_payload(|command| command.to_le_stream().collect()),
+        )
+    }
+
     fn unstarted_transceiver() -> (Transceiver, tokio::sync::mpsc::Receiver<Event>) {
         let (aps_sender, _aps_receiver) = channel(MPSC_CHANNEL_SIZE);
         let (events, application_events) = channel(MPSC_CHANNEL_SIZE);
@@ -732,6 +1127,8 @@ mod tests {
                 Aps::new(aps_sender),
                 EventSink::new(events),
                 inbox.downgrade(),
+                Retrier::disabled(),
+                Timeouts::new(),
             ),
             application_events,
         )
@@ -770,6 +1167,56 @@ mod tests {
         DataIndication::new(metadata, frame)
     }
 
+    fn acknowledge(
+        response: oneshot::Sender<Result<TransmissionResponse, Error>>,
+        aps_sender: &tokio::sync::mpsc::Sender<ApsMessage>,
+    ) {
+        let (completion, deferred) = oneshot::channel();
+        completion
+            .send(Ok(()))
+            .expect("deferred APS result is awaited");
+        response
+            .send(Ok(TransmissionResponse::test_new(
+                deferred,
+                APS_COUNTER,
+                aps_sender.downgrade(),
+            )))
+            .expect("ZCL transceiver awaits the APS handoff");
+    }
+
+    fn received_indication(
+        cluster_id: ClusterId,
+        profile_id: u16,
+        header: ZclHeader,
+        payload: Cluster,
+    ) -> DataIndication<Frame<Cluster>, (), ()> {
+        let metadata = IndicationMetadata::new(
+            ReceivedDestination::Network {
+                address: NetworkAddress::new(LOCAL_NODE_ID)
+                    .expect("coordinator address is a valid NWK address"),
+                endpoint: IndividualEndpoint::new(
+                    Endpoint::try_from(LOCAL_ENDPOINT_ID).expect("local endpoint is valid"),
+                )
+                .expect("application endpoint is individual"),
+            },
+            Source::Network {
+                address: NetworkAddress::new(SOURCE_NODE_ID)
+                    .expect("source address is a valid NWK address"),
+                endpoint: IndividualEndpoint::new(
+                    Endpoint::try_from(REMOTE_ENDPOINT_ID).expect("remote endpoint is valid"),
+                )
+                .expect("application endpoint is individual"),
+            },
+            profile_id,
+            cluster_id.as_u16(),
+            IndicationStatus::success(),
+            Security::Unsecured,
+            LINK_QUALITY,
+            (),
+        );
+        DataIndication::new(metadata, Frame::new(header, payload))
+    }
+
     fn source() -> Source {
         let endpoint = IndividualEndpoint::new(Endpoint::Application(Application::MIN))
             .expect("application endpoint is individual");
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use bytes::Bytes;
 use tokio::sync::mpsc::Sender as MpscSender;
 use tokio::sync::oneshot::Sender;
@@ -34,7 +36,7 @@ pub enum Message {
     /// Fail pending protocol responses because the Zigbee network went down.
     NetworkDown,
 
-    /// Fail pending protocol responses and stop because hardware events are unavailable.
+    /// Fail pending protocol responses because the hardware session ended.
     HardwareUnavailable,
 
     /// Cancel a pending protocol response whose future was dropped.
@@ -73,10 +75,23 @@ pub enum Message {
         response: Sender<Result<TransmissionResponse, Error>>,
     },
 
+    /// Retransmit a correlated request whose previous APS transmission failed.
+    Retransmit {
+        /// Coordinator-private identity of the protocol transaction to retransmit.
+        token: Token,
+        /// Encoded APS request of the original transmission.
+        request: DataRequest<Bytes>,
+        /// Channel used to return the deferred APS result, or `None` if the response is no
+        /// longer pending.
+        response: Sender<Option<Result<TransmissionResponse, Error>>>,
+    },
+
     /// Communicate a unicast with an expected response.
     Communicate {
         /// APS request containing the outgoing ZCL command.
         request: DataRequest<UnsequencedFrame<Bytes>>,
+        /// Response timeout overriding the configured default.
+        timeout: Option<Duration>,
         /// The response channel.
         response: Sender<Result<ApsProtocolResponse<Cluster>, Error>>,
     },
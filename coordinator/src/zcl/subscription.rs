@@ -28,6 +28,8 @@ pub struct Filter {
 pub struct Subscription {
     filter: Filter,
     messages: WeakSender<Received>,
+    /// Whether a delivered frame is withheld from application-event routing.
+    consumes: bool,
 }
 
 /// Receiving half of an internal ZCL subscription.
@@ -63,15 +65,32 @@ impl Filter {
 impl Subscription {
     /// Create a bounded subscription channel for the given filter.
     pub fn channel(filter: Filter) -> (Self, SubscriptionReceiver) {
+        Self::new(filter, true)
+    }
+
+    /// Create a bounded subscription channel that receives copies of matching frames.
+    ///
+    /// Frames delivered to an observer are still routed to consuming subscriptions and to the
+    /// application event channel.
+    pub fn observer(filter: Filter) -> (Self, SubscriptionReceiver) {
+        Self::new(filter, false)
+    }
+
+    fn new(filter: Filter, consumes: bool) -> (Self, SubscriptionReceiver) {
         let (sender, messages) = tokio::sync::mpsc::channel(MPSC_CHANNEL_SIZE);
         let subscription = Self {
             filter,
             messages: sender.downgrade(),
+            consumes,
         };
         let receiver = SubscriptionReceiver { sender, messages };
         (subscription, receiver)
     }
 
+    pub(super) const fn consumes(&self) -> bool {
+        self.consumes
+    }
+
     pub(super) fn is_open(&self) -> bool {
         self.messages
             .upgrade()
@@ -1,6 +1,7 @@
 //! Transceiver to send and receive ZDP messages.
 
 use std::collections::BTreeMap;
+use std::time::Duration;
 
 use bytes::Bytes;
 use le_stream::ToLeStream;
@@ -16,20 +17,24 @@ use zb_core::FullAddress;
 use zb_core::node::Descriptor;
 use zb_core::short_id::Device;
 use zb_hw::NcpHandle;
-use zb_zdp::{Command, DeviceAndServiceDiscovery, DeviceAnnce, Frame};
+use zb_zdp::{
+    Command, DeviceAndServiceDiscovery, DeviceAnnce, Frame, Security,
+    SecurityGetAuthenticationLevelReq, SecurityStartKeyNegotiationReq, Status,
+};
 
+use self::key_negotiation::Registry as KeyNegotiation;
 pub use self::message::Message;
 use self::server::{Server, ServerRequest, is_server_request};
-use self::submission::CommunicationSubmission;
-use crate::aps::Aps;
-use crate::correlation::{
-    Cancellation, Key, PROTOCOL_QUARANTINE_TIMEOUT, PROTOCOL_RESPONSE_TIMEOUT, Registry, Token,
-};
+use self::submission::{CommunicationSubmission, Retransmission};
+use crate::aps::{Aps, TransmissionResponse};
+use crate::correlation::{Cancellation, Key, Registry, Timeouts, Token};
 use crate::event::EventSink;
 use crate::response::ApsProtocolResponse;
-use crate::{Device as DeviceEvent, Event, MPSC_CHANNEL_SIZE};
+use crate::retry::{Retrier, Transmission};
+use crate::{CoordinatorConfig, Device as DeviceEvent, Event, MPSC_CHANNEL_SIZE};
 
 mod discovery;
+mod key_negotiation;
 mod match_desc;
 mod message;
 mod node_desc;
@@ -38,6 +43,7 @@ mod submission;
 
 const INITIAL_SERVER_OPERATION_ID: u64 = 0;
 const INITIAL_COMMUNICATION_SUBMISSION_ID: u64 = 0;
+const INITIAL_RETRANSMISSION_ID: u64 = 0;
 const COMMUNICATION_SUBMISSION_LIMIT: usize = MPSC_CHANNEL_SIZE;
 const SERVER_OPERATION_LIMIT: usize = MPSC_CHANNEL_SIZE;
 
@@ -45,11 +51,16 @@ const SERVER_OPERATION_LIMIT: usize = MPSC_CHANNEL_SIZE;
 #[derive(Debug)]
 pub struct Transceiver {
     server: Server,
+    key_negotiation: KeyNegotiation,
     events: EventSink,
     responses: Registry<Command>,
     inbox: WeakSender<Message>,
     communication_submissions: BTreeMap<u64, CommunicationSubmission>,
     next_communication_submission_id: u64,
+    retransmissions: BTreeMap<u64, Retransmission>,
+    next_retransmission_id: u64,
+    retrier: Retrier,
+    timeouts: Timeouts,
     server_operations: BTreeMap<u64, AbortHandle>,
     next_server_operation_id: u64,
 }
@@ -64,14 +75,21 @@ impl Transceiver {
         events: EventSink,
         descriptor: Descriptor,
         inbox: WeakSender<Message>,
+        retrier: Retrier,
+        timeouts: Timeouts,
     ) -> Self {
         Self {
             server: Server::new(ncp, aps, descriptor, inbox.clone()),
+            key_negotiation: KeyNegotiation::default(),
             events,
             responses: Registry::new(),
             inbox,
             communication_submissions: BTreeMap::new(),
             next_communication_submission_id: INITIAL_COMMUNICATION_SUBMISSION_ID,
+            retransmissions: BTreeMap::new(),
+            next_retransmission_id: INITIAL_RETRANSMISSION_ID,
+            retrier,
+            timeouts,
             server_operations: BTreeMap::new(),
             next_server_operation_id: INITIAL_SERVER_OPERATION_ID,
         }
@@ -83,24 +101,36 @@ impl Transceiver {
         aps: Aps,
         events: EventSink,
         descriptor: Descriptor,
+        retrier: Retrier,
+        config: &CoordinatorConfig,
     ) -> Sender<Message> {
-        let (zdp_tx, zdp_rx) = tokio::sync::mpsc::channel(MPSC_CHANNEL_SIZE);
-        spawn(Self::new(ncp, aps, events, descriptor, zdp_tx.downgrade()).run(zdp_rx));
+        let (zdp_tx, zdp_rx) = tokio::sync::mpsc::channel(config.channel_size().get());
+        spawn(
+            Self::new(
+                ncp,
+                aps,
+                events,
+                descriptor,
+                zdp_tx.downgrade(),
+                retrier,
+                config.timeouts(),
+            )
+            .run(zdp_rx),
+        );
         zdp_tx
     }
 
     /// Run the transceiver.
     pub async fn run(mut self, mut messages: Receiver<Message>) {
         while let Some(message) = messages.recv().await {
-            if !self.handle_actor_message(message) {
-                break;
-            }
+            self.handle_actor_message(message);
         }
         self.abort_server_operations();
         self.abort_communication_submissions();
+        self.abort_retransmissions();
     }
 
-    fn handle_actor_message(&mut self, message: Message) -> bool {
+    fn handle_actor_message(&mut self, message: Message) {
         match message {
             Message::Received { indication } => {
                 self.handle_message_received(indication);
@@ -112,8 +142,8 @@ impl Transceiver {
             Message::HardwareUnavailable => {
                 self.abort_server_operations();
                 self.fail_communication_submissions_for_hardware_unavailability();
+                self.abort_retransmissions();
                 self.responses.hardware_unavailable();
-                return false;
             }
             Message::Cancel { token } => {
                 if self.responses.cancel(token) {
@@ -139,17 +169,18 @@ impl Transceiver {
             Message::CommunicationSubmissionFinished { id, result } => {
                 let Some(submission) = self.communication_submissions.remove(&id) else {
                     debug!("Ignoring completion for unknown ZDP communication submission {id}");
-                    return true;
+                    return;
                 };
                 let CommunicationSubmission {
                     token,
+                    request,
                     protocol_response,
                     response,
                     task: _,
                 } = submission;
                 let result = match result {
                     Ok(transmission) => Ok(ApsProtocolResponse::new(
-                        transmission,
+                        self.supervise(token, request, transmission),
                         protocol_response,
                         self.cancellation(token),
                     )),
@@ -160,15 +191,46 @@ impl Transceiver {
                 };
                 response.send(result).unwrap_or_else(drop);
             }
+            Message::Retransmit {
+                token,
+                request,
+                response,
+            } => {
+                self.retransmit(token, request, response);
+            }
+            Message::RetransmissionFinished { id } => {
+                self.retransmissions.remove(&id);
+            }
             Message::Communicate {
                 device,
                 request,
+                timeout,
                 response,
             } => {
-                self.communicate(device, request, response);
+                self.communicate(device, request, timeout, response);
+            }
+            Message::AddInstallCode {
+                ieee_address,
+                install_code,
+            } => {
+                self.key_negotiation
+                    .add_install_code(ieee_address, &install_code);
+            }
+            Message::KeyNegotiated { level } => {
+                self.key_negotiation.record(level);
+            }
+            Message::LinkKeysUnsupported => {
+                self.key_negotiation.refuse();
+            }
+            Message::GetAuthenticationLevel {
+                ieee_address,
+                response,
+            } => {
+                response
+                    .send(self.key_negotiation.authentication_level(ieee_address))
+                    .unwrap_or_else(drop);
             }
         }
-        true
     }
 }
 
@@ -193,16 +255,35 @@ impl Transceiver {
             handle_device_annce(&self.events, device_annce.as_ref());
             return;
         }
+        let command = match command {
+            Command::Security(Security::SecurityStartKeyNegotiationReq(request)) => {
+                if !request_was_broadcast {
+                    self.spawn_key_negotiation(source_address, seq, *request);
+                }
+                return;
+            }
+            Command::Security(Security::SecurityGetAuthenticationLevelReq(request)) => {
+                if !request_was_broadcast {
+                    self.spawn_authentication_level_reply(source_address, seq, &request);
+                }
+                return;
+            }
+            command => command,
+        };
         if is_server_request(&command) {
-            self.spawn_server_operation(ServerRequest::new(
-                source_address,
-                request_was_broadcast,
-                seq,
-                command,
-            ));
+            let server = self.server.clone();
+            let request = ServerRequest::new(source_address, request_was_broadcast, seq, command);
+            self.spawn_server_operation(async move { server.handle(request).await });
             return;
         }
 
+        if let Command::DeviceAndServiceDiscovery(DeviceAndServiceDiscovery::NodeDescRsp(
+            response,
+        )) = &command
+        {
+            node_desc::learn_transfer_limits(self.server.aps().transfer_limits(), response);
+        }
+
         if self.responses.complete(key, command.clone()) {
             debug!(
                 "Answering ZDP request: seq={seq} cluster_id={:#06X}",
@@ -215,7 +296,63 @@ impl Transceiver {
         }
     }
 
-    fn spawn_server_operation(&mut self, request: ServerRequest) {
+    fn spawn_key_negotiation(
+        &mut self,
+        source: NetworkAddress,
+        seq: u8,
+        request: SecurityStartKeyNegotiationReq,
+    ) {
+        let Some(initiator) = request.curve25519_public_point() else {
+            warn!("Discarding key negotiation without a Curve25519 public point");
+            return;
+        };
+        let server = self.server.clone();
+        if self.key_negotiation.is_refused() {
+            self.spawn_server_operation(async move {
+                server
+                    .reject_key_negotiation(source, seq, Status::NotSupported)
+                    .await;
+            });
+            return;
+        }
+        let passphrase = self.key_negotiation.passphrase(initiator.device_eui64());
+        self.spawn_server_operation(async move {
+            server
+                .handle_security_start_key_negotiation_req(source, seq, request, passphrase)
+                .await;
+        });
+    }
+
+    fn spawn_authentication_level_reply(
+        &mut self,
+        source: NetworkAddress,
+        seq: u8,
+        request: &SecurityGetAuthenticationLevelReq,
+    ) {
+        let Some(target) = request.target_ieee_address() else {
+            warn!("Discarding authentication level request without a target IEEE address");
+            return;
+        };
+        let response = self
+            .key_negotiation
+            .authentication_level(target.ieee_address());
+        let server = self.server.clone();
+        self.spawn_server_operation(async move {
+            server
+                .respond_to_source(
+                    source,
+                    seq,
+                    response,
+                    "Security_Get_Authentication_Level_rsp",
+                )
+                .await;
+        });
+    }
+
+    fn spawn_server_operation<F>(&mut self, operation: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
         if self.server_operations.len() >= SERVER_OPERATION_LIMIT {
             warn!(
                 "Discarding ZDP server request because the operation limit of \
@@ -225,10 +362,9 @@ impl Transceiver {
         }
 
         let id = self.allocate_server_operation_id();
-        let server = self.server.clone();
         let inbox = self.inbox.clone();
         let task = spawn(async move {
-            server.handle(request).await;
+            operation.await;
             let Some(inbox) = inbox.upgrade() else {
                 return;
             };
@@ -272,6 +408,7 @@ impl Transceiver {
         &mut self,
         device: Device,
         request: DataRequest<Bytes>,
+        timeout: Option<Duration>,
         response: tokio::sync::oneshot::Sender<Result<ApsProtocolResponse<Command>, crate::Error>>,
     ) {
         if self.communication_submissions.len() >= COMMUNICATION_SUBMISSION_LIMIT {
@@ -294,11 +431,12 @@ impl Transceiver {
                 return;
             }
         };
-        self.schedule_response_timeout(token);
+        self.schedule_response_timeout(token, timeout.unwrap_or_else(|| self.timeouts.response()));
         let request = request.map_asdu(|payload| Frame::new(seq, payload).to_le_stream().collect());
         let aps = self.server.aps().clone();
         let inbox = self.inbox.clone();
         let id = self.allocate_communication_submission_id();
+        let retained = request.clone();
         let task = spawn(async move {
             let result = aps.transmit(request).await;
             let Some(inbox) = inbox.upgrade() else {
@@ -315,6 +453,7 @@ impl Transceiver {
             id,
             CommunicationSubmission {
                 token,
+                request: retained,
                 protocol_response,
                 response,
                 task: task.abort_handle(),
@@ -325,9 +464,15 @@ impl Transceiver {
 
     fn handle_network_down(&mut self) {
         let submissions = std::mem::take(&mut self.communication_submissions);
+        let retransmissions = std::mem::take(&mut self.retransmissions);
         let protected = submissions
             .values()
             .map(|submission| submission.token)
+            .chain(
+                retransmissions
+                    .values()
+                    .map(|retransmission| retransmission.token),
+            )
             .collect::<Vec<_>>();
         let quarantined = self
             .responses
@@ -345,6 +490,10 @@ impl Transceiver {
                 ))
                 .unwrap_or_else(drop);
         }
+
+        for retransmission in retransmissions.into_values() {
+            retransmission.task.abort();
+        }
     }
 
     fn fail_communication_submissions_for_hardware_unavailability(&mut self) {
@@ -363,6 +512,92 @@ impl Transceiver {
         }
     }
 
+    fn abort_retransmissions(&mut self) {
+        for retransmission in std::mem::take(&mut self.retransmissions).into_values() {
+            retransmission.task.abort();
+        }
+    }
+
+    /// Hand the first APS transmission of a request to the retry policy.
+    fn supervise(
+        &self,
+        token: Token,
+        request: DataRequest<Bytes>,
+        transmission: TransmissionResponse,
+    ) -> Transmission {
+        let inbox = self.inbox.clone();
+
+        self.retrier.supervise(transmission, move || {
+            let inbox = inbox.clone();
+            let request = request.clone();
+
+            async move {
+                let (response, result) = tokio::sync::oneshot::channel();
+                inbox
+                    .upgrade()?
+                    .send(Message::Retransmit {
+                        token,
+                        request,
+                        response,
+                    })
+                    .await
+                    .ok()?;
+                result.await.ok().flatten()
+            }
+        })
+    }
+
+    /// Transmit a request again if its response is still pending.
+    ///
+    /// The handoff runs in the background like the original submission, and its correlation
+    /// identity stays protected across a network boundary until the handoff finishes.
+    fn retransmit(
+        &mut self,
+        token: Token,
+        request: DataRequest<Bytes>,
+        response: tokio::sync::oneshot::Sender<Option<Result<TransmissionResponse, crate::Error>>>,
+    ) {
+        if !self.responses.is_pending(token) {
+            response.send(None).unwrap_or_else(drop);
+            return;
+        }
+
+        let aps = self.server.aps().clone();
+        let inbox = self.inbox.clone();
+        let id = self.allocate_retransmission_id();
+        let task = spawn(async move {
+            response
+                .send(Some(aps.transmit(request).await))
+                .unwrap_or_else(drop);
+            let Some(inbox) = inbox.upgrade() else {
+                return;
+            };
+            inbox
+                .send(Message::RetransmissionFinished { id })
+                .await
+                .unwrap_or_else(|error| {
+                    debug!("Failed to complete ZDP retransmission: {error}");
+                });
+        });
+        self.retransmissions.insert(
+            id,
+            Retransmission {
+                token,
+                task: task.abort_handle(),
+            },
+        );
+    }
+
+    fn allocate_retransmission_id(&mut self) -> u64 {
+        loop {
+            let id = self.next_retransmission_id;
+            self.next_retransmission_id = self.next_retransmission_id.wrapping_add(1);
+            if !self.retransmissions.contains_key(&id) {
+                return id;
+            }
+        }
+    }
+
     fn allocate_communication_submission_id(&mut self) -> u64 {
         loop {
             let id = self.next_communication_submission_id;
@@ -400,10 +635,10 @@ impl Transceiver {
         })
     }
 
-    fn schedule_response_timeout(&self, token: Token) {
+    fn schedule_response_timeout(&self, token: Token, timeout: Duration) {
         let inbox = self.inbox.clone();
         spawn(async move {
-            sleep(PROTOCOL_RESPONSE_TIMEOUT).await;
+            sleep(timeout).await;
             let Some(inbox) = inbox.upgrade() else {
                 return;
             };
@@ -418,8 +653,9 @@ impl Transceiver {
 
     fn schedule_quarantine_timeout(&self, token: Token) {
         let inbox = self.inbox.clone();
+        let timeout = self.timeouts.quarantine();
         spawn(async move {
-            sleep(PROTOCOL_QUARANTINE_TIMEOUT).await;
+            sleep(timeout).await;
             let Some(inbox) = inbox.upgrade() else {
                 return;
             };
@@ -496,10 +732,11 @@ mod tests {
 
     use super::server::{permit_joining_response, track_reply_completion};
     use super::{Message, Transceiver};
-    use crate::Error;
     use crate::aps::{Aps, Message as ApsMessage, Metadata, TransmissionResponse};
-    use crate::correlation::Key;
+    use crate::correlation::{Key, Timeouts};
     use crate::event::EventSink;
+    use crate::retry::Retrier;
+    use crate::{CoordinatorConfig, Error};
 
     const CHANNEL_SIZE: usize = 1;
     const APS_COUNTER: u8 = 1;
@@ -640,7 +877,7 @@ mod tests {
     }
 
     #[test]
-    fn actor_handles_hardware_shutdown_while_endpoint_query_is_pending() {
+    fn actor_survives_hardware_unavailability_while_endpoint_query_is_pending() {
         Runtime::new()
             .expect("runtime must be available")
             .block_on(async {
@@ -659,6 +896,8 @@ mod tests {
                     Aps::new(aps_messages),
                     EventSink::new(events),
                     Descriptor::default(),
+                    Retrier::disabled(),
+                    &CoordinatorConfig::new(),
                 );
 
                 zdp.send(Message::Received {
@@ -672,10 +911,11 @@ mod tests {
                     .expect("endpoint query start sender remains available");
                 zdp.send(Message::HardwareUnavailable)
                     .await
-                    .expect("ZDP actor accepts hardware shutdown");
-                timeout(TEST_TIMEOUT, zdp.closed())
+                    .expect("ZDP actor accepts hardware unavailability");
+                timeout(TEST_TIMEOUT, zdp.send(Message::NetworkDown))
                     .await
-                    .expect("pending endpoint query must not block actor shutdown");
+                    .expect("pending endpoint query must not block the actor")
+                    .expect("ZDP actor keeps running after hardware unavailability");
 
                 release_query
                     .send(())
@@ -710,12 +950,14 @@ mod tests {
                     EventSink::new(events),
                     Descriptor::default(),
                     zdp_inbox.downgrade(),
+                    Retrier::disabled(),
+                    Timeouts::new(),
                 );
                 let device = Device::new(REMOTE_ADDRESS).expect("test device ID is valid");
                 let request = communication_request(device);
                 let (response, result) = oneshot::channel();
 
-                transceiver.communicate(device, request, response);
+                transceiver.communicate(device, request, None, response);
                 let original_sequence = transceiver
                     .communication_submissions
                     .values()
@@ -742,14 +984,14 @@ mod tests {
                     .expect("submission completion must be queued")
                     .expect("ZDP actor inbox remains available");
 
-                assert!(transceiver.handle_actor_message(Message::NetworkDown));
+                transceiver.handle_actor_message(Message::NetworkDown);
                 assert!(matches!(
                     result.await,
                     Ok(Err(Error::Hardware(HardwareError::Transmission(
                         zb_hw::TransmissionError::NoRoute
                     ))))
                 ));
-                assert!(transceiver.handle_actor_message(stale_completion));
+                transceiver.handle_actor_message(stale_completion);
 
                 let request = communication_request(device);
                 let (next_sequence, token, _response) = transceiver
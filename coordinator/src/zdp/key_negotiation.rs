@@ -0,0 +1,192 @@
+//! Dynamic link key negotiation state of the ZDP actor.
+//!
+//! Joining devices negotiate their trust-center link key with the Curve25519 SPEKE exchange of
+//! `Security_Start_Key_Negotiation_req`. The coordinator answers as responder, verifies the
+//! resulting link key with an APS frame counter challenge, and only then installs it on the NCP.
+//! Verified negotiations determine the authentication level reported in
+//! `Security_Get_Authentication_Level_rsp`.
+
+use std::collections::BTreeMap;
+
+use zb_core::IeeeAddress;
+use zb_core::security::{InstallCode, Key};
+use zb_core::types::tlv::{ActiveLinkKeyType, DeviceAuthenticationLevel, InitialJoinMethod, Tlv};
+use zb_zdp::{SecurityGetAuthenticationLevelRsp, Status};
+
+pub(super) use self::challenge::{Challenge, verify as verify_challenge};
+pub(super) use self::speke::{Exchange, Hash};
+
+mod challenge;
+mod speke;
+
+/// Passphrase used by devices without a registered install code.
+const WELL_KNOWN_PASSPHRASE: Key = Key::new(*b"ZigBeeAlliance18");
+
+/// Pre-shared secret that authenticates a key negotiation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum Passphrase {
+    /// The link key derived from the device's install code.
+    InstallCode(Key),
+
+    /// The well-known passphrase, which does not authenticate the device.
+    WellKnown,
+}
+
+/// Registered install codes and verified authentication levels.
+#[derive(Debug, Default)]
+pub(super) struct Registry {
+    passphrases: BTreeMap<IeeeAddress, Key>,
+    levels: BTreeMap<IeeeAddress, DeviceAuthenticationLevel>,
+    refused: bool,
+}
+
+impl Passphrase {
+    /// Return the passphrase key.
+    pub(super) const fn key(self) -> Key {
+        match self {
+            Self::InstallCode(key) => key,
+            Self::WellKnown => WELL_KNOWN_PASSPHRASE,
+        }
+    }
+
+    /// Return the authentication level of a device that verified a key negotiated with this
+    /// passphrase.
+    pub(super) fn authentication_level(
+        self,
+        ieee_address: IeeeAddress,
+    ) -> DeviceAuthenticationLevel {
+        match self {
+            Self::InstallCode(_) => DeviceAuthenticationLevel::new(
+                ieee_address,
+                InitialJoinMethod::AuthenticatedKeyNegotiation,
+                ActiveLinkKeyType::AuthenticatedKeyNegotiation,
+            ),
+            Self::WellKnown => DeviceAuthenticationLevel::new(
+                ieee_address,
+                InitialJoinMethod::AnonymousKeyNegotiation,
+                ActiveLinkKeyType::UnauthenticatedKeyNegotiation,
+            ),
+        }
+    }
+}
+
+impl Registry {
+    /// Use the install code of `ieee_address` as the passphrase of its key negotiations.
+    pub(super) fn add_install_code(
+        &mut self,
+        ieee_address: IeeeAddress,
+        install_code: &InstallCode,
+    ) {
+        self.passphrases
+            .insert(ieee_address, install_code.link_key());
+    }
+
+    /// Return the passphrase for a key negotiation initiated by `ieee_address`.
+    pub(super) fn passphrase(&self, ieee_address: IeeeAddress) -> Passphrase {
+        self.passphrases
+            .get(&ieee_address)
+            .copied()
+            .map_or(Passphrase::WellKnown, Passphrase::InstallCode)
+    }
+
+    /// Record the authentication level of a device after a verified key negotiation.
+    pub(super) fn record(&mut self, level: DeviceAuthenticationLevel) {
+        self.levels.insert(level.remote_node_ieee_address(), level);
+    }
+
+    /// Refuse all further key negotiations, since the NCP cannot store negotiated link keys.
+    pub(super) const fn refuse(&mut self) {
+        self.refused = true;
+    }
+
+    /// Return whether key negotiations are refused.
+    pub(super) const fn is_refused(&self) -> bool {
+        self.refused
+    }
+
+    /// Build the authentication level response for `ieee_address`.
+    ///
+    /// Devices without a verified key negotiation are reported as unauthenticated with a link key
+    /// that has not been updated.
+    pub(super) fn authentication_level(
+        &self,
+        ieee_address: IeeeAddress,
+    ) -> SecurityGetAuthenticationLevelRsp {
+        let level = self.levels.get(&ieee_address).cloned().unwrap_or_else(|| {
+            DeviceAuthenticationLevel::new(
+                ieee_address,
+                InitialJoinMethod::NoAuthentication,
+                ActiveLinkKeyType::NotUpdated,
+            )
+        });
+
+        SecurityGetAuthenticationLevelRsp::new(
+            Status::Success.into(),
+            Box::new([Tlv::Local(level)]),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zb_core::IeeeAddress;
+    use zb_core::security::InstallCode;
+    use zb_core::types::tlv::{ActiveLinkKeyType, InitialJoinMethod};
+
+    use super::{Passphrase, Registry};
+
+    const DEVICE: IeeeAddress = IeeeAddress::new(1, 2, 3, 4, 5, 6, 7, 8);
+
+    #[test]
+    fn uses_registered_install_codes_as_passphrase() {
+        let install_code: InstallCode = "83FED3407A939723A5C639B26916D505C3B5"
+            .parse()
+            .expect("install code must be valid");
+        let mut registry = Registry::default();
+
+        assert_eq!(registry.passphrase(DEVICE), Passphrase::WellKnown);
+        registry.add_install_code(DEVICE, &install_code);
+        assert_eq!(
+            registry.passphrase(DEVICE),
+            Passphrase::InstallCode(install_code.link_key())
+        );
+    }
+
+    #[test]
+    fn refuses_negotiations_once_link_keys_are_unsupported() {
+        let mut registry = Registry::default();
+
+        assert!(!registry.is_refused());
+        registry.refuse();
+        assert!(registry.is_refused());
+    }
+
+    #[test]
+    fn reports_recorded_authentication_levels() {
+        let mut registry = Registry::default();
+        let unknown = registry.authentication_level(DEVICE);
+        let level = unknown
+            .device_authentication_level()
+            .expect("response must contain a level");
+
+        assert_eq!(
+            level.initial_join_method(),
+            Ok(InitialJoinMethod::NoAuthentication)
+        );
+        assert_eq!(
+            level.active_link_key_type(),
+            Ok(ActiveLinkKeyType::NotUpdated)
+        );
+
+        registry.record(Passphrase::WellKnown.authentication_level(DEVICE));
+        let known = registry.authentication_level(DEVICE);
+        let level = known
+            .device_authentication_level()
+            .expect("response must contain a level");
+
+        assert_eq!(
+            level.active_link_key_type(),
+            Ok(ActiveLinkKeyType::UnauthenticatedKeyNegotiation)
+        );
+    }
+}
@@ -0,0 +1,89 @@
+//! APS frame counter challenge verification.
+
+use zb_aps::security::verify_challenge_response;
+use zb_core::IeeeAddress;
+use zb_core::security::Key;
+use zb_core::types::tlv::{ApsFrameCounterChallenge, ApsFrameCounterResponse};
+
+/// A random challenge value.
+pub type Challenge = [u8; ApsFrameCounterChallenge::CHALLENGE_SIZE];
+
+/// Return whether `response` answers `challenge` and proves knowledge of `key`.
+///
+/// The response must come from `responder`, echo the challenge, and carry a MIC computed with
+/// `key`.
+pub fn verify(
+    key: &Key,
+    responder: IeeeAddress,
+    challenge: Challenge,
+    response: &ApsFrameCounterResponse,
+) -> bool {
+    response.responder_eui64() == responder
+        && *response.challenge() == challenge
+        && verify_challenge_response(key, response)
+}
+
+#[cfg(test)]
+mod tests {
+    use zb_aps::security::challenge_response_mic;
+    use zb_core::IeeeAddress;
+    use zb_core::security::Key;
+    use zb_core::types::tlv::ApsFrameCounterResponse;
+
+    use super::{Challenge, verify};
+
+    const KEY: Key = Key::new(*b"0123456789ABCDEF");
+    const RESPONDER: IeeeAddress = IeeeAddress::new(1, 2, 3, 4, 5, 6, 7, 8);
+    const CHALLENGE: Challenge = [0xC0, 0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7];
+
+    fn response(key: &Key, challenge: Challenge) -> ApsFrameCounterResponse {
+        let unsigned = ApsFrameCounterResponse::new(RESPONDER, challenge, 0x10, 0x20, [0; 8]);
+        ApsFrameCounterResponse::new(
+            RESPONDER,
+            challenge,
+            0x10,
+            0x20,
+            challenge_response_mic(key, &unsigned),
+        )
+    }
+
+    #[test]
+    fn accepts_a_response_authenticated_with_the_key() {
+        assert!(verify(
+            &KEY,
+            RESPONDER,
+            CHALLENGE,
+            &response(&KEY, CHALLENGE)
+        ));
+    }
+
+    /// Accepts a MIC computed by an independent AES-CCM implementation.
+    #[test]
+    fn accepts_a_known_answer_response() {
+        let known = ApsFrameCounterResponse::new(
+            RESPONDER,
+            CHALLENGE,
+            0x10,
+            0x20,
+            [0x2F, 0xAA, 0x3A, 0x5B, 0x1C, 0xC1, 0xCC, 0xEF],
+        );
+
+        assert!(verify(&KEY, RESPONDER, CHALLENGE, &known));
+        assert_eq!(response(&KEY, CHALLENGE).mic(), known.mic());
+    }
+
+    #[test]
+    fn rejects_a_response_from_another_device() {
+        assert!(!verify(
+            &KEY,
+            IeeeAddress::new(8, 7, 6, 5, 4, 3, 2, 1),
+            CHALLENGE,
+            &response(&KEY, CHALLENGE)
+        ));
+    }
+
+    #[test]
+    fn rejects_a_response_to_another_challenge() {
+        assert!(!verify(&KEY, RESPONDER, CHALLENGE, &response(&KEY, [0; 8])));
+    }
+}
@@ -0,0 +1,229 @@
+//! SPEKE over Curve25519 as used by dynamic link key negotiation.
+//!
+//! Both devices map the pre-shared passphrase to a Curve25519 base point and exchange ephemeral
+//! public points derived from it. A device that does not know the passphrase derives a different
+//! shared secret, so the resulting link key is only shared by devices that know the passphrase.
+
+use le_stream::ToLeStream;
+use sha2::{Digest, Sha256};
+use x25519_dalek::{PublicKey, StaticSecret, x25519};
+use zb_core::IeeeAddress;
+use zb_core::security::{Key, mmo_hash};
+use zb_core::types::tlv::{Curve25519PublicPoint, KeyNegotiationProtocols};
+
+/// A Curve25519 scalar or point in its 32-octet encoding.
+pub type Point = [u8; Curve25519PublicPoint::POINT_SIZE];
+
+/// Hash function of a SPEKE key negotiation protocol.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Hash {
+    /// SPEKE using Curve25519 with hash AES-MMO-128.
+    AesMmo128,
+
+    /// SPEKE using Curve25519 with hash SHA-256.
+    Sha256,
+}
+
+/// Ephemeral responder state of one key negotiation.
+pub struct Exchange {
+    scalar: Point,
+    hash: Hash,
+}
+
+impl Hash {
+    /// Select the hash of the strongest SPEKE protocol the initiator supports.
+    ///
+    /// Initiators that do not advertise their protocols use AES-MMO-128, which every dynamic link
+    /// key capable device supports. Returns `None` if no advertised protocol uses SPEKE.
+    pub const fn select(protocols: Option<KeyNegotiationProtocols>) -> Option<Self> {
+        let Some(protocols) = protocols else {
+            return Some(Self::AesMmo128);
+        };
+
+        if protocols.contains(KeyNegotiationProtocols::SPEKE_USING_CURVE25519_WITH_HASH_SHA256) {
+            Some(Self::Sha256)
+        } else if protocols
+            .contains(KeyNegotiationProtocols::SPEKE_USING_CURVE25519_WITH_HASH_AES_MMO_128)
+        {
+            Some(Self::AesMmo128)
+        } else {
+            None
+        }
+    }
+
+    /// Map the passphrase to the u-coordinate of the SPEKE base point.
+    fn base_point(self, passphrase: &Key) -> Point {
+        let mut point = Point::default();
+
+        match self {
+            Self::AesMmo128 => {
+                let low = mmo_hash(passphrase.as_bytes());
+                let high = mmo_hash(low.as_bytes());
+                point[..Key::SIZE].copy_from_slice(low.as_bytes());
+                point[Key::SIZE..].copy_from_slice(high.as_bytes());
+            }
+            Self::Sha256 => point.copy_from_slice(&Sha256::digest(passphrase.as_bytes())),
+        }
+
+        point
+    }
+
+    /// Derive the link key from the shared secret and the identities of both devices.
+    fn link_key(
+        self,
+        shared_secret: &Point,
+        initiator: IeeeAddress,
+        responder: IeeeAddress,
+    ) -> Key {
+        let input: Vec<u8> = shared_secret
+            .iter()
+            .copied()
+            .chain(initiator.to_le_stream())
+            .chain(responder.to_le_stream())
+            .collect();
+
+        match self {
+            Self::AesMmo128 => mmo_hash(&input),
+            Self::Sha256 => {
+                let mut key = [0; Key::SIZE];
+                key.copy_from_slice(&Sha256::digest(&input)[..Key::SIZE]);
+                Key::new(key)
+            }
+        }
+    }
+}
+
+impl Exchange {
+    /// Create an exchange with the given secret scalar.
+    pub const fn new(scalar: Point, hash: Hash) -> Self {
+        Self { scalar, hash }
+    }
+
+    /// Create an exchange with a random secret scalar.
+    pub fn random(hash: Hash) -> Self {
+        Self::new(rand::random(), hash)
+    }
+
+    /// Return the public point derived from the passphrase's base point.
+    pub fn public_point(&self, passphrase: &Key) -> Point {
+        x25519(self.scalar, self.hash.base_point(passphrase))
+    }
+
+    /// Derive the link key from the peer's public point.
+    ///
+    /// Returns `None` if the peer's point is of low order and would force a known shared secret.
+    pub fn link_key(
+        &self,
+        peer_point: &Point,
+        initiator: IeeeAddress,
+        responder: IeeeAddress,
+    ) -> Option<Key> {
+        let shared_secret =
+            StaticSecret::from(self.scalar).diffie_hellman(&PublicKey::from(*peer_point));
+
+        shared_secret.was_contributory().then(|| {
+            self.hash
+                .link_key(shared_secret.as_bytes(), initiator, responder)
+        })
+    }
+}
+
+impl std::fmt::Debug for Exchange {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("Exchange")
+            .field("hash", &self.hash)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zb_core::IeeeAddress;
+    use zb_core::security::Key;
+    use zb_core::types::tlv::KeyNegotiationProtocols;
+
+    use super::{Exchange, Hash, Point};
+
+    const INITIATOR: IeeeAddress = IeeeAddress::new(1, 2, 3, 4, 5, 6, 7, 8);
+    const RESPONDER: IeeeAddress = IeeeAddress::new(8, 7, 6, 5, 4, 3, 2, 1);
+    const PASSPHRASE: Key = Key::new(*b"0123456789ABCDEF");
+
+    fn negotiate(hash: Hash, initiator_passphrase: &Key) -> (Option<Key>, Option<Key>) {
+        let initiator = Exchange::new([0x11; 32], hash);
+        let responder = Exchange::new([0x22; 32], hash);
+        let initiator_point = initiator.public_point(initiator_passphrase);
+        let responder_point = responder.public_point(&PASSPHRASE);
+
+        (
+            initiator.link_key(&responder_point, INITIATOR, RESPONDER),
+            responder.link_key(&initiator_point, INITIATOR, RESPONDER),
+        )
+    }
+
+    #[test]
+    fn devices_sharing_the_passphrase_derive_the_same_key() {
+        for hash in [Hash::AesMmo128, Hash::Sha256] {
+            let (initiator, responder) = negotiate(hash, &PASSPHRASE);
+
+            assert!(initiator.is_some());
+            assert_eq!(initiator, responder);
+        }
+    }
+
+    #[test]
+    fn devices_with_different_passphrases_derive_different_keys() {
+        let (initiator, responder) = negotiate(Hash::AesMmo128, &Key::new([0; Key::SIZE]));
+
+        assert_ne!(initiator, responder);
+    }
+
+    /// Derives the key from the shared secret of the X25519 test vector of RFC 7748, section 6.1.
+    #[test]
+    fn derives_the_link_key_of_a_known_shared_secret() {
+        let exchange = Exchange::new(
+            [
+                0x77, 0x07, 0x6D, 0x0A, 0x73, 0x18, 0xA5, 0x7D, 0x3C, 0x16, 0xC1, 0x72, 0x51, 0xB2,
+                0x66, 0x45, 0xDF, 0x4C, 0x2F, 0x87, 0xEB, 0xC0, 0x99, 0x2A, 0xB1, 0x77, 0xFB, 0xA5,
+                0x1D, 0xB9, 0x2C, 0x2A,
+            ],
+            Hash::Sha256,
+        );
+        let peer_point = [
+            0xDE, 0x9E, 0xDB, 0x7D, 0x7B, 0x7D, 0xC1, 0xB4, 0xD3, 0x5B, 0x61, 0xC2, 0xEC, 0xE4,
+            0x35, 0x37, 0x3F, 0x83, 0x43, 0xC8, 0x5B, 0x78, 0x67, 0x4D, 0xAD, 0xFC, 0x7E, 0x14,
+            0x6F, 0x88, 0x2B, 0x4F,
+        ];
+
+        assert_eq!(
+            exchange.link_key(&peer_point, INITIATOR, RESPONDER),
+            Some(Key::new([
+                0x49, 0xAD, 0xD5, 0x14, 0x9A, 0x0D, 0xDE, 0xB9, 0xF4, 0x77, 0x78, 0xBA, 0x9C, 0x4A,
+                0x3C, 0x6A,
+            ]))
+        );
+    }
+
+    #[test]
+    fn rejects_low_order_points() {
+        let exchange = Exchange::new([0x22; 32], Hash::AesMmo128);
+
+        assert_eq!(
+            exchange.link_key(&Point::default(), INITIATOR, RESPONDER),
+            None
+        );
+    }
+
+    #[test]
+    fn prefers_sha256() {
+        assert_eq!(Hash::select(None), Some(Hash::AesMmo128));
+        assert_eq!(
+            Hash::select(Some(KeyNegotiationProtocols::all())),
+            Some(Hash::Sha256)
+        );
+        assert_eq!(
+            Hash::select(Some(KeyNegotiationProtocols::STATIC_KEY_REQUEST)),
+            None
+        );
+    }
+}
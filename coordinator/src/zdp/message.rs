@@ -1,8 +1,13 @@
+use std::time::Duration;
+
 use bytes::Bytes;
 use tokio::sync::oneshot::Sender;
 use zb_aps::apsde::{DataIndication, DataRequest};
+use zb_core::IeeeAddress;
+use zb_core::security::InstallCode;
 use zb_core::short_id::Device;
-use zb_zdp::{Command, Frame};
+use zb_core::types::tlv::DeviceAuthenticationLevel;
+use zb_zdp::{Command, Frame, SecurityGetAuthenticationLevelRsp};
 
 use crate::Error;
 use crate::correlation::Token;
@@ -20,7 +25,7 @@ pub enum Message {
     /// Fail pending protocol responses because the Zigbee network went down.
     NetworkDown,
 
-    /// Fail pending protocol responses and stop because hardware events are unavailable.
+    /// Fail pending protocol responses because the hardware session ended.
     HardwareUnavailable,
 
     /// Cancel a pending protocol response whose future was dropped.
@@ -61,13 +66,57 @@ pub enum Message {
         result: Result<crate::aps::TransmissionResponse, Error>,
     },
 
+    /// Retransmit a correlated request whose previous APS transmission failed.
+    Retransmit {
+        /// Coordinator-private identity of the protocol transaction to retransmit.
+        token: Token,
+        /// Encoded APS request of the original transmission.
+        request: DataRequest<Bytes>,
+        /// Channel used to return the deferred APS result, or `None` if the response is no
+        /// longer pending.
+        response: Sender<Option<Result<crate::aps::TransmissionResponse, Error>>>,
+    },
+
+    /// A background retransmission finished its APS actor handoff.
+    RetransmissionFinished {
+        /// Coordinator-private identity of the completed retransmission.
+        id: u64,
+    },
+
     /// Communicate a unicast with an expected response.
     Communicate {
         /// Remote device expected to answer the request.
         device: Device,
         /// Complete APS data-service request.
         request: DataRequest<Bytes>,
+        /// Response timeout overriding the configured default.
+        timeout: Option<Duration>,
         /// The response channel.
         response: Sender<Result<ApsProtocolResponse<Command>, Error>>,
     },
+
+    /// Use an install code as the passphrase of a device's dynamic link key negotiations.
+    AddInstallCode {
+        /// IEEE address of the device owning the install code.
+        ieee_address: IeeeAddress,
+        /// The device's install code.
+        install_code: InstallCode,
+    },
+
+    /// Record the authentication level of a device that verified its negotiated link key.
+    KeyNegotiated {
+        /// The device's new authentication level.
+        level: DeviceAuthenticationLevel,
+    },
+
+    /// Refuse further key negotiations because the NCP cannot store link keys.
+    LinkKeysUnsupported,
+
+    /// Report the authentication level of a device.
+    GetAuthenticationLevel {
+        /// IEEE address of the device of interest.
+        ieee_address: IeeeAddress,
+        /// The response channel.
+        response: Sender<SecurityGetAuthenticationLevelRsp>,
+    },
 }
@@ -2,7 +2,10 @@
 
 use zb_core::node::LogicalType;
 use zb_core::short_id::{Device, ShortId};
-use zb_zdp::Status;
+use zb_core::types::tlv::{Global, Tlv};
+use zb_zdp::{NodeDescRsp, Status};
+
+use crate::aps::TransferLimits;
 
 /// Processing selected for an incoming Node Descriptor request.
 pub(super) enum Action {
@@ -41,6 +44,22 @@ pub(super) const fn unavailable_child_status(child_is_known: bool) -> Status {
     }
 }
 
+/// Record the transfer limits that a Node Descriptor response advertises for its device.
+pub(super) fn learn_transfer_limits(limits: &TransferLimits, response: &NodeDescRsp) {
+    let address = response.nwk_addr_of_interest();
+    let (descriptor, tlvs) = response.clone().into_parts();
+
+    if let Ok(descriptor) = descriptor {
+        limits.learn_descriptor(address, &descriptor);
+    }
+
+    for tlv in tlvs {
+        if let Tlv::Global(Global::FragmentationParameters(parameters)) = tlv {
+            limits.learn_fragmentation(parameters);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use zb_core::node::LogicalType;
@@ -1,19 +1,24 @@
 //! State used while serving incoming ZDP requests.
 
 use le_stream::ToLeStream;
-use log::{error, warn};
+use log::{debug, error, warn};
 use tokio::spawn;
 use tokio::sync::mpsc::WeakSender;
 use zb_aps::apsde::{IndividualEndpoint, NetworkAddress, NetworkDestination, RequestDestination};
 use zb_core::node::Descriptor;
 use zb_core::short_id::Device;
+use zb_core::types::tlv::{
+    ApsFrameCounterChallenge, ApsFrameCounterResponse, Curve25519PublicPoint, Global,
+    KeyNegotiationProtocols, Tlv,
+};
 use zb_core::{ClusterSpecific, Endpoint, IeeeAddress, Profile};
 use zb_hw::NcpHandle;
 use zb_zdp::{
     ActiveEpReq, ActiveEpRsp, Command, DeviceAndServiceDiscovery, Frame, IeeeAddrReq, IeeeAddrRsp,
     IeeeAddrRspResponse, MatchDescReq, MatchDescRsp, MgmtPermitJoiningRsp, NetworkManagement,
     NodeDescReq, NodeDescRsp, NwkAddrReq, NwkAddrRsp, NwkAddrRspResponse, PowerDescReq,
-    PowerDescRsp, RequestType, SimpleDescReq, SimpleDescRsp, Status, SystemServerDiscoveryReq,
+    PowerDescRsp, RequestType, SecurityChallengeReq, SecurityStartKeyNegotiationReq,
+    SecurityStartKeyNegotiationRsp, SimpleDescReq, SimpleDescRsp, Status, SystemServerDiscoveryReq,
     SystemServerDiscoveryRsp,
 };
 
@@ -22,6 +27,7 @@ use super::discovery::{
     DescriptorTarget, LOCAL_NWK_ADDRESS, active_endpoints, descriptor_target, matching_server_mask,
     simple_descriptor,
 };
+use super::key_negotiation::{Challenge, Exchange, Hash, Passphrase, verify_challenge};
 use super::match_desc::{
     Action as MatchDescAction, action as match_desc_action, local_response as local_match_response,
     matching_endpoints,
@@ -29,6 +35,7 @@ use super::match_desc::{
 use super::node_desc::{
     Action as NodeDescAction, action as node_desc_action, unavailable_child_status,
 };
+use crate::Zdp;
 use crate::aps::{Aps, Metadata, TransmissionResponse};
 
 /// Cloneable context used by bounded background ZDP request-serving operations.
@@ -266,7 +273,7 @@ impl Server {
         unavailable_child_status(device_is_known)
     }
 
-    async fn respond_to_source<T>(
+    pub(super) async fn respond_to_source<T>(
         &self,
         source: NetworkAddress,
         seq: u8,
@@ -435,6 +442,137 @@ impl Server {
             error!("Failed to send Mgmt_Permit_Joining_rsp: {error:?}");
         }
     }
+
+    /// Answer a dynamic link key negotiation as responder and verify the negotiated key.
+    ///
+    /// The negotiated key stays pending until the device answers the APS frame counter challenge
+    /// with a MIC computed with it, so a failed negotiation leaves the device's current link key on
+    /// the NCP. A verified key is then stored on the NCP and the device's new authentication level
+    /// is reported to the actor. NCPs that cannot store link keys are reported so that later
+    /// negotiations are refused before answering.
+    pub(super) async fn handle_security_start_key_negotiation_req(
+        &self,
+        source: NetworkAddress,
+        seq: u8,
+        request: SecurityStartKeyNegotiationReq,
+        passphrase: Passphrase,
+    ) {
+        let Some(initiator) = request.curve25519_public_point().cloned() else {
+            self.reject_key_negotiation(source, seq, Status::InvalidRequestType)
+                .await;
+            return;
+        };
+        let Some(hash) = Hash::select(request.tlvs().iter().find_map(|tlv| match tlv {
+            Tlv::Global(Global::SupportedKeyNegotiationMethods(methods)) => {
+                Some(methods.key_negotiation_protocols())
+            }
+            _ => None::<KeyNegotiationProtocols>,
+        })) else {
+            self.reject_key_negotiation(source, seq, Status::NotSupported)
+                .await;
+            return;
+        };
+        let local_address = match self.ncp.get_ieee_address().await {
+            Ok(local_address) => local_address,
+            Err(error) => {
+                error!("Failed to read the coordinator IEEE address for key negotiation: {error}");
+                return;
+            }
+        };
+        let device = initiator.device_eui64();
+        let exchange = Exchange::random(hash);
+        let Some(link_key) = exchange.link_key(initiator.public_point(), device, local_address)
+        else {
+            warn!("Rejecting key negotiation of {device} with a low-order public point");
+            self.reject_key_negotiation(source, seq, Status::NotAuthorized)
+                .await;
+            return;
+        };
+
+        let public_point =
+            Curve25519PublicPoint::new(local_address, exchange.public_point(&passphrase.key()));
+        let response = SecurityStartKeyNegotiationRsp::new(
+            Status::Success.into(),
+            Box::new([Tlv::Local(public_point)]),
+        );
+        self.respond_to_source(source, seq, response, "Security_Start_Key_Negotiation_rsp")
+            .await;
+
+        let challenge: Challenge = rand::random();
+        if self
+            .challenge(source, local_address, challenge)
+            .await
+            .is_none_or(|response| !verify_challenge(&link_key, device, challenge, &response))
+        {
+            warn!("Device {device} failed to verify its negotiated link key");
+            return;
+        }
+
+        match self.ncp.set_link_key(device, link_key).await {
+            Ok(()) => {}
+            Err(zb_hw::Error::Unsupported(_)) => {
+                error!("The NCP cannot store the negotiated link key of {device}");
+                self.report(Message::LinkKeysUnsupported).await;
+                return;
+            }
+            Err(error) => {
+                error!("Failed to store the negotiated link key of {device}: {error}");
+                return;
+            }
+        }
+
+        self.report(Message::KeyNegotiated {
+            level: passphrase.authentication_level(device),
+        })
+        .await;
+    }
+
+    /// Send an APS frame counter challenge and return the device's response TLV.
+    async fn challenge(
+        &self,
+        source: NetworkAddress,
+        local_address: IeeeAddress,
+        challenge: Challenge,
+    ) -> Option<ApsFrameCounterResponse> {
+        let device = Device::try_from(source.as_u16())
+            .inspect_err(|error| warn!("Invalid node ID: {error:?}"))
+            .ok()?;
+        let inbox = self.inbox.upgrade()?;
+        let request = SecurityChallengeReq::new(Box::new([Tlv::Local(
+            ApsFrameCounterChallenge::new(local_address, challenge),
+        )]));
+        let response = async { inbox.communicate(device, request).await?.await };
+
+        match response.await {
+            Ok(response) => response.aps_frame_counter_response().cloned(),
+            Err(error) => {
+                warn!("Security challenge of {device} failed: {error}");
+                None
+            }
+        }
+    }
+
+    /// Reject a key negotiation with the given status.
+    pub(super) async fn reject_key_negotiation(
+        &self,
+        source: NetworkAddress,
+        seq: u8,
+        status: Status,
+    ) {
+        let response = SecurityStartKeyNegotiationRsp::new(status.into(), Box::new([]));
+        self.respond_to_source(source, seq, response, "Security_Start_Key_Negotiation_rsp")
+            .await;
+    }
+
+    /// Report a server operation result through the actor inbox.
+    async fn report(&self, message: Message) {
+        let Some(inbox) = self.inbox.upgrade() else {
+            return;
+        };
+        inbox.send(message).await.unwrap_or_else(|error| {
+            debug!("Failed to report ZDP server operation result: {error}");
+        });
+    }
 }
 
 /// Construction of requests for background server operations.
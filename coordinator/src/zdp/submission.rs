@@ -1,7 +1,9 @@
-//! State for ZDP requests being submitted to APS.
+//! State for ZDP requests being submitted or resubmitted to APS.
 
+use bytes::Bytes;
 use tokio::sync::oneshot::{Receiver, Sender};
 use tokio::task::AbortHandle;
+use zb_aps::apsde::DataRequest;
 use zb_zdp::Command;
 
 use crate::correlation::Token;
@@ -11,7 +13,15 @@ use crate::response::ApsProtocolResponse;
 #[derive(Debug)]
 pub(super) struct CommunicationSubmission {
     pub(super) token: Token,
+    pub(super) request: DataRequest<Bytes>,
     pub(super) protocol_response: Receiver<Result<Command, crate::Error>>,
     pub(super) response: Sender<Result<ApsProtocolResponse<Command>, crate::Error>>,
     pub(super) task: AbortHandle,
 }
+
+/// Actor-owned state retained while a failed ZDP request is being handed to APS again.
+#[derive(Debug)]
+pub(super) struct Retransmission {
+    pub(super) token: Token,
+    pub(super) task: AbortHandle,
+}
@@ -131,6 +131,100 @@ pub enum Cluster {
     )]
     Time = 0x000A,
 
+    /// Analog Input (`BACnet` regular) cluster.
+    #[strum(
+        to_string = "AnalogInput (0x000C)",
+        serialize = "AnalogInput",
+        serialize = "12",
+        serialize = "0x000C",
+        serialize = "0x000c"
+    )]
+    AnalogInput = 0x000C,
+
+    /// Analog Output (`BACnet` regular) cluster.
+    #[strum(
+        to_string = "AnalogOutput (0x000D)",
+        serialize = "AnalogOutput",
+        serialize = "13",
+        serialize = "0x000D",
+        serialize = "0x000d"
+    )]
+    AnalogOutput = 0x000D,
+
+    /// Analog Value (`BACnet` regular) cluster.
+    #[strum(
+        to_string = "AnalogValue (0x000E)",
+        serialize = "AnalogValue",
+        serialize = "14",
+        serialize = "0x000E",
+        serialize = "0x000e"
+    )]
+    AnalogValue = 0x000E,
+
+    /// Binary Input (`BACnet` regular) cluster.
+    #[strum(
+        to_string = "BinaryInput (0x000F)",
+        serialize = "BinaryInput",
+        serialize = "15",
+        serialize = "0x000F",
+        serialize = "0x000f"
+    )]
+    BinaryInput = 0x000F,
+
+    /// Binary Output (`BACnet` regular) cluster.
+    #[strum(
+        to_string = "BinaryOutput (0x0010)",
+        serialize = "BinaryOutput",
+        serialize = "16",
+        serialize = "0x0010"
+    )]
+    BinaryOutput = 0x0010,
+
+    /// Binary Value (`BACnet` regular) cluster.
+    #[strum(
+        to_string = "BinaryValue (0x0011)",
+        serialize = "BinaryValue",
+        serialize = "17",
+        serialize = "0x0011"
+    )]
+    BinaryValue = 0x0011,
+
+    /// Multistate Input (`BACnet` regular) cluster.
+    #[strum(
+        to_string = "MultistateInput (0x0012)",
+        serialize = "MultistateInput",
+        serialize = "18",
+        serialize = "0x0012"
+    )]
+    MultistateInput = 0x0012,
+
+    /// Multistate Output (`BACnet` regular) cluster.
+    #[strum(
+        to_string = "MultistateOutput (0x0013)",
+        serialize = "MultistateOutput",
+        serialize = "19",
+        serialize = "0x0013"
+    )]
+    MultistateOutput = 0x0013,
+
+    /// Multistate Value (`BACnet` regular) cluster.
+    #[strum(
+        to_string = "MultistateValue (0x0014)",
+        serialize = "MultistateValue",
+        serialize = "20",
+        serialize = "0x0014"
+    )]
+    MultistateValue = 0x0014,
+
+    /// Poll Control cluster.
+    #[strum(
+        to_string = "PollControl (0x0020)",
+        serialize = "PollControl",
+        serialize = "32",
+        serialize = "0x0020"
+    )]
+    PollControl = 0x0020,
+
     /// OTA Upgrade cluster.
     #[strum(
         to_string = "OtaUpgrade (0x0019)",
@@ -194,6 +288,25 @@ pub enum Cluster {
     )]
     IasZone = 0x0500,
 
+    /// Diagnostics cluster.
+    #[strum(
+        to_string = "Diagnostics (0x0B05)",
+        serialize = "Diagnostics",
+        serialize = "2821",
+        serialize = "0x0B05",
+        serialize = "0x0b05"
+    )]
+    Diagnostics = 0x0B05,
+
+    /// Touchlink (ZLL) Commissioning cluster.
+    #[strum(
+        to_string = "TouchlinkCommissioning (0x1000)",
+        serialize = "TouchlinkCommissioning",
+        serialize = "4096",
+        serialize = "0x1000"
+    )]
+    TouchlinkCommissioning = 0x1000,
+
     /// Keep-Alive cluster.
     #[strum(
         to_string = "KeepAlive (0x0025)",
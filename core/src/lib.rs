@@ -39,6 +39,7 @@ mod group_id;
 mod ieee_address;
 pub mod node;
 mod profile;
+pub mod security;
 /// Zigbee NWK short-address domain types.
 pub mod short_id;
 mod traits;
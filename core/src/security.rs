@@ -0,0 +1,14 @@
+//! Zigbee security key material.
+//!
+//! This module contains the 128-bit [`Key`] value used for network and link keys, the AES-MMO
+//! hash and HMAC functions defined by the Zigbee specification, and validated [`InstallCode`]
+//! values from which preconfigured trust-center link keys are derived.
+
+pub use self::install_code::{InstallCode, ParseInstallCodeError};
+pub use self::key::{Key, ParseKeyError};
+pub use self::mmo::{MmoHasher, hmac_mmo, mmo_hash};
+
+mod hex;
+mod install_code;
+mod key;
+mod mmo;
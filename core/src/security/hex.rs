@@ -0,0 +1,25 @@
+/// Iterate over the hexadecimal digits of `text`, skipping common group separators.
+///
+/// Install codes and keys are commonly printed in groups separated by spaces, colons, or dashes.
+pub fn digits(text: &str) -> impl Iterator<Item = Option<u8>> + '_ {
+    text.chars()
+        .filter(|char| !matches!(char, ' ' | ':' | '-'))
+        .map(|char| char.to_digit(16).and_then(|digit| u8::try_from(digit).ok()))
+}
+
+/// Decode hexadecimal `text` into `buffer`, returning the number of decoded bytes.
+///
+/// Returns `None` if the text contains a non-hexadecimal character, an odd number of digits, or
+/// more bytes than `buffer` can hold.
+pub fn decode(text: &str, buffer: &mut [u8]) -> Option<usize> {
+    let mut digits = digits(text);
+    let mut len = 0;
+
+    while let Some(high) = digits.next() {
+        let low = digits.next()??;
+        *buffer.get_mut(len)? = (high? << 4) | low;
+        len += 1;
+    }
+
+    Some(len)
+}
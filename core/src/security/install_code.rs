@@ -0,0 +1,262 @@
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+
+use heapless::Vec;
+use thiserror::Error;
+
+use super::{Key, hex, mmo_hash};
+
+const CRC_SIZE: usize = 2;
+const CRC_INITIAL: u16 = 0xFFFF;
+const CRC_POLYNOMIAL: u16 = 0x8408;
+const CRC_FINAL_XOR: u16 = 0xFFFF;
+const MAX_CODE_SIZE: usize = 16;
+const MAX_SIZE: usize = MAX_CODE_SIZE + CRC_SIZE;
+
+/// A validated Zigbee install code.
+///
+/// An install code consists of 6, 8, 12, or 16 random octets followed by their CRC-16/X-25
+/// checksum in little-endian order. The trust center derives the device's preconfigured link key
+/// from the complete code, including the checksum, with the AES-MMO hash.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_with::SerializeDisplay, serde_with::DeserializeFromStr)
+)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct InstallCode(Vec<u8, MAX_SIZE>);
+
+impl InstallCode {
+    /// Octet counts of valid install codes, excluding the CRC.
+    pub const CODE_SIZES: [usize; 4] = [6, 8, 12, 16];
+
+    /// Create an install code from its code octets followed by the little-endian CRC.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseInstallCodeError`] if the code does not have a valid length or the CRC does
+    /// not match the code octets.
+    pub fn new(bytes: &[u8]) -> Result<Self, ParseInstallCodeError> {
+        let code_size = bytes.len().saturating_sub(CRC_SIZE);
+
+        if !Self::CODE_SIZES.contains(&code_size) {
+            return Err(ParseInstallCodeError::InvalidLength(code_size));
+        }
+
+        let (code, crc) = bytes.split_at(code_size);
+        let expected = crc16(code);
+        let actual = u16::from_le_bytes([crc[0], crc[1]]);
+
+        if expected != actual {
+            return Err(ParseInstallCodeError::CrcMismatch { expected, actual });
+        }
+
+        Vec::from_slice(bytes)
+            .map(Self)
+            .map_err(|_| ParseInstallCodeError::InvalidLength(code_size))
+    }
+
+    /// Create an install code from its code octets and append the matching CRC.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseInstallCodeError::InvalidLength`] if the code is not 6, 8, 12, or 16 octets
+    /// long.
+    pub fn with_crc(code: &[u8]) -> Result<Self, ParseInstallCodeError> {
+        if !Self::CODE_SIZES.contains(&code.len()) {
+            return Err(ParseInstallCodeError::InvalidLength(code.len()));
+        }
+
+        let mut bytes = [0; MAX_SIZE];
+        bytes[..code.len()].copy_from_slice(code);
+        bytes[code.len()..code.len() + CRC_SIZE].copy_from_slice(&crc16(code).to_le_bytes());
+        Self::new(&bytes[..code.len() + CRC_SIZE])
+    }
+
+    /// Return the code octets without the CRC.
+    #[must_use]
+    pub fn code(&self) -> &[u8] {
+        &self.0[..self.0.len() - CRC_SIZE]
+    }
+
+    /// Return the CRC-16 of the code octets.
+    #[must_use]
+    pub fn crc(&self) -> u16 {
+        let crc = &self.0[self.0.len() - CRC_SIZE..];
+        u16::from_le_bytes([crc[0], crc[1]])
+    }
+
+    /// Return the code octets followed by the little-endian CRC.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Derive the preconfigured link key from this install code.
+    #[must_use]
+    pub fn link_key(&self) -> Key {
+        mmo_hash(&self.0)
+    }
+}
+
+impl AsRef<[u8]> for InstallCode {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Display for InstallCode {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        self.0
+            .iter()
+            .try_for_each(|byte| write!(formatter, "{byte:02X}"))
+    }
+}
+
+impl FromStr for InstallCode {
+    type Err = ParseInstallCodeError;
+
+    /// Parse hexadecimal code and CRC digits, optionally grouped by spaces, colons, or dashes.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0; MAX_SIZE];
+        let len = hex::decode(text, &mut bytes).ok_or(ParseInstallCodeError::InvalidFormat)?;
+        Self::new(&bytes[..len])
+    }
+}
+
+impl TryFrom<&[u8]> for InstallCode {
+    type Error = ParseInstallCodeError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::new(bytes)
+    }
+}
+
+/// Error returned when an install code cannot be validated.
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+#[expect(
+    variant_size_differences,
+    reason = "the rejected length is reported losslessly while format errors carry no data"
+)]
+pub enum ParseInstallCodeError {
+    /// The code is not 6, 8, 12, or 16 octets long, excluding the CRC.
+    #[error("Invalid install code length: {0} octets")]
+    InvalidLength(usize),
+
+    /// The CRC does not match the code octets.
+    #[error("Install code CRC mismatch: expected {expected:#06X}, found {actual:#06X}")]
+    CrcMismatch {
+        /// CRC computed over the code octets.
+        expected: u16,
+        /// CRC supplied with the install code.
+        actual: u16,
+    },
+
+    /// The textual install code contains invalid hexadecimal digits.
+    #[error("Invalid install code format")]
+    InvalidFormat,
+}
+
+/// Compute the CRC-16/X-25 checksum used by Zigbee install codes.
+fn crc16(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(CRC_INITIAL, |mut crc, byte| {
+        crc ^= u16::from(*byte);
+
+        for _ in 0..u8::BITS {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ CRC_POLYNOMIAL
+            };
+        }
+
+        crc
+    }) ^ CRC_FINAL_XOR
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::string::ToString;
+
+    use super::{InstallCode, ParseInstallCodeError};
+    use crate::security::Key;
+
+    const CODE_16: &str = "83FED3407A939723A5C639B26916D505C3B5";
+    const KEY_16: &str = "66B6900981E1EE3CA4206B6B861C02BB";
+    const CODE_6: [u8; 6] = [0x11, 0x22, 0x33, 0x44, 0x55, 0xAB];
+
+    #[test]
+    fn derives_the_specification_link_key() {
+        let code: InstallCode = CODE_16
+            .parse()
+            .expect("specification install code is valid");
+
+        assert_eq!(code.crc(), 0xB5C3);
+        assert_eq!(code.code().len(), 16);
+        assert_eq!(
+            code.link_key(),
+            KEY_16.parse::<Key>().expect("key is valid")
+        );
+        assert_eq!(code.to_string(), CODE_16);
+    }
+
+    #[test]
+    fn appends_a_crc_to_short_codes() {
+        let code = InstallCode::with_crc(&CODE_6).expect("six-octet codes are valid");
+
+        assert_eq!(
+            code.as_bytes(),
+            &[0x11, 0x22, 0x33, 0x44, 0x55, 0xAB, 0xB3, 0x7D]
+        );
+        assert_eq!(InstallCode::new(code.as_bytes()), Ok(code));
+    }
+
+    #[test]
+    fn parses_grouped_text() {
+        assert!(
+            "83FE-D340-7A93-9723-A5C6-39B2-6916-D505-C3B5"
+                .parse::<InstallCode>()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn rejects_a_mismatching_crc() {
+        assert_eq!(
+            "83FED3407A939723A5C639B26916D505C3B4".parse::<InstallCode>(),
+            Err(ParseInstallCodeError::CrcMismatch {
+                expected: 0xB5C3,
+                actual: 0xB4C3,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_lengths() {
+        assert_eq!(
+            InstallCode::new(&[0; 12]),
+            Err(ParseInstallCodeError::InvalidLength(10))
+        );
+        assert_eq!(
+            InstallCode::with_crc(&[0; 7]),
+            Err(ParseInstallCodeError::InvalidLength(7))
+        );
+        assert_eq!(
+            InstallCode::with_crc(&[0; 17]),
+            Err(ParseInstallCodeError::InvalidLength(17))
+        );
+        assert_eq!(
+            InstallCode::with_crc(&[0; 18]),
+            Err(ParseInstallCodeError::InvalidLength(18))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_text() {
+        assert_eq!(
+            "83FED3407A939723A5C639B26916D505C3B".parse::<InstallCode>(),
+            Err(ParseInstallCodeError::InvalidFormat)
+        );
+    }
+}
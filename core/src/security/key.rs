@@ -0,0 +1,145 @@
+use core::fmt::{self, Display, Formatter, LowerHex, UpperHex};
+use core::str::FromStr;
+
+use le_stream::{FromLeStream, ToLeStream};
+use thiserror::Error;
+
+use super::hex;
+
+/// A 128-bit Zigbee security key.
+///
+/// The same representation is used for network keys, trust-center link keys, and application
+/// link keys. The key octets are stored in transmission order. With the `serde` feature enabled,
+/// keys serialize through their 32-digit hexadecimal display format.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_with::SerializeDisplay, serde_with::DeserializeFromStr)
+)]
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, FromLeStream, ToLeStream,
+)]
+pub struct Key([u8; Self::SIZE]);
+
+impl Key {
+    /// Size of a Zigbee security key in octets.
+    pub const SIZE: usize = 16;
+
+    /// The well-known default trust-center link key `ZigBeeAlliance09`.
+    pub const DEFAULT_TRUST_CENTER_LINK_KEY: Self = Self::new(*b"ZigBeeAlliance09");
+
+    /// Create a key from its octets.
+    #[must_use]
+    pub const fn new(bytes: [u8; Self::SIZE]) -> Self {
+        Self(bytes)
+    }
+
+    /// Return the key octets.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8; Self::SIZE] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for Key {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl From<[u8; Self::SIZE]> for Key {
+    fn from(bytes: [u8; Self::SIZE]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl From<Key> for [u8; Key::SIZE] {
+    fn from(key: Key) -> Self {
+        key.0
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        UpperHex::fmt(self, formatter)
+    }
+}
+
+impl LowerHex for Key {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        self.as_bytes()
+            .iter()
+            .try_for_each(|byte| write!(formatter, "{byte:02x}"))
+    }
+}
+
+impl UpperHex for Key {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        self.as_bytes()
+            .iter()
+            .try_for_each(|byte| write!(formatter, "{byte:02X}"))
+    }
+}
+
+impl FromStr for Key {
+    type Err = ParseKeyError;
+
+    /// Parse 32 hexadecimal digits, optionally grouped by spaces, colons, or dashes.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut bytes = [0; Self::SIZE];
+
+        match hex::decode(text, &mut bytes) {
+            Some(Self::SIZE) => Ok(Self::new(bytes)),
+            _ => Err(ParseKeyError),
+        }
+    }
+}
+
+/// Error returned when parsing a key that is not exactly 32 hexadecimal digits.
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+#[error("Invalid security key")]
+pub struct ParseKeyError;
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::format;
+
+    use super::{Key, ParseKeyError};
+
+    const KEY: Key = Key::new([
+        0x66, 0xB6, 0x90, 0x09, 0x81, 0xE1, 0xEE, 0x3C, 0xA4, 0x20, 0x6B, 0x6B, 0x86, 0x1C, 0x02,
+        0xBB,
+    ]);
+
+    #[test]
+    fn displays_as_uppercase_hexadecimal() {
+        assert_eq!(format!("{KEY}"), "66B6900981E1EE3CA4206B6B861C02BB");
+        assert_eq!(format!("{KEY:x}"), "66b6900981e1ee3ca4206b6b861c02bb");
+    }
+
+    #[test]
+    fn parses_grouped_hexadecimal() {
+        assert_eq!(
+            "66:B6:90:09:81:E1:EE:3C:A4:20:6B:6B:86:1C:02:BB".parse(),
+            Ok(KEY)
+        );
+        assert_eq!("66b6 9009 81e1 ee3c a420 6b6b 861c 02bb".parse(), Ok(KEY));
+    }
+
+    #[test]
+    fn rejects_short_long_and_malformed_keys() {
+        assert_eq!(
+            "66B6900981E1EE3CA4206B6B861C02".parse::<Key>(),
+            Err(ParseKeyError)
+        );
+        assert_eq!(
+            "66B6900981E1EE3CA4206B6B861C02BB00".parse::<Key>(),
+            Err(ParseKeyError)
+        );
+        assert_eq!(
+            "66B6900981E1EE3CA4206B6B861C02BG".parse::<Key>(),
+            Err(ParseKeyError)
+        );
+    }
+}
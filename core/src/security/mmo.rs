@@ -0,0 +1,230 @@
+use aes::Aes128;
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockEncrypt, KeyInit};
+
+use super::Key;
+
+const BLOCK_SIZE: usize = 16;
+const PADDING_MARKER: u8 = 0x80;
+const SHORT_LENGTH_FIELD: usize = 2;
+const LONG_LENGTH_FIELD: usize = 6;
+const HMAC_INNER_PAD: u8 = 0x36;
+const HMAC_OUTER_PAD: u8 = 0x5C;
+
+/// Hash `message` with the Zigbee AES-MMO (Matyas-Meyer-Oseas) construction.
+///
+/// Each 16-octet block is encrypted with AES-128 under the previous hash value and `XORed` with
+/// itself. The message is padded with a single set bit, zero bits, and its bit length, using the
+/// 16-bit length form for messages shorter than 2¹⁶ bits and the 32-bit form otherwise.
+///
+/// # Panics
+///
+/// Panics if `message` is 2³² bits (512 MiB) or longer, which the Zigbee padding cannot encode.
+#[must_use]
+pub fn mmo_hash(message: &[u8]) -> Key {
+    let mut mmo = MmoHasher::new();
+    mmo.update(message);
+    mmo.finalize()
+}
+
+/// Compute the keyed-hash message authentication code of `message` using AES-MMO.
+///
+/// This is the `HMAC` construction from the Zigbee specification with a block size of 16 octets.
+/// It derives, among others, the key-transport and key-load keys from a link key.
+///
+/// # Panics
+///
+/// Panics if `message` is 2³² bits (512 MiB) or longer, which the Zigbee padding cannot encode.
+#[must_use]
+pub fn hmac_mmo(key: &Key, message: &[u8]) -> Key {
+    let mut inner = MmoHasher::new();
+    inner.update(&key.as_bytes().map(|byte| byte ^ HMAC_INNER_PAD));
+    inner.update(message);
+    let inner = inner.finalize();
+
+    let mut outer = MmoHasher::new();
+    outer.update(&key.as_bytes().map(|byte| byte ^ HMAC_OUTER_PAD));
+    outer.update(inner.as_bytes());
+    outer.finalize()
+}
+
+/// Incremental AES-MMO hash state.
+///
+/// Feeding a message in several [`update`](Self::update) calls yields the same hash as
+/// [`mmo_hash`] over the concatenated message.
+#[derive(Clone, Debug, Default)]
+pub struct MmoHasher {
+    hash: [u8; BLOCK_SIZE],
+    block: [u8; BLOCK_SIZE],
+    buffered: usize,
+    len: usize,
+}
+
+impl MmoHasher {
+    /// Create the hash state of an empty message.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            hash: [0; BLOCK_SIZE],
+            block: [0; BLOCK_SIZE],
+            buffered: 0,
+            len: 0,
+        }
+    }
+
+    /// Return the number of octets hashed so far.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return whether no octets have been hashed.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Append `message` to the hashed data.
+    pub fn update(&mut self, mut message: &[u8]) {
+        self.len += message.len();
+
+        while !message.is_empty() {
+            let take = message.len().min(BLOCK_SIZE - self.buffered);
+            let (head, tail) = message.split_at(take);
+            self.block[self.buffered..self.buffered + take].copy_from_slice(head);
+            self.buffered += take;
+            message = tail;
+
+            if self.buffered == BLOCK_SIZE {
+                compress(&mut self.hash, &self.block);
+                self.buffered = 0;
+            }
+        }
+    }
+
+    /// Pad the hashed data and return its AES-MMO hash.
+    ///
+    /// # Panics
+    ///
+    /// Panics if 2³² bits (512 MiB) or more were hashed, which the Zigbee padding cannot encode.
+    #[must_use]
+    pub fn finalize(mut self) -> Key {
+        let bits = self.len * 8;
+        let length_field = if bits < 1 << 16 {
+            SHORT_LENGTH_FIELD
+        } else {
+            LONG_LENGTH_FIELD
+        };
+        let mut tail = [0; 2 * BLOCK_SIZE];
+        tail[..self.buffered].copy_from_slice(&self.block[..self.buffered]);
+        tail[self.buffered] = PADDING_MARKER;
+        let tail_len = if self.buffered + 1 + length_field <= BLOCK_SIZE {
+            BLOCK_SIZE
+        } else {
+            2 * BLOCK_SIZE
+        };
+
+        if length_field == SHORT_LENGTH_FIELD {
+            let bits = u16::try_from(bits).expect("short bit length fits into 16 bits");
+            tail[tail_len - SHORT_LENGTH_FIELD..tail_len].copy_from_slice(&bits.to_be_bytes());
+        } else {
+            let bits =
+                u32::try_from(bits).expect("AES-MMO messages must be shorter than 2^32 bits");
+            tail[tail_len - LONG_LENGTH_FIELD..tail_len - SHORT_LENGTH_FIELD]
+                .copy_from_slice(&bits.to_be_bytes());
+        }
+
+        for block in tail[..tail_len].chunks_exact(BLOCK_SIZE) {
+            compress(&mut self.hash, block);
+        }
+
+        Key::new(self.hash)
+    }
+}
+
+fn compress(hash: &mut [u8; BLOCK_SIZE], block: &[u8]) {
+    let cipher = Aes128::new(GenericArray::from_slice(hash));
+    let mut output = GenericArray::clone_from_slice(block);
+    cipher.encrypt_block(&mut output);
+
+    for ((hash, output), input) in hash.iter_mut().zip(output).zip(block) {
+        *hash = output ^ input;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MmoHasher, hmac_mmo, mmo_hash};
+    use crate::security::Key;
+
+    #[test]
+    fn hashes_a_single_octet() {
+        // Zigbee specification, annex C.5.1: single-octet message.
+        assert_eq!(
+            mmo_hash(&[0xC0]),
+            Key::new([
+                0xAE, 0x3A, 0x10, 0x2A, 0x28, 0xD4, 0x3E, 0xE0, 0xD4, 0xA0, 0x9E, 0x22, 0x78, 0x8B,
+                0x20, 0x6C
+            ])
+        );
+    }
+
+    #[test]
+    fn hashes_a_full_block() {
+        // Zigbee specification, annex C.5.2: sixteen-octet message.
+        assert_eq!(
+            mmo_hash(&[
+                0xC0, 0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7, 0xC8, 0xC9, 0xCA, 0xCB, 0xCC, 0xCD,
+                0xCE, 0xCF
+            ]),
+            Key::new([
+                0xA7, 0x97, 0x7E, 0x88, 0xBC, 0x0B, 0x61, 0xE8, 0x21, 0x08, 0x27, 0x10, 0x9A, 0x22,
+                0x8F, 0x2D
+            ])
+        );
+    }
+
+    #[test]
+    fn pads_into_a_second_block_when_the_length_does_not_fit() {
+        assert_eq!(
+            mmo_hash(&[
+                0xC0, 0xC1, 0xC2, 0xC3, 0xC4, 0xC5, 0xC6, 0xC7, 0xC8, 0xC9, 0xCA, 0xCB, 0xCC, 0xCD,
+                0xCE
+            ]),
+            Key::new([
+                0x0E, 0xD9, 0xE3, 0x56, 0x68, 0xFE, 0x9E, 0x54, 0x6F, 0x25, 0x27, 0x1E, 0x36, 0xC6,
+                0xA5, 0xBC
+            ])
+        );
+    }
+
+    #[test]
+    fn incremental_updates_match_the_one_shot_hash() {
+        let message: [u8; 37] =
+            core::array::from_fn(|index| u8::try_from(index).expect("index fits into u8"));
+        let mut hasher = MmoHasher::new();
+        for chunk in message.chunks(5) {
+            hasher.update(chunk);
+        }
+
+        assert_eq!(hasher.len(), message.len());
+        assert_eq!(hasher.finalize(), mmo_hash(&message));
+    }
+
+    #[test]
+    fn authenticates_a_single_octet() {
+        // Zigbee specification, annex C.6.1: keyed hash of a single-octet message.
+        let key = Key::new([
+            0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0x4A, 0x4B, 0x4C, 0x4D,
+            0x4E, 0x4F,
+        ]);
+
+        assert_eq!(
+            hmac_mmo(&key, &[0xC0]),
+            Key::new([
+                0x45, 0x12, 0x80, 0x7B, 0xF9, 0x4C, 0xB3, 0x40, 0x0F, 0x0E, 0x2C, 0x25, 0xFB, 0x76,
+                0xE9, 0x99
+            ])
+        );
+    }
+}
@@ -6,8 +6,8 @@ use le_stream::{FromLeStream, ToLeStream};
 use repr_discriminant::ReprDiscriminant;
 
 pub use self::analog::{
-    Int8, Int16, Int24, Int32, Int40, Int48, Int56, Int64, Uint8, Uint16, Uint24, Uint32, Uint40,
-    Uint48, Uint56, Uint64,
+    Float32, Int8, Int16, Int24, Int32, Int40, Int48, Int56, Int64, Uint8, Uint16, Uint24, Uint32,
+    Uint40, Uint48, Uint56, Uint64,
 };
 pub use self::channel_list::{ChannelList, Pages};
 pub use self::channels_field::ChannelsField;
@@ -116,6 +116,24 @@ zigbee_type_newtype! {
     BacnetObjectId(u32)
 }
 
+impl From<BacnetObjectId> for Type {
+    fn from(value: BacnetObjectId) -> Self {
+        Self::BacnetObjectId(value)
+    }
+}
+
+impl TryFrom<Type> for BacnetObjectId {
+    type Error = Type;
+
+    fn try_from(value: Type) -> Result<Self, Self::Error> {
+        if let Type::BacnetObjectId(value) = value {
+            Ok(value)
+        } else {
+            Err(value)
+        }
+    }
+}
+
 /// Commonly used type identifiers.
 #[cfg_attr(
     feature = "serde",
@@ -226,6 +244,9 @@ pub enum Type {
     /// 16-bit enumerated type.
     Enum16(Enum16) = 0x31,
 
+    /// Single precision floating point.
+    Float32(Float32) = 0x39,
+
     /// Octet string.
     OctetString(OctStr) = 0x41,
 
@@ -305,6 +326,7 @@ impl_zigbee_type!(0x2e => Int56);
 impl_zigbee_type!(0x2f => Int64);
 impl_zigbee_type!(0x30 => Enum8);
 impl_zigbee_type!(0x31 => Enum16);
+impl_zigbee_type!(0x39 => Float32);
 impl<const CAPACITY: usize> TypeId for OctStr<CAPACITY> {
     const ID: u8 = 0x41;
 }
@@ -337,6 +359,7 @@ mod tests {
         assert_eq!(Enum8::ID, 0x30);
         assert_eq!(Uint16::ID, 0x21);
         assert_eq!(Enum16::ID, 0x31);
+        assert_eq!(Float32::ID, 0x39);
         assert_eq!(ClusterId::ID, 0xe8);
         assert_eq!(AttributeId::ID, 0xe9);
         assert_eq!(BacnetObjectId::ID, 0xea);
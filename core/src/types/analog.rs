@@ -2,6 +2,10 @@
 
 use intx::{I24, I40, I48, I56, U24, U40, U48, U56};
 
+pub use self::float32::Float32;
+
+mod float32;
+
 macro_rules! analog_integer {
     (
         $(#[$attr:meta])*
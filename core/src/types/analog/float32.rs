@@ -0,0 +1,174 @@
+use core::cmp::Ordering;
+
+use le_stream::{FromLeStream, ToLeStream};
+
+/// The canonical quiet NaN, which ZCL uses as the non-value of floating point types.
+const NON_VALUE: u32 = 0x7fc0_0000;
+
+/// The bit pattern of negative zero.
+const NEGATIVE_ZERO: u32 = 0x8000_0000;
+
+/// The `single precision` floating point type, short `single`.
+///
+/// The value is stored as its IEEE 754 bit pattern, so that equality, ordering, and hashing are
+/// total. Every NaN is stored as the non-value and negative zero as positive zero. Values are
+/// ordered by [`f32::total_cmp`], which sorts the non-value after every number.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "Option<f32>", into = "Option<f32>")
+)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, ToLeStream)]
+#[repr(transparent)]
+pub struct Float32(u32);
+
+impl Float32 {
+    /// The non-value.
+    pub const NONE: Self = Self(NON_VALUE);
+
+    /// Create a new value from a floating point number.
+    #[must_use]
+    pub const fn new(value: f32) -> Self {
+        Self::from_bits(value.to_bits())
+    }
+
+    /// Create a new value from its IEEE 754 bit pattern.
+    ///
+    /// Every NaN becomes the non-value and negative zero becomes positive zero.
+    #[must_use]
+    pub const fn from_bits(bits: u32) -> Self {
+        if f32::from_bits(bits).is_nan() {
+            Self::NONE
+        } else if bits == NEGATIVE_ZERO {
+            Self(0)
+        } else {
+            Self(bits)
+        }
+    }
+
+    /// Return the IEEE 754 bit pattern.
+    #[must_use]
+    pub const fn to_bits(self) -> u32 {
+        self.0
+    }
+
+    /// Return the floating point number when it is not the non-value.
+    #[must_use]
+    pub const fn as_option(self) -> Option<f32> {
+        let value = f32::from_bits(self.0);
+
+        if value.is_nan() { None } else { Some(value) }
+    }
+}
+
+impl Ord for Float32 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        f32::from_bits(self.0).total_cmp(&f32::from_bits(other.0))
+    }
+}
+
+impl PartialOrd for Float32 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromLeStream for Float32 {
+    fn from_le_stream<T>(bytes: T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        u32::from_le_stream(bytes).map(Self::from_bits)
+    }
+}
+
+impl core::fmt::Display for Float32 {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        <f32 as core::fmt::Display>::fmt(&f32::from_bits(self.0), formatter)
+    }
+}
+
+impl From<f32> for Float32 {
+    fn from(value: f32) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<Option<f32>> for Float32 {
+    fn from(value: Option<f32>) -> Self {
+        value.map_or(Self::NONE, Self::new)
+    }
+}
+
+impl From<Float32> for Option<f32> {
+    fn from(value: Float32) -> Self {
+        value.as_option()
+    }
+}
+
+impl From<Float32> for crate::types::Type {
+    fn from(value: Float32) -> Self {
+        Self::Float32(value)
+    }
+}
+
+impl TryFrom<crate::types::Type> for Float32 {
+    type Error = crate::types::Type;
+
+    fn try_from(value: crate::types::Type) -> Result<Self, Self::Error> {
+        match value {
+            crate::types::Type::Float32(value) => Ok(value),
+            other => Err(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::vec::Vec;
+
+    use le_stream::{FromLeStream, ToLeStream};
+
+    use super::Float32;
+
+    #[test]
+    fn treats_nan_as_the_non_value() {
+        assert_eq!(Float32::new(21.5).as_option(), Some(21.5));
+        assert_eq!(Float32::NONE.as_option(), None);
+        assert_eq!(Float32::new(f32::NAN).as_option(), None);
+        assert_eq!(Float32::from(None), Float32::NONE);
+    }
+
+    #[test]
+    fn canonicalizes_nan_and_negative_zero() {
+        assert_eq!(Float32::from_bits(0x7fc0_0001), Float32::NONE);
+        assert_eq!(Float32::from_bits(0xffc0_0000).to_bits(), 0x7fc0_0000);
+        assert_eq!(Float32::new(-f32::NAN), Float32::NONE);
+        assert_eq!(Float32::new(-0.0), Float32::new(0.0));
+        assert_eq!(
+            Float32::from_le_stream([0x01, 0x00, 0xc0, 0x7f].into_iter()),
+            Some(Float32::NONE)
+        );
+    }
+
+    #[test]
+    fn orders_by_numeric_value() {
+        assert!(Float32::new(-1.0) < Float32::new(1.0));
+        assert!(Float32::new(-2.0) < Float32::new(-1.0));
+        assert!(Float32::new(f32::NEG_INFINITY) < Float32::new(-0.0));
+        assert!(Float32::new(f32::INFINITY) < Float32::NONE);
+    }
+
+    #[test]
+    fn serializes_the_little_endian_bit_pattern() {
+        let bytes: Vec<u8> = Float32::new(1.0).to_le_stream().collect();
+
+        assert_eq!(bytes, [0x00, 0x00, 0x80, 0x3f]);
+        assert_eq!(
+            Float32::from_le_stream(bytes.into_iter()),
+            Some(Float32::new(1.0))
+        );
+    }
+}
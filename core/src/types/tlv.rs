@@ -10,7 +10,10 @@ pub use self::global::{
     ManufacturerSpecific, NextChannelChange, NextPanIdChange, PanIdConflictReport,
     PreSharedSecrets, RouterInformation, SupportedKeyNegotiation, SymmetricPassphrase,
 };
-pub use self::local::{ClearAllBindingsReqEui64, Local};
+pub use self::local::{
+    ActiveLinkKeyType, ApsFrameCounterChallenge, ApsFrameCounterResponse, ClearAllBindingsReqEui64,
+    Curve25519PublicPoint, DeviceAuthenticationLevel, InitialJoinMethod, Local, TargetIeeeAddress,
+};
 pub use self::tag::Tag;
 
 mod encapsulated_global;
@@ -141,4 +144,43 @@ mod tests {
             Some(SUPPORTED_KEY_NEGOTIATION_METHODS_WITH_SOURCE_DEVICE_EUI64)
         );
     }
+
+    #[test]
+    fn curve25519_public_point_roundtrip() {
+        let tlv: Tlv<Curve25519PublicPoint> = Tlv::Local(Curve25519PublicPoint::new(
+            Eui64::new(1, 2, 3, 4, 5, 6, 7, 8),
+            [0xAB; Curve25519PublicPoint::POINT_SIZE],
+        ));
+        let bytes: Vec<_> = tlv.clone().to_le_stream().collect();
+
+        assert_eq!(bytes[..10], [0, 39, 8, 7, 6, 5, 4, 3, 2, 1]);
+        assert_eq!(bytes.len(), 42);
+        assert_eq!(Tlv::from_le_stream(bytes.into_iter()), Some(tlv));
+    }
+
+    #[test]
+    fn device_authentication_level_from_le_stream() {
+        let bytes = vec![0, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0x03, 0x03];
+        let tlv: Option<Tlv<DeviceAuthenticationLevel>> = Tlv::from_le_stream(bytes.into_iter());
+        let Some(Tlv::Local(level)) = tlv else {
+            panic!("expected a device authentication level TLV");
+        };
+
+        assert_eq!(
+            level.initial_join_method(),
+            Ok(InitialJoinMethod::AuthenticatedKeyNegotiation)
+        );
+        assert_eq!(
+            level.active_link_key_type(),
+            Ok(ActiveLinkKeyType::AuthenticatedKeyNegotiation)
+        );
+    }
+
+    #[test]
+    fn command_specific_local_tlvs_reject_foreign_tags() {
+        let bytes = vec![1, 7, 8, 7, 6, 5, 4, 3, 2, 1];
+        let tlv: Option<Tlv<TargetIeeeAddress>> = Tlv::from_le_stream(bytes.into_iter());
+
+        assert_eq!(tlv, None);
+    }
 }
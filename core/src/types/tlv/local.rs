@@ -1,11 +1,27 @@
 use le_stream::{FromLeStream, ToLeStream};
 
+pub use self::aps_frame_counter_challenge::ApsFrameCounterChallenge;
+pub use self::aps_frame_counter_response::ApsFrameCounterResponse;
 pub use self::clear_all_bindings_req_eui64::ClearAllBindingsReqEui64;
+pub use self::curve25519_public_point::Curve25519PublicPoint;
+pub use self::device_authentication_level::{
+    ActiveLinkKeyType, DeviceAuthenticationLevel, InitialJoinMethod,
+};
+pub use self::target_ieee_address::TargetIeeeAddress;
 use crate::types::tlv::{General, Tag};
 
+mod aps_frame_counter_challenge;
+mod aps_frame_counter_response;
 mod clear_all_bindings_req_eui64;
+mod curve25519_public_point;
+mod device_authentication_level;
+mod target_ieee_address;
 
 /// Local TLV structure.
+///
+/// Local TLV tags are scoped to the command carrying them. This enum covers the commands that use
+/// the default local TLV set. Commands with dedicated local TLVs use those types directly as the
+/// local parameter of [`Tlv`](super::Tlv).
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Local {
     /// Clear All Bindings Request EUI64 List.
@@ -44,3 +60,38 @@ impl ToLeStream for Local {
         General::from(self).to_le_stream()
     }
 }
+
+/// Implement the [`General`] conversions for a command-specific local TLV.
+macro_rules! impl_local_tlv {
+    ($($tlv:ty),* $(,)?) => {
+        $(
+            impl From<$tlv> for General {
+                fn from(tlv: $tlv) -> Self {
+                    Self::serialize(tlv)
+                }
+            }
+
+            impl TryFrom<General> for $tlv {
+                type Error = u8;
+
+                fn try_from(general: General) -> Result<Self, Self::Error> {
+                    let (typ, payload) = general.into_parts();
+
+                    if typ == <Self as Tag>::TAG {
+                        Self::from_le_stream(payload.into_iter()).ok_or(typ)
+                    } else {
+                        Err(typ)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_local_tlv!(
+    ApsFrameCounterChallenge,
+    ApsFrameCounterResponse,
+    Curve25519PublicPoint,
+    DeviceAuthenticationLevel,
+    TargetIeeeAddress,
+);
@@ -0,0 +1,43 @@
+use le_stream::{FromLeStream, ToLeStream};
+
+use crate::Eui64;
+use crate::types::tlv::Tag;
+
+/// APS Frame Counter Challenge TLV.
+///
+/// Carried by the Security Challenge request.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, FromLeStream, ToLeStream)]
+pub struct ApsFrameCounterChallenge {
+    sender_eui64: Eui64,
+    challenge: [u8; Self::CHALLENGE_SIZE],
+}
+
+impl ApsFrameCounterChallenge {
+    /// Size of the challenge value in octets.
+    pub const CHALLENGE_SIZE: usize = 8;
+
+    /// Create a new `ApsFrameCounterChallenge`.
+    #[must_use]
+    pub const fn new(sender_eui64: Eui64, challenge: [u8; Self::CHALLENGE_SIZE]) -> Self {
+        Self {
+            sender_eui64,
+            challenge,
+        }
+    }
+
+    /// Return the EUI-64 of the challenging device.
+    #[must_use]
+    pub const fn sender_eui64(&self) -> Eui64 {
+        self.sender_eui64
+    }
+
+    /// Return the random challenge value.
+    #[must_use]
+    pub const fn challenge(&self) -> &[u8; Self::CHALLENGE_SIZE] {
+        &self.challenge
+    }
+}
+
+impl Tag for ApsFrameCounterChallenge {
+    const TAG: u8 = 0x00;
+}
@@ -0,0 +1,75 @@
+use le_stream::{FromLeStream, ToLeStream};
+
+use super::ApsFrameCounterChallenge;
+use crate::Eui64;
+use crate::types::tlv::Tag;
+
+/// APS Frame Counter Response TLV.
+///
+/// Carried by the Security Challenge response. The MIC authenticates the preceding fields with
+/// the link key shared by the challenging and the responding device.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, FromLeStream, ToLeStream)]
+pub struct ApsFrameCounterResponse {
+    responder_eui64: Eui64,
+    challenge: [u8; ApsFrameCounterChallenge::CHALLENGE_SIZE],
+    aps_frame_counter: u32,
+    challenge_security_frame_counter: u32,
+    mic: [u8; Self::MIC_SIZE],
+}
+
+impl ApsFrameCounterResponse {
+    /// Size of the message integrity code in octets.
+    pub const MIC_SIZE: usize = 8;
+
+    /// Create a new `ApsFrameCounterResponse`.
+    #[must_use]
+    pub const fn new(
+        responder_eui64: Eui64,
+        challenge: [u8; ApsFrameCounterChallenge::CHALLENGE_SIZE],
+        aps_frame_counter: u32,
+        challenge_security_frame_counter: u32,
+        mic: [u8; Self::MIC_SIZE],
+    ) -> Self {
+        Self {
+            responder_eui64,
+            challenge,
+            aps_frame_counter,
+            challenge_security_frame_counter,
+            mic,
+        }
+    }
+
+    /// Return the EUI-64 of the responding device.
+    #[must_use]
+    pub const fn responder_eui64(&self) -> Eui64 {
+        self.responder_eui64
+    }
+
+    /// Return the challenge value echoed by the responding device.
+    #[must_use]
+    pub const fn challenge(&self) -> &[u8; ApsFrameCounterChallenge::CHALLENGE_SIZE] {
+        &self.challenge
+    }
+
+    /// Return the responding device's outgoing APS frame counter.
+    #[must_use]
+    pub const fn aps_frame_counter(&self) -> u32 {
+        self.aps_frame_counter
+    }
+
+    /// Return the frame counter used to compute the MIC.
+    #[must_use]
+    pub const fn challenge_security_frame_counter(&self) -> u32 {
+        self.challenge_security_frame_counter
+    }
+
+    /// Return the message integrity code.
+    #[must_use]
+    pub const fn mic(&self) -> &[u8; Self::MIC_SIZE] {
+        &self.mic
+    }
+}
+
+impl Tag for ApsFrameCounterResponse {
+    const TAG: u8 = 0x00;
+}
@@ -0,0 +1,43 @@
+use le_stream::{FromLeStream, ToLeStream};
+
+use crate::Eui64;
+use crate::types::tlv::Tag;
+
+/// Curve25519 Public Point TLV.
+///
+/// Carried by the Security Start Key Negotiation request and response.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, FromLeStream, ToLeStream)]
+pub struct Curve25519PublicPoint {
+    device_eui64: Eui64,
+    public_point: [u8; Self::POINT_SIZE],
+}
+
+impl Curve25519PublicPoint {
+    /// Size of a Curve25519 public point in octets.
+    pub const POINT_SIZE: usize = 32;
+
+    /// Create a new `Curve25519PublicPoint`.
+    #[must_use]
+    pub const fn new(device_eui64: Eui64, public_point: [u8; Self::POINT_SIZE]) -> Self {
+        Self {
+            device_eui64,
+            public_point,
+        }
+    }
+
+    /// Return the EUI-64 of the device that generated the public point.
+    #[must_use]
+    pub const fn device_eui64(&self) -> Eui64 {
+        self.device_eui64
+    }
+
+    /// Return the public point.
+    #[must_use]
+    pub const fn public_point(&self) -> &[u8; Self::POINT_SIZE] {
+        &self.public_point
+    }
+}
+
+impl Tag for Curve25519PublicPoint {
+    const TAG: u8 = 0x00;
+}
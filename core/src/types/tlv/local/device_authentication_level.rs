@@ -0,0 +1,63 @@
+use le_stream::{FromLeStream, ToLeStream};
+
+pub use self::active_link_key_type::ActiveLinkKeyType;
+pub use self::initial_join_method::InitialJoinMethod;
+use crate::IeeeAddress;
+use crate::types::tlv::Tag;
+
+mod active_link_key_type;
+mod initial_join_method;
+
+/// Device Authentication Level TLV.
+///
+/// Carried by the Security Get Authentication Level response.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, FromLeStream, ToLeStream)]
+pub struct DeviceAuthenticationLevel {
+    remote_node_ieee_address: IeeeAddress,
+    initial_join_method: u8,
+    active_link_key_type: u8,
+}
+
+impl DeviceAuthenticationLevel {
+    /// Create a new `DeviceAuthenticationLevel`.
+    #[must_use]
+    pub fn new(
+        remote_node_ieee_address: IeeeAddress,
+        initial_join_method: InitialJoinMethod,
+        active_link_key_type: ActiveLinkKeyType,
+    ) -> Self {
+        Self {
+            remote_node_ieee_address,
+            initial_join_method: initial_join_method.into(),
+            active_link_key_type: active_link_key_type.into(),
+        }
+    }
+
+    /// Return the IEEE address of the device the level applies to.
+    #[must_use]
+    pub const fn remote_node_ieee_address(&self) -> IeeeAddress {
+        self.remote_node_ieee_address
+    }
+
+    /// Return the method the device used to initially join the network.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw value if it is not a known join method.
+    pub fn initial_join_method(&self) -> Result<InitialJoinMethod, u8> {
+        InitialJoinMethod::try_from(self.initial_join_method)
+    }
+
+    /// Return how the device's active link key was established.
+    ///
+    /// # Errors
+    ///
+    /// Returns the raw value if it is not a known link key type.
+    pub fn active_link_key_type(&self) -> Result<ActiveLinkKeyType, u8> {
+        ActiveLinkKeyType::try_from(self.active_link_key_type)
+    }
+}
+
+impl Tag for DeviceAuthenticationLevel {
+    const TAG: u8 = 0x00;
+}
@@ -0,0 +1,25 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+/// How a device's active link key was established.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, IntoPrimitive, Ord, PartialEq, PartialOrd, TryFromPrimitive,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[num_enum(error_type(name = u8, constructor = core::convert::identity))]
+#[repr(u8)]
+pub enum ActiveLinkKeyType {
+    /// The link key has not been updated since joining.
+    NotUpdated = 0x00,
+
+    /// The link key was obtained with the Request Key method.
+    KeyRequest = 0x01,
+
+    /// The link key was negotiated with the well-known passphrase.
+    UnauthenticatedKeyNegotiation = 0x02,
+
+    /// The link key was negotiated with a pre-shared secret.
+    AuthenticatedKeyNegotiation = 0x03,
+
+    /// The link key was established with application-defined certificate-based authentication.
+    ApplicationDefinedCertificate = 0x04,
+}
@@ -0,0 +1,22 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+/// Method a device used to initially join the network.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, IntoPrimitive, Ord, PartialEq, PartialOrd, TryFromPrimitive,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[num_enum(error_type(name = u8, constructor = core::convert::identity))]
+#[repr(u8)]
+pub enum InitialJoinMethod {
+    /// The device joined without authentication.
+    NoAuthentication = 0x00,
+
+    /// The device joined with an install-code-derived link key.
+    InstallCodeKey = 0x01,
+
+    /// The device negotiated its link key with the well-known passphrase.
+    AnonymousKeyNegotiation = 0x02,
+
+    /// The device negotiated its link key with a pre-shared secret such as an install code.
+    AuthenticatedKeyNegotiation = 0x03,
+}
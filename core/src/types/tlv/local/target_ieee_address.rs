@@ -0,0 +1,30 @@
+use le_stream::{FromLeStream, ToLeStream};
+
+use crate::IeeeAddress;
+use crate::types::tlv::Tag;
+
+/// Target IEEE Address TLV.
+///
+/// Carried by the Security Get Authentication Level request.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, FromLeStream, ToLeStream)]
+pub struct TargetIeeeAddress {
+    ieee_address: IeeeAddress,
+}
+
+impl TargetIeeeAddress {
+    /// Create a new `TargetIeeeAddress`.
+    #[must_use]
+    pub const fn new(ieee_address: IeeeAddress) -> Self {
+        Self { ieee_address }
+    }
+
+    /// Return the IEEE address of the device of interest.
+    #[must_use]
+    pub const fn ieee_address(&self) -> IeeeAddress {
+        self.ieee_address
+    }
+}
+
+impl Tag for TargetIeeeAddress {
+    const TAG: u8 = 0x00;
+}
@@ -0,0 +1,38 @@
+//! Asynchronous Serial Host (ASH) framing.
+//!
+//! ASH is the UART data-link protocol between a host and a Silicon Labs NCP. It delimits frames
+//! with flag bytes, escapes reserved bytes, protects frames with a CRC, and acknowledges numbered
+//! data frames.
+
+pub use self::decoder::Decoder;
+pub use self::frame::{DecodeError, Frame};
+
+mod crc;
+mod decoder;
+mod frame;
+
+/// Terminates a frame.
+pub const FLAG: u8 = 0x7E;
+
+/// Escapes the following reserved byte.
+pub const ESCAPE: u8 = 0x7D;
+
+/// Terminates a frame in progress, discarding it.
+pub const CANCEL: u8 = 0x1A;
+
+/// Marks a frame containing a low-level communication error.
+pub const SUBSTITUTE: u8 = 0x18;
+
+/// Resumes transmission (software flow control).
+pub const XON: u8 = 0x11;
+
+/// Stops transmission (software flow control).
+pub const XOFF: u8 = 0x13;
+
+/// Number of distinct frame and acknowledgement numbers.
+pub const FRAME_NUMBERS: u8 = 8;
+
+/// Return the frame number following `number`.
+pub const fn next(number: u8) -> u8 {
+    (number + 1) % FRAME_NUMBERS
+}
@@ -0,0 +1,27 @@
+//! CRC-CCITT checksum of ASH frames.
+
+const INITIAL: u16 = 0xFFFF;
+const POLYNOMIAL: u16 = 0x1021;
+
+/// Return the CRC-CCITT of `data`.
+pub fn crc(data: &[u8]) -> u16 {
+    data.iter().fold(INITIAL, |crc, byte| {
+        (0..8).fold(crc ^ (u16::from(*byte) << 8), |crc, _| {
+            if crc & 0x8000 == 0 {
+                crc << 1
+            } else {
+                (crc << 1) ^ POLYNOMIAL
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::crc;
+
+    #[test]
+    fn computes_the_crc_of_a_reset_frame() {
+        assert_eq!(crc(&[0xC0]), 0x38BC);
+    }
+}
@@ -0,0 +1,105 @@
+//! Reassembly of ASH frames from a byte stream.
+
+use super::frame::{DecodeError, Frame};
+use super::{CANCEL, ESCAPE, FLAG, SUBSTITUTE, XOFF, XON};
+
+const ESCAPE_XOR: u8 = 0x20;
+
+/// Upper bound of an unstuffed frame: control byte, 128 byte EZSP frame, and CRC.
+const MAX_FRAME_SIZE: usize = 131;
+
+/// Splits a received byte stream into ASH frames.
+#[derive(Debug, Default)]
+pub struct Decoder {
+    buffer: Vec<u8>,
+    escaped: bool,
+    discarding: bool,
+}
+
+impl Decoder {
+    /// Consume a received byte.
+    ///
+    /// Returns the decoded frame once its terminating flag has been received. Cancelled and
+    /// substituted frames are discarded without a result.
+    pub fn push(&mut self, byte: u8) -> Option<Result<Frame, DecodeError>> {
+        match byte {
+            FLAG => {
+                let discarded = self.discarding;
+                let frame = std::mem::take(&mut self.buffer);
+                self.escaped = false;
+                self.discarding = false;
+
+                if discarded || frame.is_empty() {
+                    None
+                } else {
+                    Some(Frame::decode(&frame))
+                }
+            }
+            CANCEL => {
+                self.buffer.clear();
+                self.escaped = false;
+                self.discarding = false;
+                None
+            }
+            SUBSTITUTE => {
+                self.discarding = true;
+                None
+            }
+            XON | XOFF => None,
+            ESCAPE => {
+                self.escaped = true;
+                None
+            }
+            byte if !self.discarding => {
+                let byte = if std::mem::take(&mut self.escaped) {
+                    byte ^ ESCAPE_XOR
+                } else {
+                    byte
+                };
+
+                if self.buffer.len() < MAX_FRAME_SIZE {
+                    self.buffer.push(byte);
+                } else {
+                    self.discarding = true;
+                }
+
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Decoder;
+    use crate::ash::{DecodeError, Frame};
+
+    fn decode_all(decoder: &mut Decoder, bytes: &[u8]) -> Vec<Result<Frame, DecodeError>> {
+        bytes
+            .iter()
+            .filter_map(|byte| decoder.push(*byte))
+            .collect()
+    }
+
+    #[test]
+    fn reassembles_stuffed_frames() {
+        let frame = Frame::RstAck {
+            version: 0x7E,
+            reset_code: 0x7D,
+        };
+        let mut decoder = Decoder::default();
+
+        assert_eq!(decode_all(&mut decoder, &frame.encode()), [Ok(frame)]);
+    }
+
+    #[test]
+    fn discards_cancelled_and_substituted_frames() {
+        let mut decoder = Decoder::default();
+        let mut bytes = vec![0x12, 0x34, 0x1A];
+        bytes.extend([0x56, 0x18, 0x78, 0x7E]);
+        bytes.extend(Frame::Rst.encode());
+
+        assert_eq!(decode_all(&mut decoder, &bytes), [Ok(Frame::Rst)]);
+    }
+}
@@ -0,0 +1,295 @@
+//! ASH frame encoding and decoding.
+
+use thiserror::Error;
+
+use super::crc::crc;
+use super::{CANCEL, ESCAPE, FLAG, SUBSTITUTE, XOFF, XON};
+
+const DATA_MASK: u8 = 0x80;
+const ACK: u8 = 0x80;
+const NAK: u8 = 0xA0;
+const ACK_NAK_MASK: u8 = 0xE0;
+const RST: u8 = 0xC0;
+const RSTACK: u8 = 0xC1;
+const ERROR: u8 = 0xC2;
+const RETRANSMITTED: u8 = 0x08;
+const NOT_READY: u8 = 0x08;
+const NUMBER_MASK: u8 = 0x07;
+const ESCAPE_XOR: u8 = 0x20;
+const RANDOM_SEED: u8 = 0x42;
+const RANDOM_TAP: u8 = 0xB8;
+const CRC_SIZE: usize = 2;
+
+/// An ASH frame.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Frame {
+    /// A numbered frame carrying an EZSP frame.
+    Data {
+        /// Number of this frame.
+        frame_number: u8,
+        /// Whether the frame is a retransmission.
+        retransmitted: bool,
+        /// Number of the next frame expected from the peer.
+        ack_number: u8,
+        /// The EZSP frame.
+        payload: Vec<u8>,
+    },
+
+    /// Acknowledges received data frames.
+    Ack {
+        /// Number of the next frame expected from the peer.
+        ack_number: u8,
+        /// Whether the sender cannot receive further data frames.
+        not_ready: bool,
+    },
+
+    /// Requests retransmission of data frames.
+    Nak {
+        /// Number of the next frame expected from the peer.
+        ack_number: u8,
+        /// Whether the sender cannot receive further data frames.
+        not_ready: bool,
+    },
+
+    /// Requests an NCP reset.
+    Rst,
+
+    /// Reports that the NCP has reset.
+    RstAck {
+        /// The ASH protocol version.
+        version: u8,
+        /// The reason of the reset.
+        reset_code: u8,
+    },
+
+    /// Reports that the NCP entered the failed state.
+    Error {
+        /// The ASH protocol version.
+        version: u8,
+        /// The reason of the failure.
+        error_code: u8,
+    },
+}
+
+/// Errors of received frames.
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+pub enum DecodeError {
+    /// The frame is too short to contain a control byte and CRC.
+    #[error("ASH frame too short")]
+    TooShort,
+
+    /// The CRC does not match the frame's content.
+    #[error("ASH frame CRC mismatch")]
+    Crc,
+
+    /// The control byte or the frame's length are invalid.
+    #[error("Invalid ASH frame")]
+    Invalid,
+}
+
+impl Frame {
+    /// Return the stuffed frame, including the terminating flag.
+    #[must_use]
+    pub fn encode(&self) -> Vec<u8> {
+        let mut unstuffed = vec![self.control()];
+
+        match self {
+            Self::Data { payload, .. } => unstuffed.extend(randomized(payload)),
+            Self::RstAck {
+                version,
+                reset_code: code,
+            }
+            | Self::Error {
+                version,
+                error_code: code,
+            } => unstuffed.extend([*version, *code]),
+            Self::Ack { .. } | Self::Nak { .. } | Self::Rst => {}
+        }
+
+        unstuffed.extend(crc(&unstuffed).to_be_bytes());
+        let mut stuffed = Vec::with_capacity(unstuffed.len() * 2 + 1);
+
+        for byte in unstuffed {
+            if is_reserved(byte) {
+                stuffed.extend([ESCAPE, byte ^ ESCAPE_XOR]);
+            } else {
+                stuffed.push(byte);
+            }
+        }
+
+        stuffed.push(FLAG);
+        stuffed
+    }
+
+    /// Decode an unstuffed frame without its terminating flag.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeError`] if the frame is damaged.
+    pub fn decode(frame: &[u8]) -> Result<Self, DecodeError> {
+        let Some((content, checksum)) = frame
+            .len()
+            .checked_sub(CRC_SIZE)
+            .filter(|length| *length > 0)
+            .map(|length| frame.split_at(length))
+        else {
+            return Err(DecodeError::TooShort);
+        };
+
+        if crc(content).to_be_bytes() != checksum {
+            return Err(DecodeError::Crc);
+        }
+
+        let (control, data) = (content[0], &content[1..]);
+
+        if control & DATA_MASK == 0 {
+            return if data.is_empty() {
+                Err(DecodeError::Invalid)
+            } else {
+                Ok(Self::Data {
+                    frame_number: (control >> 4) & NUMBER_MASK,
+                    retransmitted: control & RETRANSMITTED != 0,
+                    ack_number: control & NUMBER_MASK,
+                    payload: randomized(data).collect(),
+                })
+            };
+        }
+
+        let ack_number = control & NUMBER_MASK;
+        let not_ready = control & NOT_READY != 0;
+
+        match (control, data) {
+            (RST, []) => Ok(Self::Rst),
+            (RSTACK, [version, reset_code]) => Ok(Self::RstAck {
+                version: *version,
+                reset_code: *reset_code,
+            }),
+            (ERROR, [version, error_code]) => Ok(Self::Error {
+                version: *version,
+                error_code: *error_code,
+            }),
+            (control, []) if control & ACK_NAK_MASK == ACK => Ok(Self::Ack {
+                ack_number,
+                not_ready,
+            }),
+            (control, []) if control & ACK_NAK_MASK == NAK => Ok(Self::Nak {
+                ack_number,
+                not_ready,
+            }),
+            _ => Err(DecodeError::Invalid),
+        }
+    }
+
+    fn control(&self) -> u8 {
+        match self {
+            Self::Data {
+                frame_number,
+                retransmitted,
+                ack_number,
+                ..
+            } => {
+                ((frame_number & NUMBER_MASK) << 4)
+                    | if *retransmitted { RETRANSMITTED } else { 0 }
+                    | (ack_number & NUMBER_MASK)
+            }
+            Self::Ack {
+                ack_number,
+                not_ready,
+            } => ACK | if *not_ready { NOT_READY } else { 0 } | (ack_number & NUMBER_MASK),
+            Self::Nak {
+                ack_number,
+                not_ready,
+            } => NAK | if *not_ready { NOT_READY } else { 0 } | (ack_number & NUMBER_MASK),
+            Self::Rst => RST,
+            Self::RstAck { .. } => RSTACK,
+            Self::Error { .. } => ERROR,
+        }
+    }
+}
+
+/// Return whether `byte` must be escaped within a frame.
+pub const fn is_reserved(byte: u8) -> bool {
+    matches!(byte, FLAG | ESCAPE | XON | XOFF | SUBSTITUTE | CANCEL)
+}
+
+/// Apply the data-field randomization, which is its own inverse.
+fn randomized(data: &[u8]) -> impl Iterator<Item = u8> {
+    data.iter().scan(RANDOM_SEED, |random, byte| {
+        let output = byte ^ *random;
+        *random = if *random & 0x01 == 0 {
+            *random >> 1
+        } else {
+            (*random >> 1) ^ RANDOM_TAP
+        };
+        Some(output)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DecodeError, Frame};
+
+    /// Decode a frame without escaped bytes.
+    fn decode(stuffed: &[u8]) -> Result<Frame, DecodeError> {
+        Frame::decode(stuffed.strip_suffix(&[0x7E]).unwrap_or(stuffed))
+    }
+
+    #[test]
+    fn encodes_control_frames() {
+        assert_eq!(Frame::Rst.encode(), [0xC0, 0x38, 0xBC, 0x7E]);
+        assert_eq!(
+            Frame::Ack {
+                ack_number: 1,
+                not_ready: false
+            }
+            .encode(),
+            [0x81, 0x60, 0x59, 0x7E]
+        );
+    }
+
+    #[test]
+    fn decodes_reset_acknowledgement() {
+        assert_eq!(
+            decode(&[0xC1, 0x02, 0x0B, 0x0A, 0x52, 0x7E]),
+            Ok(Frame::RstAck {
+                version: 2,
+                reset_code: 0x0B
+            })
+        );
+    }
+
+    #[test]
+    fn randomizes_data_payloads() {
+        let frame = Frame::Data {
+            frame_number: 0,
+            retransmitted: false,
+            ack_number: 0,
+            payload: vec![0x00, 0x00, 0x00, 0x04],
+        };
+        let encoded = frame.encode();
+
+        assert_eq!(encoded, [0x00, 0x42, 0x21, 0xA8, 0x50, 0xED, 0x2C, 0x7E]);
+        assert_eq!(decode(&encoded), Ok(frame));
+    }
+
+    #[test]
+    fn escapes_reserved_bytes() {
+        let frame = Frame::RstAck {
+            version: 0x7E,
+            reset_code: 0x11,
+        };
+        let encoded = frame.encode();
+
+        assert_eq!(&encoded[..5], [0xC1, 0x7D, 0x5E, 0x7D, 0x31]);
+        assert_eq!(
+            encoded.iter().position(|byte| *byte == 0x7E),
+            Some(encoded.len() - 1)
+        );
+    }
+
+    #[test]
+    fn rejects_damaged_frames() {
+        assert_eq!(Frame::decode(&[0xC0]), Err(DecodeError::TooShort));
+        assert_eq!(Frame::decode(&[0xC0, 0x38, 0xBD]), Err(DecodeError::Crc));
+    }
+}
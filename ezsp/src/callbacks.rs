@@ -0,0 +1,205 @@
+//! Translation of EZSP callbacks into hardware events.
+
+use bytes::Bytes;
+use log::{debug, warn};
+use tokio::sync::mpsc::{Receiver, Sender};
+use zb_hw::aps::apsde::{
+    ConfirmStatus, DataConfirm, DataIndication, Destination, IndicationMetadata, IndicationStatus,
+    IndividualEndpoint, NetworkAddress, ReceivedDestination, Security, Source, Status,
+};
+use zb_hw::core::short_id::{Broadcast, Device};
+use zb_hw::core::{Endpoint, FullAddress, GroupId};
+use zb_hw::{ApsdeEvent, DeviceEvent, Event, NetworkEvent, RouteError};
+
+use crate::frame::{FrameId, Received};
+use crate::inter_pan;
+use crate::parameters::{
+    IncomingMessage, IncomingRouteError, MacFilterMatchMessage, MessageSent, TrustCenterJoin,
+    aps_option, device_update, ember_status, incoming, outgoing,
+};
+
+/// NWK address of the coordinator.
+const COORDINATOR: u16 = 0x0000;
+
+/// Index of the APS link key shared with the trust center.
+const TRUST_CENTER_LINK_KEY_INDEX: u8 = 0;
+
+/// Translate callbacks into events until the transport fails.
+///
+/// Reports [`NetworkEvent::Down`] once the callback channel closes, since the NCP can no longer be
+/// reached.
+pub async fn translate(mut callbacks: Receiver<Bytes>, events: Sender<Event>) {
+    while let Some(callback) = callbacks.recv().await {
+        let Some(callback) = Received::parse(&callback) else {
+            warn!("Discarding malformed callback");
+            continue;
+        };
+
+        if let Some(event) = translate_callback(&callback)
+            && events.send(event).await.is_err()
+        {
+            return;
+        }
+    }
+
+    events
+        .send(NetworkEvent::Down.into())
+        .await
+        .unwrap_or_else(drop);
+}
+
+/// Translate a single callback into an event.
+fn translate_callback(callback: &Received) -> Option<Event> {
+    match callback.frame_id() {
+        FrameId::STACK_STATUS_HANDLER => stack_status(callback.parameters()?),
+        FrameId::TRUST_CENTER_JOIN_HANDLER => trust_center_join(&callback.parameters()?),
+        FrameId::MESSAGE_SENT_HANDLER => message_sent(&callback.parameters()?),
+        FrameId::INCOMING_MESSAGE_HANDLER => incoming_message(callback.parameters()?),
+        FrameId::MAC_FILTER_MATCH_MESSAGE_HANDLER => mac_filter_match(callback.parameters()?),
+        FrameId::INCOMING_ROUTE_ERROR_HANDLER => route_error(callback.parameters()?),
+        frame_id => {
+            debug!("Ignoring callback {frame_id}");
+            None
+        }
+    }
+}
+
+fn stack_status(status: u8) -> Option<Event> {
+    match status {
+        ember_status::NETWORK_UP => Some(NetworkEvent::Up.into()),
+        ember_status::NETWORK_DOWN => Some(NetworkEvent::Down.into()),
+        ember_status::NETWORK_OPENED => Some(NetworkEvent::Opened.into()),
+        ember_status::NETWORK_CLOSED => Some(NetworkEvent::Closed.into()),
+        status => {
+            debug!("Ignoring stack status {status:#04X}");
+            None
+        }
+    }
+}
+
+fn trust_center_join(join: &TrustCenterJoin) -> Option<Event> {
+    let address = FullAddress::new(join.new_node_eui64, Device::new(join.new_node_id)?);
+
+    match join.status {
+        device_update::UNSECURED_JOIN => Some(DeviceEvent::Joined(address).into()),
+        device_update::SECURED_REJOIN => Some(
+            DeviceEvent::Rejoined {
+                address,
+                secured: true,
+            }
+            .into(),
+        ),
+        device_update::UNSECURED_REJOIN => Some(
+            DeviceEvent::Rejoined {
+                address,
+                secured: false,
+            }
+            .into(),
+        ),
+        device_update::DEVICE_LEFT => Some(DeviceEvent::Left(address).into()),
+        status => {
+            debug!("Ignoring device update {status:#04X}");
+            None
+        }
+    }
+}
+
+/// Confirm an acknowledged transmission, whose message tag is the APS counter.
+fn message_sent(sent: &MessageSent) -> Option<Event> {
+    if sent.aps_frame.options & aps_option::RETRY == 0 {
+        return None;
+    }
+
+    let destination = match sent.message_type {
+        outgoing::DIRECT => Destination::Network {
+            address: NetworkAddress::new(sent.index_or_destination)?,
+            endpoint: Endpoint::try_from(sent.aps_frame.destination_endpoint).ok()?,
+        },
+        outgoing::MULTICAST => Destination::Group(GroupId::new(sent.aps_frame.group_id)?),
+        _ => return None,
+    };
+    let status = match sent.status {
+        ember_status::SUCCESS => ConfirmStatus::Aps(Status::Success),
+        ember_status::DELIVERY_FAILED => ConfirmStatus::Aps(Status::NoAcknowledgement),
+        status => ConfirmStatus::Network(status),
+    };
+    let confirmation = DataConfirm::new(
+        destination,
+        IndividualEndpoint::new(Endpoint::try_from(sent.aps_frame.source_endpoint).ok()?)?,
+        status,
+        (),
+    );
+
+    Some(
+        ApsdeEvent::DataConfirm {
+            counter: sent.message_tag,
+            confirmation,
+        }
+        .into(),
+    )
+}
+
+/// Indicate a received message.
+///
+/// EZSP does not report the NWK broadcast address of received broadcasts, so they are reported as
+/// addressed to all devices.
+fn incoming_message(message: IncomingMessage) -> Option<Event> {
+    let frame = message.aps_frame;
+    let endpoint = Endpoint::try_from(frame.destination_endpoint).ok()?;
+    let destination = match message.message_type {
+        incoming::MULTICAST_LOOPBACK | incoming::BROADCAST_LOOPBACK => return None,
+        incoming::MULTICAST => ReceivedDestination::Group(GroupId::new(frame.group_id)?),
+        incoming::BROADCAST => ReceivedDestination::Broadcast {
+            address: Broadcast::AllDevices,
+            endpoint,
+        },
+        _ => ReceivedDestination::Network {
+            address: NetworkAddress::new(COORDINATOR)?,
+            endpoint: IndividualEndpoint::new(endpoint)?,
+        },
+    };
+    let security = if frame.options & aps_option::ENCRYPTION == 0 {
+        Security::NetworkKey
+    } else {
+        Security::LinkKey {
+            key_index: TRUST_CENTER_LINK_KEY_INDEX,
+            device_key_pair_entry: (),
+        }
+    };
+    let metadata = IndicationMetadata::new(
+        destination,
+        Source::Network {
+            address: NetworkAddress::new(message.sender)?,
+            endpoint: IndividualEndpoint::new(Endpoint::try_from(frame.source_endpoint).ok()?)?,
+        },
+        frame.profile_id,
+        frame.cluster_id,
+        IndicationStatus::success(),
+        security,
+        message.last_hop_lqi,
+        (),
+    )
+    .with_rssi(message.last_hop_rssi);
+
+    Some(
+        ApsdeEvent::DataIndication(DataIndication::new(metadata, message.message.into_data()))
+            .into(),
+    )
+}
+
+/// Indicate a received inter-PAN frame.
+fn mac_filter_match(message: MacFilterMatchMessage) -> Option<Event> {
+    inter_pan::decode(&message.message.into_data(), message.last_hop_lqi).map(Into::into)
+}
+
+fn route_error(error: IncomingRouteError) -> Option<Event> {
+    match error.status {
+        ember_status::SOURCE_ROUTE_FAILURE => {
+            Some(NetworkEvent::RouteError(RouteError::Source(error.target)).into())
+        }
+        ember_status::MANY_TO_ONE_ROUTE_FAILURE => {
+            Some(NetworkEvent::RouteError(RouteError::ManyToOne(error.target)).into())
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,162 @@
+//! EZSP command client.
+
+use le_stream::{FromLeStream, ToLeStream};
+use log::{debug, warn};
+
+use crate::error::Error;
+use crate::frame::{self, FrameId, Received};
+use crate::parameters::NcpVersion;
+use crate::transport::Transport;
+
+/// Lowest supported EZSP protocol version, the first to use the extended frame format.
+const MIN_VERSION: u8 = 8;
+
+/// Highest supported EZSP protocol version.
+const MAX_VERSION: u8 = 13;
+
+/// Status of successful EZSP and Ember commands.
+const SUCCESS: u8 = 0x00;
+
+/// Status of successful security manager commands.
+const SL_STATUS_OK: u32 = 0x0000_0000;
+
+/// Sends EZSP commands over an ASH transport.
+#[derive(Debug)]
+pub struct Client {
+    transport: Transport,
+    sequence: u8,
+    version: u8,
+}
+
+impl Client {
+    /// Create a client sending commands over the transport.
+    pub const fn new(transport: Transport) -> Self {
+        Self {
+            transport,
+            sequence: 0,
+            version: MIN_VERSION,
+        }
+    }
+
+    /// Return the negotiated protocol version.
+    pub const fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Negotiate the protocol version.
+    ///
+    /// The first `version` command uses the legacy frame format. All later commands use the
+    /// extended format, which the NCP accepts once the version has been confirmed in it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP speaks an unsupported protocol version.
+    pub async fn negotiate_version(&mut self) -> Result<NcpVersion, Error> {
+        let sequence = self.next_sequence();
+        let response = self
+            .transport
+            .request(
+                FrameId::VERSION,
+                frame::legacy_version(sequence, MAX_VERSION),
+            )
+            .await?;
+        let version: NcpVersion = Received::parse_legacy(&response)
+            .and_then(|response| response.parameters())
+            .ok_or(Error::MalformedResponse(FrameId::VERSION))?;
+
+        if !(MIN_VERSION..=MAX_VERSION).contains(&version.protocol_version) {
+            return Err(Error::UnsupportedVersion(version.protocol_version));
+        }
+
+        let confirmed: NcpVersion = self
+            .call(FrameId::VERSION, version.protocol_version)
+            .await?;
+
+        if confirmed.protocol_version != version.protocol_version {
+            return Err(Error::UnsupportedVersion(confirmed.protocol_version));
+        }
+
+        self.version = confirmed.protocol_version;
+        debug!(
+            "Negotiated EZSP version {} with stack version {:#06X}",
+            confirmed.protocol_version, confirmed.stack_version
+        );
+        Ok(confirmed)
+    }
+
+    /// Send a command and return its parsed response.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the command fails or the response cannot be parsed.
+    pub async fn call<P, R>(&mut self, frame_id: FrameId, parameters: P) -> Result<R, Error>
+    where
+        P: ToLeStream,
+        R: FromLeStream,
+    {
+        let sequence = self.next_sequence();
+        let response = self
+            .transport
+            .request(frame_id, frame::command(sequence, frame_id, parameters))
+            .await?;
+        let response = Received::parse(&response)
+            .filter(|response| response.frame_id() == frame_id)
+            .ok_or(Error::MalformedResponse(frame_id))?;
+
+        if response.is_degraded() {
+            warn!("NCP reported overflow or truncation in response to {frame_id}");
+        }
+
+        response
+            .parameters()
+            .ok_or(Error::MalformedResponse(frame_id))
+    }
+
+    /// Send a command whose response is a single status byte.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the command fails or the NCP reports an unsuccessful status.
+    pub async fn call_with_status<P>(
+        &mut self,
+        frame_id: FrameId,
+        parameters: P,
+    ) -> Result<(), Error>
+    where
+        P: ToLeStream,
+    {
+        check(frame_id, self.call(frame_id, parameters).await?)
+    }
+
+    const fn next_sequence(&mut self) -> u8 {
+        let sequence = self.sequence;
+        self.sequence = self.sequence.wrapping_add(1);
+        sequence
+    }
+}
+
+/// Return an error unless `status` reports success.
+///
+/// # Errors
+///
+/// Returns [`Error::Status`] if the status is unsuccessful.
+pub const fn check(frame_id: FrameId, status: u8) -> Result<(), Error> {
+    if status == SUCCESS {
+        Ok(())
+    } else {
+        Err(Error::Status { frame_id, status })
+    }
+}
+
+/// Return an error unless the `sl_status_t` of a security manager command reports success.
+///
+/// # Errors
+///
+/// Returns [`Error::SecurityStatus`] if the status is unsuccessful.
+pub const fn check_security(frame_id: FrameId, status: u32) -> Result<(), Error> {
+    if status == SL_STATUS_OK {
+        Ok(())
+    } else {
+        Err(Error::SecurityStatus { frame_id, status })
+    }
+}
@@ -0,0 +1,72 @@
+//! Startup configuration of the EZSP backend.
+
+use zb_hw::Formation;
+use zb_hw::zdp::SimpleDescriptor;
+
+/// Default radio transmit power in dBm.
+const DEFAULT_TX_POWER: i8 = 8;
+
+/// Startup configuration of an [`Ezsp`](crate::Ezsp) driver.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    endpoints: Vec<SimpleDescriptor>,
+    formation: Option<Formation>,
+    tx_power: i8,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Config {
+    /// Create a configuration without endpoints that only resumes an existing network.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            endpoints: Vec::new(),
+            formation: None,
+            tx_power: DEFAULT_TX_POWER,
+        }
+    }
+
+    /// Register an application endpoint on the NCP.
+    #[must_use]
+    pub fn with_endpoint(mut self, endpoint: SimpleDescriptor) -> Self {
+        self.endpoints.push(endpoint);
+        self
+    }
+
+    /// Form a network with the given parameters if the NCP has not joined one.
+    #[must_use]
+    pub const fn with_formation(mut self, formation: Formation) -> Self {
+        self.formation = Some(formation);
+        self
+    }
+
+    /// Set the radio transmit power in dBm of networks formed by the NCP.
+    #[must_use]
+    pub const fn with_tx_power(mut self, tx_power: i8) -> Self {
+        self.tx_power = tx_power;
+        self
+    }
+
+    /// Return the application endpoints.
+    #[must_use]
+    pub fn endpoints(&self) -> &[SimpleDescriptor] {
+        &self.endpoints
+    }
+
+    /// Return the parameters of a network to form.
+    #[must_use]
+    pub const fn formation(&self) -> Option<&Formation> {
+        self.formation.as_ref()
+    }
+
+    /// Return the radio transmit power in dBm.
+    #[must_use]
+    pub const fn tx_power(&self) -> i8 {
+        self.tx_power
+    }
+}
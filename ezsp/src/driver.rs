@@ -0,0 +1,1317 @@
+//! The EZSP driver.
+
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use bytes::Bytes;
+use log::info;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::spawn;
+use tokio::sync::mpsc::{Receiver, channel};
+use zb_hw::aps::TxOptions;
+use zb_hw::aps::apsde::{Block, DataRequest, RequestDestination};
+use zb_hw::core::IeeeAddress;
+use zb_hw::core::security::Key;
+use zb_hw::core::short_id::Device;
+use zb_hw::zdp::SimpleDescriptor;
+use zb_hw::{
+    BackedUpDevice, Channel, ChannelMask, Counter, Counters, Driver, Error as HwError, Event,
+    Formation, FoundNetwork, InterPanRequest, JoinPolicy, LinkKeyState, NetworkBackup,
+    NetworkParameters as HwNetworkParameters, Operation, ScanDuration, ScannedChannel,
+    TrustCenterPolicy,
+};
+
+use crate::callbacks::translate;
+use crate::client::{Client, check};
+use crate::config::Config;
+use crate::error::Error;
+use crate::frame::FrameId;
+use crate::parameters::{
+    AddEndpoint, ApsFrame, GetNetworkParameters, InitialSecurityState, LookupEui64,
+    ManyToOneRouteRequest, Message, NetworkParameters, SendBroadcast, SendMulticast, SendUnicast,
+    Sent, SetPolicy, aps_option, ember_status, network_status, outgoing, policy, value_id,
+};
+use crate::transport::Transport;
+use crate::{inter_pan, keys};
+
+/// Longest permit-joining period of a Zigbee network.
+const MAX_PERMIT_JOINING: Duration = Duration::from_secs(254);
+
+/// Hardware counters in the order of the NCP's `EmberCounterType` values.
+///
+/// Counters without a hardware equivalent, such as the ASH error counters, are skipped.
+const COUNTERS: [Option<Counter>; 29] = [
+    Some(Counter::MacRxBroadcast),
+    Some(Counter::MacTxBroadcast),
+    Some(Counter::MacRxUnicast),
+    Some(Counter::MacTxUnicastSuccess),
+    Some(Counter::MacTxUnicastRetry),
+    Some(Counter::MacTxUnicastFailure),
+    Some(Counter::ApsRxBroadcast),
+    Some(Counter::ApsTxBroadcast),
+    Some(Counter::ApsRxUnicast),
+    Some(Counter::ApsTxUnicastSuccess),
+    Some(Counter::ApsTxUnicastRetry),
+    Some(Counter::ApsTxUnicastFailure),
+    Some(Counter::RouteDiscoveryInitiated),
+    Some(Counter::NeighborAdded),
+    Some(Counter::NeighborRemoved),
+    Some(Counter::NeighborStale),
+    Some(Counter::JoinIndication),
+    Some(Counter::ChildRemoved),
+    None,
+    None,
+    None,
+    Some(Counter::NwkFrameCounterFailure),
+    Some(Counter::ApsFrameCounterFailure),
+    None,
+    Some(Counter::ApsUnauthorizedKey),
+    Some(Counter::NwkDecryptionFailure),
+    Some(Counter::ApsDecryptionFailure),
+    Some(Counter::BufferAllocationFailure),
+    Some(Counter::RelayedUnicast),
+];
+
+/// Concentrator type of a high-RAM concentrator, which keeps source routes on the NCP.
+const HIGH_RAM_CONCENTRATOR: u16 = 0xFFF9;
+
+/// Node ID reported for unknown devices.
+const UNKNOWN_NODE_ID: u16 = 0xFFFF;
+
+/// Bitmask of `networkInit` without rejoin options.
+const NETWORK_INIT_BITMASK: u16 = 0x0000;
+
+/// Join method of a formed network.
+const MAC_ASSOCIATION: u8 = 0x00;
+
+/// Security bitmask of a formed network.
+///
+/// The trust center uses a global link key, has a preconfigured link key and network key, and
+/// requires joining devices to use an encrypted key.
+const SECURITY_BITMASK: u16 = 0x0004 | 0x0100 | 0x0200 | 0x0800;
+
+/// Security bitmask option keeping the outgoing frame counters of a restored network.
+const NO_FRAME_COUNTER_RESET: u16 = 0x1000;
+
+/// Capacity of the callback channel between the transport and the event translator.
+const CALLBACK_CAPACITY: usize = 32;
+
+/// Radius of multicasts to non-members of the group.
+const NONMEMBER_RADIUS: u8 = 7;
+
+/// A [`Driver`] for Silicon Labs NCPs speaking EZSP over ASH.
+#[derive(Debug)]
+pub struct Ezsp {
+    client: Client,
+    ieee_address: IeeeAddress,
+    endpoints: Box<[SimpleDescriptor]>,
+    tx_power: i8,
+    inter_pan_channel: Option<Channel>,
+    mac_sequence: u8,
+}
+
+impl Ezsp {
+    /// Connect to an NCP over a serial stream and bring up its network.
+    ///
+    /// Resets the NCP, negotiates the EZSP version, registers the configured endpoints, and
+    /// resumes the network stored on the NCP. If the NCP has not joined a network and the
+    /// configuration contains a [`Formation`], a new network is formed.
+    ///
+    /// Returns the driver and the receiver of its hardware events. The receiver reports
+    /// [`NetworkEvent::Down`](zb_hw::NetworkEvent::Down) and closes when the serial
+    /// connection fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP cannot be initialized.
+    pub async fn connect<S>(
+        stream: S,
+        config: Config,
+        event_capacity: NonZeroUsize,
+    ) -> Result<(Self, Receiver<Event>), Error>
+    where
+        S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    {
+        let (callbacks_out, callbacks) = channel(CALLBACK_CAPACITY);
+        let (events_out, events) = channel(event_capacity.get());
+        let mut client = Client::new(Transport::start(stream, callbacks_out).await?);
+        spawn(translate(callbacks, events_out));
+        client.negotiate_version().await?;
+        let ieee_address = client.call(FrameId::GET_EUI64, ()).await?;
+
+        for endpoint in config.endpoints() {
+            client
+                .call_with_status(FrameId::ADD_ENDPOINT, add_endpoint(endpoint))
+                .await?;
+        }
+
+        match (
+            client
+                .call_with_status(FrameId::NETWORK_INIT, NETWORK_INIT_BITMASK)
+                .await,
+            config.formation(),
+        ) {
+            (
+                Err(Error::Status {
+                    status: ember_status::NOT_JOINED,
+                    ..
+                }),
+                Some(formation),
+            ) => {
+                info!("NCP has not joined a network; forming one");
+                form_network(&mut client, ieee_address, formation, config.tx_power()).await?;
+            }
+            (result, _) => result?,
+        }
+
+        Ok((
+            Self {
+                client,
+                ieee_address,
+                endpoints: config.endpoints().into(),
+                tx_power: config.tx_power(),
+                inter_pan_channel: None,
+                mac_sequence: 0,
+            },
+            events,
+        ))
+    }
+
+    /// Send a unicast to a node ID.
+    async fn send_unicast(
+        &mut self,
+        destination: u16,
+        aps_frame: ApsFrame,
+        counter: u8,
+        message: Message,
+    ) -> Result<(), Error> {
+        self.send(
+            FrameId::SEND_UNICAST,
+            SendUnicast {
+                message_type: outgoing::DIRECT,
+                destination,
+                aps_frame,
+                message_tag: counter,
+                message,
+            },
+        )
+        .await
+    }
+
+    async fn send<P>(&mut self, frame_id: FrameId, parameters: P) -> Result<(), Error>
+    where
+        P: le_stream::ToLeStream,
+    {
+        let sent: Sent = self.client.call(frame_id, parameters).await?;
+        check(frame_id, sent.status)
+    }
+
+    async fn network_parameters(&mut self) -> Result<NetworkParameters, Error> {
+        let response: GetNetworkParameters = self
+            .client
+            .call(FrameId::GET_NETWORK_PARAMETERS, ())
+            .await?;
+        check(FrameId::GET_NETWORK_PARAMETERS, response.status)?;
+        Ok(response.parameters)
+    }
+
+    async fn set_radio_channel(&mut self, channel: u8) -> Result<(), Error> {
+        self.client
+            .call_with_status(FrameId::SET_RADIO_CHANNEL, channel)
+            .await
+    }
+
+    async fn set_policy(&mut self, policy_id: u8, decision_id: u8) -> Result<(), Error> {
+        self.client
+            .call_with_status(
+                FrameId::SET_POLICY,
+                SetPolicy {
+                    policy_id,
+                    decision_id,
+                },
+            )
+            .await
+    }
+
+    async fn node_id(&mut self, ieee_address: IeeeAddress) -> Result<u16, Error> {
+        let node_id: u16 = self
+            .client
+            .call(FrameId::LOOKUP_NODE_ID_BY_EUI64, ieee_address)
+            .await?;
+
+        if node_id == UNKNOWN_NODE_ID {
+            Err(Error::UnknownDevice)
+        } else {
+            Ok(node_id)
+        }
+    }
+}
+
+impl Driver for Ezsp {
+    async fn get_endpoints(&self) -> Result<Box<[SimpleDescriptor]>, HwError> {
+        Ok(self.endpoints.clone())
+    }
+
+    async fn get_pan_id(&mut self) -> Result<u16, HwError> {
+        Ok(self.network_parameters().await?.pan_id)
+    }
+
+    async fn get_ieee_address(&mut self) -> Result<IeeeAddress, HwError> {
+        Ok(self.ieee_address)
+    }
+
+    async fn scan_networks(
+        &mut self,
+        _channel_mask: ChannelMask,
+        _duration: ScanDuration,
+    ) -> Result<Vec<FoundNetwork>, HwError> {
+        Err(HwError::Unsupported(Operation::ScanNetworks))
+    }
+
+    async fn scan_channels(
+        &mut self,
+        _channel_mask: ChannelMask,
+        _duration: ScanDuration,
+    ) -> Result<Vec<ScannedChannel>, HwError> {
+        Err(HwError::Unsupported(Operation::ScanChannels))
+    }
+
+    async fn allow_joins(&mut self, duration: Duration) -> Result<Duration, HwError> {
+        let duration = duration.min(MAX_PERMIT_JOINING);
+        let seconds = u8::try_from(duration.as_secs()).unwrap_or(u8::MAX);
+        self.client
+            .call_with_status(FrameId::PERMIT_JOINING, seconds)
+            .await?;
+        Ok(Duration::from_secs(seconds.into()))
+    }
+
+    async fn route_request(&mut self, radius: u8) -> Result<(), HwError> {
+        self.client
+            .call_with_status(
+                FrameId::SEND_MANY_TO_ONE_ROUTE_REQUEST,
+                ManyToOneRouteRequest {
+                    concentrator_type: HIGH_RAM_CONCENTRATOR,
+                    radius,
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn short_id_to_ieee_address(&mut self, short_id: Device) -> Result<IeeeAddress, HwError> {
+        let response: LookupEui64 = self
+            .client
+            .call(FrameId::LOOKUP_EUI64_BY_NODE_ID, short_id.as_u16())
+            .await?;
+
+        if response.status == ember_status::SUCCESS {
+            Ok(response.eui64)
+        } else {
+            Err(Error::UnknownDevice.into())
+        }
+    }
+
+    async fn ieee_address_to_short_id(
+        &mut self,
+        ieee_address: IeeeAddress,
+    ) -> Result<Device, HwError> {
+        let node_id = self.node_id(ieee_address).await?;
+        Device::new(node_id).ok_or_else(|| Error::UnknownDevice.into())
+    }
+
+    async fn transmit(&mut self, request: DataRequest<Bytes>, counter: u8) -> Result<(), HwError> {
+        let message = Message::try_from(request.asdu().clone())
+            .map_err(|_| HwError::Unsupported(Operation::Transmit))?;
+        let mut aps_frame = ApsFrame {
+            profile_id: request.profile_id(),
+            cluster_id: request.cluster_id(),
+            source_endpoint: request.source_endpoint().get().as_u8(),
+            destination_endpoint: 0,
+            options: aps_options(request.tx_options(), request.block()),
+            group_id: request.block().map_or(0, fragment_group_id),
+            sequence: counter,
+        };
+
+        match request.destination() {
+            RequestDestination::Network { address, endpoint } => {
+                aps_frame.destination_endpoint = endpoint.as_u8();
+                self.send_unicast(address.as_u16(), aps_frame, counter, message)
+                    .await?;
+            }
+            RequestDestination::Extended { address, endpoint } => {
+                aps_frame.destination_endpoint = endpoint.as_u8();
+                let node_id = self.node_id(address).await?;
+                self.send_unicast(node_id, aps_frame, counter, message)
+                    .await?;
+            }
+            RequestDestination::Broadcast { address, endpoint } => {
+                aps_frame.destination_endpoint = endpoint.as_u8();
+                self.send(
+                    FrameId::SEND_BROADCAST,
+                    SendBroadcast {
+                        destination: address.as_u16(),
+                        aps_frame,
+                        radius: request.radius_counter(),
+                        message_tag: counter,
+                        message,
+                    },
+                )
+                .await?;
+            }
+            RequestDestination::Group { address, .. } => {
+                aps_frame.group_id = address.as_u16();
+                self.send(
+                    FrameId::SEND_MULTICAST,
+                    SendMulticast {
+                        aps_frame,
+                        hops: request.radius_counter(),
+                        nonmember_radius: NONMEMBER_RADIUS,
+                        message_tag: counter,
+                        message,
+                    },
+                )
+                .await?;
+            }
+            RequestDestination::Bound => return Err(HwError::Unsupported(Operation::Transmit)),
+        }
+
+        Ok(())
+    }
+
+    async fn add_transient_link_key(
+        &mut self,
+        ieee_address: IeeeAddress,
+        key: Key,
+    ) -> Result<(), HwError> {
+        keys::import_transient_key(&mut self.client, ieee_address, key)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn set_link_key(&mut self, ieee_address: IeeeAddress, key: Key) -> Result<(), HwError> {
+        keys::import_link_key(&mut self.client, ieee_address, key)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn form_network(&mut self, formation: Formation) -> Result<(), HwError> {
+        form_network(
+            &mut self.client,
+            self.ieee_address,
+            &formation,
+            self.tx_power,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn leave_network(&mut self) -> Result<(), HwError> {
+        self.client
+            .call_with_status(FrameId::LEAVE_NETWORK, ())
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_network_parameters(&mut self) -> Result<HwNetworkParameters, HwError> {
+        hw_network_parameters(&self.network_parameters().await?).map_err(Into::into)
+    }
+
+    async fn set_trust_center_policy(&mut self, policy: TrustCenterPolicy) -> Result<(), HwError> {
+        self.set_policy(policy::TRUST_CENTER, trust_center_decision(policy))
+            .await?;
+        self.set_policy(
+            policy::TC_KEY_REQUEST,
+            if policy.allows_link_key_requests() {
+                policy::ALLOW_TC_KEY_REQUESTS
+            } else {
+                policy::DENY_TC_KEY_REQUESTS
+            },
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn backup_network(&mut self) -> Result<NetworkBackup, HwError> {
+        let parameters = self.network_parameters().await?;
+        let network_key = keys::network_key(&mut self.client).await?;
+        let mut backup = NetworkBackup::new(
+            self.ieee_address,
+            hw_network_parameters(&parameters)?,
+            network_key,
+        )
+        .with_nwk_update_id(parameters.nwk_update_id);
+
+        if let Some(channel_mask) = ChannelMask::new(parameters.channels) {
+            backup = backup.with_channel_mask(channel_mask);
+        }
+
+        Ok(keys::link_keys(&mut self.client)
+            .await?
+            .into_iter()
+            .map(|(ieee_address, link_key)| {
+                BackedUpDevice::new(ieee_address).with_link_key(link_key)
+            })
+            .fold(backup, NetworkBackup::with_device))
+    }
+
+    async fn restore_network(&mut self, backup: NetworkBackup) -> Result<(), HwError> {
+        restore_network(&mut self.client, self.ieee_address, &backup, self.tx_power)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_counters(&mut self) -> Result<Counters, HwError> {
+        let values: Vec<u16> = self.client.call(FrameId::READ_COUNTERS, ()).await?;
+
+        if values.len() < COUNTERS.len() {
+            return Err(Error::MalformedResponse(FrameId::READ_COUNTERS).into());
+        }
+
+        Ok(COUNTERS
+            .into_iter()
+            .zip(values)
+            .filter_map(|(counter, value)| counter.map(|counter| (counter, value.into())))
+            .collect())
+    }
+
+    async fn get_maximum_payload_length(&mut self) -> Result<u8, HwError> {
+        Ok(self
+            .client
+            .call(FrameId::MAXIMUM_PAYLOAD_LENGTH, ())
+            .await?)
+    }
+
+    async fn transmit_inter_pan(&mut self, request: InterPanRequest) -> Result<(), HwError> {
+        let pan_id = self.network_parameters().await?.pan_id;
+
+        if self.inter_pan_channel != Some(request.channel()) {
+            self.set_radio_channel(request.channel().as_u8()).await?;
+            self.inter_pan_channel = Some(request.channel());
+        }
+
+        let frame = inter_pan::encode(&request, self.mac_sequence, pan_id, self.ieee_address);
+        self.mac_sequence = self.mac_sequence.wrapping_add(1);
+        let message = Message::try_from(frame)
+            .map_err(|_| HwError::Unsupported(Operation::TransmitInterPan))?;
+        self.client
+            .call_with_status(FrameId::SEND_RAW_MESSAGE, message)
+            .await?;
+        Ok(())
+    }
+
+    async fn end_inter_pan(&mut self) -> Result<(), HwError> {
+        if self.inter_pan_channel.is_none() {
+            return Ok(());
+        }
+
+        let channel = self.network_parameters().await?.radio_channel;
+        self.set_radio_channel(channel).await?;
+        self.inter_pan_channel = None;
+        Ok(())
+    }
+}
+
+/// Return the decision bitmask of the trust center policy.
+const fn trust_center_decision(policy: TrustCenterPolicy) -> u8 {
+    let mut decision = match policy.joins() {
+        JoinPolicy::AllowDefaultKey => policy::ALLOW_JOINS,
+        JoinPolicy::InstallCodeOnly => policy::ALLOW_JOINS | policy::JOINS_USE_INSTALL_CODE_KEY,
+        _ => 0,
+    };
+
+    if policy.allows_unsecured_rejoins() {
+        decision |= policy::ALLOW_UNSECURED_REJOINS;
+    }
+
+    decision
+}
+
+/// Return the parameters of `addEndpoint` for an endpoint.
+fn add_endpoint(endpoint: &SimpleDescriptor) -> AddEndpoint {
+    let input_clusters = endpoint.input_clusters().to_vec();
+    let output_clusters = endpoint.output_clusters().to_vec();
+
+    AddEndpoint {
+        endpoint: endpoint.endpoint_id(),
+        profile_id: endpoint.profile_id(),
+        device_id: endpoint.device_id(),
+        app_flags: endpoint.version(),
+        input_cluster_count: u8::try_from(input_clusters.len()).unwrap_or(u8::MAX),
+        output_cluster_count: u8::try_from(output_clusters.len()).unwrap_or(u8::MAX),
+        input_clusters,
+        output_clusters,
+    }
+}
+
+/// Return the EZSP APS options of the APS transmission options.
+const fn aps_options(tx_options: TxOptions, block: Option<Block>) -> u16 {
+    let mut options = aps_option::ENABLE_ROUTE_DISCOVERY;
+
+    if block.is_some() {
+        options |= aps_option::FRAGMENT;
+    }
+
+    if tx_options.contains(TxOptions::SECURITY_ENABLED) {
+        options |= aps_option::ENCRYPTION;
+    }
+
+    if tx_options.contains(TxOptions::ACKNOWLEDGED_TRANSMISSION) {
+        options |= aps_option::RETRY;
+    }
+
+    options
+}
+
+/// Return the group ID field that carries a fragment's block count and index.
+///
+/// The NCP reads the total block count from the high byte and the block index from the low byte
+/// of the APS frame's otherwise unused group ID.
+const fn fragment_group_id(block: Block) -> u16 {
+    u16::from_be_bytes([block.count(), block.index()])
+}
+
+/// Configure the trust center's security and form a network on the lowest channel of the mask.
+async fn form_network(
+    client: &mut Client,
+    ieee_address: IeeeAddress,
+    formation: &Formation,
+    tx_power: i8,
+) -> Result<(), Error> {
+    let channel = formation
+        .channel_mask()
+        .channels()
+        .next()
+        .ok_or(Error::EmptyChannelMask)?;
+    client
+        .call_with_status(
+            FrameId::SET_INITIAL_SECURITY_STATE,
+            InitialSecurityState {
+                bitmask: SECURITY_BITMASK,
+                preconfigured_key: Key::DEFAULT_TRUST_CENTER_LINK_KEY,
+                network_key: *formation.network_key(),
+                network_key_sequence_number: 0,
+                preconfigured_trust_center_eui64: ieee_address,
+            },
+        )
+        .await?;
+    client
+        .call_with_status(
+            FrameId::FORM_NETWORK,
+            NetworkParameters {
+                extended_pan_id: formation.extended_pan_id(),
+                pan_id: formation.pan_id(),
+                radio_tx_power: tx_power,
+                radio_channel: channel.as_u8(),
+                join_method: MAC_ASSOCIATION,
+                nwk_manager_id: 0x0000,
+                nwk_update_id: 0,
+                channels: formation.channel_mask().bits(),
+            },
+        )
+        .await
+}
+
+/// Restore a backed-up network and form it with the backup's parameters.
+///
+/// The NCP keeps its own EUI-64 as the trust center address, since EZSP can only change it through
+/// a manufacturing token. The NWK frame counter continues from the backup, and the trust-center
+/// APS frame counter from the highest outgoing counter of the backed-up link keys.
+async fn restore_network(
+    client: &mut Client,
+    ieee_address: IeeeAddress,
+    backup: &NetworkBackup,
+    tx_power: i8,
+) -> Result<(), Error> {
+    let state: u8 = client.call(FrameId::NETWORK_STATE, ()).await?;
+
+    if state != network_status::NO_NETWORK {
+        return Err(Error::AlreadyJoined);
+    }
+
+    let network_key = backup.network_key();
+    client
+        .call_with_status(
+            FrameId::SET_INITIAL_SECURITY_STATE,
+            InitialSecurityState {
+                bitmask: SECURITY_BITMASK | NO_FRAME_COUNTER_RESET,
+                preconfigured_key: Key::DEFAULT_TRUST_CENTER_LINK_KEY,
+                network_key: *network_key.key(),
+                network_key_sequence_number: network_key.sequence_number(),
+                preconfigured_trust_center_eui64: ieee_address,
+            },
+        )
+        .await?;
+    keys::set_frame_counter(
+        client,
+        value_id::NWK_FRAME_COUNTER,
+        network_key.frame_counter(),
+    )
+    .await?;
+
+    for device in backup.devices() {
+        if let Some(link_key) = device.link_key() {
+            keys::import_link_key(client, device.ieee_address(), *link_key.key()).await?;
+        }
+    }
+
+    if let Some(frame_counter) = backup
+        .devices()
+        .iter()
+        .filter_map(BackedUpDevice::link_key)
+        .map(LinkKeyState::tx_counter)
+        .max()
+    {
+        keys::set_frame_counter(client, value_id::APS_FRAME_COUNTER, frame_counter).await?;
+    }
+
+    let parameters = backup.parameters();
+    client
+        .call_with_status(
+            FrameId::FORM_NETWORK,
+            NetworkParameters {
+                extended_pan_id: parameters.extended_pan_id(),
+                pan_id: parameters.pan_id(),
+                radio_tx_power: tx_power,
+                radio_channel: parameters.channel().as_u8(),
+                join_method: MAC_ASSOCIATION,
+                nwk_manager_id: 0x0000,
+                nwk_update_id: backup.nwk_update_id(),
+                channels: backup.channel_mask().bits(),
+            },
+        )
+        .await
+}
+
+/// Convert the NCP's network parameters to the hardware-independent ones.
+fn hw_network_parameters(parameters: &NetworkParameters) -> Result<HwNetworkParameters, Error> {
+    let channel = Channel::new(parameters.radio_channel)
+        .ok_or(Error::MalformedResponse(FrameId::GET_NETWORK_PARAMETERS))?;
+    Ok(HwNetworkParameters::new(
+        parameters.pan_id,
+        parameters.extended_pan_id,
+        channel,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use bytes::Bytes;
+    use tokio::runtime::Builder;
+    use tokio::spawn;
+    use tokio::sync::mpsc::Receiver;
+    use zb_hw::aps::TxOptions;
+    use zb_hw::aps::apsde::{
+        Block, ConfirmStatus, DataRequest, IndividualEndpoint, NetworkAddress, RequestDestination,
+        Source, Status,
+    };
+    use zb_hw::core::security::Key;
+    use zb_hw::core::{Application, Endpoint, IeeeAddress, Profile};
+    use zb_hw::zdp::{AppFlags, Clusters, SimpleDescriptor};
+    use zb_hw::{
+        ApsdeEvent, Channel, ChannelMask, Counter, Driver, Event, Formation, InterPanDestination,
+        InterPanRequest, JoinPolicy, NcpHandle, NetworkBackup, NetworkEvent, NetworkKeyState,
+        NetworkParameters, TrustCenterPolicy,
+    };
+
+    use super::Ezsp;
+    use crate::config::Config;
+    use crate::stub::Stub;
+
+    const CAPACITY: NonZeroUsize = NonZeroUsize::new(8).expect("capacity is non-zero");
+    const EUI64: [u8; 8] = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+    const ENDPOINT: Endpoint = Endpoint::Application(Application::MIN);
+    const ON_OFF: u16 = 0x0006;
+    const DEVICE: u16 = 0x1234;
+    const PAN_ID: u16 = 0x1A62;
+
+    fn run<F>(test: F)
+    where
+        F: Future<Output = ()>,
+    {
+        Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .expect("runtime must be available")
+            .block_on(test);
+    }
+
+    /// Connect to a stub whose network is already up.
+    async fn connect(
+        ncp: &mut Stub,
+        host: tokio::io::DuplexStream,
+    ) -> (NcpHandle, Receiver<Event>) {
+        connect_with_version(ncp, host, 13).await
+    }
+
+    /// Connect to a stub speaking `version` whose network is already up.
+    async fn connect_with_version(
+        ncp: &mut Stub,
+        host: tokio::io::DuplexStream,
+        version: u8,
+    ) -> (NcpHandle, Receiver<Event>) {
+        let connect = spawn(Ezsp::connect(host, Config::new(), CAPACITY));
+        ncp.reset().await;
+        ncp.negotiate(version).await;
+        ncp.answer(0x0026, &EUI64).await;
+        ncp.answer(0x0017, &[0x00]).await;
+        let (driver, events) = connect
+            .await
+            .expect("task must finish")
+            .expect("NCP must initialize");
+        let (handle, actor) = driver.into_actor(CAPACITY);
+        spawn(actor);
+        (handle, events)
+    }
+
+    #[test]
+    fn registers_endpoints_and_forms_a_network() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let config = Config::new()
+                .with_endpoint(SimpleDescriptor::new(
+                    ENDPOINT,
+                    Profile::ZigbeeHomeAutomation,
+                    0x0005,
+                    AppFlags::empty(),
+                    Clusters::from_slice(&[0x0000]).expect("one cluster fits"),
+                    Clusters::from_slice(&[ON_OFF]).expect("one cluster fits"),
+                ))
+                .with_formation(Formation::new(
+                    PAN_ID,
+                    IeeeAddress::new(0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD),
+                    ChannelMask::new(1 << 15).expect("channel 15 is valid"),
+                    Key::new([0xAB; Key::SIZE]),
+                ));
+            let connect = spawn(Ezsp::connect(host, config, CAPACITY));
+
+            ncp.reset().await;
+            ncp.negotiate_version().await;
+            ncp.answer(0x0026, &EUI64).await;
+            assert_eq!(
+                ncp.answer(0x0002, &[0x00]).await,
+                [
+                    0x01, 0x04, 0x01, 0x05, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x06, 0x00
+                ]
+            );
+            ncp.answer(0x0017, &[0x93]).await;
+            let security = ncp.answer(0x0068, &[0x00]).await;
+            assert_eq!(security[..2], [0x04, 0x0B]);
+            assert_eq!(security[2..18], *b"ZigBeeAlliance09");
+            assert_eq!(security[18..34], [0xAB; 16]);
+            assert_eq!(security[35..], EUI64);
+            assert_eq!(
+                ncp.answer(0x001E, &[0x00]).await,
+                [
+                    0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0x62, 0x1A, 0x08, 0x0F, 0x00,
+                    0x00, 0x00, 0x00, 0x00, 0x80, 0x00, 0x00
+                ]
+            );
+            ncp.callback(0x0019, &[0x90]).await;
+
+            let (mut driver, mut events) = connect
+                .await
+                .expect("task must finish")
+                .expect("NCP must initialize");
+            assert!(matches!(
+                events.recv().await,
+                Some(Event::Network(NetworkEvent::Up))
+            ));
+            assert_eq!(
+                driver
+                    .get_ieee_address()
+                    .await
+                    .expect("EUI-64 must be cached"),
+                IeeeAddress::new(8, 7, 6, 5, 4, 3, 2, 1)
+            );
+            assert_eq!(
+                driver
+                    .get_endpoints()
+                    .await
+                    .expect("endpoints must be cached")
+                    .len(),
+                1
+            );
+        });
+    }
+
+    #[test]
+    fn confirms_acknowledged_unicasts_with_the_aps_counter() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (handle, mut events) = connect(&mut ncp, host).await;
+            let request = DataRequest::new(
+                RequestDestination::Network {
+                    address: NetworkAddress::new(DEVICE).expect("address is valid"),
+                    endpoint: ENDPOINT,
+                },
+                Profile::ZigbeeHomeAutomation.as_u16(),
+                ON_OFF,
+                IndividualEndpoint::new(ENDPOINT).expect("endpoint is individual"),
+                Bytes::from_static(&[0x01, 0x2A, 0x02]),
+            )
+            .with_tx_options(TxOptions::ACKNOWLEDGED_TRANSMISSION);
+            let transmit = {
+                let handle = handle.clone();
+                spawn(async move { handle.transmit(request, 7).await })
+            };
+
+            assert_eq!(
+                ncp.answer(0x0034, &[0x00, 0x42]).await,
+                [
+                    0x00, 0x34, 0x12, 0x04, 0x01, 0x06, 0x00, 0x01, 0x01, 0x40, 0x01, 0x00, 0x00,
+                    0x07, 0x07, 0x03, 0x01, 0x2A, 0x02
+                ]
+            );
+            transmit
+                .await
+                .expect("task must finish")
+                .expect("NCP must accept the unicast");
+
+            ncp.callback(
+                0x003F,
+                &[
+                    0x00, 0x34, 0x12, 0x04, 0x01, 0x06, 0x00, 0x01, 0x01, 0x40, 0x01, 0x00, 0x00,
+                    0x42, 0x07, 0x00, 0x00,
+                ],
+            )
+            .await;
+            let Some(Event::Apsde(ApsdeEvent::DataConfirm {
+                counter,
+                confirmation,
+            })) = events.recv().await
+            else {
+                panic!("acknowledged unicast must be confirmed");
+            };
+            assert_eq!(counter, 7);
+            assert_eq!(confirmation.status(), ConfirmStatus::Aps(Status::Success));
+        });
+    }
+
+    #[test]
+    fn sends_fragment_blocks_with_their_position_in_the_group_id() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (handle, _events) = connect(&mut ncp, host).await;
+            let request = DataRequest::new(
+                RequestDestination::Network {
+                    address: NetworkAddress::new(DEVICE).expect("address is valid"),
+                    endpoint: ENDPOINT,
+                },
+                Profile::ZigbeeHomeAutomation.as_u16(),
+                ON_OFF,
+                IndividualEndpoint::new(ENDPOINT).expect("endpoint is individual"),
+                Bytes::from_static(&[0x01, 0x2A]),
+            )
+            .with_tx_options(TxOptions::ACKNOWLEDGED_TRANSMISSION)
+            .with_block(Block::new(1, 3).expect("block lies within the transmission"));
+            let transmit = {
+                let handle = handle.clone();
+                spawn(async move { handle.transmit(request, 7).await })
+            };
+
+            assert_eq!(
+                ncp.answer(0x0034, &[0x00, 0x42]).await,
+                [
+                    0x00, 0x34, 0x12, 0x04, 0x01, 0x06, 0x00, 0x01, 0x01, 0x40, 0x81, 0x01, 0x03,
+                    0x07, 0x07, 0x02, 0x01, 0x2A
+                ]
+            );
+            transmit
+                .await
+                .expect("task must finish")
+                .expect("NCP must accept the block");
+
+            let read = spawn(async move { handle.get_maximum_payload_length().await });
+            assert_eq!(ncp.answer(0x0033, &[0x52]).await, []);
+            assert_eq!(
+                read.await
+                    .expect("task must finish")
+                    .expect("NCP must report its payload length"),
+                0x52
+            );
+        });
+    }
+
+    #[test]
+    fn indicates_incoming_messages_and_reports_unknown_devices() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (handle, mut events) = connect(&mut ncp, host).await;
+
+            ncp.callback(
+                0x0045,
+                &[
+                    0x00, 0x04, 0x01, 0x06, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x05, 0xC8,
+                    0xD8, 0x34, 0x12, 0xFF, 0xFF, 0x02, 0x18, 0x05,
+                ],
+            )
+            .await;
+            let Some(Event::Apsde(ApsdeEvent::DataIndication(indication))) = events.recv().await
+            else {
+                panic!("incoming message must be indicated");
+            };
+            assert_eq!(indication.metadata().link_quality(), 200);
+            assert_eq!(indication.metadata().rssi(), Some(-40));
+            assert!(matches!(
+                indication.metadata().source(),
+                Source::Network { address, .. } if address.as_u16() == DEVICE
+            ));
+            assert_eq!(indication.asdu().as_ref(), [0x18, 0x05]);
+
+            let lookup = spawn(async move {
+                handle
+                    .ieee_address_to_short_id(IeeeAddress::new(1, 1, 1, 1, 1, 1, 1, 1))
+                    .await
+            });
+            ncp.answer(0x0060, &[0xFF, 0xFF]).await;
+            assert!(lookup.await.expect("task must finish").is_err());
+        });
+    }
+
+    #[test]
+    fn adds_transient_link_keys_with_the_commands_of_the_negotiated_version() {
+        run(async {
+            for (version, frame_id, status) in [
+                (13, 0x0111, &[0x00, 0x00, 0x00, 0x00][..]),
+                (12, 0x00AF, &[0x00][..]),
+            ] {
+                let (mut ncp, host) = Stub::new();
+                let (handle, _events) = connect_with_version(&mut ncp, host, version).await;
+                let add = spawn(async move {
+                    handle
+                        .add_transient_link_key(
+                            IeeeAddress::new(1, 1, 1, 1, 1, 1, 1, 1),
+                            Key::new([0xAB; Key::SIZE]),
+                        )
+                        .await
+                });
+
+                let parameters = ncp.answer(frame_id, status).await;
+                assert_eq!(parameters[..8], [0x01; 8]);
+                assert_eq!(parameters[8..24], [0xAB; 16]);
+                add.await
+                    .expect("task must finish")
+                    .expect("NCP must store the transient key");
+            }
+        });
+    }
+
+    #[test]
+    fn replaces_the_key_table_entry_of_a_device() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (handle, _events) = connect(&mut ncp, host).await;
+            let set = spawn(async move {
+                handle
+                    .set_link_key(
+                        IeeeAddress::new(1, 1, 1, 1, 1, 1, 1, 1),
+                        Key::new([0xAB; Key::SIZE]),
+                    )
+                    .await
+            });
+
+            assert_eq!(
+                ncp.answer(0x0075, &[0x03]).await,
+                [0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01]
+            );
+            let parameters = ncp.answer(0x010E, &[0x00, 0x00, 0x00, 0x00]).await;
+            assert_eq!(parameters[0], 0x03);
+            assert_eq!(parameters[1..9], [0x01; 8]);
+            assert_eq!(parameters[9..], [0xAB; 16]);
+            set.await
+                .expect("task must finish")
+                .expect("NCP must store the link key");
+
+            let (mut ncp, host) = Stub::new();
+            let (handle, _events) = connect_with_version(&mut ncp, host, 12).await;
+            let set = spawn(async move {
+                handle
+                    .set_link_key(
+                        IeeeAddress::new(1, 1, 1, 1, 1, 1, 1, 1),
+                        Key::new([0xAB; Key::SIZE]),
+                    )
+                    .await
+            });
+
+            let parameters = ncp.answer(0x0066, &[0x00]).await;
+            assert_eq!(
+                parameters[..9],
+                [0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01]
+            );
+            assert_eq!(parameters[9..], [0xAB; 16]);
+            set.await
+                .expect("task must finish")
+                .expect("NCP must store the link key");
+        });
+    }
+
+    #[test]
+    fn reports_rejected_transient_link_keys() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (handle, _events) = connect(&mut ncp, host).await;
+            let add = spawn(async move {
+                handle
+                    .add_transient_link_key(
+                        IeeeAddress::new(1, 1, 1, 1, 1, 1, 1, 1),
+                        Key::new([0xAB; Key::SIZE]),
+                    )
+                    .await
+            });
+
+            assert_eq!(
+                ncp.answer(0x0111, &[0x0C, 0x00, 0x00, 0x00]).await[24],
+                0x00
+            );
+            assert!(add.await.expect("task must finish").is_err());
+        });
+    }
+
+    #[test]
+    fn sets_the_trust_center_policy_and_leaves_the_network() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (handle, _events) = connect(&mut ncp, host).await;
+            let policy = {
+                let handle = handle.clone();
+                spawn(async move {
+                    handle
+                        .set_trust_center_policy(
+                            TrustCenterPolicy::new(JoinPolicy::InstallCodeOnly)
+                                .with_link_key_requests(false),
+                        )
+                        .await
+                })
+            };
+
+            assert_eq!(ncp.answer(0x0055, &[0x00]).await, [0x00, 0x11]);
+            assert_eq!(ncp.answer(0x0055, &[0x00]).await, [0x05, 0x50]);
+            policy
+                .await
+                .expect("task must finish")
+                .expect("NCP must accept the policy");
+
+            let leave = spawn(async move { handle.leave_network().await });
+            assert_eq!(ncp.answer(0x0020, &[0x00]).await, []);
+            leave
+                .await
+                .expect("task must finish")
+                .expect("NCP must leave the network");
+        });
+    }
+
+    #[test]
+    fn exchanges_inter_pan_frames_on_another_channel() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (handle, mut events) = connect(&mut ncp, host).await;
+            let mut network_parameters = vec![0x00, 0x01];
+            network_parameters.extend([0xDD; 8]);
+            network_parameters.extend([0x62, 0x1A, 0x08, 0x0B, 0x00, 0x00, 0x00, 0x00]);
+            network_parameters.extend([0x00, 0x08, 0x00, 0x00]);
+            let transmit = {
+                let handle = handle.clone();
+                spawn(async move {
+                    handle
+                        .transmit_inter_pan(InterPanRequest::new(
+                            Channel::new(15).expect("channel 15 is valid"),
+                            InterPanDestination::Broadcast,
+                            0xC05E,
+                            0x1000,
+                            Bytes::from_static(&[0x11, 0x00, 0x00]),
+                        ))
+                        .await
+                })
+            };
+
+            ncp.answer(0x0028, &network_parameters).await;
+            assert_eq!(ncp.answer(0x009A, &[0x00]).await, [0x0F]);
+            let raw = ncp.answer(0x0096, &[0x00]).await;
+            assert_eq!(usize::from(raw[0]), raw.len() - 1);
+            assert_eq!(raw[1..3], [0x01, 0xC8]);
+            assert_eq!(raw[raw.len() - 3..], [0x11, 0x00, 0x00]);
+            transmit
+                .await
+                .expect("task must finish")
+                .expect("NCP must send the raw frame");
+
+            let mut filter_match = vec![0x00, 0x00, 0xC8, 0xD8, 0x00];
+            filter_match.extend([0x21, 0xCC, 0x07, 0xFF, 0xFF]);
+            filter_match.extend(EUI64);
+            filter_match.extend([0x0B, 0x0B, 0x01, 0x00, 0x00, 0x00, 0x01, 0x88, 0x17, 0x00]);
+            filter_match.extend([0x0B, 0x00, 0x03, 0x00, 0x10, 0x5E, 0xC0, 0x19, 0x00, 0x01]);
+            filter_match[4] = u8::try_from(filter_match.len() - 5).expect("frame fits");
+            ncp.callback(0x0046, &filter_match).await;
+            let Some(Event::InterPan(indication)) = events.recv().await else {
+                panic!("inter-PAN frame must be indicated");
+            };
+            assert_eq!(
+                indication.source(),
+                IeeeAddress::new(0, 0x17, 0x88, 0x01, 0, 0, 0, 1)
+            );
+            assert_eq!(indication.source_pan_id(), 0x0B0B);
+            assert_eq!(indication.link_quality(), 200);
+            assert_eq!(indication.asdu().as_ref(), [0x19, 0x00, 0x01]);
+
+            let end = spawn(async move { handle.end_inter_pan().await });
+            ncp.answer(0x0028, &network_parameters).await;
+            assert_eq!(ncp.answer(0x009A, &[0x00]).await, [0x0B]);
+            end.await
+                .expect("task must finish")
+                .expect("NCP must return to the network channel");
+        });
+    }
+
+    #[test]
+    fn restores_backed_up_networks() {
+        run(async {
+            let device = IeeeAddress::new(1, 1, 1, 1, 1, 1, 1, 1);
+            let (mut ncp, host) = Stub::new();
+            let (handle, _events) = connect(&mut ncp, host).await;
+            let backup = spawn(async move { handle.backup_network().await });
+
+            let mut network_parameters = vec![0x00, 0x01];
+            network_parameters.extend([0xDD; 8]);
+            network_parameters.extend([0x62, 0x1A, 0x08, 0x0F, 0x00, 0x00, 0x00, 0x03]);
+            network_parameters.extend([0x00, 0x80, 0x00, 0x00]);
+            ncp.answer(0x0028, &network_parameters).await;
+            let mut network_key = vec![0xAB; 16];
+            network_key.extend([0x00; 4]);
+            assert_eq!(ncp.answer(0x0114, &network_key).await[0], 0x01);
+            ncp.answer(
+                0x0116,
+                &[
+                    0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x05, 0x00, 0x00, 0x10, 0x00, 0x00,
+                ],
+            )
+            .await;
+            assert_eq!(ncp.answer(0x0052, &[0x00, 0x02, 0x00]).await, [0x1E]);
+            let mut link_key = vec![0x01; 8];
+            link_key.extend([0xCD; 16]);
+            link_key.extend([0x00, 0x00, 0x40, 0x00, 0x00, 0x00, 0x20, 0x00, 0x00, 0x00]);
+            link_key.extend([0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+            assert_eq!(ncp.answer(0x010F, &link_key).await, [0x00]);
+            let mut empty = vec![0x00; 36];
+            empty.extend([0x0E, 0x00, 0x00, 0x00]);
+            assert_eq!(ncp.answer(0x010F, &empty).await, [0x01]);
+            let backup = backup
+                .await
+                .expect("task must finish")
+                .expect("NCP must export its network");
+
+            assert_eq!(
+                backup.ieee_address(),
+                IeeeAddress::new(8, 7, 6, 5, 4, 3, 2, 1)
+            );
+            assert_eq!(backup.parameters().pan_id(), PAN_ID);
+            assert_eq!(
+                backup.channel(),
+                Channel::new(15).expect("channel 15 is valid")
+            );
+            assert_eq!(backup.nwk_update_id(), 3);
+            assert_eq!(backup.network_key().sequence_number(), 5);
+            assert_eq!(backup.network_key().frame_counter(), 0x1000);
+            assert_eq!(backup.devices().len(), 1);
+            assert_eq!(backup.devices()[0].ieee_address(), device);
+            let link_key = backup.devices()[0]
+                .link_key()
+                .expect("link key must be exported");
+            assert_eq!(*link_key.key(), Key::new([0xCD; Key::SIZE]));
+            assert_eq!(link_key.tx_counter(), 0x40);
+            assert_eq!(link_key.rx_counter(), 0x20);
+
+            let (mut ncp, host) = Stub::new();
+            let (handle, mut events) = connect(&mut ncp, host).await;
+            let restore = spawn(async move { handle.restore_network(backup).await });
+
+            ncp.answer(0x0018, &[0x00]).await;
+            let security = ncp.answer(0x0068, &[0x00]).await;
+            assert_eq!(security[..2], [0x04, 0x1B]);
+            assert_eq!(security[18..34], [0xAB; 16]);
+            assert_eq!(security[34], 5);
+            assert_eq!(
+                ncp.answer(0x00AB, &[0x00]).await,
+                [0x24, 0x04, 0x00, 0x10, 0x00, 0x00]
+            );
+            assert_eq!(ncp.answer(0x0075, &[0xFF]).await, [0x01; 9]);
+            let import = ncp.answer(0x010E, &[0x00; 4]).await;
+            assert_eq!(
+                import[..9],
+                [0xFF, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01]
+            );
+            assert_eq!(import[9..], [0xCD; 16]);
+            assert_eq!(
+                ncp.answer(0x00AB, &[0x00]).await,
+                [0x25, 0x04, 0x40, 0x00, 0x00, 0x00]
+            );
+            assert_eq!(ncp.answer(0x001E, &[0x00]).await, network_parameters[2..]);
+            ncp.callback(0x0019, &[0x90]).await;
+            restore
+                .await
+                .expect("task must finish")
+                .expect("NCP must restore the network");
+            assert!(matches!(
+                events.recv().await,
+                Some(Event::Network(NetworkEvent::Up))
+            ));
+        });
+    }
+
+    #[test]
+    fn refuses_to_restore_over_a_network() {
+        run(async {
+            let backup = NetworkBackup::new(
+                IeeeAddress::new(8, 7, 6, 5, 4, 3, 2, 1),
+                NetworkParameters::new(
+                    PAN_ID,
+                    IeeeAddress::new(0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD),
+                    Channel::new(15).expect("channel 15 is valid"),
+                ),
+                NetworkKeyState::new(Key::new([0xAB; Key::SIZE]), 0, 0),
+            );
+            let (mut ncp, host) = Stub::new();
+            let (handle, _events) = connect(&mut ncp, host).await;
+            let restore = spawn(async move { handle.restore_network(backup).await });
+
+            ncp.answer(0x0018, &[0x02]).await;
+            assert!(restore.await.expect("task must finish").is_err());
+        });
+    }
+
+    #[test]
+    fn reads_counters_in_ember_counter_type_order() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (handle, _events) = connect(&mut ncp, host).await;
+            let read = spawn(async move { handle.get_counters().await });
+            let response: Vec<u8> = (0..40u16).flat_map(u16::to_le_bytes).collect();
+
+            assert_eq!(ncp.answer(0x00F1, &response).await, []);
+            let counters = read
+                .await
+                .expect("task must finish")
+                .expect("NCP must report its counters");
+            assert_eq!(counters.get(Counter::MacTxUnicastRetry), Some(4));
+            assert_eq!(counters.get(Counter::ApsTxUnicastFailure), Some(11));
+            assert_eq!(counters.get(Counter::RouteDiscoveryInitiated), Some(12));
+            assert_eq!(counters.get(Counter::NwkFrameCounterFailure), Some(21));
+            assert_eq!(counters.get(Counter::BufferAllocationFailure), Some(27));
+            assert_eq!(counters.len(), 25);
+        });
+    }
+
+    #[test]
+    fn rejects_truncated_counters() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (handle, _events) = connect(&mut ncp, host).await;
+            let read = spawn(async move { handle.get_counters().await });
+
+            ncp.answer(0x00F1, &[0x01, 0x00]).await;
+            assert!(read.await.expect("task must finish").is_err());
+        });
+    }
+}
@@ -0,0 +1,95 @@
+//! Errors of the EZSP backend.
+
+use std::io;
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::frame::FrameId;
+
+/// Errors of the EZSP backend.
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// Reading from or writing to the serial stream failed.
+    #[error("Serial I/O error: {0}")]
+    Io(#[source] Arc<io::Error>),
+
+    /// The NCP closed the serial stream.
+    #[error("Serial stream closed")]
+    Closed,
+
+    /// The NCP did not answer the ASH reset.
+    #[error("NCP did not acknowledge the reset")]
+    ResetTimeout,
+
+    /// The NCP speaks an unsupported ASH version.
+    #[error("Unsupported ASH version: {0}")]
+    UnsupportedAshVersion(u8),
+
+    /// The NCP entered the ASH failed state.
+    #[error("NCP failed with error code {0:#04X}")]
+    NcpFailed(u8),
+
+    /// The NCP reset unexpectedly.
+    #[error("NCP reset with reset code {0:#04X}")]
+    Reset(u8),
+
+    /// The NCP did not acknowledge a data frame after all retransmissions.
+    #[error("NCP did not acknowledge a data frame")]
+    NoAcknowledgement,
+
+    /// The NCP did not respond to a command in time.
+    #[error("NCP did not respond to {0}")]
+    Timeout(FrameId),
+
+    /// The NCP speaks an unsupported EZSP protocol version.
+    #[error("Unsupported EZSP protocol version: {0}")]
+    UnsupportedVersion(u8),
+
+    /// The NCP sent a response that could not be parsed.
+    #[error("Malformed response to {0}")]
+    MalformedResponse(FrameId),
+
+    /// The NCP rejected a command.
+    #[error("{frame_id} failed with status {status:#04X}")]
+    Status {
+        /// The rejected command.
+        frame_id: FrameId,
+        /// The EZSP or Ember status reported by the NCP.
+        status: u8,
+    },
+
+    /// The NCP's security manager rejected a command.
+    #[error("{frame_id} failed with security manager status {status:#06X}")]
+    SecurityStatus {
+        /// The rejected command.
+        frame_id: FrameId,
+        /// The `sl_status_t` reported by the NCP.
+        status: u32,
+    },
+
+    /// A network cannot be restored on an NCP that is already part of one.
+    #[error("NCP is already part of a network")]
+    AlreadyJoined,
+
+    /// A network cannot be formed on an empty channel mask.
+    #[error("Channel mask contains no channel")]
+    EmptyChannelMask,
+
+    /// The NCP does not know the requested device.
+    #[error("Unknown device")]
+    UnknownDevice,
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(Arc::new(error))
+    }
+}
+
+impl From<Error> for zb_hw::Error {
+    fn from(error: Error) -> Self {
+        Self::backend(error)
+    }
+}
@@ -0,0 +1,262 @@
+//! EZSP frames.
+
+use std::fmt::{self, Display, Formatter};
+
+use bytes::Bytes;
+use le_stream::{FromLeStream, ToLeStream};
+
+/// Frame control byte of commands sent by the host.
+const COMMAND: u8 = 0x00;
+
+/// Frame control bit of responses sent by the NCP.
+const RESPONSE: u8 = 0x80;
+
+/// Frame control bits of the callback type.
+const CALLBACK_TYPE: u8 = 0x18;
+
+/// Frame control bit signalling that the NCP ran out of memory.
+const OVERFLOW: u8 = 0x01;
+
+/// Frame control bit signalling that the NCP truncated the response.
+const TRUNCATED: u8 = 0x02;
+
+/// High frame control byte of the extended frame format version 1.
+const FRAME_FORMAT_VERSION: u8 = 0x01;
+
+/// Size of the extended header: sequence, two frame control bytes, and the frame ID.
+const HEADER_SIZE: usize = 5;
+
+/// Size of the legacy header: sequence, frame control, and the frame ID.
+const LEGACY_HEADER_SIZE: usize = 3;
+
+macro_rules! frame_ids {
+    ($($(#[$doc:meta])* $name:ident = $id:literal => $display:literal,)+) => {
+        /// Identifier of an EZSP command, response, or callback.
+        #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        pub struct FrameId(u16);
+
+        impl FrameId {
+            $(
+                $(#[$doc])*
+                pub const $name: Self = Self($id);
+            )+
+
+            /// Return the raw frame ID.
+            #[must_use]
+            pub const fn as_u16(self) -> u16 {
+                self.0
+            }
+        }
+
+        impl Display for FrameId {
+            fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+                match *self {
+                    $(Self::$name => formatter.write_str($display),)+
+                    Self(id) => write!(formatter, "EZSP frame {id:#06X}"),
+                }
+            }
+        }
+    };
+}
+
+frame_ids! {
+    /// Negotiates the protocol version.
+    VERSION = 0x0000 => "version",
+    /// Configures an application endpoint.
+    ADD_ENDPOINT = 0x0002 => "addEndpoint",
+    /// Resumes the network stored in the NCP's tokens.
+    NETWORK_INIT = 0x0017 => "networkInit",
+    /// Reads whether the NCP is part of a network.
+    NETWORK_STATE = 0x0018 => "networkState",
+    /// Reports a change of the network state.
+    STACK_STATUS_HANDLER = 0x0019 => "stackStatusHandler",
+    /// Forms a new network.
+    FORM_NETWORK = 0x001E => "formNetwork",
+    /// Leaves the current network.
+    LEAVE_NETWORK = 0x0020 => "leaveNetwork",
+    /// Permits devices to join.
+    PERMIT_JOINING = 0x0022 => "permitJoining",
+    /// Reports a device joining or leaving the network.
+    TRUST_CENTER_JOIN_HANDLER = 0x0024 => "trustCenterJoinHandler",
+    /// Reads the NCP's EUI-64.
+    GET_EUI64 = 0x0026 => "getEui64",
+    /// Reads the current network parameters.
+    GET_NETWORK_PARAMETERS = 0x0028 => "getNetworkParameters",
+    /// Reads the longest APS payload the NCP sends in a single frame.
+    MAXIMUM_PAYLOAD_LENGTH = 0x0033 => "maximumPayloadLength",
+    /// Sends a unicast.
+    SEND_UNICAST = 0x0034 => "sendUnicast",
+    /// Sends a broadcast.
+    SEND_BROADCAST = 0x0036 => "sendBroadcast",
+    /// Sends a multicast.
+    SEND_MULTICAST = 0x0038 => "sendMulticast",
+    /// Reports the completion of a sent message.
+    MESSAGE_SENT_HANDLER = 0x003F => "messageSentHandler",
+    /// Sends a many-to-one route request.
+    SEND_MANY_TO_ONE_ROUTE_REQUEST = 0x0041 => "sendManyToOneRouteRequest",
+    /// Reports a received message.
+    INCOMING_MESSAGE_HANDLER = 0x0045 => "incomingMessageHandler",
+    /// Reports a received MAC frame matching a filter, such as an inter-PAN frame.
+    MAC_FILTER_MATCH_MESSAGE_HANDLER = 0x0046 => "macFilterMatchMessageHandler",
+    /// Reads a configuration value.
+    GET_CONFIGURATION_VALUE = 0x0052 => "getConfigurationValue",
+    /// Sets the NCP's decision for a policy.
+    SET_POLICY = 0x0055 => "setPolicy",
+    /// Resolves an EUI-64 to a node ID.
+    LOOKUP_NODE_ID_BY_EUI64 = 0x0060 => "lookupNodeIdByEui64",
+    /// Resolves a node ID to an EUI-64.
+    LOOKUP_EUI64_BY_NODE_ID = 0x0061 => "lookupEui64ByNodeId",
+    /// Adds or replaces a key table entry of EZSP versions before 13.
+    ADD_OR_UPDATE_KEY_TABLE_ENTRY = 0x0066 => "addOrUpdateKeyTableEntry",
+    /// Configures the security of a network to be formed.
+    SET_INITIAL_SECURITY_STATE = 0x0068 => "setInitialSecurityState",
+    /// Reads a key of EZSP versions before 13.
+    GET_KEY = 0x006A => "getKey",
+    /// Reads a key table entry of EZSP versions before 13.
+    GET_KEY_TABLE_ENTRY = 0x0071 => "getKeyTableEntry",
+    /// Finds the key table entry of a device.
+    FIND_KEY_TABLE_ENTRY = 0x0075 => "findKeyTableEntry",
+    /// Sends a raw IEEE 802.15.4 frame.
+    SEND_RAW_MESSAGE = 0x0096 => "sendRawMessage",
+    /// Switches the radio to another channel without leaving the network.
+    SET_RADIO_CHANNEL = 0x009A => "setRadioChannel",
+    /// Writes a value.
+    SET_VALUE = 0x00AB => "setValue",
+    /// Adds a transient link key of EZSP versions before 13.
+    ADD_TRANSIENT_LINK_KEY = 0x00AF => "addTransientLinkKey",
+    /// Reports a route error.
+    INCOMING_ROUTE_ERROR_HANDLER = 0x0080 => "incomingRouteErrorHandler",
+    /// Reads the NCP's diagnostic counters.
+    READ_COUNTERS = 0x00F1 => "readCounters",
+    /// Imports a link key into the key table.
+    IMPORT_LINK_KEY = 0x010E => "importLinkKey",
+    /// Exports a key table entry.
+    EXPORT_LINK_KEY_BY_INDEX = 0x010F => "exportLinkKeyByIndex",
+    /// Imports a transient link key.
+    IMPORT_TRANSIENT_KEY = 0x0111 => "importTransientKey",
+    /// Exports a key of the security manager.
+    EXPORT_KEY = 0x0114 => "exportKey",
+    /// Reads the sequence number and frame counter of the network key.
+    GET_NETWORK_KEY_INFO = 0x0116 => "getNetworkKeyInfo",
+}
+
+/// A parsed EZSP frame received from the NCP.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Received {
+    control: u8,
+    frame_id: FrameId,
+    parameters: Bytes,
+}
+
+impl Received {
+    /// Parse an extended-format frame.
+    #[must_use]
+    pub fn parse(frame: &Bytes) -> Option<Self> {
+        if frame.len() < HEADER_SIZE {
+            return None;
+        }
+
+        Some(Self {
+            control: frame[1],
+            frame_id: FrameId(u16::from_le_bytes([frame[3], frame[4]])),
+            parameters: frame.slice(HEADER_SIZE..),
+        })
+    }
+
+    /// Parse a legacy-format frame, used only to answer the initial version command.
+    #[must_use]
+    pub fn parse_legacy(frame: &Bytes) -> Option<Self> {
+        if frame.len() < LEGACY_HEADER_SIZE {
+            return None;
+        }
+
+        Some(Self {
+            control: frame[1],
+            frame_id: FrameId(frame[2].into()),
+            parameters: frame.slice(LEGACY_HEADER_SIZE..),
+        })
+    }
+
+    /// Return the frame ID.
+    #[must_use]
+    pub const fn frame_id(&self) -> FrameId {
+        self.frame_id
+    }
+
+    /// Return whether the NCP reported running out of memory or truncating the frame.
+    #[must_use]
+    pub const fn is_degraded(&self) -> bool {
+        self.control & (OVERFLOW | TRUNCATED) != 0
+    }
+
+    /// Parse the frame's parameters.
+    pub fn parameters<T>(&self) -> Option<T>
+    where
+        T: FromLeStream,
+    {
+        T::from_le_stream(self.parameters.iter().copied())
+    }
+}
+
+/// Return whether an encoded frame received from the NCP is a callback.
+#[must_use]
+pub fn is_callback(frame: &[u8]) -> bool {
+    frame
+        .get(1)
+        .is_some_and(|control| control & RESPONSE != 0 && control & CALLBACK_TYPE != 0)
+}
+
+/// Return the sequence number of an encoded frame.
+#[must_use]
+pub const fn sequence(frame: &[u8]) -> Option<u8> {
+    frame.first().copied()
+}
+
+/// Encode an extended-format command.
+pub fn command<T>(sequence: u8, frame_id: FrameId, parameters: T) -> Vec<u8>
+where
+    T: ToLeStream,
+{
+    [sequence, COMMAND, FRAME_FORMAT_VERSION]
+        .into_iter()
+        .chain(frame_id.as_u16().to_le_stream())
+        .chain(parameters.to_le_stream())
+        .collect()
+}
+
+/// Encode a legacy-format version command.
+pub fn legacy_version(sequence: u8, desired_version: u8) -> Vec<u8> {
+    vec![sequence, COMMAND, 0x00, desired_version]
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::{FrameId, Received, command, is_callback};
+
+    #[test]
+    fn encodes_extended_commands() {
+        assert_eq!(
+            command(0x12, FrameId::PERMIT_JOINING, 0x3C_u8),
+            [0x12, 0x00, 0x01, 0x22, 0x00, 0x3C]
+        );
+    }
+
+    #[test]
+    fn parses_responses_and_callbacks() {
+        let response = Bytes::from_static(&[0x12, 0x80, 0x01, 0x22, 0x00, 0x00]);
+        let received = Received::parse(&response).expect("response is complete");
+
+        assert_eq!(received.frame_id(), FrameId::PERMIT_JOINING);
+        assert_eq!(received.parameters::<u8>(), Some(0x00));
+        assert!(!is_callback(&response));
+        assert!(is_callback(&[0x00, 0x90, 0x01, 0x19, 0x00, 0x90]));
+    }
+
+    #[test]
+    fn displays_known_and_unknown_frame_ids() {
+        assert_eq!(FrameId::SEND_UNICAST.to_string(), "sendUnicast");
+        assert_eq!(FrameId(0x1234).to_string(), "EZSP frame 0x1234");
+    }
+}
@@ -0,0 +1,276 @@
+//! Raw IEEE 802.15.4 frames carrying inter-PAN messages.
+//!
+//! The NCP sends inter-PAN frames with `sendRawMessage` and reports received ones with
+//! `macFilterMatchMessageHandler`, so the host builds and parses the MAC header, the stub NWK
+//! header, and the stub APS header itself. The frame check sequence is added and checked by the
+//! radio.
+
+use bytes::Bytes;
+use le_stream::{FromLeStream, ToLeStream};
+use zb_hw::core::IeeeAddress;
+use zb_hw::{InterPanDestination, InterPanIndication, InterPanRequest};
+
+/// MAC frame type of data frames.
+const MAC_DATA: u16 = 0x0001;
+
+/// MAC frame control bit requesting an acknowledgement.
+const MAC_ACK_REQUEST: u16 = 0x0020;
+
+/// MAC frame control bit omitting the source PAN ID.
+const MAC_PAN_ID_COMPRESSION: u16 = 0x0040;
+
+/// Offset of the destination address mode in the MAC frame control.
+const MAC_DESTINATION_MODE_SHIFT: u16 = 10;
+
+/// Offset of the source address mode in the MAC frame control.
+const MAC_SOURCE_MODE_SHIFT: u16 = 14;
+
+/// MAC address mode of 16-bit short addresses.
+const SHORT_MODE: u16 = 0b10;
+
+/// MAC address mode of 64-bit extended addresses.
+const EXTENDED_MODE: u16 = 0b11;
+
+/// Mask of a MAC address mode.
+const MODE_MASK: u16 = 0b11;
+
+/// Short address of every device in radio range.
+const BROADCAST_ADDRESS: u16 = 0xFFFF;
+
+/// Frame control of the stub NWK header: an inter-PAN frame of protocol version 2.
+const STUB_NWK: u16 = 0x000B;
+
+/// Mask of the frame type in the NWK frame control.
+const NWK_FRAME_TYPE_MASK: u16 = 0x0003;
+
+/// Frame type of inter-PAN frames in the NWK and APS frame controls.
+const INTER_PAN: u8 = 0x03;
+
+/// Mask of the frame type in the APS frame control.
+const APS_FRAME_TYPE_MASK: u8 = 0x03;
+
+/// APS delivery mode of unicasts.
+const APS_UNICAST: u8 = 0x00;
+
+/// APS delivery mode of broadcasts.
+const APS_BROADCAST: u8 = 0x08;
+
+/// APS delivery mode of group frames, which carry a group ID.
+const APS_GROUP: u8 = 0x0C;
+
+/// Mask of the delivery mode in the APS frame control.
+const APS_DELIVERY_MODE_MASK: u8 = 0x0C;
+
+/// Encode an inter-PAN request as a MAC frame sent from `source` on the PAN `source_pan_id`.
+pub fn encode(
+    request: &InterPanRequest,
+    sequence: u8,
+    source_pan_id: u16,
+    source: IeeeAddress,
+) -> Bytes {
+    let destination = request.destination();
+    let (mac_control, aps_control) = match destination {
+        InterPanDestination::Broadcast => (
+            MAC_DATA | SHORT_MODE << MAC_DESTINATION_MODE_SHIFT,
+            APS_BROADCAST | INTER_PAN,
+        ),
+        InterPanDestination::Device { .. } => (
+            MAC_DATA | MAC_ACK_REQUEST | EXTENDED_MODE << MAC_DESTINATION_MODE_SHIFT,
+            APS_UNICAST | INTER_PAN,
+        ),
+    };
+    let mut frame = Vec::with_capacity(request.asdu().len() + 32);
+    frame.extend((mac_control | EXTENDED_MODE << MAC_SOURCE_MODE_SHIFT).to_le_bytes());
+    frame.push(sequence);
+    frame.extend(destination.pan_id().to_le_bytes());
+
+    match destination {
+        InterPanDestination::Broadcast => frame.extend(BROADCAST_ADDRESS.to_le_bytes()),
+        InterPanDestination::Device { ieee_address, .. } => {
+            frame.extend(ieee_address.to_le_stream());
+        }
+    }
+
+    frame.extend(source_pan_id.to_le_bytes());
+    frame.extend(source.to_le_stream());
+    frame.extend(STUB_NWK.to_le_bytes());
+    frame.push(aps_control);
+    frame.extend(request.cluster_id().to_le_bytes());
+    frame.extend(request.profile_id().to_le_bytes());
+    frame.extend_from_slice(request.asdu());
+    frame.into()
+}
+
+/// Decode a received MAC frame holding an inter-PAN message from an extended source address.
+///
+/// Returns `None` for other frames.
+pub fn decode(frame: &Bytes, link_quality: u8) -> Option<InterPanIndication> {
+    let mut reader = Reader { frame, offset: 0 };
+    let mac_control = reader.u16()?;
+
+    if mac_control & 0x0007 != MAC_DATA
+        || (mac_control >> MAC_SOURCE_MODE_SHIFT) & MODE_MASK != EXTENDED_MODE
+    {
+        return None;
+    }
+
+    reader.skip(1)?;
+    let destination_pan_id = reader.u16()?;
+
+    match (mac_control >> MAC_DESTINATION_MODE_SHIFT) & MODE_MASK {
+        SHORT_MODE => reader.skip(2)?,
+        EXTENDED_MODE => reader.skip(8)?,
+        _ => return None,
+    }
+
+    let source_pan_id = if mac_control & MAC_PAN_ID_COMPRESSION == 0 {
+        reader.u16()?
+    } else {
+        destination_pan_id
+    };
+    let source = reader.ieee_address()?;
+
+    if reader.u16()? & NWK_FRAME_TYPE_MASK != u16::from(INTER_PAN) {
+        return None;
+    }
+
+    let aps_control = reader.u8()?;
+
+    if aps_control & APS_FRAME_TYPE_MASK != INTER_PAN {
+        return None;
+    }
+
+    if aps_control & APS_DELIVERY_MODE_MASK == APS_GROUP {
+        reader.skip(2)?;
+    }
+
+    let cluster_id = reader.u16()?;
+    let profile_id = reader.u16()?;
+
+    Some(InterPanIndication::new(
+        source,
+        source_pan_id,
+        profile_id,
+        cluster_id,
+        link_quality,
+        frame.slice(reader.offset..),
+    ))
+}
+
+/// Reads little-endian fields from the front of a frame.
+struct Reader<'frame> {
+    frame: &'frame Bytes,
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let bytes = self
+            .frame
+            .get(self.offset..self.offset + N)?
+            .try_into()
+            .ok()?;
+        self.offset += N;
+        Some(bytes)
+    }
+
+    fn skip(&mut self, count: usize) -> Option<()> {
+        self.offset = self.offset.checked_add(count)?;
+        (self.offset <= self.frame.len()).then_some(())
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take::<1>().map(|[byte]| byte)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.take().map(u16::from_le_bytes)
+    }
+
+    fn ieee_address(&mut self) -> Option<IeeeAddress> {
+        self.take::<8>()
+            .and_then(|bytes| IeeeAddress::from_le_stream(bytes.into_iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use zb_hw::core::IeeeAddress;
+    use zb_hw::{Channel, InterPanDestination, InterPanRequest};
+
+    use super::{decode, encode};
+
+    const SOURCE: IeeeAddress = IeeeAddress::new(8, 7, 6, 5, 4, 3, 2, 1);
+    const TARGET: IeeeAddress = IeeeAddress::new(0, 0x17, 0x88, 0x01, 0, 0, 0, 1);
+
+    fn request(destination: InterPanDestination) -> InterPanRequest {
+        InterPanRequest::new(
+            Channel::new(11).expect("channel 11 is valid"),
+            destination,
+            0xC05E,
+            0x1000,
+            Bytes::from_static(&[0x11, 0x00, 0x00]),
+        )
+    }
+
+    #[test]
+    fn encodes_broadcasts_with_a_short_destination() {
+        assert_eq!(
+            encode(
+                &request(InterPanDestination::Broadcast),
+                0x2A,
+                0x1A62,
+                SOURCE
+            )
+            .as_ref(),
+            [
+                0x01, 0xC8, 0x2A, 0xFF, 0xFF, 0xFF, 0xFF, 0x62, 0x1A, 0x01, 0x02, 0x03, 0x04, 0x05,
+                0x06, 0x07, 0x08, 0x0B, 0x00, 0x0B, 0x00, 0x10, 0x5E, 0xC0, 0x11, 0x00, 0x00
+            ]
+        );
+    }
+
+    #[test]
+    fn encodes_acknowledged_unicasts_to_extended_addresses() {
+        let frame = encode(
+            &request(InterPanDestination::device(TARGET)),
+            0x2A,
+            0x1A62,
+            SOURCE,
+        );
+
+        assert_eq!(frame[..2], [0x21, 0xCC]);
+        assert_eq!(
+            frame[5..13],
+            [0x01, 0x00, 0x00, 0x00, 0x01, 0x88, 0x17, 0x00]
+        );
+        assert_eq!(frame[25], 0x03);
+    }
+
+    #[test]
+    fn decodes_unicasts_from_extended_sources() {
+        let frame = encode(
+            &request(InterPanDestination::device(SOURCE)),
+            0x2A,
+            0x0B0B,
+            TARGET,
+        );
+        let indication = decode(&frame, 200).expect("frame is an inter-PAN frame");
+
+        assert_eq!(indication.source(), TARGET);
+        assert_eq!(indication.source_pan_id(), 0x0B0B);
+        assert_eq!(indication.profile_id(), 0xC05E);
+        assert_eq!(indication.cluster_id(), 0x1000);
+        assert_eq!(indication.link_quality(), 200);
+        assert_eq!(indication.asdu().as_ref(), [0x11, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn ignores_network_frames() {
+        let mut frame = encode(&request(InterPanDestination::Broadcast), 0, 0, SOURCE).to_vec();
+        frame[17] = 0x08;
+
+        assert!(decode(&frame.into(), 200).is_none());
+    }
+}
@@ -0,0 +1,200 @@
+//! Security keys across EZSP versions.
+//!
+//! EZSP 13 replaced the key commands of earlier versions with those of the security manager, so
+//! each operation sends the commands of the negotiated protocol version.
+
+use zb_hw::core::IeeeAddress;
+use zb_hw::core::security::Key;
+use zb_hw::{LinkKeyState, NetworkKeyState};
+
+use crate::client::{Client, check, check_security};
+use crate::error::Error;
+use crate::frame::FrameId;
+use crate::parameters::{
+    AddOrUpdateKeyTableEntry, AddTransientLinkKey, ConfigurationValue, ExportedKey,
+    ExportedLinkKey, FindKeyTableEntry, GetKey, ImportLinkKey, ImportTransientKey, NetworkKeyInfo,
+    SecurityManagerContext, SetValue, config_id, key_type, security_manager_key,
+};
+
+/// First EZSP version with the security manager commands.
+const SECURITY_MANAGER_VERSION: u8 = 13;
+
+/// Security manager context flags without options.
+const NO_FLAGS: u8 = 0x00;
+
+/// Size of the 32-bit values written with `setValue`.
+const U32_VALUE_LENGTH: u8 = 4;
+
+/// Return the network key with its sequence number and outgoing NWK frame counter.
+pub async fn network_key(client: &mut Client) -> Result<NetworkKeyState, Error> {
+    if client.version() < SECURITY_MANAGER_VERSION {
+        let response: GetKey = client
+            .call(FrameId::GET_KEY, key_type::CURRENT_NETWORK_KEY)
+            .await?;
+        check(FrameId::GET_KEY, response.status)?;
+        let key = response.key_struct;
+        return Ok(NetworkKeyState::new(
+            key.key,
+            key.sequence_number,
+            key.outgoing_frame_counter,
+        ));
+    }
+
+    let exported: ExportedKey = client
+        .call(
+            FrameId::EXPORT_KEY,
+            SecurityManagerContext {
+                core_key_type: security_manager_key::NETWORK,
+                key_index: 0,
+                derived_type: 0,
+                eui64: IeeeAddress::default(),
+                multi_network_index: 0,
+                flags: NO_FLAGS,
+                psa_key_alg_permission: 0,
+            },
+        )
+        .await?;
+    check_security(FrameId::EXPORT_KEY, exported.status)?;
+    let info: NetworkKeyInfo = client.call(FrameId::GET_NETWORK_KEY_INFO, ()).await?;
+    check_security(FrameId::GET_NETWORK_KEY_INFO, info.status)?;
+    Ok(NetworkKeyState::new(
+        exported.key,
+        info.network_key_sequence_number,
+        info.network_key_frame_counter,
+    ))
+}
+
+/// Return the devices of the key table with their link keys and APS frame counters.
+///
+/// Empty entries are skipped.
+pub async fn link_keys(client: &mut Client) -> Result<Vec<(IeeeAddress, LinkKeyState)>, Error> {
+    let size: ConfigurationValue = client
+        .call(FrameId::GET_CONFIGURATION_VALUE, config_id::KEY_TABLE_SIZE)
+        .await?;
+    check(FrameId::GET_CONFIGURATION_VALUE, size.status)?;
+    let mut link_keys = Vec::new();
+
+    for index in 0..u8::try_from(size.value).unwrap_or(u8::MAX) {
+        if client.version() < SECURITY_MANAGER_VERSION {
+            let response: GetKey = client.call(FrameId::GET_KEY_TABLE_ENTRY, index).await?;
+
+            if check(FrameId::GET_KEY_TABLE_ENTRY, response.status).is_ok() {
+                let entry = response.key_struct;
+                link_keys.push((
+                    entry.partner_eui64,
+                    LinkKeyState::new(
+                        entry.key,
+                        entry.outgoing_frame_counter,
+                        entry.incoming_frame_counter,
+                    ),
+                ));
+            }
+        } else {
+            let entry: ExportedLinkKey = client
+                .call(FrameId::EXPORT_LINK_KEY_BY_INDEX, index)
+                .await?;
+
+            if check_security(FrameId::EXPORT_LINK_KEY_BY_INDEX, entry.status).is_ok() {
+                link_keys.push((
+                    entry.eui64,
+                    LinkKeyState::new(
+                        entry.key,
+                        entry.outgoing_frame_counter,
+                        entry.incoming_frame_counter,
+                    ),
+                ));
+            }
+        }
+    }
+
+    Ok(link_keys)
+}
+
+/// Store a device's link key in the key table, replacing its previous entry.
+pub async fn import_link_key(
+    client: &mut Client,
+    ieee_address: IeeeAddress,
+    key: Key,
+) -> Result<(), Error> {
+    if client.version() < SECURITY_MANAGER_VERSION {
+        return client
+            .call_with_status(
+                FrameId::ADD_OR_UPDATE_KEY_TABLE_ENTRY,
+                AddOrUpdateKeyTableEntry {
+                    address: ieee_address,
+                    link_key: true,
+                    key,
+                },
+            )
+            .await;
+    }
+
+    // The NCP reports 0xFF for devices without an entry, which also selects a free entry.
+    let index: u8 = client
+        .call(
+            FrameId::FIND_KEY_TABLE_ENTRY,
+            FindKeyTableEntry {
+                address: ieee_address,
+                link_key: true,
+            },
+        )
+        .await?;
+    let status = client
+        .call(
+            FrameId::IMPORT_LINK_KEY,
+            ImportLinkKey {
+                index,
+                address: ieee_address,
+                key,
+            },
+        )
+        .await?;
+    check_security(FrameId::IMPORT_LINK_KEY, status)
+}
+
+/// Store a transient link key that authenticates a device's next join.
+pub async fn import_transient_key(
+    client: &mut Client,
+    ieee_address: IeeeAddress,
+    key: Key,
+) -> Result<(), Error> {
+    if client.version() < SECURITY_MANAGER_VERSION {
+        return client
+            .call_with_status(
+                FrameId::ADD_TRANSIENT_LINK_KEY,
+                AddTransientLinkKey {
+                    partner: ieee_address,
+                    key,
+                },
+            )
+            .await;
+    }
+
+    let status = client
+        .call(
+            FrameId::IMPORT_TRANSIENT_KEY,
+            ImportTransientKey {
+                eui64: ieee_address,
+                key,
+                flags: NO_FLAGS,
+            },
+        )
+        .await?;
+    check_security(FrameId::IMPORT_TRANSIENT_KEY, status)
+}
+
+/// Write a frame counter value, such as [`value_id::NWK_FRAME_COUNTER`].
+///
+/// [`value_id::NWK_FRAME_COUNTER`]: crate::parameters::value_id::NWK_FRAME_COUNTER
+pub async fn set_frame_counter(client: &mut Client, value_id: u8, value: u32) -> Result<(), Error> {
+    client
+        .call_with_status(
+            FrameId::SET_VALUE,
+            SetValue {
+                value_id,
+                value_length: U32_VALUE_LENGTH,
+                value,
+            },
+        )
+        .await
+}
@@ -0,0 +1,52 @@
+//! EZSP backend for Silicon Labs Zigbee NCPs.
+//!
+//! This crate implements the `apis-saltans-hw` [`Driver`](zb_hw::Driver) for network
+//! co-processors running the Silicon Labs `EmberZNet` stack. The host talks to the NCP with the
+//! `EmberZNet` Serial Protocol (EZSP), framed by the Asynchronous Serial Host (ASH) protocol, over
+//! any [`AsyncRead`](tokio::io::AsyncRead) and [`AsyncWrite`](tokio::io::AsyncWrite) stream, such
+//! as a serial port.
+//!
+//! [`Ezsp::connect`] resets the NCP, negotiates EZSP versions 8 through 13, registers the
+//! application endpoints of the [`Config`], and resumes or forms the network. It returns the
+//! driver and the receiver of the hardware events translated from EZSP callbacks:
+//!
+//! ```no_run
+//! use std::num::NonZeroUsize;
+//!
+//! use apis_saltans_ezsp::{Config, Ezsp};
+//! use zb_hw::Driver;
+//! # async fn connect(
+//! #     stream: impl tokio::io::AsyncRead + tokio::io::AsyncWrite + Send + Unpin + 'static,
+//! # ) -> Result<(), apis_saltans_ezsp::Error> {
+//! let capacity = NonZeroUsize::new(32).expect("capacity is non-zero");
+//! let (driver, events) = Ezsp::connect(stream, Config::new(), capacity).await?;
+//! let (ncp, actor) = driver.into_actor(capacity);
+//! tokio::spawn(actor);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Transmissions carry the coordinator's APS counter as the EZSP message tag. Acknowledged
+//! transmissions are confirmed by an `ApsdeEvent::DataConfirm` with that counter once the NCP
+//! reports the message as sent.
+
+pub use zb_hw::Formation;
+
+pub use self::config::Config;
+pub use self::driver::Ezsp;
+pub use self::error::Error;
+pub use self::frame::FrameId;
+
+mod ash;
+mod callbacks;
+mod client;
+mod config;
+mod driver;
+mod error;
+mod frame;
+mod inter_pan;
+mod keys;
+mod parameters;
+#[cfg(test)]
+mod stub;
+mod transport;
@@ -0,0 +1,437 @@
+//! Parameters of EZSP commands, responses, and callbacks.
+
+use bytes::Bytes;
+use le_stream::{FromLeStream, Prefixed, ToLeStream};
+use zb_hw::core::IeeeAddress;
+use zb_hw::core::security::Key;
+
+/// Message contents prefixed with their length.
+pub type Message = Prefixed<u8, Bytes>;
+
+/// APS frame options.
+pub mod aps_option {
+    /// Encrypt the message with the APS link key.
+    pub const ENCRYPTION: u16 = 0x0020;
+
+    /// Request an APS acknowledgement and retry until it is received.
+    pub const RETRY: u16 = 0x0040;
+
+    /// Discover a route if none is known.
+    pub const ENABLE_ROUTE_DISCOVERY: u16 = 0x0100;
+
+    /// Send the message as one block of a host-fragmented transmission.
+    pub const FRAGMENT: u16 = 0x8000;
+}
+
+/// Ember status values.
+pub mod ember_status {
+    /// The operation succeeded.
+    pub const SUCCESS: u8 = 0x00;
+
+    /// The message was not acknowledged by its destination.
+    pub const DELIVERY_FAILED: u8 = 0x66;
+
+    /// The network is up.
+    pub const NETWORK_UP: u8 = 0x90;
+
+    /// The network is down.
+    pub const NETWORK_DOWN: u8 = 0x91;
+
+    /// The NCP is not joined to a network.
+    pub const NOT_JOINED: u8 = 0x93;
+
+    /// The network accepts joining devices.
+    pub const NETWORK_OPENED: u8 = 0x9C;
+
+    /// The network no longer accepts joining devices.
+    pub const NETWORK_CLOSED: u8 = 0x9D;
+
+    /// A source route failed.
+    pub const SOURCE_ROUTE_FAILURE: u8 = 0xA9;
+
+    /// A many-to-one route failed.
+    pub const MANY_TO_ONE_ROUTE_FAILURE: u8 = 0xAA;
+}
+
+/// Types of outgoing messages.
+pub mod outgoing {
+    /// A unicast sent directly to a node ID.
+    pub const DIRECT: u8 = 0x00;
+
+    /// A multicast.
+    pub const MULTICAST: u8 = 0x03;
+}
+
+/// Types of incoming messages.
+pub mod incoming {
+    /// A multicast sent by the NCP itself.
+    pub const MULTICAST_LOOPBACK: u8 = 0x03;
+
+    /// A broadcast.
+    pub const BROADCAST: u8 = 0x04;
+
+    /// A broadcast sent by the NCP itself.
+    pub const BROADCAST_LOOPBACK: u8 = 0x05;
+
+    /// A multicast.
+    pub const MULTICAST: u8 = 0x02;
+}
+
+/// Device updates reported to the trust center.
+pub mod device_update {
+    /// A device rejoined with network-layer security.
+    pub const SECURED_REJOIN: u8 = 0x00;
+
+    /// A device joined.
+    pub const UNSECURED_JOIN: u8 = 0x01;
+
+    /// A device left.
+    pub const DEVICE_LEFT: u8 = 0x02;
+
+    /// A device rejoined without network-layer security.
+    pub const UNSECURED_REJOIN: u8 = 0x03;
+}
+
+/// Policies of the NCP and their decisions.
+pub mod policy {
+    /// Decides how the trust center handles joining and rejoining devices.
+    pub const TRUST_CENTER: u8 = 0x00;
+
+    /// Decides how the trust center answers link key requests.
+    pub const TC_KEY_REQUEST: u8 = 0x05;
+
+    /// Trust center decision bit allowing devices to join.
+    pub const ALLOW_JOINS: u8 = 0x01;
+
+    /// Trust center decision bit allowing devices to rejoin without network-layer security.
+    pub const ALLOW_UNSECURED_REJOINS: u8 = 0x02;
+
+    /// Trust center decision bit requiring joining devices to use an install-code key.
+    pub const JOINS_USE_INSTALL_CODE_KEY: u8 = 0x10;
+
+    /// Deny link key requests.
+    pub const DENY_TC_KEY_REQUESTS: u8 = 0x50;
+
+    /// Answer link key requests with the current trust center link key.
+    pub const ALLOW_TC_KEY_REQUESTS: u8 = 0x51;
+}
+
+/// Key types of `getKey`.
+pub mod key_type {
+    /// The network key in use.
+    pub const CURRENT_NETWORK_KEY: u8 = 0x03;
+}
+
+/// Key types of the security manager context.
+pub mod security_manager_key {
+    /// The network key.
+    pub const NETWORK: u8 = 0x01;
+}
+
+/// Identifiers of configuration values.
+pub mod config_id {
+    /// Number of entries in the key table.
+    pub const KEY_TABLE_SIZE: u8 = 0x1E;
+}
+
+/// Identifiers of values.
+pub mod value_id {
+    /// The outgoing NWK frame counter.
+    pub const NWK_FRAME_COUNTER: u8 = 0x24;
+
+    /// The outgoing APS frame counter of the trust-center link key.
+    pub const APS_FRAME_COUNTER: u8 = 0x25;
+}
+
+/// Network states of `networkState`.
+pub mod network_status {
+    /// The NCP is not part of a network.
+    pub const NO_NETWORK: u8 = 0x00;
+}
+
+/// The APS header of a sent or received message.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream, ToLeStream)]
+pub struct ApsFrame {
+    pub profile_id: u16,
+    pub cluster_id: u16,
+    pub source_endpoint: u8,
+    pub destination_endpoint: u8,
+    pub options: u16,
+    pub group_id: u16,
+    pub sequence: u8,
+}
+
+/// Parameters of a network.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream, ToLeStream)]
+pub struct NetworkParameters {
+    pub extended_pan_id: IeeeAddress,
+    pub pan_id: u16,
+    pub radio_tx_power: i8,
+    pub radio_channel: u8,
+    pub join_method: u8,
+    pub nwk_manager_id: u16,
+    pub nwk_update_id: u8,
+    pub channels: u32,
+}
+
+/// Security configuration of a network to be formed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ToLeStream)]
+pub struct InitialSecurityState {
+    pub bitmask: u16,
+    pub preconfigured_key: Key,
+    pub network_key: Key,
+    pub network_key_sequence_number: u8,
+    pub preconfigured_trust_center_eui64: IeeeAddress,
+}
+
+/// Parameters of `addEndpoint`.
+#[derive(Clone, Debug, Eq, PartialEq, ToLeStream)]
+pub struct AddEndpoint {
+    pub endpoint: u8,
+    pub profile_id: u16,
+    pub device_id: u16,
+    pub app_flags: u8,
+    pub input_cluster_count: u8,
+    pub output_cluster_count: u8,
+    pub input_clusters: Vec<u16>,
+    pub output_clusters: Vec<u16>,
+}
+
+/// Parameters of `setPolicy`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ToLeStream)]
+pub struct SetPolicy {
+    pub policy_id: u8,
+    pub decision_id: u8,
+}
+
+/// Response to `version`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct NcpVersion {
+    pub protocol_version: u8,
+    pub stack_type: u8,
+    pub stack_version: u16,
+}
+
+/// Response to `getNetworkParameters`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct GetNetworkParameters {
+    pub status: u8,
+    pub node_type: u8,
+    pub parameters: NetworkParameters,
+}
+
+/// Response to `lookupEui64ByNodeId`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct LookupEui64 {
+    pub status: u8,
+    pub eui64: IeeeAddress,
+}
+
+/// Parameters of `sendUnicast`.
+#[derive(Clone, Debug, Eq, PartialEq, ToLeStream)]
+pub struct SendUnicast {
+    pub message_type: u8,
+    pub destination: u16,
+    pub aps_frame: ApsFrame,
+    pub message_tag: u8,
+    pub message: Message,
+}
+
+/// Parameters of `sendBroadcast`.
+#[derive(Clone, Debug, Eq, PartialEq, ToLeStream)]
+pub struct SendBroadcast {
+    pub destination: u16,
+    pub aps_frame: ApsFrame,
+    pub radius: u8,
+    pub message_tag: u8,
+    pub message: Message,
+}
+
+/// Parameters of `sendMulticast`.
+#[derive(Clone, Debug, Eq, PartialEq, ToLeStream)]
+pub struct SendMulticast {
+    pub aps_frame: ApsFrame,
+    pub hops: u8,
+    pub nonmember_radius: u8,
+    pub message_tag: u8,
+    pub message: Message,
+}
+
+/// Response to the send commands.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct Sent {
+    pub status: u8,
+    pub sequence: u8,
+}
+
+/// Parameters of `sendManyToOneRouteRequest`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ToLeStream)]
+pub struct ManyToOneRouteRequest {
+    pub concentrator_type: u16,
+    pub radius: u8,
+}
+
+/// Parameters of `trustCenterJoinHandler`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct TrustCenterJoin {
+    pub new_node_id: u16,
+    pub new_node_eui64: IeeeAddress,
+    pub status: u8,
+    pub policy_decision: u8,
+    pub parent_of_new_node_id: u16,
+}
+
+/// Parameters of `messageSentHandler`.
+#[derive(Clone, Debug, Eq, PartialEq, FromLeStream)]
+pub struct MessageSent {
+    pub message_type: u8,
+    pub index_or_destination: u16,
+    pub aps_frame: ApsFrame,
+    pub message_tag: u8,
+    pub status: u8,
+    pub message: Message,
+}
+
+/// Parameters of `incomingMessageHandler`.
+#[derive(Clone, Debug, Eq, PartialEq, FromLeStream)]
+pub struct IncomingMessage {
+    pub message_type: u8,
+    pub aps_frame: ApsFrame,
+    pub last_hop_lqi: u8,
+    pub last_hop_rssi: i8,
+    pub sender: u16,
+    pub binding_index: u8,
+    pub address_index: u8,
+    pub message: Message,
+}
+
+/// Parameters of `macFilterMatchMessageHandler`.
+#[derive(Clone, Debug, Eq, PartialEq, FromLeStream)]
+pub struct MacFilterMatchMessage {
+    pub filter_index_match: u8,
+    pub legacy_passthrough_type: u8,
+    pub last_hop_lqi: u8,
+    pub last_hop_rssi: i8,
+    pub message: Message,
+}
+
+/// Parameters of `incomingRouteErrorHandler`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct IncomingRouteError {
+    pub status: u8,
+    pub target: u16,
+}
+
+/// Response to `getConfigurationValue`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct ConfigurationValue {
+    pub status: u8,
+    pub value: u16,
+}
+
+/// Parameters of `setValue` writing a 32-bit value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ToLeStream)]
+pub struct SetValue {
+    pub value_id: u8,
+    pub value_length: u8,
+    pub value: u32,
+}
+
+/// An `EmberKeyStruct` of EZSP versions before 13.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct KeyStruct {
+    pub bitmask: u16,
+    pub key_type: u8,
+    pub key: Key,
+    pub outgoing_frame_counter: u32,
+    pub incoming_frame_counter: u32,
+    pub sequence_number: u8,
+    pub partner_eui64: IeeeAddress,
+}
+
+/// Response to `getKey` and `getKeyTableEntry`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct GetKey {
+    pub status: u8,
+    pub key_struct: KeyStruct,
+}
+
+/// Parameters of `findKeyTableEntry`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ToLeStream)]
+pub struct FindKeyTableEntry {
+    pub address: IeeeAddress,
+    pub link_key: bool,
+}
+
+/// Parameters of `addOrUpdateKeyTableEntry`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ToLeStream)]
+pub struct AddOrUpdateKeyTableEntry {
+    pub address: IeeeAddress,
+    pub link_key: bool,
+    pub key: Key,
+}
+
+/// Parameters of `addTransientLinkKey`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ToLeStream)]
+pub struct AddTransientLinkKey {
+    pub partner: IeeeAddress,
+    pub key: Key,
+}
+
+/// Parameters of `importLinkKey`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ToLeStream)]
+pub struct ImportLinkKey {
+    pub index: u8,
+    pub address: IeeeAddress,
+    pub key: Key,
+}
+
+/// Parameters of `importTransientKey`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ToLeStream)]
+pub struct ImportTransientKey {
+    pub eui64: IeeeAddress,
+    pub key: Key,
+    pub flags: u8,
+}
+
+/// Response to `exportLinkKeyByIndex`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct ExportedLinkKey {
+    pub eui64: IeeeAddress,
+    pub key: Key,
+    pub bitmask: u16,
+    pub outgoing_frame_counter: u32,
+    pub incoming_frame_counter: u32,
+    pub ttl_in_seconds: u16,
+    pub status: u32,
+}
+
+/// A `sl_zb_sec_man_context_t` selecting a key of the security manager.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ToLeStream)]
+pub struct SecurityManagerContext {
+    pub core_key_type: u8,
+    pub key_index: u8,
+    pub derived_type: u16,
+    pub eui64: IeeeAddress,
+    pub multi_network_index: u8,
+    pub flags: u8,
+    pub psa_key_alg_permission: u32,
+}
+
+/// Response to `exportKey`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct ExportedKey {
+    pub key: Key,
+    pub status: u32,
+}
+
+/// Response to `getNetworkKeyInfo`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct NetworkKeyInfo {
+    pub status: u32,
+    pub network_key_set: u8,
+    pub alternate_network_key_set: u8,
+    pub network_key_sequence_number: u8,
+    pub alternate_network_key_sequence_number: u8,
+    pub network_key_frame_counter: u32,
+}
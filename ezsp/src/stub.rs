@@ -0,0 +1,160 @@
+//! A scripted NCP speaking ASH over an in-memory stream.
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream, duplex};
+
+use crate::ash::{self, Decoder, Frame};
+
+/// Size of the in-memory stream's buffers.
+const BUFFER_SIZE: usize = 1024;
+
+/// Reset code reported after a software reset.
+const SOFTWARE_RESET: u8 = 0x0B;
+
+/// The NCP side of an in-memory serial stream.
+pub struct Stub {
+    stream: DuplexStream,
+    decoder: Decoder,
+    frame_number: u8,
+    ack_number: u8,
+}
+
+impl Stub {
+    /// Create a stub and the host side of its stream.
+    pub fn new() -> (Self, DuplexStream) {
+        let (host, ncp) = duplex(BUFFER_SIZE);
+
+        (
+            Self {
+                stream: ncp,
+                decoder: Decoder::default(),
+                frame_number: 0,
+                ack_number: 0,
+            },
+            host,
+        )
+    }
+
+    /// Read exactly `bytes` from the host.
+    pub async fn expect_bytes(&mut self, bytes: &[u8]) {
+        let mut received = vec![0; bytes.len()];
+        self.stream
+            .read_exact(&mut received)
+            .await
+            .expect("host must write the expected bytes");
+        assert_eq!(received, bytes);
+    }
+
+    /// Write raw bytes to the host.
+    pub async fn write_bytes(&mut self, bytes: &[u8]) {
+        self.stream
+            .write_all(bytes)
+            .await
+            .expect("host must read from the stream");
+    }
+
+    /// Answer the host's reset.
+    pub async fn reset(&mut self) {
+        self.expect_bytes(&[0x1A, 0xC0, 0x38, 0xBC, 0x7E]).await;
+        self.send(&Frame::RstAck {
+            version: 2,
+            reset_code: SOFTWARE_RESET,
+        })
+        .await;
+    }
+
+    /// Return the next frame written by the host.
+    pub async fn receive(&mut self) -> Frame {
+        loop {
+            let byte = self
+                .stream
+                .read_u8()
+                .await
+                .expect("host must write a frame");
+
+            if let Some(frame) = self.decoder.push(byte) {
+                return frame.expect("host must write valid frames");
+            }
+        }
+    }
+
+    /// Return the next EZSP command, skipping acknowledgements, without acknowledging it.
+    pub async fn receive_command(&mut self) -> (u8, bool, Vec<u8>) {
+        loop {
+            match self.receive().await {
+                Frame::Data {
+                    frame_number,
+                    retransmitted,
+                    payload,
+                    ..
+                } => return (frame_number, retransmitted, payload),
+                Frame::Ack { .. } => {}
+                frame => panic!("expected a data frame, received {frame:?}"),
+            }
+        }
+    }
+
+    /// Return the next EZSP command, which the next response acknowledges.
+    pub async fn expect_command(&mut self) -> Vec<u8> {
+        let (frame_number, _, payload) = self.receive_command().await;
+        assert_eq!(
+            frame_number, self.ack_number,
+            "host must send frames in order"
+        );
+        self.ack_number = ash::next(frame_number);
+        payload
+    }
+
+    /// Send an EZSP response or callback.
+    pub async fn respond(&mut self, payload: &[u8]) {
+        let frame = Frame::Data {
+            frame_number: self.frame_number,
+            retransmitted: false,
+            ack_number: self.ack_number,
+            payload: payload.to_vec(),
+        };
+        self.frame_number = ash::next(self.frame_number);
+        self.send(&frame).await;
+    }
+
+    /// Answer an extended-format command and return the command's parameters.
+    pub async fn answer(&mut self, frame_id: u16, parameters: &[u8]) -> Vec<u8> {
+        let command = self.expect_command().await;
+        assert_eq!(
+            u16::from_le_bytes([command[3], command[4]]),
+            frame_id,
+            "host must send {frame_id:#06X}"
+        );
+        let mut response = vec![command[0], 0x80, 0x01];
+        response.extend(frame_id.to_le_bytes());
+        response.extend(parameters);
+        self.respond(&response).await;
+        command[5..].to_vec()
+    }
+
+    /// Answer the version negotiation with EZSP version 13.
+    pub async fn negotiate_version(&mut self) {
+        self.negotiate(13).await;
+    }
+
+    /// Answer the version negotiation with an EZSP version up to 13.
+    pub async fn negotiate(&mut self, version: u8) {
+        let command = self.expect_command().await;
+        assert_eq!(command, [0x00, 0x00, 0x00, 13]);
+        self.respond(&[0x00, 0x80, 0x00, version, 0x02, 0x30, 0x74])
+            .await;
+        self.answer(0x0000, &[version, 0x02, 0x30, 0x74]).await;
+    }
+
+    /// Send an asynchronous callback.
+    pub async fn callback(&mut self, frame_id: u16, parameters: &[u8]) {
+        let mut callback = vec![0x00, 0x90, 0x01];
+        callback.extend(frame_id.to_le_bytes());
+        callback.extend(parameters);
+        self.respond(&callback).await;
+    }
+
+    /// Write a frame to the host.
+    pub async fn send(&mut self, frame: &Frame) {
+        self.write_bytes(&frame.encode()).await;
+    }
+}
@@ -0,0 +1,648 @@
+//! The ASH transport actor.
+//!
+//! The actor owns the write half of the serial stream and keeps a window of one outstanding data
+//! frame. A reader task decodes frames from the read half, and timers are tasks that report
+//! expired deadlines back to the actor. Responses complete the command that carries the same EZSP
+//! sequence number, while callbacks are forwarded to the callback channel.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bytes::Bytes;
+use log::{debug, error, warn};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, WriteHalf, split};
+use tokio::spawn;
+use tokio::sync::mpsc::{Receiver, Sender, WeakSender, channel};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout};
+
+use crate::ash::{self, CANCEL, DecodeError, Decoder, Frame};
+use crate::error::Error;
+use crate::frame::{self, FrameId};
+
+/// The supported ASH protocol version.
+const ASH_VERSION: u8 = 2;
+
+/// Time to wait for the NCP to acknowledge a reset.
+const RESET_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time to wait for the acknowledgement of a data frame before retransmitting it.
+const ACK_TIMEOUT: Duration = Duration::from_millis(1600);
+
+/// Number of retransmissions of an unacknowledged data frame.
+const MAX_RETRANSMISSIONS: u8 = 4;
+
+/// Time to wait for the response to a command.
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Capacity of the actor's input channel.
+const INPUT_CAPACITY: usize = 32;
+
+/// Size of the read buffer of the reader task.
+const READ_BUFFER_SIZE: usize = 64;
+
+/// Handle of the ASH transport actor.
+#[derive(Clone, Debug)]
+pub struct Transport {
+    inputs: Sender<Input>,
+}
+
+impl Transport {
+    /// Reset the NCP and start the transport actor on the stream.
+    ///
+    /// Callbacks received from the NCP are forwarded to `callbacks`. The callback channel closes
+    /// when the transport fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP does not acknowledge the reset.
+    pub async fn start<S>(mut stream: S, callbacks: Sender<Bytes>) -> Result<Self, Error>
+    where
+        S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    {
+        stream.write_all(&[CANCEL]).await?;
+        stream.write_all(&Frame::Rst.encode()).await?;
+        stream.flush().await?;
+
+        let mut decoder = Decoder::default();
+        let reset_code = timeout(RESET_TIMEOUT, await_reset(&mut stream, &mut decoder))
+            .await
+            .map_err(|_| Error::ResetTimeout)??;
+        debug!("NCP reset with reset code {reset_code:#04X}");
+
+        let (reader, writer) = split(stream);
+        let (inputs, receiver) = channel(INPUT_CAPACITY);
+        let reader = spawn(read(reader, decoder, inputs.downgrade()));
+        let actor = Actor {
+            writer,
+            inputs: inputs.downgrade(),
+            callbacks: Some(callbacks),
+            reader,
+            frame_number: 0,
+            ack_number: 0,
+            next_id: 0,
+            queue: VecDeque::new(),
+            pending: None,
+            failure: None,
+        };
+        spawn(actor.run(receiver));
+        Ok(Self { inputs })
+    }
+
+    /// Send an EZSP command and return the NCP's response.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the transport failed or the NCP did not respond in time.
+    pub async fn request(&self, frame_id: FrameId, frame: Vec<u8>) -> Result<Bytes, Error> {
+        let (response, receiver) = oneshot::channel();
+        self.inputs
+            .send(Input::Command(Command {
+                frame_id,
+                frame,
+                response,
+            }))
+            .await
+            .map_err(|_| Error::Closed)?;
+        receiver.await.map_err(|_| Error::Closed)?
+    }
+}
+
+/// Inputs of the transport actor.
+enum Input {
+    Command(Command),
+    Received(Result<Frame, DecodeError>),
+    ReadFailed(Error),
+    AckTimeout { id: u64, attempt: u8 },
+    ResponseTimeout { id: u64 },
+}
+
+/// A command waiting to be sent.
+struct Command {
+    frame_id: FrameId,
+    frame: Vec<u8>,
+    response: oneshot::Sender<Result<Bytes, Error>>,
+}
+
+/// The command whose data frame is outstanding or whose response is awaited.
+struct Pending {
+    id: u64,
+    command: Command,
+    frame_number: u8,
+    acknowledged: bool,
+    retransmissions: u8,
+}
+
+struct Actor<S> {
+    writer: WriteHalf<S>,
+    inputs: WeakSender<Input>,
+    callbacks: Option<Sender<Bytes>>,
+    reader: JoinHandle<()>,
+    frame_number: u8,
+    ack_number: u8,
+    next_id: u64,
+    queue: VecDeque<Command>,
+    pending: Option<Pending>,
+    failure: Option<Error>,
+}
+
+impl<S> Actor<S>
+where
+    S: AsyncWrite,
+{
+    async fn run(mut self, mut inputs: Receiver<Input>) {
+        while let Some(input) = inputs.recv().await {
+            match input {
+                Input::Command(command) => {
+                    if let Some(failure) = &self.failure {
+                        command
+                            .response
+                            .send(Err(failure.clone()))
+                            .unwrap_or_else(drop);
+                    } else {
+                        self.queue.push_back(command);
+                        self.send_next().await;
+                    }
+                }
+                Input::Received(Ok(frame)) => self.receive(frame).await,
+                Input::Received(Err(error)) => {
+                    warn!("Discarding received frame: {error}");
+                    self.write(&Frame::Nak {
+                        ack_number: self.ack_number,
+                        not_ready: false,
+                    })
+                    .await;
+                }
+                Input::ReadFailed(error) => self.fail(error),
+                Input::AckTimeout { id, attempt } => {
+                    if self.pending.as_ref().is_some_and(|pending| {
+                        pending.id == id
+                            && !pending.acknowledged
+                            && pending.retransmissions == attempt
+                    }) {
+                        if attempt < MAX_RETRANSMISSIONS {
+                            self.retransmit().await;
+                        } else {
+                            self.fail(Error::NoAcknowledgement);
+                        }
+                    }
+                }
+                Input::ResponseTimeout { id } => {
+                    if let Some(pending) = self.pending.take_if(|pending| pending.id == id) {
+                        let frame_id = pending.command.frame_id;
+                        warn!("NCP did not respond to {frame_id}");
+                        pending
+                            .command
+                            .response
+                            .send(Err(Error::Timeout(frame_id)))
+                            .unwrap_or_else(drop);
+                        self.send_next().await;
+                    }
+                }
+            }
+        }
+
+        self.reader.abort();
+    }
+
+    /// Send the next queued command unless a command is outstanding.
+    async fn send_next(&mut self) {
+        if self.pending.is_some() || self.failure.is_some() {
+            return;
+        }
+
+        let Some(command) = self.queue.pop_front() else {
+            return;
+        };
+
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        let frame_number = self.frame_number;
+        self.frame_number = ash::next(frame_number);
+        self.pending = Some(Pending {
+            id,
+            command,
+            frame_number,
+            acknowledged: false,
+            retransmissions: 0,
+        });
+        self.transmit(false).await;
+        self.schedule(RESPONSE_TIMEOUT, Input::ResponseTimeout { id });
+    }
+
+    /// Retransmit the outstanding data frame.
+    async fn retransmit(&mut self) {
+        if let Some(pending) = &mut self.pending {
+            pending.retransmissions += 1;
+            debug!("Retransmitting frame {}", pending.frame_number);
+            self.transmit(true).await;
+        }
+    }
+
+    /// Write the outstanding data frame and start its acknowledgement timer.
+    async fn transmit(&mut self, retransmitted: bool) {
+        let Some(pending) = &self.pending else {
+            return;
+        };
+
+        let frame = Frame::Data {
+            frame_number: pending.frame_number,
+            retransmitted,
+            ack_number: self.ack_number,
+            payload: pending.command.frame.clone(),
+        };
+        let timer = Input::AckTimeout {
+            id: pending.id,
+            attempt: pending.retransmissions,
+        };
+        self.write(&frame).await;
+        self.schedule(ACK_TIMEOUT, timer);
+    }
+
+    async fn receive(&mut self, frame: Frame) {
+        match frame {
+            Frame::Data {
+                frame_number,
+                retransmitted,
+                ack_number,
+                payload,
+            } => {
+                self.acknowledge(ack_number);
+
+                if frame_number == self.ack_number {
+                    self.ack_number = ash::next(frame_number);
+                    self.write(&Frame::Ack {
+                        ack_number: self.ack_number,
+                        not_ready: false,
+                    })
+                    .await;
+                    self.deliver(payload.into()).await;
+                } else if retransmitted {
+                    self.write(&Frame::Ack {
+                        ack_number: self.ack_number,
+                        not_ready: false,
+                    })
+                    .await;
+                } else {
+                    self.write(&Frame::Nak {
+                        ack_number: self.ack_number,
+                        not_ready: false,
+                    })
+                    .await;
+                }
+            }
+            Frame::Ack { ack_number, .. } => self.acknowledge(ack_number),
+            Frame::Nak { ack_number, .. } => {
+                self.acknowledge(ack_number);
+
+                if self
+                    .pending
+                    .as_ref()
+                    .is_some_and(|pending| !pending.acknowledged)
+                {
+                    self.retransmit().await;
+                }
+            }
+            Frame::RstAck { reset_code, .. } => self.fail(Error::Reset(reset_code)),
+            Frame::Error { error_code, .. } => self.fail(Error::NcpFailed(error_code)),
+            Frame::Rst => warn!("Ignoring reset request from NCP"),
+        }
+    }
+
+    /// Mark the outstanding data frame as acknowledged if `ack_number` follows it.
+    fn acknowledge(&mut self, ack_number: u8) {
+        if let Some(pending) = self
+            .pending
+            .as_mut()
+            .filter(|pending| ash::next(pending.frame_number) == ack_number)
+        {
+            pending.acknowledged = true;
+        }
+    }
+
+    /// Forward a received EZSP frame to its command or the callback channel.
+    async fn deliver(&mut self, frame: Bytes) {
+        if frame::is_callback(&frame) {
+            if let Some(callbacks) = &self.callbacks
+                && callbacks.send(frame).await.is_err()
+            {
+                self.callbacks = None;
+            }
+
+            return;
+        }
+
+        let sequence = frame::sequence(&frame);
+
+        if let Some(pending) = self
+            .pending
+            .take_if(|pending| frame::sequence(&pending.command.frame) == sequence)
+        {
+            pending
+                .command
+                .response
+                .send(Ok(frame))
+                .unwrap_or_else(drop);
+            self.send_next().await;
+        } else {
+            warn!("Discarding unexpected response {sequence:?}");
+        }
+    }
+
+    async fn write(&mut self, frame: &Frame) {
+        let result = match self.writer.write_all(&frame.encode()).await {
+            Ok(()) => self.writer.flush().await,
+            Err(error) => Err(error),
+        };
+
+        if let Err(error) = result {
+            self.fail(error.into());
+        }
+    }
+
+    /// Fail all commands and reject further ones.
+    fn fail(&mut self, error: Error) {
+        if self.failure.is_some() {
+            return;
+        }
+
+        error!("ASH transport failed: {error}");
+        self.reader.abort();
+        self.callbacks = None;
+
+        for command in self
+            .pending
+            .take()
+            .map(|pending| pending.command)
+            .into_iter()
+            .chain(self.queue.drain(..))
+        {
+            command
+                .response
+                .send(Err(error.clone()))
+                .unwrap_or_else(drop);
+        }
+
+        self.failure = Some(error);
+    }
+
+    /// Send `input` to the actor after `delay`.
+    fn schedule(&self, delay: Duration, input: Input) {
+        let inputs = self.inputs.clone();
+
+        spawn(async move {
+            sleep(delay).await;
+
+            if let Some(inputs) = inputs.upgrade() {
+                inputs.send(input).await.unwrap_or_else(drop);
+            }
+        });
+    }
+}
+
+/// Read frames until the NCP acknowledges the reset and return its reset code.
+async fn await_reset<S>(stream: &mut S, decoder: &mut Decoder) -> Result<u8, Error>
+where
+    S: AsyncRead + Unpin,
+{
+    loop {
+        let byte = stream.read_u8().await.map_err(|error| {
+            if error.kind() == std::io::ErrorKind::UnexpectedEof {
+                Error::Closed
+            } else {
+                error.into()
+            }
+        })?;
+
+        match decoder.push(byte) {
+            Some(Ok(Frame::RstAck {
+                version,
+                reset_code,
+            })) => {
+                return if version == ASH_VERSION {
+                    Ok(reset_code)
+                } else {
+                    Err(Error::UnsupportedAshVersion(version))
+                };
+            }
+            Some(frame) => debug!("Ignoring frame before reset acknowledgement: {frame:?}"),
+            None => {}
+        }
+    }
+}
+
+/// Decode frames from the read half and forward them to the actor.
+async fn read<R>(mut reader: R, mut decoder: Decoder, inputs: WeakSender<Input>)
+where
+    R: AsyncRead + Unpin,
+{
+    let mut buffer = [0; READ_BUFFER_SIZE];
+
+    loop {
+        let (frames, failure) = match reader.read(&mut buffer).await {
+            Ok(0) => (Vec::new(), Some(Error::Closed)),
+            Ok(size) => (
+                buffer[..size]
+                    .iter()
+                    .filter_map(|byte| decoder.push(*byte))
+                    .collect(),
+                None,
+            ),
+            Err(error) => (Vec::new(), Some(error.into())),
+        };
+
+        let Some(inputs) = inputs.upgrade() else {
+            return;
+        };
+
+        for frame in frames {
+            if inputs.send(Input::Received(frame)).await.is_err() {
+                return;
+            }
+        }
+
+        if let Some(failure) = failure {
+            inputs
+                .send(Input::ReadFailed(failure))
+                .await
+                .unwrap_or_else(drop);
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::runtime::Builder;
+    use tokio::spawn;
+    use tokio::sync::mpsc::channel;
+
+    use super::Transport;
+    use crate::ash::Frame;
+    use crate::error::Error;
+    use crate::frame::FrameId;
+    use crate::stub::Stub;
+
+    const CALLBACK_CAPACITY: usize = 4;
+
+    fn run<F>(test: F)
+    where
+        F: Future<Output = ()>,
+    {
+        Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .expect("runtime must be available")
+            .block_on(test);
+    }
+
+    #[test]
+    fn exchanges_recorded_frames() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (callbacks, _callbacks) = channel(CALLBACK_CAPACITY);
+            let start = spawn(Transport::start(host, callbacks));
+            ncp.reset().await;
+            let transport = start
+                .await
+                .expect("task must finish")
+                .expect("reset must be acknowledged");
+
+            let request = spawn(async move {
+                transport
+                    .request(FrameId::VERSION, vec![0x00, 0x00, 0x00, 0x0D])
+                    .await
+            });
+            ncp.expect_bytes(&[0x00, 0x42, 0x21, 0xA8, 0x59, 0x7C, 0x05, 0x7E])
+                .await;
+            ncp.write_bytes(&[
+                0x01, 0x42, 0xA1, 0xA8, 0x59, 0x28, 0x25, 0xC6, 0xAE, 0x91, 0x7E,
+            ])
+            .await;
+            ncp.expect_bytes(&[0x81, 0x60, 0x59, 0x7E]).await;
+
+            assert_eq!(
+                request
+                    .await
+                    .expect("task must finish")
+                    .expect("NCP must respond")
+                    .as_ref(),
+                [0x00, 0x80, 0x00, 0x0D, 0x02, 0x30, 0x74]
+            );
+        });
+    }
+
+    #[test]
+    fn retransmits_rejected_frames_and_forwards_callbacks() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (callbacks, mut received_callbacks) = channel(CALLBACK_CAPACITY);
+            let start = spawn(Transport::start(host, callbacks));
+            ncp.reset().await;
+            let transport = start
+                .await
+                .expect("task must finish")
+                .expect("reset must be acknowledged");
+
+            let request = spawn(async move {
+                transport
+                    .request(FrameId::GET_EUI64, vec![0x05, 0x00, 0x01, 0x26, 0x00])
+                    .await
+            });
+            let (frame_number, retransmitted, _) = ncp.receive_command().await;
+            assert_eq!((frame_number, retransmitted), (0, false));
+            ncp.send(&Frame::Nak {
+                ack_number: 0,
+                not_ready: false,
+            })
+            .await;
+            let (frame_number, retransmitted, _) = ncp.receive_command().await;
+            assert_eq!((frame_number, retransmitted), (0, true));
+
+            ncp.callback(0x0019, &[0x90]).await;
+            assert_eq!(
+                received_callbacks
+                    .recv()
+                    .await
+                    .expect("callback must be forwarded")
+                    .as_ref(),
+                [0x00, 0x90, 0x01, 0x19, 0x00, 0x90]
+            );
+
+            ncp.respond(&[0x05, 0x80, 0x01, 0x26, 0x00, 1, 2, 3, 4, 5, 6, 7, 8])
+                .await;
+            assert!(request.await.expect("task must finish").is_ok());
+        });
+    }
+
+    #[test]
+    fn fails_commands_when_the_ncp_resets() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (callbacks, mut received_callbacks) = channel(CALLBACK_CAPACITY);
+            let start = spawn(Transport::start(host, callbacks));
+            ncp.reset().await;
+            let transport = start
+                .await
+                .expect("task must finish")
+                .expect("reset must be acknowledged");
+            let request = {
+                let transport = transport.clone();
+                spawn(async move {
+                    transport
+                        .request(FrameId::GET_EUI64, vec![0x00, 0x00, 0x01, 0x26, 0x00])
+                        .await
+                })
+            };
+
+            ncp.expect_command().await;
+            ncp.send(&Frame::RstAck {
+                version: 2,
+                reset_code: 0x02,
+            })
+            .await;
+
+            assert!(matches!(
+                request.await.expect("task must finish"),
+                Err(Error::Reset(0x02))
+            ));
+            assert!(received_callbacks.recv().await.is_none());
+            assert!(matches!(
+                transport
+                    .request(FrameId::GET_EUI64, vec![0x01, 0x00, 0x01, 0x26, 0x00])
+                    .await,
+                Err(Error::Reset(0x02))
+            ));
+        });
+    }
+
+    #[test]
+    fn gives_up_on_unacknowledged_frames() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (callbacks, _callbacks) = channel(CALLBACK_CAPACITY);
+            let start = spawn(Transport::start(host, callbacks));
+            ncp.reset().await;
+            let transport = start
+                .await
+                .expect("task must finish")
+                .expect("reset must be acknowledged");
+            let request = spawn(async move {
+                transport
+                    .request(FrameId::GET_EUI64, vec![0x00, 0x00, 0x01, 0x26, 0x00])
+                    .await
+            });
+
+            for _ in 0..5 {
+                ncp.receive_command().await;
+            }
+
+            assert!(matches!(
+                request.await.expect("task must finish"),
+                Err(Error::NoAcknowledgement)
+            ));
+        });
+    }
+}
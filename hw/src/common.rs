@@ -5,9 +5,14 @@
 #[cfg(feature = "driver")]
 pub use self::driver::Driver;
 pub use self::error::{Error, Operation, TransmissionError};
-pub use self::event::{ApsdeEvent, DeviceEvent, Event, NetworkEvent, RouteError};
+pub use self::event::{
+    ApsdeEvent, DeviceEvent, Event, InterPanIndication, NetworkEvent, RouteError,
+};
 pub use self::message::{
-    Channel, ChannelMask, FoundNetwork, NetworkDescriptor, ScanDuration, ScannedChannel,
+    BackedUpDevice, Channel, ChannelMask, Counter, Counters, Formation, FoundNetwork,
+    InterPanDestination, InterPanRequest, JoinPolicy, LinkKeyState, NetworkBackup,
+    NetworkDescriptor, NetworkKeyState, NetworkParameters, ScanDuration, ScannedChannel,
+    TrustCenterPolicy,
 };
 pub use self::ncp_handle::{NcpHandle, WeakNcpHandle};
 
@@ -3,13 +3,18 @@ use std::time::Duration;
 
 use bytes::Bytes;
 use tokio::sync::mpsc::Receiver;
+use tokio::sync::oneshot;
 use zb_aps::apsde::DataRequest;
 use zb_core::IeeeAddress;
+use zb_core::security::Key;
 use zb_core::short_id::Device;
 use zb_zdp::SimpleDescriptor;
 
 use crate::common::message::Message;
-use crate::{ChannelMask, Error, FoundNetwork, NcpHandle, ScanDuration, ScannedChannel};
+use crate::{
+    ChannelMask, Counters, Error, Formation, FoundNetwork, InterPanRequest, NcpHandle,
+    NetworkBackup, NetworkParameters, Operation, ScanDuration, ScannedChannel, TrustCenterPolicy,
+};
 
 /// A common Zigbee NCP driver interface.
 pub trait Driver: Send + 'static {
@@ -129,11 +134,190 @@ pub trait Driver: Send + 'static {
         counter: u8,
     ) -> impl Future<Output = Result<(), Error>> + Send;
 
+    /// Add a transient trust-center link key for a device that is about to join.
+    ///
+    /// The key authenticates the device's next join, for example after deriving it from an
+    /// install code. Backends keep the key only until the device has joined or the backend's
+    /// transient-key timeout expires.
+    ///
+    /// The default implementation reports [`Operation::AddTransientLinkKey`] as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backend cannot store the key.
+    fn add_transient_link_key(
+        &mut self,
+        _ieee_address: IeeeAddress,
+        _key: Key,
+    ) -> impl Future<Output = Result<(), Error>> + Send {
+        async { Err(Error::Unsupported(Operation::AddTransientLinkKey)) }
+    }
+
+    /// Set the trust-center link key that the NCP shares with a device.
+    ///
+    /// Unlike a transient link key, the key replaces the device's entry in the NCP's key table
+    /// and secures subsequent APS traffic with the device.
+    ///
+    /// The default implementation reports [`Operation::SetLinkKey`] as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backend cannot store the key.
+    fn set_link_key(
+        &mut self,
+        _ieee_address: IeeeAddress,
+        _key: Key,
+    ) -> impl Future<Output = Result<(), Error>> + Send {
+        async { Err(Error::Unsupported(Operation::SetLinkKey)) }
+    }
+
+    /// Form a new network as its coordinator and trust center.
+    ///
+    /// Returning success means the NCP started forming the network. Backends report
+    /// [`crate::NetworkEvent::Up`] once the network is operational.
+    ///
+    /// The default implementation reports [`Operation::FormNetwork`] as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the NCP cannot form the network, for example because it is already
+    /// part of one.
+    fn form_network(
+        &mut self,
+        _formation: Formation,
+    ) -> impl Future<Output = Result<(), Error>> + Send {
+        async { Err(Error::Unsupported(Operation::FormNetwork)) }
+    }
+
+    /// Leave the current network and forget its parameters.
+    ///
+    /// Backends report [`crate::NetworkEvent::Down`] once the NCP has left the network.
+    ///
+    /// The default implementation reports [`Operation::LeaveNetwork`] as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the NCP cannot leave the network.
+    fn leave_network(&mut self) -> impl Future<Output = Result<(), Error>> + Send {
+        async { Err(Error::Unsupported(Operation::LeaveNetwork)) }
+    }
+
+    /// Return the parameters of the current network.
+    ///
+    /// The default implementation reports [`Operation::GetNetworkParameters`] as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the NCP is not part of a network or the operation fails.
+    fn get_network_parameters(
+        &mut self,
+    ) -> impl Future<Output = Result<NetworkParameters, Error>> + Send {
+        async { Err(Error::Unsupported(Operation::GetNetworkParameters)) }
+    }
+
+    /// Set the security policy that the trust center applies to joining devices.
+    ///
+    /// The default implementation reports [`Operation::SetTrustCenterPolicy`] as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the NCP cannot apply the policy.
+    fn set_trust_center_policy(
+        &mut self,
+        _policy: TrustCenterPolicy,
+    ) -> impl Future<Output = Result<(), Error>> + Send {
+        async { Err(Error::Unsupported(Operation::SetTrustCenterPolicy)) }
+    }
+
+    /// Export the state needed to move the current network to another NCP.
+    ///
+    /// The default implementation reports [`Operation::BackupNetwork`] as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the NCP is not part of a network or its state cannot be read.
+    fn backup_network(&mut self) -> impl Future<Output = Result<NetworkBackup, Error>> + Send {
+        async { Err(Error::Unsupported(Operation::BackupNetwork)) }
+    }
+
+    /// Import a network exported by [`Driver::backup_network`], possibly from another backend.
+    ///
+    /// The NCP must not be part of a network. Backends adopt the coordinator IEEE address of the
+    /// backup where the hardware allows it, continue the network key's frame counter, and report
+    /// [`crate::NetworkEvent::Up`] once the restored network is operating.
+    ///
+    /// The default implementation reports [`Operation::RestoreNetwork`] as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the NCP is part of a network or cannot import the state.
+    fn restore_network(
+        &mut self,
+        _backup: NetworkBackup,
+    ) -> impl Future<Output = Result<(), Error>> + Send {
+        async { Err(Error::Unsupported(Operation::RestoreNetwork)) }
+    }
+
+    /// Return a snapshot of the NCP's diagnostic counters.
+    ///
+    /// The default implementation reports [`Operation::GetCounters`] as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the counters cannot be read.
+    fn get_counters(&mut self) -> impl Future<Output = Result<Counters, Error>> + Send {
+        async { Err(Error::Unsupported(Operation::GetCounters)) }
+    }
+
+    /// Return the longest APS payload, in octets, that the NCP transmits in a single frame.
+    ///
+    /// Coordinators fragment longer acknowledged unicasts into blocks of at most this size,
+    /// less the APS extended header. The default implementation reports
+    /// [`Operation::GetMaximumPayloadLength`] as unsupported, which disables host-side
+    /// fragmentation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the payload length cannot be read.
+    fn get_maximum_payload_length(&mut self) -> impl Future<Output = Result<u8, Error>> + Send {
+        async { Err(Error::Unsupported(Operation::GetMaximumPayloadLength)) }
+    }
+
+    /// Transmit an inter-PAN frame on the request's channel.
+    ///
+    /// The backend switches the radio to the request's channel if necessary and keeps listening
+    /// there, reporting received inter-PAN frames through [`crate::Event::InterPan`], until
+    /// [`Driver::end_inter_pan`] is called. Traffic of the NCP's own network may be lost meanwhile.
+    ///
+    /// The default implementation reports [`Operation::TransmitInterPan`] as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backend cannot switch the channel or transmit the frame.
+    fn transmit_inter_pan(
+        &mut self,
+        _request: InterPanRequest,
+    ) -> impl Future<Output = Result<(), Error>> + Send {
+        async { Err(Error::Unsupported(Operation::TransmitInterPan)) }
+    }
+
+    /// Return the radio from an inter-PAN channel to the channel of the NCP's network.
+    ///
+    /// The default implementation reports [`Operation::EndInterPan`] as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the backend cannot restore the network channel.
+    fn end_inter_pan(&mut self) -> impl Future<Output = Result<(), Error>> + Send {
+        async { Err(Error::Unsupported(Operation::EndInterPan)) }
+    }
+
     /// Convert this driver into an actor handle and its driving future.
     ///
     /// The returned future must be spawned or otherwise continuously polled.
     /// It resolves with the driver after every strong [`NcpHandle`] has been
-    /// dropped.
+    /// dropped, or after the actor has been stopped or replaced through
+    /// [`NcpHandle::stop`] or [`NcpHandle::restart`].
     fn into_actor(
         self,
         channel_capacity: NonZeroUsize,
@@ -146,83 +330,97 @@ pub trait Driver: Send + 'static {
     }
 }
 
-async fn serve<T>(mut driver: T, mut receiver: Receiver<Message>) -> T
+pub(super) async fn serve<T>(mut driver: T, mut receiver: Receiver<Message>) -> T
 where
     T: Driver,
 {
     while let Some(message) = receiver.recv().await {
         match message {
-            Message::GetEndpoints { response } => {
-                response
-                    .send(driver.get_endpoints().await)
-                    .unwrap_or_else(drop);
-            }
-            Message::GetPanId { response } => {
-                response
-                    .send(driver.get_pan_id().await)
-                    .unwrap_or_else(drop);
-            }
+            Message::GetEndpoints { response } => respond(response, driver.get_endpoints().await),
+            Message::GetPanId { response } => respond(response, driver.get_pan_id().await),
             Message::GetIeeeAddress { response } => {
-                response
-                    .send(driver.get_ieee_address().await)
-                    .unwrap_or_else(drop);
+                respond(response, driver.get_ieee_address().await);
             }
             Message::ScanNetworks {
                 channel_mask,
                 duration,
                 response,
-            } => {
-                response
-                    .send(driver.scan_networks(channel_mask, duration).await)
-                    .unwrap_or_else(drop);
-            }
+            } => respond(response, driver.scan_networks(channel_mask, duration).await),
             Message::ScanChannels {
                 channel_mask,
                 duration,
                 response,
-            } => {
-                response
-                    .send(driver.scan_channels(channel_mask, duration).await)
-                    .unwrap_or_else(drop);
-            }
+            } => respond(response, driver.scan_channels(channel_mask, duration).await),
             Message::AllowJoins { duration, response } => {
-                response
-                    .send(driver.allow_joins(duration).await)
-                    .unwrap_or_else(drop);
+                respond(response, driver.allow_joins(duration).await);
             }
             Message::RouteRequest { radius, response } => {
-                response
-                    .send(driver.route_request(radius).await)
-                    .unwrap_or_else(drop);
+                respond(response, driver.route_request(radius).await);
             }
             Message::TranslateIeeeAddress { short_id, response } => {
-                response
-                    .send(driver.short_id_to_ieee_address(short_id).await)
-                    .unwrap_or_else(drop);
+                respond(response, driver.short_id_to_ieee_address(short_id).await);
             }
             Message::TranslateShortId {
                 ieee_address,
                 response,
-            } => {
-                response
-                    .send(driver.ieee_address_to_short_id(ieee_address).await)
-                    .unwrap_or_else(drop);
-            }
+            } => respond(
+                response,
+                driver.ieee_address_to_short_id(ieee_address).await,
+            ),
             Message::Transmit {
                 request,
                 counter,
                 response,
-            } => {
-                response
-                    .send(driver.transmit(request, counter).await)
-                    .unwrap_or_else(drop);
+            } => respond(response, driver.transmit(request, counter).await),
+            Message::AddTransientLinkKey {
+                ieee_address,
+                key,
+                response,
+            } => respond(
+                response,
+                driver.add_transient_link_key(ieee_address, key).await,
+            ),
+            Message::SetLinkKey {
+                ieee_address,
+                key,
+                response,
+            } => respond(response, driver.set_link_key(ieee_address, key).await),
+            Message::FormNetwork {
+                formation,
+                response,
+            } => respond(response, driver.form_network(formation).await),
+            Message::LeaveNetwork { response } => respond(response, driver.leave_network().await),
+            Message::GetNetworkParameters { response } => {
+                respond(response, driver.get_network_parameters().await);
+            }
+            Message::SetTrustCenterPolicy { policy, response } => {
+                respond(response, driver.set_trust_center_policy(policy).await);
+            }
+            Message::BackupNetwork { response } => {
+                respond(response, driver.backup_network().await);
+            }
+            Message::RestoreNetwork { backup, response } => {
+                respond(response, driver.restore_network(backup).await);
             }
+            Message::GetCounters { response } => respond(response, driver.get_counters().await),
+            Message::GetMaximumPayloadLength { response } => {
+                respond(response, driver.get_maximum_payload_length().await);
+            }
+            Message::TransmitInterPan { request, response } => {
+                respond(response, driver.transmit_inter_pan(request).await);
+            }
+            Message::EndInterPan { response } => respond(response, driver.end_inter_pan().await),
         }
     }
 
     driver
 }
 
+/// Send a result to the caller, who may have stopped waiting for it.
+fn respond<T>(response: oneshot::Sender<T>, result: T) {
+    response.send(result).unwrap_or_else(drop);
+}
+
 #[cfg(all(test, feature = "coordinator"))]
 mod tests {
     use std::num::NonZeroUsize;
@@ -231,16 +429,21 @@ mod tests {
     use bytes::Bytes;
     use tokio::runtime::Builder;
     use zb_aps::apsde::{DataRequest, IndividualEndpoint, RequestDestination};
+    use zb_core::security::Key;
     use zb_core::short_id::{Broadcast, Device};
     use zb_core::{Endpoint, IeeeAddress, Profile};
     use zb_zdp::SimpleDescriptor;
 
     use super::Driver;
-    use crate::{ChannelMask, Error, FoundNetwork, Operation, ScanDuration, ScannedChannel};
+    use crate::{
+        Channel, ChannelMask, Error, FoundNetwork, InterPanDestination, InterPanRequest, Operation,
+        ScanDuration, ScannedChannel, TrustCenterPolicy,
+    };
 
     const ACTOR_CAPACITY: NonZeroUsize = NonZeroUsize::MIN;
     const APS_COUNTER: u8 = 0;
     const CLUSTER_ID: u16 = 0x0006;
+    const TOUCHLINK_CLUSTER_ID: u16 = 0x1000;
     const DEVICE_SHORT_ID: u16 = 0x1234;
     const PAN_ID: u16 = 0xABCD;
     const PROFILE_ID: Profile = Profile::ZigbeeHomeAutomation;
@@ -387,4 +590,105 @@ mod tests {
                 assert!(driver.transmitted_counter.is_none());
             });
     }
+
+    #[test]
+    fn handle_restarts_the_actor_with_a_new_driver() {
+        Builder::new_current_thread()
+            .build()
+            .expect("runtime must be available")
+            .block_on(async {
+                let (handle, actor) = FakeDriver::default().into_actor(ACTOR_CAPACITY);
+                let task = tokio::spawn(actor);
+                let clone = handle.clone();
+
+                handle.stop();
+                task.await.expect("stopped actor task must finish");
+                assert!(clone.is_closed());
+                assert!(matches!(
+                    clone.get_pan_id().await,
+                    Err(Error::ActorUnavailable)
+                ));
+
+                let task = tokio::spawn(handle.restart(FakeDriver::default(), ACTOR_CAPACITY));
+                assert!(!clone.is_closed());
+                clone
+                    .transmit(request(), APS_COUNTER)
+                    .await
+                    .expect("restarted driver must accept transmission");
+
+                drop(handle);
+                drop(clone);
+                let driver = task.await.expect("restarted actor task must finish");
+                assert_eq!(driver.transmitted_counter, Some(APS_COUNTER));
+            });
+    }
+
+    #[test]
+    fn actor_reports_optional_operations_as_unsupported_by_default() {
+        Builder::new_current_thread()
+            .build()
+            .expect("runtime must be available")
+            .block_on(async {
+                let (handle, actor) = FakeDriver::default().into_actor(ACTOR_CAPACITY);
+                let task = tokio::spawn(actor);
+
+                assert!(matches!(
+                    handle
+                        .add_transient_link_key(IEEE_ADDRESS, Key::DEFAULT_TRUST_CENTER_LINK_KEY)
+                        .await,
+                    Err(Error::Unsupported(Operation::AddTransientLinkKey))
+                ));
+                assert!(matches!(
+                    handle
+                        .set_link_key(IEEE_ADDRESS, Key::DEFAULT_TRUST_CENTER_LINK_KEY)
+                        .await,
+                    Err(Error::Unsupported(Operation::SetLinkKey))
+                ));
+                assert!(matches!(
+                    handle.leave_network().await,
+                    Err(Error::Unsupported(Operation::LeaveNetwork))
+                ));
+                assert!(matches!(
+                    handle.get_network_parameters().await,
+                    Err(Error::Unsupported(Operation::GetNetworkParameters))
+                ));
+                assert!(matches!(
+                    handle
+                        .set_trust_center_policy(TrustCenterPolicy::default())
+                        .await,
+                    Err(Error::Unsupported(Operation::SetTrustCenterPolicy))
+                ));
+                assert!(matches!(
+                    handle.backup_network().await,
+                    Err(Error::Unsupported(Operation::BackupNetwork))
+                ));
+                assert!(matches!(
+                    handle.get_counters().await,
+                    Err(Error::Unsupported(Operation::GetCounters))
+                ));
+                assert!(matches!(
+                    handle.get_maximum_payload_length().await,
+                    Err(Error::Unsupported(Operation::GetMaximumPayloadLength))
+                ));
+                assert!(matches!(
+                    handle
+                        .transmit_inter_pan(InterPanRequest::new(
+                            Channel::MIN,
+                            InterPanDestination::Broadcast,
+                            Profile::TouchLink.as_u16(),
+                            TOUCHLINK_CLUSTER_ID,
+                            Bytes::new(),
+                        ))
+                        .await,
+                    Err(Error::Unsupported(Operation::TransmitInterPan))
+                ));
+                assert!(matches!(
+                    handle.end_inter_pan().await,
+                    Err(Error::Unsupported(Operation::EndInterPan))
+                ));
+
+                drop(handle);
+                task.await.expect("actor task must finish");
+            });
+    }
 }
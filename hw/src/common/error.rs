@@ -40,6 +40,42 @@ pub enum Operation {
 
     /// Transmitting an APS frame.
     Transmit,
+
+    /// Adding a transient trust-center link key.
+    AddTransientLinkKey,
+
+    /// Set a device's trust-center link key.
+    SetLinkKey,
+
+    /// Forming a network.
+    FormNetwork,
+
+    /// Leaving the current network.
+    LeaveNetwork,
+
+    /// Reading the current network parameters.
+    GetNetworkParameters,
+
+    /// Setting the trust-center security policy.
+    SetTrustCenterPolicy,
+
+    /// Exporting the network state.
+    BackupNetwork,
+
+    /// Importing the network state.
+    RestoreNetwork,
+
+    /// Reading the diagnostic counters.
+    GetCounters,
+
+    /// Reading the longest APS payload the NCP transmits unfragmented.
+    GetMaximumPayloadLength,
+
+    /// Transmitting an inter-PAN frame.
+    TransmitInterPan,
+
+    /// Returning from an inter-PAN channel to the network channel.
+    EndInterPan,
 }
 
 impl Display for Operation {
@@ -55,6 +91,18 @@ impl Display for Operation {
             Self::ShortIdToIeeeAddress => "short ID to IEEE address translation",
             Self::IeeeAddressToShortId => "IEEE address to short ID translation",
             Self::Transmit => "APS transmission",
+            Self::AddTransientLinkKey => "add transient link key",
+            Self::SetLinkKey => "set link key",
+            Self::FormNetwork => "form network",
+            Self::LeaveNetwork => "leave network",
+            Self::GetNetworkParameters => "get network parameters",
+            Self::SetTrustCenterPolicy => "set trust center policy",
+            Self::BackupNetwork => "back up network",
+            Self::RestoreNetwork => "restore network",
+            Self::GetCounters => "get counters",
+            Self::GetMaximumPayloadLength => "get maximum payload length",
+            Self::TransmitInterPan => "inter-PAN transmission",
+            Self::EndInterPan => "end inter-PAN",
         })
     }
 }
@@ -1,10 +1,12 @@
 pub use self::apsde::ApsdeEvent;
 pub use self::device::DeviceEvent;
+pub use self::inter_pan::InterPanIndication;
 pub use self::network::NetworkEvent;
 pub use self::route_error::RouteError;
 
 mod apsde;
 mod device;
+mod inter_pan;
 mod network;
 mod route_error;
 
@@ -23,6 +25,9 @@ pub enum Event<T = (), K = ()> {
 
     /// APS data-service indication or confirmation.
     Apsde(ApsdeEvent<T, K>),
+
+    /// Inter-PAN frame received while listening on an inter-PAN channel.
+    InterPan(InterPanIndication),
 }
 
 impl<T, K> From<NetworkEvent> for Event<T, K> {
@@ -42,3 +47,9 @@ impl<T, K> From<ApsdeEvent<T, K>> for Event<T, K> {
         Self::Apsde(event)
     }
 }
+
+impl<T, K> From<InterPanIndication> for Event<T, K> {
+    fn from(indication: InterPanIndication) -> Self {
+        Self::InterPan(indication)
+    }
+}
@@ -0,0 +1,80 @@
+use bytes::Bytes;
+use zb_core::IeeeAddress;
+
+/// Stub-NWK frame received from a device outside of the NCP's network.
+///
+/// Backends report inter-PAN frames only while the NCP listens on an inter-PAN channel, that is
+/// between `NcpHandle::transmit_inter_pan` and `NcpHandle::end_inter_pan`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct InterPanIndication {
+    source: IeeeAddress,
+    source_pan_id: u16,
+    profile_id: u16,
+    cluster_id: u16,
+    link_quality: u8,
+    asdu: Bytes,
+}
+
+impl InterPanIndication {
+    /// Create an indication of a frame received from `source`.
+    #[must_use]
+    pub const fn new(
+        source: IeeeAddress,
+        source_pan_id: u16,
+        profile_id: u16,
+        cluster_id: u16,
+        link_quality: u8,
+        asdu: Bytes,
+    ) -> Self {
+        Self {
+            source,
+            source_pan_id,
+            profile_id,
+            cluster_id,
+            link_quality,
+            asdu,
+        }
+    }
+
+    /// Return the IEEE address of the sender.
+    #[must_use]
+    pub const fn source(&self) -> IeeeAddress {
+        self.source
+    }
+
+    /// Return the PAN ID of the sender.
+    #[must_use]
+    pub const fn source_pan_id(&self) -> u16 {
+        self.source_pan_id
+    }
+
+    /// Return the application profile ID.
+    #[must_use]
+    pub const fn profile_id(&self) -> u16 {
+        self.profile_id
+    }
+
+    /// Return the cluster ID.
+    #[must_use]
+    pub const fn cluster_id(&self) -> u16 {
+        self.cluster_id
+    }
+
+    /// Return the link-quality indication of the received frame.
+    #[must_use]
+    pub const fn link_quality(&self) -> u8 {
+        self.link_quality
+    }
+
+    /// Return the application service data unit.
+    #[must_use]
+    pub const fn asdu(&self) -> &Bytes {
+        &self.asdu
+    }
+
+    /// Return the application service data unit, consuming the indication.
+    #[must_use]
+    pub fn into_asdu(self) -> Bytes {
+        self.asdu
+    }
+}
@@ -4,21 +4,34 @@ use bytes::Bytes;
 use tokio::sync::oneshot::Sender;
 use zb_aps::apsde::DataRequest;
 use zb_core::IeeeAddress;
+use zb_core::security::Key;
 use zb_core::short_id::Device;
 use zb_zdp::SimpleDescriptor;
 
 pub use self::channel::Channel;
 pub use self::channel_mask::ChannelMask;
+pub use self::counters::{Counter, Counters};
+pub use self::formation::Formation;
 pub use self::found_network::{FoundNetwork, NetworkDescriptor};
+pub use self::inter_pan::{InterPanDestination, InterPanRequest};
+pub use self::network_backup::{BackedUpDevice, LinkKeyState, NetworkBackup, NetworkKeyState};
+pub use self::network_parameters::NetworkParameters;
 pub use self::scan_duration::ScanDuration;
 pub use self::scanned_channel::ScannedChannel;
+pub use self::trust_center_policy::{JoinPolicy, TrustCenterPolicy};
 use crate::common::Error;
 
 mod channel;
 mod channel_mask;
+mod counters;
+mod formation;
 mod found_network;
+mod inter_pan;
+mod network_backup;
+mod network_parameters;
 mod scan_duration;
 mod scanned_channel;
+mod trust_center_policy;
 
 /// Messages exchanged with the NCP driver actor.
 #[cfg_attr(
@@ -105,4 +118,92 @@ pub enum Message {
         /// One-shot channel used to report whether the backend accepted the request.
         response: Sender<Result<(), Error>>,
     },
+
+    /// Add a transient trust-center link key for a joining device.
+    AddTransientLinkKey {
+        /// IEEE address of the device that may join with the key.
+        ieee_address: IeeeAddress,
+        /// Link key, typically derived from the device's install code.
+        key: Key,
+        /// One-shot channel used to return success or driver error.
+        response: Sender<Result<(), Error>>,
+    },
+
+    /// Set the trust-center link key of a device.
+    SetLinkKey {
+        /// IEEE address of the device that shares the key.
+        ieee_address: IeeeAddress,
+        /// Link key, for example one negotiated with the device.
+        key: Key,
+        /// One-shot channel used to return success or driver error.
+        response: Sender<Result<(), Error>>,
+    },
+
+    /// Form a new network.
+    FormNetwork {
+        /// Parameters of the network to form.
+        formation: Formation,
+        /// One-shot channel used to return success or driver error.
+        response: Sender<Result<(), Error>>,
+    },
+
+    /// Leave the current network.
+    LeaveNetwork {
+        /// One-shot channel used to return success or driver error.
+        response: Sender<Result<(), Error>>,
+    },
+
+    /// Return the parameters of the current network.
+    GetNetworkParameters {
+        /// One-shot channel used to return the network parameters or driver error.
+        response: Sender<Result<NetworkParameters, Error>>,
+    },
+
+    /// Set the security policy of the trust center.
+    SetTrustCenterPolicy {
+        /// Policy to apply.
+        policy: TrustCenterPolicy,
+        /// One-shot channel used to return success or driver error.
+        response: Sender<Result<(), Error>>,
+    },
+
+    /// Export the network state.
+    BackupNetwork {
+        /// One-shot channel used to return the network backup or driver error.
+        response: Sender<Result<NetworkBackup, Error>>,
+    },
+
+    /// Import the network state.
+    RestoreNetwork {
+        /// Network state to import.
+        backup: NetworkBackup,
+        /// One-shot channel used to return success or driver error.
+        response: Sender<Result<(), Error>>,
+    },
+
+    /// Return the diagnostic counters of the NCP.
+    GetCounters {
+        /// One-shot channel used to return the counter snapshot or driver error.
+        response: Sender<Result<Counters, Error>>,
+    },
+
+    /// Return the longest APS payload the NCP transmits unfragmented.
+    GetMaximumPayloadLength {
+        /// One-shot channel used to return the payload length or driver error.
+        response: Sender<Result<u8, Error>>,
+    },
+
+    /// Transmit an inter-PAN frame and listen on its channel.
+    TransmitInterPan {
+        /// Inter-PAN frame to transmit.
+        request: InterPanRequest,
+        /// One-shot channel used to return success or driver error.
+        response: Sender<Result<(), Error>>,
+    },
+
+    /// Return from the inter-PAN channel to the network channel.
+    EndInterPan {
+        /// One-shot channel used to return success or driver error.
+        response: Sender<Result<(), Error>>,
+    },
 }
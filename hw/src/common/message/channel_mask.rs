@@ -41,6 +41,13 @@ impl ChannelMask {
     pub const fn contains(self, channel: Channel) -> bool {
         self.0 & (SINGLE_CHANNEL_BIT << channel.as_u8()) != 0
     }
+
+    /// Return the channels of the mask in ascending order.
+    pub fn channels(self) -> impl Iterator<Item = Channel> {
+        (Channel::MIN.as_u8()..=Channel::MAX.as_u8())
+            .filter_map(Channel::new)
+            .filter(move |channel| self.contains(*channel))
+    }
 }
 
 impl FromIterator<Channel> for ChannelMask {
@@ -93,5 +100,9 @@ mod tests {
 
         assert!(mask.contains(Channel::MIN));
         assert!(mask.contains(Channel::MAX));
+        assert_eq!(
+            mask.channels().collect::<Vec<_>>(),
+            [Channel::MIN, Channel::MAX]
+        );
     }
 }
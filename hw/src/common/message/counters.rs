@@ -0,0 +1,70 @@
+//! Diagnostic counters of the NCP.
+
+use std::collections::BTreeMap;
+
+pub use self::counter::Counter;
+
+mod counter;
+
+/// A snapshot of the NCP's diagnostic counters.
+///
+/// Backends include only the counters their firmware maintains. Counter values are cumulative
+/// since the NCP started or last cleared them, and may wrap around.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Counters {
+    values: BTreeMap<Counter, u32>,
+}
+
+impl Counters {
+    /// Create an empty snapshot.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            values: BTreeMap::new(),
+        }
+    }
+
+    /// Set the value of a counter.
+    #[must_use]
+    pub fn with_counter(mut self, counter: Counter, value: u32) -> Self {
+        self.values.insert(counter, value);
+        self
+    }
+
+    /// Return the value of a counter, if the backend reported it.
+    #[must_use]
+    pub fn get(&self, counter: Counter) -> Option<u32> {
+        self.values.get(&counter).copied()
+    }
+
+    /// Iterate over the reported counters and their values in counter order.
+    pub fn iter(&self) -> impl Iterator<Item = (Counter, u32)> + '_ {
+        self.values
+            .iter()
+            .map(|(&counter, &value)| (counter, value))
+    }
+
+    /// Return the number of reported counters.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Return whether no counter was reported.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl FromIterator<(Counter, u32)> for Counters {
+    fn from_iter<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (Counter, u32)>,
+    {
+        Self {
+            values: iter.into_iter().collect(),
+        }
+    }
+}
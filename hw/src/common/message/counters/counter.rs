@@ -0,0 +1,125 @@
+use std::fmt::{self, Display, Formatter};
+
+/// A diagnostic counter maintained by the NCP.
+///
+/// The counters follow the attributes of the Diagnostics cluster, so that the NCP's own statistics
+/// can be compared with those read from remote devices.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum Counter {
+    /// Received MAC broadcasts.
+    MacRxBroadcast,
+
+    /// Transmitted MAC broadcasts.
+    MacTxBroadcast,
+
+    /// Received MAC unicasts.
+    MacRxUnicast,
+
+    /// MAC unicasts acknowledged by the next hop.
+    MacTxUnicastSuccess,
+
+    /// Retransmissions of MAC unicasts.
+    MacTxUnicastRetry,
+
+    /// MAC unicasts not acknowledged by the next hop after all retries.
+    MacTxUnicastFailure,
+
+    /// Received APS broadcasts.
+    ApsRxBroadcast,
+
+    /// Transmitted APS broadcasts.
+    ApsTxBroadcast,
+
+    /// Received APS unicasts.
+    ApsRxUnicast,
+
+    /// APS unicasts delivered to their destination.
+    ApsTxUnicastSuccess,
+
+    /// Retransmissions of APS unicasts.
+    ApsTxUnicastRetry,
+
+    /// APS unicasts that were not delivered.
+    ApsTxUnicastFailure,
+
+    /// Route discoveries initiated by the NCP.
+    RouteDiscoveryInitiated,
+
+    /// Neighbors added to the neighbor table.
+    NeighborAdded,
+
+    /// Neighbors removed from the neighbor table.
+    NeighborRemoved,
+
+    /// Neighbors that went stale.
+    NeighborStale,
+
+    /// Devices that joined or rejoined through the NCP.
+    JoinIndication,
+
+    /// Children removed from the NCP.
+    ChildRemoved,
+
+    /// Frames dropped because of an outdated NWK frame counter.
+    NwkFrameCounterFailure,
+
+    /// Frames dropped because of an outdated APS frame counter.
+    ApsFrameCounterFailure,
+
+    /// Frames dropped because they were secured with an unauthorized APS key.
+    ApsUnauthorizedKey,
+
+    /// Frames that failed NWK decryption.
+    NwkDecryptionFailure,
+
+    /// Frames that failed APS decryption.
+    ApsDecryptionFailure,
+
+    /// Failed packet buffer allocations.
+    BufferAllocationFailure,
+
+    /// Unicasts relayed to another device.
+    RelayedUnicast,
+}
+
+impl Counter {
+    /// Return the counter's name as used for metrics, for example `mac_tx_unicast_retry`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::MacRxBroadcast => "mac_rx_broadcast",
+            Self::MacTxBroadcast => "mac_tx_broadcast",
+            Self::MacRxUnicast => "mac_rx_unicast",
+            Self::MacTxUnicastSuccess => "mac_tx_unicast_success",
+            Self::MacTxUnicastRetry => "mac_tx_unicast_retry",
+            Self::MacTxUnicastFailure => "mac_tx_unicast_failure",
+            Self::ApsRxBroadcast => "aps_rx_broadcast",
+            Self::ApsTxBroadcast => "aps_tx_broadcast",
+            Self::ApsRxUnicast => "aps_rx_unicast",
+            Self::ApsTxUnicastSuccess => "aps_tx_unicast_success",
+            Self::ApsTxUnicastRetry => "aps_tx_unicast_retry",
+            Self::ApsTxUnicastFailure => "aps_tx_unicast_failure",
+            Self::RouteDiscoveryInitiated => "route_discovery_initiated",
+            Self::NeighborAdded => "neighbor_added",
+            Self::NeighborRemoved => "neighbor_removed",
+            Self::NeighborStale => "neighbor_stale",
+            Self::JoinIndication => "join_indication",
+            Self::ChildRemoved => "child_removed",
+            Self::NwkFrameCounterFailure => "nwk_frame_counter_failure",
+            Self::ApsFrameCounterFailure => "aps_frame_counter_failure",
+            Self::ApsUnauthorizedKey => "aps_unauthorized_key",
+            Self::NwkDecryptionFailure => "nwk_decryption_failure",
+            Self::ApsDecryptionFailure => "aps_decryption_failure",
+            Self::BufferAllocationFailure => "buffer_allocation_failure",
+            Self::RelayedUnicast => "relayed_unicast",
+        }
+    }
+}
+
+impl Display for Counter {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.name())
+    }
+}
@@ -0,0 +1,59 @@
+use zb_core::IeeeAddress;
+use zb_core::security::Key;
+
+use super::ChannelMask;
+
+/// Parameters of a network formed by the NCP.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Formation {
+    pan_id: u16,
+    extended_pan_id: IeeeAddress,
+    channel_mask: ChannelMask,
+    network_key: Key,
+}
+
+impl Formation {
+    /// Create formation parameters.
+    ///
+    /// The NCP forms the network on one of the channels in `channel_mask`. Backends that cannot
+    /// select a channel by energy scan use the lowest channel of the mask.
+    #[must_use]
+    pub const fn new(
+        pan_id: u16,
+        extended_pan_id: IeeeAddress,
+        channel_mask: ChannelMask,
+        network_key: Key,
+    ) -> Self {
+        Self {
+            pan_id,
+            extended_pan_id,
+            channel_mask,
+            network_key,
+        }
+    }
+
+    /// Return the PAN ID.
+    #[must_use]
+    pub const fn pan_id(&self) -> u16 {
+        self.pan_id
+    }
+
+    /// Return the extended PAN ID.
+    #[must_use]
+    pub const fn extended_pan_id(&self) -> IeeeAddress {
+        self.extended_pan_id
+    }
+
+    /// Return the channels the network may be formed on.
+    #[must_use]
+    pub const fn channel_mask(&self) -> ChannelMask {
+        self.channel_mask
+    }
+
+    /// Return the network key.
+    #[must_use]
+    pub const fn network_key(&self) -> &Key {
+        &self.network_key
+    }
+}
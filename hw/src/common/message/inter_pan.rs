@@ -0,0 +1,105 @@
+use bytes::Bytes;
+use zb_core::IeeeAddress;
+
+use super::Channel;
+
+/// PAN ID addressing every PAN in radio range.
+const BROADCAST_PAN_ID: u16 = 0xFFFF;
+
+/// Receiver of an inter-PAN frame.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum InterPanDestination {
+    /// Every device in radio range, regardless of its PAN.
+    Broadcast,
+
+    /// One device, addressed by its IEEE address.
+    Device {
+        /// IEEE address of the device.
+        ieee_address: IeeeAddress,
+        /// PAN ID of the device, or `0xFFFF` if the device may operate on any PAN.
+        pan_id: u16,
+    },
+}
+
+impl InterPanDestination {
+    /// Address one device on any PAN.
+    #[must_use]
+    pub const fn device(ieee_address: IeeeAddress) -> Self {
+        Self::Device {
+            ieee_address,
+            pan_id: BROADCAST_PAN_ID,
+        }
+    }
+
+    /// Return the destination PAN ID.
+    #[must_use]
+    pub const fn pan_id(self) -> u16 {
+        match self {
+            Self::Broadcast => BROADCAST_PAN_ID,
+            Self::Device { pan_id, .. } => pan_id,
+        }
+    }
+}
+
+/// Stub-NWK frame sent to devices outside of the NCP's network, such as Touchlink targets.
+///
+/// Inter-PAN frames bypass the NWK and APS layers: they are neither routed, acknowledged, nor
+/// secured, and only reach devices in direct radio range on the request's channel.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct InterPanRequest {
+    channel: Channel,
+    destination: InterPanDestination,
+    profile_id: u16,
+    cluster_id: u16,
+    asdu: Bytes,
+}
+
+impl InterPanRequest {
+    /// Create a request that sends `asdu` on `channel`.
+    #[must_use]
+    pub const fn new(
+        channel: Channel,
+        destination: InterPanDestination,
+        profile_id: u16,
+        cluster_id: u16,
+        asdu: Bytes,
+    ) -> Self {
+        Self {
+            channel,
+            destination,
+            profile_id,
+            cluster_id,
+            asdu,
+        }
+    }
+
+    /// Return the radio channel to transmit on.
+    #[must_use]
+    pub const fn channel(&self) -> Channel {
+        self.channel
+    }
+
+    /// Return the receiver of the frame.
+    #[must_use]
+    pub const fn destination(&self) -> InterPanDestination {
+        self.destination
+    }
+
+    /// Return the application profile ID.
+    #[must_use]
+    pub const fn profile_id(&self) -> u16 {
+        self.profile_id
+    }
+
+    /// Return the cluster ID.
+    #[must_use]
+    pub const fn cluster_id(&self) -> u16 {
+        self.cluster_id
+    }
+
+    /// Return the application service data unit.
+    #[must_use]
+    pub const fn asdu(&self) -> &Bytes {
+        &self.asdu
+    }
+}
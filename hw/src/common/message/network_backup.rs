@@ -0,0 +1,114 @@
+//! Network state exported from and imported into an NCP.
+
+use std::iter::once;
+
+use zb_core::IeeeAddress;
+
+pub use self::backed_up_device::BackedUpDevice;
+pub use self::link_key_state::LinkKeyState;
+pub use self::network_key_state::NetworkKeyState;
+use super::{Channel, ChannelMask, NetworkParameters};
+
+mod backed_up_device;
+mod link_key_state;
+mod network_key_state;
+
+/// The state needed to move a network to another NCP.
+///
+/// A backup holds the network parameters, the network key with its sequence number and outgoing
+/// frame counter, and the device table with each device's trust-center link key. Restoring it on
+/// another NCP lets the devices stay on the network without re-pairing.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct NetworkBackup {
+    ieee_address: IeeeAddress,
+    parameters: NetworkParameters,
+    channel_mask: ChannelMask,
+    nwk_update_id: u8,
+    network_key: NetworkKeyState,
+    devices: Vec<BackedUpDevice>,
+}
+
+impl NetworkBackup {
+    /// Create a backup without devices.
+    ///
+    /// The channel mask contains only the network's channel, and the NWK update ID is zero.
+    #[must_use]
+    pub fn new(
+        ieee_address: IeeeAddress,
+        parameters: NetworkParameters,
+        network_key: NetworkKeyState,
+    ) -> Self {
+        Self {
+            ieee_address,
+            parameters,
+            channel_mask: once(parameters.channel()).collect(),
+            nwk_update_id: 0,
+            network_key,
+            devices: Vec::new(),
+        }
+    }
+
+    /// Set the channels the network may move to.
+    #[must_use]
+    pub const fn with_channel_mask(mut self, channel_mask: ChannelMask) -> Self {
+        self.channel_mask = channel_mask;
+        self
+    }
+
+    /// Set the NWK update ID, which counts channel changes.
+    #[must_use]
+    pub const fn with_nwk_update_id(mut self, nwk_update_id: u8) -> Self {
+        self.nwk_update_id = nwk_update_id;
+        self
+    }
+
+    /// Add a device of the network.
+    #[must_use]
+    pub fn with_device(mut self, device: BackedUpDevice) -> Self {
+        self.devices.push(device);
+        self
+    }
+
+    /// Return the IEEE address of the coordinator.
+    #[must_use]
+    pub const fn ieee_address(&self) -> IeeeAddress {
+        self.ieee_address
+    }
+
+    /// Return the PAN ID, extended PAN ID, and channel.
+    #[must_use]
+    pub const fn parameters(&self) -> NetworkParameters {
+        self.parameters
+    }
+
+    /// Return the channel of the network.
+    #[must_use]
+    pub const fn channel(&self) -> Channel {
+        self.parameters.channel()
+    }
+
+    /// Return the channels the network may move to.
+    #[must_use]
+    pub const fn channel_mask(&self) -> ChannelMask {
+        self.channel_mask
+    }
+
+    /// Return the NWK update ID.
+    #[must_use]
+    pub const fn nwk_update_id(&self) -> u8 {
+        self.nwk_update_id
+    }
+
+    /// Return the network key and its counters.
+    #[must_use]
+    pub const fn network_key(&self) -> &NetworkKeyState {
+        &self.network_key
+    }
+
+    /// Return the devices of the network.
+    #[must_use]
+    pub fn devices(&self) -> &[BackedUpDevice] {
+        &self.devices
+    }
+}
@@ -0,0 +1,73 @@
+use zb_core::IeeeAddress;
+use zb_core::short_id::Device;
+
+use super::LinkKeyState;
+
+/// An entry of a backed-up device table.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BackedUpDevice {
+    ieee_address: IeeeAddress,
+    short_id: Option<Device>,
+    is_child: bool,
+    link_key: Option<LinkKeyState>,
+}
+
+impl BackedUpDevice {
+    /// Create a device entry without a short ID or link key that is not a child of the
+    /// coordinator.
+    #[must_use]
+    pub const fn new(ieee_address: IeeeAddress) -> Self {
+        Self {
+            ieee_address,
+            short_id: None,
+            is_child: false,
+            link_key: None,
+        }
+    }
+
+    /// Set the device's last known short ID.
+    #[must_use]
+    pub const fn with_short_id(mut self, short_id: Device) -> Self {
+        self.short_id = Some(short_id);
+        self
+    }
+
+    /// Mark the device as a child of the coordinator.
+    #[must_use]
+    pub const fn with_child(mut self, is_child: bool) -> Self {
+        self.is_child = is_child;
+        self
+    }
+
+    /// Set the device's trust-center link key.
+    #[must_use]
+    pub const fn with_link_key(mut self, link_key: LinkKeyState) -> Self {
+        self.link_key = Some(link_key);
+        self
+    }
+
+    /// Return the IEEE address.
+    #[must_use]
+    pub const fn ieee_address(&self) -> IeeeAddress {
+        self.ieee_address
+    }
+
+    /// Return the last known short ID.
+    #[must_use]
+    pub const fn short_id(&self) -> Option<Device> {
+        self.short_id
+    }
+
+    /// Return whether the device is a child of the coordinator.
+    #[must_use]
+    pub const fn is_child(&self) -> bool {
+        self.is_child
+    }
+
+    /// Return the trust-center link key.
+    #[must_use]
+    pub const fn link_key(&self) -> Option<&LinkKeyState> {
+        self.link_key.as_ref()
+    }
+}
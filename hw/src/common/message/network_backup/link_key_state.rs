@@ -0,0 +1,40 @@
+use zb_core::security::Key;
+
+/// A device's trust-center link key and its APS frame counters.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct LinkKeyState {
+    key: Key,
+    tx_counter: u32,
+    rx_counter: u32,
+}
+
+impl LinkKeyState {
+    /// Create a link key state.
+    #[must_use]
+    pub const fn new(key: Key, tx_counter: u32, rx_counter: u32) -> Self {
+        Self {
+            key,
+            tx_counter,
+            rx_counter,
+        }
+    }
+
+    /// Return the link key.
+    #[must_use]
+    pub const fn key(&self) -> &Key {
+        &self.key
+    }
+
+    /// Return the outgoing APS frame counter.
+    #[must_use]
+    pub const fn tx_counter(&self) -> u32 {
+        self.tx_counter
+    }
+
+    /// Return the last incoming APS frame counter.
+    #[must_use]
+    pub const fn rx_counter(&self) -> u32 {
+        self.rx_counter
+    }
+}
@@ -0,0 +1,43 @@
+use zb_core::security::Key;
+
+/// The network key with the state that must survive a move to another NCP.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct NetworkKeyState {
+    key: Key,
+    sequence_number: u8,
+    frame_counter: u32,
+}
+
+impl NetworkKeyState {
+    /// Create a network key state.
+    ///
+    /// The frame counter is the NCP's outgoing NWK frame counter. Devices drop frames whose counter
+    /// does not exceed the last one they accepted, so a restored NCP must continue from it.
+    #[must_use]
+    pub const fn new(key: Key, sequence_number: u8, frame_counter: u32) -> Self {
+        Self {
+            key,
+            sequence_number,
+            frame_counter,
+        }
+    }
+
+    /// Return the network key.
+    #[must_use]
+    pub const fn key(&self) -> &Key {
+        &self.key
+    }
+
+    /// Return the key sequence number.
+    #[must_use]
+    pub const fn sequence_number(&self) -> u8 {
+        self.sequence_number
+    }
+
+    /// Return the outgoing NWK frame counter.
+    #[must_use]
+    pub const fn frame_counter(&self) -> u32 {
+        self.frame_counter
+    }
+}
@@ -0,0 +1,42 @@
+use zb_core::IeeeAddress;
+
+use super::Channel;
+
+/// Parameters of the network the NCP currently operates.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct NetworkParameters {
+    pan_id: u16,
+    extended_pan_id: IeeeAddress,
+    channel: Channel,
+}
+
+impl NetworkParameters {
+    /// Create network parameters.
+    #[must_use]
+    pub const fn new(pan_id: u16, extended_pan_id: IeeeAddress, channel: Channel) -> Self {
+        Self {
+            pan_id,
+            extended_pan_id,
+            channel,
+        }
+    }
+
+    /// Return the PAN ID.
+    #[must_use]
+    pub const fn pan_id(&self) -> u16 {
+        self.pan_id
+    }
+
+    /// Return the extended PAN ID.
+    #[must_use]
+    pub const fn extended_pan_id(&self) -> IeeeAddress {
+        self.extended_pan_id
+    }
+
+    /// Return the radio channel.
+    #[must_use]
+    pub const fn channel(&self) -> Channel {
+        self.channel
+    }
+}
@@ -0,0 +1,75 @@
+/// How the trust center admits joining devices.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum JoinPolicy {
+    /// Reject every join.
+    Deny,
+
+    /// Admit devices using the default trust-center link key or a transient link key.
+    #[default]
+    AllowDefaultKey,
+
+    /// Admit only devices with a transient link key, such as one derived from an install code.
+    InstallCodeOnly,
+}
+
+/// Security policy of the trust center.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TrustCenterPolicy {
+    joins: JoinPolicy,
+    allow_unsecured_rejoins: bool,
+    allow_link_key_requests: bool,
+}
+
+impl TrustCenterPolicy {
+    /// Create a policy that admits joins according to `joins`, rejects unsecured rejoins, and
+    /// answers link-key requests.
+    #[must_use]
+    pub const fn new(joins: JoinPolicy) -> Self {
+        Self {
+            joins,
+            allow_unsecured_rejoins: false,
+            allow_link_key_requests: true,
+        }
+    }
+
+    /// Set whether devices may rejoin without network-layer security.
+    #[must_use]
+    pub const fn with_unsecured_rejoins(mut self, allow: bool) -> Self {
+        self.allow_unsecured_rejoins = allow;
+        self
+    }
+
+    /// Set whether the trust center answers requests for new trust-center link keys.
+    #[must_use]
+    pub const fn with_link_key_requests(mut self, allow: bool) -> Self {
+        self.allow_link_key_requests = allow;
+        self
+    }
+
+    /// Return how joining devices are admitted.
+    #[must_use]
+    pub const fn joins(&self) -> JoinPolicy {
+        self.joins
+    }
+
+    /// Return whether devices may rejoin without network-layer security.
+    #[must_use]
+    pub const fn allows_unsecured_rejoins(&self) -> bool {
+        self.allow_unsecured_rejoins
+    }
+
+    /// Return whether the trust center answers requests for new trust-center link keys.
+    #[must_use]
+    pub const fn allows_link_key_requests(&self) -> bool {
+        self.allow_link_key_requests
+    }
+}
+
+impl Default for TrustCenterPolicy {
+    fn default() -> Self {
+        Self::new(JoinPolicy::default())
+    }
+}
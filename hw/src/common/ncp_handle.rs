@@ -1,11 +1,12 @@
 #[cfg(feature = "driver")]
 use std::num::NonZeroUsize;
+use std::sync::{Arc, PoisonError, RwLock, Weak};
 #[cfg(feature = "coordinator")]
 use std::time::Duration;
 
 #[cfg(feature = "coordinator")]
 use bytes::Bytes;
-use tokio::sync::mpsc::{Sender, WeakSender};
+use tokio::sync::mpsc::Sender;
 #[cfg(feature = "coordinator")]
 use tokio::sync::oneshot::channel;
 #[cfg(feature = "coordinator")]
@@ -13,45 +14,100 @@ use zb_aps::apsde::DataRequest;
 #[cfg(feature = "coordinator")]
 use zb_core::IeeeAddress;
 #[cfg(feature = "coordinator")]
+use zb_core::security::Key;
+#[cfg(feature = "coordinator")]
 use zb_core::short_id::Device;
 #[cfg(feature = "coordinator")]
 use zb_zdp::SimpleDescriptor;
 
 use super::message::Message;
 #[cfg(feature = "coordinator")]
-use super::message::{ChannelMask, FoundNetwork, ScanDuration, ScannedChannel};
+use super::message::{
+    ChannelMask, Counters, Formation, FoundNetwork, InterPanRequest, NetworkBackup,
+    NetworkParameters, ScanDuration, ScannedChannel, TrustCenterPolicy,
+};
 #[cfg(feature = "coordinator")]
 use crate::Error;
 
 /// A handle on the NCP driver actor.
+///
+/// Clones share the channel to the current driver actor. Stopping or restarting the actor through
+/// any clone therefore redirects every other clone as well.
 #[derive(Clone, Debug)]
 pub struct NcpHandle {
-    sender: Sender<Message>,
+    sender: Arc<RwLock<Sender<Message>>>,
 }
 
 impl NcpHandle {
     #[cfg(feature = "driver")]
     pub(crate) fn channel(capacity: NonZeroUsize) -> (Self, tokio::sync::mpsc::Receiver<Message>) {
         let (sender, receiver) = tokio::sync::mpsc::channel(capacity.get());
-        let handle = Self { sender };
+        let handle = Self {
+            sender: Arc::new(RwLock::new(sender)),
+        };
         (handle, receiver)
     }
 
     #[cfg(feature = "coordinator")]
     async fn send(&self, message: Message) -> Result<(), Error> {
-        Ok(self.sender.send(message).await?)
+        let sender = self.sender();
+        Ok(sender.send(message).await?)
+    }
+
+    fn sender(&self) -> Sender<Message> {
+        self.sender
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    fn replace_sender(&self, sender: Sender<Message>) {
+        *self.sender.write().unwrap_or_else(PoisonError::into_inner) = sender;
     }
 
     /// Create a weak handle that does not keep the driver actor channel open.
     #[must_use]
     pub fn downgrade(&self) -> WeakNcpHandle {
-        WeakNcpHandle(self.sender.downgrade())
+        WeakNcpHandle(Arc::downgrade(&self.sender))
     }
 
     /// Return whether the driver actor channel has closed.
+    ///
+    /// The channel is closed after the actor has been stopped and before it has been restarted.
     #[must_use]
     pub fn is_closed(&self) -> bool {
-        self.sender.is_closed()
+        self.sender().is_closed()
+    }
+
+    /// Stop the driver actor.
+    ///
+    /// The actor finishes the requests already queued and then resolves with its driver. Until
+    /// [`NcpHandle::restart`] installs a new actor, every request through this handle or any of
+    /// its clones fails with [`crate::Error::ActorUnavailable`].
+    pub fn stop(&self) {
+        let (sender, _) = tokio::sync::mpsc::channel(1);
+        self.replace_sender(sender);
+    }
+
+    /// Restart the driver actor with the given driver.
+    ///
+    /// A running actor is stopped as if by [`NcpHandle::stop`]. This handle and all of its clones
+    /// then send their requests to the new actor, which serves them until it is stopped or
+    /// restarted again, or every strong handle has been dropped. Like
+    /// [`Driver::into_actor`](crate::Driver::into_actor), this returns an unspawned future that
+    /// must be spawned or otherwise continuously polled and that resolves with the driver.
+    #[cfg(feature = "driver")]
+    pub fn restart<T>(
+        &self,
+        driver: T,
+        channel_capacity: NonZeroUsize,
+    ) -> impl Future<Output = T> + Send + use<T>
+    where
+        T: super::driver::Driver,
+    {
+        let (sender, receiver) = tokio::sync::mpsc::channel(channel_capacity.get());
+        self.replace_sender(sender);
+        super::driver::serve(driver, receiver)
     }
 
     /// Return the local application endpoints provided by the NCP.
@@ -221,11 +277,189 @@ impl NcpHandle {
         receiver.await??;
         Ok(())
     }
+
+    /// Add a transient trust-center link key that lets a device join the network.
+    ///
+    /// Derive the key from a device's install code with
+    /// [`InstallCode::link_key`](zb_core::security::InstallCode::link_key).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the driver actor is unavailable or the backend cannot store the key.
+    #[cfg(feature = "coordinator")]
+    pub async fn add_transient_link_key(
+        &self,
+        ieee_address: IeeeAddress,
+        key: Key,
+    ) -> Result<(), Error> {
+        let (response, receiver) = channel();
+        self.send(Message::AddTransientLinkKey {
+            ieee_address,
+            key,
+            response,
+        })
+        .await?;
+        receiver.await?
+    }
+
+    /// Set the trust-center link key that the NCP shares with a device.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the driver actor is unavailable or the backend cannot store the key.
+    #[cfg(feature = "coordinator")]
+    pub async fn set_link_key(&self, ieee_address: IeeeAddress, key: Key) -> Result<(), Error> {
+        let (response, receiver) = channel();
+        self.send(Message::SetLinkKey {
+            ieee_address,
+            key,
+            response,
+        })
+        .await?;
+        receiver.await?
+    }
+
+    /// Form a new network with the coordinator as its trust center.
+    ///
+    /// Success means the NCP started forming the network. The backend reports
+    /// [`crate::NetworkEvent::Up`] once the network is operational.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the driver actor is unavailable or the NCP cannot form the network.
+    #[cfg(feature = "coordinator")]
+    pub async fn form_network(&self, formation: Formation) -> Result<(), Error> {
+        let (response, receiver) = channel();
+        self.send(Message::FormNetwork {
+            formation,
+            response,
+        })
+        .await?;
+        receiver.await?
+    }
+
+    /// Leave the current network and forget its parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the driver actor is unavailable or the NCP cannot leave the network.
+    #[cfg(feature = "coordinator")]
+    pub async fn leave_network(&self) -> Result<(), Error> {
+        let (response, receiver) = channel();
+        self.send(Message::LeaveNetwork { response }).await?;
+        receiver.await?
+    }
+
+    /// Return the PAN ID, extended PAN ID, and channel of the current network.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the driver actor is unavailable or the NCP is not part of a network.
+    #[cfg(feature = "coordinator")]
+    pub async fn get_network_parameters(&self) -> Result<NetworkParameters, Error> {
+        let (response, receiver) = channel();
+        self.send(Message::GetNetworkParameters { response })
+            .await?;
+        receiver.await?
+    }
+
+    /// Set the security policy that the trust center applies to joining devices.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the driver actor is unavailable or the NCP cannot apply the policy.
+    #[cfg(feature = "coordinator")]
+    pub async fn set_trust_center_policy(&self, policy: TrustCenterPolicy) -> Result<(), Error> {
+        let (response, receiver) = channel();
+        self.send(Message::SetTrustCenterPolicy { policy, response })
+            .await?;
+        receiver.await?
+    }
+
+    /// Export the state needed to move the current network to another NCP.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the driver actor is unavailable or the NCP cannot export its state.
+    #[cfg(feature = "coordinator")]
+    pub async fn backup_network(&self) -> Result<NetworkBackup, Error> {
+        let (response, receiver) = channel();
+        self.send(Message::BackupNetwork { response }).await?;
+        receiver.await?
+    }
+
+    /// Import a network exported by [`NcpHandle::backup_network`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the driver actor is unavailable or the NCP cannot import the state.
+    #[cfg(feature = "coordinator")]
+    pub async fn restore_network(&self, backup: NetworkBackup) -> Result<(), Error> {
+        let (response, receiver) = channel();
+        self.send(Message::RestoreNetwork { backup, response })
+            .await?;
+        receiver.await?
+    }
+
+    /// Return a snapshot of the NCP's diagnostic counters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the driver actor is unavailable or the counters cannot be read.
+    #[cfg(feature = "coordinator")]
+    pub async fn get_counters(&self) -> Result<Counters, Error> {
+        let (response, receiver) = channel();
+        self.send(Message::GetCounters { response }).await?;
+        receiver.await?
+    }
+
+    /// Return the longest APS payload, in octets, that the NCP transmits in a single frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the driver actor is unavailable or the payload length cannot be read.
+    #[cfg(feature = "coordinator")]
+    pub async fn get_maximum_payload_length(&self) -> Result<u8, Error> {
+        let (response, receiver) = channel();
+        self.send(Message::GetMaximumPayloadLength { response })
+            .await?;
+        receiver.await?
+    }
+
+    /// Transmit an inter-PAN frame and listen for inter-PAN frames on its channel.
+    ///
+    /// Received frames are reported as [`crate::Event::InterPan`] until
+    /// [`NcpHandle::end_inter_pan`] returns the radio to the network channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the driver actor is unavailable or the backend cannot transmit the
+    /// frame.
+    #[cfg(feature = "coordinator")]
+    pub async fn transmit_inter_pan(&self, request: InterPanRequest) -> Result<(), Error> {
+        let (response, receiver) = channel();
+        self.send(Message::TransmitInterPan { request, response })
+            .await?;
+        receiver.await?
+    }
+
+    /// Return the radio from an inter-PAN channel to the network channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the driver actor is unavailable or the backend cannot restore the
+    /// network channel.
+    #[cfg(feature = "coordinator")]
+    pub async fn end_inter_pan(&self) -> Result<(), Error> {
+        let (response, receiver) = channel();
+        self.send(Message::EndInterPan { response }).await?;
+        receiver.await?
+    }
 }
 
 /// A weak handle on the NCP that does not keep the driver actor channel open.
 #[derive(Clone, Debug)]
-pub struct WeakNcpHandle(WeakSender<Message>);
+pub struct WeakNcpHandle(Weak<RwLock<Sender<Message>>>);
 
 impl WeakNcpHandle {
     /// Attempt to upgrade this weak handle.
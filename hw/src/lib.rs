@@ -9,6 +9,8 @@
 //! - `coordinator` adds the caller-facing inherent methods on `NcpHandle` and enables `types`.
 //! - `driver` adds the implementor-facing `Driver` trait and protocol re-export modules, and enables
 //!   `types`.
+//! - `sim` adds the [`sim`] module with a simulated NCP driver for hardware-free integration tests,
+//!   and enables `driver`.
 //!
 //! Event translation and startup wiring are backend concerns; this crate does not prescribe
 //! backend configuration or provide an event-translator abstraction.
@@ -22,6 +24,10 @@
 //! backends report incoming ASDUs and acknowledged transmission completion asynchronously through
 //! [`Event::Apsde`] using [`ApsdeEvent::DataIndication`] and [`ApsdeEvent::DataConfirm`].
 //!
+//! Backends that support inter-PAN communication, as used by Touchlink commissioning, transmit
+//! stub-NWK frames through `NcpHandle::transmit_inter_pan` and report received ones through
+//! [`Event::InterPan`] until `NcpHandle::end_inter_pan` returns the radio to the network channel.
+//!
 //! Every `Driver` implementation must provide the NCP's local application endpoints through
 //! `Driver::get_endpoints`. Each endpoint is represented by a complete
 //! `zb_zdp::SimpleDescriptor`; coordinator code retrieves the same descriptors through
@@ -38,9 +44,11 @@ pub use self::common::Driver;
 #[cfg(feature = "types")]
 #[cfg_attr(docsrs, doc(cfg(feature = "types")))]
 pub use self::common::{
-    ApsdeEvent, Channel, ChannelMask, DeviceEvent, Error, Event, FoundNetwork, NcpHandle,
-    NetworkDescriptor, NetworkEvent, Operation, RouteError, ScanDuration, ScannedChannel,
-    TransmissionError, WeakNcpHandle,
+    ApsdeEvent, BackedUpDevice, Channel, ChannelMask, Counter, Counters, DeviceEvent, Error, Event,
+    Formation, FoundNetwork, InterPanDestination, InterPanIndication, InterPanRequest, JoinPolicy,
+    LinkKeyState, NcpHandle, NetworkBackup, NetworkDescriptor, NetworkEvent, NetworkKeyState,
+    NetworkParameters, Operation, RouteError, ScanDuration, ScannedChannel, TransmissionError,
+    TrustCenterPolicy, WeakNcpHandle,
 };
 #[cfg(feature = "driver")]
 #[cfg_attr(docsrs, doc(cfg(feature = "driver")))]
@@ -48,3 +56,6 @@ pub use self::reexports::{aps, core, zdp};
 
 mod common;
 mod reexports;
+#[cfg(feature = "sim")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sim")))]
+pub mod sim;
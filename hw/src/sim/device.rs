@@ -0,0 +1,283 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use zb_core::node::MacCapabilityFlags;
+use zb_core::short_id::{Broadcast, Device};
+use zb_core::types::Type;
+use zb_core::{Endpoint, FullAddress, IeeeAddress};
+use zb_zdp::{Command, SimpleDescriptor};
+
+use super::Link;
+use crate::Channel;
+
+/// Custom answer to ZDP requests of one cluster.
+///
+/// The responder receives the parsed request and returns the response command, or [`None`] to
+/// stay silent.
+pub type ZdpResponder = Arc<dyn Fn(&Command) -> Option<Command> + Send + Sync>;
+
+/// Answer to inter-PAN frames of one cluster.
+///
+/// The responder receives the frame's ASDU and returns the ASDU of the answer, or [`None`] to
+/// stay silent.
+pub type InterPanResponder = Arc<dyn Fn(&[u8]) -> Option<Bytes> + Send + Sync>;
+
+/// Endpoint, cluster ID, and attribute ID of a ZCL attribute.
+type AttributeKey = (u8, u16, u16);
+
+/// A scripted device of a [`VirtualNetwork`](super::VirtualNetwork).
+///
+/// By default the device is a mains-powered router that joins when the network starts, never
+/// leaves, and is reached over a [perfect](Link::PERFECT) link. It answers address, node, and
+/// endpoint discovery from its capabilities and endpoints, and ZCL attribute reads, writes, and
+/// reporting configurations from its attribute table. It ignores inter-PAN frames unless it has an
+/// inter-PAN responder for their cluster.
+#[derive(Clone)]
+pub struct VirtualDevice {
+    address: FullAddress,
+    capabilities: MacCapabilityFlags,
+    endpoints: Vec<SimpleDescriptor>,
+    attributes: BTreeMap<AttributeKey, Type>,
+    zdp_responders: BTreeMap<u16, ZdpResponder>,
+    inter_pan_responders: BTreeMap<u16, InterPanResponder>,
+    radio_channel: Option<Channel>,
+    link: Link,
+    joins_after: Duration,
+    leaves_after: Option<Duration>,
+}
+
+impl VirtualDevice {
+    /// Create a router with the given addresses and no endpoints.
+    #[must_use]
+    pub fn new(ieee_address: IeeeAddress, short_id: Device) -> Self {
+        Self {
+            address: FullAddress::new(ieee_address, short_id),
+            capabilities: MacCapabilityFlags::DEVICE_TYPE
+                | MacCapabilityFlags::POWER_SOURCE
+                | MacCapabilityFlags::RECEIVER_ON_WHEN_IDLE
+                | MacCapabilityFlags::ALLOCATE_ADDRESS,
+            endpoints: Vec::new(),
+            attributes: BTreeMap::new(),
+            zdp_responders: BTreeMap::new(),
+            inter_pan_responders: BTreeMap::new(),
+            radio_channel: None,
+            link: Link::PERFECT,
+            joins_after: Duration::ZERO,
+            leaves_after: None,
+        }
+    }
+
+    /// Announce the given MAC capabilities.
+    ///
+    /// The capabilities also decide which broadcasts the device receives: only devices with
+    /// [`MacCapabilityFlags::RECEIVER_ON_WHEN_IDLE`] receive `RxOnWhenIdle` broadcasts, and only
+    /// full-function devices receive router broadcasts.
+    #[must_use]
+    pub const fn with_capabilities(mut self, capabilities: MacCapabilityFlags) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Add an application endpoint.
+    #[must_use]
+    pub fn with_endpoint(mut self, descriptor: SimpleDescriptor) -> Self {
+        self.endpoints.push(descriptor);
+        self
+    }
+
+    /// Serve a ZCL attribute of a server cluster.
+    #[must_use]
+    pub fn with_attribute<T>(
+        mut self,
+        endpoint: Endpoint,
+        cluster_id: u16,
+        attribute_id: u16,
+        value: T,
+    ) -> Self
+    where
+        T: Into<Type>,
+    {
+        self.attributes
+            .insert((endpoint.into(), cluster_id, attribute_id), value.into());
+        self
+    }
+
+    /// Answer ZDP requests of the given cluster with a custom responder.
+    ///
+    /// Custom responders take precedence over the device's built-in ZDP behavior.
+    #[must_use]
+    pub fn with_zdp_responder<F>(mut self, cluster_id: u16, responder: F) -> Self
+    where
+        F: Fn(&Command) -> Option<Command> + Send + Sync + 'static,
+    {
+        self.zdp_responders.insert(cluster_id, Arc::new(responder));
+        self
+    }
+
+    /// Answer inter-PAN frames of the given cluster with a responder.
+    #[must_use]
+    pub fn with_inter_pan_responder<F>(mut self, cluster_id: u16, responder: F) -> Self
+    where
+        F: Fn(&[u8]) -> Option<Bytes> + Send + Sync + 'static,
+    {
+        self.inter_pan_responders
+            .insert(cluster_id, Arc::new(responder));
+        self
+    }
+
+    /// Receive inter-PAN frames on the given channel instead of the network's channel.
+    #[must_use]
+    pub const fn with_radio_channel(mut self, channel: Channel) -> Self {
+        self.radio_channel = Some(channel);
+        self
+    }
+
+    /// Reach the device over the given link.
+    #[must_use]
+    pub const fn with_link(mut self, link: Link) -> Self {
+        self.link = link;
+        self
+    }
+
+    /// Join the network the given time after the simulation starts.
+    #[must_use]
+    pub const fn joins_after(mut self, delay: Duration) -> Self {
+        self.joins_after = delay;
+        self
+    }
+
+    /// Leave the network the given time after the simulation starts.
+    #[must_use]
+    pub const fn leaves_after(mut self, delay: Duration) -> Self {
+        self.leaves_after = Some(delay);
+        self
+    }
+
+    /// Return the device's addresses.
+    #[must_use]
+    pub const fn address(&self) -> FullAddress {
+        self.address
+    }
+
+    /// Return the device's MAC capabilities.
+    #[must_use]
+    pub const fn capabilities(&self) -> MacCapabilityFlags {
+        self.capabilities
+    }
+
+    /// Return the device's application endpoints.
+    #[must_use]
+    pub fn endpoints(&self) -> &[SimpleDescriptor] {
+        &self.endpoints
+    }
+
+    /// Return the current value of a ZCL attribute.
+    #[must_use]
+    pub fn attribute(
+        &self,
+        endpoint: Endpoint,
+        cluster_id: u16,
+        attribute_id: u16,
+    ) -> Option<&Type> {
+        self.attributes
+            .get(&(endpoint.into(), cluster_id, attribute_id))
+    }
+
+    /// Return the channel the device receives inter-PAN frames on, if it differs from the
+    /// network's channel.
+    #[must_use]
+    pub const fn radio_channel(&self) -> Option<Channel> {
+        self.radio_channel
+    }
+
+    /// Return the link to the device.
+    #[must_use]
+    pub const fn link(&self) -> Link {
+        self.link
+    }
+
+    /// Return when the device joins the network.
+    #[must_use]
+    pub const fn join_time(&self) -> Duration {
+        self.joins_after
+    }
+
+    /// Return when the device leaves the network, if it does.
+    #[must_use]
+    pub const fn leave_time(&self) -> Option<Duration> {
+        self.leaves_after
+    }
+
+    /// Return whether the device is part of the network at the given simulation time.
+    pub(super) fn is_present(&self, elapsed: Duration) -> bool {
+        elapsed >= self.joins_after && self.leaves_after.is_none_or(|leave| elapsed < leave)
+    }
+
+    /// Return whether the device receives broadcasts to the given receiver set.
+    pub(super) const fn receives(&self, broadcast: Broadcast) -> bool {
+        match broadcast {
+            Broadcast::AllDevices => true,
+            Broadcast::RxOnWhenIdle => self
+                .capabilities
+                .contains(MacCapabilityFlags::RECEIVER_ON_WHEN_IDLE),
+            Broadcast::RoutersAndCoordinator | Broadcast::LowPowerRouters => {
+                self.capabilities.contains(MacCapabilityFlags::DEVICE_TYPE)
+            }
+        }
+    }
+
+    /// Return the simple descriptor of an endpoint.
+    pub(super) fn descriptor(&self, endpoint: Endpoint) -> Option<&SimpleDescriptor> {
+        self.endpoints
+            .iter()
+            .find(|descriptor| descriptor.endpoint() == Ok(endpoint))
+    }
+
+    /// Return the custom ZDP responder for a cluster.
+    pub(super) fn zdp_responder(&self, cluster_id: u16) -> Option<&ZdpResponder> {
+        self.zdp_responders.get(&cluster_id)
+    }
+
+    /// Return the inter-PAN responder for a cluster.
+    pub(super) fn inter_pan_responder(&self, cluster_id: u16) -> Option<&InterPanResponder> {
+        self.inter_pan_responders.get(&cluster_id)
+    }
+
+    /// Return a mutable ZCL attribute.
+    pub(super) fn attribute_mut(
+        &mut self,
+        endpoint: Endpoint,
+        cluster_id: u16,
+        attribute_id: u16,
+    ) -> Option<&mut Type> {
+        self.attributes
+            .get_mut(&(endpoint.into(), cluster_id, attribute_id))
+    }
+}
+
+impl Debug for VirtualDevice {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("VirtualDevice")
+            .field("address", &self.address)
+            .field("capabilities", &self.capabilities)
+            .field("endpoints", &self.endpoints)
+            .field("attributes", &self.attributes)
+            .field(
+                "zdp_responders",
+                &self.zdp_responders.keys().collect::<Vec<_>>(),
+            )
+            .field(
+                "inter_pan_responders",
+                &self.inter_pan_responders.keys().collect::<Vec<_>>(),
+            )
+            .field("radio_channel", &self.radio_channel)
+            .field("link", &self.link)
+            .field("joins_after", &self.joins_after)
+            .field("leaves_after", &self.leaves_after)
+            .finish()
+    }
+}
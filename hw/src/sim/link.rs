@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+/// Radio link between the simulated NCP and a virtual device.
+///
+/// Every frame crossing the link is delayed by the latency and lost with the loss percentage,
+/// independently for each direction. Received frames report the link-quality value.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Link {
+    quality: u8,
+    loss_percent: u8,
+    latency: Duration,
+}
+
+impl Link {
+    /// A lossless link with perfect link quality and no latency.
+    pub const PERFECT: Self = Self::new(u8::MAX);
+
+    /// Create a lossless link without latency that reports the given link quality.
+    #[must_use]
+    pub const fn new(link_quality: u8) -> Self {
+        Self {
+            quality: link_quality,
+            loss_percent: 0,
+            latency: Duration::ZERO,
+        }
+    }
+
+    /// Lose the given percentage of frames, saturating at 100.
+    #[must_use]
+    pub const fn with_loss(mut self, percent: u8) -> Self {
+        self.loss_percent = if percent > 100 { 100 } else { percent };
+        self
+    }
+
+    /// Delay every frame by the given latency.
+    #[must_use]
+    pub const fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Return the link-quality indication reported for received frames.
+    #[must_use]
+    pub const fn link_quality(self) -> u8 {
+        self.quality
+    }
+
+    /// Return the percentage of lost frames.
+    #[must_use]
+    pub const fn loss_percent(self) -> u8 {
+        self.loss_percent
+    }
+
+    /// Return the one-way latency.
+    #[must_use]
+    pub const fn latency(self) -> Duration {
+        self.latency
+    }
+}
+
+impl Default for Link {
+    fn default() -> Self {
+        Self::PERFECT
+    }
+}
@@ -0,0 +1,1157 @@
+use std::collections::BTreeMap;
+use std::iter::once;
+use std::time::Duration;
+
+use bytes::Bytes;
+use thiserror::Error;
+use tokio::spawn;
+use tokio::sync::mpsc::Sender;
+use tokio::time::{Instant, sleep_until};
+use zb_aps::TxOptions;
+use zb_aps::apsde::{
+    ConfirmStatus, DataConfirm, DataIndication, DataRequest, Destination, IndicationMetadata,
+    IndicationStatus, IndividualEndpoint, NetworkAddress, ReceivedDestination, RequestDestination,
+    Security, Source, Status,
+};
+use zb_core::node::MacCapabilityFlags;
+use zb_core::security::Key;
+use zb_core::short_id::Device;
+use zb_core::{Endpoint, IeeeAddress, Profile};
+use zb_zdp::SimpleDescriptor;
+
+use super::rng::Rng;
+use super::{VirtualDevice, zcl, zdp};
+use crate::{
+    ApsdeEvent, BackedUpDevice, Channel, ChannelMask, Counter, Counters, Driver, Error, Event,
+    Formation, FoundNetwork, InterPanDestination, InterPanIndication, InterPanRequest, JoinPolicy,
+    LinkKeyState, NetworkBackup, NetworkEvent, NetworkKeyState, NetworkParameters, ScanDuration,
+    ScannedChannel, TrustCenterPolicy,
+};
+
+/// Longest permit-joining period of a Zigbee network.
+const MAX_PERMIT_JOINING: Duration = Duration::from_secs(254);
+
+/// NWK address of the simulated coordinator.
+const COORDINATOR: u16 = 0x0000;
+
+/// PAN ID reported by devices outside of any network.
+const NO_PAN_ID: u16 = 0xFFFF;
+
+/// Error reported when an address does not belong to a device currently in the virtual network.
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+#[error("Unknown virtual device")]
+pub struct UnknownDevice;
+
+/// Error reported when the simulated NCP's network state does not permit an operation.
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum NetworkStateError {
+    /// The NCP is not part of a network.
+    #[error("Not part of a network")]
+    NotJoined,
+
+    /// The NCP is already part of a network.
+    #[error("Already part of a network")]
+    AlreadyJoined,
+
+    /// The formation's channel mask contains no channel.
+    #[error("Empty channel mask")]
+    EmptyChannelMask,
+}
+
+/// A simulated NCP coordinating a [`VirtualNetwork`](super::VirtualNetwork).
+///
+/// Transmissions are delivered to the virtual devices addressed by the request. Acknowledged
+/// unicasts complete with an [`ApsdeEvent::DataConfirm`] after the link's round trip, and device
+/// responses arrive as [`ApsdeEvent::DataIndication`]s after the same delay. Each leg of a round
+/// trip may be lost according to the device's [`Link`](super::Link).
+///
+/// [`Driver::leave_network`] takes the simulated coordinator off its network, and
+/// [`Driver::form_network`] brings it up again on the lowest channel of the formation's mask.
+/// [`Driver::backup_network`] exports the channel mask and NWK update ID of the last formation or
+/// restore, the network key, the link keys set through [`Driver::set_link_key`], and the devices
+/// currently present. The NWK frame counter counts transmissions.
+///
+/// [`Driver::get_counters`] reports the APS transmission counters, the received APS unicasts, and
+/// the route discoveries requested through [`Driver::route_request`].
+///
+/// [`Driver::transmit_inter_pan`] delivers inter-PAN frames to the present devices that receive on
+/// the request's channel, which is the network's channel unless a device selects another one, and
+/// reports their inter-PAN responders' answers as [`Event::InterPan`] after the link's round trip.
+#[derive(Debug)]
+pub struct SimulatedNcp {
+    ieee_address: IeeeAddress,
+    network: Option<NetworkParameters>,
+    channel_mask: ChannelMask,
+    nwk_update_id: u8,
+    network_key: NetworkKeyState,
+    link_keys: BTreeMap<IeeeAddress, Key>,
+    trust_center_policy: TrustCenterPolicy,
+    endpoints: Box<[SimpleDescriptor]>,
+    devices: Vec<VirtualDevice>,
+    events: Sender<Event>,
+    started: Instant,
+    rng: Rng,
+    counters: BTreeMap<Counter, u32>,
+    inter_pan_channel: Option<Channel>,
+}
+
+/// A response leaving a virtual device.
+struct Reply {
+    source_endpoint: IndividualEndpoint,
+    cluster_id: u16,
+    asdu: Bytes,
+}
+
+impl SimulatedNcp {
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "the virtual network hands over its whole description at once"
+    )]
+    pub(super) fn new(
+        ieee_address: IeeeAddress,
+        network: NetworkParameters,
+        network_key: Key,
+        endpoints: Box<[SimpleDescriptor]>,
+        devices: Vec<VirtualDevice>,
+        events: Sender<Event>,
+        started: Instant,
+        rng: Rng,
+    ) -> Self {
+        Self {
+            ieee_address,
+            network: Some(network),
+            channel_mask: once(network.channel()).collect(),
+            nwk_update_id: 0,
+            network_key: NetworkKeyState::new(network_key, 0, 0),
+            link_keys: BTreeMap::new(),
+            trust_center_policy: TrustCenterPolicy::new(JoinPolicy::AllowDefaultKey),
+            endpoints,
+            devices,
+            events,
+            started,
+            rng,
+            counters: BTreeMap::new(),
+            inter_pan_channel: None,
+        }
+    }
+
+    /// Return the virtual devices, including their current attribute values.
+    #[must_use]
+    pub fn devices(&self) -> &[VirtualDevice] {
+        &self.devices
+    }
+
+    /// Return the trust-center policy last set through [`Driver::set_trust_center_policy`].
+    #[must_use]
+    pub const fn trust_center_policy(&self) -> TrustCenterPolicy {
+        self.trust_center_policy
+    }
+
+    /// Return the channel the NCP listens on for inter-PAN frames, if it left the network's
+    /// channel through [`Driver::transmit_inter_pan`].
+    #[must_use]
+    pub const fn inter_pan_channel(&self) -> Option<Channel> {
+        self.inter_pan_channel
+    }
+
+    /// Increment a diagnostic counter.
+    fn count(&mut self, counter: Counter) {
+        let value = self.counters.entry(counter).or_default();
+        *value = value.wrapping_add(1);
+    }
+
+    /// Return the parameters of the current network.
+    fn network(&self) -> Result<NetworkParameters, Error> {
+        self.network
+            .ok_or_else(|| Error::backend(NetworkStateError::NotJoined))
+    }
+
+    /// Return the devices currently in the network.
+    fn present(&self) -> impl Iterator<Item = (usize, &VirtualDevice)> {
+        let elapsed = self.started.elapsed();
+
+        self.devices
+            .iter()
+            .enumerate()
+            .filter(move |(_, device)| device.is_present(elapsed))
+    }
+
+    /// Return the indices of the devices receiving a transmission to the destination.
+    fn recipients(&self, destination: RequestDestination) -> Vec<usize> {
+        self.present()
+            .filter(|(_, device)| {
+                let address = device.address();
+
+                match destination {
+                    RequestDestination::Network { address: nwk, .. } => {
+                        address.short_id().as_u16() == nwk.as_u16()
+                    }
+                    RequestDestination::Extended { address: ieee, .. } => {
+                        address.ieee_address() == ieee
+                    }
+                    RequestDestination::Broadcast { address, .. } => device.receives(address),
+                    RequestDestination::Group { .. } | RequestDestination::Bound => false,
+                }
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+impl Driver for SimulatedNcp {
+    async fn get_endpoints(&self) -> Result<Box<[SimpleDescriptor]>, Error> {
+        Ok(self.endpoints.clone())
+    }
+
+    async fn get_pan_id(&mut self) -> Result<u16, Error> {
+        Ok(self.network()?.pan_id())
+    }
+
+    async fn get_ieee_address(&mut self) -> Result<IeeeAddress, Error> {
+        Ok(self.ieee_address)
+    }
+
+    async fn scan_networks(
+        &mut self,
+        _channel_mask: ChannelMask,
+        _duration: ScanDuration,
+    ) -> Result<Vec<FoundNetwork>, Error> {
+        Ok(Vec::new())
+    }
+
+    async fn scan_channels(
+        &mut self,
+        _channel_mask: ChannelMask,
+        _duration: ScanDuration,
+    ) -> Result<Vec<ScannedChannel>, Error> {
+        Ok(Vec::new())
+    }
+
+    async fn allow_joins(&mut self, duration: Duration) -> Result<Duration, Error> {
+        let duration = duration.min(MAX_PERMIT_JOINING);
+        let timeline = if duration.is_zero() {
+            vec![(Duration::ZERO, NetworkEvent::Closed.into())]
+        } else {
+            vec![
+                (Duration::ZERO, NetworkEvent::Opened.into()),
+                (duration, NetworkEvent::Closed.into()),
+            ]
+        };
+
+        schedule(&self.events, Instant::now(), timeline);
+        Ok(duration)
+    }
+
+    async fn route_request(&mut self, _radius: u8) -> Result<(), Error> {
+        self.count(Counter::RouteDiscoveryInitiated);
+        Ok(())
+    }
+
+    async fn short_id_to_ieee_address(&mut self, short_id: Device) -> Result<IeeeAddress, Error> {
+        self.present()
+            .map(|(_, device)| device.address())
+            .find(|address| address.short_id() == short_id)
+            .map(|address| address.ieee_address())
+            .ok_or_else(|| Error::backend(UnknownDevice))
+    }
+
+    async fn ieee_address_to_short_id(
+        &mut self,
+        ieee_address: IeeeAddress,
+    ) -> Result<Device, Error> {
+        self.present()
+            .map(|(_, device)| device.address())
+            .find(|address| address.ieee_address() == ieee_address)
+            .map(|address| address.short_id())
+            .ok_or_else(|| Error::backend(UnknownDevice))
+    }
+
+    async fn transmit(&mut self, request: DataRequest<Bytes>, counter: u8) -> Result<(), Error> {
+        let destination = request.destination();
+        let (endpoint, broadcast, confirmed) = match destination {
+            RequestDestination::Network { address, endpoint } => (
+                Some(endpoint),
+                false,
+                Some(Destination::Network { address, endpoint }),
+            ),
+            RequestDestination::Extended { address, endpoint } => (
+                Some(endpoint),
+                false,
+                Some(Destination::Extended { address, endpoint }),
+            ),
+            RequestDestination::Broadcast { endpoint, .. } => (Some(endpoint), true, None),
+            RequestDestination::Group { .. } | RequestDestination::Bound => (None, true, None),
+        };
+        let acknowledged = request
+            .tx_options()
+            .contains(TxOptions::ACKNOWLEDGED_TRANSMISSION);
+        let mut acknowledged_by = None;
+        let mut timeline = Vec::new();
+
+        for index in self.recipients(destination) {
+            let link = self.devices[index].link();
+
+            if self.rng.loses(link.loss_percent()) {
+                continue;
+            }
+
+            let round_trip = link.latency() * 2;
+            let replies = endpoint.map_or_else(Vec::new, |endpoint| {
+                receive(&mut self.devices[index], &request, endpoint, broadcast)
+            });
+            let source = self.devices[index].address().short_id();
+
+            if !broadcast && !self.rng.loses(link.loss_percent()) {
+                acknowledged_by = Some(round_trip);
+            }
+
+            for reply in replies {
+                if !self.rng.loses(link.loss_percent()) {
+                    self.count(Counter::ApsRxUnicast);
+                    timeline.push((
+                        round_trip,
+                        indication(&request, source, link.link_quality(), reply),
+                    ));
+                }
+            }
+        }
+
+        if let Some(destination) = confirmed.filter(|_| acknowledged) {
+            let (delay, status) = acknowledged_by
+                .map_or((Duration::ZERO, Status::NoAcknowledgement), |round_trip| {
+                    (round_trip, Status::Success)
+                });
+            self.count(if status == Status::Success {
+                Counter::ApsTxUnicastSuccess
+            } else {
+                Counter::ApsTxUnicastFailure
+            });
+            let confirmation = DataConfirm::new(
+                destination,
+                request.source_endpoint(),
+                ConfirmStatus::Aps(status),
+                (),
+            );
+
+            // Confirmations precede replies that arrive at the same time.
+            timeline.insert(
+                0,
+                (
+                    delay,
+                    ApsdeEvent::DataConfirm {
+                        counter,
+                        confirmation,
+                    }
+                    .into(),
+                ),
+            );
+        }
+
+        if broadcast {
+            self.count(Counter::ApsTxBroadcast);
+        }
+
+        schedule(&self.events, Instant::now(), timeline);
+        self.network_key = NetworkKeyState::new(
+            *self.network_key.key(),
+            self.network_key.sequence_number(),
+            self.network_key.frame_counter().wrapping_add(1),
+        );
+        Ok(())
+    }
+
+    async fn add_transient_link_key(
+        &mut self,
+        _ieee_address: IeeeAddress,
+        _key: Key,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    async fn set_link_key(&mut self, ieee_address: IeeeAddress, key: Key) -> Result<(), Error> {
+        self.link_keys.insert(ieee_address, key);
+        Ok(())
+    }
+
+    async fn form_network(&mut self, formation: Formation) -> Result<(), Error> {
+        if self.network.is_some() {
+            return Err(Error::backend(NetworkStateError::AlreadyJoined));
+        }
+
+        let channel = formation
+            .channel_mask()
+            .channels()
+            .next()
+            .ok_or_else(|| Error::backend(NetworkStateError::EmptyChannelMask))?;
+        self.network = Some(NetworkParameters::new(
+            formation.pan_id(),
+            formation.extended_pan_id(),
+            channel,
+        ));
+        self.channel_mask = formation.channel_mask();
+        self.nwk_update_id = 0;
+        self.network_key = NetworkKeyState::new(
+            *formation.network_key(),
+            0,
+            self.network_key.frame_counter(),
+        );
+        schedule(
+            &self.events,
+            Instant::now(),
+            vec![(Duration::ZERO, NetworkEvent::Up.into())],
+        );
+        Ok(())
+    }
+
+    async fn leave_network(&mut self) -> Result<(), Error> {
+        self.network()?;
+        self.network = None;
+        schedule(
+            &self.events,
+            Instant::now(),
+            vec![(Duration::ZERO, NetworkEvent::Down.into())],
+        );
+        Ok(())
+    }
+
+    async fn get_network_parameters(&mut self) -> Result<NetworkParameters, Error> {
+        self.network()
+    }
+
+    async fn set_trust_center_policy(&mut self, policy: TrustCenterPolicy) -> Result<(), Error> {
+        self.trust_center_policy = policy;
+        Ok(())
+    }
+
+    async fn backup_network(&mut self) -> Result<NetworkBackup, Error> {
+        let backup = NetworkBackup::new(self.ieee_address, self.network()?, self.network_key)
+            .with_channel_mask(self.channel_mask)
+            .with_nwk_update_id(self.nwk_update_id);
+
+        Ok(self
+            .present()
+            .map(|(_, device)| {
+                let address = device.address();
+                let entry = BackedUpDevice::new(address.ieee_address())
+                    .with_short_id(address.short_id())
+                    .with_child(
+                        !device
+                            .capabilities()
+                            .contains(MacCapabilityFlags::DEVICE_TYPE),
+                    );
+
+                self.link_keys
+                    .get(&address.ieee_address())
+                    .map_or(entry, |key| {
+                        entry.with_link_key(LinkKeyState::new(*key, 0, 0))
+                    })
+            })
+            .fold(backup, NetworkBackup::with_device))
+    }
+
+    async fn restore_network(&mut self, backup: NetworkBackup) -> Result<(), Error> {
+        if self.network.is_some() {
+            return Err(Error::backend(NetworkStateError::AlreadyJoined));
+        }
+
+        self.ieee_address = backup.ieee_address();
+        self.network = Some(backup.parameters());
+        self.channel_mask = backup.channel_mask();
+        self.nwk_update_id = backup.nwk_update_id();
+        self.network_key = *backup.network_key();
+        self.link_keys = backup
+            .devices()
+            .iter()
+            .filter_map(|device| {
+                device
+                    .link_key()
+                    .map(|link_key| (device.ieee_address(), *link_key.key()))
+            })
+            .collect();
+        schedule(
+            &self.events,
+            Instant::now(),
+            vec![(Duration::ZERO, NetworkEvent::Up.into())],
+        );
+        Ok(())
+    }
+
+    async fn get_counters(&mut self) -> Result<Counters, Error> {
+        Ok(self
+            .counters
+            .iter()
+            .map(|(&counter, &value)| (counter, value))
+            .collect())
+    }
+
+    async fn transmit_inter_pan(&mut self, request: InterPanRequest) -> Result<(), Error> {
+        let network = self.network;
+        let recipients: Vec<usize> = self
+            .present()
+            .filter(|(_, device)| {
+                device
+                    .radio_channel()
+                    .or_else(|| network.map(|network| network.channel()))
+                    == Some(request.channel())
+                    && match request.destination() {
+                        InterPanDestination::Broadcast => true,
+                        InterPanDestination::Device { ieee_address, .. } => {
+                            device.address().ieee_address() == ieee_address
+                        }
+                    }
+            })
+            .map(|(index, _)| index)
+            .collect();
+        let mut timeline = Vec::new();
+
+        for index in recipients {
+            let device = &self.devices[index];
+            let link = device.link();
+            let Some(reply) = device
+                .inter_pan_responder(request.cluster_id())
+                .and_then(|responder| responder(request.asdu()))
+            else {
+                continue;
+            };
+            let source = device.address().ieee_address();
+
+            if self.rng.loses(link.loss_percent()) || self.rng.loses(link.loss_percent()) {
+                continue;
+            }
+
+            timeline.push((
+                link.latency() * 2,
+                InterPanIndication::new(
+                    source,
+                    network.map_or(NO_PAN_ID, |network| network.pan_id()),
+                    request.profile_id(),
+                    request.cluster_id(),
+                    link.link_quality(),
+                    reply,
+                )
+                .into(),
+            ));
+        }
+
+        self.inter_pan_channel = Some(request.channel());
+        schedule(&self.events, Instant::now(), timeline);
+        Ok(())
+    }
+
+    async fn end_inter_pan(&mut self) -> Result<(), Error> {
+        self.inter_pan_channel = None;
+        Ok(())
+    }
+}
+
+/// Emit events at the given offsets from `start`.
+///
+/// Events with equal offsets keep their order.
+pub(super) fn schedule(
+    events: &Sender<Event>,
+    start: Instant,
+    mut timeline: Vec<(Duration, Event)>,
+) {
+    if timeline.is_empty() {
+        return;
+    }
+
+    timeline.sort_by_key(|(offset, _)| *offset);
+    let events = events.clone();
+
+    spawn(async move {
+        for (offset, event) in timeline {
+            sleep_until(start + offset).await;
+
+            if events.send(event).await.is_err() {
+                return;
+            }
+        }
+    });
+}
+
+/// Return the NWK address of a virtual device.
+pub(super) const fn network_address(short_id: Device) -> NetworkAddress {
+    NetworkAddress::new(short_id.as_u16())
+        .expect("device short addresses are valid APSDE network addresses")
+}
+
+/// Let a device process a request and return its replies.
+fn receive(
+    device: &mut VirtualDevice,
+    request: &DataRequest<Bytes>,
+    endpoint: Endpoint,
+    broadcast: bool,
+) -> Vec<Reply> {
+    let asdu = request.asdu();
+
+    if request.profile_id() == Profile::Network.as_u16() {
+        return if endpoint == Endpoint::Data {
+            zdp::respond(device, request.cluster_id(), asdu, broadcast)
+                .map(|(cluster_id, asdu)| Reply {
+                    source_endpoint: IndividualEndpoint::new(Endpoint::Data)
+                        .expect("ZDO endpoint is individual"),
+                    cluster_id,
+                    asdu,
+                })
+                .into_iter()
+                .collect()
+        } else {
+            Vec::new()
+        };
+    }
+
+    let endpoint_broadcast = endpoint == Endpoint::Broadcast;
+    let endpoints: Vec<Endpoint> = if endpoint_broadcast {
+        device
+            .endpoints()
+            .iter()
+            .filter(|descriptor| {
+                descriptor.profile_id() == request.profile_id()
+                    && descriptor.input_clusters().contains(&request.cluster_id())
+            })
+            .filter_map(|descriptor| descriptor.endpoint().ok())
+            .collect()
+    } else {
+        vec![endpoint]
+    };
+
+    endpoints
+        .into_iter()
+        .filter_map(|endpoint| {
+            let source_endpoint = IndividualEndpoint::new(endpoint)?;
+            let asdu = zcl::respond(
+                device,
+                endpoint,
+                request.cluster_id(),
+                asdu,
+                broadcast || endpoint_broadcast,
+            )?;
+
+            Some(Reply {
+                source_endpoint,
+                cluster_id: request.cluster_id(),
+                asdu,
+            })
+        })
+        .collect()
+}
+
+/// Return the indication of a reply received by the coordinator.
+fn indication(
+    request: &DataRequest<Bytes>,
+    source: Device,
+    link_quality: u8,
+    reply: Reply,
+) -> Event {
+    let metadata = IndicationMetadata::new(
+        ReceivedDestination::Network {
+            address: NetworkAddress::new(COORDINATOR)
+                .expect("the coordinator address is a valid APSDE network address"),
+            endpoint: request.source_endpoint(),
+        },
+        Source::Network {
+            address: network_address(source),
+            endpoint: reply.source_endpoint,
+        },
+        request.profile_id(),
+        reply.cluster_id,
+        IndicationStatus::success(),
+        Security::NetworkKey,
+        link_quality,
+        (),
+    );
+
+    ApsdeEvent::DataIndication(DataIndication::new(metadata, reply.asdu)).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::time::Duration;
+
+    use bytes::Bytes;
+    use tokio::runtime::Builder;
+    use tokio::sync::mpsc::Receiver;
+    use tokio::time::Instant;
+    use zb_aps::TxOptions;
+    use zb_aps::apsde::{
+        ConfirmStatus, DataRequest, IndividualEndpoint, NetworkAddress, RequestDestination, Status,
+    };
+    use zb_core::security::Key;
+    use zb_core::short_id::Device;
+    use zb_core::types::{Type, Uint8};
+    use zb_core::{Application, Endpoint, IeeeAddress, Profile};
+    use zb_zdp::{AppFlags, Clusters, SimpleDescriptor};
+
+    use crate::sim::{Link, VirtualDevice, VirtualNetwork};
+    use crate::{
+        ApsdeEvent, Channel, ChannelMask, Counter, DeviceEvent, Driver, Event, Formation,
+        InterPanDestination, InterPanRequest, JoinPolicy, LinkKeyState, NetworkEvent,
+        NetworkParameters, TrustCenterPolicy,
+    };
+
+    const CAPACITY: NonZeroUsize = NonZeroUsize::new(16).expect("capacity is non-zero");
+    const COORDINATOR_IEEE_ADDRESS: IeeeAddress = IeeeAddress::new(0, 0, 0, 0, 0, 0, 0, 0xAA);
+    const DEVICE_IEEE_ADDRESS: IeeeAddress = IeeeAddress::new(0, 0, 0, 0, 0, 0, 0, 1);
+    const DEVICE_SHORT_ID: u16 = 0x1234;
+    const ENDPOINT: Endpoint = Endpoint::Application(Application::MIN);
+    const LATENCY: Duration = Duration::from_millis(50);
+    const LEVEL_CONTROL: u16 = 0x0008;
+    const CURRENT_LEVEL: u16 = 0x0000;
+    const PAN_ID: u16 = 0x1A62;
+    const TOUCHLINK: u16 = 0x1000;
+
+    fn device() -> VirtualDevice {
+        VirtualDevice::new(
+            DEVICE_IEEE_ADDRESS,
+            Device::new(DEVICE_SHORT_ID).expect("test short ID is valid"),
+        )
+        .with_endpoint(SimpleDescriptor::new(
+            ENDPOINT,
+            Profile::ZigbeeHomeAutomation,
+            0x0101,
+            AppFlags::empty(),
+            Clusters::from_slice(&[LEVEL_CONTROL]).expect("one cluster fits"),
+            Clusters::new(),
+        ))
+        .with_attribute(ENDPOINT, LEVEL_CONTROL, CURRENT_LEVEL, Uint8::new(0x80))
+    }
+
+    fn start(device: VirtualDevice) -> (super::SimulatedNcp, Receiver<Event>) {
+        VirtualNetwork::new(COORDINATOR_IEEE_ADDRESS, PAN_ID)
+            .with_device(device)
+            .start(CAPACITY)
+    }
+
+    fn request(
+        profile: Profile,
+        cluster_id: u16,
+        endpoint: Endpoint,
+        asdu: &[u8],
+    ) -> DataRequest<Bytes> {
+        DataRequest::new(
+            RequestDestination::Network {
+                address: NetworkAddress::new(DEVICE_SHORT_ID).expect("test address is valid"),
+                endpoint,
+            },
+            profile.as_u16(),
+            cluster_id,
+            IndividualEndpoint::new(ENDPOINT).expect("application endpoint is individual"),
+            Bytes::copy_from_slice(asdu),
+        )
+        .with_tx_options(TxOptions::ACKNOWLEDGED_TRANSMISSION)
+    }
+
+    /// Skip the network and device join events emitted at startup.
+    async fn skip_startup(events: &mut Receiver<Event>) {
+        for _ in 0..3 {
+            events.recv().await.expect("startup event must be emitted");
+        }
+    }
+
+    fn run<F>(test: F)
+    where
+        F: Future<Output = ()>,
+    {
+        Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .expect("runtime must be available")
+            .block_on(test);
+    }
+
+    #[test]
+    fn announces_network_and_joining_devices() {
+        run(async {
+            let (_ncp, mut events) = start(device());
+
+            assert!(matches!(
+                events.recv().await,
+                Some(Event::Network(NetworkEvent::Up))
+            ));
+            assert!(matches!(
+                events.recv().await,
+                Some(Event::Device(DeviceEvent::Joined(address)))
+                    if address.ieee_address() == DEVICE_IEEE_ADDRESS
+            ));
+            let Some(Event::Apsde(ApsdeEvent::DataIndication(indication))) = events.recv().await
+            else {
+                panic!("device announcement must follow the join");
+            };
+            assert_eq!(indication.metadata().cluster_id(), 0x0013);
+        });
+    }
+
+    #[test]
+    fn answers_attribute_reads_after_the_round_trip() {
+        run(async {
+            let (mut ncp, mut events) =
+                start(device().with_link(Link::new(200).with_latency(LATENCY)));
+            skip_startup(&mut events).await;
+            let sent = Instant::now();
+
+            ncp.transmit(
+                request(
+                    Profile::ZigbeeHomeAutomation,
+                    LEVEL_CONTROL,
+                    ENDPOINT,
+                    &[0x00, 0x07, 0x00, 0x00, 0x00],
+                ),
+                7,
+            )
+            .await
+            .expect("simulated NCP must accept the request");
+
+            let Some(Event::Apsde(ApsdeEvent::DataConfirm {
+                counter,
+                confirmation,
+            })) = events.recv().await
+            else {
+                panic!("acknowledged request must be confirmed");
+            };
+            assert_eq!(counter, 7);
+            assert!(confirmation.status().is_success());
+            assert_eq!(sent.elapsed(), LATENCY * 2);
+
+            let Some(Event::Apsde(ApsdeEvent::DataIndication(indication))) = events.recv().await
+            else {
+                panic!("device must answer the read");
+            };
+            assert_eq!(indication.metadata().link_quality(), 200);
+            assert_eq!(
+                indication.asdu().as_ref(),
+                [0x18, 0x07, 0x01, 0x00, 0x00, 0x00, 0x20, 0x80]
+            );
+        });
+    }
+
+    #[test]
+    fn applies_attribute_writes() {
+        run(async {
+            let (mut ncp, mut events) = start(device());
+            skip_startup(&mut events).await;
+
+            ncp.transmit(
+                request(
+                    Profile::ZigbeeHomeAutomation,
+                    LEVEL_CONTROL,
+                    ENDPOINT,
+                    &[0x00, 0x08, 0x02, 0x00, 0x00, 0x20, 0x10],
+                ),
+                8,
+            )
+            .await
+            .expect("simulated NCP must accept the request");
+            events.recv().await.expect("write must be confirmed");
+
+            let Some(Event::Apsde(ApsdeEvent::DataIndication(indication))) = events.recv().await
+            else {
+                panic!("device must answer the write");
+            };
+            assert_eq!(indication.asdu().as_ref(), [0x18, 0x08, 0x04, 0x00]);
+            assert_eq!(
+                ncp.devices()[0].attribute(ENDPOINT, LEVEL_CONTROL, CURRENT_LEVEL),
+                Some(&Type::Uint8(Uint8::new(0x10)))
+            );
+        });
+    }
+
+    #[test]
+    fn rejects_reporting_of_unknown_attributes() {
+        run(async {
+            let (mut ncp, mut events) = start(device());
+            skip_startup(&mut events).await;
+
+            ncp.transmit(
+                request(
+                    Profile::ZigbeeHomeAutomation,
+                    LEVEL_CONTROL,
+                    ENDPOINT,
+                    &[
+                        0x00, 0x0B, 0x06, 0x00, 0x00, 0x00, 0x20, 0x01, 0x00, 0x10, 0x0E, 0x01,
+                        0x00, 0x11, 0x00, 0x20, 0x01, 0x00, 0x10, 0x0E, 0x01,
+                    ],
+                ),
+                8,
+            )
+            .await
+            .expect("simulated NCP must accept the request");
+            events.recv().await.expect("request must be confirmed");
+
+            let Some(Event::Apsde(ApsdeEvent::DataIndication(indication))) = events.recv().await
+            else {
+                panic!("device must answer Configure Reporting");
+            };
+            assert_eq!(
+                indication.asdu().as_ref(),
+                [0x18, 0x0B, 0x07, 0x86, 0x00, 0x11, 0x00]
+            );
+        });
+    }
+
+    #[test]
+    fn answers_inter_pan_frames_on_the_device_channel() {
+        run(async {
+            let channel = Channel::new(15).expect("channel 15 is valid");
+            let (mut ncp, mut events) = start(
+                device()
+                    .with_radio_channel(channel)
+                    .with_link(Link::new(180).with_latency(LATENCY))
+                    .with_inter_pan_responder(TOUCHLINK, |asdu| {
+                        (asdu.first() == Some(&0x11)).then(|| Bytes::from_static(&[0x19, 0x00]))
+                    }),
+            );
+            skip_startup(&mut events).await;
+            let scan = |channel| {
+                InterPanRequest::new(
+                    channel,
+                    InterPanDestination::Broadcast,
+                    Profile::TouchLink.as_u16(),
+                    TOUCHLINK,
+                    Bytes::from_static(&[0x11, 0x00]),
+                )
+            };
+
+            ncp.transmit_inter_pan(scan(Channel::MIN))
+                .await
+                .expect("simulated NCP must transmit inter-PAN frames");
+            ncp.transmit_inter_pan(scan(channel))
+                .await
+                .expect("simulated NCP must transmit inter-PAN frames");
+            assert_eq!(ncp.inter_pan_channel(), Some(channel));
+
+            let Some(Event::InterPan(indication)) = events.recv().await else {
+                panic!("device must answer on its channel");
+            };
+            assert_eq!(indication.source(), DEVICE_IEEE_ADDRESS);
+            assert_eq!(indication.source_pan_id(), PAN_ID);
+            assert_eq!(indication.link_quality(), 180);
+            assert_eq!(indication.asdu().as_ref(), [0x19, 0x00]);
+            assert!(events.try_recv().is_err());
+
+            ncp.end_inter_pan()
+                .await
+                .expect("simulated NCP must return to the network channel");
+            assert_eq!(ncp.inter_pan_channel(), None);
+        });
+    }
+
+    #[test]
+    fn answers_zdp_discovery() {
+        run(async {
+            let (mut ncp, mut events) = start(device());
+            skip_startup(&mut events).await;
+
+            ncp.transmit(
+                request(
+                    Profile::Network,
+                    0x0005,
+                    Endpoint::Data,
+                    &[0x2A, 0x34, 0x12],
+                ),
+                9,
+            )
+            .await
+            .expect("simulated NCP must accept the request");
+            events.recv().await.expect("request must be confirmed");
+
+            let Some(Event::Apsde(ApsdeEvent::DataIndication(indication))) = events.recv().await
+            else {
+                panic!("device must answer Active_EP_req");
+            };
+            assert_eq!(indication.metadata().cluster_id(), 0x8005);
+            assert_eq!(
+                indication.asdu().as_ref(),
+                [0x2A, 0x00, 0x34, 0x12, 0x01, 0x01]
+            );
+        });
+    }
+
+    #[test]
+    fn reports_lost_frames_as_unacknowledged() {
+        run(async {
+            let (mut ncp, mut events) = start(device().with_link(Link::new(0).with_loss(100)));
+            skip_startup(&mut events).await;
+
+            ncp.transmit(
+                request(
+                    Profile::ZigbeeHomeAutomation,
+                    LEVEL_CONTROL,
+                    ENDPOINT,
+                    &[0x00, 0x01, 0x00, 0x00, 0x00],
+                ),
+                10,
+            )
+            .await
+            .expect("simulated NCP must accept the request");
+
+            let Some(Event::Apsde(ApsdeEvent::DataConfirm { confirmation, .. })) =
+                events.recv().await
+            else {
+                panic!("lost request must be confirmed");
+            };
+            assert_eq!(
+                confirmation.status(),
+                ConfirmStatus::Aps(Status::NoAcknowledgement)
+            );
+            drop(ncp);
+            assert!(events.recv().await.is_none());
+        });
+    }
+
+    #[test]
+    fn counts_transmissions_and_route_discoveries() {
+        run(async {
+            let (mut ncp, mut events) = start(device());
+            skip_startup(&mut events).await;
+            let read = request(
+                Profile::ZigbeeHomeAutomation,
+                LEVEL_CONTROL,
+                ENDPOINT,
+                &[0x00, 0x01, 0x00, 0x00, 0x00],
+            );
+
+            ncp.transmit(read, 11)
+                .await
+                .expect("simulated NCP must accept the request");
+            ncp.route_request(0)
+                .await
+                .expect("simulated NCP must send the route request");
+
+            let counters = ncp
+                .get_counters()
+                .await
+                .expect("counters must be available");
+            assert_eq!(counters.get(Counter::ApsTxUnicastSuccess), Some(1));
+            assert_eq!(counters.get(Counter::ApsRxUnicast), Some(1));
+            assert_eq!(counters.get(Counter::RouteDiscoveryInitiated), Some(1));
+            assert_eq!(counters.get(Counter::ApsTxUnicastFailure), None);
+        });
+    }
+
+    #[test]
+    fn leaves_and_forms_networks() {
+        run(async {
+            let (mut ncp, mut events) = start(device());
+            skip_startup(&mut events).await;
+
+            ncp.leave_network()
+                .await
+                .expect("simulated NCP must leave its network");
+            assert!(matches!(
+                events.recv().await,
+                Some(Event::Network(NetworkEvent::Down))
+            ));
+            assert!(ncp.get_network_parameters().await.is_err());
+
+            let channel = Channel::new(20).expect("channel 20 is valid");
+            let formation = Formation::new(
+                0x2B00,
+                DEVICE_IEEE_ADDRESS,
+                [channel, Channel::MAX].into_iter().collect(),
+                Key::new([0x11; Key::SIZE]),
+            );
+            ncp.form_network(formation)
+                .await
+                .expect("simulated NCP must form a network");
+            assert!(matches!(
+                events.recv().await,
+                Some(Event::Network(NetworkEvent::Up))
+            ));
+            assert_eq!(
+                ncp.get_network_parameters()
+                    .await
+                    .expect("network must be up"),
+                NetworkParameters::new(0x2B00, DEVICE_IEEE_ADDRESS, channel)
+            );
+            assert!(ncp.form_network(formation).await.is_err());
+
+            let policy = TrustCenterPolicy::new(JoinPolicy::InstallCodeOnly);
+            ncp.set_trust_center_policy(policy)
+                .await
+                .expect("simulated NCP must accept the policy");
+            assert_eq!(ncp.trust_center_policy(), policy);
+        });
+    }
+
+    #[test]
+    fn restores_backed_up_networks() {
+        run(async {
+            let (mut ncp, mut events) = start(device());
+            skip_startup(&mut events).await;
+            let link_key = Key::new([0x22; Key::SIZE]);
+            ncp.set_link_key(DEVICE_IEEE_ADDRESS, link_key)
+                .await
+                .expect("simulated NCP must store the link key");
+            ncp.transmit(
+                request(
+                    Profile::ZigbeeHomeAutomation,
+                    LEVEL_CONTROL,
+                    ENDPOINT,
+                    &[0x00, 0x01, 0x00, 0x00, 0x00],
+                ),
+                10,
+            )
+            .await
+            .expect("simulated NCP must accept the request");
+
+            let backup = ncp.backup_network().await.expect("network must be up");
+            assert_eq!(backup.ieee_address(), COORDINATOR_IEEE_ADDRESS);
+            assert_eq!(backup.network_key().frame_counter(), 1);
+            let [device] = backup.devices() else {
+                panic!("backup must contain the device");
+            };
+            assert_eq!(device.ieee_address(), DEVICE_IEEE_ADDRESS);
+            assert_eq!(device.link_key().map(LinkKeyState::key), Some(&link_key));
+
+            let channel_mask = ChannelMask::new(0x0000_8800).expect("channels 11 and 15 are valid");
+            let backup = backup.with_channel_mask(channel_mask).with_nwk_update_id(3);
+            assert!(ncp.restore_network(backup.clone()).await.is_err());
+            ncp.leave_network()
+                .await
+                .expect("simulated NCP must leave its network");
+            ncp.restore_network(backup.clone())
+                .await
+                .expect("simulated NCP must restore the network");
+            assert_eq!(
+                ncp.get_network_parameters()
+                    .await
+                    .expect("network must be up"),
+                backup.parameters()
+            );
+            assert_eq!(
+                ncp.backup_network().await.expect("network must be up"),
+                backup
+            );
+        });
+    }
+
+    #[test]
+    fn forgets_devices_after_they_leave() {
+        run(async {
+            let leave_time = Duration::from_secs(10);
+            let (mut ncp, mut events) = start(device().leaves_after(leave_time));
+            skip_startup(&mut events).await;
+
+            assert_eq!(
+                ncp.ieee_address_to_short_id(DEVICE_IEEE_ADDRESS)
+                    .await
+                    .expect("device must be known before leaving")
+                    .as_u16(),
+                DEVICE_SHORT_ID
+            );
+            assert!(matches!(
+                events.recv().await,
+                Some(Event::Device(DeviceEvent::Left(_)))
+            ));
+            assert!(
+                ncp.ieee_address_to_short_id(DEVICE_IEEE_ADDRESS)
+                    .await
+                    .is_err()
+            );
+        });
+    }
+}
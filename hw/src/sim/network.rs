@@ -0,0 +1,166 @@
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use le_stream::ToLeStream;
+use tokio::sync::mpsc::{Receiver, channel};
+use tokio::time::Instant;
+use zb_aps::apsde::{
+    DataIndication, IndicationMetadata, IndicationStatus, IndividualEndpoint, ReceivedDestination,
+    Security, Source,
+};
+use zb_core::security::Key;
+use zb_core::short_id::Broadcast;
+use zb_core::{Endpoint, IeeeAddress, Profile};
+use zb_zdp::{DeviceAnnce, Frame, SimpleDescriptor};
+
+use super::ncp::{network_address, schedule};
+use super::rng::{DEFAULT_SEED, Rng};
+use super::{SimulatedNcp, VirtualDevice};
+use crate::{ApsdeEvent, Channel, DeviceEvent, Event, NetworkEvent, NetworkParameters};
+
+/// A virtual Zigbee network of scripted devices.
+///
+/// The network describes the simulated coordinator and its devices. [`VirtualNetwork::start`]
+/// turns it into a [`SimulatedNcp`] driver and the receiver of the hardware events it emits.
+#[derive(Clone, Debug)]
+pub struct VirtualNetwork {
+    ieee_address: IeeeAddress,
+    pan_id: u16,
+    channel: Channel,
+    network_key: Key,
+    endpoints: Vec<SimpleDescriptor>,
+    devices: Vec<VirtualDevice>,
+    seed: u64,
+}
+
+impl VirtualNetwork {
+    /// Create an empty network coordinated by the given IEEE address.
+    ///
+    /// The network operates on channel 11 with an all-zero network key and uses the coordinator's
+    /// IEEE address as its extended PAN ID.
+    #[must_use]
+    pub const fn new(ieee_address: IeeeAddress, pan_id: u16) -> Self {
+        Self {
+            ieee_address,
+            pan_id,
+            channel: Channel::MIN,
+            network_key: Key::new([0; Key::SIZE]),
+            endpoints: Vec::new(),
+            devices: Vec::new(),
+            seed: DEFAULT_SEED,
+        }
+    }
+
+    /// Set the radio channel of the network.
+    #[must_use]
+    pub const fn with_channel(mut self, channel: Channel) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    /// Set the network key.
+    #[must_use]
+    pub const fn with_network_key(mut self, network_key: Key) -> Self {
+        self.network_key = network_key;
+        self
+    }
+
+    /// Add a local application endpoint of the simulated NCP.
+    #[must_use]
+    pub fn with_endpoint(mut self, descriptor: SimpleDescriptor) -> Self {
+        self.endpoints.push(descriptor);
+        self
+    }
+
+    /// Add a virtual device.
+    #[must_use]
+    pub fn with_device(mut self, device: VirtualDevice) -> Self {
+        self.devices.push(device);
+        self
+    }
+
+    /// Seed the generator deciding which frames lossy links drop.
+    ///
+    /// Simulations with the same seed and the same sequence of transmissions lose the same frames.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Start the simulation.
+    ///
+    /// Returns the simulated NCP and the receiver of its hardware events. The network reports
+    /// [`NetworkEvent::Up`] immediately. Each device reports [`DeviceEvent::Joined`] followed by a
+    /// `Device_annce` indication at its join time, and [`DeviceEvent::Left`] at its leave time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside a Tokio runtime with the time driver enabled.
+    #[must_use]
+    pub fn start(self, event_capacity: NonZeroUsize) -> (SimulatedNcp, Receiver<Event>) {
+        let (events, receiver) = channel(event_capacity.get());
+        let started = Instant::now();
+        let mut timeline = vec![(Duration::ZERO, NetworkEvent::Up.into())];
+
+        for device in &self.devices {
+            timeline.push((
+                device.join_time(),
+                DeviceEvent::Joined(device.address()).into(),
+            ));
+            timeline.push((device.join_time(), announcement(device)));
+
+            if let Some(leave_time) = device.leave_time() {
+                timeline.push((leave_time, DeviceEvent::Left(device.address()).into()));
+            }
+        }
+
+        schedule(&events, started, timeline);
+
+        let ncp = SimulatedNcp::new(
+            self.ieee_address,
+            NetworkParameters::new(self.pan_id, self.ieee_address, self.channel),
+            self.network_key,
+            self.endpoints.into_boxed_slice(),
+            self.devices,
+            events,
+            started,
+            Rng::new(self.seed),
+        );
+
+        (ncp, receiver)
+    }
+}
+
+/// Return the `Device_annce` broadcast of a joining device.
+fn announcement(device: &VirtualDevice) -> Event {
+    let address = device.address();
+    let zdo = IndividualEndpoint::new(Endpoint::Data).expect("ZDO endpoint is individual");
+    let metadata = IndicationMetadata::new(
+        ReceivedDestination::Broadcast {
+            address: Broadcast::RxOnWhenIdle,
+            endpoint: Endpoint::Data,
+        },
+        Source::Network {
+            address: network_address(address.short_id()),
+            endpoint: zdo,
+        },
+        Profile::Network.as_u16(),
+        DeviceAnnce::ID,
+        IndicationStatus::success(),
+        Security::NetworkKey,
+        device.link().link_quality(),
+        (),
+    );
+    let annce = DeviceAnnce::new(
+        address.short_id().as_u16(),
+        address.ieee_address(),
+        device.capabilities(),
+    );
+
+    ApsdeEvent::DataIndication(DataIndication::new(
+        metadata,
+        Frame::new(0, annce).to_le_stream().collect(),
+    ))
+    .into()
+}
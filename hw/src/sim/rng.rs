@@ -0,0 +1,62 @@
+//! Deterministic pseudo-random numbers for reproducible frame loss.
+
+/// Seed used when the virtual network does not configure one.
+pub(super) const DEFAULT_SEED: u64 = 0x5EED_0F5A_1775_A175;
+
+/// A xorshift64* generator.
+///
+/// Simulations must be reproducible, so the generator is seeded explicitly instead of drawing
+/// from the operating system.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) struct Rng(u64);
+
+impl Rng {
+    /// Create a generator from a seed.
+    ///
+    /// A zero seed would lock xorshift at zero, so it is replaced by [`DEFAULT_SEED`].
+    pub(super) const fn new(seed: u64) -> Self {
+        if seed == 0 {
+            Self(DEFAULT_SEED)
+        } else {
+            Self(seed)
+        }
+    }
+
+    /// Return whether a frame on a link with the given loss percentage is lost.
+    pub(super) const fn loses(&mut self, loss_percent: u8) -> bool {
+        // Skip the draw for perfect links so adding a lossless device does not change the outcome
+        // of lossy ones.
+        loss_percent > 0 && self.next() % 100 < loss_percent as u64
+    }
+
+    const fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn never_loses_on_perfect_links_and_always_on_dead_ones() {
+        let mut rng = Rng::new(1);
+
+        assert!((0..1000).all(|_| !rng.loses(0)));
+        assert!((0..1000).all(|_| rng.loses(100)));
+    }
+
+    #[test]
+    fn loss_rate_approximates_the_percentage() {
+        let mut rng = Rng::new(42);
+        let lost = (0..10_000).filter(|_| rng.loses(25)).count();
+
+        assert!(
+            (2_300..2_700).contains(&lost),
+            "lost {lost} of 10000 frames"
+        );
+    }
+}
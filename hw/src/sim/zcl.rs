@@ -0,0 +1,282 @@
+//! Minimal ZCL server behavior of virtual devices.
+//!
+//! Virtual devices answer global attribute reads and writes from their attribute table, accept
+//! reporting configurations for attributes they hold, and acknowledge every other client command
+//! with a default response. Frames are encoded by hand so
+//! the hardware crate does not depend on the ZCL crate.
+
+use std::iter::once;
+use std::mem::discriminant;
+
+use bytes::Bytes;
+use le_stream::{FromLeStream, ToLeStream};
+use zb_core::Endpoint;
+use zb_core::types::Type;
+
+use super::VirtualDevice;
+
+const FRAME_TYPE_MASK: u8 = 0b0000_0011;
+const GLOBAL: u8 = 0b0000_0000;
+const MANUFACTURER_SPECIFIC: u8 = 0b0000_0100;
+const SERVER_TO_CLIENT: u8 = 0b0000_1000;
+const DISABLE_DEFAULT_RESPONSE: u8 = 0b0001_0000;
+
+const REPORTED: u8 = 0x00;
+const RECEIVED: u8 = 0x01;
+
+const READ_ATTRIBUTES: u8 = 0x00;
+const READ_ATTRIBUTES_RESPONSE: u8 = 0x01;
+const WRITE_ATTRIBUTES: u8 = 0x02;
+const WRITE_ATTRIBUTES_RESPONSE: u8 = 0x04;
+const CONFIGURE_REPORTING: u8 = 0x06;
+const CONFIGURE_REPORTING_RESPONSE: u8 = 0x07;
+const DEFAULT_RESPONSE: u8 = 0x0B;
+
+const SUCCESS: u8 = 0x00;
+const MALFORMED_COMMAND: u8 = 0x80;
+const UNSUPPORTED_COMMAND: u8 = 0x81;
+const UNSUPPORTED_ATTRIBUTE: u8 = 0x86;
+const INVALID_DATA_TYPE: u8 = 0x8D;
+const UNSUPPORTED_CLUSTER: u8 = 0xC3;
+
+/// Header fields of a received ZCL frame.
+struct Header {
+    control: u8,
+    manufacturer_code: Option<u16>,
+    seq: u8,
+    command_id: u8,
+}
+
+impl Header {
+    fn parse<T>(bytes: &mut T) -> Option<Self>
+    where
+        T: Iterator<Item = u8>,
+    {
+        let control = bytes.next()?;
+        let manufacturer_code = if control & MANUFACTURER_SPECIFIC == 0 {
+            None
+        } else {
+            Some(u16::from_le_stream(&mut *bytes)?)
+        };
+
+        Some(Self {
+            control,
+            manufacturer_code,
+            seq: bytes.next()?,
+            command_id: bytes.next()?,
+        })
+    }
+
+    const fn is_global(&self) -> bool {
+        self.control & FRAME_TYPE_MASK == GLOBAL
+    }
+
+    const fn is_server_to_client(&self) -> bool {
+        self.control & SERVER_TO_CLIENT != 0
+    }
+
+    const fn disables_default_response(&self) -> bool {
+        self.control & DISABLE_DEFAULT_RESPONSE != 0
+    }
+
+    /// Start a global server-to-client response to this frame.
+    fn response(&self, command_id: u8) -> Vec<u8> {
+        let mut control = GLOBAL | SERVER_TO_CLIENT | DISABLE_DEFAULT_RESPONSE;
+        let mut frame = Vec::new();
+
+        if self.manufacturer_code.is_some() {
+            control |= MANUFACTURER_SPECIFIC;
+        }
+
+        frame.push(control);
+        frame.extend(self.manufacturer_code.to_le_stream());
+        frame.push(self.seq);
+        frame.push(command_id);
+        frame
+    }
+
+    /// Return the default response with the given status, if one is required.
+    ///
+    /// Errors are always reported, while success is reported only if the client did not disable
+    /// the default response.
+    fn default_response(&self, status: u8) -> Option<Bytes> {
+        if status == SUCCESS && self.disables_default_response() {
+            return None;
+        }
+
+        let mut frame = self.response(DEFAULT_RESPONSE);
+        frame.push(self.command_id);
+        frame.push(status);
+        Some(frame.into())
+    }
+}
+
+/// Return the device's response to a ZCL frame received on one of its endpoints.
+///
+/// Frames received as a broadcast or groupcast never trigger a default response.
+pub(super) fn respond(
+    device: &mut VirtualDevice,
+    endpoint: Endpoint,
+    cluster_id: u16,
+    asdu: &[u8],
+    broadcast: bool,
+) -> Option<Bytes> {
+    let mut bytes = asdu.iter().copied();
+    let header = Header::parse(&mut bytes)?;
+    let default_response = |status| {
+        if broadcast {
+            None
+        } else {
+            header.default_response(status)
+        }
+    };
+
+    if header.is_server_to_client() {
+        return None;
+    }
+
+    if !device
+        .descriptor(endpoint)?
+        .input_clusters()
+        .contains(&cluster_id)
+    {
+        return default_response(UNSUPPORTED_CLUSTER);
+    }
+
+    if !header.is_global() {
+        return default_response(SUCCESS);
+    }
+
+    match header.command_id {
+        READ_ATTRIBUTES => Some(read_attributes(
+            device, endpoint, cluster_id, &header, bytes,
+        )),
+        WRITE_ATTRIBUTES => write_attributes(device, endpoint, cluster_id, &header, bytes)
+            .or_else(|| default_response(MALFORMED_COMMAND)),
+        CONFIGURE_REPORTING => configure_reporting(device, endpoint, cluster_id, &header, bytes)
+            .or_else(|| default_response(MALFORMED_COMMAND)),
+        _ => default_response(UNSUPPORTED_COMMAND),
+    }
+}
+
+fn read_attributes<T>(
+    device: &VirtualDevice,
+    endpoint: Endpoint,
+    cluster_id: u16,
+    header: &Header,
+    mut attribute_ids: T,
+) -> Bytes
+where
+    T: Iterator<Item = u8>,
+{
+    let mut frame = header.response(READ_ATTRIBUTES_RESPONSE);
+
+    while let Some(attribute_id) = u16::from_le_stream(&mut attribute_ids) {
+        frame.extend(attribute_id.to_le_stream());
+
+        if let Some(value) = device.attribute(endpoint, cluster_id, attribute_id) {
+            frame.push(SUCCESS);
+            frame.extend(value.clone().to_le_stream());
+        } else {
+            frame.push(UNSUPPORTED_ATTRIBUTE);
+        }
+    }
+
+    frame.into()
+}
+
+fn write_attributes<T>(
+    device: &mut VirtualDevice,
+    endpoint: Endpoint,
+    cluster_id: u16,
+    header: &Header,
+    mut records: T,
+) -> Option<Bytes>
+where
+    T: Iterator<Item = u8>,
+{
+    let mut failures = Vec::new();
+
+    while let Some(attribute_id) = u16::from_le_stream(&mut records) {
+        let value = Type::from_le_stream(&mut records)?;
+
+        match device.attribute_mut(endpoint, cluster_id, attribute_id) {
+            Some(current) if discriminant(current) == discriminant(&value) => *current = value,
+            Some(_) => failures.push((INVALID_DATA_TYPE, attribute_id)),
+            None => failures.push((UNSUPPORTED_ATTRIBUTE, attribute_id)),
+        }
+    }
+
+    let mut frame = header.response(WRITE_ATTRIBUTES_RESPONSE);
+
+    if failures.is_empty() {
+        frame.push(SUCCESS);
+    } else {
+        for (status, attribute_id) in failures {
+            frame.push(status);
+            frame.extend(attribute_id.to_le_stream());
+        }
+    }
+
+    Some(frame.into())
+}
+
+/// Validate attribute reporting configuration records.
+///
+/// Virtual devices never send reports, so accepted configurations are not stored.
+fn configure_reporting<T>(
+    device: &VirtualDevice,
+    endpoint: Endpoint,
+    cluster_id: u16,
+    header: &Header,
+    mut records: T,
+) -> Option<Bytes>
+where
+    T: Iterator<Item = u8>,
+{
+    let mut failures = Vec::new();
+
+    while let Some(direction) = records.next() {
+        let attribute_id = u16::from_le_stream(&mut records)?;
+
+        match direction {
+            REPORTED => {
+                let type_id = records.next()?;
+                let _minimum_interval = u16::from_le_stream(&mut records)?;
+                let _maximum_interval = u16::from_le_stream(&mut records)?;
+                let change = Type::from_le_stream(&mut once(type_id).chain(&mut records))?;
+
+                match device.attribute(endpoint, cluster_id, attribute_id) {
+                    Some(current) if discriminant(current) == discriminant(&change) => {}
+                    Some(_) => failures.push((INVALID_DATA_TYPE, direction, attribute_id)),
+                    None => failures.push((UNSUPPORTED_ATTRIBUTE, direction, attribute_id)),
+                }
+            }
+            RECEIVED => {
+                let _timeout = u16::from_le_stream(&mut records)?;
+
+                if device
+                    .attribute(endpoint, cluster_id, attribute_id)
+                    .is_none()
+                {
+                    failures.push((UNSUPPORTED_ATTRIBUTE, direction, attribute_id));
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    let mut frame = header.response(CONFIGURE_REPORTING_RESPONSE);
+
+    if failures.is_empty() {
+        frame.push(SUCCESS);
+    } else {
+        for (status, direction, attribute_id) in failures {
+            frame.push(status);
+            frame.push(direction);
+            frame.extend(attribute_id.to_le_stream());
+        }
+    }
+
+    Some(frame.into())
+}
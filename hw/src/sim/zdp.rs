@@ -0,0 +1,149 @@
+//! Built-in ZDP server behavior of virtual devices.
+
+use bytes::Bytes;
+use le_stream::ToLeStream;
+use zb_core::node::{Descriptor, DeviceType, Flags, LogicalType, ServerMask};
+use zb_core::{ByteSizedVec, Endpoint};
+use zb_zdp::{
+    ActiveEpRsp, Command, DeviceAndServiceDiscovery, Frame, IeeeAddrRsp, IeeeAddrRspResponse,
+    NodeDescRsp, NwkAddrRsp, NwkAddrRspResponse, SimpleDescRsp, Status,
+};
+
+use super::VirtualDevice;
+
+const MAXIMUM_BUFFER_SIZE: u8 = 82;
+const MAXIMUM_TRANSFER_SIZE: u16 = 82;
+
+/// Return the cluster ID and ASDU of the device's response to a ZDP frame.
+///
+/// Requests received as a broadcast are answered only if they concern the device.
+pub(super) fn respond(
+    device: &VirtualDevice,
+    cluster_id: u16,
+    asdu: &[u8],
+    broadcast: bool,
+) -> Option<(u16, Bytes)> {
+    let (seq, request) = Frame::parse_with_cluster_id(cluster_id, asdu.iter().copied())
+        .ok()
+        .flatten()?
+        .into_parts();
+    let response = device.zdp_responder(cluster_id).map_or_else(
+        || built_in(device, &request, broadcast),
+        |responder| responder(&request),
+    )?;
+
+    Some((
+        response.cluster_id(),
+        Frame::new(seq, response).to_le_stream().collect(),
+    ))
+}
+
+fn built_in(device: &VirtualDevice, request: &Command, broadcast: bool) -> Option<Command> {
+    let Command::DeviceAndServiceDiscovery(request) = request else {
+        return None;
+    };
+    let address = device.address();
+    let short_id = address.short_id().as_u16();
+    let concerns = |nwk_addr_of_interest: u16| {
+        if nwk_addr_of_interest == short_id {
+            Some(Ok(()))
+        } else if broadcast {
+            None
+        } else {
+            Some(Err(Status::DeviceNotFound))
+        }
+    };
+
+    match request {
+        DeviceAndServiceDiscovery::NwkAddrReq(request) => {
+            (request.ieee_addr() == address.ieee_address()).then(|| {
+                NwkAddrRsp::new(Ok(NwkAddrRspResponse::Single {
+                    ieee_addr_remote_dev: address.ieee_address(),
+                    nwk_addr_remote_dev: short_id,
+                }))
+                .into()
+            })
+        }
+        DeviceAndServiceDiscovery::IeeeAddrReq(request) => concerns(request.nwk_addr_of_interest())
+            .map(|result| {
+                IeeeAddrRsp::new(result.map(|()| IeeeAddrRspResponse::Single {
+                    ieee_addr_remote_dev: address.ieee_address(),
+                    nwk_addr_remote_dev: short_id,
+                }))
+                .into()
+            }),
+        DeviceAndServiceDiscovery::ActiveEpReq(request) => concerns(request.nwk_addr_of_interest())
+            .map(|result| {
+                ActiveEpRsp::new(
+                    request.nwk_addr_of_interest(),
+                    result.and_then(|()| active_endpoints(device)),
+                )
+                .into()
+            }),
+        DeviceAndServiceDiscovery::NodeDescReq(request) => {
+            concerns(request.nwk_addr()).map(|result| {
+                NodeDescRsp::new(
+                    request.nwk_addr(),
+                    result.map(|()| node_descriptor(device)),
+                    Vec::new(),
+                )
+                .into()
+            })
+        }
+        DeviceAndServiceDiscovery::SimpleDescReq(request) => {
+            let nwk_addr_of_interest = request.nwk_address_of_interest();
+
+            concerns(nwk_addr_of_interest).map(|result| {
+                SimpleDescRsp::new(
+                    nwk_addr_of_interest,
+                    result.and_then(|()| {
+                        request
+                            .endpoint()
+                            .ok()
+                            .and_then(|endpoint| device.descriptor(endpoint))
+                            .cloned()
+                            .ok_or(Status::InvalidEndpoint)
+                    }),
+                )
+                .into()
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Describe the device by its MAC capabilities.
+fn node_descriptor(device: &VirtualDevice) -> Descriptor {
+    let capabilities = device.capabilities();
+    let mut flags = Flags::default();
+    flags.set_logical_type(match capabilities.device_type() {
+        DeviceType::FullFunctionDevice => LogicalType::Router,
+        DeviceType::ReducedFunctionDevice => LogicalType::EndDevice,
+    });
+
+    Descriptor::new(
+        flags,
+        capabilities,
+        0,
+        MAXIMUM_BUFFER_SIZE,
+        MAXIMUM_TRANSFER_SIZE,
+        ServerMask::empty(),
+        MAXIMUM_TRANSFER_SIZE,
+    )
+}
+
+fn active_endpoints(device: &VirtualDevice) -> Result<ByteSizedVec<Endpoint>, Status> {
+    let mut endpoints = ByteSizedVec::new();
+
+    for endpoint in device
+        .endpoints()
+        .iter()
+        .filter_map(|descriptor| descriptor.endpoint().ok())
+    {
+        endpoints
+            .push(endpoint)
+            .map_err(|_| Status::InsufficientSpace)?;
+    }
+
+    Ok(endpoints)
+}
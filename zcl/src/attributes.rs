@@ -5,10 +5,17 @@ pub use self::analog::Analog;
 pub use self::discrete::Discrete;
 pub use self::errors::{InvalidType, ParseAttributeError};
 use crate::alarms::Reportable as AlarmsAttributes;
+use crate::analog_input::Reportable as AnalogInputAttributes;
+use crate::analog_output::Reportable as AnalogOutputAttributes;
+use crate::analog_value::Reportable as AnalogValueAttributes;
 use crate::ballast_configuration::Reportable as BallastConfigurationAttributes;
 use crate::basic::Reportable as BasicAttributes;
+use crate::binary_input::Reportable as BinaryInputAttributes;
+use crate::binary_output::Reportable as BinaryOutputAttributes;
+use crate::binary_value::Reportable as BinaryValueAttributes;
 use crate::color_control::Reportable as ColorControlAttributes;
 use crate::device_temperature_configuration::Reportable as DeviceTemperatureConfigurationAttributes;
+use crate::diagnostics::Reportable as DiagnosticsAttributes;
 use crate::global::configure_reporting;
 use crate::global::write_attributes::Record;
 use crate::groups::Reportable as GroupsAttributes;
@@ -17,8 +24,12 @@ use crate::identify::Reportable as IdentifyAttributes;
 use crate::illuminance_level_sensing::Reportable as IlluminanceLevelSensingAttributes;
 use crate::illuminance_measurement::Reportable as IlluminanceMeasurementAttributes;
 use crate::level::Reportable as LevelAttributes;
+use crate::multistate_input::Reportable as MultistateInputAttributes;
+use crate::multistate_output::Reportable as MultistateOutputAttributes;
+use crate::multistate_value::Reportable as MultistateValueAttributes;
 use crate::occupancy_sensing::Reportable as OccupancySensingAttributes;
 use crate::on_off::Reportable as OnOffAttributes;
+use crate::poll_control::Reportable as PollControlAttributes;
 use crate::power_configuration::Reportable as PowerConfigurationAttributes;
 use crate::scenes::Reportable as ScenesAttributes;
 use crate::time::Reportable as TimeAttributes;
@@ -82,6 +93,26 @@ pub enum AttributeReport {
     Alarms(AlarmsAttributes),
     /// Reportable attributes of the Time cluster.
     Time(TimeAttributes),
+    /// Reportable attributes of the Analog Input cluster.
+    AnalogInput(AnalogInputAttributes),
+    /// Reportable attributes of the Analog Output cluster.
+    AnalogOutput(AnalogOutputAttributes),
+    /// Reportable attributes of the Analog Value cluster.
+    AnalogValue(AnalogValueAttributes),
+    /// Reportable attributes of the Binary Input cluster.
+    BinaryInput(BinaryInputAttributes),
+    /// Reportable attributes of the Binary Output cluster.
+    BinaryOutput(BinaryOutputAttributes),
+    /// Reportable attributes of the Binary Value cluster.
+    BinaryValue(BinaryValueAttributes),
+    /// Reportable attributes of the Multistate Input cluster.
+    MultistateInput(MultistateInputAttributes),
+    /// Reportable attributes of the Multistate Output cluster.
+    MultistateOutput(MultistateOutputAttributes),
+    /// Reportable attributes of the Multistate Value cluster.
+    MultistateValue(MultistateValueAttributes),
+    /// Reportable attributes of the Poll Control cluster.
+    PollControl(PollControlAttributes),
     /// Reportable attributes of the Illuminance Measurement cluster.
     IlluminanceMeasurement(IlluminanceMeasurementAttributes),
     /// Reportable attributes of the Illuminance Level Sensing cluster.
@@ -94,6 +125,8 @@ pub enum AttributeReport {
     ColorControl(ColorControlAttributes),
     /// Reportable attributes of the IAS Zone cluster.
     IasZone(IasZoneAttributes),
+    /// Reportable attributes of the Diagnostics cluster.
+    Diagnostics(DiagnosticsAttributes),
 }
 
 impl AttributeReport {
@@ -132,6 +165,36 @@ impl AttributeReport {
             <LevelAttributes as ClusterSpecific>::ID => parse_cluster!(LevelAttributes, Level),
             <AlarmsAttributes as ClusterSpecific>::ID => parse_cluster!(AlarmsAttributes, Alarms),
             <TimeAttributes as ClusterSpecific>::ID => parse_cluster!(TimeAttributes, Time),
+            <AnalogInputAttributes as ClusterSpecific>::ID => {
+                parse_cluster!(AnalogInputAttributes, AnalogInput)
+            }
+            <AnalogOutputAttributes as ClusterSpecific>::ID => {
+                parse_cluster!(AnalogOutputAttributes, AnalogOutput)
+            }
+            <AnalogValueAttributes as ClusterSpecific>::ID => {
+                parse_cluster!(AnalogValueAttributes, AnalogValue)
+            }
+            <BinaryInputAttributes as ClusterSpecific>::ID => {
+                parse_cluster!(BinaryInputAttributes, BinaryInput)
+            }
+            <BinaryOutputAttributes as ClusterSpecific>::ID => {
+                parse_cluster!(BinaryOutputAttributes, BinaryOutput)
+            }
+            <BinaryValueAttributes as ClusterSpecific>::ID => {
+                parse_cluster!(BinaryValueAttributes, BinaryValue)
+            }
+            <MultistateInputAttributes as ClusterSpecific>::ID => {
+                parse_cluster!(MultistateInputAttributes, MultistateInput)
+            }
+            <MultistateOutputAttributes as ClusterSpecific>::ID => {
+                parse_cluster!(MultistateOutputAttributes, MultistateOutput)
+            }
+            <MultistateValueAttributes as ClusterSpecific>::ID => {
+                parse_cluster!(MultistateValueAttributes, MultistateValue)
+            }
+            <PollControlAttributes as ClusterSpecific>::ID => {
+                parse_cluster!(PollControlAttributes, PollControl)
+            }
             <IlluminanceMeasurementAttributes as ClusterSpecific>::ID => {
                 parse_cluster!(IlluminanceMeasurementAttributes, IlluminanceMeasurement)
             }
@@ -150,6 +213,9 @@ impl AttributeReport {
             <IasZoneAttributes as ClusterSpecific>::ID => {
                 parse_cluster!(IasZoneAttributes, IasZone)
             }
+            <DiagnosticsAttributes as ClusterSpecific>::ID => {
+                parse_cluster!(DiagnosticsAttributes, Diagnostics)
+            }
             _ => Err(ParseAttributeError::InvalidId(attribute_id)),
         }
     }
@@ -170,10 +236,11 @@ impl AttributeReport {
 #[cfg(test)]
 mod tests {
     use zb_core::Cluster;
-    use zb_core::types::{Bool, Type, Uint8};
+    use zb_core::types::{Bool, Float32, Type, Uint8, Uint16};
 
     use super::{AttributeReport, ParseAttributeError};
     use crate::clusters::general;
+    use crate::{analog_input, binary_output, multistate_value};
 
     #[test]
     fn parses_reportable_attribute() {
@@ -204,4 +271,49 @@ mod tests {
 
         assert!(matches!(error, ParseAttributeError::InvalidType(_)));
     }
+
+    #[test]
+    fn parses_bacnet_present_values() {
+        assert_eq!(
+            AttributeReport::parse(
+                Cluster::AnalogInput.as_u16(),
+                0x0055,
+                Type::Float32(Float32::new(21.5))
+            ),
+            Ok(AttributeReport::AnalogInput(
+                analog_input::Reportable::PresentValue(Float32::new(21.5))
+            ))
+        );
+        assert_eq!(
+            AttributeReport::parse(
+                Cluster::BinaryOutput.as_u16(),
+                0x0055,
+                Type::Boolean(Bool::TRUE)
+            ),
+            Ok(AttributeReport::BinaryOutput(
+                binary_output::Reportable::PresentValue(Bool::TRUE)
+            ))
+        );
+        assert_eq!(
+            AttributeReport::parse(
+                Cluster::MultistateValue.as_u16(),
+                0x006f,
+                Type::Map8(0b0000_1001)
+            ),
+            Ok(AttributeReport::MultistateValue(
+                multistate_value::Reportable::StatusFlags(
+                    multistate_value::StatusFlags::IN_ALARM
+                        | multistate_value::StatusFlags::OUT_OF_SERVICE
+                )
+            ))
+        );
+        assert_eq!(
+            AttributeReport::parse(
+                Cluster::MultistateValue.as_u16(),
+                0x004a,
+                Type::Uint16(Uint16::new(3))
+            ),
+            Err(ParseAttributeError::InvalidId(0x004a))
+        );
+    }
 }
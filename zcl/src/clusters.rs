@@ -1,11 +1,16 @@
 //! Cluster groups.
 
-use self::general::{alarms, basic, groups, identify, level, on_off, ota_upgrade, scenes};
+use self::commissioning::touchlink;
+use self::general::{
+    alarms, basic, groups, identify, level, on_off, ota_upgrade, poll_control, scenes,
+};
 use self::lighting::color_control;
 use crate::{Header, ParseFrameError, Scope};
 
+pub mod commissioning;
 pub mod general;
 pub mod global;
+pub mod home_automation;
 pub mod ias;
 pub mod lighting;
 pub mod measurement_and_sensing;
@@ -41,11 +46,17 @@ pub enum Cluster {
     /// OTA Upgrade cluster commands.
     OtaUpgrade(ota_upgrade::Command),
 
+    /// Poll Control cluster commands.
+    PollControl(poll_control::Command),
+
     /// Color Control cluster commands.
     ColorControl(color_control::Command),
 
     /// IAS Zone cluster commands.
     IasZone(ias::zone::Command),
+
+    /// Touchlink Commissioning cluster commands.
+    Touchlink(touchlink::Command),
 }
 
 impl Cluster {
@@ -90,12 +101,18 @@ impl Cluster {
                 <ota_upgrade::Command as zb_core::ClusterSpecific>::ID => {
                     ota_upgrade::Command::parse_zcl_frame(header, bytes).map(Self::OtaUpgrade)
                 }
+                <poll_control::Command as zb_core::ClusterSpecific>::ID => {
+                    poll_control::Command::parse_zcl_frame(header, bytes).map(Self::PollControl)
+                }
                 <color_control::Command as zb_core::ClusterSpecific>::ID => {
                     color_control::Command::parse_zcl_frame(header, bytes).map(Self::ColorControl)
                 }
                 <ias::zone::Command as zb_core::ClusterSpecific>::ID => {
                     ias::zone::Command::parse_zcl_frame(header, bytes).map(Self::IasZone)
                 }
+                <touchlink::Command as zb_core::ClusterSpecific>::ID => {
+                    touchlink::Command::parse_zcl_frame(header, bytes).map(Self::Touchlink)
+                }
                 invalid_cluster_id => Err(ParseFrameError::InvalidClusterId(invalid_cluster_id)),
             },
         }
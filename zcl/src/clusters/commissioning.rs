@@ -0,0 +1,3 @@
+//! Commissioning clusters.
+
+pub mod touchlink;
@@ -0,0 +1,180 @@
+//! Touchlink Commissioning cluster.
+//!
+//! Touchlink, formerly ZLL commissioning, lets an initiator commission lights in close proximity
+//! without a shared network. Its commands are exchanged as inter-PAN frames under the Touchlink
+//! profile: the initiator broadcasts a [`ScanRequest`] and addresses the targets that answered
+//! with a [`ScanResponse`] by IEEE address, repeating the scan's inter-PAN transaction identifier
+//! in every subsequent command.
+
+pub use self::allocation::{Allocation, EncryptedNetworkKey, Network, Range};
+pub use self::commands::{
+    Command, IdentifyRequest, NetworkJoinEndDeviceRequest, NetworkJoinEndDeviceResponse,
+    NetworkJoinRouterRequest, NetworkJoinRouterResponse, NetworkStartRequest, NetworkStartResponse,
+    ResetToFactoryNewRequest, ScanRequest, ScanResponse,
+};
+pub use self::information::{TouchlinkInformation, ZigbeeInformation};
+pub use self::status::Status;
+pub use self::sub_device::SubDevice;
+
+mod allocation;
+mod commands;
+mod information;
+mod status;
+mod sub_device;
+
+#[cfg(test)]
+mod tests {
+    use le_stream::ToLeStream;
+    use zb_core::security::Key;
+    use zb_core::{Cluster as ClusterId, IeeeAddress, Profile, Profiled};
+
+    use super::{
+        Allocation, Command, EncryptedNetworkKey, IdentifyRequest, Network,
+        NetworkJoinEndDeviceRequest, NetworkJoinEndDeviceResponse, NetworkJoinRouterRequest,
+        NetworkJoinRouterResponse, NetworkStartRequest, NetworkStartResponse, Range,
+        ResetToFactoryNewRequest, ScanRequest, ScanResponse, Status, SubDevice,
+        TouchlinkInformation, ZigbeeInformation,
+    };
+    use crate::{Cluster, Command as CommandMetadata, Directed, Frame, Header, Scope};
+
+    const SEQUENCE_NUMBER: u8 = 0x2A;
+    const TRANSACTION_ID: u32 = 0x1234_5678;
+    const RESPONSE_ID: u32 = 0x9ABC_DEF0;
+    const EXTENDED_PAN_ID: IeeeAddress = IeeeAddress::new(1, 2, 3, 4, 5, 6, 7, 8);
+    const INITIATOR: IeeeAddress = IeeeAddress::new(8, 7, 6, 5, 4, 3, 2, 1);
+
+    fn assert_runtime_round_trip<T>(command: T)
+    where
+        T: Clone + CommandMetadata + Directed + Into<Command> + ToLeStream,
+    {
+        let expected = Cluster::Touchlink(command.clone().into());
+        let header = Header::new(
+            Scope::ClusterSpecific,
+            T::DIRECTION,
+            T::DISABLE_DEFAULT_RESPONSE,
+            T::MANUFACTURER_CODE,
+            SEQUENCE_NUMBER,
+            T::ID,
+        );
+        let bytes = header.to_le_stream().chain(command.to_le_stream());
+        let frame = Frame::parse(ClusterId::TouchlinkCommissioning.as_u16(), bytes)
+            .expect("valid Touchlink command should parse");
+
+        assert_eq!(frame.into_payload(), expected);
+    }
+
+    fn network() -> Network {
+        Network::new(
+            EXTENDED_PAN_ID,
+            EncryptedNetworkKey::new(EncryptedNetworkKey::CERTIFICATION, Key::new([0xAB; 16])),
+        )
+    }
+
+    fn allocation() -> Allocation {
+        Allocation::new(0x0001)
+            .with_group_identifiers(Range::new(0x0010, 0x0011))
+            .with_free_network_addresses(Range::new(0x0002, 0x7FFF))
+    }
+
+    #[test]
+    fn every_command_round_trips_through_runtime_dispatch() {
+        assert_runtime_round_trip(ScanRequest::new(
+            TRANSACTION_ID,
+            ZigbeeInformation::ROUTER | ZigbeeInformation::RX_ON_WHEN_IDLE,
+            TouchlinkInformation::INITIATOR,
+        ));
+        assert_runtime_round_trip(
+            ScanResponse::new(
+                TRANSACTION_ID,
+                RESPONSE_ID,
+                ZigbeeInformation::ROUTER,
+                TouchlinkInformation::FACTORY_NEW,
+            )
+            .with_sub_devices(1, 0, Some(SubDevice::new(11, 0xC05E, 0x0100, 2, 0))),
+        );
+        assert_runtime_round_trip(IdentifyRequest::new(TRANSACTION_ID, 3));
+        assert_runtime_round_trip(ResetToFactoryNewRequest::new(TRANSACTION_ID));
+        assert_runtime_round_trip(NetworkStartRequest::new(
+            TRANSACTION_ID,
+            network(),
+            11,
+            0x1A62,
+            allocation(),
+            INITIATOR,
+            0x0000,
+        ));
+        assert_runtime_round_trip(NetworkStartResponse::new(
+            TRANSACTION_ID,
+            Status::Success,
+            EXTENDED_PAN_ID,
+            0,
+            11,
+            0x1A62,
+        ));
+        assert_runtime_round_trip(NetworkJoinRouterRequest::new(
+            TRANSACTION_ID,
+            network(),
+            0,
+            15,
+            0x1A62,
+            allocation(),
+        ));
+        assert_runtime_round_trip(NetworkJoinRouterResponse::new(
+            TRANSACTION_ID,
+            Status::Failure,
+        ));
+        assert_runtime_round_trip(NetworkJoinEndDeviceRequest::new(
+            TRANSACTION_ID,
+            network(),
+            0,
+            20,
+            0x1A62,
+            Allocation::new(0x0002),
+        ));
+        assert_runtime_round_trip(NetworkJoinEndDeviceResponse::new(
+            TRANSACTION_ID,
+            Status::Success,
+        ));
+    }
+
+    #[test]
+    fn commands_use_the_touchlink_profile() {
+        assert_eq!(<Command as Profiled>::PROFILE, Profile::TouchLink);
+        assert_eq!(<ScanRequest as Profiled>::PROFILE, Profile::TouchLink);
+    }
+
+    #[test]
+    fn parses_scan_responses_without_sub_device_information() {
+        let bytes = [
+            0x19, 0x00, 0x01, // Frame control, sequence number, and command ID.
+            0x78, 0x56, 0x34, 0x12, // Inter-PAN transaction identifier.
+            0x00, 0x05, 0x01, // RSSI correction, Zigbee, and Touchlink information.
+            0x10, 0x80, // Key bit mask with the master and certification keys.
+            0xF0, 0xDE, 0xBC, 0x9A, // Response identifier.
+            0, 0, 0, 0, 0, 0, 0, 0, // Extended PAN ID.
+            0x00, 0x0B, 0x00, 0x00, 0xFF, 0xFF, // Update ID, channel, PAN ID, and address.
+            0x02, 0x00, // Two sub-devices without group identifiers.
+        ];
+
+        let frame = Frame::parse(
+            ClusterId::TouchlinkCommissioning.as_u16(),
+            bytes.into_iter(),
+        )
+        .expect("scan response should parse");
+        let Cluster::Touchlink(Command::ScanResponse(response)) = frame.into_payload() else {
+            panic!("expected a scan response");
+        };
+
+        assert_eq!(response.transaction_id(), TRANSACTION_ID);
+        assert_eq!(response.response_id(), RESPONSE_ID);
+        assert!(
+            response
+                .touchlink_information()
+                .contains(TouchlinkInformation::FACTORY_NEW)
+        );
+        assert_eq!(response.key_bitmask(), 0x8010);
+        assert_eq!(response.logical_channel(), 11);
+        assert_eq!(response.number_of_sub_devices(), 2);
+        assert_eq!(response.sub_device(), None);
+    }
+}
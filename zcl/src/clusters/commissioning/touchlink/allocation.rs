@@ -0,0 +1,181 @@
+use le_stream::{FromLeStream, ToLeStream};
+use zb_core::IeeeAddress;
+use zb_core::security::Key;
+
+/// Inclusive range of network addresses or group identifiers.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, FromLeStream, ToLeStream,
+)]
+pub struct Range {
+    begin: u16,
+    end: u16,
+}
+
+impl Range {
+    /// The empty range, encoded as zero bounds.
+    pub const EMPTY: Self = Self::new(0, 0);
+
+    /// Create a range from its first and last value.
+    #[must_use]
+    pub const fn new(begin: u16, end: u16) -> Self {
+        Self { begin, end }
+    }
+
+    /// Return the first value.
+    #[must_use]
+    pub const fn begin(self) -> u16 {
+        self.begin
+    }
+
+    /// Return the last value.
+    #[must_use]
+    pub const fn end(self) -> u16 {
+        self.end
+    }
+}
+
+/// Addresses assigned to a target by a Touchlink network start or join request.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, FromLeStream, ToLeStream,
+)]
+pub struct Allocation {
+    network_address: u16,
+    group_identifiers: Range,
+    free_network_addresses: Range,
+    free_group_identifiers: Range,
+}
+
+impl Allocation {
+    /// Assign `network_address` to the target without any group identifiers or free ranges.
+    #[must_use]
+    pub const fn new(network_address: u16) -> Self {
+        Self {
+            network_address,
+            group_identifiers: Range::EMPTY,
+            free_network_addresses: Range::EMPTY,
+            free_group_identifiers: Range::EMPTY,
+        }
+    }
+
+    /// Assign the group identifiers that the target uses for its endpoints.
+    #[must_use]
+    pub const fn with_group_identifiers(mut self, range: Range) -> Self {
+        self.group_identifiers = range;
+        self
+    }
+
+    /// Hand over network addresses that a target with address assignment capability may assign.
+    #[must_use]
+    pub const fn with_free_network_addresses(mut self, range: Range) -> Self {
+        self.free_network_addresses = range;
+        self
+    }
+
+    /// Hand over group identifiers that a target with address assignment capability may assign.
+    #[must_use]
+    pub const fn with_free_group_identifiers(mut self, range: Range) -> Self {
+        self.free_group_identifiers = range;
+        self
+    }
+
+    /// Return the network address assigned to the target.
+    #[must_use]
+    pub const fn network_address(self) -> u16 {
+        self.network_address
+    }
+
+    /// Return the group identifiers assigned to the target.
+    #[must_use]
+    pub const fn group_identifiers(self) -> Range {
+        self.group_identifiers
+    }
+
+    /// Return the network addresses the target may assign.
+    #[must_use]
+    pub const fn free_network_addresses(self) -> Range {
+        self.free_network_addresses
+    }
+
+    /// Return the group identifiers the target may assign.
+    #[must_use]
+    pub const fn free_group_identifiers(self) -> Range {
+        self.free_group_identifiers
+    }
+}
+
+/// Network key encrypted with the Touchlink transport key selected by the key index.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, FromLeStream, ToLeStream,
+)]
+pub struct EncryptedNetworkKey {
+    key_index: u8,
+    key: Key,
+}
+
+impl EncryptedNetworkKey {
+    /// Key index of the development transport key.
+    pub const DEVELOPMENT: u8 = 0;
+
+    /// Key index of the master transport key.
+    pub const MASTER: u8 = 4;
+
+    /// Key index of the certification transport key.
+    pub const CERTIFICATION: u8 = 15;
+
+    /// Create an encrypted network key.
+    ///
+    /// The key must already be encrypted with the transport key of `key_index`, which is derived
+    /// from the transaction and response identifiers of the preceding scan.
+    #[must_use]
+    pub const fn new(key_index: u8, key: Key) -> Self {
+        Self { key_index, key }
+    }
+
+    /// Return the index of the transport key.
+    #[must_use]
+    pub const fn key_index(self) -> u8 {
+        self.key_index
+    }
+
+    /// Return the encrypted network key.
+    #[must_use]
+    pub const fn key(self) -> Key {
+        self.key
+    }
+}
+
+/// Network selected by a Touchlink network start or join request.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, FromLeStream, ToLeStream,
+)]
+pub struct Network {
+    extended_pan_id: IeeeAddress,
+    network_key: EncryptedNetworkKey,
+}
+
+impl Network {
+    /// Create the description of a network.
+    #[must_use]
+    pub const fn new(extended_pan_id: IeeeAddress, network_key: EncryptedNetworkKey) -> Self {
+        Self {
+            extended_pan_id,
+            network_key,
+        }
+    }
+
+    /// Return the extended PAN ID.
+    #[must_use]
+    pub const fn extended_pan_id(self) -> IeeeAddress {
+        self.extended_pan_id
+    }
+
+    /// Return the encrypted network key.
+    #[must_use]
+    pub const fn network_key(self) -> EncryptedNetworkKey {
+        self.network_key
+    }
+}
@@ -0,0 +1,42 @@
+//! Inter-PAN commands of the Touchlink Commissioning cluster.
+
+use zb_core::{Cluster, Profile};
+
+pub use self::identify_request::IdentifyRequest;
+pub use self::network_join_end_device_request::NetworkJoinEndDeviceRequest;
+pub use self::network_join_end_device_response::NetworkJoinEndDeviceResponse;
+pub use self::network_join_router_request::NetworkJoinRouterRequest;
+pub use self::network_join_router_response::NetworkJoinRouterResponse;
+pub use self::network_start_request::NetworkStartRequest;
+pub use self::network_start_response::NetworkStartResponse;
+pub use self::reset_to_factory_new_request::ResetToFactoryNewRequest;
+pub use self::scan_request::ScanRequest;
+pub use self::scan_response::ScanResponse;
+use crate::macros::zcl_command_enum;
+
+mod identify_request;
+mod network_join_end_device_request;
+mod network_join_end_device_response;
+mod network_join_router_request;
+mod network_join_router_response;
+mod network_start_request;
+mod network_start_response;
+mod reset_to_factory_new_request;
+mod scan_request;
+mod scan_response;
+
+// Inter-PAN commands of the Touchlink Commissioning cluster.
+zcl_command_enum! {
+    { Cluster::TouchlinkCommissioning } => Touchlink;
+    profile: Profile::TouchLink;
+    ScanRequest(ScanRequest),
+    ScanResponse(ScanResponse),
+    IdentifyRequest(IdentifyRequest),
+    ResetToFactoryNewRequest(ResetToFactoryNewRequest),
+    NetworkStartRequest(NetworkStartRequest),
+    NetworkStartResponse(NetworkStartResponse),
+    NetworkJoinRouterRequest(NetworkJoinRouterRequest),
+    NetworkJoinRouterResponse(NetworkJoinRouterResponse),
+    NetworkJoinEndDeviceRequest(NetworkJoinEndDeviceRequest),
+    NetworkJoinEndDeviceResponse(NetworkJoinEndDeviceResponse),
+}
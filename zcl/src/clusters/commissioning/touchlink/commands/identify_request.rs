@@ -0,0 +1,53 @@
+use zb_core::{Cluster, Direction, Profile};
+
+use crate::macros::zcl_command;
+
+zcl_command! {
+    /// Make a Touchlink target identify itself, for example by blinking.
+    IdentifyRequest {
+        { Cluster::TouchlinkCommissioning } => Touchlink;
+        profile: Profile::TouchLink;
+        command_id: 0x06;
+        direction: Direction::ClientToServer;
+        disable_default_response: true;
+        derive(Copy);
+        fields {
+            transaction_id: u32,
+            identify_duration: u16,
+        }
+
+        constructor {
+            /// Identify for `identify_duration` seconds.
+            ///
+            /// A duration of [`IdentifyRequest::STOP`] stops identifying and
+            /// [`IdentifyRequest::DEFAULT_DURATION`] selects the target's default duration.
+            #[must_use]
+            pub const fn new(transaction_id: u32, identify_duration: u16) -> Self {
+                Self {
+                    transaction_id,
+                    identify_duration,
+                }
+            }
+        }
+
+        getters {
+            /// Identify duration that stops identifying.
+            pub const STOP: u16 = 0x0000;
+
+            /// Identify duration that selects the target's default duration.
+            pub const DEFAULT_DURATION: u16 = 0xFFFF;
+
+            /// Return the inter-PAN transaction identifier.
+            #[must_use]
+            pub const fn transaction_id(self) -> u32 {
+                self.transaction_id
+            }
+
+            /// Return the identify duration in seconds.
+            #[must_use]
+            pub const fn identify_duration(self) -> u16 {
+                self.identify_duration
+            }
+        }
+    }
+}
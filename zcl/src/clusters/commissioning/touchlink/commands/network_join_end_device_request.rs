@@ -0,0 +1,86 @@
+use zb_core::{Cluster, Direction, Profile};
+
+use super::NetworkJoinEndDeviceResponse;
+use crate::clusters::commissioning::touchlink::{Allocation, Network};
+use crate::macros::zcl_command;
+
+zcl_command! {
+    /// Ask a end device target to join an existing network.
+    NetworkJoinEndDeviceRequest {
+        { Cluster::TouchlinkCommissioning } => Touchlink;
+        profile: Profile::TouchLink;
+        command_id: 0x14;
+        direction: Direction::ClientToServer;
+        disable_default_response: true;
+        response: NetworkJoinEndDeviceResponse;
+        derive(Copy);
+        fields {
+            transaction_id: u32,
+            network: Network,
+            network_update_id: u8,
+            logical_channel: u8,
+            pan_id: u16,
+            allocation: Allocation,
+        }
+
+        constructor {
+            /// Create a new `NetworkJoinEndDeviceRequest` command.
+            #[must_use]
+            pub const fn new(
+                transaction_id: u32,
+                network: Network,
+                network_update_id: u8,
+                logical_channel: u8,
+                pan_id: u16,
+                allocation: Allocation,
+            ) -> Self {
+                Self {
+                    transaction_id,
+                    network,
+                    network_update_id,
+                    logical_channel,
+                    pan_id,
+                    allocation,
+                }
+            }
+        }
+
+        getters {
+            /// Return the inter-PAN transaction identifier.
+            #[must_use]
+            pub const fn transaction_id(self) -> u32 {
+                self.transaction_id
+            }
+
+            /// Return the network to join.
+            #[must_use]
+            pub const fn network(self) -> Network {
+                self.network
+            }
+
+            /// Return the network update identifier of the network.
+            #[must_use]
+            pub const fn network_update_id(self) -> u8 {
+                self.network_update_id
+            }
+
+            /// Return the channel of the network.
+            #[must_use]
+            pub const fn logical_channel(self) -> u8 {
+                self.logical_channel
+            }
+
+            /// Return the PAN ID of the network.
+            #[must_use]
+            pub const fn pan_id(self) -> u16 {
+                self.pan_id
+            }
+
+            /// Return the addresses assigned to the target.
+            #[must_use]
+            pub const fn allocation(self) -> Allocation {
+                self.allocation
+            }
+        }
+    }
+}
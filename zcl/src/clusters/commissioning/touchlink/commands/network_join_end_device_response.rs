@@ -0,0 +1,48 @@
+use zb_core::{Cluster, Direction, Profile};
+
+use crate::clusters::commissioning::touchlink::Status;
+use crate::macros::zcl_command;
+
+zcl_command! {
+    /// Answer a [`NetworkJoinEndDeviceRequest`](super::NetworkJoinEndDeviceRequest).
+    NetworkJoinEndDeviceResponse {
+        { Cluster::TouchlinkCommissioning } => Touchlink;
+        profile: Profile::TouchLink;
+        command_id: 0x15;
+        direction: Direction::ServerToClient;
+        disable_default_response: true;
+        derive(Copy);
+        fields {
+            transaction_id: u32,
+            status: u8,
+        }
+
+        constructor {
+            /// Create a new `NetworkJoinEndDeviceResponse` command.
+            #[must_use]
+            pub fn new(transaction_id: u32, status: Status) -> Self {
+                Self {
+                    transaction_id,
+                    status: status.into(),
+                }
+            }
+        }
+
+        getters {
+            /// Return the inter-PAN transaction identifier.
+            #[must_use]
+            pub const fn transaction_id(self) -> u32 {
+                self.transaction_id
+            }
+
+            /// Return whether the target joined the network.
+            ///
+            /// # Errors
+            ///
+            /// Returns the raw status if it is not a valid [`Status`].
+            pub fn status(self) -> Result<Status, u8> {
+                Status::try_from(self.status)
+            }
+        }
+    }
+}
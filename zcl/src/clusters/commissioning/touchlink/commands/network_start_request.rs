@@ -0,0 +1,97 @@
+use zb_core::{Cluster, Direction, IeeeAddress, Profile};
+
+use super::NetworkStartResponse;
+use crate::clusters::commissioning::touchlink::{Allocation, Network};
+use crate::macros::zcl_command;
+
+zcl_command! {
+    /// Ask a factory-new router target to form a new network.
+    NetworkStartRequest {
+        { Cluster::TouchlinkCommissioning } => Touchlink;
+        profile: Profile::TouchLink;
+        command_id: 0x10;
+        direction: Direction::ClientToServer;
+        disable_default_response: true;
+        response: NetworkStartResponse;
+        derive(Copy);
+        fields {
+            transaction_id: u32,
+            network: Network,
+            logical_channel: u8,
+            pan_id: u16,
+            allocation: Allocation,
+            initiator_ieee_address: IeeeAddress,
+            initiator_network_address: u16,
+        }
+
+        constructor {
+            /// Create a new `NetworkStartRequest` command.
+            ///
+            /// Zero values of the extended PAN ID, channel, and PAN ID let the target choose them.
+            #[must_use]
+            pub const fn new(
+                transaction_id: u32,
+                network: Network,
+                logical_channel: u8,
+                pan_id: u16,
+                allocation: Allocation,
+                initiator_ieee_address: IeeeAddress,
+                initiator_network_address: u16,
+            ) -> Self {
+                Self {
+                    transaction_id,
+                    network,
+                    logical_channel,
+                    pan_id,
+                    allocation,
+                    initiator_ieee_address,
+                    initiator_network_address,
+                }
+            }
+        }
+
+        getters {
+            /// Return the inter-PAN transaction identifier.
+            #[must_use]
+            pub const fn transaction_id(self) -> u32 {
+                self.transaction_id
+            }
+
+            /// Return the network to form.
+            #[must_use]
+            pub const fn network(self) -> Network {
+                self.network
+            }
+
+            /// Return the channel to form the network on.
+            #[must_use]
+            pub const fn logical_channel(self) -> u8 {
+                self.logical_channel
+            }
+
+            /// Return the PAN ID of the network.
+            #[must_use]
+            pub const fn pan_id(self) -> u16 {
+                self.pan_id
+            }
+
+            /// Return the addresses assigned to the target.
+            #[must_use]
+            pub const fn allocation(self) -> Allocation {
+                self.allocation
+            }
+
+            /// Return the IEEE address of the initiator.
+            #[must_use]
+            pub const fn initiator_ieee_address(self) -> IeeeAddress {
+                self.initiator_ieee_address
+            }
+
+            /// Return the network address of the initiator.
+            #[must_use]
+            pub const fn initiator_network_address(self) -> u16 {
+                self.initiator_network_address
+            }
+        }
+    }
+}
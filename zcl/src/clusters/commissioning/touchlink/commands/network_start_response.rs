@@ -0,0 +1,87 @@
+use zb_core::{Cluster, Direction, IeeeAddress, Profile};
+
+use crate::clusters::commissioning::touchlink::Status;
+use crate::macros::zcl_command;
+
+zcl_command! {
+    /// Answer a [`NetworkStartRequest`](super::NetworkStartRequest) with the formed network.
+    NetworkStartResponse {
+        { Cluster::TouchlinkCommissioning } => Touchlink;
+        profile: Profile::TouchLink;
+        command_id: 0x11;
+        direction: Direction::ServerToClient;
+        disable_default_response: true;
+        derive(Copy);
+        fields {
+            transaction_id: u32,
+            status: u8,
+            extended_pan_id: IeeeAddress,
+            network_update_id: u8,
+            logical_channel: u8,
+            pan_id: u16,
+        }
+
+        constructor {
+            /// Create a new `NetworkStartResponse` command.
+            #[must_use]
+            pub fn new(
+                transaction_id: u32,
+                status: Status,
+                extended_pan_id: IeeeAddress,
+                network_update_id: u8,
+                logical_channel: u8,
+                pan_id: u16,
+            ) -> Self {
+                Self {
+                    transaction_id,
+                    status: status.into(),
+                    extended_pan_id,
+                    network_update_id,
+                    logical_channel,
+                    pan_id,
+                }
+            }
+        }
+
+        getters {
+            /// Return the inter-PAN transaction identifier.
+            #[must_use]
+            pub const fn transaction_id(self) -> u32 {
+                self.transaction_id
+            }
+
+            /// Return whether the target formed the network.
+            ///
+            /// # Errors
+            ///
+            /// Returns the raw status if it is not a valid [`Status`].
+            pub fn status(self) -> Result<Status, u8> {
+                Status::try_from(self.status)
+            }
+
+            /// Return the extended PAN ID of the formed network.
+            #[must_use]
+            pub const fn extended_pan_id(self) -> IeeeAddress {
+                self.extended_pan_id
+            }
+
+            /// Return the network update identifier of the formed network.
+            #[must_use]
+            pub const fn network_update_id(self) -> u8 {
+                self.network_update_id
+            }
+
+            /// Return the channel of the formed network.
+            #[must_use]
+            pub const fn logical_channel(self) -> u8 {
+                self.logical_channel
+            }
+
+            /// Return the PAN ID of the formed network.
+            #[must_use]
+            pub const fn pan_id(self) -> u16 {
+                self.pan_id
+            }
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use zb_core::{Cluster, Direction, Profile};
+
+use crate::macros::zcl_command;
+
+zcl_command! {
+    /// Make a Touchlink target leave its network and return to its factory-new state.
+    ResetToFactoryNewRequest {
+        { Cluster::TouchlinkCommissioning } => Touchlink;
+        profile: Profile::TouchLink;
+        command_id: 0x07;
+        direction: Direction::ClientToServer;
+        disable_default_response: true;
+        derive(Copy);
+        fields {
+            transaction_id: u32,
+        }
+
+        constructor {
+            /// Create a new `ResetToFactoryNewRequest` command.
+            #[must_use]
+            pub const fn new(transaction_id: u32) -> Self {
+                Self { transaction_id }
+            }
+        }
+
+        getters {
+            /// Return the inter-PAN transaction identifier.
+            #[must_use]
+            pub const fn transaction_id(self) -> u32 {
+                self.transaction_id
+            }
+        }
+    }
+}
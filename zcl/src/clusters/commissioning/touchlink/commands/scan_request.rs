@@ -0,0 +1,64 @@
+use zb_core::{Cluster, Direction, Profile};
+
+use super::ScanResponse;
+use crate::clusters::commissioning::touchlink::{TouchlinkInformation, ZigbeeInformation};
+use crate::macros::zcl_command;
+
+zcl_command! {
+    /// Discover Touchlink targets in radio range.
+    ///
+    /// Initiators broadcast scan requests over inter-PAN and collect the [`ScanResponse`]s that
+    /// carry the same inter-PAN transaction identifier.
+    ScanRequest {
+        { Cluster::TouchlinkCommissioning } => Touchlink;
+        profile: Profile::TouchLink;
+        command_id: 0x00;
+        direction: Direction::ClientToServer;
+        disable_default_response: true;
+        response: ScanResponse;
+        derive(Copy);
+        fields {
+            transaction_id: u32,
+            zigbee_information: ZigbeeInformation,
+            touchlink_information: TouchlinkInformation,
+        }
+
+        constructor {
+            /// Create a new `ScanRequest` command.
+            ///
+            /// The transaction identifier must be random and non-zero.
+            #[must_use]
+            pub const fn new(
+                transaction_id: u32,
+                zigbee_information: ZigbeeInformation,
+                touchlink_information: TouchlinkInformation,
+            ) -> Self {
+                Self {
+                    transaction_id,
+                    zigbee_information,
+                    touchlink_information,
+                }
+            }
+        }
+
+        getters {
+            /// Return the inter-PAN transaction identifier.
+            #[must_use]
+            pub const fn transaction_id(self) -> u32 {
+                self.transaction_id
+            }
+
+            /// Return the Zigbee information of the initiator.
+            #[must_use]
+            pub const fn zigbee_information(self) -> ZigbeeInformation {
+                self.zigbee_information
+            }
+
+            /// Return the Touchlink information of the initiator.
+            #[must_use]
+            pub const fn touchlink_information(self) -> TouchlinkInformation {
+                self.touchlink_information
+            }
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use zb_core::{Cluster, Direction, IeeeAddress, Profile};
+
+use crate::clusters::commissioning::touchlink::{
+    SubDevice, TouchlinkInformation, ZigbeeInformation,
+};
+use crate::macros::zcl_command;
+
+zcl_command! {
+    /// Answer a [`ScanRequest`](super::ScanRequest) with the target's network and endpoint
+    /// information.
+    ScanResponse {
+        { Cluster::TouchlinkCommissioning } => Touchlink;
+        profile: Profile::TouchLink;
+        command_id: 0x01;
+        direction: Direction::ServerToClient;
+        disable_default_response: true;
+        derive(Copy);
+        fields {
+            transaction_id: u32,
+            rssi_correction: u8,
+            zigbee_information: ZigbeeInformation,
+            touchlink_information: TouchlinkInformation,
+            key_bitmask: u16,
+            response_id: u32,
+            extended_pan_id: IeeeAddress,
+            network_update_id: u8,
+            logical_channel: u8,
+            pan_id: u16,
+            network_address: u16,
+            number_of_sub_devices: u8,
+            total_group_identifiers: u8,
+            sub_device: Option<SubDevice>,
+        }
+
+        constructor {
+            /// Create a new `ScanResponse` command of a target without a network or endpoints.
+            #[must_use]
+            pub const fn new(
+                transaction_id: u32,
+                response_id: u32,
+                zigbee_information: ZigbeeInformation,
+                touchlink_information: TouchlinkInformation,
+            ) -> Self {
+                Self {
+                    transaction_id,
+                    rssi_correction: 0,
+                    zigbee_information,
+                    touchlink_information,
+                    key_bitmask: 0,
+                    response_id,
+                    extended_pan_id: IeeeAddress::new(0, 0, 0, 0, 0, 0, 0, 0),
+                    network_update_id: 0,
+                    logical_channel: 0,
+                    pan_id: 0,
+                    network_address: 0,
+                    number_of_sub_devices: 0,
+                    total_group_identifiers: 0,
+                    sub_device: None,
+                }
+            }
+        }
+
+        getters {
+            /// Set the correction that the initiator adds to the received signal strength.
+            #[must_use]
+            pub const fn with_rssi_correction(mut self, rssi_correction: u8) -> Self {
+                self.rssi_correction = rssi_correction;
+                self
+            }
+
+            /// Set the bit mask of the transport key indices supported by the target.
+            #[must_use]
+            pub const fn with_key_bitmask(mut self, key_bitmask: u16) -> Self {
+                self.key_bitmask = key_bitmask;
+                self
+            }
+
+            /// Set the network that the target currently operates on.
+            #[must_use]
+            pub const fn with_network(
+                mut self,
+                extended_pan_id: IeeeAddress,
+                network_update_id: u8,
+                logical_channel: u8,
+                pan_id: u16,
+                network_address: u16,
+            ) -> Self {
+                self.extended_pan_id = extended_pan_id;
+                self.network_update_id = network_update_id;
+                self.logical_channel = logical_channel;
+                self.pan_id = pan_id;
+                self.network_address = network_address;
+                self
+            }
+
+            /// Set the endpoints of the target.
+            ///
+            /// Targets with exactly one sub-device describe it in the response itself.
+            #[must_use]
+            pub const fn with_sub_devices(
+                mut self,
+                number_of_sub_devices: u8,
+                total_group_identifiers: u8,
+                sub_device: Option<SubDevice>,
+            ) -> Self {
+                self.number_of_sub_devices = number_of_sub_devices;
+                self.total_group_identifiers = total_group_identifiers;
+                self.sub_device = sub_device;
+                self
+            }
+
+            /// Return the inter-PAN transaction identifier of the answered scan request.
+            #[must_use]
+            pub const fn transaction_id(self) -> u32 {
+                self.transaction_id
+            }
+
+            /// Return the correction to add to the received signal strength.
+            #[must_use]
+            pub const fn rssi_correction(self) -> u8 {
+                self.rssi_correction
+            }
+
+            /// Return the Zigbee information of the target.
+            #[must_use]
+            pub const fn zigbee_information(self) -> ZigbeeInformation {
+                self.zigbee_information
+            }
+
+            /// Return the Touchlink information of the target.
+            #[must_use]
+            pub const fn touchlink_information(self) -> TouchlinkInformation {
+                self.touchlink_information
+            }
+
+            /// Return the bit mask of the supported transport key indices.
+            #[must_use]
+            pub const fn key_bitmask(self) -> u16 {
+                self.key_bitmask
+            }
+
+            /// Return the random response identifier chosen by the target.
+            #[must_use]
+            pub const fn response_id(self) -> u32 {
+                self.response_id
+            }
+
+            /// Return the extended PAN ID of the target's network.
+            #[must_use]
+            pub const fn extended_pan_id(self) -> IeeeAddress {
+                self.extended_pan_id
+            }
+
+            /// Return the network update identifier of the target's network.
+            #[must_use]
+            pub const fn network_update_id(self) -> u8 {
+                self.network_update_id
+            }
+
+            /// Return the channel that the target operates on.
+            #[must_use]
+            pub const fn logical_channel(self) -> u8 {
+                self.logical_channel
+            }
+
+            /// Return the PAN ID of the target's network.
+            #[must_use]
+            pub const fn pan_id(self) -> u16 {
+                self.pan_id
+            }
+
+            /// Return the network address of the target.
+            #[must_use]
+            pub const fn network_address(self) -> u16 {
+                self.network_address
+            }
+
+            /// Return the number of endpoints of the target.
+            #[must_use]
+            pub const fn number_of_sub_devices(self) -> u8 {
+                self.number_of_sub_devices
+            }
+
+            /// Return the number of group identifiers the target requires.
+            #[must_use]
+            pub const fn total_group_identifiers(self) -> u8 {
+                self.total_group_identifiers
+            }
+
+            /// Return the endpoint of a target with a single sub-device.
+            #[must_use]
+            pub const fn sub_device(self) -> Option<SubDevice> {
+                self.sub_device
+            }
+        }
+    }
+}
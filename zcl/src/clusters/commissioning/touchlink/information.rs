@@ -0,0 +1,46 @@
+use bitflags::bitflags;
+use le_stream::{FromLeStream, ToLeStream};
+
+/// Zigbee information field of Touchlink scan requests and responses.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, FromLeStream, ToLeStream,
+)]
+pub struct ZigbeeInformation(u8);
+
+bitflags! {
+    impl ZigbeeInformation: u8 {
+        /// The device is a router.
+        const ROUTER = 0b0000_0001;
+        /// The device is an end device.
+        const END_DEVICE = 0b0000_0010;
+        /// The device keeps its receiver on when idle.
+        const RX_ON_WHEN_IDLE = 0b0000_0100;
+    }
+}
+
+crate::macros::impl_bitflags_display_and_from_str!(ZigbeeInformation);
+
+/// Touchlink information field of Touchlink scan requests and responses.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, FromLeStream, ToLeStream,
+)]
+pub struct TouchlinkInformation(u8);
+
+bitflags! {
+    impl TouchlinkInformation: u8 {
+        /// The device has not joined a network since its last reset.
+        const FACTORY_NEW = 0b0000_0001;
+        /// The device can assign network addresses and group identifiers.
+        const ADDRESS_ASSIGNMENT = 0b0000_0010;
+        /// The device can initiate Touchlink commissioning.
+        const INITIATOR = 0b0001_0000;
+        /// The scan was triggered by the user, for example by pressing a button.
+        const PRIORITY_REQUEST = 0b0010_0000;
+        /// The device supports profile interoperability.
+        const PROFILE_INTEROP = 0b1000_0000;
+    }
+}
+
+crate::macros::impl_bitflags_display_and_from_str!(TouchlinkInformation);
@@ -0,0 +1,13 @@
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+/// Status of a Touchlink network start or join response.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = u8, constructor = core::convert::identity))]
+#[repr(u8)]
+pub enum Status {
+    /// The target started or joined the network.
+    Success = 0x00,
+    /// The target rejected the request.
+    Failure = 0x01,
+}
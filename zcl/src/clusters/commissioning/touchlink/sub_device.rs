@@ -0,0 +1,64 @@
+use le_stream::{FromLeStream, ToLeStream};
+
+/// Endpoint information of a scan response from a device with a single sub-device.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(
+    Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash, FromLeStream, ToLeStream,
+)]
+pub struct SubDevice {
+    endpoint: u8,
+    profile_id: u16,
+    device_id: u16,
+    version: u8,
+    group_identifier_count: u8,
+}
+
+impl SubDevice {
+    /// Create the information of a sub-device.
+    #[must_use]
+    pub const fn new(
+        endpoint: u8,
+        profile_id: u16,
+        device_id: u16,
+        version: u8,
+        group_identifier_count: u8,
+    ) -> Self {
+        Self {
+            endpoint,
+            profile_id,
+            device_id,
+            version,
+            group_identifier_count,
+        }
+    }
+
+    /// Return the endpoint ID.
+    #[must_use]
+    pub const fn endpoint(self) -> u8 {
+        self.endpoint
+    }
+
+    /// Return the profile ID of the endpoint.
+    #[must_use]
+    pub const fn profile_id(self) -> u16 {
+        self.profile_id
+    }
+
+    /// Return the device ID of the endpoint.
+    #[must_use]
+    pub const fn device_id(self) -> u16 {
+        self.device_id
+    }
+
+    /// Return the device version of the endpoint.
+    #[must_use]
+    pub const fn version(self) -> u8 {
+        self.version
+    }
+
+    /// Return the number of group identifiers the endpoint requires.
+    #[must_use]
+    pub const fn group_identifier_count(self) -> u8 {
+        self.group_identifier_count
+    }
+}
@@ -1,13 +1,24 @@
 //! General ZCL zcl.
 
 pub mod alarms;
+pub mod analog_input;
+pub mod analog_output;
+pub mod analog_value;
+pub mod bacnet;
 pub mod basic;
+pub mod binary_input;
+pub mod binary_output;
+pub mod binary_value;
 pub mod device_temperature_configuration;
 pub mod groups;
 pub mod identify;
 pub mod level;
+pub mod multistate_input;
+pub mod multistate_output;
+pub mod multistate_value;
 pub mod on_off;
 pub mod ota_upgrade;
+pub mod poll_control;
 pub mod power_configuration;
 pub mod scenes;
 pub mod time;
@@ -1,7 +1,9 @@
 //! Alarms cluster implementation.
 
 pub use self::attributes::{AlarmCount, Id, Readable, Reportable, SendReport, Writable};
-pub use self::commands::{Command, GetAlarm, ResetAlarm, ResetAlarmLog, ResetAllAlarms};
+pub use self::commands::{
+    Alarm, Command, GetAlarm, GetAlarmResponse, ResetAlarm, ResetAlarmLog, ResetAllAlarms,
+};
 pub use self::table::Entry;
 
 mod attributes;
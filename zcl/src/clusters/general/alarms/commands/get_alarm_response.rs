@@ -1,9 +1,14 @@
 use zb_core::{Cluster, Direction};
 
+use crate::Status;
+use crate::alarms::Entry;
 use crate::macros::zcl_command;
 
 zcl_command! {
     /// Response to a [`GetAlarm`](super::GetAlarm) command.
+    ///
+    /// The alarm entry is only present if the status is `SUCCESS`.
+    /// An empty alarm log is reported with a `NOT_FOUND` status and no entry.
     GetAlarmResponse {
         { Cluster::Alarms } => Alarms;
         command_id: 0x01;
@@ -12,36 +17,75 @@ zcl_command! {
         derive(Copy);
         fields {
             status: u8,
-            alarm_code: u8,
-            cluster_id: u16,
-            timestamp: u32,
+            entry: Option<Entry>,
         }
 
-        getters {
-            /// Returns the status of the `GetAlarm` command.
-            #[must_use]
-            pub const fn status(self) -> u8 {
-                self.status
-            }
-
-            /// Returns the alarm code of the earliest generated entry.
+        constructor {
+            /// Creates a new `GetAlarmResponse` with the given status and removed alarm entry.
             #[must_use]
-            pub const fn alarm_code(self) -> u8 {
-                self.alarm_code
+            pub fn new(status: Status, entry: Option<Entry>) -> Self {
+                Self {
+                    status: status.into(),
+                    entry,
+                }
             }
+        }
 
-            /// Returns the cluster ID associated with the earliest generated entry.
-            #[must_use]
-            pub const fn cluster_id(self) -> u16 {
-                self.cluster_id
+        getters {
+            /// Returns the status of the `GetAlarm` command.
+            ///
+            /// # Errors
+            ///
+            /// If the status byte does not correspond to a valid `Status`, this will return the raw status value as an error.
+            pub fn status(self) -> Result<Status, u8> {
+                Status::try_from(self.status)
             }
 
-            /// Returns the timestamp of when the earliest generated entry was created.
+            /// Returns the earliest generated entry, which the device removed from its alarm log.
             #[must_use]
-            pub const fn timestamp(self) -> u32 {
-                // TODO: Is this really a `Uint32` or actually a `UtcTime`?
-                self.timestamp
+            pub const fn entry(self) -> Option<Entry> {
+                self.entry
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::alarms::{Command, GetAlarmResponse};
+    use crate::{Cluster, Frame, Status};
+
+    fn parse(bytes: &[u8]) -> GetAlarmResponse {
+        let frame = Frame::parse(0x0009, bytes.iter().copied())
+            .expect("get alarm response frame should parse");
+
+        let Cluster::Alarms(Command::GetAlarmResponse(response)) = frame.into_payload() else {
+            panic!("expected get alarm response");
+        };
+
+        *response
+    }
+
+    #[test]
+    fn parses_not_found_response_without_entry() {
+        let response = parse(&[0x19, 0x06, 0x01, 0x8b]);
+
+        assert_eq!(response.status(), Ok(Status::NotFound));
+        assert_eq!(response.entry(), None);
+    }
+
+    #[test]
+    fn parses_success_response_with_entry() {
+        let response = parse(&[
+            0x19, 0x06, 0x01, 0x00, 0x02, 0x01, 0x00, 0x34, 0x12, 0x00, 0x00,
+        ]);
+        let entry = response
+            .entry()
+            .expect("successful response should carry an entry");
+
+        assert_eq!(response.status(), Ok(Status::Success));
+        assert_eq!(entry.code().into_inner(), 0x02);
+        assert_eq!(entry.cluster_id(), 0x0001);
+        assert_eq!(entry.timestamp().into_inner(), 0x1234);
+    }
+}
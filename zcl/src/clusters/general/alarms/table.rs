@@ -4,7 +4,8 @@ use le_stream::{FromLeStream, ToLeStream};
 use zb_core::types::{Uint8, Uint32};
 
 /// An entry in the Alarms cluster's table.
-#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, FromLeStream, ToLeStream)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, FromLeStream, ToLeStream)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Entry {
     code: Uint8,
     cluster_id: u16,
@@ -0,0 +1,8 @@
+//! Analog Input (`BACnet` regular) cluster.
+//!
+//! Models a measured analog point, such as a sensor reading.
+
+pub use self::attributes::{Id, Readable, Reportable, SendReport, Writable};
+pub use crate::bacnet::{ApplicationType, EngineeringUnits, ObjectType, Reliability, StatusFlags};
+
+mod attributes;
@@ -0,0 +1,70 @@
+//! Attributes of the Analog Input cluster.
+
+use zb_core::Cluster;
+use zb_core::types::{BacnetObjectId, Bool, Float32, String, Uint8};
+
+use crate::bacnet::{ApplicationType, EngineeringUnits, ObjectType, Reliability, StatusFlags};
+use crate::macros::zcl_attributes;
+
+zcl_attributes! {
+    cluster: Cluster::AnalogInput;
+
+    /// Minimum change of the present value that triggers a change-of-value notification.
+    CovIncrement = 0x0016: Float32 { R, W },
+    /// Description of the point.
+    Description = 0x001c: String<16> { R, W },
+    /// Description of the physical device connected to the point.
+    DeviceType = 0x001f: String { R },
+    /// Highest reliable present value.
+    MaxPresentValue = 0x0041: Float32 { R, W },
+    /// Lowest reliable present value.
+    MinPresentValue = 0x0045: Float32 { R, W },
+    /// `BACnet` object identifier of the point.
+    ObjectIdentifier = 0x004b: BacnetObjectId { R },
+    /// `BACnet` object name of the point.
+    ObjectName = 0x004d: String { R },
+    /// `BACnet` object type of the point.
+    ObjectType = 0x004f: ObjectType { R },
+    /// Whether the present value is decoupled from the physical point.
+    OutOfService = 0x0051: Bool { R, W },
+    /// Current value of the point.
+    PresentValue = 0x0055: Float32 { R, W, P },
+    /// Whether the present value is reliable.
+    Reliability = 0x0067: Reliability { R, W },
+    /// Smallest detectable change of the present value.
+    Resolution = 0x006a: Float32 { R, W },
+    /// Health of the point.
+    StatusFlags = 0x006f: StatusFlags { R, P },
+    /// Engineering units of the present value.
+    EngineeringUnits = 0x0075: EngineeringUnits { R, W },
+    /// Interval in seconds at which the present value is updated.
+    UpdateInterval = 0x0076: Uint8 { R, W },
+    /// Name of the `BACnet` object profile of the point.
+    ProfileName = 0x00a8: String { R },
+    /// Application group, type, and index of the point.
+    ApplicationType = 0x0100: ApplicationType { R },
+}
+
+#[cfg(test)]
+mod tests {
+    use le_stream::ToLeStream;
+    use zb_core::types::Float32;
+
+    use super::SendReport;
+    use crate::Analog;
+    use crate::global::configure_reporting::send::AttributeReportingConfiguration;
+
+    #[test]
+    fn configures_present_value_reporting_with_a_single_precision_change() {
+        let configuration = AttributeReportingConfiguration::from(SendReport::PresentValue(
+            Analog::new(1, 300, Float32::new(0.5)),
+        ));
+
+        assert_eq!(
+            configuration.to_le_stream().collect::<Vec<_>>(),
+            [
+                0x00, 0x55, 0x00, 0x39, 0x01, 0x00, 0x2c, 0x01, 0x00, 0x00, 0x00, 0x3f
+            ]
+        );
+    }
+}
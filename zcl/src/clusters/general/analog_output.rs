@@ -0,0 +1,8 @@
+//! Analog Output (`BACnet` regular) cluster.
+//!
+//! Models a commanded analog point, such as an actuator setpoint.
+
+pub use self::attributes::{Id, Readable, Reportable, SendReport, Writable};
+pub use crate::bacnet::{ApplicationType, EngineeringUnits, ObjectType, Reliability, StatusFlags};
+
+mod attributes;
@@ -0,0 +1,46 @@
+//! Attributes of the Analog Output cluster.
+
+use zb_core::Cluster;
+use zb_core::types::{BacnetObjectId, Bool, Float32, String};
+
+use crate::bacnet::{ApplicationType, EngineeringUnits, ObjectType, Reliability, StatusFlags};
+use crate::macros::zcl_attributes;
+
+zcl_attributes! {
+    cluster: Cluster::AnalogOutput;
+
+    /// Minimum change of the present value that triggers a change-of-value notification.
+    CovIncrement = 0x0016: Float32 { R, W },
+    /// Description of the point.
+    Description = 0x001c: String<16> { R, W },
+    /// Description of the physical device connected to the point.
+    DeviceType = 0x001f: String { R },
+    /// Highest reliable present value.
+    MaxPresentValue = 0x0041: Float32 { R, W },
+    /// Lowest reliable present value.
+    MinPresentValue = 0x0045: Float32 { R, W },
+    /// `BACnet` object identifier of the point.
+    ObjectIdentifier = 0x004b: BacnetObjectId { R },
+    /// `BACnet` object name of the point.
+    ObjectName = 0x004d: String { R },
+    /// `BACnet` object type of the point.
+    ObjectType = 0x004f: ObjectType { R },
+    /// Whether the present value is decoupled from the physical point.
+    OutOfService = 0x0051: Bool { R, W },
+    /// Current value of the point.
+    PresentValue = 0x0055: Float32 { R, W, P },
+    /// Whether the present value is reliable.
+    Reliability = 0x0067: Reliability { R, W },
+    /// Present value used when all priority array entries are relinquished.
+    RelinquishDefault = 0x0068: Float32 { R, W },
+    /// Smallest detectable change of the present value.
+    Resolution = 0x006a: Float32 { R, W },
+    /// Health of the point.
+    StatusFlags = 0x006f: StatusFlags { R, P },
+    /// Engineering units of the present value.
+    EngineeringUnits = 0x0075: EngineeringUnits { R, W },
+    /// Name of the `BACnet` object profile of the point.
+    ProfileName = 0x00a8: String { R },
+    /// Application group, type, and index of the point.
+    ApplicationType = 0x0100: ApplicationType { R },
+}
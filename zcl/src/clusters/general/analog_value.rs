@@ -0,0 +1,8 @@
+//! Analog Value (`BACnet` regular) cluster.
+//!
+//! Models a analog control system parameter.
+
+pub use self::attributes::{Id, Readable, Reportable, SendReport, Writable};
+pub use crate::bacnet::{ApplicationType, EngineeringUnits, ObjectType, Reliability, StatusFlags};
+
+mod attributes;
@@ -0,0 +1,18 @@
+//! Attribute types shared by the `BACnet` regular Analog, Binary, and Multistate clusters.
+//!
+//! The Analog, Binary, and Multistate Input, Output, and Value clusters model the present value of
+//! a physical or virtual point along with the `BACnet` object properties that describe it. Array
+//! and structure attributes, such as the priority array and the multistate state texts, are not
+//! modeled.
+
+pub use self::application_type::ApplicationType;
+pub use self::polarity::Polarity;
+pub use self::reliability::Reliability;
+pub use self::status_flags::StatusFlags;
+pub use self::types::{EngineeringUnits, ObjectType};
+
+mod application_type;
+mod polarity;
+mod reliability;
+mod status_flags;
+mod types;
@@ -0,0 +1,45 @@
+use zb_core::types::Uint32;
+
+use crate::macros::zcl_attribute_newtype;
+
+zcl_attribute_newtype! {
+    /// The application group, type, and index of a point.
+    pub struct ApplicationType(Uint32) => Uint32;
+}
+
+impl ApplicationType {
+    /// Return the application group.
+    #[must_use]
+    pub const fn group(self) -> u8 {
+        self.0.into_inner().to_be_bytes()[0]
+    }
+
+    /// Return the application type within the group.
+    #[must_use]
+    pub const fn typ(self) -> u8 {
+        self.0.into_inner().to_be_bytes()[1]
+    }
+
+    /// Return the application index within the type.
+    #[must_use]
+    pub const fn index(self) -> u16 {
+        let [_, _, high, low] = self.0.into_inner().to_be_bytes();
+        u16::from_be_bytes([high, low])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zb_core::types::Uint32;
+
+    use super::ApplicationType;
+
+    #[test]
+    fn splits_group_type_and_index() {
+        let application_type = ApplicationType::new(Uint32::new(0x0102_0304));
+
+        assert_eq!(application_type.group(), 0x01);
+        assert_eq!(application_type.typ(), 0x02);
+        assert_eq!(application_type.index(), 0x0304);
+    }
+}
@@ -0,0 +1,11 @@
+use crate::macros::zcl_attribute_newtype;
+
+zcl_attribute_newtype! {
+    /// Relationship between the physical state of a binary point and its present value.
+    pub enum Polarity: Enum8 {
+        /// The present value is active while the physical state is active.
+        Normal = 0x00,
+        /// The present value is active while the physical state is inactive.
+        Reverse = 0x01,
+    }
+}
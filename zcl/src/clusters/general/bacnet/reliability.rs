@@ -0,0 +1,29 @@
+use crate::macros::zcl_attribute_newtype;
+
+zcl_attribute_newtype! {
+    /// Whether the present value of a point is reliable.
+    pub enum Reliability: Enum8 {
+        /// The present value is reliable.
+        NoFaultDetected = 0x00,
+        /// The sensor is disconnected.
+        NoSensor = 0x01,
+        /// The sensor reading is above the range of the point.
+        OverRange = 0x02,
+        /// The sensor reading is below the range of the point.
+        UnderRange = 0x03,
+        /// The connection of the point is open.
+        OpenLoop = 0x04,
+        /// The connection of the point is short-circuited.
+        ShortedLoop = 0x05,
+        /// The output is disconnected.
+        NoOutput = 0x06,
+        /// Another fault makes the present value unreliable.
+        UnreliableOther = 0x07,
+        /// A process error makes the present value unreliable.
+        ProcessError = 0x08,
+        /// The present value of a multistate point matches a fault state.
+        MultiStateFault = 0x09,
+        /// The point is configured inconsistently.
+        ConfigurationError = 0x0a,
+    }
+}
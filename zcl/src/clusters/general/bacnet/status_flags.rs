@@ -0,0 +1,15 @@
+use crate::macros::zcl_attribute_newtype;
+
+zcl_attribute_newtype! {
+    /// Health of a point.
+    pub bitflags StatusFlags(u8) => Map8 {
+        /// The point is in alarm.
+        const IN_ALARM = 0b0000_0001;
+        /// The reliability of the point is not `NoFaultDetected`.
+        const FAULT = 0b0000_0010;
+        /// The output of the point is overridden by local means.
+        const OVERRIDDEN = 0b0000_0100;
+        /// The point is out of service.
+        const OUT_OF_SERVICE = 0b0000_1000;
+    }
+}
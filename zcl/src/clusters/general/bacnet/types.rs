@@ -0,0 +1,13 @@
+use zb_core::types::Enum16;
+
+use crate::macros::zcl_attribute_newtype;
+
+zcl_attribute_newtype! {
+    /// The `BACnet` engineering units of a present value.
+    pub struct EngineeringUnits(Enum16) => Enum16;
+}
+
+zcl_attribute_newtype! {
+    /// The `BACnet` object type of a point.
+    pub struct ObjectType(Enum16) => Enum16;
+}
@@ -0,0 +1,8 @@
+//! Binary Input (`BACnet` regular) cluster.
+//!
+//! Models a measured binary point, such as a contact input.
+
+pub use self::attributes::{Id, Readable, Reportable, SendReport, Writable};
+pub use crate::bacnet::{ApplicationType, ObjectType, Polarity, Reliability, StatusFlags};
+
+mod attributes;
@@ -0,0 +1,8 @@
+//! Binary Output (`BACnet` regular) cluster.
+//!
+//! Models a commanded binary point, such as a relay.
+
+pub use self::attributes::{Id, Readable, Reportable, SendReport, Writable};
+pub use crate::bacnet::{ApplicationType, ObjectType, Polarity, Reliability, StatusFlags};
+
+mod attributes;
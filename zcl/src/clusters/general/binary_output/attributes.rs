@@ -0,0 +1,50 @@
+//! Attributes of the Binary Output cluster.
+
+use zb_core::Cluster;
+use zb_core::types::{BacnetObjectId, Bool, String, Uint32};
+
+use crate::bacnet::{ApplicationType, ObjectType, Polarity, Reliability, StatusFlags};
+use crate::macros::zcl_attributes;
+
+zcl_attributes! {
+    cluster: Cluster::BinaryOutput;
+
+    /// Text describing the active state.
+    ActiveText = 0x0004: String<16> { R, W },
+    /// Number of changes of the present value since the last reset.
+    ChangeOfStateCount = 0x000f: Uint32 { R, W },
+    /// Description of the point.
+    Description = 0x001c: String<16> { R, W },
+    /// Description of the physical device connected to the point.
+    DeviceType = 0x001f: String { R },
+    /// Time in seconds the present value has been active since the last reset.
+    ElapsedActiveTime = 0x0021: Uint32 { R, W },
+    /// Text describing the inactive state.
+    InactiveText = 0x002e: String<16> { R, W },
+    /// Minimum time in seconds the present value stays inactive after becoming inactive.
+    MinimumOffTime = 0x0042: Uint32 { R, W },
+    /// Minimum time in seconds the present value stays active after becoming active.
+    MinimumOnTime = 0x0043: Uint32 { R, W },
+    /// `BACnet` object identifier of the point.
+    ObjectIdentifier = 0x004b: BacnetObjectId { R },
+    /// `BACnet` object name of the point.
+    ObjectName = 0x004d: String { R },
+    /// `BACnet` object type of the point.
+    ObjectType = 0x004f: ObjectType { R },
+    /// Whether the present value is decoupled from the physical point.
+    OutOfService = 0x0051: Bool { R, W },
+    /// Relationship between the physical state and the present value.
+    Polarity = 0x0054: Polarity { R },
+    /// Current state of the point.
+    PresentValue = 0x0055: Bool { R, W, P },
+    /// Whether the present value is reliable.
+    Reliability = 0x0067: Reliability { R, W },
+    /// Present value used when all priority array entries are relinquished.
+    RelinquishDefault = 0x0068: Bool { R, W },
+    /// Health of the point.
+    StatusFlags = 0x006f: StatusFlags { R, P },
+    /// Name of the `BACnet` object profile of the point.
+    ProfileName = 0x00a8: String { R },
+    /// Application group, type, and index of the point.
+    ApplicationType = 0x0100: ApplicationType { R },
+}
@@ -0,0 +1,8 @@
+//! Binary Value (`BACnet` regular) cluster.
+//!
+//! Models a binary control system parameter.
+
+pub use self::attributes::{Id, Readable, Reportable, SendReport, Writable};
+pub use crate::bacnet::{ApplicationType, ObjectType, Reliability, StatusFlags};
+
+mod attributes;
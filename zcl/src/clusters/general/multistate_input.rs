@@ -0,0 +1,8 @@
+//! Multistate Input (`BACnet` regular) cluster.
+//!
+//! Models a measured point with a finite number of states, such as a selector switch.
+
+pub use self::attributes::{Id, Readable, Reportable, SendReport, Writable};
+pub use crate::bacnet::{ApplicationType, ObjectType, Reliability, StatusFlags};
+
+mod attributes;
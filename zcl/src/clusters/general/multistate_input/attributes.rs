@@ -0,0 +1,36 @@
+//! Attributes of the Multistate Input cluster.
+
+use zb_core::Cluster;
+use zb_core::types::{BacnetObjectId, Bool, String, Uint16};
+
+use crate::bacnet::{ApplicationType, ObjectType, Reliability, StatusFlags};
+use crate::macros::zcl_attributes;
+
+zcl_attributes! {
+    cluster: Cluster::MultistateInput;
+
+    /// Description of the point.
+    Description = 0x001c: String<16> { R, W },
+    /// Description of the physical device connected to the point.
+    DeviceType = 0x001f: String { R },
+    /// Number of states the present value may take.
+    NumberOfStates = 0x004a: Uint16 { R, W },
+    /// `BACnet` object identifier of the point.
+    ObjectIdentifier = 0x004b: BacnetObjectId { R },
+    /// `BACnet` object name of the point.
+    ObjectName = 0x004d: String { R },
+    /// `BACnet` object type of the point.
+    ObjectType = 0x004f: ObjectType { R },
+    /// Whether the present value is decoupled from the physical point.
+    OutOfService = 0x0051: Bool { R, W },
+    /// Current state of the point, numbered from one.
+    PresentValue = 0x0055: Uint16 { R, W, P },
+    /// Whether the present value is reliable.
+    Reliability = 0x0067: Reliability { R, W },
+    /// Health of the point.
+    StatusFlags = 0x006f: StatusFlags { R, P },
+    /// Name of the `BACnet` object profile of the point.
+    ProfileName = 0x00a8: String { R },
+    /// Application group, type, and index of the point.
+    ApplicationType = 0x0100: ApplicationType { R },
+}
@@ -0,0 +1,8 @@
+//! Multistate Output (`BACnet` regular) cluster.
+//!
+//! Models a commanded point with a finite number of states, such as a fan speed.
+
+pub use self::attributes::{Id, Readable, Reportable, SendReport, Writable};
+pub use crate::bacnet::{ApplicationType, ObjectType, Reliability, StatusFlags};
+
+mod attributes;
@@ -0,0 +1,8 @@
+//! Multistate Value (`BACnet` regular) cluster.
+//!
+//! Models a control system parameter with a finite number of states.
+
+pub use self::attributes::{Id, Readable, Reportable, SendReport, Writable};
+pub use crate::bacnet::{ApplicationType, ObjectType, Reliability, StatusFlags};
+
+mod attributes;
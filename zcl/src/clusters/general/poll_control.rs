@@ -0,0 +1,72 @@
+//! Poll Control cluster.
+//!
+//! Sleepy end devices send a [`CheckIn`] at the check-in interval. The client answers with a
+//! [`CheckInResponse`] that may keep the device in fast poll mode while queued messages are sent.
+
+pub use self::attributes::{Id, Readable, Reportable, SendReport, Writable};
+pub use self::commands::{
+    CheckIn, CheckInResponse, Command, FastPollStop, SetLongPollInterval, SetShortPollInterval,
+};
+
+mod attributes;
+mod commands;
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use le_stream::ToLeStream;
+    use zb_core::Cluster as ClusterId;
+
+    use super::{
+        CheckIn, CheckInResponse, FastPollStop, SetLongPollInterval, SetShortPollInterval,
+    };
+    use crate::{Command as CommandMetadata, Directed, Frame, Header, Scope};
+
+    const SEQUENCE_NUMBER: u8 = 0x17;
+    const FAST_POLL_TIMEOUT: u16 = 40;
+    const LONG_POLL_INTERVAL: u32 = 14_400;
+    const SHORT_POLL_INTERVAL: u16 = 2;
+
+    fn assert_runtime_round_trip<T>(command: T)
+    where
+        T: Clone + CommandMetadata + Directed + Into<crate::Cluster> + ToLeStream,
+    {
+        let expected = command.clone().into();
+        let header = Header::new(
+            Scope::ClusterSpecific,
+            T::DIRECTION,
+            T::DISABLE_DEFAULT_RESPONSE,
+            T::MANUFACTURER_CODE,
+            SEQUENCE_NUMBER,
+            T::ID,
+        );
+        let bytes = header.to_le_stream().chain(command.to_le_stream());
+        let frame = Frame::parse(ClusterId::PollControl.as_u16(), bytes)
+            .expect("valid Poll Control command should parse");
+
+        assert_eq!(frame.into_payload(), expected);
+    }
+
+    #[test]
+    fn every_command_round_trips_through_runtime_dispatch() {
+        assert_runtime_round_trip(CheckIn);
+        assert_runtime_round_trip(CheckInResponse::new(true, FAST_POLL_TIMEOUT));
+        assert_runtime_round_trip(FastPollStop);
+        assert_runtime_round_trip(SetLongPollInterval::new(LONG_POLL_INTERVAL));
+        assert_runtime_round_trip(SetShortPollInterval::new(SHORT_POLL_INTERVAL));
+    }
+
+    #[test]
+    fn check_in_response_encodes_fast_poll_request() {
+        let response = CheckInResponse::new(true, FAST_POLL_TIMEOUT);
+
+        assert_eq!(
+            response.to_le_stream().collect::<Vec<_>>(),
+            [0x01, 40, 0x00]
+        );
+        assert!(response.start_fast_polling());
+        assert_eq!(response.fast_poll_timeout(), Some(Duration::from_secs(10)));
+        assert_eq!(CheckInResponse::new(false, 0).fast_poll_timeout(), None);
+    }
+}
@@ -0,0 +1,27 @@
+//! Attributes of the Poll Control cluster.
+//!
+//! Intervals and timeouts are expressed in quarter seconds.
+
+use zb_core::Cluster;
+use zb_core::types::{Uint16, Uint32};
+
+use crate::macros::zcl_attributes;
+
+zcl_attributes! {
+    cluster: Cluster::PollControl;
+
+    /// Interval between two check-ins of the device.
+    CheckInInterval = 0x0000: Uint32 { R, W },
+    /// Longest interval between two data polls of the device.
+    LongPollInterval = 0x0001: Uint32 { R },
+    /// Interval between two data polls while the device is fast polling.
+    ShortPollInterval = 0x0002: Uint16 { R },
+    /// Time the device stays in fast poll mode after a check-in response asks it to.
+    FastPollTimeout = 0x0003: Uint16 { R, W },
+    /// Smallest check-in interval the device accepts.
+    CheckInIntervalMin = 0x0004: Uint32 { R },
+    /// Smallest long poll interval the device accepts.
+    LongPollIntervalMin = 0x0005: Uint32 { R },
+    /// Largest fast poll timeout the device accepts.
+    FastPollTimeoutMax = 0x0006: Uint16 { R },
+}
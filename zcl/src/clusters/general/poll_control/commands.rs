@@ -0,0 +1,26 @@
+//! Commands of the Poll Control cluster.
+
+use zb_core::Cluster;
+
+pub use self::check_in::CheckIn;
+pub use self::check_in_response::CheckInResponse;
+pub use self::fast_poll_stop::FastPollStop;
+pub use self::set_long_poll_interval::SetLongPollInterval;
+pub use self::set_short_poll_interval::SetShortPollInterval;
+use crate::macros::zcl_command_enum;
+
+mod check_in;
+mod check_in_response;
+mod fast_poll_stop;
+mod set_long_poll_interval;
+mod set_short_poll_interval;
+
+// Commands of the Poll Control cluster.
+zcl_command_enum! {
+    { Cluster::PollControl } => PollControl;
+    CheckInResponse(CheckInResponse),
+    FastPollStop(FastPollStop),
+    SetLongPollInterval(SetLongPollInterval),
+    SetShortPollInterval(SetShortPollInterval),
+    CheckIn(CheckIn),
+}
@@ -0,0 +1,16 @@
+use zb_core::{Cluster, Direction};
+
+use super::CheckInResponse;
+use crate::macros::zcl_command;
+
+zcl_command! {
+    /// Announce that a sleepy device is awake and polling for queued messages.
+    CheckIn {
+        { Cluster::PollControl } => PollControl;
+        command_id: 0x00;
+        direction: Direction::ServerToClient;
+        response: CheckInResponse;
+        derive(Default);
+        fields;
+    }
+}
@@ -0,0 +1,59 @@
+use core::time::Duration;
+
+use zb_core::{Cluster, Direction};
+
+use crate::macros::zcl_command;
+
+/// Duration of one quarter second, the unit of Poll Control intervals and timeouts.
+const QUARTER_SECOND: Duration = Duration::from_millis(250);
+
+zcl_command! {
+    /// Answer a [`CheckIn`](crate::clusters::general::poll_control::CheckIn), optionally keeping
+    /// the device in fast poll mode.
+    CheckInResponse {
+        { Cluster::PollControl } => PollControl;
+        command_id: 0x00;
+        direction: Direction::ClientToServer;
+        derive(Copy);
+        fields {
+            start_fast_polling: u8,
+            fast_poll_timeout: u16,
+        }
+
+        constructor {
+            /// Create a new `CheckInResponse` command.
+            ///
+            /// A `fast_poll_timeout` of zero selects the `FastPollTimeout` attribute of the device.
+            #[must_use]
+            pub const fn new(start_fast_polling: bool, fast_poll_timeout: u16) -> Self {
+                Self {
+                    start_fast_polling: start_fast_polling as u8,
+                    fast_poll_timeout,
+                }
+            }
+        }
+
+        getters {
+            /// Return whether the device should enter fast poll mode.
+            #[must_use]
+            pub const fn start_fast_polling(self) -> bool {
+                self.start_fast_polling != 0
+            }
+
+            /// Return the fast poll timeout in quarter seconds.
+            #[must_use]
+            pub const fn fast_poll_timeout_quarter_secs(self) -> u16 {
+                self.fast_poll_timeout
+            }
+
+            /// Return the fast poll timeout.
+            ///
+            /// Returns `None` if the device should use its `FastPollTimeout` attribute instead.
+            #[must_use]
+            pub fn fast_poll_timeout(self) -> Option<Duration> {
+                (self.fast_poll_timeout != 0)
+                    .then(|| QUARTER_SECOND * u32::from(self.fast_poll_timeout))
+            }
+        }
+    }
+}
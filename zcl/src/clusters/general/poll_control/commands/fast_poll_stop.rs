@@ -0,0 +1,14 @@
+use zb_core::{Cluster, Direction};
+
+use crate::macros::zcl_command;
+
+zcl_command! {
+    /// Stop fast polling before the fast poll timeout expires.
+    FastPollStop {
+        { Cluster::PollControl } => PollControl;
+        command_id: 0x01;
+        direction: Direction::ClientToServer;
+        derive(Default);
+        fields;
+    }
+}
@@ -0,0 +1,24 @@
+use zb_core::{Cluster, Direction};
+
+use crate::macros::zcl_command;
+
+zcl_command! {
+    /// Set the `LongPollInterval` attribute of a device.
+    SetLongPollInterval {
+        { Cluster::PollControl } => PollControl;
+        command_id: 0x02;
+        direction: Direction::ClientToServer;
+        derive(Copy);
+        fields {
+            new_long_poll_interval: u32,
+        }
+
+        getters {
+            /// Return the new long poll interval in quarter seconds.
+            #[must_use]
+            pub const fn new_long_poll_interval(self) -> u32 {
+                self.new_long_poll_interval
+            }
+        }
+    }
+}
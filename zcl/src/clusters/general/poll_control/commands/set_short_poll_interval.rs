@@ -0,0 +1,24 @@
+use zb_core::{Cluster, Direction};
+
+use crate::macros::zcl_command;
+
+zcl_command! {
+    /// Set the `ShortPollInterval` attribute of a device.
+    SetShortPollInterval {
+        { Cluster::PollControl } => PollControl;
+        command_id: 0x03;
+        direction: Direction::ClientToServer;
+        derive(Copy);
+        fields {
+            new_short_poll_interval: u16,
+        }
+
+        getters {
+            /// Return the new short poll interval in quarter seconds.
+            #[must_use]
+            pub const fn new_short_poll_interval(self) -> u16 {
+                self.new_short_poll_interval
+            }
+        }
+    }
+}
@@ -26,7 +26,7 @@ zcl_attributes! {
     MainsVoltageDwellTripPoint = 0x0013: Uint16 { R, W },
 
     /// Current measured battery voltage, in units of 100 mV.
-    BatteryVoltage = 0x0020: Uint8 { R },
+    BatteryVoltage = 0x0020: Uint8 { R, P },
     /// Remaining battery life as a half-integer percentage.
     BatteryPercentageRemaining = 0x0021: Uint8 { R, P },
 
@@ -62,7 +62,7 @@ zcl_attributes! {
     BatteryAlarmState = 0x003e: BatteryAlarmState { R, P },
 
     /// Current measured battery source 2 voltage, in units of 100 mV.
-    Battery2Voltage = 0x0040: Uint8 { R },
+    Battery2Voltage = 0x0040: Uint8 { R, P },
     /// Remaining battery source 2 life as a half-integer percentage.
     Battery2PercentageRemaining = 0x0041: Uint8 { R, P },
 
@@ -98,7 +98,7 @@ zcl_attributes! {
     Battery2AlarmState = 0x005e: BatteryAlarmState { R, P },
 
     /// Current measured battery source 3 voltage, in units of 100 mV.
-    Battery3Voltage = 0x0060: Uint8 { R },
+    Battery3Voltage = 0x0060: Uint8 { R, P },
     /// Remaining battery source 3 life as a half-integer percentage.
     Battery3PercentageRemaining = 0x0061: Uint8 { R, P },
 
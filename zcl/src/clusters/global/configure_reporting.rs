@@ -42,7 +42,7 @@ mod tests {
     use zb_core::Direction;
     use zb_core::types::{Bool, Uint16};
 
-    use super::{Receive, Send, receive};
+    use super::{AttributeStatus, Receive, Response, Send, receive};
     use crate::clusters::general::{level, on_off};
     use crate::{Analog, Directed, Discrete};
 
@@ -118,4 +118,21 @@ mod tests {
         assert_eq!(bytes, expected);
         assert_eq!(Receive::from_le_stream(bytes.into_iter()), Some(command));
     }
+
+    #[test]
+    fn parses_single_success_and_per_attribute_responses() {
+        assert_eq!(
+            Response::from_le_stream([0x00].into_iter()),
+            Some(Response::new(Box::new([AttributeStatus::success()])))
+        );
+
+        let mut bytes = vec![0x86, Direction::ClientToServer as u8];
+        bytes.extend(SEND_ATTRIBUTE_ID.to_le_bytes());
+        let response = Response::from_le_stream(bytes.into_iter()).expect("valid response");
+        assert_eq!(
+            response.status(),
+            [AttributeStatus::new(0x86, 0x00, SEND_ATTRIBUTE_ID)]
+        );
+        assert_eq!(response.status()[0].attribute_id(), Some(SEND_ATTRIBUTE_ID));
+    }
 }
@@ -1,12 +1,15 @@
 use le_stream::{FromLeStream, ToLeStream};
 
 /// Status of an attribute reporting configuration.
+///
+/// A device that configured every attribute successfully replies with a single record carrying
+/// only the `SUCCESS` status, in which case the direction and attribute ID are absent.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, FromLeStream, ToLeStream)]
 pub struct AttributeStatus {
     status: u8,
-    direction: u8,
-    attribute_id: u16,
+    direction: Option<u8>,
+    attribute_id: Option<u16>,
 }
 
 impl AttributeStatus {
@@ -15,8 +18,18 @@ impl AttributeStatus {
     pub const fn new(status: u8, direction: u8, attribute_id: u16) -> Self {
         Self {
             status,
-            direction,
-            attribute_id,
+            direction: Some(direction),
+            attribute_id: Some(attribute_id),
+        }
+    }
+
+    /// Creates the single `AttributeStatus` reporting that every attribute was configured.
+    #[must_use]
+    pub const fn success() -> Self {
+        Self {
+            status: 0x00,
+            direction: None,
+            attribute_id: None,
         }
     }
 
@@ -26,15 +39,15 @@ impl AttributeStatus {
         self.status
     }
 
-    /// Returns the direction.
+    /// Returns the direction, unless every attribute was configured successfully.
     #[must_use]
-    pub const fn direction(&self) -> u8 {
+    pub const fn direction(&self) -> Option<u8> {
         self.direction
     }
 
-    /// Returns the attribute ID.
+    /// Returns the attribute ID, unless every attribute was configured successfully.
     #[must_use]
-    pub const fn attribute_id(&self) -> u16 {
+    pub const fn attribute_id(&self) -> Option<u16> {
         self.attribute_id
     }
 }
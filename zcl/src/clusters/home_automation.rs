@@ -0,0 +1,3 @@
+//! Home Automation cluster definitions.
+
+pub mod diagnostics;
@@ -0,0 +1,7 @@
+//! Diagnostics cluster.
+//!
+//! The server side exposes hardware and stack counters of a device for remote inspection.
+
+pub use self::attributes::{Id, Readable, Reportable, SendReport, Writable};
+
+mod attributes;
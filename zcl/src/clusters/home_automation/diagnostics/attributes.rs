@@ -0,0 +1,108 @@
+//! Attributes of the Diagnostics cluster.
+
+use zb_core::Cluster;
+use zb_core::types::{Int8, Uint8, Uint16, Uint32};
+
+use crate::macros::zcl_attributes;
+
+zcl_attributes! {
+    cluster: Cluster::Diagnostics;
+
+    /// Number of device resets.
+    NumberOfResets = 0x0000: Uint16 { R },
+    /// Number of writes to persistent memory.
+    PersistentMemoryWrites = 0x0001: Uint16 { R },
+    /// Number of received MAC broadcasts.
+    MacRxBcast = 0x0100: Uint32 { R },
+    /// Number of transmitted MAC broadcasts.
+    MacTxBcast = 0x0101: Uint32 { R },
+    /// Number of received MAC unicasts.
+    MacRxUcast = 0x0102: Uint32 { R },
+    /// Number of transmitted MAC unicasts.
+    MacTxUcast = 0x0103: Uint32 { R },
+    /// Number of MAC unicast retries.
+    MacTxUcastRetry = 0x0104: Uint16 { R },
+    /// Number of failed MAC unicasts.
+    MacTxUcastFail = 0x0105: Uint16 { R },
+    /// Number of received APS broadcasts.
+    ApsRxBcast = 0x0106: Uint16 { R },
+    /// Number of transmitted APS broadcasts.
+    ApsTxBcast = 0x0107: Uint16 { R },
+    /// Number of received APS unicasts.
+    ApsRxUcast = 0x0108: Uint16 { R },
+    /// Number of successfully transmitted APS unicasts.
+    ApsTxUcastSuccess = 0x0109: Uint16 { R },
+    /// Number of APS unicast retries.
+    ApsTxUcastRetry = 0x010A: Uint16 { R },
+    /// Number of failed APS unicasts.
+    ApsTxUcastFail = 0x010B: Uint16 { R },
+    /// Number of initiated route discoveries.
+    RouteDiscInitiated = 0x010C: Uint16 { R },
+    /// Number of neighbors added to the neighbor table.
+    NeighborAdded = 0x010D: Uint16 { R },
+    /// Number of neighbors removed from the neighbor table.
+    NeighborRemoved = 0x010E: Uint16 { R },
+    /// Number of neighbors removed because they became stale.
+    NeighborStale = 0x010F: Uint16 { R },
+    /// Number of join indications.
+    JoinIndication = 0x0110: Uint16 { R },
+    /// Number of children that moved away.
+    ChildMoved = 0x0111: Uint16 { R },
+    /// Number of NWK frame counter failures.
+    NwkFcFailure = 0x0112: Uint16 { R },
+    /// Number of APS frame counter failures.
+    ApsFcFailure = 0x0113: Uint16 { R },
+    /// Number of APS frames secured with an unauthorized key.
+    ApsUnauthorizedKey = 0x0114: Uint16 { R },
+    /// Number of NWK decryption failures.
+    NwkDecryptFailures = 0x0115: Uint16 { R },
+    /// Number of APS decryption failures.
+    ApsDecryptFailures = 0x0116: Uint16 { R },
+    /// Number of failed packet buffer allocations.
+    PacketBufferAllocateFailures = 0x0117: Uint16 { R },
+    /// Number of relayed unicasts.
+    RelayedUcast = 0x0118: Uint16 { R },
+    /// Number of times the PHY to MAC queue limit was reached.
+    PhyToMacQueueLimitReached = 0x0119: Uint16 { R },
+    /// Number of frames dropped by packet validation.
+    PacketValidateDropCount = 0x011A: Uint16 { R },
+    /// Average number of MAC retries per sent APS message.
+    AverageMacRetryPerApsMessageSent = 0x011B: Uint16 { R },
+    /// Link quality of the last received message.
+    LastMessageLqi = 0x011C: Uint8 { R },
+    /// Received signal strength of the last received message in dBm.
+    LastMessageRssi = 0x011D: Int8 { R },
+}
+
+#[cfg(test)]
+mod tests {
+    use zb_core::types::{Int8, Type, Uint16};
+
+    use super::{Id, Readable};
+
+    const RETRIES: u16 = 42;
+    const RSSI: i8 = -70;
+
+    #[test]
+    fn parses_counter_attribute() {
+        let attribute =
+            Readable::try_from((Id::MacTxUcastRetry, Type::Uint16(Uint16::new(RETRIES))))
+                .expect("valid MAC retry attribute");
+
+        assert_eq!(attribute, Readable::MacTxUcastRetry(Uint16::new(RETRIES)));
+    }
+
+    #[test]
+    fn parses_signed_rssi_attribute() {
+        let attribute = Readable::try_from((Id::LastMessageRssi, Type::Int8(Int8::new(RSSI))))
+            .expect("valid RSSI attribute");
+
+        assert_eq!(attribute, Readable::LastMessageRssi(Int8::new(RSSI)));
+    }
+
+    #[test]
+    fn resolves_attribute_ids() {
+        assert_eq!(Id::try_from(0x0117), Ok(Id::PacketBufferAllocateFailures));
+        assert_eq!(u16::from(Id::LastMessageLqi), 0x011C);
+    }
+}
@@ -4,10 +4,12 @@
 //! cluster-specific commands, and generated access-specific attribute enums.
 //!
 //! Runtime command dispatch currently covers global commands plus the Basic, Groups, Identify,
-//! On/Off, Level Control, Alarms, Scenes, OTA Upgrade, Color Control, and IAS Zone clusters.
-//! Attribute modules are broader and currently cover implemented General, Lighting, Measurement
-//! and Sensing, and IAS clusters. Use [`AttributeReport::parse`] to construct a typed reportable
-//! attribute from a cluster ID, attribute ID, and raw [`zb_core::types::Type`].
+//! On/Off, Level Control, Alarms, Scenes, OTA Upgrade, Poll Control, Color Control, IAS Zone, and
+//! Touchlink Commissioning clusters. Attribute modules are broader and currently cover implemented
+//! General, including the `BACnet`-style Analog, Binary, and Multistate clusters, Lighting,
+//! Measurement and Sensing, IAS, and Home Automation clusters. Use [`AttributeReport::parse`] to
+//! construct a typed reportable attribute from a cluster ID, attribute ID, and raw
+//! [`zb_core::types::Type`].
 //!
 //! Set `ZCL_DISABLE_DEFAULT_RESPONSE=true` in the build environment to make commands that do not
 //! specify their own default-response behavior set the disable-default-response bit in outgoing
@@ -17,10 +19,14 @@ pub use self::attributes::{
     Analog, AttributeReport, Discrete, InvalidType, ParseAttributeError, Readable, Reportable,
     Writable,
 };
+pub use self::clusters::commissioning::touchlink;
 pub use self::clusters::general::{
-    alarms, basic, device_temperature_configuration, groups, identify, level, on_off, ota_upgrade,
-    power_configuration, scenes, time,
+    alarms, analog_input, analog_output, analog_value, bacnet, basic, binary_input, binary_output,
+    binary_value, device_temperature_configuration, groups, identify, level, multistate_input,
+    multistate_output, multistate_value, on_off, ota_upgrade, poll_control, power_configuration,
+    scenes, time,
 };
+pub use self::clusters::home_automation::diagnostics;
 pub use self::clusters::lighting::{ballast_configuration, color_control};
 pub use self::clusters::measurement_and_sensing::{
     illuminance_level_sensing, illuminance_measurement, occupancy_sensing,
@@ -1044,17 +1044,46 @@ macro_rules! zcl_command {
 pub(crate) use zcl_command;
 
 macro_rules! zcl_command_enum {
+    // The profile arms precede the default-profile arms, since an optional `profile:` prefix would
+    // be ambiguous with the first command identifier.
     (
         $(#[$attr:meta])*
         { $cluster_id:expr } => $cluster_name:ident;
-        $(profile: $profile:expr;)?
+        profile: $profile:expr;
+        $($variant:ident($command:ty)),+ $(,)?
+    ) => {
+        $crate::macros::zcl_command_enum! {
+            @define
+            [$(#[$attr])*]
+            [$cluster_name]
+            [cluster $cluster_id; $profile]
+            [$($variant($command)),+]
+        }
+    };
+    (
+        $(#[$attr:meta])*
+        { $cluster_id:expr } => $cluster_name:ident;
+        profile: $profile:expr;
         $($command:ident),+ $(,)?
     ) => {
         $crate::macros::zcl_command_enum! {
             @define
             [$(#[$attr])*]
             [$cluster_name]
-            [cluster $cluster_id; $($profile)?]
+            [cluster $cluster_id; $profile]
+            [$($command($command)),+]
+        }
+    };
+    (
+        $(#[$attr:meta])*
+        { $cluster_id:expr } => $cluster_name:ident;
+        $($command:ident),+ $(,)?
+    ) => {
+        $crate::macros::zcl_command_enum! {
+            @define
+            [$(#[$attr])*]
+            [$cluster_name]
+            [cluster $cluster_id;]
             [$($command($command)),+]
         }
     };
@@ -1074,14 +1103,13 @@ macro_rules! zcl_command_enum {
     (
         $(#[$attr:meta])*
         { $cluster_id:expr } => $cluster_name:ident;
-        $(profile: $profile:expr;)?
         $($variant:ident($command:ty)),+ $(,)?
     ) => {
         $crate::macros::zcl_command_enum! {
             @define
             [$(#[$attr])*]
             [$cluster_name]
-            [cluster $cluster_id; $($profile)?]
+            [cluster $cluster_id;]
             [$($variant($command)),+]
         }
     };
@@ -2850,6 +2878,9 @@ macro_rules! zcl_attributes {
     (@classify_send_report_variant $manufacturer_code:tt $variants:tt $type_id_arms:tt $conversion_arms:tt $rest:tt $attrs:tt $variant:tt [Int64] $id:tt) => {
         $crate::macros::zcl_attributes! { @send_report_analog $manufacturer_code $variants $type_id_arms $conversion_arms $rest $attrs $variant [Int64] $id }
     };
+    (@classify_send_report_variant $manufacturer_code:tt $variants:tt $type_id_arms:tt $conversion_arms:tt $rest:tt $attrs:tt $variant:tt [Float32] $id:tt) => {
+        $crate::macros::zcl_attributes! { @send_report_analog $manufacturer_code $variants $type_id_arms $conversion_arms $rest $attrs $variant [Float32] $id }
+    };
     (@classify_send_report_variant $manufacturer_code:tt $variants:tt $type_id_arms:tt $conversion_arms:tt $rest:tt $attrs:tt $variant:tt [TimeOfDay] $id:tt) => {
         $crate::macros::zcl_attributes! { @send_report_analog $manufacturer_code $variants $type_id_arms $conversion_arms $rest $attrs $variant [TimeOfDay] $id }
     };
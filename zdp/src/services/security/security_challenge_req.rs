@@ -1,4 +1,4 @@
-use zb_core::types::tlv::Tlv;
+use zb_core::types::tlv::{ApsFrameCounterChallenge, Tlv};
 
 crate::zdp_command! {
     /// Security Challenge Request.
@@ -7,8 +7,22 @@ crate::zdp_command! {
     group: Security;
     response: crate::SecurityChallengeRsp;
     fields {
-        tlvs: Box<[Tlv]>,
+        tlvs: Box<[Tlv<ApsFrameCounterChallenge>]>,
     }
     getters {
+        /// Return the APS frame counter challenge, if present.
+        #[must_use]
+        pub fn aps_frame_counter_challenge(&self) -> Option<&ApsFrameCounterChallenge> {
+            self.tlvs.iter().find_map(|tlv| match tlv {
+                Tlv::Local(local) => Some(local),
+                Tlv::Global(_) => None,
+            })
+        }
+
+        /// Return the TLVs.
+        #[must_use]
+        pub fn tlvs(&self) -> &[Tlv<ApsFrameCounterChallenge>] {
+            &self.tlvs
+        }
     }
 }
@@ -1,4 +1,4 @@
-use zb_core::types::tlv::Tlv;
+use zb_core::types::tlv::{ApsFrameCounterResponse, Tlv};
 
 crate::zdp_command! {
     /// Security Challenge Response.
@@ -6,8 +6,22 @@ crate::zdp_command! {
     cluster_id: 0x8047;
     group: Security;
     fields {
-        tlvs: Box<[Tlv]>,
+        tlvs: Box<[Tlv<ApsFrameCounterResponse>]>,
     }
     getters {
+        /// Return the APS frame counter response, if present.
+        #[must_use]
+        pub fn aps_frame_counter_response(&self) -> Option<&ApsFrameCounterResponse> {
+            self.tlvs.iter().find_map(|tlv| match tlv {
+                Tlv::Local(local) => Some(local),
+                Tlv::Global(_) => None,
+            })
+        }
+
+        /// Return the TLVs.
+        #[must_use]
+        pub fn tlvs(&self) -> &[Tlv<ApsFrameCounterResponse>] {
+            &self.tlvs
+        }
     }
 }
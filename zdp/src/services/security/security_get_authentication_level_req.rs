@@ -1,4 +1,4 @@
-use zb_core::types::tlv::Tlv;
+use zb_core::types::tlv::{TargetIeeeAddress, Tlv};
 
 crate::zdp_command! {
     /// Security Get Authentication Level Request.
@@ -7,8 +7,22 @@ crate::zdp_command! {
     group: Security;
     response: crate::SecurityGetAuthenticationLevelRsp;
     fields {
-        tlvs: Box<[Tlv]>,
+        tlvs: Box<[Tlv<TargetIeeeAddress>]>,
     }
     getters {
+        /// Return the IEEE address of the device of interest, if present.
+        #[must_use]
+        pub fn target_ieee_address(&self) -> Option<&TargetIeeeAddress> {
+            self.tlvs.iter().find_map(|tlv| match tlv {
+                Tlv::Local(local) => Some(local),
+                Tlv::Global(_) => None,
+            })
+        }
+
+        /// Return the TLVs.
+        #[must_use]
+        pub fn tlvs(&self) -> &[Tlv<TargetIeeeAddress>] {
+            &self.tlvs
+        }
     }
 }
@@ -1,4 +1,4 @@
-use zb_core::types::tlv::Tlv;
+use zb_core::types::tlv::{DeviceAuthenticationLevel, Tlv};
 
 use crate::Status;
 
@@ -9,7 +9,7 @@ crate::zdp_command! {
     group: Security;
     fields {
         status: u8,
-        tlvs: Box<[Tlv]>,
+        tlvs: Box<[Tlv<DeviceAuthenticationLevel>]>,
     }
     getters {
         /// Return the status of the response.
@@ -20,5 +20,20 @@ crate::zdp_command! {
         pub fn status(&self) -> Result<Status, u8> {
             self.status.try_into()
         }
+
+        /// Return the device authentication level, if present.
+        #[must_use]
+        pub fn device_authentication_level(&self) -> Option<&DeviceAuthenticationLevel> {
+            self.tlvs.iter().find_map(|tlv| match tlv {
+                Tlv::Local(local) => Some(local),
+                Tlv::Global(_) => None,
+            })
+        }
+
+        /// Return the TLVs.
+        #[must_use]
+        pub fn tlvs(&self) -> &[Tlv<DeviceAuthenticationLevel>] {
+            &self.tlvs
+        }
     }
 }
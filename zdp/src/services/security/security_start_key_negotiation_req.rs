@@ -1,4 +1,4 @@
-use zb_core::types::tlv::Tlv;
+use zb_core::types::tlv::{Curve25519PublicPoint, Tlv};
 
 crate::zdp_command! {
     /// Security Start Key Negotiation Request.
@@ -7,8 +7,22 @@ crate::zdp_command! {
     group: Security;
     response: crate::SecurityStartKeyNegotiationRsp;
     fields {
-        tlvs: Box<[Tlv]>,
+        tlvs: Box<[Tlv<Curve25519PublicPoint>]>,
     }
     getters {
+        /// Return the initiator's Curve25519 public point, if present.
+        #[must_use]
+        pub fn curve25519_public_point(&self) -> Option<&Curve25519PublicPoint> {
+            self.tlvs.iter().find_map(|tlv| match tlv {
+                Tlv::Local(local) => Some(local),
+                Tlv::Global(_) => None,
+            })
+        }
+
+        /// Return the TLVs.
+        #[must_use]
+        pub fn tlvs(&self) -> &[Tlv<Curve25519PublicPoint>] {
+            &self.tlvs
+        }
     }
 }
@@ -0,0 +1,293 @@
+//! Translation of MT indications into hardware events.
+
+use std::collections::BTreeMap;
+
+use log::{debug, warn};
+use tokio::sync::mpsc::{Receiver, Sender};
+use zb_hw::aps::apsde::{
+    ConfirmStatus, DataConfirm, DataIndication, Destination, IndicationMetadata, IndicationStatus,
+    IndividualEndpoint, NetworkAddress, ReceivedDestination, Security, Source, Status,
+};
+use zb_hw::core::short_id::{Broadcast, Device};
+use zb_hw::core::{Endpoint, FullAddress, GroupId, Profile};
+use zb_hw::{ApsdeEvent, DeviceEvent, Event, NetworkEvent};
+
+use crate::command::CommandId;
+use crate::frame::Frame;
+use crate::parameters::{
+    AfDataConfirm, AfIncomingMsg, ZdoLeaveInd, ZdoMsgCbIncoming, ZdoTcDevInd, status,
+};
+
+/// NWK address of the coordinator.
+const COORDINATOR: u16 = 0x0000;
+
+/// Device state of a started coordinator.
+const DEV_ZB_COORD: u8 = 0x09;
+
+/// Index of the APS link key shared with the trust center.
+const TRUST_CENTER_LINK_KEY_INDEX: u8 = 0;
+
+/// Inputs of the translator.
+pub enum Input {
+    /// An asynchronous frame received from the NCP.
+    Indication(Frame),
+
+    /// An acknowledged transmission that `AF_DATA_CONFIRM` will complete.
+    Transmitted {
+        /// The AF transaction ID, which is the APS counter.
+        transaction_id: u8,
+        /// The destination of the transmission.
+        destination: Destination,
+    },
+}
+
+impl From<Frame> for Input {
+    fn from(frame: Frame) -> Self {
+        Self::Indication(frame)
+    }
+}
+
+/// Translates MT indications into events.
+pub struct Translator {
+    /// Profiles of the registered local endpoints, since `AF_INCOMING_MSG` omits the profile.
+    profiles: BTreeMap<u8, u16>,
+    /// Destinations of acknowledged transmissions, since `AF_DATA_CONFIRM` omits them.
+    destinations: BTreeMap<u8, Destination>,
+}
+
+impl Translator {
+    /// Create a translator for the registered local endpoints.
+    pub const fn new(profiles: BTreeMap<u8, u16>) -> Self {
+        Self {
+            profiles,
+            destinations: BTreeMap::new(),
+        }
+    }
+
+    /// Translate inputs into events until the transport fails.
+    ///
+    /// Reports [`NetworkEvent::Down`] once the input channel closes, since the NCP can no longer
+    /// be reached.
+    pub async fn run(mut self, mut inputs: Receiver<Input>, events: Sender<Event>) {
+        while let Some(input) = inputs.recv().await {
+            let event = match input {
+                Input::Indication(frame) => self.translate(&frame),
+                Input::Transmitted {
+                    transaction_id,
+                    destination,
+                } => {
+                    self.destinations.insert(transaction_id, destination);
+                    None
+                }
+            };
+
+            if let Some(event) = event
+                && events.send(event).await.is_err()
+            {
+                return;
+            }
+        }
+
+        events
+            .send(NetworkEvent::Down.into())
+            .await
+            .unwrap_or_else(drop);
+    }
+
+    fn translate(&mut self, frame: &Frame) -> Option<Event> {
+        match frame.command() {
+            CommandId::ZDO_STATE_CHANGE_IND => state_change(parse(frame)?),
+            CommandId::ZDO_PERMIT_JOIN_IND => Some(permit_join(parse(frame)?)),
+            CommandId::ZDO_TC_DEV_IND => device_joined(&parse(frame)?),
+            CommandId::ZDO_LEAVE_IND => device_left(&parse(frame)?),
+            CommandId::ZDO_MSG_CB_INCOMING => zdo_message(&parse(frame)?),
+            CommandId::AF_INCOMING_MSG => self.incoming_message(parse(frame)?),
+            CommandId::AF_DATA_CONFIRM => self.data_confirm(parse(frame)?),
+            command => {
+                debug!("Ignoring indication {command}");
+                None
+            }
+        }
+    }
+
+    fn incoming_message(&self, message: AfIncomingMsg) -> Option<Event> {
+        let Some(profile_id) = self.profiles.get(&message.destination_endpoint) else {
+            debug!(
+                "Ignoring message to unregistered endpoint {}",
+                message.destination_endpoint
+            );
+            return None;
+        };
+        let endpoint = Endpoint::try_from(message.destination_endpoint).ok()?;
+        let destination = if message.group_id != 0 {
+            ReceivedDestination::Group(GroupId::new(message.group_id)?)
+        } else if message.was_broadcast != 0 {
+            ReceivedDestination::Broadcast {
+                address: Broadcast::AllDevices,
+                endpoint,
+            }
+        } else {
+            ReceivedDestination::Network {
+                address: NetworkAddress::new(COORDINATOR)?,
+                endpoint: IndividualEndpoint::new(endpoint)?,
+            }
+        };
+
+        indication(
+            destination,
+            message.source,
+            Endpoint::try_from(message.source_endpoint).ok()?,
+            *profile_id,
+            message.cluster_id,
+            message.security_use,
+            message.link_quality,
+            message.data.into_data(),
+        )
+    }
+
+    /// Confirm an acknowledged transmission, whose transaction ID is the APS counter.
+    fn data_confirm(&mut self, confirm: AfDataConfirm) -> Option<Event> {
+        let destination = self.destinations.remove(&confirm.transaction_id)?;
+        let status = match confirm.status {
+            status::SUCCESS => ConfirmStatus::Aps(Status::Success),
+            status::APS_NO_ACK => ConfirmStatus::Aps(Status::NoAcknowledgement),
+            status => ConfirmStatus::Network(status),
+        };
+        let confirmation = DataConfirm::new(
+            destination,
+            IndividualEndpoint::new(Endpoint::try_from(confirm.endpoint).ok()?)?,
+            status,
+            (),
+        );
+
+        Some(
+            ApsdeEvent::DataConfirm {
+                counter: confirm.transaction_id,
+                confirmation,
+            }
+            .into(),
+        )
+    }
+}
+
+fn parse<T>(frame: &Frame) -> Option<T>
+where
+    T: le_stream::FromLeStream,
+{
+    let parameters = T::from_le_stream(frame.data().iter().copied());
+
+    if parameters.is_none() {
+        warn!("Discarding malformed {}", frame.command());
+    }
+
+    parameters
+}
+
+fn state_change(state: u8) -> Option<Event> {
+    if state == DEV_ZB_COORD {
+        Some(NetworkEvent::Up.into())
+    } else {
+        debug!("Ignoring device state {state:#04X}");
+        None
+    }
+}
+
+fn permit_join(duration: u8) -> Event {
+    if duration == 0 {
+        NetworkEvent::Closed.into()
+    } else {
+        NetworkEvent::Opened.into()
+    }
+}
+
+fn device_joined(indication: &ZdoTcDevInd) -> Option<Event> {
+    Some(
+        DeviceEvent::Joined(FullAddress::new(
+            indication.ieee_address,
+            Device::new(indication.network_address)?,
+        ))
+        .into(),
+    )
+}
+
+fn device_left(indication: &ZdoLeaveInd) -> Option<Event> {
+    if indication.rejoin != 0 {
+        return None;
+    }
+
+    Some(
+        DeviceEvent::Left(FullAddress::new(
+            indication.ieee_address,
+            Device::new(indication.network_address)?,
+        ))
+        .into(),
+    )
+}
+
+/// Indicate a received ZDO message, whose transaction sequence number Z-Stack strips.
+fn zdo_message(message: &ZdoMsgCbIncoming) -> Option<Event> {
+    let destination = if message.was_broadcast == 0 {
+        ReceivedDestination::Network {
+            address: NetworkAddress::new(COORDINATOR)?,
+            endpoint: IndividualEndpoint::new(Endpoint::Data)?,
+        }
+    } else {
+        ReceivedDestination::Broadcast {
+            address: Broadcast::AllDevices,
+            endpoint: Endpoint::Data,
+        }
+    };
+    let mut asdu = Vec::with_capacity(message.data.len() + 1);
+    asdu.push(message.sequence);
+    asdu.extend_from_slice(&message.data);
+
+    indication(
+        destination,
+        message.source,
+        Endpoint::Data,
+        Profile::Network.as_u16(),
+        message.cluster_id,
+        message.security_use,
+        0,
+        asdu.into(),
+    )
+}
+
+#[expect(
+    clippy::too_many_arguments,
+    reason = "fields mirror the APSDE primitive"
+)]
+fn indication(
+    destination: ReceivedDestination,
+    source: u16,
+    source_endpoint: Endpoint,
+    profile_id: u16,
+    cluster_id: u16,
+    security_use: u8,
+    link_quality: u8,
+    asdu: bytes::Bytes,
+) -> Option<Event> {
+    let security = if security_use == 0 {
+        Security::NetworkKey
+    } else {
+        Security::LinkKey {
+            key_index: TRUST_CENTER_LINK_KEY_INDEX,
+            device_key_pair_entry: (),
+        }
+    };
+    let metadata = IndicationMetadata::new(
+        destination,
+        Source::Network {
+            address: NetworkAddress::new(source)?,
+            endpoint: IndividualEndpoint::new(source_endpoint)?,
+        },
+        profile_id,
+        cluster_id,
+        IndicationStatus::success(),
+        security,
+        link_quality,
+        (),
+    );
+
+    Some(ApsdeEvent::DataIndication(DataIndication::new(metadata, asdu)).into())
+}
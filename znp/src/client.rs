@@ -0,0 +1,62 @@
+//! MT command client.
+
+use bytes::Bytes;
+use le_stream::{FromLeStream, ToLeStream};
+
+use crate::command::CommandId;
+use crate::error::Error;
+use crate::parameters::status;
+use crate::transport::Transport;
+
+/// Sends MT commands over a transport.
+#[derive(Clone, Debug)]
+pub struct Client {
+    transport: Transport,
+}
+
+impl Client {
+    /// Create a client sending commands over the transport.
+    pub const fn new(transport: Transport) -> Self {
+        Self { transport }
+    }
+
+    /// Send a synchronous request and return its parsed response.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the request fails or the response cannot be parsed.
+    pub async fn call<P, R>(&self, command: CommandId, parameters: P) -> Result<R, Error>
+    where
+        P: ToLeStream,
+        R: FromLeStream,
+    {
+        let data: Vec<u8> = parameters.to_le_stream().collect();
+        let response = self.transport.request(command, Bytes::from(data)).await?;
+        R::from_le_stream(response.iter().copied()).ok_or(Error::MalformedResponse(command))
+    }
+
+    /// Send a synchronous request whose response is a single status byte.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the request fails or the NCP reports an unsuccessful status.
+    pub async fn call_with_status<P>(&self, command: CommandId, parameters: P) -> Result<(), Error>
+    where
+        P: ToLeStream,
+    {
+        check(command, self.call(command, parameters).await?)
+    }
+}
+
+/// Return an error unless `status` reports success.
+///
+/// # Errors
+///
+/// Returns [`Error::Status`] if the status is unsuccessful.
+pub const fn check(command: CommandId, status: u8) -> Result<(), Error> {
+    if status == status::SUCCESS {
+        Ok(())
+    } else {
+        Err(Error::Status { command, status })
+    }
+}
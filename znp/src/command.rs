@@ -0,0 +1,129 @@
+//! Identifiers of MT commands.
+
+use std::fmt::{self, Display, Formatter};
+
+/// MT subsystems.
+mod subsystem {
+    pub const RPC_ERROR: u8 = 0x00;
+    pub const SYS: u8 = 0x01;
+    pub const AF: u8 = 0x04;
+    pub const ZDO: u8 = 0x05;
+    pub const UTIL: u8 = 0x07;
+    pub const APP_CNF: u8 = 0x0F;
+}
+
+macro_rules! commands {
+    ($($(#[$doc:meta])* $name:ident = ($subsystem:ident, $id:literal) => $display:literal,)+) => {
+        /// Identifier of an MT command: its subsystem and command ID.
+        #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        pub struct CommandId {
+            subsystem: u8,
+            id: u8,
+        }
+
+        impl CommandId {
+            $(
+                $(#[$doc])*
+                pub const $name: Self = Self::new(subsystem::$subsystem, $id);
+            )+
+
+            /// Create a command identifier.
+            #[must_use]
+            pub const fn new(subsystem: u8, id: u8) -> Self {
+                Self { subsystem, id }
+            }
+
+            /// Return the subsystem.
+            #[must_use]
+            pub const fn subsystem(self) -> u8 {
+                self.subsystem
+            }
+
+            /// Return the command ID within the subsystem.
+            #[must_use]
+            pub const fn id(self) -> u8 {
+                self.id
+            }
+        }
+
+        impl Display for CommandId {
+            fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+                match *self {
+                    $(Self::$name => formatter.write_str($display),)+
+                    Self { subsystem, id } => {
+                        write!(formatter, "MT command {subsystem:#04X}/{id:#04X}")
+                    }
+                }
+            }
+        }
+    };
+}
+
+commands! {
+    /// Reports an unsupported or malformed request.
+    RPC_ERROR = (RPC_ERROR, 0x00) => "RPC error",
+    /// Checks that the NCP responds.
+    SYS_PING = (SYS, 0x01) => "SYS_PING",
+    /// Reads a non-volatile memory item.
+    SYS_OSAL_NV_READ = (SYS, 0x08) => "SYS_OSAL_NV_READ",
+    /// Writes a non-volatile memory item.
+    SYS_OSAL_NV_WRITE = (SYS, 0x09) => "SYS_OSAL_NV_WRITE",
+    /// Reports that the NCP has reset.
+    SYS_RESET_IND = (SYS, 0x80) => "SYS_RESET_IND",
+    /// Registers an application endpoint.
+    AF_REGISTER = (AF, 0x00) => "AF_REGISTER",
+    /// Sends a message to a short address.
+    AF_DATA_REQUEST = (AF, 0x01) => "AF_DATA_REQUEST",
+    /// Sends a message to any address mode.
+    AF_DATA_REQUEST_EXT = (AF, 0x02) => "AF_DATA_REQUEST_EXT",
+    /// Reports the completion of a sent message.
+    AF_DATA_CONFIRM = (AF, 0x80) => "AF_DATA_CONFIRM",
+    /// Reports a received message.
+    AF_INCOMING_MSG = (AF, 0x81) => "AF_INCOMING_MSG",
+    /// Permits devices to join.
+    ZDO_MGMT_PERMIT_JOIN_REQ = (ZDO, 0x36) => "ZDO_MGMT_PERMIT_JOIN_REQ",
+    /// Forwards received ZDO messages of a cluster to the host.
+    ZDO_MSG_CB_REGISTER = (ZDO, 0x3E) => "ZDO_MSG_CB_REGISTER",
+    /// Starts the network stack.
+    ZDO_STARTUP_FROM_APP = (ZDO, 0x40) => "ZDO_STARTUP_FROM_APP",
+    /// Starts a route discovery.
+    ZDO_EXT_ROUTE_DISC = (ZDO, 0x45) => "ZDO_EXT_ROUTE_DISC",
+    /// Reads the network information.
+    ZDO_EXT_NWK_INFO = (ZDO, 0x50) => "ZDO_EXT_NWK_INFO",
+    /// Reports a change of the device state.
+    ZDO_STATE_CHANGE_IND = (ZDO, 0xC0) => "ZDO_STATE_CHANGE_IND",
+    /// Reports a device leaving the network.
+    ZDO_LEAVE_IND = (ZDO, 0xC9) => "ZDO_LEAVE_IND",
+    /// Reports a device joining the network through the trust center.
+    ZDO_TC_DEV_IND = (ZDO, 0xCA) => "ZDO_TC_DEV_IND",
+    /// Reports a change of the permit-joining state.
+    ZDO_PERMIT_JOIN_IND = (ZDO, 0xCB) => "ZDO_PERMIT_JOIN_IND",
+    /// Reports a received ZDO message.
+    ZDO_MSG_CB_INCOMING = (ZDO, 0xFF) => "ZDO_MSG_CB_INCOMING",
+    /// Reads the device information.
+    UTIL_GET_DEVICE_INFO = (UTIL, 0x00) => "UTIL_GET_DEVICE_INFO",
+    /// Resolves an IEEE address to a short address.
+    UTIL_ADDRMGR_EXT_ADDR_LOOKUP = (UTIL, 0x40) => "UTIL_ADDRMGR_EXT_ADDR_LOOKUP",
+    /// Resolves a short address to an IEEE address.
+    UTIL_ADDRMGR_NWK_ADDR_LOOKUP = (UTIL, 0x41) => "UTIL_ADDRMGR_NWK_ADDR_LOOKUP",
+    /// Adds an install code or a key derived from one for a joining device.
+    APP_CNF_BDB_ADD_INSTALLCODE = (APP_CNF, 0x04) => "APP_CNF_BDB_ADD_INSTALLCODE",
+    /// Starts base device behavior commissioning.
+    APP_CNF_BDB_START_COMMISSIONING = (APP_CNF, 0x05) => "APP_CNF_BDB_START_COMMISSIONING",
+    /// Sets the commissioning channels.
+    APP_CNF_BDB_SET_CHANNEL = (APP_CNF, 0x08) => "APP_CNF_BDB_SET_CHANNEL",
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommandId;
+
+    #[test]
+    fn displays_known_and_unknown_commands() {
+        assert_eq!(CommandId::AF_DATA_REQUEST.to_string(), "AF_DATA_REQUEST");
+        assert_eq!(
+            CommandId::new(0x02, 0x10).to_string(),
+            "MT command 0x02/0x10"
+        );
+    }
+}
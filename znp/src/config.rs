@@ -0,0 +1,48 @@
+//! Startup configuration of the ZNP backend.
+
+use zb_hw::Formation;
+use zb_hw::zdp::SimpleDescriptor;
+
+/// Startup configuration of an [`Znp`](crate::Znp) driver.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Config {
+    endpoints: Vec<SimpleDescriptor>,
+    formation: Option<Formation>,
+}
+
+impl Config {
+    /// Create a configuration without endpoints that only resumes an existing network.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            endpoints: Vec::new(),
+            formation: None,
+        }
+    }
+
+    /// Register an application endpoint on the NCP.
+    #[must_use]
+    pub fn with_endpoint(mut self, endpoint: SimpleDescriptor) -> Self {
+        self.endpoints.push(endpoint);
+        self
+    }
+
+    /// Form a network with the given parameters if the NCP has not joined one.
+    #[must_use]
+    pub const fn with_formation(mut self, formation: Formation) -> Self {
+        self.formation = Some(formation);
+        self
+    }
+
+    /// Return the application endpoints.
+    #[must_use]
+    pub fn endpoints(&self) -> &[SimpleDescriptor] {
+        &self.endpoints
+    }
+
+    /// Return the parameters of a network to form.
+    #[must_use]
+    pub const fn formation(&self) -> Option<&Formation> {
+        self.formation.as_ref()
+    }
+}
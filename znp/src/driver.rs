@@ -0,0 +1,926 @@
+//! The ZNP driver.
+
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use bytes::Bytes;
+use le_stream::{Prefixed, ToLeStream};
+use log::info;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::spawn;
+use tokio::sync::mpsc::{Receiver, WeakSender, channel};
+use zb_hw::aps::TxOptions;
+use zb_hw::aps::apsde::{DataRequest, Destination, RequestDestination};
+use zb_hw::core::security::Key;
+use zb_hw::core::short_id::Device;
+use zb_hw::core::{Endpoint, IeeeAddress};
+use zb_hw::zdp::SimpleDescriptor;
+use zb_hw::{
+    Channel, ChannelMask, Driver, Error as HwError, Event, Formation, FoundNetwork,
+    NetworkParameters, Operation, ScanDuration, ScannedChannel,
+};
+
+use crate::callbacks::{Input, Translator};
+use crate::client::{Client, check};
+use crate::command::CommandId;
+use crate::config::Config;
+use crate::error::Error;
+use crate::parameters::{
+    AfDataRequest, AfDataRequestExt, AfRegister, BdbAddInstallCode, BdbSetChannel, DeviceInfo,
+    ExtNwkInfo, ExtRouteDisc, MgmtPermitJoinReq, NvRead, NvValue, NvWrite, address_mode, af_option,
+    install_code_format, nv, status,
+};
+use crate::transport::Transport;
+
+/// Longest permit-joining period of a Zigbee network.
+const MAX_PERMIT_JOINING: Duration = Duration::from_secs(254);
+
+/// Broadcast address of all routers and the coordinator.
+const ALL_ROUTERS: u16 = 0xFFFC;
+
+/// Address mode of a 16-bit address in ZDO requests.
+const SHORT_ADDRESS_MODE: u8 = 0x02;
+
+/// Network address reported for unknown devices.
+const UNKNOWN_NETWORK_ADDRESS: u16 = 0xFFFE;
+
+/// Cluster ID registering for all ZDO messages.
+const ALL_ZDO_MESSAGES: u16 = 0xFFFF;
+
+/// Route discovery options of a many-to-one route request with a route record table.
+const MANY_TO_ONE_WITH_ROUTE_CACHE: u8 = 0x03;
+
+/// Logical type of a coordinator.
+const COORDINATOR: u8 = 0x00;
+
+/// BDB commissioning mode forming a network.
+const NETWORK_FORMATION: u8 = 0x04;
+
+/// Status of `ZDO_STARTUP_FROM_APP` once the device left its network and did not start.
+const LEAVE_AND_NOT_STARTED: u8 = 0x02;
+
+/// Capacity of the input channel of the event translator.
+const CALLBACK_CAPACITY: usize = 32;
+
+/// A [`Driver`] for Texas Instruments NCPs running the Z-Stack ZNP firmware.
+#[derive(Debug)]
+pub struct Znp {
+    client: Client,
+    translator: WeakSender<Input>,
+    ieee_address: IeeeAddress,
+    endpoints: Box<[SimpleDescriptor]>,
+}
+
+impl Znp {
+    /// Connect to an NCP over a serial stream and bring up its network.
+    ///
+    /// Pings the NCP, registers the configured endpoints and a callback for all ZDO messages,
+    /// and starts the network stored on the NCP. If the NCP is not on a network and the
+    /// configuration contains a [`Formation`], a new network is formed through BDB
+    /// commissioning instead.
+    ///
+    /// Returns the driver and the receiver of its hardware events. The receiver reports
+    /// [`NetworkEvent::Down`](zb_hw::NetworkEvent::Down) and closes when the serial
+    /// connection fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the NCP cannot be initialized.
+    pub async fn connect<S>(
+        stream: S,
+        config: Config,
+        event_capacity: NonZeroUsize,
+    ) -> Result<(Self, Receiver<Event>), Error>
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (inputs_out, inputs) = channel(CALLBACK_CAPACITY);
+        let (events_out, events) = channel(event_capacity.get());
+        let translator = inputs_out.downgrade();
+        let client = Client::new(Transport::start(stream, inputs_out));
+        let profiles = config
+            .endpoints()
+            .iter()
+            .map(|endpoint| (endpoint.endpoint_id(), endpoint.profile_id()))
+            .collect::<BTreeMap<_, _>>();
+        spawn(Translator::new(profiles).run(inputs, events_out));
+
+        let _capabilities: u16 = client.call(CommandId::SYS_PING, ()).await?;
+        let device_info: DeviceInfo = client.call(CommandId::UTIL_GET_DEVICE_INFO, ()).await?;
+        check(CommandId::UTIL_GET_DEVICE_INFO, device_info.status)?;
+
+        for endpoint in config.endpoints() {
+            register(&client, endpoint).await?;
+        }
+
+        client
+            .call_with_status(CommandId::ZDO_MSG_CB_REGISTER, ALL_ZDO_MESSAGES)
+            .await?;
+
+        match config.formation() {
+            Some(formation) if !is_on_network(&client).await? => {
+                info!("NCP is not on a network; forming one");
+                form_network(&client, formation).await?;
+            }
+            _ => start(&client).await?,
+        }
+
+        Ok((
+            Self {
+                client,
+                translator,
+                ieee_address: device_info.ieee_address,
+                endpoints: config.endpoints().into(),
+            },
+            events,
+        ))
+    }
+
+    /// Send `AF_DATA_REQUEST_EXT` to a group, broadcast, or IEEE address.
+    async fn send_extended(
+        &self,
+        request: &DataRequest<Bytes>,
+        counter: u8,
+        address_mode: u8,
+        destination: [u8; 8],
+        destination_endpoint: u8,
+    ) -> Result<(), HwError> {
+        let data = Prefixed::try_from(request.asdu().clone())
+            .map_err(|_| HwError::Unsupported(Operation::Transmit))?;
+
+        self.client
+            .call_with_status(
+                CommandId::AF_DATA_REQUEST_EXT,
+                AfDataRequestExt {
+                    address_mode,
+                    destination,
+                    destination_endpoint,
+                    destination_pan_id: 0,
+                    source_endpoint: request.source_endpoint().get().as_u8(),
+                    cluster_id: request.cluster_id(),
+                    transaction_id: counter,
+                    options: af_options(request.tx_options()),
+                    radius: request.radius_counter(),
+                    data,
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Announce an acknowledged transmission to the translator, which confirms it later.
+    async fn expect_confirm(&self, request: &DataRequest<Bytes>, counter: u8) {
+        if !request
+            .tx_options()
+            .contains(TxOptions::ACKNOWLEDGED_TRANSMISSION)
+        {
+            return;
+        }
+
+        let destination = match request.destination() {
+            RequestDestination::Network { address, endpoint } => {
+                Destination::Network { address, endpoint }
+            }
+            RequestDestination::Extended { address, endpoint } => {
+                Destination::Extended { address, endpoint }
+            }
+            RequestDestination::Group { address, .. } => Destination::Group(address),
+            RequestDestination::Broadcast { .. } | RequestDestination::Bound => return,
+        };
+
+        if let Some(translator) = self.translator.upgrade() {
+            translator
+                .send(Input::Transmitted {
+                    transaction_id: counter,
+                    destination,
+                })
+                .await
+                .unwrap_or_else(drop);
+        }
+    }
+}
+
+impl Driver for Znp {
+    async fn get_endpoints(&self) -> Result<Box<[SimpleDescriptor]>, HwError> {
+        Ok(self.endpoints.clone())
+    }
+
+    async fn get_pan_id(&mut self) -> Result<u16, HwError> {
+        let info: ExtNwkInfo = self.client.call(CommandId::ZDO_EXT_NWK_INFO, ()).await?;
+        Ok(info.pan_id)
+    }
+
+    async fn get_network_parameters(&mut self) -> Result<NetworkParameters, HwError> {
+        let info: ExtNwkInfo = self.client.call(CommandId::ZDO_EXT_NWK_INFO, ()).await?;
+        let channel = Channel::new(info.channel)
+            .ok_or(Error::MalformedResponse(CommandId::ZDO_EXT_NWK_INFO))?;
+        Ok(NetworkParameters::new(
+            info.pan_id,
+            info.extended_pan_id,
+            channel,
+        ))
+    }
+
+    async fn get_ieee_address(&mut self) -> Result<IeeeAddress, HwError> {
+        Ok(self.ieee_address)
+    }
+
+    async fn scan_networks(
+        &mut self,
+        _channel_mask: ChannelMask,
+        _duration: ScanDuration,
+    ) -> Result<Vec<FoundNetwork>, HwError> {
+        Err(HwError::Unsupported(Operation::ScanNetworks))
+    }
+
+    async fn scan_channels(
+        &mut self,
+        _channel_mask: ChannelMask,
+        _duration: ScanDuration,
+    ) -> Result<Vec<ScannedChannel>, HwError> {
+        Err(HwError::Unsupported(Operation::ScanChannels))
+    }
+
+    async fn allow_joins(&mut self, duration: Duration) -> Result<Duration, HwError> {
+        let duration = duration.min(MAX_PERMIT_JOINING);
+        let seconds = u8::try_from(duration.as_secs()).unwrap_or(u8::MAX);
+        self.client
+            .call_with_status(
+                CommandId::ZDO_MGMT_PERMIT_JOIN_REQ,
+                MgmtPermitJoinReq {
+                    address_mode: SHORT_ADDRESS_MODE,
+                    destination: ALL_ROUTERS,
+                    duration: seconds,
+                    tc_significance: 0,
+                },
+            )
+            .await?;
+        Ok(Duration::from_secs(seconds.into()))
+    }
+
+    async fn route_request(&mut self, radius: u8) -> Result<(), HwError> {
+        self.client
+            .call_with_status(
+                CommandId::ZDO_EXT_ROUTE_DISC,
+                ExtRouteDisc {
+                    destination: ALL_ROUTERS,
+                    options: MANY_TO_ONE_WITH_ROUTE_CACHE,
+                    radius,
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn short_id_to_ieee_address(&mut self, short_id: Device) -> Result<IeeeAddress, HwError> {
+        let ieee_address: IeeeAddress = self
+            .client
+            .call(CommandId::UTIL_ADDRMGR_NWK_ADDR_LOOKUP, short_id.as_u16())
+            .await?;
+
+        if ieee_address == IeeeAddress::default()
+            || ieee_address == IeeeAddress::new(0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF)
+        {
+            Err(Error::UnknownDevice.into())
+        } else {
+            Ok(ieee_address)
+        }
+    }
+
+    async fn ieee_address_to_short_id(
+        &mut self,
+        ieee_address: IeeeAddress,
+    ) -> Result<Device, HwError> {
+        let network_address: u16 = self
+            .client
+            .call(CommandId::UTIL_ADDRMGR_EXT_ADDR_LOOKUP, ieee_address)
+            .await?;
+
+        if network_address == UNKNOWN_NETWORK_ADDRESS {
+            return Err(Error::UnknownDevice.into());
+        }
+
+        Device::new(network_address).ok_or_else(|| Error::UnknownDevice.into())
+    }
+
+    async fn transmit(&mut self, request: DataRequest<Bytes>, counter: u8) -> Result<(), HwError> {
+        self.expect_confirm(&request, counter).await;
+
+        match request.destination() {
+            RequestDestination::Network { address, endpoint } => {
+                let data = Prefixed::try_from(request.asdu().clone())
+                    .map_err(|_| HwError::Unsupported(Operation::Transmit))?;
+                self.client
+                    .call_with_status(
+                        CommandId::AF_DATA_REQUEST,
+                        AfDataRequest {
+                            destination: address.as_u16(),
+                            destination_endpoint: endpoint.as_u8(),
+                            source_endpoint: request.source_endpoint().get().as_u8(),
+                            cluster_id: request.cluster_id(),
+                            transaction_id: counter,
+                            options: af_options(request.tx_options()),
+                            radius: request.radius_counter(),
+                            data,
+                        },
+                    )
+                    .await?;
+            }
+            RequestDestination::Extended { address, endpoint } => {
+                self.send_extended(
+                    &request,
+                    counter,
+                    address_mode::IEEE,
+                    extended_address(address),
+                    endpoint.as_u8(),
+                )
+                .await?;
+            }
+            RequestDestination::Broadcast { address, endpoint } => {
+                self.send_extended(
+                    &request,
+                    counter,
+                    address_mode::BROADCAST,
+                    short_address(address.as_u16()),
+                    endpoint.as_u8(),
+                )
+                .await?;
+            }
+            RequestDestination::Group { address, .. } => {
+                self.send_extended(
+                    &request,
+                    counter,
+                    address_mode::GROUP,
+                    short_address(address.as_u16()),
+                    Endpoint::Broadcast.as_u8(),
+                )
+                .await?;
+            }
+            RequestDestination::Bound => return Err(HwError::Unsupported(Operation::Transmit)),
+        }
+
+        Ok(())
+    }
+
+    async fn set_link_key(&mut self, ieee_address: IeeeAddress, key: Key) -> Result<(), HwError> {
+        add_link_key(&self.client, ieee_address, key)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn add_transient_link_key(
+        &mut self,
+        ieee_address: IeeeAddress,
+        key: Key,
+    ) -> Result<(), HwError> {
+        add_link_key(&self.client, ieee_address, key)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn form_network(&mut self, formation: Formation) -> Result<(), HwError> {
+        form_network(&self.client, &formation)
+            .await
+            .map_err(Into::into)
+    }
+}
+
+/// Store a device's trust center link key as an install code derived key.
+///
+/// Z-Stack replaces the previous link key of the device.
+async fn add_link_key(client: &Client, ieee_address: IeeeAddress, key: Key) -> Result<(), Error> {
+    client
+        .call_with_status(
+            CommandId::APP_CNF_BDB_ADD_INSTALLCODE,
+            BdbAddInstallCode {
+                format: install_code_format::DERIVED_KEY,
+                ieee_address,
+                key,
+            },
+        )
+        .await
+}
+
+/// Register an application endpoint, tolerating endpoints registered before a host restart.
+async fn register(client: &Client, endpoint: &SimpleDescriptor) -> Result<(), Error> {
+    let input_clusters = endpoint.input_clusters().to_vec();
+    let output_clusters = endpoint.output_clusters().to_vec();
+    let status: u8 = client
+        .call(
+            CommandId::AF_REGISTER,
+            AfRegister {
+                endpoint: endpoint.endpoint_id(),
+                profile_id: endpoint.profile_id(),
+                device_id: endpoint.device_id(),
+                device_version: endpoint.version(),
+                latency: 0,
+                input_cluster_count: u8::try_from(input_clusters.len()).unwrap_or(u8::MAX),
+                input_clusters,
+                output_cluster_count: u8::try_from(output_clusters.len()).unwrap_or(u8::MAX),
+                output_clusters,
+            },
+        )
+        .await?;
+
+    if status == status::APS_DUPLICATE_ENTRY {
+        Ok(())
+    } else {
+        check(CommandId::AF_REGISTER, status)
+    }
+}
+
+/// Return whether BDB commissioning has put the NCP on a network.
+async fn is_on_network(client: &Client) -> Result<bool, Error> {
+    let value: NvValue = client
+        .call(
+            CommandId::SYS_OSAL_NV_READ,
+            NvRead {
+                id: nv::BDB_NODE_IS_ON_A_NETWORK,
+                offset: 0,
+            },
+        )
+        .await?;
+
+    Ok(value.status == status::SUCCESS && value.value.first().is_some_and(|&on| on != 0))
+}
+
+/// Start the network stored on the NCP.
+async fn start(client: &Client) -> Result<(), Error> {
+    let status: u8 = client.call(CommandId::ZDO_STARTUP_FROM_APP, 0u16).await?;
+
+    if status == LEAVE_AND_NOT_STARTED {
+        Err(Error::Status {
+            command: CommandId::ZDO_STARTUP_FROM_APP,
+            status,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Store the network parameters in non-volatile memory and form a network.
+///
+/// BDB commissioning picks the formation channel from the primary channel mask.
+async fn form_network(client: &Client, formation: &Formation) -> Result<(), Error> {
+    write_nv(client, nv::LOGICAL_TYPE, vec![COORDINATOR]).await?;
+    write_nv(
+        client,
+        nv::PAN_ID,
+        formation.pan_id().to_le_bytes().to_vec(),
+    )
+    .await?;
+    write_nv(
+        client,
+        nv::EXTENDED_PAN_ID,
+        formation.extended_pan_id().to_le_stream().collect(),
+    )
+    .await?;
+    write_nv(
+        client,
+        nv::PRECONFIGURED_KEY,
+        formation.network_key().to_le_stream().collect(),
+    )
+    .await?;
+    write_nv(client, nv::PRECONFIGURED_KEYS_ENABLE, vec![1]).await?;
+
+    for (is_primary, channels) in [(1, formation.channel_mask().bits()), (0, 0)] {
+        client
+            .call_with_status(
+                CommandId::APP_CNF_BDB_SET_CHANNEL,
+                BdbSetChannel {
+                    is_primary,
+                    channels,
+                },
+            )
+            .await?;
+    }
+
+    client
+        .call_with_status(
+            CommandId::APP_CNF_BDB_START_COMMISSIONING,
+            NETWORK_FORMATION,
+        )
+        .await
+}
+
+async fn write_nv(client: &Client, id: u16, value: Vec<u8>) -> Result<(), Error> {
+    client
+        .call_with_status(
+            CommandId::SYS_OSAL_NV_WRITE,
+            NvWrite {
+                id,
+                offset: 0,
+                length: u8::try_from(value.len()).unwrap_or(u8::MAX),
+                value,
+            },
+        )
+        .await
+}
+
+/// Return the AF transmission options of the APS transmission options.
+const fn af_options(tx_options: TxOptions) -> u8 {
+    let mut options = af_option::DISCOVER_ROUTE;
+
+    if tx_options.contains(TxOptions::SECURITY_ENABLED) {
+        options |= af_option::SECURITY;
+    }
+
+    if tx_options.contains(TxOptions::ACKNOWLEDGED_TRANSMISSION) {
+        options |= af_option::ACK_REQUEST;
+    }
+
+    options
+}
+
+/// Return the address field of `AF_DATA_REQUEST_EXT` holding an IEEE address.
+fn extended_address(address: IeeeAddress) -> [u8; 8] {
+    let mut field = [0; 8];
+    field
+        .iter_mut()
+        .zip(address.to_le_stream())
+        .for_each(|(byte, octet)| *byte = octet);
+    field
+}
+
+/// Return the address field of `AF_DATA_REQUEST_EXT` holding a 16-bit address.
+fn short_address(address: u16) -> [u8; 8] {
+    let mut field = [0; 8];
+    field[..2].copy_from_slice(&address.to_le_bytes());
+    field
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use bytes::Bytes;
+    use tokio::runtime::Builder;
+    use tokio::spawn;
+    use tokio::sync::mpsc::Receiver;
+    use zb_hw::aps::TxOptions;
+    use zb_hw::aps::apsde::{
+        ConfirmStatus, DataRequest, IndividualEndpoint, NetworkAddress, RequestDestination, Source,
+        Status,
+    };
+    use zb_hw::core::security::Key;
+    use zb_hw::core::{Application, Endpoint, IeeeAddress, Profile};
+    use zb_hw::zdp::{AppFlags, Clusters, SimpleDescriptor};
+    use zb_hw::{
+        ApsdeEvent, ChannelMask, DeviceEvent, Driver, Event, Formation, NcpHandle, NetworkEvent,
+    };
+
+    use super::Znp;
+    use crate::command::CommandId;
+    use crate::config::Config;
+    use crate::stub::Stub;
+
+    const CAPACITY: NonZeroUsize = NonZeroUsize::new(8).expect("capacity is non-zero");
+    const IEEE_ADDRESS: [u8; 8] = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+    const ENDPOINT: Endpoint = Endpoint::Application(Application::MIN);
+    const ON_OFF: u16 = 0x0006;
+    const DEVICE: u16 = 0x1234;
+    const PAN_ID: u16 = 0x1A62;
+
+    fn run<F>(test: F)
+    where
+        F: Future<Output = ()>,
+    {
+        Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .expect("runtime must be available")
+            .block_on(test);
+    }
+
+    fn config() -> Config {
+        Config::new().with_endpoint(SimpleDescriptor::new(
+            ENDPOINT,
+            Profile::ZigbeeHomeAutomation,
+            0x0005,
+            AppFlags::empty(),
+            Clusters::from_slice(&[0x0000]).expect("one cluster fits"),
+            Clusters::from_slice(&[ON_OFF]).expect("one cluster fits"),
+        ))
+    }
+
+    /// Answer the requests common to every startup.
+    async fn initialize(ncp: &mut Stub) {
+        ncp.answer(CommandId::SYS_PING, &[0x79, 0x01]).await;
+        let mut device_info = vec![0x00];
+        device_info.extend(IEEE_ADDRESS);
+        device_info.extend([0x00, 0x00, 0x07, 0x09, 0x00]);
+        ncp.answer(CommandId::UTIL_GET_DEVICE_INFO, &device_info)
+            .await;
+        assert_eq!(
+            ncp.answer(CommandId::AF_REGISTER, &[0x00]).await,
+            [
+                0x01, 0x04, 0x01, 0x05, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x01, 0x06, 0x00
+            ]
+        );
+        assert_eq!(
+            ncp.answer(CommandId::ZDO_MSG_CB_REGISTER, &[0x00]).await,
+            [0xFF, 0xFF]
+        );
+    }
+
+    /// Connect to a stub whose network is already stored.
+    async fn connect(
+        ncp: &mut Stub,
+        host: tokio::io::DuplexStream,
+    ) -> (NcpHandle, Receiver<Event>) {
+        let connect = spawn(Znp::connect(host, config(), CAPACITY));
+        initialize(ncp).await;
+        ncp.answer(CommandId::ZDO_STARTUP_FROM_APP, &[0x00]).await;
+        let (driver, events) = connect
+            .await
+            .expect("task must finish")
+            .expect("NCP must initialize");
+        let (handle, actor) = driver.into_actor(CAPACITY);
+        spawn(actor);
+        (handle, events)
+    }
+
+    #[test]
+    fn registers_endpoints_and_forms_a_network() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let config = config().with_formation(Formation::new(
+                PAN_ID,
+                IeeeAddress::new(0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD),
+                ChannelMask::new(1 << 15).expect("channel 15 is valid"),
+                Key::new([0xAB; Key::SIZE]),
+            ));
+            let connect = spawn(Znp::connect(host, config, CAPACITY));
+
+            initialize(&mut ncp).await;
+            assert_eq!(
+                ncp.answer(CommandId::SYS_OSAL_NV_READ, &[0x00, 0x01, 0x00])
+                    .await,
+                [0x55, 0x00, 0x00]
+            );
+            assert_eq!(
+                ncp.answer(CommandId::SYS_OSAL_NV_WRITE, &[0x00]).await,
+                [0x87, 0x00, 0x00, 0x01, 0x00]
+            );
+            assert_eq!(
+                ncp.answer(CommandId::SYS_OSAL_NV_WRITE, &[0x00]).await,
+                [0x83, 0x00, 0x00, 0x02, 0x62, 0x1A]
+            );
+            assert_eq!(
+                ncp.answer(CommandId::SYS_OSAL_NV_WRITE, &[0x00]).await,
+                [
+                    0x2D, 0x00, 0x00, 0x08, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD, 0xDD
+                ]
+            );
+            let network_key = ncp.answer(CommandId::SYS_OSAL_NV_WRITE, &[0x00]).await;
+            assert_eq!(network_key[..4], [0x62, 0x00, 0x00, 0x10]);
+            assert_eq!(network_key[4..], [0xAB; Key::SIZE]);
+            assert_eq!(
+                ncp.answer(CommandId::SYS_OSAL_NV_WRITE, &[0x00]).await,
+                [0x63, 0x00, 0x00, 0x01, 0x01]
+            );
+            assert_eq!(
+                ncp.answer(CommandId::APP_CNF_BDB_SET_CHANNEL, &[0x00])
+                    .await,
+                [0x01, 0x00, 0x80, 0x00, 0x00]
+            );
+            assert_eq!(
+                ncp.answer(CommandId::APP_CNF_BDB_SET_CHANNEL, &[0x00])
+                    .await,
+                [0x00, 0x00, 0x00, 0x00, 0x00]
+            );
+            assert_eq!(
+                ncp.answer(CommandId::APP_CNF_BDB_START_COMMISSIONING, &[0x00])
+                    .await,
+                [0x04]
+            );
+            ncp.indicate(CommandId::ZDO_STATE_CHANGE_IND, &[0x09]).await;
+
+            let (mut driver, mut events) = connect
+                .await
+                .expect("task must finish")
+                .expect("NCP must initialize");
+            assert!(matches!(
+                events.recv().await,
+                Some(Event::Network(NetworkEvent::Up))
+            ));
+            assert_eq!(
+                driver
+                    .get_ieee_address()
+                    .await
+                    .expect("IEEE address must be cached"),
+                IeeeAddress::new(8, 7, 6, 5, 4, 3, 2, 1)
+            );
+        });
+    }
+
+    #[test]
+    fn confirms_acknowledged_unicasts_with_the_aps_counter() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (handle, mut events) = connect(&mut ncp, host).await;
+            let request = DataRequest::new(
+                RequestDestination::Network {
+                    address: NetworkAddress::new(DEVICE).expect("address is valid"),
+                    endpoint: ENDPOINT,
+                },
+                Profile::ZigbeeHomeAutomation.as_u16(),
+                ON_OFF,
+                IndividualEndpoint::new(ENDPOINT).expect("endpoint is individual"),
+                Bytes::from_static(&[0x01, 0x2A, 0x02]),
+            )
+            .with_tx_options(TxOptions::ACKNOWLEDGED_TRANSMISSION);
+            let transmit = {
+                let handle = handle.clone();
+                spawn(async move { handle.transmit(request, 7).await })
+            };
+
+            assert_eq!(
+                ncp.answer(CommandId::AF_DATA_REQUEST, &[0x00]).await,
+                [
+                    0x34, 0x12, 0x01, 0x01, 0x06, 0x00, 0x07, 0x30, 0x00, 0x03, 0x01, 0x2A, 0x02
+                ]
+            );
+            transmit
+                .await
+                .expect("task must finish")
+                .expect("NCP must accept the unicast");
+
+            ncp.indicate(CommandId::AF_DATA_CONFIRM, &[0x00, 0x01, 0x07])
+                .await;
+            let Some(Event::Apsde(ApsdeEvent::DataConfirm {
+                counter,
+                confirmation,
+            })) = events.recv().await
+            else {
+                panic!("acknowledged unicast must be confirmed");
+            };
+            assert_eq!(counter, 7);
+            assert_eq!(confirmation.status(), ConfirmStatus::Aps(Status::Success));
+        });
+    }
+
+    #[test]
+    fn rejects_oversized_requests_and_keeps_running() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (handle, _events) = connect(&mut ncp, host).await;
+            let request = |asdu| {
+                DataRequest::new(
+                    RequestDestination::Network {
+                        address: NetworkAddress::new(DEVICE).expect("address is valid"),
+                        endpoint: ENDPOINT,
+                    },
+                    Profile::ZigbeeHomeAutomation.as_u16(),
+                    ON_OFF,
+                    IndividualEndpoint::new(ENDPOINT).expect("endpoint is individual"),
+                    asdu,
+                )
+            };
+
+            assert!(handle.get_maximum_payload_length().await.is_err());
+            assert!(
+                handle
+                    .transmit(request(Bytes::from(vec![0; 241])), 1)
+                    .await
+                    .is_err()
+            );
+
+            let transmit = {
+                let handle = handle.clone();
+                spawn(async move {
+                    handle
+                        .transmit(request(Bytes::from_static(&[0x01, 0x2A, 0x02])), 2)
+                        .await
+                })
+            };
+            ncp.answer(CommandId::AF_DATA_REQUEST, &[0x00]).await;
+            transmit
+                .await
+                .expect("task must finish")
+                .expect("NCP must accept the unicast");
+        });
+    }
+
+    #[test]
+    fn sets_link_keys_as_derived_install_code_keys() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (handle, _events) = connect(&mut ncp, host).await;
+            let set = spawn(async move {
+                handle
+                    .set_link_key(
+                        IeeeAddress::new(1, 1, 1, 1, 1, 1, 1, 1),
+                        Key::new([0xCD; Key::SIZE]),
+                    )
+                    .await
+            });
+
+            let parameters = ncp
+                .answer(CommandId::APP_CNF_BDB_ADD_INSTALLCODE, &[0x00])
+                .await;
+            assert_eq!(parameters[0], 0x02);
+            assert_eq!(parameters[1..9], [0x01; 8]);
+            assert_eq!(parameters[9..], [0xCD; 16]);
+            set.await
+                .expect("task must finish")
+                .expect("NCP must store the key");
+        });
+    }
+
+    #[test]
+    fn adds_transient_link_keys_as_derived_install_code_keys() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (handle, _events) = connect(&mut ncp, host).await;
+            let add = spawn(async move {
+                handle
+                    .add_transient_link_key(
+                        IeeeAddress::new(1, 1, 1, 1, 1, 1, 1, 1),
+                        Key::new([0xAB; Key::SIZE]),
+                    )
+                    .await
+            });
+
+            let parameters = ncp
+                .answer(CommandId::APP_CNF_BDB_ADD_INSTALLCODE, &[0x00])
+                .await;
+            assert_eq!(parameters[0], 0x02);
+            assert_eq!(parameters[1..9], [0x01; 8]);
+            assert_eq!(parameters[9..], [0xAB; 16]);
+            add.await
+                .expect("task must finish")
+                .expect("NCP must store the key");
+        });
+    }
+
+    #[test]
+    fn indicates_messages_and_joins_and_reports_unknown_devices() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (handle, mut events) = connect(&mut ncp, host).await;
+
+            ncp.indicate(
+                CommandId::AF_INCOMING_MSG,
+                &[
+                    0x00, 0x00, 0x06, 0x00, 0x34, 0x12, 0x01, 0x01, 0x00, 0xC8, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x05, 0x02, 0x18, 0x05,
+                ],
+            )
+            .await;
+            let Some(Event::Apsde(ApsdeEvent::DataIndication(indication))) = events.recv().await
+            else {
+                panic!("incoming message must be indicated");
+            };
+            assert_eq!(indication.metadata().link_quality(), 200);
+            assert_eq!(
+                indication.metadata().profile_id(),
+                Profile::ZigbeeHomeAutomation.as_u16()
+            );
+            assert!(matches!(
+                indication.metadata().source(),
+                Source::Network { address, .. } if address.as_u16() == DEVICE
+            ));
+            assert_eq!(indication.asdu().as_ref(), [0x18, 0x05]);
+
+            ncp.indicate(
+                CommandId::ZDO_MSG_CB_INCOMING,
+                &[
+                    0x34, 0x12, 0x00, 0x05, 0x80, 0x00, 0x2A, 0x00, 0x00, 0x00, 0x34, 0x12, 0x01,
+                    0x01,
+                ],
+            )
+            .await;
+            let Some(Event::Apsde(ApsdeEvent::DataIndication(indication))) = events.recv().await
+            else {
+                panic!("ZDO message must be indicated");
+            };
+            assert_eq!(indication.metadata().cluster_id(), 0x8005);
+            assert_eq!(
+                indication.asdu().as_ref(),
+                [0x2A, 0x00, 0x34, 0x12, 0x01, 0x01]
+            );
+
+            let mut join = vec![0x34, 0x12];
+            join.extend(IEEE_ADDRESS);
+            join.extend([0x00, 0x00]);
+            ncp.indicate(CommandId::ZDO_TC_DEV_IND, &join).await;
+            assert!(matches!(
+                events.recv().await,
+                Some(Event::Device(DeviceEvent::Joined(address)))
+                    if address.ieee_address() == IeeeAddress::new(8, 7, 6, 5, 4, 3, 2, 1)
+            ));
+
+            let lookup = spawn(async move {
+                handle
+                    .ieee_address_to_short_id(IeeeAddress::new(1, 1, 1, 1, 1, 1, 1, 1))
+                    .await
+            });
+            ncp.answer(CommandId::UTIL_ADDRMGR_EXT_ADDR_LOOKUP, &[0xFE, 0xFF])
+                .await;
+            assert!(lookup.await.expect("task must finish").is_err());
+        });
+    }
+}
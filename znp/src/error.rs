@@ -0,0 +1,75 @@
+//! Errors of the ZNP backend.
+
+use std::io;
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::command::CommandId;
+
+/// Errors of the ZNP backend.
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+#[expect(
+    variant_size_differences,
+    reason = "I/O errors retain a shared source while protocol statuses stay inline"
+)]
+pub enum Error {
+    /// Reading from or writing to the serial stream failed.
+    #[error("Serial I/O error: {0}")]
+    Io(#[source] Arc<io::Error>),
+
+    /// The NCP closed the serial stream.
+    #[error("Serial stream closed")]
+    Closed,
+
+    /// The NCP reset unexpectedly.
+    #[error("NCP reset with reason {0:#04X}")]
+    Reset(u8),
+
+    /// The NCP did not respond to a command in time.
+    #[error("NCP did not respond to {0}")]
+    Timeout(CommandId),
+
+    /// The NCP did not understand a command.
+    #[error("NCP rejected {command} with RPC error {code:#04X}")]
+    Rpc {
+        /// The rejected command.
+        command: CommandId,
+        /// The MT RPC error code.
+        code: u8,
+    },
+
+    /// The NCP sent a response that could not be parsed.
+    #[error("Malformed response to {0}")]
+    MalformedResponse(CommandId),
+
+    /// The NCP reported an unsuccessful status.
+    #[error("{command} failed with status {status:#04X}")]
+    Status {
+        /// The failed command.
+        command: CommandId,
+        /// The Z-Stack status.
+        status: u8,
+    },
+
+    /// The data of a request does not fit into an MT frame.
+    #[error("Data of {0} exceeds the MT frame size")]
+    TooLong(CommandId),
+
+    /// The NCP does not know the requested device.
+    #[error("Unknown device")]
+    UnknownDevice,
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(Arc::new(error))
+    }
+}
+
+impl From<Error> for zb_hw::Error {
+    fn from(error: Error) -> Self {
+        Self::backend(error)
+    }
+}
@@ -0,0 +1,228 @@
+//! MT frames.
+//!
+//! A frame consists of a start-of-frame byte, the data length, two command bytes, the data, and a
+//! frame check sequence that XORs all bytes after the start of frame.
+
+use bytes::Bytes;
+use thiserror::Error;
+
+use crate::command::CommandId;
+
+/// Starts a frame.
+const SOF: u8 = 0xFE;
+
+/// Largest data field of a frame.
+pub const MAX_DATA_SIZE: usize = 250;
+
+/// Size of a frame without its data: SOF, length, two command bytes, and FCS.
+const OVERHEAD: usize = 5;
+
+/// Bits of the first command byte holding the command type.
+const TYPE_MASK: u8 = 0xE0;
+
+/// Bits of the first command byte holding the subsystem.
+const SUBSYSTEM_MASK: u8 = 0x1F;
+
+/// Types of MT commands.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(u8)]
+pub enum Type {
+    /// A poll for queued asynchronous messages.
+    Poll = 0x00,
+
+    /// A synchronous request, answered by a synchronous response.
+    Sreq = 0x20,
+
+    /// An asynchronous request or indication.
+    Areq = 0x40,
+
+    /// A synchronous response.
+    Srsp = 0x60,
+}
+
+/// An MT frame.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Frame {
+    kind: Type,
+    command: CommandId,
+    data: Bytes,
+}
+
+/// Errors of received frames.
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+pub enum DecodeError {
+    /// The frame check sequence does not match the frame's content.
+    #[error("MT frame check sequence mismatch")]
+    Fcs,
+
+    /// The frame's length exceeds the maximum.
+    #[error("MT frame too long")]
+    TooLong,
+}
+
+/// Errors of frames to send.
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+pub enum EncodeError {
+    /// The data exceeds [`MAX_DATA_SIZE`].
+    #[error("MT frame data of {0} bytes exceeds the maximum")]
+    TooLong(usize),
+}
+
+impl Frame {
+    /// Create a frame.
+    #[must_use]
+    pub const fn new(kind: Type, command: CommandId, data: Bytes) -> Self {
+        Self {
+            kind,
+            command,
+            data,
+        }
+    }
+
+    /// Return the command type.
+    #[must_use]
+    pub const fn kind(&self) -> Type {
+        self.kind
+    }
+
+    /// Return the command identifier.
+    #[must_use]
+    pub const fn command(&self) -> CommandId {
+        self.command
+    }
+
+    /// Return the data field.
+    #[must_use]
+    pub const fn data(&self) -> &Bytes {
+        &self.data
+    }
+
+    /// Return the encoded frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`EncodeError`] if the data exceeds [`MAX_DATA_SIZE`].
+    pub fn encode(&self) -> Result<Vec<u8>, EncodeError> {
+        let length = u8::try_from(self.data.len())
+            .ok()
+            .filter(|length| usize::from(*length) <= MAX_DATA_SIZE)
+            .ok_or(EncodeError::TooLong(self.data.len()))?;
+        let mut frame = Vec::with_capacity(self.data.len() + OVERHEAD);
+        frame.extend([
+            SOF,
+            length,
+            self.kind as u8 | self.command.subsystem(),
+            self.command.id(),
+        ]);
+        frame.extend_from_slice(&self.data);
+        frame.push(fcs(&frame[1..]));
+        Ok(frame)
+    }
+}
+
+/// Reassembles MT frames from a byte stream.
+#[derive(Debug, Default)]
+pub struct Decoder {
+    buffer: Vec<u8>,
+}
+
+impl Decoder {
+    /// Consume a received byte.
+    ///
+    /// Returns the decoded frame once it is complete. Bytes outside of frames are skipped.
+    pub fn push(&mut self, byte: u8) -> Option<Result<Frame, DecodeError>> {
+        if self.buffer.is_empty() && byte != SOF {
+            return None;
+        }
+
+        self.buffer.push(byte);
+        let length = usize::from(*self.buffer.get(1)?);
+
+        if length > MAX_DATA_SIZE {
+            self.buffer.clear();
+            return Some(Err(DecodeError::TooLong));
+        }
+
+        if self.buffer.len() < length + OVERHEAD {
+            return None;
+        }
+
+        let frame = std::mem::take(&mut self.buffer);
+        let (content, checksum) = frame[1..].split_at(length + 3);
+
+        if checksum != [fcs(content)] {
+            return Some(Err(DecodeError::Fcs));
+        }
+
+        let kind = match content[1] & TYPE_MASK {
+            0x20 => Type::Sreq,
+            0x40 => Type::Areq,
+            0x60 => Type::Srsp,
+            _ => Type::Poll,
+        };
+
+        Some(Ok(Frame::new(
+            kind,
+            CommandId::new(content[1] & SUBSYSTEM_MASK, content[2]),
+            Bytes::copy_from_slice(&content[3..]),
+        )))
+    }
+}
+
+/// Return the frame check sequence of `content`.
+fn fcs(content: &[u8]) -> u8 {
+    content.iter().fold(0, |fcs, byte| fcs ^ byte)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::{DecodeError, Decoder, EncodeError, Frame, MAX_DATA_SIZE, Type};
+    use crate::command::CommandId;
+
+    fn decode_all(bytes: &[u8]) -> Vec<Result<Frame, DecodeError>> {
+        let mut decoder = Decoder::default();
+        bytes
+            .iter()
+            .filter_map(|byte| decoder.push(*byte))
+            .collect()
+    }
+
+    #[test]
+    fn encodes_requests() {
+        assert_eq!(
+            Frame::new(Type::Sreq, CommandId::SYS_PING, Bytes::new()).encode(),
+            Ok(vec![0xFE, 0x00, 0x21, 0x01, 0x20])
+        );
+    }
+
+    #[test]
+    fn rejects_oversized_data() {
+        let data = Bytes::from(vec![0; MAX_DATA_SIZE + 1]);
+        assert_eq!(
+            Frame::new(Type::Sreq, CommandId::AF_DATA_REQUEST, data).encode(),
+            Err(EncodeError::TooLong(MAX_DATA_SIZE + 1))
+        );
+    }
+
+    #[test]
+    fn decodes_responses_between_noise() {
+        assert_eq!(
+            decode_all(&[0x00, 0xFE, 0x02, 0x61, 0x01, 0x59, 0x06, 0x3D, 0xEF]),
+            [Ok(Frame::new(
+                Type::Srsp,
+                CommandId::SYS_PING,
+                Bytes::from_static(&[0x59, 0x06])
+            ))]
+        );
+    }
+
+    #[test]
+    fn rejects_damaged_frames() {
+        assert_eq!(
+            decode_all(&[0xFE, 0x00, 0x21, 0x01, 0x21]),
+            [Err(DecodeError::Fcs)]
+        );
+    }
+}
@@ -0,0 +1,50 @@
+//! Z-Stack ZNP backend for Texas Instruments Zigbee NCPs.
+//!
+//! This crate implements the `apis-saltans-hw` [`Driver`](zb_hw::Driver) for network
+//! co-processors, such as CC2652-based sticks, running the Texas Instruments Z-Stack ZNP
+//! firmware. The host talks to the NCP with the Monitor and Test (MT) serial protocol over any
+//! [`AsyncRead`](tokio::io::AsyncRead) and [`AsyncWrite`](tokio::io::AsyncWrite) stream, such as
+//! a serial port.
+//!
+//! [`Znp::connect`] pings the NCP, registers the application endpoints of the [`Config`], and
+//! starts or forms the network. It returns the driver and the receiver of the hardware events
+//! translated from MT indications:
+//!
+//! ```no_run
+//! use std::num::NonZeroUsize;
+//!
+//! use apis_saltans_znp::{Config, Znp};
+//! use zb_hw::Driver;
+//! # async fn connect(
+//! #     stream: impl tokio::io::AsyncRead + tokio::io::AsyncWrite + Send + 'static,
+//! # ) -> Result<(), apis_saltans_znp::Error> {
+//! let capacity = NonZeroUsize::new(32).expect("capacity is non-zero");
+//! let (driver, events) = Znp::connect(stream, Config::new(), capacity).await?;
+//! let (ncp, actor) = driver.into_actor(capacity);
+//! tokio::spawn(actor);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Transmissions carry the coordinator's APS counter as the AF transaction ID. Acknowledged
+//! transmissions are confirmed by an `ApsdeEvent::DataConfirm` with that counter once the NCP
+//! reports `AF_DATA_CONFIRM`.
+
+pub use zb_hw::Formation;
+
+pub use self::command::CommandId;
+pub use self::config::Config;
+pub use self::driver::Znp;
+pub use self::error::Error;
+
+mod callbacks;
+mod client;
+mod command;
+mod config;
+mod driver;
+mod error;
+mod frame;
+mod parameters;
+#[cfg(test)]
+mod stub;
+mod transport;
@@ -0,0 +1,239 @@
+//! Parameters of MT requests, responses, and indications.
+
+use bytes::Bytes;
+use le_stream::{FromLeStream, Prefixed, ToLeStream};
+use zb_hw::core::IeeeAddress;
+use zb_hw::core::security::Key;
+
+/// Z-Stack status values.
+pub mod status {
+    /// The operation succeeded.
+    pub const SUCCESS: u8 = 0x00;
+
+    /// The destination did not acknowledge the message.
+    pub const APS_NO_ACK: u8 = 0xB7;
+
+    /// The entry already exists, for example an endpoint registered before a host restart.
+    pub const APS_DUPLICATE_ENTRY: u8 = 0xB8;
+}
+
+/// AF transmission options.
+pub mod af_option {
+    /// Request an APS acknowledgement.
+    pub const ACK_REQUEST: u8 = 0x10;
+
+    /// Discover a route if none is known.
+    pub const DISCOVER_ROUTE: u8 = 0x20;
+
+    /// Secure the message with APS security.
+    pub const SECURITY: u8 = 0x40;
+}
+
+/// Address modes of `AF_DATA_REQUEST_EXT`.
+pub mod address_mode {
+    /// An APS group.
+    pub const GROUP: u8 = 0x01;
+
+    /// A 64-bit IEEE address.
+    pub const IEEE: u8 = 0x03;
+
+    /// A 16-bit broadcast address.
+    pub const BROADCAST: u8 = 0x0F;
+}
+
+/// Formats of `APP_CNF_BDB_ADD_INSTALLCODE`.
+pub mod install_code_format {
+    /// A link key derived from an install code.
+    pub const DERIVED_KEY: u8 = 0x02;
+}
+
+/// Identifiers of non-volatile memory items.
+pub mod nv {
+    /// The device's logical type.
+    pub const LOGICAL_TYPE: u16 = 0x0087;
+
+    /// The PAN ID.
+    pub const PAN_ID: u16 = 0x0083;
+
+    /// The extended PAN ID.
+    pub const EXTENDED_PAN_ID: u16 = 0x002D;
+
+    /// The network key.
+    pub const PRECONFIGURED_KEY: u16 = 0x0062;
+
+    /// Whether the network key is distributed unencrypted.
+    pub const PRECONFIGURED_KEYS_ENABLE: u16 = 0x0063;
+
+    /// Whether the device is on a network.
+    pub const BDB_NODE_IS_ON_A_NETWORK: u16 = 0x0055;
+}
+
+/// Parameters of `AF_REGISTER`.
+#[derive(Clone, Debug, Eq, PartialEq, ToLeStream)]
+pub struct AfRegister {
+    pub endpoint: u8,
+    pub profile_id: u16,
+    pub device_id: u16,
+    pub device_version: u8,
+    pub latency: u8,
+    pub input_cluster_count: u8,
+    pub input_clusters: Vec<u16>,
+    pub output_cluster_count: u8,
+    pub output_clusters: Vec<u16>,
+}
+
+/// Parameters of `AF_DATA_REQUEST`.
+#[derive(Clone, Debug, Eq, PartialEq, ToLeStream)]
+pub struct AfDataRequest {
+    pub destination: u16,
+    pub destination_endpoint: u8,
+    pub source_endpoint: u8,
+    pub cluster_id: u16,
+    pub transaction_id: u8,
+    pub options: u8,
+    pub radius: u8,
+    pub data: Prefixed<u8, Bytes>,
+}
+
+/// Parameters of `AF_DATA_REQUEST_EXT`.
+#[derive(Clone, Debug, Eq, PartialEq, ToLeStream)]
+pub struct AfDataRequestExt {
+    pub address_mode: u8,
+    pub destination: [u8; 8],
+    pub destination_endpoint: u8,
+    pub destination_pan_id: u16,
+    pub source_endpoint: u8,
+    pub cluster_id: u16,
+    pub transaction_id: u8,
+    pub options: u8,
+    pub radius: u8,
+    pub data: Prefixed<u16, Bytes>,
+}
+
+/// Parameters of `AF_DATA_CONFIRM`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct AfDataConfirm {
+    pub status: u8,
+    pub endpoint: u8,
+    pub transaction_id: u8,
+}
+
+/// Parameters of `AF_INCOMING_MSG`.
+#[derive(Clone, Debug, Eq, PartialEq, FromLeStream)]
+pub struct AfIncomingMsg {
+    pub group_id: u16,
+    pub cluster_id: u16,
+    pub source: u16,
+    pub source_endpoint: u8,
+    pub destination_endpoint: u8,
+    pub was_broadcast: u8,
+    pub link_quality: u8,
+    pub security_use: u8,
+    pub timestamp: u32,
+    pub sequence: u8,
+    pub data: Prefixed<u8, Bytes>,
+}
+
+/// Parameters of `ZDO_MSG_CB_INCOMING`.
+#[derive(Clone, Debug, Eq, PartialEq, FromLeStream)]
+pub struct ZdoMsgCbIncoming {
+    pub source: u16,
+    pub was_broadcast: u8,
+    pub cluster_id: u16,
+    pub security_use: u8,
+    pub sequence: u8,
+    pub mac_destination: u16,
+    pub data: Bytes,
+}
+
+/// Parameters of `ZDO_TC_DEV_IND`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct ZdoTcDevInd {
+    pub network_address: u16,
+    pub ieee_address: IeeeAddress,
+    pub parent: u16,
+}
+
+/// Parameters of `ZDO_LEAVE_IND`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct ZdoLeaveInd {
+    pub network_address: u16,
+    pub ieee_address: IeeeAddress,
+    pub request: u8,
+    pub remove: u8,
+    pub rejoin: u8,
+}
+
+/// Parameters of `ZDO_MGMT_PERMIT_JOIN_REQ`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ToLeStream)]
+pub struct MgmtPermitJoinReq {
+    pub address_mode: u8,
+    pub destination: u16,
+    pub duration: u8,
+    pub tc_significance: u8,
+}
+
+/// Parameters of `ZDO_EXT_ROUTE_DISC`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ToLeStream)]
+pub struct ExtRouteDisc {
+    pub destination: u16,
+    pub options: u8,
+    pub radius: u8,
+}
+
+/// Response to `ZDO_EXT_NWK_INFO`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct ExtNwkInfo {
+    pub short_address: u16,
+    pub device_state: u8,
+    pub pan_id: u16,
+    pub parent_address: u16,
+    pub extended_pan_id: IeeeAddress,
+    pub parent_ieee_address: IeeeAddress,
+    pub channel: u8,
+}
+
+/// Leading fields of the response to `UTIL_GET_DEVICE_INFO`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, FromLeStream)]
+pub struct DeviceInfo {
+    pub status: u8,
+    pub ieee_address: IeeeAddress,
+}
+
+/// Parameters of `SYS_OSAL_NV_READ`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ToLeStream)]
+pub struct NvRead {
+    pub id: u16,
+    pub offset: u8,
+}
+
+/// Response to `SYS_OSAL_NV_READ`.
+#[derive(Clone, Debug, Eq, PartialEq, FromLeStream)]
+pub struct NvValue {
+    pub status: u8,
+    pub value: Prefixed<u8, Bytes>,
+}
+
+/// Parameters of `SYS_OSAL_NV_WRITE`.
+#[derive(Clone, Debug, Eq, PartialEq, ToLeStream)]
+pub struct NvWrite {
+    pub id: u16,
+    pub offset: u8,
+    pub length: u8,
+    pub value: Vec<u8>,
+}
+
+/// Parameters of `APP_CNF_BDB_SET_CHANNEL`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ToLeStream)]
+pub struct BdbSetChannel {
+    pub is_primary: u8,
+    pub channels: u32,
+}
+
+/// Parameters of `APP_CNF_BDB_ADD_INSTALLCODE` with a key derived from an install code.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ToLeStream)]
+pub struct BdbAddInstallCode {
+    pub format: u8,
+    pub ieee_address: IeeeAddress,
+    pub key: Key,
+}
@@ -0,0 +1,71 @@
+//! A scripted NCP speaking MT over an in-memory stream.
+
+use bytes::Bytes;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream, duplex};
+
+use crate::command::CommandId;
+use crate::frame::{Decoder, Frame, Type};
+
+/// Size of the in-memory stream's buffers.
+const BUFFER_SIZE: usize = 1024;
+
+/// The NCP side of an in-memory serial stream.
+pub struct Stub {
+    stream: DuplexStream,
+    decoder: Decoder,
+}
+
+impl Stub {
+    /// Create a stub and the host side of its stream.
+    pub fn new() -> (Self, DuplexStream) {
+        let (host, ncp) = duplex(BUFFER_SIZE);
+
+        (
+            Self {
+                stream: ncp,
+                decoder: Decoder::default(),
+            },
+            host,
+        )
+    }
+
+    /// Return the next frame written by the host.
+    pub async fn receive(&mut self) -> Frame {
+        loop {
+            let byte = self
+                .stream
+                .read_u8()
+                .await
+                .expect("host must write a frame");
+
+            if let Some(frame) = self.decoder.push(byte) {
+                return frame.expect("host must write valid frames");
+            }
+        }
+    }
+
+    /// Answer the next synchronous request and return the request's data.
+    pub async fn answer(&mut self, command: CommandId, data: &[u8]) -> Vec<u8> {
+        let request = self.receive().await;
+        assert_eq!(request.kind(), Type::Sreq, "host must send a request");
+        assert_eq!(request.command(), command, "host must send {command}");
+        self.send(Type::Srsp, command, data).await;
+        request.data().to_vec()
+    }
+
+    /// Send an asynchronous indication.
+    pub async fn indicate(&mut self, command: CommandId, data: &[u8]) {
+        self.send(Type::Areq, command, data).await;
+    }
+
+    /// Write a frame to the host.
+    pub async fn send(&mut self, kind: Type, command: CommandId, data: &[u8]) {
+        let frame = Frame::new(kind, command, Bytes::copy_from_slice(data))
+            .encode()
+            .expect("stub frames must fit");
+        self.stream
+            .write_all(&frame)
+            .await
+            .expect("host must read from the stream");
+    }
+}
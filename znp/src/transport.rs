@@ -0,0 +1,443 @@
+//! The MT transport actor.
+//!
+//! The actor owns the write half of the serial stream and keeps at most one synchronous request
+//! outstanding. A reader task decodes frames from the read half, and timers are tasks that report
+//! expired deadlines back to the actor. Synchronous responses complete the outstanding request,
+//! while asynchronous frames are forwarded to the callback channel.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bytes::Bytes;
+use log::{error, warn};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, WriteHalf, split};
+use tokio::spawn;
+use tokio::sync::mpsc::{Receiver, Sender, WeakSender, channel};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+
+use crate::command::CommandId;
+use crate::error::Error;
+use crate::frame::{DecodeError, Decoder, Frame, Type};
+
+/// Time to wait for the synchronous response to a request.
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(6);
+
+/// Capacity of the actor's input channel.
+const INPUT_CAPACITY: usize = 32;
+
+/// Size of the read buffer of the reader task.
+const READ_BUFFER_SIZE: usize = 64;
+
+/// Handle of the MT transport actor.
+#[derive(Clone, Debug)]
+pub struct Transport {
+    inputs: Sender<Input>,
+}
+
+impl Transport {
+    /// Start the transport actor on the stream.
+    ///
+    /// Asynchronous frames received from the NCP are forwarded to `callbacks`. The callback
+    /// channel closes when the transport fails.
+    pub fn start<S, T>(stream: S, callbacks: Sender<T>) -> Self
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+        T: From<Frame> + Send + 'static,
+    {
+        let (reader, writer) = split(stream);
+        let (inputs, receiver) = channel(INPUT_CAPACITY);
+        let reader = spawn(read(reader, inputs.downgrade()));
+        let actor = Actor {
+            writer,
+            inputs: inputs.downgrade(),
+            callbacks: Some(callbacks),
+            reader,
+            next_id: 0,
+            queue: VecDeque::new(),
+            pending: None,
+            failure: None,
+        };
+        spawn(actor.run(receiver));
+        Self { inputs }
+    }
+
+    /// Send a synchronous request and return the data of its response.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the transport failed or the NCP did not respond in time.
+    pub async fn request(&self, command: CommandId, data: Bytes) -> Result<Bytes, Error> {
+        let (response, receiver) = oneshot::channel();
+        self.inputs
+            .send(Input::Request(Request {
+                command,
+                data,
+                response,
+            }))
+            .await
+            .map_err(|_| Error::Closed)?;
+        receiver.await.map_err(|_| Error::Closed)?
+    }
+}
+
+/// Inputs of the transport actor.
+enum Input {
+    Request(Request),
+    Received(Result<Frame, DecodeError>),
+    ReadFailed(Error),
+    ResponseTimeout { id: u64 },
+}
+
+/// A synchronous request waiting to be sent.
+struct Request {
+    command: CommandId,
+    data: Bytes,
+    response: oneshot::Sender<Result<Bytes, Error>>,
+}
+
+/// The request whose response is awaited.
+struct Pending {
+    id: u64,
+    request: Request,
+}
+
+struct Actor<S, T> {
+    writer: WriteHalf<S>,
+    inputs: WeakSender<Input>,
+    callbacks: Option<Sender<T>>,
+    reader: JoinHandle<()>,
+    next_id: u64,
+    queue: VecDeque<Request>,
+    pending: Option<Pending>,
+    failure: Option<Error>,
+}
+
+impl<S, T> Actor<S, T>
+where
+    S: AsyncWrite,
+    T: From<Frame>,
+{
+    async fn run(mut self, mut inputs: Receiver<Input>) {
+        while let Some(input) = inputs.recv().await {
+            match input {
+                Input::Request(request) => {
+                    if let Some(failure) = &self.failure {
+                        request
+                            .response
+                            .send(Err(failure.clone()))
+                            .unwrap_or_else(drop);
+                    } else {
+                        self.queue.push_back(request);
+                        self.send_next().await;
+                    }
+                }
+                Input::Received(Ok(frame)) => self.receive(frame).await,
+                Input::Received(Err(error)) => warn!("Discarding received frame: {error}"),
+                Input::ReadFailed(error) => self.fail(error),
+                Input::ResponseTimeout { id } => {
+                    if let Some(pending) = self.pending.take_if(|pending| pending.id == id) {
+                        let command = pending.request.command;
+                        warn!("NCP did not respond to {command}");
+                        pending
+                            .request
+                            .response
+                            .send(Err(Error::Timeout(command)))
+                            .unwrap_or_else(drop);
+                        self.send_next().await;
+                    }
+                }
+            }
+        }
+
+        self.reader.abort();
+    }
+
+    /// Send the next queued request unless a request is outstanding.
+    async fn send_next(&mut self) {
+        if self.pending.is_some() || self.failure.is_some() {
+            return;
+        }
+
+        let (request, frame) = loop {
+            let Some(request) = self.queue.pop_front() else {
+                return;
+            };
+
+            match Frame::new(Type::Sreq, request.command, request.data.clone()).encode() {
+                Ok(frame) => break (request, frame),
+                Err(error) => {
+                    warn!("Rejecting {}: {error}", request.command);
+                    request
+                        .response
+                        .send(Err(Error::TooLong(request.command)))
+                        .unwrap_or_else(drop);
+                }
+            }
+        };
+
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.pending = Some(Pending { id, request });
+        let result = match self.writer.write_all(&frame).await {
+            Ok(()) => self.writer.flush().await,
+            Err(error) => Err(error),
+        };
+
+        if let Err(error) = result {
+            self.fail(error.into());
+        } else {
+            self.schedule(RESPONSE_TIMEOUT, Input::ResponseTimeout { id });
+        }
+    }
+
+    async fn receive(&mut self, frame: Frame) {
+        match frame.kind() {
+            Type::Srsp => self.respond(&frame).await,
+            Type::Areq if frame.command() == CommandId::SYS_RESET_IND => {
+                self.fail(Error::Reset(
+                    frame.data().first().copied().unwrap_or_default(),
+                ));
+            }
+            Type::Areq => {
+                if let Some(callbacks) = &self.callbacks
+                    && callbacks.send(frame.into()).await.is_err()
+                {
+                    self.callbacks = None;
+                }
+            }
+            Type::Sreq | Type::Poll => warn!("Ignoring request {} from NCP", frame.command()),
+        }
+    }
+
+    /// Complete the outstanding request with a synchronous response.
+    async fn respond(&mut self, frame: &Frame) {
+        let command = frame.command();
+        let Some(pending) = self.pending.take_if(|pending| {
+            command == CommandId::RPC_ERROR || pending.request.command == command
+        }) else {
+            warn!("Discarding unexpected response {command}");
+            return;
+        };
+
+        let result = if command == CommandId::RPC_ERROR {
+            Err(Error::Rpc {
+                command: pending.request.command,
+                code: frame.data().first().copied().unwrap_or_default(),
+            })
+        } else {
+            Ok(frame.data().clone())
+        };
+
+        pending.request.response.send(result).unwrap_or_else(drop);
+        self.send_next().await;
+    }
+
+    /// Fail all requests and reject further ones.
+    fn fail(&mut self, error: Error) {
+        if self.failure.is_some() {
+            return;
+        }
+
+        error!("MT transport failed: {error}");
+        self.reader.abort();
+        self.callbacks = None;
+
+        for request in self
+            .pending
+            .take()
+            .map(|pending| pending.request)
+            .into_iter()
+            .chain(self.queue.drain(..))
+        {
+            request
+                .response
+                .send(Err(error.clone()))
+                .unwrap_or_else(drop);
+        }
+
+        self.failure = Some(error);
+    }
+
+    /// Send `input` to the actor after `delay`.
+    fn schedule(&self, delay: Duration, input: Input) {
+        let inputs = self.inputs.clone();
+
+        spawn(async move {
+            sleep(delay).await;
+
+            if let Some(inputs) = inputs.upgrade() {
+                inputs.send(input).await.unwrap_or_else(drop);
+            }
+        });
+    }
+}
+
+/// Decode frames from the read half and forward them to the actor.
+async fn read<R>(mut reader: R, inputs: WeakSender<Input>)
+where
+    R: AsyncRead + Unpin,
+{
+    let mut decoder = Decoder::default();
+    let mut buffer = [0; READ_BUFFER_SIZE];
+
+    loop {
+        let (frames, failure) = match reader.read(&mut buffer).await {
+            Ok(0) => (Vec::new(), Some(Error::Closed)),
+            Ok(size) => (
+                buffer[..size]
+                    .iter()
+                    .filter_map(|byte| decoder.push(*byte))
+                    .collect(),
+                None,
+            ),
+            Err(error) => (Vec::new(), Some(error.into())),
+        };
+
+        let Some(inputs) = inputs.upgrade() else {
+            return;
+        };
+
+        for frame in frames {
+            if inputs.send(Input::Received(frame)).await.is_err() {
+                return;
+            }
+        }
+
+        if let Some(failure) = failure {
+            inputs
+                .send(Input::ReadFailed(failure))
+                .await
+                .unwrap_or_else(drop);
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bytes::Bytes;
+    use tokio::runtime::Builder;
+    use tokio::spawn;
+    use tokio::sync::mpsc::channel;
+    use tokio::time::sleep;
+
+    use super::{RESPONSE_TIMEOUT, Transport};
+    use crate::command::CommandId;
+    use crate::error::Error;
+    use crate::frame::{Frame, Type};
+    use crate::stub::Stub;
+
+    const CALLBACK_CAPACITY: usize = 4;
+
+    fn run<F>(test: F)
+    where
+        F: Future<Output = ()>,
+    {
+        Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .expect("runtime must be available")
+            .block_on(test);
+    }
+
+    #[test]
+    fn completes_requests_and_forwards_indications() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (callbacks, mut indications) = channel::<Frame>(CALLBACK_CAPACITY);
+            let transport = Transport::start(host, callbacks);
+            let request =
+                spawn(async move { transport.request(CommandId::SYS_PING, Bytes::new()).await });
+
+            ncp.indicate(CommandId::ZDO_STATE_CHANGE_IND, &[0x09]).await;
+            assert!(
+                ncp.answer(CommandId::SYS_PING, &[0x59, 0x06])
+                    .await
+                    .is_empty()
+            );
+            assert_eq!(
+                request
+                    .await
+                    .expect("task must finish")
+                    .expect("NCP must respond")
+                    .as_ref(),
+                [0x59, 0x06]
+            );
+            assert_eq!(
+                indications.recv().await,
+                Some(Frame::new(
+                    Type::Areq,
+                    CommandId::ZDO_STATE_CHANGE_IND,
+                    Bytes::from_static(&[0x09])
+                ))
+            );
+        });
+    }
+
+    #[test]
+    fn reports_rpc_errors_and_timeouts() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (callbacks, _indications) = channel::<Frame>(CALLBACK_CAPACITY);
+            let transport = Transport::start(host, callbacks);
+            let requests = spawn(async move {
+                let rejected = transport
+                    .request(CommandId::ZDO_EXT_NWK_INFO, Bytes::new())
+                    .await;
+                let unanswered = transport.request(CommandId::SYS_PING, Bytes::new()).await;
+                (rejected, unanswered)
+            });
+
+            ncp.receive().await;
+            ncp.send(Type::Srsp, CommandId::RPC_ERROR, &[0x02, 0x25, 0x50])
+                .await;
+            ncp.receive().await;
+            sleep(RESPONSE_TIMEOUT + Duration::from_millis(1)).await;
+
+            let (rejected, unanswered) = requests.await.expect("task must finish");
+            assert!(matches!(
+                rejected,
+                Err(Error::Rpc {
+                    command: CommandId::ZDO_EXT_NWK_INFO,
+                    code: 0x02
+                })
+            ));
+            assert!(matches!(
+                unanswered,
+                Err(Error::Timeout(CommandId::SYS_PING))
+            ));
+        });
+    }
+
+    #[test]
+    fn fails_requests_when_the_ncp_resets() {
+        run(async {
+            let (mut ncp, host) = Stub::new();
+            let (callbacks, mut indications) = channel::<Frame>(CALLBACK_CAPACITY);
+            let transport = Transport::start(host, callbacks);
+            let request = {
+                let transport = transport.clone();
+                spawn(async move { transport.request(CommandId::SYS_PING, Bytes::new()).await })
+            };
+
+            ncp.receive().await;
+            ncp.indicate(CommandId::SYS_RESET_IND, &[0x02, 0x00, 0x02, 0x07, 0x01])
+                .await;
+
+            assert!(matches!(
+                request.await.expect("task must finish"),
+                Err(Error::Reset(0x02))
+            ));
+            assert!(indications.recv().await.is_none());
+            assert!(matches!(
+                transport.request(CommandId::SYS_PING, Bytes::new()).await,
+                Err(Error::Reset(0x02))
+            ));
+        });
+    }
+}